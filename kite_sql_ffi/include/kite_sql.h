@@ -0,0 +1,172 @@
+#ifndef KITE_SQL_H
+#define KITE_SQL_H
+
+/* Generated by cbindgen from kite_sql_ffi/src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The result of the functions of the ABI.
+typedef enum KiteSqlStatus {
+  KITE_SQL_STATUS_OK = 0,
+  // [`kite_sql_fetch`] read a row
+  KITE_SQL_STATUS_ROW = 1,
+  // [`kite_sql_fetch`] read all the rows and the statement is committed
+  KITE_SQL_STATUS_DONE = 2,
+  KITE_SQL_STATUS_ERROR = -1,
+} KiteSqlStatus;
+
+// The types of [`KiteSqlValue`]: the integers are widened to 64 bits, the floats to doubles,
+// and the other types of KiteSQL, e.g. `DATE` or `DECIMAL`, are given as their text.
+typedef enum KiteSqlValueTag {
+  KITE_SQL_VALUE_TAG_NULL,
+  KITE_SQL_VALUE_TAG_BOOLEAN,
+  KITE_SQL_VALUE_TAG_INT64,
+  KITE_SQL_VALUE_TAG_UINT64,
+  KITE_SQL_VALUE_TAG_FLOAT64,
+  KITE_SQL_VALUE_TAG_TEXT,
+} KiteSqlValueTag;
+
+typedef struct KiteSqlDatabase KiteSqlDatabase;
+
+// The rows of a statement, which read the database of the statement until they are closed.
+typedef struct KiteSqlRows KiteSqlRows;
+
+// A statement of [`kite_sql_prepare`] with the parameters bound to it.
+typedef struct KiteSqlStatement KiteSqlStatement;
+
+// UTF-8 bytes followed by a NUL, which may also be among the `len` bytes.
+typedef struct KiteSqlText {
+  const char *data;
+  size_t len;
+} KiteSqlText;
+
+typedef union KiteSqlValueData {
+  bool boolean;
+  int64_t int64;
+  uint64_t uint64;
+  double float64;
+  struct KiteSqlText text;
+} KiteSqlValueData;
+
+// A value of a row or of a parameter, the field of `data` being given by `tag`.
+typedef struct KiteSqlValue {
+  enum KiteSqlValueTag tag;
+  union KiteSqlValueData data;
+} KiteSqlValue;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Opens the database stored in the directory `path`, creating it if it does not exist, or
+// gives NULL on failure.
+//
+// # Safety
+// `path` is a NUL terminated string.
+struct KiteSqlDatabase *kite_sql_open(const char *path);
+
+// Closes a database of [`kite_sql_open`].
+//
+// # Safety
+// `database` is NULL or given by [`kite_sql_open`], and none of its rows is still open.
+void kite_sql_close(struct KiteSqlDatabase *database);
+
+// Runs a statement, whose rows are put in `rows` and read by [`kite_sql_fetch`].
+//
+// # Safety
+// `database` is given by [`kite_sql_open`] and outlives `rows`, `sql` is a NUL terminated
+// string and `rows` is writable.
+enum KiteSqlStatus kite_sql_run(const struct KiteSqlDatabase *database,
+                                const char *sql,
+                                struct KiteSqlRows **rows);
+
+// Prepares a statement whose placeholders `?n` or `$n` are bound by [`kite_sql_bind`].
+//
+// # Safety
+// `database` is given by [`kite_sql_open`], `sql` is a NUL terminated string and `statement`
+// is writable.
+enum KiteSqlStatus kite_sql_prepare(const struct KiteSqlDatabase *database,
+                                    const char *sql,
+                                    struct KiteSqlStatement **statement);
+
+// Binds the value of the placeholders `?index` and `$index`, `index` starting at 1. The text
+// of `value` is copied.
+//
+// # Safety
+// `statement` is given by [`kite_sql_prepare`] and the text of `value` has `len` bytes.
+enum KiteSqlStatus kite_sql_bind(struct KiteSqlStatement *statement,
+                                 size_t index,
+                                 struct KiteSqlValue value);
+
+// Runs a prepared statement with the parameters bound to it, see [`kite_sql_run`].
+//
+// # Safety
+// `database` is given by [`kite_sql_open`] and outlives `rows`, `statement` by
+// [`kite_sql_prepare`] on it, and `rows` is writable.
+enum KiteSqlStatus kite_sql_execute(const struct KiteSqlDatabase *database,
+                                    const struct KiteSqlStatement *statement,
+                                    struct KiteSqlRows **rows);
+
+// Frees a statement of [`kite_sql_prepare`].
+//
+// # Safety
+// `statement` is NULL or given by [`kite_sql_prepare`].
+void kite_sql_statement_close(struct KiteSqlStatement *statement);
+
+// Reads the next row, giving [`KiteSqlStatus::Row`], or [`KiteSqlStatus::Done`] once the
+// statement is committed after its last row.
+//
+// # Safety
+// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+enum KiteSqlStatus kite_sql_fetch(struct KiteSqlRows *rows);
+
+// The count of the columns of the rows.
+//
+// # Safety
+// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+size_t kite_sql_column_count(const struct KiteSqlRows *rows);
+
+// The name of a column, starting at 0, or NULL out of the columns. It lives as long as the rows.
+//
+// # Safety
+// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+const char *kite_sql_column_name(const struct KiteSqlRows *rows, size_t index);
+
+// The type of a column in SQL, e.g. `INTEGER`, or NULL out of the columns. It lives as long as
+// the rows.
+//
+// # Safety
+// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+const char *kite_sql_column_type(const struct KiteSqlRows *rows, size_t index);
+
+// Puts the value of a column of the row read by [`kite_sql_fetch`] in `value`. Its text lives
+// until the next row is read.
+//
+// # Safety
+// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`] and `value` is writable.
+enum KiteSqlStatus kite_sql_column_value(const struct KiteSqlRows *rows,
+                                         size_t index,
+                                         struct KiteSqlValue *value);
+
+// Frees the rows, rolling back their statement if they were not all read.
+//
+// # Safety
+// `rows` is NULL or given by [`kite_sql_run`] or [`kite_sql_execute`].
+void kite_sql_rows_close(struct KiteSqlRows *rows);
+
+// The message of the last failure of the thread, which lives until the next one.
+const char *kite_sql_last_error(void);
+
+// The SQLSTATE of the last failure of the thread, e.g. `23505` for a duplicate key, which lives
+// until the next one.
+const char *kite_sql_last_sqlstate(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* KITE_SQL_H */
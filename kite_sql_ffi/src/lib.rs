@@ -0,0 +1,656 @@
+//! The C ABI of KiteSQL, on which the bindings of other languages are built. Its header
+//! `include/kite_sql.h` is written by cbindgen when the crate is built.
+//!
+//! A database is opened by [`kite_sql_open`], its statements are run by [`kite_sql_run`], or
+//! prepared by [`kite_sql_prepare`], bound by [`kite_sql_bind`] and run by [`kite_sql_execute`].
+//! The rows are read by [`kite_sql_fetch`] and [`kite_sql_column_value`] as [`KiteSqlValue`]s.
+//!
+//! The functions returning a [`KiteSqlStatus`] give [`KiteSqlStatus::Error`] on failure, whose
+//! message is [`kite_sql_last_error`].
+use kite_sql::db::{DataBaseBuilder, Database, DatabaseIter, ResultIter, Statement};
+use kite_sql::errors::DatabaseError;
+use kite_sql::storage::rocksdb::RocksStorage;
+use kite_sql::types::tuple::{SchemaRef, Tuple};
+use kite_sql::types::value::DataValue;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::sync::Mutex;
+use std::{mem, ptr, slice};
+
+/// The result of the functions of the ABI.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KiteSqlStatus {
+    Ok = 0,
+    /// [`kite_sql_fetch`] read a row
+    Row = 1,
+    /// [`kite_sql_fetch`] read all the rows and the statement is committed
+    Done = 2,
+    Error = -1,
+}
+
+/// The types of [`KiteSqlValue`]: the integers are widened to 64 bits, the floats to doubles,
+/// and the other types of KiteSQL, e.g. `DATE` or `DECIMAL`, are given as their text.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KiteSqlValueTag {
+    Null,
+    Boolean,
+    Int64,
+    Uint64,
+    Float64,
+    Text,
+}
+
+/// UTF-8 bytes followed by a NUL, which may also be among the `len` bytes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct KiteSqlText {
+    pub data: *const c_char,
+    pub len: usize,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union KiteSqlValueData {
+    pub boolean: bool,
+    pub int64: i64,
+    pub uint64: u64,
+    pub float64: f64,
+    pub text: KiteSqlText,
+}
+
+/// A value of a row or of a parameter, the field of `data` being given by `tag`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct KiteSqlValue {
+    pub tag: KiteSqlValueTag,
+    pub data: KiteSqlValueData,
+}
+
+pub struct KiteSqlDatabase {
+    inner: Database<RocksStorage>,
+}
+
+/// A statement of [`kite_sql_prepare`] with the parameters bound to it.
+pub struct KiteSqlStatement {
+    statement: Statement,
+    params: Vec<(&'static str, DataValue)>,
+}
+
+/// The rows of a statement, which read the database of the statement until they are closed.
+pub struct KiteSqlRows {
+    /// `None` once all the rows are read or the statement failed
+    iter: Option<DatabaseIter<'static, RocksStorage>>,
+    names: Vec<CString>,
+    types: Vec<CString>,
+    row: Option<Tuple>,
+    /// The text of the values of `row` given as [`KiteSqlValueTag::Text`], NUL included
+    texts: Vec<Option<Vec<u8>>>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+    static LAST_SQLSTATE: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn fail(err: DatabaseError) -> KiteSqlStatus {
+    let message = CString::new(err.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message);
+    let sqlstate = CString::new(err.sqlstate()).unwrap_or_default();
+    LAST_SQLSTATE.with(|last_sqlstate| *last_sqlstate.borrow_mut() = sqlstate);
+
+    KiteSqlStatus::Error
+}
+
+fn null_pointer(name: &str) -> DatabaseError {
+    DatabaseError::InvalidValue(format!("null pointer of {name}"))
+}
+
+unsafe fn c_str<'a>(s: *const c_char, name: &str) -> Result<&'a str, DatabaseError> {
+    if s.is_null() {
+        return Err(null_pointer(name));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| DatabaseError::InvalidValue(format!("{name} of invalid UTF-8")))
+}
+
+/// The names `?n` and `$n` of the placeholders of the n-th parameter, kept for the life of the
+/// process as the parameters of [`Database::execute`] are named by `&'static str`.
+fn placeholder_names(index: usize) -> [&'static str; 2] {
+    static NAMES: Mutex<Vec<[&'static str; 2]>> = Mutex::new(Vec::new());
+    let mut names = NAMES.lock().unwrap();
+
+    while names.len() < index {
+        let n = names.len() + 1;
+        names.push([
+            Box::leak(format!("?{n}").into_boxed_str()),
+            Box::leak(format!("${n}").into_boxed_str()),
+        ]);
+    }
+    names[index - 1]
+}
+
+fn text_of(value: &DataValue) -> Option<Vec<u8>> {
+    let text = match value {
+        DataValue::Null
+        | DataValue::Boolean(_)
+        | DataValue::Int8(_)
+        | DataValue::Int16(_)
+        | DataValue::Int32(_)
+        | DataValue::Int64(_)
+        | DataValue::UInt8(_)
+        | DataValue::UInt16(_)
+        | DataValue::UInt32(_)
+        | DataValue::UInt64(_)
+        | DataValue::Float32(_)
+        | DataValue::Float64(_) => return None,
+        DataValue::Utf8 { value, .. } => value.clone(),
+        value => value.to_string(),
+    };
+    let mut bytes = text.into_bytes();
+    bytes.push(0);
+
+    Some(bytes)
+}
+
+fn into_c_value(value: &DataValue, text: Option<&Vec<u8>>) -> KiteSqlValue {
+    let (tag, data) = match value {
+        DataValue::Null => (KiteSqlValueTag::Null, KiteSqlValueData { int64: 0 }),
+        DataValue::Boolean(v) => (KiteSqlValueTag::Boolean, KiteSqlValueData { boolean: *v }),
+        DataValue::Int8(v) => (
+            KiteSqlValueTag::Int64,
+            KiteSqlValueData { int64: *v as i64 },
+        ),
+        DataValue::Int16(v) => (
+            KiteSqlValueTag::Int64,
+            KiteSqlValueData { int64: *v as i64 },
+        ),
+        DataValue::Int32(v) => (
+            KiteSqlValueTag::Int64,
+            KiteSqlValueData { int64: *v as i64 },
+        ),
+        DataValue::Int64(v) => (KiteSqlValueTag::Int64, KiteSqlValueData { int64: *v }),
+        DataValue::UInt8(v) => (
+            KiteSqlValueTag::Uint64,
+            KiteSqlValueData { uint64: *v as u64 },
+        ),
+        DataValue::UInt16(v) => (
+            KiteSqlValueTag::Uint64,
+            KiteSqlValueData { uint64: *v as u64 },
+        ),
+        DataValue::UInt32(v) => (
+            KiteSqlValueTag::Uint64,
+            KiteSqlValueData { uint64: *v as u64 },
+        ),
+        DataValue::UInt64(v) => (KiteSqlValueTag::Uint64, KiteSqlValueData { uint64: *v }),
+        DataValue::Float32(v) => (
+            KiteSqlValueTag::Float64,
+            KiteSqlValueData {
+                float64: v.0 as f64,
+            },
+        ),
+        DataValue::Float64(v) => (KiteSqlValueTag::Float64, KiteSqlValueData { float64: v.0 }),
+        _ => {
+            let text = text.map_or(&[0_u8] as &[u8], |text| text.as_slice());
+
+            (
+                KiteSqlValueTag::Text,
+                KiteSqlValueData {
+                    text: KiteSqlText {
+                        data: text.as_ptr() as *const c_char,
+                        len: text.len() - 1,
+                    },
+                },
+            )
+        }
+    };
+    KiteSqlValue { tag, data }
+}
+
+unsafe fn from_c_value(value: &KiteSqlValue) -> Result<DataValue, DatabaseError> {
+    Ok(match value.tag {
+        KiteSqlValueTag::Null => DataValue::Null,
+        KiteSqlValueTag::Boolean => DataValue::Boolean(value.data.boolean),
+        KiteSqlValueTag::Int64 => DataValue::Int64(value.data.int64),
+        KiteSqlValueTag::Uint64 => DataValue::UInt64(value.data.uint64),
+        KiteSqlValueTag::Float64 => DataValue::from(value.data.float64),
+        KiteSqlValueTag::Text => {
+            let KiteSqlText { data, len } = value.data.text;
+            if data.is_null() {
+                return Err(null_pointer("text"));
+            }
+            let bytes = slice::from_raw_parts(data as *const u8, len);
+
+            DataValue::from(
+                String::from_utf8(bytes.to_vec()).map_err(|_| {
+                    DatabaseError::InvalidValue("text of invalid UTF-8".to_string())
+                })?,
+            )
+        }
+    })
+}
+
+fn into_rows(
+    iter: Result<DatabaseIter<'_, RocksStorage>, DatabaseError>,
+    rows: *mut *mut KiteSqlRows,
+) -> KiteSqlStatus {
+    let iter = match iter {
+        Ok(iter) => iter,
+        Err(err) => return fail(err),
+    };
+    let schema: SchemaRef = iter.schema().clone();
+    let c_string = |s: String| CString::new(s.replace('\0', " ")).unwrap_or_default();
+    let names = schema
+        .iter()
+        .map(|column| c_string(column.name().to_string()))
+        .collect();
+    let types = schema
+        .iter()
+        .map(|column| c_string(column.datatype().to_string()))
+        .collect();
+    // the rows are closed before their database as told by `kite_sql_run`
+    let iter = unsafe {
+        mem::transmute::<DatabaseIter<'_, RocksStorage>, DatabaseIter<'static, RocksStorage>>(iter)
+    };
+    let boxed = Box::new(KiteSqlRows {
+        iter: Some(iter),
+        names,
+        types,
+        row: None,
+        texts: Vec::new(),
+    });
+    unsafe { *rows = Box::into_raw(boxed) };
+
+    KiteSqlStatus::Ok
+}
+
+/// Opens the database stored in the directory `path`, creating it if it does not exist, or
+/// gives NULL on failure.
+///
+/// # Safety
+/// `path` is a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_open(path: *const c_char) -> *mut KiteSqlDatabase {
+    let database = c_str(path, "path").and_then(|path| DataBaseBuilder::path(path).build());
+
+    match database {
+        Ok(inner) => Box::into_raw(Box::new(KiteSqlDatabase { inner })),
+        Err(err) => {
+            fail(err);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Closes a database of [`kite_sql_open`].
+///
+/// # Safety
+/// `database` is NULL or given by [`kite_sql_open`], and none of its rows is still open.
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_close(database: *mut KiteSqlDatabase) {
+    if !database.is_null() {
+        drop(Box::from_raw(database));
+    }
+}
+
+/// Runs a statement, whose rows are put in `rows` and read by [`kite_sql_fetch`].
+///
+/// # Safety
+/// `database` is given by [`kite_sql_open`] and outlives `rows`, `sql` is a NUL terminated
+/// string and `rows` is writable.
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_run(
+    database: *const KiteSqlDatabase,
+    sql: *const c_char,
+    rows: *mut *mut KiteSqlRows,
+) -> KiteSqlStatus {
+    let (Some(database), false) = (database.as_ref(), rows.is_null()) else {
+        return fail(null_pointer("database or rows"));
+    };
+    match c_str(sql, "sql") {
+        Ok(sql) => into_rows(database.inner.run(sql), rows),
+        Err(err) => fail(err),
+    }
+}
+
+/// Prepares a statement whose placeholders `?n` or `$n` are bound by [`kite_sql_bind`].
+///
+/// # Safety
+/// `database` is given by [`kite_sql_open`], `sql` is a NUL terminated string and `statement`
+/// is writable.
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_prepare(
+    database: *const KiteSqlDatabase,
+    sql: *const c_char,
+    statement: *mut *mut KiteSqlStatement,
+) -> KiteSqlStatus {
+    let (Some(database), false) = (database.as_ref(), statement.is_null()) else {
+        return fail(null_pointer("database or statement"));
+    };
+    match c_str(sql, "sql").and_then(|sql| database.inner.prepare(sql)) {
+        Ok(prepared) => {
+            *statement = Box::into_raw(Box::new(KiteSqlStatement {
+                statement: prepared,
+                params: Vec::new(),
+            }));
+            KiteSqlStatus::Ok
+        }
+        Err(err) => fail(err),
+    }
+}
+
+/// Binds the value of the placeholders `?index` and `$index`, `index` starting at 1. The text
+/// of `value` is copied.
+///
+/// # Safety
+/// `statement` is given by [`kite_sql_prepare`] and the text of `value` has `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_bind(
+    statement: *mut KiteSqlStatement,
+    index: usize,
+    value: KiteSqlValue,
+) -> KiteSqlStatus {
+    let Some(statement) = statement.as_mut() else {
+        return fail(null_pointer("statement"));
+    };
+    if index == 0 {
+        return fail(DatabaseError::InvalidValue(
+            "parameter index 0, which starts at 1".to_string(),
+        ));
+    }
+    let value = match from_c_value(&value) {
+        Ok(value) => value,
+        Err(err) => return fail(err),
+    };
+    let names = placeholder_names(index);
+
+    statement.params.retain(|(name, _)| !names.contains(name));
+    statement
+        .params
+        .extend(names.into_iter().map(|name| (name, value.clone())));
+
+    KiteSqlStatus::Ok
+}
+
+/// Runs a prepared statement with the parameters bound to it, see [`kite_sql_run`].
+///
+/// # Safety
+/// `database` is given by [`kite_sql_open`] and outlives `rows`, `statement` by
+/// [`kite_sql_prepare`] on it, and `rows` is writable.
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_execute(
+    database: *const KiteSqlDatabase,
+    statement: *const KiteSqlStatement,
+    rows: *mut *mut KiteSqlRows,
+) -> KiteSqlStatus {
+    let (Some(database), Some(statement), false) =
+        (database.as_ref(), statement.as_ref(), rows.is_null())
+    else {
+        return fail(null_pointer("database, statement or rows"));
+    };
+    into_rows(
+        database
+            .inner
+            .execute(&statement.statement, statement.params.as_slice()),
+        rows,
+    )
+}
+
+/// Frees a statement of [`kite_sql_prepare`].
+///
+/// # Safety
+/// `statement` is NULL or given by [`kite_sql_prepare`].
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_statement_close(statement: *mut KiteSqlStatement) {
+    if !statement.is_null() {
+        drop(Box::from_raw(statement));
+    }
+}
+
+/// Reads the next row, giving [`KiteSqlStatus::Row`], or [`KiteSqlStatus::Done`] once the
+/// statement is committed after its last row.
+///
+/// # Safety
+/// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_fetch(rows: *mut KiteSqlRows) -> KiteSqlStatus {
+    let Some(rows) = rows.as_mut() else {
+        return fail(null_pointer("rows"));
+    };
+    rows.row = None;
+    rows.texts.clear();
+
+    let Some(iter) = rows.iter.as_mut() else {
+        return KiteSqlStatus::Done;
+    };
+    match iter.next() {
+        Some(Ok(tuple)) => {
+            rows.texts = tuple.values.iter().map(text_of).collect();
+            rows.row = Some(tuple);
+            KiteSqlStatus::Row
+        }
+        Some(Err(err)) => {
+            // dropping the statement rolls it back
+            rows.iter = None;
+            fail(err)
+        }
+        None => match rows.iter.take().map(ResultIter::done) {
+            Some(Err(err)) => fail(err),
+            _ => KiteSqlStatus::Done,
+        },
+    }
+}
+
+/// The count of the columns of the rows.
+///
+/// # Safety
+/// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_column_count(rows: *const KiteSqlRows) -> usize {
+    rows.as_ref().map_or(0, |rows| rows.names.len())
+}
+
+/// The name of a column, starting at 0, or NULL out of the columns. It lives as long as the rows.
+///
+/// # Safety
+/// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_column_name(
+    rows: *const KiteSqlRows,
+    index: usize,
+) -> *const c_char {
+    rows.as_ref()
+        .and_then(|rows| rows.names.get(index))
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// The type of a column in SQL, e.g. `INTEGER`, or NULL out of the columns. It lives as long as
+/// the rows.
+///
+/// # Safety
+/// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`].
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_column_type(
+    rows: *const KiteSqlRows,
+    index: usize,
+) -> *const c_char {
+    rows.as_ref()
+        .and_then(|rows| rows.types.get(index))
+        .map_or(ptr::null(), |ty| ty.as_ptr())
+}
+
+/// Puts the value of a column of the row read by [`kite_sql_fetch`] in `value`. Its text lives
+/// until the next row is read.
+///
+/// # Safety
+/// `rows` is given by [`kite_sql_run`] or [`kite_sql_execute`] and `value` is writable.
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_column_value(
+    rows: *const KiteSqlRows,
+    index: usize,
+    value: *mut KiteSqlValue,
+) -> KiteSqlStatus {
+    let (Some(rows), false) = (rows.as_ref(), value.is_null()) else {
+        return fail(null_pointer("rows or value"));
+    };
+    let Some(row) = rows.row.as_ref() else {
+        return fail(DatabaseError::InvalidValue(
+            "no row read by kite_sql_fetch".to_string(),
+        ));
+    };
+    let Some(data_value) = row.values.get(index) else {
+        return fail(DatabaseError::InvalidValue(format!("column index {index}")));
+    };
+    *value = into_c_value(data_value, rows.texts[index].as_ref());
+
+    KiteSqlStatus::Ok
+}
+
+/// Frees the rows, rolling back their statement if they were not all read.
+///
+/// # Safety
+/// `rows` is NULL or given by [`kite_sql_run`] or [`kite_sql_execute`].
+#[no_mangle]
+pub unsafe extern "C" fn kite_sql_rows_close(rows: *mut KiteSqlRows) {
+    if !rows.is_null() {
+        drop(Box::from_raw(rows));
+    }
+}
+
+/// The message of the last failure of the thread, which lives until the next one.
+#[no_mangle]
+pub extern "C" fn kite_sql_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| last_error.borrow().as_ptr())
+}
+
+/// The SQLSTATE of the last failure of the thread, e.g. `23505` for a duplicate key, which lives
+/// until the next one.
+#[no_mangle]
+pub extern "C" fn kite_sql_last_sqlstate() -> *const c_char {
+    LAST_SQLSTATE.with(|last_sqlstate| last_sqlstate.borrow().as_ptr())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    unsafe fn run(database: *const KiteSqlDatabase, sql: &str) -> Vec<Vec<KiteSqlValue>> {
+        let sql = CString::new(sql).unwrap();
+        let mut rows = ptr::null_mut();
+        assert_eq!(
+            kite_sql_run(database, sql.as_ptr(), &mut rows),
+            KiteSqlStatus::Ok
+        );
+        let result = fetch_all(rows);
+        kite_sql_rows_close(rows);
+
+        result
+    }
+
+    unsafe fn fetch_all(rows: *mut KiteSqlRows) -> Vec<Vec<KiteSqlValue>> {
+        let mut result = Vec::new();
+
+        while kite_sql_fetch(rows) == KiteSqlStatus::Row {
+            let mut row = Vec::new();
+            for i in 0..kite_sql_column_count(rows) {
+                let mut value = mem::zeroed();
+                assert_eq!(
+                    kite_sql_column_value(rows, i, &mut value),
+                    KiteSqlStatus::Ok
+                );
+                // the text lives until the next row
+                if value.tag == KiteSqlValueTag::Text {
+                    let text = value.data.text;
+                    let bytes = slice::from_raw_parts(text.data as *const u8, text.len);
+                    let leaked = Box::leak(bytes.to_vec().into_boxed_slice());
+                    value.data.text.data = leaked.as_ptr() as *const c_char;
+                }
+                row.push(value);
+            }
+            result.push(row);
+        }
+        result
+    }
+
+    unsafe fn text(value: &KiteSqlValue) -> &str {
+        assert_eq!(value.tag, KiteSqlValueTag::Text);
+        let text = value.data.text;
+        std::str::from_utf8(slice::from_raw_parts(text.data as *const u8, text.len)).unwrap()
+    }
+
+    #[test]
+    fn test_c_abi() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = CString::new(temp_dir.path().to_str().unwrap()).unwrap();
+
+        unsafe {
+            let database = kite_sql_open(path.as_ptr());
+            assert!(!database.is_null());
+
+            run(
+                database,
+                "create table t1 (a int primary key, b varchar, c double, d date)",
+            );
+            run(
+                database,
+                "insert into t1 values (1, 'one', 1.5, '2024-01-01'), (2, null, 2.5, null)",
+            );
+
+            let sql = CString::new("select a, b, c, d from t1 where a >= ?1").unwrap();
+            let mut statement = ptr::null_mut();
+            assert_eq!(
+                kite_sql_prepare(database, sql.as_ptr(), &mut statement),
+                KiteSqlStatus::Ok
+            );
+            let param = KiteSqlValue {
+                tag: KiteSqlValueTag::Int64,
+                data: KiteSqlValueData { int64: 1 },
+            };
+            assert_eq!(kite_sql_bind(statement, 1, param), KiteSqlStatus::Ok);
+
+            let mut rows = ptr::null_mut();
+            assert_eq!(
+                kite_sql_execute(database, statement, &mut rows),
+                KiteSqlStatus::Ok
+            );
+            assert_eq!(kite_sql_column_count(rows), 4);
+            assert_eq!(
+                CStr::from_ptr(kite_sql_column_name(rows, 1)).to_str(),
+                Ok("b")
+            );
+            assert!(kite_sql_column_name(rows, 4).is_null());
+
+            let result = fetch_all(rows);
+            assert_eq!(kite_sql_fetch(rows), KiteSqlStatus::Done);
+            kite_sql_rows_close(rows);
+            kite_sql_statement_close(statement);
+
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0][0].tag, KiteSqlValueTag::Int64);
+            assert_eq!(result[0][0].data.int64, 1);
+            assert_eq!(text(&result[0][1]), "one");
+            assert_eq!(result[0][2].tag, KiteSqlValueTag::Float64);
+            assert_eq!(result[0][2].data.float64, 1.5);
+            assert_eq!(text(&result[0][3]), "2024-01-01");
+            assert_eq!(result[1][1].tag, KiteSqlValueTag::Null);
+            assert_eq!(result[1][3].tag, KiteSqlValueTag::Null);
+
+            let sql = CString::new("select * from t2").unwrap();
+            let mut rows = ptr::null_mut();
+            assert_eq!(
+                kite_sql_run(database, sql.as_ptr(), &mut rows),
+                KiteSqlStatus::Error
+            );
+            assert!(!CStr::from_ptr(kite_sql_last_error()).is_empty());
+            assert_eq!(
+                CStr::from_ptr(kite_sql_last_sqlstate()).to_str(),
+                Ok("42P01")
+            );
+
+            kite_sql_close(database);
+        }
+    }
+}
@@ -0,0 +1,483 @@
+//! The Python module `kite_sql`, following the DB-API (PEP 249):
+//!
+//! ```python
+//! import kite_sql
+//!
+//! with kite_sql.connect("./kitesql_data") as conn:
+//!     cur = conn.cursor()
+//!     cur.execute("insert into t1 values (?1, ?2)", (1, "one"))
+//!     cur.execute("select * from t1 where a >= ?1", (1,))
+//!     print(cur.fetchall())
+//! ```
+//!
+//! The statements of a connection run in a transaction begun by the first of them and ended by
+//! `commit` or `rollback`, or by the `with` block of the connection. The DDL statements run on
+//! their own, out of any transaction.
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use kite_sql::binder::{command_type, CommandType};
+use kite_sql::db::{DBTransaction, DataBaseBuilder, Database, ResultIter};
+use kite_sql::errors::{DatabaseError as KiteSqlError, ErrorCategory};
+use kite_sql::storage::rocksdb::RocksStorage;
+use kite_sql::types::tuple::SchemaRef;
+use kite_sql::types::value::DataValue;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{
+    PyBool, PyDate, PyDateTime, PyDict, PyFloat, PyInt, PyList, PyString, PyTime, PyTuple,
+};
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+use std::mem;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+create_exception!(kite_sql, Error, PyException);
+create_exception!(kite_sql, InterfaceError, Error);
+create_exception!(kite_sql, DatabaseError, Error);
+create_exception!(kite_sql, DataError, DatabaseError);
+create_exception!(kite_sql, OperationalError, DatabaseError);
+create_exception!(kite_sql, IntegrityError, DatabaseError);
+create_exception!(kite_sql, InternalError, DatabaseError);
+create_exception!(kite_sql, ProgrammingError, DatabaseError);
+create_exception!(kite_sql, NotSupportedError, DatabaseError);
+
+/// The exception of the DB-API for the category of the error, with its SQLSTATE as `sqlstate`.
+fn database_error(err: KiteSqlError) -> PyErr {
+    let message = err.to_string();
+    let py_err = match err.category() {
+        ErrorCategory::Syntax if err.sqlstate() == "0A000" => NotSupportedError::new_err(message),
+        ErrorCategory::Syntax
+        | ErrorCategory::UndefinedObject
+        | ErrorCategory::DuplicateObject
+        | ErrorCategory::Semantic => ProgrammingError::new_err(message),
+        ErrorCategory::IntegrityConstraint => IntegrityError::new_err(message),
+        ErrorCategory::Data => DataError::new_err(message),
+        ErrorCategory::TransactionState | ErrorCategory::Cancelled | ErrorCategory::System => {
+            OperationalError::new_err(message)
+        }
+        ErrorCategory::Internal => InternalError::new_err(message),
+    };
+    Python::with_gil(|py| {
+        let _ = py_err.value_bound(py).setattr("sqlstate", err.sqlstate());
+    });
+
+    py_err
+}
+
+/// The names `?n` and `$n` of the placeholders of the n-th parameter, kept for the life of the
+/// process as the parameters of a statement are named by `&'static str`.
+fn placeholder_names(index: usize) -> [&'static str; 2] {
+    static NAMES: Mutex<Vec<[&'static str; 2]>> = Mutex::new(Vec::new());
+    let mut names = NAMES.lock().unwrap();
+
+    while names.len() < index {
+        let n = names.len() + 1;
+        names.push([
+            Box::leak(format!("?{n}").into_boxed_str()),
+            Box::leak(format!("${n}").into_boxed_str()),
+        ]);
+    }
+    names[index - 1]
+}
+
+fn from_py(value: &Bound<'_, PyAny>) -> PyResult<DataValue> {
+    Ok(if value.is_none() {
+        DataValue::Null
+    } else if let Ok(value) = value.downcast::<PyBool>() {
+        DataValue::Boolean(value.is_true())
+    } else if value.is_instance_of::<PyInt>() {
+        match value.extract::<i64>() {
+            Ok(value) => DataValue::Int64(value),
+            Err(_) => DataValue::from(value.extract::<i128>()?),
+        }
+    } else if value.is_instance_of::<PyFloat>() {
+        DataValue::from(value.extract::<f64>()?)
+    } else if value.is_instance_of::<PyString>() {
+        DataValue::from(value.extract::<String>()?)
+    } else if value.is_instance_of::<PyDateTime>() {
+        DataValue::from(&value.extract::<NaiveDateTime>()?)
+    } else if value.is_instance_of::<PyDate>() {
+        DataValue::from(&value.extract::<NaiveDate>()?)
+    } else if value.is_instance_of::<PyTime>() {
+        DataValue::from(&value.extract::<NaiveTime>()?)
+    } else if value.is_instance(&value.py().import_bound("decimal")?.getattr("Decimal")?)? {
+        let text = value.str()?.to_string();
+
+        DataValue::Decimal(
+            Decimal::from_str(&text).map_err(|err| PyTypeError::new_err(err.to_string()))?,
+        )
+    } else {
+        return Err(PyTypeError::new_err(format!(
+            "unsupported parameter type: {}",
+            value.get_type().name()?
+        )));
+    })
+}
+
+fn into_py(py: Python<'_>, value: DataValue) -> PyResult<PyObject> {
+    Ok(match value {
+        DataValue::Null => py.None(),
+        DataValue::Boolean(v) => v.into_py(py),
+        DataValue::Int8(v) => v.into_py(py),
+        DataValue::Int16(v) => v.into_py(py),
+        DataValue::Int32(v) => v.into_py(py),
+        DataValue::Int64(v) => v.into_py(py),
+        DataValue::UInt8(v) => v.into_py(py),
+        DataValue::UInt16(v) => v.into_py(py),
+        DataValue::UInt32(v) => v.into_py(py),
+        DataValue::UInt64(v) => v.into_py(py),
+        DataValue::Int128(v) => v.into_py(py),
+        DataValue::UInt128(v) => v.into_py(py),
+        DataValue::Float32(v) => (v.0 as f64).into_py(py),
+        DataValue::Float64(v) => v.0.into_py(py),
+        DataValue::Utf8 { value, .. } => value.into_py(py),
+        DataValue::Date32(_) => value.date().into_py(py),
+        DataValue::Date64(_) => value.datetime().into_py(py),
+        DataValue::Time32(..) => value.time().into_py(py),
+        DataValue::Decimal(_) | DataValue::Numeric(_) => py
+            .import_bound("decimal")?
+            .getattr("Decimal")?
+            .call1((value.to_string(),))?
+            .unbind(),
+        DataValue::Tuple(values, _) | DataValue::Array(values) => {
+            let values = values
+                .into_iter()
+                .map(|value| into_py(py, value))
+                .collect::<PyResult<Vec<_>>>()?;
+
+            PyList::new_bound(py, values).into_py(py)
+        }
+        DataValue::Vector(values) => {
+            PyList::new_bound(py, values.into_iter().map(|value| value.0 as f64)).into_py(py)
+        }
+        DataValue::Point(x, y) => (x.0, y.0).into_py(py),
+        DataValue::Box(x_min, y_min, x_max, y_max) => {
+            (x_min.0, y_min.0, x_max.0, y_max.0).into_py(py)
+        }
+        DataValue::Struct(fields) => {
+            let dict = PyDict::new_bound(py);
+
+            for (name, value) in fields {
+                dict.set_item(name, into_py(py, value)?)?;
+            }
+            dict.into_py(py)
+        }
+        value => value.to_string().into_py(py),
+    })
+}
+
+/// `connect(path)`: opens the database stored in the directory `path`, creating it if it does
+/// not exist.
+#[pyfunction]
+fn connect(path: &str) -> PyResult<Connection> {
+    let database = DataBaseBuilder::path(path)
+        .build()
+        .map_err(database_error)?;
+
+    Ok(Connection {
+        transaction: None,
+        database: Some(Arc::new(database)),
+    })
+}
+
+#[pyclass(unsendable, module = "kite_sql")]
+pub struct Connection {
+    /// Reads `database`, and is declared before it to be dropped first
+    transaction: Option<DBTransaction<'static, RocksStorage>>,
+    /// `None` once the connection is closed
+    database: Option<Arc<Database<RocksStorage>>>,
+}
+
+/// The result of a statement: the columns and rows of a query, none for the other statements.
+type StatementResult = Option<(SchemaRef, Vec<Vec<PyObject>>)>;
+
+impl Connection {
+    fn run(
+        &mut self,
+        py: Python<'_>,
+        sql: &str,
+        parameters: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<StatementResult> {
+        let database = self
+            .database
+            .clone()
+            .ok_or_else(|| InterfaceError::new_err("the connection is closed"))?;
+        let mut params = Vec::new();
+
+        if let Some(parameters) = parameters {
+            for (i, value) in parameters.iter()?.enumerate() {
+                let value = from_py(&value?)?;
+
+                for name in placeholder_names(i + 1) {
+                    params.push((name, value.clone()));
+                }
+            }
+        }
+        let statement = database.prepare(sql).map_err(database_error)?;
+        let command_type = command_type(&statement).map_err(database_error)?;
+        let mut tuples = Vec::new();
+
+        let schema = if let CommandType::DDL = command_type {
+            if self.transaction.is_some() {
+                return Err(InterfaceError::new_err(
+                    "commit or rollback the transaction before DDL",
+                ));
+            }
+            let mut iter = database
+                .execute(&statement, params)
+                .map_err(database_error)?;
+            for tuple in iter.by_ref() {
+                tuples.push(tuple.map_err(database_error)?);
+            }
+            let schema = iter.schema().clone();
+            iter.done().map_err(database_error)?;
+            schema
+        } else {
+            if self.transaction.is_none() {
+                let transaction = database.new_transaction().map_err(database_error)?;
+                // the `Arc` of the database outlives the transaction, see `Connection`
+                self.transaction = Some(unsafe {
+                    mem::transmute::<
+                        DBTransaction<'_, RocksStorage>,
+                        DBTransaction<'static, RocksStorage>,
+                    >(transaction)
+                });
+            }
+            let transaction = self.transaction.as_mut().unwrap();
+            let mut iter = transaction
+                .execute(&statement, params)
+                .map_err(database_error)?;
+            for tuple in iter.by_ref() {
+                tuples.push(tuple.map_err(database_error)?);
+            }
+            let schema = iter.schema().clone();
+            iter.done().map_err(database_error)?;
+            schema
+        };
+        if !matches!(command_type, CommandType::DQL) {
+            return Ok(None);
+        }
+        let rows = tuples
+            .into_iter()
+            .map(|tuple| {
+                tuple
+                    .values
+                    .into_iter()
+                    .map(|value| into_py(py, value))
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(Some((schema, rows)))
+    }
+}
+
+#[pymethods]
+impl Connection {
+    fn cursor(slf: &Bound<'_, Self>) -> Cursor {
+        Cursor::new(slf.clone().unbind())
+    }
+
+    /// Shorthand of `cursor().execute(sql, parameters)`.
+    #[pyo3(signature = (sql, parameters=None))]
+    fn execute(
+        slf: &Bound<'_, Self>,
+        sql: &str,
+        parameters: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Cursor> {
+        let mut cursor = Cursor::new(slf.clone().unbind());
+        cursor.run(slf.py(), sql, parameters)?;
+
+        Ok(cursor)
+    }
+
+    fn commit(&mut self) -> PyResult<()> {
+        if let Some(transaction) = self.transaction.take() {
+            transaction.commit().map_err(database_error)?;
+        }
+        Ok(())
+    }
+
+    fn rollback(&mut self) {
+        self.transaction = None;
+    }
+
+    /// Closes the connection, rolling back its transaction.
+    fn close(&mut self) {
+        self.transaction = None;
+        self.database = None;
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// Commits the transaction of the `with` block, or rolls it back if the block raised.
+    #[pyo3(signature = (exc_type, _exc_value, _traceback))]
+    fn __exit__(
+        &mut self,
+        exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        if exc_type.is_some_and(|exc_type| !exc_type.is_none()) {
+            self.rollback();
+        } else {
+            self.commit()?;
+        }
+        Ok(false)
+    }
+}
+
+/// The rows of the last statement executed, all read once it runs.
+#[pyclass(unsendable, module = "kite_sql")]
+pub struct Cursor {
+    connection: Py<Connection>,
+    rows: VecDeque<Vec<PyObject>>,
+    #[pyo3(get)]
+    description: Option<PyObject>,
+    #[pyo3(get)]
+    rowcount: i64,
+    #[pyo3(get, set)]
+    arraysize: usize,
+}
+
+impl Cursor {
+    fn new(connection: Py<Connection>) -> Self {
+        Cursor {
+            connection,
+            rows: VecDeque::new(),
+            description: None,
+            rowcount: -1,
+            arraysize: 1,
+        }
+    }
+
+    fn run(
+        &mut self,
+        py: Python<'_>,
+        sql: &str,
+        parameters: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<()> {
+        let result = self.connection.borrow_mut(py).run(py, sql, parameters)?;
+
+        (self.rows, self.description, self.rowcount) = match result {
+            Some((schema, rows)) => {
+                // (name, type_code, display_size, internal_size, precision, scale, null_ok)
+                let description = schema
+                    .iter()
+                    .map(|column| {
+                        (
+                            column.name(),
+                            column.datatype().to_string(),
+                            py.None(),
+                            py.None(),
+                            py.None(),
+                            py.None(),
+                            column.nullable(),
+                        )
+                            .into_py(py)
+                    })
+                    .collect::<Vec<_>>();
+                let rowcount = rows.len() as i64;
+
+                (
+                    rows.into(),
+                    Some(PyTuple::new_bound(py, description).into_py(py)),
+                    rowcount,
+                )
+            }
+            None => (VecDeque::new(), None, -1),
+        };
+        Ok(())
+    }
+
+    fn row(py: Python<'_>, row: Vec<PyObject>) -> PyObject {
+        PyTuple::new_bound(py, row).into_py(py)
+    }
+}
+
+#[pymethods]
+impl Cursor {
+    #[pyo3(signature = (sql, parameters=None))]
+    fn execute<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        sql: &str,
+        parameters: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let py = slf.py();
+        slf.run(py, sql, parameters)?;
+
+        Ok(slf)
+    }
+
+    /// Executes the statement once by parameters of `seq_of_parameters`.
+    fn executemany(
+        &mut self,
+        py: Python<'_>,
+        sql: &str,
+        seq_of_parameters: &Bound<'_, PyAny>,
+    ) -> PyResult<()> {
+        for parameters in seq_of_parameters.iter()? {
+            self.run(py, sql, Some(&parameters?))?;
+        }
+        Ok(())
+    }
+
+    fn fetchone(&mut self, py: Python<'_>) -> Option<PyObject> {
+        self.rows.pop_front().map(|row| Self::row(py, row))
+    }
+
+    #[pyo3(signature = (size=None))]
+    fn fetchmany(&mut self, py: Python<'_>, size: Option<usize>) -> Vec<PyObject> {
+        let size = size.unwrap_or(self.arraysize).min(self.rows.len());
+
+        self.rows
+            .drain(..size)
+            .map(|row| Self::row(py, row))
+            .collect()
+    }
+
+    fn fetchall(&mut self, py: Python<'_>) -> Vec<PyObject> {
+        self.rows.drain(..).map(|row| Self::row(py, row)).collect()
+    }
+
+    fn close(&mut self) {
+        self.rows.clear();
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> Option<PyObject> {
+        self.fetchone(py)
+    }
+}
+
+#[pymodule]
+fn kite_sql(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+
+    m.add("apilevel", "2.0")?;
+    m.add("threadsafety", 1)?;
+    m.add("paramstyle", "numeric")?;
+    m.add("Error", py.get_type_bound::<Error>())?;
+    m.add("InterfaceError", py.get_type_bound::<InterfaceError>())?;
+    m.add("DatabaseError", py.get_type_bound::<DatabaseError>())?;
+    m.add("DataError", py.get_type_bound::<DataError>())?;
+    m.add("OperationalError", py.get_type_bound::<OperationalError>())?;
+    m.add("IntegrityError", py.get_type_bound::<IntegrityError>())?;
+    m.add("InternalError", py.get_type_bound::<InternalError>())?;
+    m.add("ProgrammingError", py.get_type_bound::<ProgrammingError>())?;
+    m.add(
+        "NotSupportedError",
+        py.get_type_bound::<NotSupportedError>(),
+    )?;
+    m.add_class::<Connection>()?;
+    m.add_class::<Cursor>()?;
+    m.add_function(wrap_pyfunction!(connect, m)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+import datetime
+import decimal
+
+import pytest
+
+import kite_sql
+
+
+@pytest.fixture
+def conn(tmp_path):
+    conn = kite_sql.connect(str(tmp_path))
+    conn.execute("create table t1 (a int primary key, b varchar, c double, d date, e decimal(10, 2))")
+    yield conn
+    conn.close()
+
+
+def test_fetch(conn):
+    with conn:
+        cur = conn.cursor()
+        cur.executemany(
+            "insert into t1 values (?1, ?2, ?3, ?4, ?5)",
+            [
+                (1, "one", 1.5, datetime.date(2024, 1, 1), decimal.Decimal("1.25")),
+                (2, None, 2.5, None, None),
+            ],
+        )
+
+    cur = conn.execute("select a, b, c, d, e from t1 where a >= ?1 order by a", (1,))
+    assert [column[0] for column in cur.description] == ["a", "b", "c", "d", "e"]
+    assert cur.rowcount == 2
+    assert cur.fetchone() == (1, "one", 1.5, datetime.date(2024, 1, 1), decimal.Decimal("1.25"))
+    assert cur.fetchall() == [(2, None, 2.5, None, None)]
+    assert cur.fetchone() is None
+
+
+def test_transaction(conn):
+    conn.execute("insert into t1 values (1, 'one', 1.5, null, null)")
+    conn.rollback()
+    assert conn.execute("select count(*) from t1").fetchone() == (0,)
+
+    with pytest.raises(ZeroDivisionError):
+        with conn:
+            conn.execute("insert into t1 values (1, 'one', 1.5, null, null)")
+            1 / 0
+    assert conn.execute("select count(*) from t1").fetchone() == (0,)
+
+    with pytest.raises(kite_sql.InterfaceError):
+        conn.execute("create table t2 (a int primary key)")
+    conn.commit()
+    conn.execute("create table t2 (a int primary key)")
+
+
+def test_error(conn):
+    with pytest.raises(kite_sql.DatabaseError):
+        conn.execute("select * from t3")
+
+
+def test_error_category(conn):
+    with pytest.raises(kite_sql.ProgrammingError) as info:
+        conn.execute("select * from t3")
+    assert info.value.sqlstate == "42P01"
+
+
+def test_integrity_error(conn):
+    with conn:
+        conn.execute("insert into t1 (a, b) values (?1, ?2)", (9, "nine"))
+    with pytest.raises(kite_sql.IntegrityError) as info:
+        conn.execute("insert into t1 (a, b) values (?1, ?2)", (9, "nine"))
+    assert info.value.sqlstate == "23505"
@@ -33,7 +33,8 @@ fn process_type(ty: &Type) -> TokenStream {
         let ident = &path.segments.last().unwrap().ident;
 
         match ident.to_string().as_str() {
-            "Vec" | "Option" | "Arc" | "Box" | "PhantomData" | "Bound" | "CountMinSketch" => {
+            "Vec" | "Option" | "Arc" | "Box" | "PhantomData" | "Bound" | "CountMinSketch"
+            | "OrderedFloat" => {
                 if let PathArguments::AngleBracketed(AngleBracketedGenericArguments {
                     args, ..
                 }) = &path.segments.last().unwrap().arguments
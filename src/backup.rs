@@ -0,0 +1,360 @@
+//! Hot backups: a database streams all of its keys as of a snapshot, while writes continue.
+//!
+//! A [`Backup`] sends its data by chunks to a writer, e.g. the callback of an uploader. Each
+//! chunk ends at an [`BackupOffset`], from which the same backup resumes if the writer failed.
+//! The chunks restore a database by [`Database::restore_chunk`], in any order.
+//!
+//! The statistics of `ANALYZE` are files the keys of the database only name, a backup carries
+//! them in place of their names, and the restored database writes them under its own.
+use crate::binder::CommandType;
+use crate::db::Database;
+use crate::errors::DatabaseError;
+use crate::execution::dml::analyze::Analyze;
+use crate::storage::rocksdb::RocksStorage;
+use crate::storage::table_codec::{BumpBytes, TableCodec};
+use crate::storage::{Storage, Transaction};
+use bumpalo::Bump;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use rocksdb::{
+    Direction, IteratorMode, OptimisticTransactionDB, ReadOptions, SnapshotWithThreadMode,
+};
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+/// 1: the keys and their values only
+/// 2: each of them prefixed by its kind, [`KEY_VALUE`] or [`STATISTICS`]
+const BACKUP_VERSION: u8 = 2;
+const KEY_VALUE: u8 = 0;
+/// The key of the path of the statistics of an index, followed by the path and the content of the
+/// file
+const STATISTICS: u8 = 1;
+const DEFAULT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// The last key of a chunk, the backup resumes after it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BackupOffset(Vec<u8>);
+
+impl BackupOffset {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for BackupOffset {
+    fn from(bytes: Vec<u8>) -> Self {
+        BackupOffset(bytes)
+    }
+}
+
+/// About `chunk_size` bytes of a backup: a version byte followed by the keys and their values,
+/// each of them prefixed by its length in 4 bytes, and by its kind since the version 2.
+#[derive(Debug, Clone)]
+pub struct BackupChunk {
+    pub offset: BackupOffset,
+    pub data: Vec<u8>,
+}
+
+/// A snapshot of a database to stream, see [`Database::backup`].
+pub struct Backup<'a> {
+    snapshot: SnapshotWithThreadMode<'a, OptimisticTransactionDB>,
+    chunk_size: usize,
+}
+
+impl Backup<'_> {
+    /// Bytes of data a chunk holds at least, unless it is the last one, `4MiB` by default.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sends the chunks after `offset`, or all of them, to `writer` in the order of their keys,
+    /// and returns the offset of the last one.
+    ///
+    /// A writer that fails stops the stream, which resumes from the offset of the last chunk
+    /// written by calling it again, as long as the backup is kept.
+    pub fn stream<F>(
+        &self,
+        offset: Option<&BackupOffset>,
+        mut writer: F,
+    ) -> Result<Option<BackupOffset>, DatabaseError>
+    where
+        F: FnMut(&BackupChunk) -> Result<(), DatabaseError>,
+    {
+        let mut options = ReadOptions::default();
+        options.set_total_order_seek(true);
+        let mode = match offset {
+            Some(offset) => IteratorMode::From(offset.as_bytes(), Direction::Forward),
+            None => IteratorMode::Start,
+        };
+        let mut last_offset = offset.cloned();
+        let mut data = Vec::new();
+        let mut last_key = None;
+
+        for item in self.snapshot.iterator_opt(mode, options) {
+            let (key, value) = item?;
+
+            if offset.is_some_and(|offset| offset.as_bytes() == key.as_ref()) {
+                continue;
+            }
+            if data.is_empty() {
+                data.push(BACKUP_VERSION);
+            }
+            if TableCodec::is_statistics_path_key(&key) {
+                let path = TableCodec::decode_statistics_path(&value)?;
+                // dropped by the table since the snapshot
+                let Ok(statistics) = fs::read(&path) else {
+                    continue;
+                };
+                data.push(STATISTICS);
+                write_bytes(&mut data, &key)?;
+                write_bytes(&mut data, &value)?;
+                write_bytes(&mut data, &statistics)?;
+            } else {
+                data.push(KEY_VALUE);
+                write_bytes(&mut data, &key)?;
+                write_bytes(&mut data, &value)?;
+            }
+            last_key = Some(key);
+
+            if data.len() >= self.chunk_size {
+                last_offset = Some(Self::write_chunk(&mut writer, &mut data, &mut last_key)?);
+            }
+        }
+        if !data.is_empty() {
+            last_offset = Some(Self::write_chunk(&mut writer, &mut data, &mut last_key)?);
+        }
+        Ok(last_offset)
+    }
+
+    fn write_chunk<F>(
+        writer: &mut F,
+        data: &mut Vec<u8>,
+        last_key: &mut Option<Box<[u8]>>,
+    ) -> Result<BackupOffset, DatabaseError>
+    where
+        F: FnMut(&BackupChunk) -> Result<(), DatabaseError>,
+    {
+        let chunk = BackupChunk {
+            offset: BackupOffset(last_key.take().map(Vec::from).unwrap_or_default()),
+            data: std::mem::take(data),
+        };
+        writer(&chunk)?;
+
+        Ok(chunk.offset)
+    }
+}
+
+impl Database<RocksStorage> {
+    /// Takes a snapshot of the database to back up, the writes committed after it are not in
+    /// the backup. The snapshot is kept until the backup is dropped.
+    pub fn backup(&self) -> Backup<'_> {
+        Backup {
+            snapshot: self.storage.inner.snapshot(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Writes the keys of a [`BackupChunk`] in a transaction, the database restored from all the
+    /// chunks of a backup should be empty before the first of them.
+    pub fn restore_chunk(&self, chunk: &[u8]) -> Result<(), DatabaseError> {
+        self.state.check_writable(&CommandType::DML)?;
+        let _guard = self.write_mdl()?;
+        let transaction = self.storage.transaction()?;
+        let arena = Bump::new();
+
+        let mut reader = Cursor::new(chunk);
+        let version = reader.read_u8()?;
+        if version != 1 && version != BACKUP_VERSION {
+            return Err(DatabaseError::InvalidValue(format!(
+                "backup chunk version {version} is not supported"
+            )));
+        }
+        while (reader.position() as usize) < chunk.len() {
+            let kind = if version == 1 {
+                KEY_VALUE
+            } else {
+                reader.read_u8()?
+            };
+            let key = read_bytes(&mut reader, &arena)?;
+            let value = read_bytes(&mut reader, &arena)?;
+
+            match kind {
+                KEY_VALUE => transaction.set(key, value)?,
+                STATISTICS => {
+                    let statistics = read_bytes(&mut reader, &arena)?;
+                    let path = restore_statistics(&value, &statistics)?;
+
+                    transaction.set(key, BumpBytes::from_iter_in(path.into_bytes(), &arena))?;
+                }
+                kind => {
+                    return Err(DatabaseError::InvalidValue(format!(
+                        "backup chunk entry of kind {kind}"
+                    )))
+                }
+            }
+        }
+        transaction.commit()?;
+
+        // the chunk may hold the metas of any table or view
+        self.state.meta_cache().clear();
+        self.state.table_cache().clear();
+        self.state.view_cache().clear();
+        Ok(())
+    }
+}
+
+/// Writes the statistics of an index where `ANALYZE` of the restored database would, by the
+/// table and the index named by the last components of their path in the backed up database.
+fn restore_statistics(path: &[u8], statistics: &[u8]) -> Result<String, DatabaseError> {
+    let path = TableCodec::decode_statistics_path(path)?;
+    let path = Path::new(&path);
+    let (Some(file), Some(table_name)) =
+        (path.file_name(), path.parent().and_then(Path::file_name))
+    else {
+        return Err(DatabaseError::InvalidValue(format!(
+            "path of statistics: {}",
+            path.display()
+        )));
+    };
+    let dir =
+        Analyze::build_statistics_meta_path(&Arc::new(table_name.to_string_lossy().into_owned()));
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(file);
+    fs::write(&path, statistics)?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn write_bytes(data: &mut Vec<u8>, bytes: &[u8]) -> Result<(), DatabaseError> {
+    data.write_u32::<BigEndian>(bytes.len() as u32)?;
+    data.extend_from_slice(bytes);
+
+    Ok(())
+}
+
+fn read_bytes<'a>(
+    reader: &mut Cursor<&[u8]>,
+    arena: &'a Bump,
+) -> Result<BumpBytes<'a>, DatabaseError> {
+    let len = reader.read_u32::<BigEndian>()? as usize;
+    let mut bytes = BumpBytes::from_iter_in(std::iter::repeat_n(0, len), arena);
+    reader.read_exact(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backup::BackupOffset;
+    use crate::db::{DataBaseBuilder, Database, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::execution::dml::analyze::Analyze;
+    use crate::storage::rocksdb::RocksStorage;
+    use crate::storage::{Storage, Transaction};
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn rows(database: &Database<RocksStorage>) -> Result<usize, DatabaseError> {
+        let mut iter = database.run("select * from t1")?;
+        let rows = iter.by_ref().collect::<Result<Vec<_>, _>>()?.len();
+        iter.done()?;
+
+        Ok(rows)
+    }
+
+    #[test]
+    fn test_backup_and_restore() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let restored_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t1 (a int primary key, b varchar)")?
+            .done()?;
+        for i in 0..100 {
+            kite_sql
+                .run(format!("insert into t1 values ({i}, 'row {i}')"))?
+                .done()?;
+        }
+        let backup = kite_sql.backup().chunk_size(256);
+        // not in the backup
+        kite_sql.run("delete from t1 where a < 50")?.done()?;
+
+        let mut chunks = Vec::new();
+        let mut last_offset: Option<BackupOffset> = None;
+        // the uploader fails once after 3 chunks
+        let result = backup.stream(None, |chunk| {
+            if chunks.len() == 3 {
+                return Err(DatabaseError::InvalidValue("upload failed".to_string()));
+            }
+            chunks.push(chunk.data.clone());
+            last_offset = Some(chunk.offset.clone());
+            Ok(())
+        });
+        assert!(result.is_err());
+        let end = backup.stream(last_offset.as_ref(), |chunk| {
+            chunks.push(chunk.data.clone());
+            Ok(())
+        })?;
+        assert!(end.is_some());
+        assert!(chunks.len() > 4);
+        assert_eq!(backup.stream(end.as_ref(), |_| unreachable!())?, end);
+
+        let restored = DataBaseBuilder::path(restored_dir.path()).build()?;
+        for chunk in chunks.iter().rev() {
+            restored.restore_chunk(chunk)?;
+        }
+        assert_eq!(rows(&restored)?, 100);
+        assert_eq!(rows(&kite_sql)?, 50);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_after_analyze() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let restored_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let table_name = Arc::new("t_backup_statistics".to_string());
+
+        kite_sql
+            .run("create table t_backup_statistics (a int primary key, b int)")?
+            .done()?;
+        // ANALYZE builds a histogram of 100 buckets, which needs as many rows
+        for i in 0..101 {
+            kite_sql
+                .run(format!("insert into t_backup_statistics values ({i}, {i})"))?
+                .done()?;
+        }
+        kite_sql.run("analyze table t_backup_statistics")?.done()?;
+
+        let mut chunks = Vec::new();
+        kite_sql.backup().stream(None, |chunk| {
+            chunks.push(chunk.data.clone());
+            Ok(())
+        })?;
+        // the statistics come from the backup, not from the files of the database backed up
+        fs::remove_dir_all(Analyze::build_statistics_meta_path(&table_name))?;
+
+        let restored = DataBaseBuilder::path(restored_dir.path()).build()?;
+        for chunk in chunks {
+            restored.restore_chunk(&chunk)?;
+        }
+        let transaction = restored.storage.transaction()?;
+        let loader = transaction.meta_loader(restored.state.meta_cache());
+        let statistics = loader
+            .load(&table_name, 0)?
+            .expect("the statistics of the primary key are restored");
+        assert_eq!(statistics.index_id(), 0);
+        drop(transaction);
+
+        let mut iter = restored.run("select * from t_backup_statistics where a = 1")?;
+        assert_eq!(iter.by_ref().count(), 1);
+        iter.done()?;
+
+        Ok(())
+    }
+}
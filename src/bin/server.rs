@@ -1,25 +1,35 @@
 use async_trait::async_trait;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use clap::Parser;
-use futures::stream;
+use futures::{stream, Sink, SinkExt};
+use kite_sql::binder::copy::FileFormat;
 use kite_sql::db::{DBTransaction, DataBaseBuilder, Database, ResultIter};
 use kite_sql::errors::DatabaseError;
+use kite_sql::parser::parse_sql;
 use kite_sql::storage::rocksdb::RocksStorage;
 use kite_sql::types::tuple::{Schema, SchemaRef, Tuple};
+use kite_sql::types::value::DataValue;
 use kite_sql::types::LogicalType;
 use log::{error, info, LevelFilter};
 use parking_lot::Mutex;
 use pgwire::api::auth::noop::NoopStartupHandler;
-use pgwire::api::copy::NoopCopyHandler;
+use pgwire::api::copy::CopyHandler;
 use pgwire::api::query::{PlaceholderExtendedQueryHandler, SimpleQueryHandler};
-use pgwire::api::results::{DataRowEncoder, FieldFormat, FieldInfo, QueryResponse, Response, Tag};
+use pgwire::api::results::{
+    CopyResponse, DataRowEncoder, FieldFormat, FieldInfo, QueryResponse, Response, Tag,
+};
 use pgwire::api::{ClientInfo, NoopErrorHandler, PgWireServerHandlers, Type};
 use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
+use pgwire::messages::copy::{CopyData, CopyDone, CopyFail};
+use pgwire::messages::PgWireBackendMessage;
 use pgwire::tokio::process_socket;
+use sqlparser::ast::{CopyOption, CopySource, CopyTarget, Statement};
 use std::fmt::Debug;
-use std::io;
-use std::mem::transmute;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::mem::{self, transmute};
 use std::ops::{Deref, DerefMut};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::ptr::NonNull;
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -79,9 +89,101 @@ impl KiteSQLBackend {
     }
 }
 
+/// The signature of the binary format of `COPY`.
+const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// The field of a NULL in the CSV file loaded for the text and binary formats.
+const NULL_FIELD: &str = "\\N";
+
+/// The formats of the rows of `COPY ... FROM STDIN` and `COPY ... TO STDOUT`.
+#[derive(Debug, Clone)]
+enum CopyFormat {
+    /// A line by row of the fields split by `delimiter`, with `\` escapes and `null` for a NULL
+    Text { delimiter: char, null: String },
+    /// The CSV of `COPY ... FROM 'file'`, a NULL being an empty field unless `NULL` is given
+    Csv { options: Vec<CopyOption> },
+    /// The signature, then the fields of each row prefixed by their length
+    Binary,
+}
+
+impl CopyFormat {
+    fn from_options(options: &[CopyOption]) -> PgWireResult<Self> {
+        let format = options
+            .iter()
+            .find_map(|option| match option {
+                CopyOption::Format(format) => Some(format.value.to_lowercase()),
+                _ => None,
+            })
+            .unwrap_or_else(|| "text".to_string());
+        let unsupported = |option: &CopyOption| {
+            api_error(DatabaseError::UnsupportedStmt(format!(
+                "copy option of FORMAT {format}: {option}"
+            )))
+        };
+
+        match format.as_str() {
+            "text" => {
+                let (mut delimiter, mut null) = ('\t', "\\N".to_string());
+
+                for option in options {
+                    match option {
+                        CopyOption::Format(_) => (),
+                        CopyOption::Delimiter(c) => delimiter = *c,
+                        CopyOption::Null(s) => null = s.clone(),
+                        option => return Err(unsupported(option)),
+                    }
+                }
+                Ok(CopyFormat::Text { delimiter, null })
+            }
+            "csv" => {
+                let mut options = options.to_vec();
+
+                if !options
+                    .iter()
+                    .any(|option| matches!(option, CopyOption::Null(_)))
+                {
+                    options.push(CopyOption::Null(String::new()));
+                }
+                FileFormat::from_options(&options).map_err(api_error)?;
+                Ok(CopyFormat::Csv { options })
+            }
+            "binary" => {
+                if let Some(option) = options
+                    .iter()
+                    .find(|option| !matches!(option, CopyOption::Format(_)))
+                {
+                    return Err(unsupported(option));
+                }
+                Ok(CopyFormat::Binary)
+            }
+            format => Err(api_error(DatabaseError::UnsupportedStmt(format!(
+                "FORMAT {format}"
+            )))),
+        }
+    }
+
+    fn code(&self) -> i8 {
+        match self {
+            CopyFormat::Text { .. } | CopyFormat::Csv { .. } => 0,
+            CopyFormat::Binary => 1,
+        }
+    }
+}
+
+/// A `COPY ... FROM STDIN` receiving its rows, which are written as they come into a file loaded
+/// once the client is done.
+struct CopyIn {
+    source: CopySource,
+    format: CopyFormat,
+    schema: SchemaRef,
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
 pub struct SessionBackend {
     inner: Arc<Database<RocksStorage>>,
     tx: Mutex<Option<TransactionPtr>>,
+    copy_in: Mutex<Option<CopyIn>>,
 }
 
 impl SessionBackend {
@@ -89,8 +191,155 @@ impl SessionBackend {
         SessionBackend {
             inner,
             tx: Mutex::new(None),
+            copy_in: Mutex::new(None),
+        }
+    }
+
+    /// Runs a statement in the transaction of the session if one is open.
+    fn run(&self, sql: &str) -> PgWireResult<(SchemaRef, Vec<Tuple>)> {
+        let mut guard = self.tx.lock();
+
+        let mut tuples = Vec::new();
+        let schema = if let Some(transaction) = guard.as_mut() {
+            let mut iter = unsafe { transaction.as_mut().run(sql) }.map_err(api_error)?;
+            for tuple in iter.by_ref() {
+                tuples.push(tuple.map_err(api_error)?);
+            }
+            let schema = iter.schema().clone();
+            iter.done().map_err(api_error)?;
+            schema
+        } else {
+            let mut iter = self.inner.run(sql).map_err(api_error)?;
+            for tuple in iter.by_ref() {
+                tuples.push(tuple.map_err(api_error)?);
+            }
+            let schema = iter.schema().clone();
+            iter.done().map_err(api_error)?;
+            schema
+        };
+        Ok((schema, tuples))
+    }
+
+    /// Starts `COPY ... FROM STDIN` or runs `COPY ... TO STDOUT`, `None` for any other statement.
+    fn copy_stdio<'a>(&self, query: &str) -> PgWireResult<Option<Response<'a>>> {
+        let Ok(statements) = parse_sql(query) else {
+            return Ok(None);
+        };
+        let [Statement::Copy {
+            source,
+            to,
+            target,
+            options,
+            ..
+        }] = statements.as_slice()
+        else {
+            return Ok(None);
+        };
+        let select = match source {
+            CopySource::Table {
+                table_name,
+                columns,
+            } if columns.is_empty() => format!("SELECT * FROM {table_name}"),
+            CopySource::Table {
+                table_name,
+                columns,
+            } => format!(
+                "SELECT {} FROM {table_name}",
+                columns
+                    .iter()
+                    .map(|column| column.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            CopySource::Query(query) => query.to_string(),
+        };
+
+        match (target, *to) {
+            (CopyTarget::Stdin, false) => {
+                if !matches!(source, CopySource::Table { columns, .. } if columns.is_empty()) {
+                    return Err(api_error(DatabaseError::UnsupportedStmt(
+                        "COPY FROM STDIN of a query or of some columns".to_string(),
+                    )));
+                }
+                let format = CopyFormat::from_options(options)?;
+                let (schema, _) = self.run(&format!("{select} LIMIT 0"))?;
+                let path = copy_file_path("in");
+                let writer = BufWriter::new(File::create(&path).map_err(api_error)?);
+                let response = CopyResponse::new(format.code(), schema.len(), stream::empty());
+
+                self.copy_in.lock().replace(CopyIn {
+                    source: source.clone(),
+                    format,
+                    schema,
+                    path,
+                    writer,
+                });
+                Ok(Some(Response::CopyIn(response)))
+            }
+            (CopyTarget::Stdout, true) => {
+                let format = CopyFormat::from_options(options)?;
+                let (schema, tuples) = self.run(&select)?;
+                let rows = encode_copy_rows(&format, &schema, tuples)?;
+
+                Ok(Some(Response::CopyOut(CopyResponse::new(
+                    format.code(),
+                    schema.len(),
+                    stream::iter(rows.into_iter().map(|row| Ok(CopyData::new(row.into())))),
+                ))))
+            }
+            _ => Ok(None),
         }
     }
+
+    /// Loads the rows received by `COPY ... FROM STDIN`, returning how many of them were loaded.
+    fn finish_copy_in(&self, copy_in: CopyIn) -> PgWireResult<usize> {
+        let CopyIn {
+            source,
+            format,
+            schema,
+            path,
+            writer,
+        } = copy_in;
+        drop(writer.into_inner().map_err(|e| api_error(e.into_error()))?);
+
+        let (load_path, options) = match format {
+            CopyFormat::Csv { options } => (path.clone(), options),
+            format => {
+                let load_path = copy_file_path("csv");
+                let options = vec![CopyOption::Null(NULL_FIELD.to_string())];
+
+                if let Err(err) = into_csv(&format, &schema, &path, &load_path) {
+                    let _ = fs::remove_file(&load_path);
+                    let _ = fs::remove_file(&path);
+                    return Err(err);
+                }
+                (load_path, options)
+            }
+        };
+        let statement = Statement::Copy {
+            source,
+            to: false,
+            target: CopyTarget::File {
+                filename: load_path.to_string_lossy().to_string(),
+            },
+            options,
+            legacy_options: vec![],
+            values: vec![],
+        };
+        let result = self.run(&statement.to_string());
+        let _ = fs::remove_file(&load_path);
+        let _ = fs::remove_file(&path);
+        let (_, tuples) = result?;
+
+        // the result of `COPY ... FROM` is `import n rows`
+        Ok(tuples
+            .first()
+            .and_then(|tuple| tuple.values.first())
+            .and_then(|value| value.utf8())
+            .and_then(|message| message.split_whitespace().nth(1))
+            .and_then(|rows| rows.parse().ok())
+            .unwrap_or(0))
+    }
 }
 
 impl NoopStartupHandler for SessionBackend {}
@@ -109,7 +358,7 @@ impl PgWireServerHandlers for CustomBackendFactory {
     type StartupHandler = SessionBackend;
     type SimpleQueryHandler = SessionBackend;
     type ExtendedQueryHandler = PlaceholderExtendedQueryHandler;
-    type CopyHandler = NoopCopyHandler;
+    type CopyHandler = SessionBackend;
     type ErrorHandler = NoopErrorHandler;
 
     fn simple_query_handler(&self) -> Arc<Self::SimpleQueryHandler> {
@@ -125,7 +374,7 @@ impl PgWireServerHandlers for CustomBackendFactory {
     }
 
     fn copy_handler(&self) -> Arc<Self::CopyHandler> {
-        Arc::new(NoopCopyHandler)
+        self.handler.clone()
     }
 
     fn error_handler(&self) -> Arc<Self::ErrorHandler> {
@@ -148,14 +397,9 @@ impl SimpleQueryHandler for SessionBackend {
                 let mut guard = self.tx.lock();
 
                 if guard.is_some() {
-                    return Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::TransactionAlreadyExists,
-                    )));
+                    return Err(api_error(DatabaseError::TransactionAlreadyExists));
                 }
-                let transaction = self
-                    .inner
-                    .new_transaction()
-                    .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+                let transaction = self.inner.new_transaction().map_err(api_error)?;
                 guard.replace(TransactionPtr(
                     Box::leak(Box::<DBTransaction<'static, RocksStorage>>::new(unsafe {
                         transmute(transaction)
@@ -171,13 +415,11 @@ impl SimpleQueryHandler for SessionBackend {
                 if let Some(transaction) = guard.take() {
                     unsafe { Box::from_raw(transaction.as_ptr()) }
                         .commit()
-                        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+                        .map_err(api_error)?;
 
                     Ok(vec![Response::Execution(Tag::new("OK"))])
                 } else {
-                    Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::NoTransactionBegin,
-                    )))
+                    Err(api_error(DatabaseError::NoTransactionBegin))
                 }
             }
             "ROLLBACK;" | "ROLLBACK" => {
@@ -186,44 +428,409 @@ impl SimpleQueryHandler for SessionBackend {
                 if let Some(transaction) = guard.take() {
                     unsafe { drop(Box::from_raw(transaction.as_ptr())) }
                 } else {
-                    return Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::NoTransactionBegin,
-                    )));
+                    return Err(api_error(DatabaseError::NoTransactionBegin));
                 }
 
                 Ok(vec![Response::Execution(Tag::new("OK"))])
             }
             _ => {
-                let mut guard = self.tx.lock();
+                if let Some(response) = self.copy_stdio(query)? {
+                    return Ok(vec![response]);
+                }
+                let (schema, tuples) = self.run(query)?;
+
+                Ok(vec![Response::Query(encode_tuples(&schema, tuples)?)])
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CopyHandler for SessionBackend {
+    async fn on_copy_data<C>(&self, _client: &mut C, copy_data: CopyData) -> PgWireResult<()>
+    where
+        C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send + Sync,
+        C::Error: Debug,
+        PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+    {
+        let mut guard = self.copy_in.lock();
+        let copy_in = guard
+            .as_mut()
+            .ok_or_else(|| api_error(DatabaseError::UnsupportedStmt("COPY data".to_string())))?;
+
+        copy_in.writer.write_all(&copy_data.data).map_err(api_error)
+    }
+
+    async fn on_copy_done<C>(&self, client: &mut C, _done: CopyDone) -> PgWireResult<()>
+    where
+        C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send + Sync,
+        C::Error: Debug,
+        PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+    {
+        let copy_in =
+            self.copy_in.lock().take().ok_or_else(|| {
+                api_error(DatabaseError::UnsupportedStmt("COPY done".to_string()))
+            })?;
+        let rows = self.finish_copy_in(copy_in)?;
+
+        client
+            .send(PgWireBackendMessage::CommandComplete(
+                Tag::new("COPY").with_rows(rows).into(),
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn on_copy_fail<C>(&self, _client: &mut C, fail: CopyFail) -> PgWireError
+    where
+        C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send + Sync,
+        C::Error: Debug,
+        PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+    {
+        if let Some(copy_in) = self.copy_in.lock().take() {
+            drop(copy_in.writer);
+            let _ = fs::remove_file(&copy_in.path);
+        }
+        PgWireError::UserError(Box::new(ErrorInfo::new(
+            "ERROR".to_owned(),
+            "57014".to_owned(),
+            format!("COPY from stdin failed: {}", fail.message),
+        )))
+    }
+}
 
-                let mut tuples = Vec::new();
-                let response = if let Some(transaction) = guard.as_mut() {
-                    let mut iter = unsafe { transaction.as_mut().run(query) }
-                        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-                    for tuple in iter.by_ref() {
-                        tuples.push(tuple.map_err(|e| PgWireError::ApiError(Box::new(e)))?);
+/// Reports the error to the client with its SQLSTATE.
+fn api_error(err: impl Into<DatabaseError>) -> PgWireError {
+    let err = err.into();
+
+    PgWireError::UserError(Box::new(ErrorInfo::new(
+        "ERROR".to_owned(),
+        err.sqlstate().to_owned(),
+        err.to_string(),
+    )))
+}
+
+/// A file of the temporary directory for the rows of a `COPY ... FROM STDIN`.
+fn copy_file_path(extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("kitesql-copy-{}.{extension}", ulid::Ulid::new()))
+}
+
+/// Writes the rows of the text or binary format in `path` as a CSV file with [`NULL_FIELD`] for
+/// the NULLs.
+fn into_csv(
+    format: &CopyFormat,
+    schema: &Schema,
+    path: &Path,
+    csv_path: &Path,
+) -> PgWireResult<()> {
+    let mut reader = BufReader::new(File::open(path).map_err(api_error)?);
+    let mut writer = csv::Writer::from_path(csv_path).map_err(api_error)?;
+    let mut write = |fields: Vec<Option<String>>| -> PgWireResult<()> {
+        if fields.len() != schema.len() {
+            return Err(api_error(DatabaseError::MisMatch("columns", "values")));
+        }
+        writer
+            .write_record(
+                fields
+                    .iter()
+                    .map(|field| field.as_deref().unwrap_or(NULL_FIELD)),
+            )
+            .map_err(api_error)
+    };
+
+    match format {
+        CopyFormat::Text { delimiter, null } => {
+            for line in reader.split(b'\n') {
+                let line = String::from_utf8(line.map_err(api_error)?).map_err(|_| {
+                    api_error(DatabaseError::InvalidValue(
+                        "COPY data of invalid UTF-8".to_string(),
+                    ))
+                })?;
+                let line = line.strip_suffix('\r').unwrap_or(&line);
+
+                if line == "\\." {
+                    break;
+                }
+                write(decode_text_line(line, *delimiter, null))?;
+            }
+        }
+        CopyFormat::Binary => {
+            let mut signature = [0; 11];
+            reader.read_exact(&mut signature).map_err(api_error)?;
+            if signature != BINARY_SIGNATURE {
+                return Err(api_error(DatabaseError::InvalidValue(
+                    "COPY binary signature".to_string(),
+                )));
+            }
+            let _flags = reader.read_i32::<BigEndian>().map_err(api_error)?;
+            let extension = reader.read_i32::<BigEndian>().map_err(api_error)?;
+            io::copy(
+                &mut reader.by_ref().take(extension.max(0) as u64),
+                &mut io::sink(),
+            )
+            .map_err(api_error)?;
+
+            loop {
+                let count = reader.read_i16::<BigEndian>().map_err(api_error)?;
+                if count < 0 {
+                    break;
+                }
+                let mut fields = Vec::with_capacity(count as usize);
+
+                for i in 0..count as usize {
+                    let len = reader.read_i32::<BigEndian>().map_err(api_error)?;
+                    if len < 0 {
+                        fields.push(None);
+                        continue;
                     }
-                    let schema = iter.schema().clone();
-                    iter.done()
-                        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-                    encode_tuples(&schema, tuples)?
-                } else {
-                    let mut iter = self
-                        .inner
-                        .run(query)
-                        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-                    for tuple in iter.by_ref() {
-                        tuples.push(tuple.map_err(|e| PgWireError::ApiError(Box::new(e)))?);
+                    let mut bytes = vec![0; len as usize];
+                    reader.read_exact(&mut bytes).map_err(api_error)?;
+
+                    let column = schema
+                        .get(i)
+                        .ok_or_else(|| api_error(DatabaseError::MisMatch("columns", "values")))?;
+                    fields.push(Some(decode_binary_field(column.datatype(), &bytes)?));
+                }
+                write(fields)?;
+            }
+        }
+        CopyFormat::Csv { .. } => unreachable!(),
+    }
+    writer.flush().map_err(api_error)
+}
+
+/// The fields of a line of the text format, `None` for a NULL.
+fn decode_text_line(line: &str, delimiter: char, null: &str) -> Vec<Option<String>> {
+    let mut fields = Vec::new();
+    let mut raw = String::new();
+    let mut chars = line.chars();
+
+    loop {
+        match chars.next() {
+            Some('\\') => {
+                raw.push('\\');
+                raw.extend(chars.next());
+            }
+            Some(c) if c == delimiter => fields.push(mem::take(&mut raw)),
+            Some(c) => raw.push(c),
+            None => {
+                fields.push(raw);
+                break;
+            }
+        }
+    }
+    fields
+        .into_iter()
+        .map(|raw| (raw != null).then(|| unescape_text(&raw)))
+        .collect()
+}
+
+fn unescape_text(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('b') => value.push('\u{8}'),
+            Some('f') => value.push('\u{c}'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('v') => value.push('\u{b}'),
+            Some(c) => value.push(c),
+            None => value.push('\\'),
+        }
+    }
+    value
+}
+
+fn escape_text(value: &str, delimiter: char, row: &mut String) {
+    for c in value.chars() {
+        match c {
+            '\\' => row.push_str("\\\\"),
+            '\n' => row.push_str("\\n"),
+            '\r' => row.push_str("\\r"),
+            '\t' => row.push_str("\\t"),
+            c if c == delimiter => {
+                row.push('\\');
+                row.push(c);
+            }
+            c => row.push(c),
+        }
+    }
+}
+
+fn binary_unsupported(data_type: &LogicalType) -> PgWireError {
+    api_error(DatabaseError::UnsupportedStmt(format!(
+        "COPY of FORMAT binary with {data_type}"
+    )))
+}
+
+/// The text of a field of the binary format, cast by `COPY ... FROM` to the type of its column.
+fn decode_binary_field(data_type: &LogicalType, bytes: &[u8]) -> PgWireResult<String> {
+    let mut reader = bytes;
+    let invalid = |_: io::Error| {
+        api_error(DatabaseError::InvalidValue(format!(
+            "COPY binary field of {data_type}"
+        )))
+    };
+
+    Ok(match data_type {
+        LogicalType::Boolean => (reader.read_u8().map_err(invalid)? != 0).to_string(),
+        LogicalType::Tinyint
+        | LogicalType::UTinyint
+        | LogicalType::Smallint
+        | LogicalType::USmallint
+        | LogicalType::Integer
+        | LogicalType::UInteger
+        | LogicalType::Bigint
+        | LogicalType::UBigint => match bytes.len() {
+            1 => reader.read_i8().map_err(invalid)?.to_string(),
+            2 => reader.read_i16::<BigEndian>().map_err(invalid)?.to_string(),
+            4 => reader.read_i32::<BigEndian>().map_err(invalid)?.to_string(),
+            8 => reader.read_i64::<BigEndian>().map_err(invalid)?.to_string(),
+            _ => return Err(invalid(io::ErrorKind::InvalidData.into())),
+        },
+        LogicalType::Float | LogicalType::Double => match bytes.len() {
+            4 => reader.read_f32::<BigEndian>().map_err(invalid)?.to_string(),
+            8 => reader.read_f64::<BigEndian>().map_err(invalid)?.to_string(),
+            _ => return Err(invalid(io::ErrorKind::InvalidData.into())),
+        },
+        LogicalType::Char(..) | LogicalType::Varchar(..) => String::from_utf8(bytes.to_vec())
+            .map_err(|_| invalid(io::ErrorKind::InvalidData.into()))?,
+        data_type => return Err(binary_unsupported(data_type)),
+    })
+}
+
+fn encode_binary_field(value: &DataValue, row: &mut Vec<u8>) -> PgWireResult<()> {
+    if value.is_null() {
+        row.write_i32::<BigEndian>(-1).map_err(api_error)?;
+        return Ok(());
+    }
+    let mut field = Vec::new();
+    let written = match value.logical_type() {
+        LogicalType::Boolean => field.write_u8(value.bool().unwrap_or_default() as u8),
+        LogicalType::Tinyint => field.write_i8(value.i8().unwrap_or_default()),
+        LogicalType::UTinyint => field.write_i8(value.u8().unwrap_or_default() as i8),
+        LogicalType::Smallint => field.write_i16::<BigEndian>(value.i16().unwrap_or_default()),
+        LogicalType::USmallint => {
+            field.write_i16::<BigEndian>(value.u16().unwrap_or_default() as i16)
+        }
+        LogicalType::Integer => field.write_i32::<BigEndian>(value.i32().unwrap_or_default()),
+        LogicalType::UInteger => {
+            field.write_i32::<BigEndian>(value.u32().unwrap_or_default() as i32)
+        }
+        LogicalType::Bigint => field.write_i64::<BigEndian>(value.i64().unwrap_or_default()),
+        LogicalType::UBigint => {
+            field.write_i64::<BigEndian>(value.u64().unwrap_or_default() as i64)
+        }
+        LogicalType::Float => field.write_f32::<BigEndian>(value.float().unwrap_or_default()),
+        LogicalType::Double => field.write_f64::<BigEndian>(value.double().unwrap_or_default()),
+        LogicalType::Char(..)
+        | LogicalType::Varchar(..)
+        | LogicalType::Vector(_)
+        | LogicalType::Array(_)
+        | LogicalType::Struct(_) => field.write_all(value.to_string().as_bytes()),
+        data_type => return Err(binary_unsupported(&data_type)),
+    };
+    written.map_err(api_error)?;
+    row.write_i32::<BigEndian>(field.len() as i32)
+        .map_err(api_error)?;
+    row.append(&mut field);
+
+    Ok(())
+}
+
+/// The `CopyData` of the rows of `COPY ... TO STDOUT`, the signature and the trailer of the
+/// binary format being a row of their own.
+fn encode_copy_rows(
+    format: &CopyFormat,
+    schema: &Schema,
+    tuples: Vec<Tuple>,
+) -> PgWireResult<Vec<Vec<u8>>> {
+    let mut rows = Vec::with_capacity(tuples.len() + 2);
+
+    match format {
+        CopyFormat::Text { delimiter, null } => {
+            for tuple in tuples {
+                let mut row = String::new();
+
+                for (i, value) in tuple.values.iter().enumerate() {
+                    if i > 0 {
+                        row.push(*delimiter);
+                    }
+                    if value.is_null() {
+                        row.push_str(null);
+                    } else {
+                        escape_text(&value.to_string(), *delimiter, &mut row);
                     }
-                    let schema = iter.schema().clone();
-                    iter.done()
-                        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-                    encode_tuples(&schema, tuples)?
-                };
-                Ok(vec![Response::Query(response)])
+                }
+                row.push('\n');
+                rows.push(row.into_bytes());
             }
         }
+        CopyFormat::Csv { options } => {
+            let FileFormat::Csv {
+                delimiter,
+                quote,
+                header,
+                null,
+                ..
+            } = FileFormat::from_options(options).map_err(api_error)?
+            else {
+                unreachable!()
+            };
+            let null = null.unwrap_or_default();
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(delimiter as u8)
+                .quote(quote as u8)
+                .from_writer(Vec::new());
+            let take = |writer: &mut csv::Writer<Vec<u8>>| -> PgWireResult<Vec<u8>> {
+                writer.flush().map_err(api_error)?;
+                Ok(mem::take(writer.get_mut()))
+            };
+
+            if header {
+                writer
+                    .write_record(schema.iter().map(|column| column.name()))
+                    .map_err(api_error)?;
+                rows.push(take(&mut writer)?);
+            }
+            for tuple in tuples {
+                writer
+                    .write_record(tuple.values.iter().map(|value| {
+                        if value.is_null() {
+                            null.clone()
+                        } else {
+                            value.to_string()
+                        }
+                    }))
+                    .map_err(api_error)?;
+                rows.push(take(&mut writer)?);
+            }
+        }
+        CopyFormat::Binary => {
+            let mut header = BINARY_SIGNATURE.to_vec();
+            header.extend_from_slice(&[0; 8]);
+            rows.push(header);
+
+            for tuple in tuples {
+                let mut row = Vec::new();
+                row.write_i16::<BigEndian>(tuple.values.len() as i16)
+                    .map_err(api_error)?;
+                for value in tuple.values.iter() {
+                    encode_binary_field(value, &mut row)?;
+                }
+                rows.push(row);
+            }
+            rows.push((-1_i16).to_be_bytes().to_vec());
+        }
     }
+    Ok(rows)
 }
 
 fn encode_tuples<'a>(schema: &SchemaRef, tuples: Vec<Tuple>) -> PgWireResult<QueryResponse<'a>> {
@@ -274,6 +881,14 @@ fn encode_tuples<'a>(schema: &SchemaRef, tuples: Vec<Tuple>) -> PgWireResult<Que
                 LogicalType::Decimal(_, _) => {
                     encoder.encode_field(&value.decimal().map(|decimal| decimal.to_string()))
                 }
+                LogicalType::Hugeint | LogicalType::UHugeint | LogicalType::Numeric => {
+                    encoder.encode_field(&Some(value.to_string()))
+                }
+                LogicalType::Vector(_)
+                | LogicalType::Point
+                | LogicalType::Box
+                | LogicalType::Array(_)
+                | LogicalType::Struct(_) => encoder.encode_field(&Some(value.to_string())),
                 _ => unreachable!(),
             }?;
         }
@@ -298,7 +913,13 @@ fn into_pg_type(data_type: &LogicalType) -> PgWireResult<Type> {
         LogicalType::Date | LogicalType::DateTime => Type::DATE,
         LogicalType::Char(..) => Type::CHAR,
         LogicalType::Time => Type::TIME,
-        LogicalType::Decimal(_, _) => Type::NUMERIC,
+        LogicalType::Decimal(_, _)
+        | LogicalType::Hugeint
+        | LogicalType::UHugeint
+        | LogicalType::Numeric => Type::NUMERIC,
+        LogicalType::Vector(_) | LogicalType::Array(_) | LogicalType::Struct(_) => Type::VARCHAR,
+        LogicalType::Point => Type::POINT,
+        LogicalType::Box => Type::BOX,
         _ => {
             return Err(PgWireError::UserError(Box::new(ErrorInfo::new(
                 "ERROR".to_owned(),
@@ -4,6 +4,8 @@ use sqlparser::ast::{Expr, OrderByExpr};
 use std::collections::HashSet;
 
 use super::{Binder, QueryBindStep};
+use crate::binder::Source;
+use crate::catalog::{ColumnRelation, ColumnSummary};
 use crate::errors::DatabaseError;
 use crate::expression::function::scala::ScalarFunction;
 use crate::planner::LogicalPlan;
@@ -23,7 +25,13 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
     ) -> LogicalPlan {
         self.context.step(QueryBindStep::Agg);
 
-        AggregateOperator::build(children, agg_calls, groupby_exprs, false)
+        AggregateOperator::build(
+            children,
+            agg_calls,
+            groupby_exprs,
+            false,
+            self.context.aggregate_memory_limit,
+        )
     }
 
     pub fn extract_select_aggregate(
@@ -45,12 +53,20 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         for expr in groupby.iter() {
             group_by_exprs.push(self.bind_expr(expr)?);
         }
+        // the columns of the select list are already nullable as the joins make them
+        self.extract_select_join(&mut group_by_exprs);
 
-        self.validate_groupby_illegal_column(select_list, &group_by_exprs)?;
+        let dependent_exprs = self.validate_groupby_illegal_column(select_list, &group_by_exprs)?;
 
         for expr in group_by_exprs.iter_mut() {
             self.visit_group_by_expr(select_list, expr);
         }
+        // grouping by the columns determined by the GROUP BY clause keeps the groups as they are
+        for expr in dependent_exprs {
+            if !self.context.group_by_exprs.contains(&expr) {
+                self.context.group_by_exprs.push(expr);
+            }
+        }
         Ok(())
     }
 
@@ -210,15 +226,16 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
     }
 
     /// Validate select exprs must appear in the GROUP BY clause or be used in
-    /// an aggregate function.
+    /// an aggregate function, unless the GROUP BY clause determines them, which are returned.
     /// e.g. SELECT a,count(b) FROM t GROUP BY a. it's ok.
     ///      SELECT a,b FROM t GROUP BY a.        it's error.
     ///      SELECT a,count(b) FROM t GROUP BY b. it's error.
+    ///      SELECT id,b FROM t GROUP BY id.      it's ok, with `id` the primary key of t.
     fn validate_groupby_illegal_column(
         &mut self,
         select_items: &[ScalarExpression],
         groupby: &[ScalarExpression],
-    ) -> Result<(), DatabaseError> {
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
         let mut group_raw_exprs = vec![];
         for expr in groupby {
             if let ScalarExpression::Alias { alias, .. } = expr {
@@ -242,6 +259,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         }
         let mut group_raw_set: HashSet<&ScalarExpression, RandomState> =
             HashSet::from_iter(group_raw_exprs.iter().copied());
+        let mut dependent_exprs = Vec::new();
 
         for expr in select_items {
             if expr.has_agg_call() {
@@ -249,7 +267,12 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             }
             group_raw_set.remove(expr);
 
-            if !group_raw_exprs.iter().contains(&expr) {
+            if group_raw_exprs.iter().contains(&expr) {
+                continue;
+            }
+            if self.is_determined_by(expr, &group_raw_exprs) {
+                dependent_exprs.push(expr.clone());
+            } else {
                 return Err(DatabaseError::AggMiss(format!(
                     "`{}` must appear in the GROUP BY clause or be used in an aggregate function",
                     expr
@@ -263,7 +286,44 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             ));
         }
 
-        Ok(())
+        Ok(dependent_exprs)
+    }
+
+    /// Whether the columns of `expr` are functionally determined by the GROUP BY clause, which
+    /// holds all the primary keys of their tables.
+    fn is_determined_by(&self, expr: &ScalarExpression, groupby: &[&ScalarExpression]) -> bool {
+        let group_columns: HashSet<&ColumnSummary, RandomState> = groupby
+            .iter()
+            .filter_map(|expr| match expr.unpack_alias_ref() {
+                ScalarExpression::ColumnRef(column) => Some(column.summary()),
+                _ => None,
+            })
+            .collect();
+        let columns = expr.referenced_columns(true);
+
+        !columns.is_empty()
+            && columns.iter().all(|column| {
+                let ColumnRelation::Table { table_name, .. } = &column.summary().relation else {
+                    return false;
+                };
+                // a table bound twice has the same columns on both sides
+                let Ok(Source::Table(table)) = self
+                    .context
+                    .bind_table
+                    .iter()
+                    .filter(|((name, ..), _)| name == table_name)
+                    .map(|(_, source)| source)
+                    .exactly_one()
+                else {
+                    return false;
+                };
+
+                !table.primary_keys().is_empty()
+                    && table
+                        .primary_keys()
+                        .iter()
+                        .all(|(_, pk)| group_columns.contains(pk.summary()))
+            })
     }
 
     fn visit_group_by_expr(
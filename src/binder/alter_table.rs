@@ -3,13 +3,14 @@ use sqlparser::ast::{AlterTableOperation, ObjectName};
 use std::sync::Arc;
 
 use super::{is_valid_identifier, Binder};
-use crate::binder::lower_case_name;
+use crate::catalog::DELETED_AT_COLUMN;
 use crate::errors::DatabaseError;
 use crate::planner::operator::alter_table::add_column::AddColumnOperator;
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
 use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::tuple_codec::TupleCodecKind;
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 
@@ -19,18 +20,18 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         name: &ObjectName,
         operation: &AlterTableOperation,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name: Arc<String> = Arc::new(lower_case_name(name)?);
+        let table_name: Arc<String> = Arc::new(self.writable_table_name(name)?);
         let table = self
             .context
             .table(table_name.clone())?
-            .ok_or(DatabaseError::TableNotFound)?;
+            .cloned()
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
         let plan = match operation {
             AlterTableOperation::AddColumn {
                 column_keyword: _,
                 if_not_exists,
                 column_def,
             } => {
-                let plan = TableScanOperator::build(table_name.clone(), table, true);
                 let column = self.bind_column(column_def, None)?;
 
                 if !is_valid_identifier(column.name()) {
@@ -38,13 +39,34 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                         "illegal column naming".to_string(),
                     ));
                 }
+                if column.name() == DELETED_AT_COLUMN {
+                    return Err(DatabaseError::InvalidColumn(format!(
+                        "{DELETED_AT_COLUMN} is reserved for soft delete"
+                    )));
+                }
+                let desc = column.desc();
+                // the rows of a versioned table are decoded with the default of the new column,
+                // unless it is not a constant or has to be indexed as unique
+                let is_rewritten = !(*if_not_exists && table.contains_column(column.name()))
+                    && (table.tuple_codec == TupleCodecKind::Plain
+                        || desc.is_unique() && desc.default.is_some()
+                        || desc
+                            .default
+                            .as_ref()
+                            .is_some_and(|expr| expr.unpack_val().is_none()));
+                let childrens = if is_rewritten {
+                    Childrens::Only(TableScanOperator::build(table_name.clone(), &table, true))
+                } else {
+                    Childrens::None
+                };
+
                 LogicalPlan::new(
                     Operator::AddColumn(AddColumnOperator {
                         table_name,
                         if_not_exists: *if_not_exists,
                         column,
                     }),
-                    Childrens::Only(plan),
+                    childrens,
                 )
             }
             AlterTableOperation::DropColumn {
@@ -52,7 +74,12 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                 if_exists,
                 ..
             } => {
-                let plan = TableScanOperator::build(table_name.clone(), table, true);
+                // the rows of a versioned table skip the dropped column by their version
+                let childrens = if table.tuple_codec == TupleCodecKind::Plain {
+                    Childrens::Only(TableScanOperator::build(table_name.clone(), &table, true))
+                } else {
+                    Childrens::None
+                };
                 let column_name = column_name.value.clone();
 
                 LogicalPlan::new(
@@ -61,7 +88,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                         if_exists: *if_exists,
                         column_name,
                     }),
-                    Childrens::Only(plan),
+                    childrens,
                 )
             }
             op => {
@@ -75,3 +102,82 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         Ok(plan)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::{build_t1_table, TableState};
+    use crate::catalog::{AuditColumns, ColumnCatalog, ColumnDesc};
+    use crate::errors::DatabaseError;
+    use crate::planner::operator::Operator;
+    use crate::planner::Childrens;
+    use crate::storage::rocksdb::RocksStorage;
+    use crate::storage::tuple_codec::TupleCodecKind;
+    use crate::storage::{Storage, Transaction};
+    use crate::types::LogicalType::Integer;
+    use std::sync::Arc;
+
+    fn build_versioned_table() -> Result<TableState<RocksStorage>, DatabaseError> {
+        let table_state = build_t1_table()?;
+        let transaction = table_state.storage.transaction()?;
+        transaction.create_table(
+            &table_state.table_cache,
+            Arc::new("t3".to_string()),
+            vec![
+                ColumnCatalog::new(
+                    "c1".to_string(),
+                    false,
+                    ColumnDesc::new(Integer, Some(0), false, None)?,
+                ),
+                ColumnCatalog::new(
+                    "c2".to_string(),
+                    false,
+                    ColumnDesc::new(Integer, None, true, None)?,
+                ),
+            ],
+            TupleCodecKind::Versioned,
+            AuditColumns::default(),
+            false,
+        )?;
+        transaction.commit()?;
+
+        Ok(table_state)
+    }
+
+    #[test]
+    fn test_add_column_without_rewrite() -> Result<(), DatabaseError> {
+        let table_state = build_versioned_table()?;
+        let is_rewritten = |sql: &str| -> Result<bool, DatabaseError> {
+            let plan = table_state.plan(sql)?;
+            assert!(matches!(plan.operator, Operator::AddColumn(_)));
+
+            Ok(matches!(plan.childrens.as_ref(), Childrens::Only(_)))
+        };
+
+        assert!(is_rewritten("alter table t1 add column c3 int")?);
+        assert!(!is_rewritten("alter table t3 add column c3 int")?);
+        assert!(!is_rewritten(
+            "alter table t3 add column c3 int default 1 + 1"
+        )?);
+        assert!(!is_rewritten(
+            "alter table t3 add column if not exists c2 int default 1"
+        )?);
+        assert!(is_rewritten(
+            "alter table t3 add column c3 int unique default 1"
+        )?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_column_without_rewrite() -> Result<(), DatabaseError> {
+        let table_state = build_versioned_table()?;
+        let plan = table_state.plan("alter table t1 drop column c2")?;
+        assert!(matches!(plan.childrens.as_ref(), Childrens::Only(_)));
+
+        let plan = table_state.plan("alter table t3 drop column c2")?;
+        assert!(matches!(plan.operator, Operator::DropColumn(_)));
+        assert!(matches!(plan.childrens.as_ref(), Childrens::None));
+
+        Ok(())
+    }
+}
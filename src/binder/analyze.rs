@@ -1,4 +1,4 @@
-use crate::binder::{lower_case_name, Binder, Source};
+use crate::binder::{fold_ident, fold_name, Binder, Source};
 use crate::errors::DatabaseError;
 use crate::planner::operator::analyze::AnalyzeOperator;
 use crate::planner::operator::table_scan::TableScanOperator;
@@ -6,12 +6,22 @@ use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
-use sqlparser::ast::ObjectName;
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use sqlparser::ast::{Ident, ObjectName};
 use std::sync::Arc;
 
 impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
-    pub(crate) fn bind_analyze(&mut self, name: &ObjectName) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(name)?);
+    /// `ANALYZE TABLE t` rebuilds the statistics of every index,
+    /// `ANALYZE TABLE t FOR COLUMNS` only those of indexes modified since the last run,
+    /// and `ANALYZE TABLE t FOR COLUMNS a, b` those of indexes covering the given columns.
+    pub(crate) fn bind_analyze(
+        &mut self,
+        name: &ObjectName,
+        for_columns: bool,
+        columns: &[Ident],
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let table_name = Arc::new(fold_name(name)?);
 
         let table = self
             .context
@@ -23,14 +33,36 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                     None
                 }
             })
-            .ok_or(DatabaseError::TableNotFound)?;
-        let index_metas = table.indexes.clone();
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let mut index_metas = table.indexes.clone();
+
+        if !columns.is_empty() {
+            let column_ids = columns
+                .iter()
+                .map(|ident| {
+                    let column_name = fold_ident(ident);
+
+                    table
+                        .get_column_by_name(&column_name)
+                        .and_then(|column| column.id())
+                        .ok_or(DatabaseError::ColumnNotFound(column_name))
+                })
+                .try_collect::<_, Vec<_>, _>()?;
+            index_metas.retain(|index_meta| {
+                index_meta
+                    .column_ids
+                    .iter()
+                    .any(|column_id| column_ids.contains(column_id))
+            });
+        }
 
         let scan_op = TableScanOperator::build(table_name.clone(), table, false);
         Ok(LogicalPlan::new(
             Operator::Analyze(AnalyzeOperator {
                 table_name,
                 index_metas,
+                is_incremental: for_columns && columns.is_empty(),
+                sample_fraction: OrderedFloat(self.context.statistics_sample_fraction),
             }),
             Childrens::Only(scan_op),
         ))
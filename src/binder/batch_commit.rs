@@ -0,0 +1,304 @@
+use crate::binder::{fold_ident, fold_name};
+use crate::errors::DatabaseError;
+use crate::parser::{parse_sql_with_options, quote_identifier, ParserOptions};
+use crate::types::value::DataValue;
+use itertools::Itertools;
+use sqlparser::ast::{BinaryOperator, Expr, Ident, Statement, TableFactor, TableWithJoins, Value};
+use std::borrow::Cow;
+
+pub(crate) const BATCH_COMMIT: &str = "batch_commit";
+
+/// `UPDATE t WITH (batch_commit = n) ...` and `DELETE FROM t WITH (batch_commit = n) ...`, which
+/// are run as transactions of `n` rows each in the order of the primary key.
+///
+/// Each batch first reads the primary keys of its rows, then runs the statement over the rows
+/// from the last key of the batch before, up to its own last key, which it commits with the rows.
+/// The statement run again after a failure starts after the last batch committed.
+#[derive(Debug)]
+pub(crate) struct BatchCommit {
+    pub(crate) size: usize,
+    // the statement without the hint
+    statement: Statement,
+}
+
+fn is_batch_commit_hint(hint: &Expr) -> bool {
+    matches!(hint, Expr::BinaryOp { left, op: BinaryOperator::Eq, .. } if matches!(left.as_ref(), Expr::Identifier(ident) if ident.value.eq_ignore_ascii_case(BATCH_COMMIT)))
+}
+
+/// The hint left to the binder is of a statement that cannot commit, e.g. in a transaction.
+pub(crate) fn check_batch_commit_hints(hints: &[Expr]) -> Result<(), DatabaseError> {
+    if hints.iter().any(is_batch_commit_hint) {
+        return Err(DatabaseError::UnsupportedStmt(format!(
+            "{BATCH_COMMIT} outside of the UPDATE and DELETE run by `Database::run`"
+        )));
+    }
+    Ok(())
+}
+
+fn table(statement: &Statement) -> Option<&TableWithJoins> {
+    match statement {
+        Statement::Update { table, .. } => Some(table),
+        Statement::Delete { from, .. } => from.first(),
+        _ => None,
+    }
+}
+
+fn table_mut(statement: &mut Statement) -> Option<&mut TableWithJoins> {
+    match statement {
+        Statement::Update { table, .. } => Some(table),
+        Statement::Delete { from, .. } => from.first_mut(),
+        _ => None,
+    }
+}
+
+fn selection_mut(statement: &mut Statement) -> Option<&mut Option<Expr>> {
+    match statement {
+        Statement::Update { selection, .. } | Statement::Delete { selection, .. } => {
+            Some(selection)
+        }
+        _ => None,
+    }
+}
+
+impl BatchCommit {
+    pub(crate) fn from_statement(statement: &Statement) -> Result<Option<Self>, DatabaseError> {
+        let is_batched = table(statement).is_some_and(|table| {
+            matches!(&table.relation, TableFactor::Table { with_hints, .. } if with_hints.iter().any(is_batch_commit_hint))
+        });
+        if !is_batched {
+            return Ok(None);
+        }
+        let mut statement = statement.clone();
+        let table = table_mut(&mut statement).unwrap();
+        if !table.joins.is_empty() {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "{BATCH_COMMIT} with joins"
+            )));
+        }
+        let TableFactor::Table { with_hints, .. } = &mut table.relation else {
+            unreachable!()
+        };
+        let i = with_hints.iter().position(is_batch_commit_hint).unwrap();
+        let hint = with_hints.remove(i);
+        let size = match &hint {
+            Expr::BinaryOp { right, .. } => match right.as_ref() {
+                Expr::Value(Value::Number(size, _)) => size.parse::<usize>().ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+        .filter(|size| *size > 0)
+        .ok_or_else(|| DatabaseError::InvalidValue(format!("table hint: {hint}")))?;
+
+        Ok(Some(BatchCommit { size, statement }))
+    }
+
+    pub(crate) fn table_name(&self) -> Result<String, DatabaseError> {
+        let TableFactor::Table { name, .. } = &table(&self.statement).unwrap().relation else {
+            unreachable!()
+        };
+        fold_name(name)
+    }
+
+    /// The batches are ranges of the primary key, which the rows must not leave.
+    pub(crate) fn check(&self, primary_keys: &[String]) -> Result<(), DatabaseError> {
+        if let Statement::Update { assignments, .. } = &self.statement {
+            for assignment in assignments {
+                if let Some(column) = assignment.id.last() {
+                    if primary_keys.contains(&fold_ident(column)) {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "{BATCH_COMMIT} updating the primary key {column}"
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `SELECT <primary key> FROM t WHERE ... ORDER BY <primary key> LIMIT <size>`, the rows of the
+    /// next batch, parsed by the options the statement was.
+    pub(crate) fn next_batch_query(
+        &self,
+        primary_keys: &[String],
+        after: Option<&[DataValue]>,
+        options: ParserOptions,
+    ) -> Result<Statement, DatabaseError> {
+        let quote = options.dialect.quote_style();
+        let mut statement = self.statement.clone();
+        let selection = selection_mut(&mut statement).unwrap().take();
+        let predicates = selection
+            .map(|selection| Expr::Nested(Box::new(selection)))
+            .into_iter()
+            .chain(after.map(|after| key_range(primary_keys, after, BinaryOperator::Gt, quote)))
+            .reduce(|left, right| binary_op(left, BinaryOperator::And, right))
+            .map(|predicate| format!(" WHERE {predicate}"))
+            .unwrap_or_default();
+        let columns = primary_keys
+            .iter()
+            .map(|column| ident(column, quote))
+            .join(", ");
+        let sql = format!(
+            "SELECT {columns} FROM {}{predicates} ORDER BY {columns} LIMIT {}",
+            table(&statement).unwrap().relation,
+            self.size
+        );
+
+        parse_sql_with_options(sql, options)?
+            .pop()
+            .ok_or(DatabaseError::EmptyStatement)
+    }
+
+    /// The statement over the rows of the keys in `(after, last]`, quoting the keys as the
+    /// dialect of `options` does.
+    pub(crate) fn batch_statement(
+        &self,
+        primary_keys: &[String],
+        after: Option<&[DataValue]>,
+        last: &[DataValue],
+        options: ParserOptions,
+    ) -> Statement {
+        let quote = options.dialect.quote_style();
+        let mut statement = self.statement.clone();
+        let selection = selection_mut(&mut statement).unwrap();
+        let predicates = selection
+            .take()
+            .map(|selection| Expr::Nested(Box::new(selection)))
+            .into_iter()
+            .chain(after.map(|after| key_range(primary_keys, after, BinaryOperator::Gt, quote)))
+            .chain([key_range(primary_keys, last, BinaryOperator::LtEq, quote)]);
+        *selection = predicates.reduce(|left, right| binary_op(left, BinaryOperator::And, right));
+
+        statement
+    }
+}
+
+fn binary_op(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+/// The column as an identifier, quoted by `quote` where [`quote_identifier`] quotes it.
+fn ident(column: &str, quote: char) -> Ident {
+    match quote_identifier(column) {
+        Cow::Borrowed(_) => Ident::new(column),
+        Cow::Owned(_) => Ident::with_quote(quote, column),
+    }
+}
+
+fn literal(value: &DataValue) -> Expr {
+    match value {
+        DataValue::Utf8 { value, .. } => Expr::Value(Value::SingleQuotedString(value.clone())),
+        value => Expr::Value(Value::Number(value.to_string(), false)),
+    }
+}
+
+/// The keys after `values` with `>`, or up to them with `<=`, compared column by column.
+fn key_range(columns: &[String], values: &[DataValue], op: BinaryOperator, quote: char) -> Expr {
+    let strict_op = match op {
+        BinaryOperator::LtEq => BinaryOperator::Lt,
+        ref op => op.clone(),
+    };
+    let ranges = (0..columns.len()).map(|i| {
+        let predicates = (0..=i).map(|j| {
+            let op = if j < i {
+                BinaryOperator::Eq
+            } else if i + 1 < columns.len() {
+                strict_op.clone()
+            } else {
+                op.clone()
+            };
+            binary_op(
+                Expr::Identifier(ident(&columns[j], quote)),
+                op,
+                literal(&values[j]),
+            )
+        });
+        predicates
+            .reduce(|left, right| binary_op(left, BinaryOperator::And, right))
+            .unwrap()
+    });
+
+    Expr::Nested(Box::new(
+        ranges
+            .reduce(|left, right| binary_op(left, BinaryOperator::Or, right))
+            .unwrap(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::batch_commit::BatchCommit;
+    use crate::errors::DatabaseError;
+    use crate::parser::{parse_sql, ParserOptions, SqlDialect};
+    use crate::types::value::{DataValue, Utf8Type};
+    use sqlparser::ast::CharLengthUnits;
+
+    #[test]
+    fn test_batch_commit_statements() -> Result<(), DatabaseError> {
+        let statement =
+            parse_sql("update t1 with (batch_commit = 100) set c = 1 where c > 0")?.remove(0);
+        let batch_commit = BatchCommit::from_statement(&statement)?.unwrap();
+        let primary_keys = vec!["a".to_string(), "b".to_string()];
+        let after = [
+            DataValue::Int32(1),
+            DataValue::Utf8 {
+                value: "x".to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            },
+        ];
+        let last = [DataValue::Int32(3), after[1].clone()];
+
+        assert_eq!(batch_commit.size, 100);
+        assert_eq!(batch_commit.table_name()?, "t1");
+        assert_eq!(
+            batch_commit
+                .next_batch_query(&primary_keys, Some(&after), ParserOptions::default())?
+                .to_string(),
+            "SELECT a, b FROM t1 WHERE (c > 0) AND (a > 1 OR a = 1 AND b > 'x') ORDER BY a, b LIMIT 100"
+        );
+        assert_eq!(
+            batch_commit
+                .batch_statement(&primary_keys, None, &last, ParserOptions::default())
+                .to_string(),
+            "UPDATE t1 SET c = 1 WHERE (c > 0) AND (a < 3 OR a = 3 AND b <= 'x')"
+        );
+        // the keys named by reserved keywords or in upper case are quoted to be read back
+        let primary_keys = vec!["order".to_string(), "Id".to_string()];
+        assert_eq!(
+            batch_commit
+                .next_batch_query(&primary_keys, None, ParserOptions::default())?
+                .to_string(),
+            r#"SELECT "order", "Id" FROM t1 WHERE (c > 0) ORDER BY "order", "Id" LIMIT 100"#
+        );
+        // both statements of a batch quote the keys by the dialect
+        let options = ParserOptions {
+            dialect: SqlDialect::MySql,
+            ..Default::default()
+        };
+        assert_eq!(
+            batch_commit
+                .next_batch_query(&primary_keys, None, options)?
+                .to_string(),
+            "SELECT `order`, `Id` FROM t1 WHERE (c > 0) ORDER BY `order`, `Id` LIMIT 100"
+        );
+        assert_eq!(
+            batch_commit
+                .batch_statement(&primary_keys, Some(&after), &last, options)
+                .to_string(),
+            "UPDATE t1 SET c = 1 WHERE (c > 0) AND (`order` > 1 OR `order` = 1 AND `Id` > 'x') AND (`order` < 3 OR `order` = 3 AND `Id` <= 'x')"
+        );
+        assert!(batch_commit.check(&["c".to_string()]).is_err());
+        assert!(BatchCommit::from_statement(&parse_sql("delete from t1")?.remove(0))?.is_none());
+        assert!(BatchCommit::from_statement(
+            &parse_sql("delete from t1 with (batch_commit = 0)")?.remove(0)
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
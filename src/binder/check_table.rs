@@ -0,0 +1,47 @@
+use crate::binder::{fold_name, Binder, Source};
+use crate::errors::DatabaseError;
+use crate::planner::operator::check_table::CheckTableOperator;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::Transaction;
+use crate::types::index::IndexType;
+use crate::types::value::DataValue;
+use sqlparser::ast::ObjectName;
+use std::sync::Arc;
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    pub(crate) fn bind_check_table(
+        &mut self,
+        name: &ObjectName,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let table_name = Arc::new(fold_name(name)?);
+
+        let table = self
+            .context
+            .source_and_bind(table_name.clone(), None, None, true)?
+            .and_then(|source| {
+                if let Source::Table(table) = source {
+                    Some(table)
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let index_metas = table
+            .indexes
+            .iter()
+            .filter(|index_meta| !matches!(index_meta.ty, IndexType::PrimaryKey { .. }))
+            .cloned()
+            .collect();
+
+        let scan_op = TableScanOperator::build(table_name.clone(), table, true);
+        Ok(LogicalPlan::new(
+            Operator::CheckTable(CheckTableOperator {
+                table_name,
+                index_metas,
+            }),
+            Childrens::Only(scan_op),
+        ))
+    }
+}
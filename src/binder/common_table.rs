@@ -0,0 +1,380 @@
+use crate::binder::{fold_ident, Binder, Source};
+use crate::errors::DatabaseError;
+use crate::planner::operator::join::JoinType;
+use crate::planner::operator::recursive_union::{
+    RecursiveUnionOperator, WorkTable, WorkTableScanOperator,
+};
+use crate::planner::operator::shared_scan::{SharedScanOperator, SharedTuples};
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use itertools::Itertools;
+use sqlparser::ast::{Ident, Query, SetExpr, SetOperator, SetQuantifier, TableAlias, With};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+// `AS [NOT] MATERIALIZED` and `CYCLE column, ...`, as carried by the parser in the `FROM` of the
+// common table expression
+const MATERIALIZED: &str = "materialized";
+const NOT_MATERIALIZED: &str = "not_materialized";
+const CYCLE: &str = "cycle=";
+
+/// A common table expression of a `WITH` clause, bound as a derived table at each of its
+/// references.
+#[derive(Debug)]
+pub(crate) struct CommonTable {
+    name: String,
+    columns: Vec<Ident>,
+    body: CommonTableBody,
+    // the common tables its query sees: those of the outer queries and those listed before it
+    scope: Vec<Arc<CommonTable>>,
+    // the rows of `AS MATERIALIZED`, read by the first of its references for all of them
+    materialized: Option<(usize, SharedTuples)>,
+    // of `WITH RECURSIVE`, its query may read itself in the second term of a `UNION`
+    recursive: bool,
+    cycle: Vec<String>,
+}
+
+#[derive(Debug)]
+enum CommonTableBody {
+    Query(Query),
+    // the table read by the recursive term of its query, the rows of the previous iteration
+    WorkTable(WorkTableScanOperator),
+}
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    pub(crate) fn bind_with(&mut self, with: &With) -> Result<(), DatabaseError> {
+        for cte in with.cte_tables.iter() {
+            let mut materialized = None;
+            let mut cycle = Vec::new();
+
+            for option in cte.from.iter().map(fold_ident) {
+                for option in option.split_whitespace() {
+                    match option {
+                        MATERIALIZED => {
+                            materialized = Some((
+                                self.context.temp_table_id.fetch_add(1, Ordering::SeqCst),
+                                SharedTuples::default(),
+                            ))
+                        }
+                        NOT_MATERIALIZED => materialized = None,
+                        option if option.starts_with(CYCLE) => {
+                            cycle = option[CYCLE.len()..].split(',').map(String::from).collect()
+                        }
+                        option => {
+                            return Err(DatabaseError::UnsupportedStmt(format!(
+                                "FROM {} of the common table expression {}",
+                                option, cte.alias.name
+                            )))
+                        }
+                    }
+                }
+            }
+            let common_table = CommonTable {
+                name: fold_ident(&cte.alias.name),
+                columns: cte.alias.columns.clone(),
+                body: CommonTableBody::Query(Query::clone(&cte.query)),
+                scope: self.common_tables_in_scope(),
+                materialized,
+                recursive: with.recursive,
+                cycle,
+            };
+            self.common_tables.push(Arc::new(common_table));
+        }
+        Ok(())
+    }
+
+    /// The common table named so by the `WITH` clause of this query or of the outer ones, the
+    /// innermost first.
+    pub(crate) fn common_table(&self, name: &str) -> Option<Arc<CommonTable>> {
+        self.common_tables
+            .iter()
+            .rev()
+            .find(|common_table| common_table.name == name)
+            .cloned()
+            .or_else(|| self.parent.and_then(|parent| parent.common_table(name)))
+    }
+
+    fn common_tables_in_scope(&self) -> Vec<Arc<CommonTable>> {
+        let mut common_tables = self
+            .parent
+            .map(|parent| parent.common_tables_in_scope())
+            .unwrap_or_default();
+        common_tables.extend(self.common_tables.iter().cloned());
+        common_tables
+    }
+
+    /// A reference to a common table, whose query is bound again for it unless the table is
+    /// `MATERIALIZED`: then all of its references read the rows kept by a shared scan.
+    pub(crate) fn bind_common_table(
+        &mut self,
+        common_table: &CommonTable,
+        alias: Option<&TableAlias>,
+        join_type: Option<JoinType>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let mut plan = match &common_table.body {
+            CommonTableBody::Query(query) => {
+                match self.bind_recursive_query(common_table, query)? {
+                    Some(plan) => plan,
+                    None if !common_table.cycle.is_empty() => {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "CYCLE of the common table expression {}, which does not read itself",
+                            common_table.name
+                        )))
+                    }
+                    None => {
+                        let mut binder = Binder::new(self.context.child(), self.args, None);
+                        binder.common_tables = common_table.scope.clone();
+                        binder.bind_query(query)?
+                    }
+                }
+            }
+            CommonTableBody::WorkTable(op) => {
+                LogicalPlan::new(Operator::WorkTableScan(op.clone()), Childrens::None)
+            }
+        };
+
+        if let Some((id, tuples)) = &common_table.materialized {
+            plan = LogicalPlan::new(
+                Operator::SharedScan(SharedScanOperator {
+                    id: *id,
+                    tuples: tuples.clone(),
+                }),
+                Childrens::Only(plan),
+            );
+        }
+        let table_alias =
+            Arc::new(alias.map_or(common_table.name.clone(), |alias| fold_ident(&alias.name)));
+        let alias_column = match alias {
+            Some(alias) if !alias.columns.is_empty() => &alias.columns,
+            _ => &common_table.columns,
+        };
+        let table_name = Arc::new(common_table.name.clone());
+        let mut plan =
+            self.bind_alias(plan, alias_column, table_alias.clone(), table_name.clone())?;
+        // its columns are found by their aliases, as those of a table are by its name
+        self.context.bind_table.insert(
+            (table_name, Some(table_alias), join_type),
+            Source::CommonTable(plan.output_schema().clone()),
+        );
+
+        Ok(plan)
+    }
+
+    /// The query `anchor UNION [ALL] recursive` of a common table of `WITH RECURSIVE` whose
+    /// recursive term reads the table, as the rows of the previous iteration, or `None` for the
+    /// queries of the other common tables.
+    fn bind_recursive_query(
+        &mut self,
+        common_table: &CommonTable,
+        query: &Query,
+    ) -> Result<Option<LogicalPlan>, DatabaseError> {
+        let SetExpr::SetOperation {
+            op: SetOperator::Union,
+            set_quantifier,
+            left,
+            right,
+        } = query.body.as_ref()
+        else {
+            return Ok(None);
+        };
+        if !common_table.recursive
+            || !query.order_by.is_empty()
+            || query.limit.is_some()
+            || query.offset.is_some()
+        {
+            return Ok(None);
+        }
+        let mut binder = Binder::new(self.context.child(), self.args, None);
+        binder.common_tables = common_table.scope.clone();
+        let mut anchor = binder.bind_set_expr(left)?;
+        let schema_ref = anchor.output_schema().clone();
+
+        let id = self.context.temp_table_id.fetch_add(1, Ordering::SeqCst);
+        let work_table = WorkTable::default();
+        let work_table_scan = CommonTable {
+            name: common_table.name.clone(),
+            columns: common_table.columns.clone(),
+            body: CommonTableBody::WorkTable(WorkTableScanOperator {
+                id,
+                name: common_table.name.clone(),
+                schema_ref: schema_ref.clone(),
+                work_table: work_table.clone(),
+            }),
+            scope: vec![],
+            materialized: None,
+            recursive: false,
+            cycle: vec![],
+        };
+        let mut binder = Binder::new(self.context.child(), self.args, None);
+        binder.common_tables = common_table.scope.clone();
+        binder.common_tables.push(Arc::new(work_table_scan));
+        let mut recursive = binder.bind_set_expr(right)?;
+
+        if !reads_work_table(&recursive, id) {
+            return Ok(None);
+        }
+        let recursive_schema = recursive.output_schema();
+
+        if schema_ref.len() != recursive_schema.len()
+            || schema_ref
+                .iter()
+                .zip(recursive_schema.iter())
+                .any(|(left, right)| left.datatype() != right.datatype())
+        {
+            return Err(DatabaseError::MisMatch(
+                "the output types of the anchor",
+                "the output types of the recursive term",
+            ));
+        }
+        let names = if common_table.columns.is_empty() {
+            schema_ref
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect_vec()
+        } else {
+            common_table.columns.iter().map(fold_ident).collect_vec()
+        };
+        let cycle = common_table
+            .cycle
+            .iter()
+            .map(|column| {
+                names
+                    .iter()
+                    .position(|name| name == column)
+                    .ok_or_else(|| DatabaseError::ColumnNotFound(column.clone()))
+            })
+            .try_collect()?;
+
+        Ok(Some(LogicalPlan::new(
+            Operator::RecursiveUnion(RecursiveUnionOperator {
+                id,
+                name: common_table.name.clone(),
+                schema_ref,
+                is_all: matches!(set_quantifier, SetQuantifier::All),
+                cycle,
+                limits: self.context.recursion_limits,
+                work_table,
+            }),
+            Childrens::Twins {
+                left: anchor,
+                right: recursive,
+            },
+        )))
+    }
+}
+
+fn reads_work_table(plan: &LogicalPlan, id: usize) -> bool {
+    matches!(&plan.operator, Operator::WorkTableScan(op) if op.id == id)
+        || plan
+            .childrens
+            .iter()
+            .any(|child| reads_work_table(child, id))
+}
+
+/// Gives the shared scans and the recursive queries of a plan kept by a view rows of their own,
+/// which are read again by each statement reading the view.
+pub(crate) fn renew_shared_rows(plan: &mut LogicalPlan) {
+    fn renew(
+        plan: &mut LogicalPlan,
+        tuples: &mut HashMap<usize, SharedTuples>,
+        work_tables: &mut HashMap<usize, WorkTable>,
+    ) {
+        match &mut plan.operator {
+            Operator::SharedScan(op) => op.tuples = tuples.entry(op.id).or_default().clone(),
+            Operator::RecursiveUnion(RecursiveUnionOperator { id, work_table, .. })
+            | Operator::WorkTableScan(WorkTableScanOperator { id, work_table, .. }) => {
+                *work_table = work_tables.entry(*id).or_default().clone()
+            }
+            _ => (),
+        }
+        match plan.childrens.as_mut() {
+            Childrens::Only(child) => renew(child, tuples, work_tables),
+            Childrens::Twins { left, right } => {
+                renew(left, tuples, work_tables);
+                renew(right, tuples, work_tables);
+            }
+            Childrens::None => (),
+        }
+    }
+    renew(plan, &mut HashMap::new(), &mut HashMap::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::build_t1_table;
+    use crate::errors::DatabaseError;
+    use crate::planner::operator::shared_scan::SharedTuples;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+
+    fn shared_tuples(plan: &LogicalPlan) -> Vec<SharedTuples> {
+        let mut tuples = match &plan.operator {
+            Operator::SharedScan(op) => vec![op.tuples.clone()],
+            _ => vec![],
+        };
+        for child in plan.childrens.iter() {
+            tuples.append(&mut shared_tuples(child));
+        }
+        tuples
+    }
+
+    fn find_operator<'a>(
+        plan: &'a LogicalPlan,
+        predicate: &dyn Fn(&Operator) -> bool,
+    ) -> Option<&'a Operator> {
+        if predicate(&plan.operator) {
+            return Some(&plan.operator);
+        }
+        plan.childrens
+            .iter()
+            .find_map(|child| find_operator(child, predicate))
+    }
+
+    #[test]
+    fn test_bind_common_table() -> Result<(), DatabaseError> {
+        let table_states = build_t1_table()?;
+
+        let plan = table_states.plan(
+            "with c(a, b) as materialized (select c1, c2 from t1 where c1 > 1) select x.a, y.b from c x join c y on x.a = y.b",
+        )?;
+        let tuples = shared_tuples(&plan);
+        assert_eq!(tuples.len(), 2);
+        assert_eq!(tuples[0], tuples[1]);
+
+        let plan = table_states.plan(
+            "with c(a, b) as not materialized (select c1, c2 from t1) select x.a, y.b from c x join c y on x.a = y.b",
+        )?;
+        assert!(shared_tuples(&plan).is_empty());
+
+        let plan = table_states.plan(
+            "with c as (select c1 from t1), d as (select c1 from c where c1 > 1) select c1 from d",
+        )?;
+        assert_eq!(plan.referenced_table().len(), 1);
+        assert!(table_states
+            .plan("with c as (select c1 from c) select c1 from c")
+            .is_err());
+
+        let plan = table_states.plan(
+            "with recursive r(n) as (select c1 from t1 union all select n + 1 from r where n < 10) cycle n select n from r",
+        )?;
+        let Some(Operator::RecursiveUnion(op)) = find_operator(&plan, &|operator| {
+            matches!(operator, Operator::RecursiveUnion(_))
+        }) else {
+            unreachable!()
+        };
+        assert!(op.is_all);
+        assert_eq!(op.cycle, vec![0]);
+        assert!(matches!(
+            find_operator(&plan, &|operator| matches!(operator, Operator::WorkTableScan(_))),
+            Some(Operator::WorkTableScan(scan)) if scan.work_table == op.work_table
+        ));
+        assert!(table_states
+            .plan("with recursive r(n) as (select c1 from t1 union all select c3 from t2) cycle n select n from r")
+            .is_err());
+
+        Ok(())
+    }
+}
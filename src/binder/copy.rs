@@ -1,6 +1,8 @@
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::thread;
 
 use super::*;
 use crate::errors::DatabaseError;
@@ -9,9 +11,10 @@ use crate::planner::operator::copy_to_file::CopyToFileOperator;
 use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::operator::Operator;
 use crate::planner::Childrens;
+use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
 use serde::{Deserialize, Serialize};
-use sqlparser::ast::{CopyOption, CopySource, CopyTarget};
+use sqlparser::ast::{CopyOption, CopySource, CopyTarget, Ident};
 
 #[derive(Debug, PartialEq, PartialOrd, Ord, Hash, Eq, Clone, ReferenceSerialization)]
 pub struct ExtSource {
@@ -42,7 +45,69 @@ pub enum FileFormat {
         escape: Option<char>,
         /// Whether or not the file has a header line.
         header: bool,
+        /// The field of a NULL, `NULL 'string'` among the options, read and written as the
+        /// text of the values without it.
+        null: Option<String>,
     },
+    /// Apache Parquet, which `COPY ... TO` writes with the feature `parquet`.
+    Parquet {
+        /// Codec of the pages of the columns.
+        compression: Compression,
+    },
+}
+
+/// The codec of the files written by `COPY ... TO`, `COMPRESSION codec` among its options.
+#[derive(
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Eq,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ReferenceSerialization,
+)]
+pub enum Compression {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "none" | "uncompressed" => Some(Compression::Uncompressed),
+            "snappy" => Some(Compression::Snappy),
+            "gzip" => Some(Compression::Gzip),
+            "zstd" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// How `COPY FROM` loads the rows of a file, set by `DataBaseBuilder::copy_threads` and
+/// `DataBaseBuilder::copy_defer_indexes`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, ReferenceSerialization)]
+pub struct CopyLoadOptions {
+    /// Threads casting the values of the rows and encoding them, which are written in the order
+    /// of the file by the statement itself
+    pub threads: usize,
+    /// Whether the indexes are rebuilt from the table after the rows are written instead of
+    /// having the entries of each row written with it
+    pub defer_indexes: bool,
+}
+
+impl Default for CopyLoadOptions {
+    fn default() -> Self {
+        CopyLoadOptions {
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            defer_indexes: false,
+        }
+    }
 }
 
 impl std::fmt::Display for ExtSource {
@@ -72,82 +137,171 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         target: CopyTarget,
         options: &[CopyOption],
     ) -> Result<LogicalPlan, DatabaseError> {
-        let (table_name, ..) = match source {
-            CopySource::Table {
-                table_name,
-                columns,
-            } => (table_name, columns),
-            CopySource::Query(_) => {
-                return Err(DatabaseError::UnsupportedStmt("'COPY SOURCE'".to_string()));
+        let ext_source = ExtSource {
+            path: match target {
+                CopyTarget::File { filename } => filename.into(),
+                t => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "copy target: {:?}",
+                        t
+                    )))
+                }
+            },
+            format: FileFormat::from_options(options)?,
+        };
+        // `PARTITION BY (columns)` is carried as `FORCE_NULL (columns)`, see `parse_sql`
+        let partition_by = options
+            .iter()
+            .filter_map(|option| match option {
+                CopyOption::ForceNull(columns) => Some(columns),
+                _ => None,
+            })
+            .flatten()
+            .collect_vec();
+
+        let table_name = match source {
+            CopySource::Table { table_name, .. } => table_name,
+            CopySource::Query(query) => {
+                if !to {
+                    return Err(DatabaseError::UnsupportedStmt("'COPY SOURCE'".to_string()));
+                }
+                // COPY (<query>) TO <dest_file>
+                let input = self.bind_query(&query)?;
+                return Self::bind_copy_to(ext_source, input, &partition_by);
             }
         };
-        let table_name = Arc::new(lower_case_name(&table_name)?);
+        let table_name = Arc::new(self.writable_table_name(&table_name)?);
+        let masking = self.context.masking;
 
         if let Some(table) = self.context.table(table_name.clone())? {
-            let schema_ref = table.schema_ref().clone();
-            let ext_source = ExtSource {
-                path: match target {
-                    CopyTarget::File { filename } => filename.into(),
-                    t => {
-                        return Err(DatabaseError::UnsupportedStmt(format!(
-                            "copy target: {:?}",
-                            t
-                        )))
-                    }
-                },
-                format: FileFormat::from_options(options),
-            };
-
             if to {
                 // COPY <source_table> TO <dest_file>
-                Ok(LogicalPlan::new(
-                    Operator::CopyToFile(CopyToFileOperator {
-                        target: ext_source,
-                        schema_ref,
-                    }),
-                    Childrens::Only(TableScanOperator::build(table_name, table, false)),
-                ))
+                let input = TableScanOperator::build(table_name, table, false);
+                let input = Self::mask_columns(input, table, masking)?;
+                Self::bind_copy_to(ext_source, input, &partition_by)
             } else {
                 // COPY <dest_table> FROM <source_file>
+                if !matches!(ext_source.format, FileFormat::Csv { .. }) || !partition_by.is_empty()
+                {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "COPY FROM of a file other than CSV, or with PARTITION BY".to_string(),
+                    ));
+                }
+                table.check_writable()?;
                 Ok(LogicalPlan::new(
                     Operator::CopyFromFile(CopyFromFileOperator {
                         source: ext_source,
-                        schema_ref,
+                        schema_ref: table.schema_ref().clone(),
                         table: table_name,
+                        load: self.context.copy_load_options,
                     }),
                     Childrens::None,
                 ))
             }
         } else {
-            Err(DatabaseError::TableNotFound)
+            Err(DatabaseError::TableNotFound(table_name.to_string()))
         }
     }
+
+    /// `COPY ... TO`, the rows of a partition, by the values of the columns of `PARTITION BY`,
+    /// are written to a file of its own under the target directory, see [`CopyToFileOperator`].
+    fn bind_copy_to(
+        target: ExtSource,
+        mut input: LogicalPlan,
+        partition_by: &[&Ident],
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let schema_ref = input.output_schema().clone();
+        let partition_by = partition_by
+            .iter()
+            .map(|ident| {
+                let name = fold_ident(ident);
+
+                schema_ref
+                    .iter()
+                    .position(|column| column.name() == name)
+                    .ok_or(DatabaseError::ColumnNotFound(name))
+            })
+            .try_collect::<_, Vec<_>, _>()?;
+
+        if !partition_by.is_empty() && partition_by.iter().unique().count() == schema_ref.len() {
+            return Err(DatabaseError::MisMatch(
+                "columns out of PARTITION BY",
+                "the files of COPY TO",
+            ));
+        }
+        Ok(LogicalPlan::new(
+            Operator::CopyToFile(CopyToFileOperator {
+                target,
+                schema_ref,
+                partition_by,
+            }),
+            Childrens::Only(input),
+        ))
+    }
 }
 
 impl FileFormat {
-    /// Create from copy options.
-    pub fn from_options(options: &[CopyOption]) -> Self {
+    /// Create from copy options, `COMPRESSION codec` is carried as `FORCE_NOT_NULL (codec)`.
+    pub fn from_options(options: &[CopyOption]) -> Result<Self, DatabaseError> {
+        let mut format = "csv".to_string();
         let mut delimiter = ',';
         let mut quote = '"';
         let mut escape = None;
         let mut header = false;
+        let mut null = None;
+        let mut compression = None;
         for opt in options {
             match opt {
-                CopyOption::Format(fmt) => {
-                    debug_assert_eq!(fmt.value.to_lowercase(), "csv", "only support CSV format")
-                }
+                CopyOption::Format(fmt) => format = fmt.value.to_lowercase(),
                 CopyOption::Delimiter(c) => delimiter = *c,
                 CopyOption::Header(b) => header = *b,
                 CopyOption::Quote(c) => quote = *c,
                 CopyOption::Escape(c) => escape = Some(*c),
-                o => panic!("unsupported copy option: {:?}", o),
+                CopyOption::Null(s) => null = Some(s.clone()),
+                CopyOption::ForceNotNull(codec) => {
+                    let [codec] = codec.as_slice() else {
+                        return Err(DatabaseError::MisMatch("one codec", "COMPRESSION"));
+                    };
+                    compression = Some(Compression::from_name(&codec.value).ok_or_else(|| {
+                        DatabaseError::InvalidValue(format!("COMPRESSION {}", codec.value))
+                    })?);
+                }
+                CopyOption::ForceNull(_) => (),
+                o => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "copy option: {:?}",
+                        o
+                    )))
+                }
+            }
+        }
+        match format.as_str() {
+            "csv" if matches!(compression, None | Some(Compression::Uncompressed)) => {
+                Ok(FileFormat::Csv {
+                    delimiter,
+                    quote,
+                    escape,
+                    header,
+                    null,
+                })
             }
+            "parquet" if cfg!(feature = "parquet") => Ok(FileFormat::Parquet {
+                compression: compression.unwrap_or(Compression::Snappy),
+            }),
+            "csv" => Err(DatabaseError::UnsupportedStmt(
+                "COMPRESSION of FORMAT csv".to_string(),
+            )),
+            "parquet" => Err(DatabaseError::UnsupportedStmt(
+                "FORMAT parquet without the feature `parquet`".to_string(),
+            )),
+            format => Err(DatabaseError::UnsupportedStmt(format!("FORMAT {format}"))),
         }
-        FileFormat::Csv {
-            delimiter,
-            quote,
-            escape,
-            header,
+    }
+
+    pub(crate) fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Csv { .. } => "csv",
+            FileFormat::Parquet { .. } => "parquet",
         }
     }
 }
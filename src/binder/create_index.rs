@@ -1,4 +1,4 @@
-use crate::binder::{lower_case_name, Binder, Source};
+use crate::binder::{fold_name, Binder, Source};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::create_index::CreateIndexOperator;
@@ -8,7 +8,8 @@ use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::Transaction;
 use crate::types::index::IndexType;
 use crate::types::value::DataValue;
-use sqlparser::ast::{ObjectName, OrderByExpr};
+use crate::types::LogicalType;
+use sqlparser::ast::{Ident, ObjectName, OrderByExpr};
 use std::sync::Arc;
 
 impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
@@ -19,10 +20,26 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         exprs: &[OrderByExpr],
         if_not_exists: bool,
         is_unique: bool,
+        using: Option<&Ident>,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(table_name)?);
-        let index_name = lower_case_name(name)?;
-        let ty = if is_unique {
+        let table_name = Arc::new(self.writable_table_name(table_name)?);
+        let index_name = fold_name(name)?;
+        let using_ty = match using.map(|using| using.value.to_lowercase()).as_deref() {
+            Some("fulltext") => Some(IndexType::FullText),
+            Some("trigram") => Some(IndexType::Trigram),
+            Some("ivfflat") => Some(IndexType::Vector),
+            Some("zorder") => Some(IndexType::ZOrder),
+            Some(using) => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "'CREATE INDEX' using {}",
+                    using
+                )))
+            }
+            None => None,
+        };
+        let ty = if let Some(ty) = using_ty {
+            ty
+        } else if is_unique {
             IndexType::Unique
         } else if exprs.len() == 1 {
             IndexType::Normal
@@ -33,10 +50,14 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         let source = self
             .context
             .source_and_bind(table_name.clone(), None, None, false)?
-            .ok_or(DatabaseError::SourceNotFound)?;
+            .ok_or_else(|| DatabaseError::SourceNotFound(table_name.to_string()))?;
         let plan = match source {
-            Source::Table(table) => TableScanOperator::build(table_name.clone(), table, true),
+            Source::Table(table) => {
+                table.check_writable()?;
+                TableScanOperator::build(table_name.clone(), table, true)
+            }
             Source::View(view) => LogicalPlan::clone(&view.plan),
+            Source::CommonTable(_) => unreachable!(),
         };
         let mut columns = Vec::with_capacity(exprs.len());
 
@@ -52,6 +73,32 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                 }
             }
         }
+        let is_vector_column =
+            columns.len() == 1 && matches!(columns[0].datatype(), LogicalType::Vector(_));
+        if matches!(ty, IndexType::Vector) && (is_unique || !is_vector_column) {
+            return Err(DatabaseError::UnsupportedStmt(
+                "Vector index is only on a single vector column".to_string(),
+            ));
+        }
+        let is_point_column = columns.len() == 1 && columns[0].datatype() == &LogicalType::Point;
+        if matches!(ty, IndexType::ZOrder) && (is_unique || !is_point_column) {
+            return Err(DatabaseError::UnsupportedStmt(
+                "ZOrder index is only on a single point column".to_string(),
+            ));
+        }
+        if matches!(ty, IndexType::FullText | IndexType::Trigram)
+            && (is_unique
+                || columns.len() != 1
+                || !matches!(
+                    columns[0].datatype(),
+                    LogicalType::Varchar(..) | LogicalType::Char(..)
+                ))
+        {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "{:?} index is only on a single text column",
+                ty
+            )));
+        }
 
         Ok(LogicalPlan::new(
             Operator::CreateIndex(CreateIndexOperator {
@@ -1,16 +1,17 @@
 use super::{is_valid_identifier, Binder};
-use crate::binder::lower_case_name;
-use crate::catalog::{ColumnCatalog, ColumnDesc};
+use crate::binder::{fold_ident, fold_name};
+use crate::catalog::{AuditColumns, ColumnCatalog, ColumnDesc, ForeignTable, DELETED_AT_COLUMN};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::create_table::CreateTableOperator;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::tuple_codec::TupleCodecKind;
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use itertools::Itertools;
-use sqlparser::ast::{ColumnDef, ColumnOption, ObjectName, TableConstraint};
+use sqlparser::ast::{ColumnDef, ColumnOption, ObjectName, SqlOption, TableConstraint, Value};
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -21,9 +22,10 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         name: &ObjectName,
         columns: &[ColumnDef],
         constraints: &[TableConstraint],
+        with_options: &[SqlOption],
         if_not_exists: bool,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(name)?);
+        let table_name = Arc::new(fold_name(name)?);
 
         if !is_valid_identifier(&table_name) {
             return Err(DatabaseError::InvalidTable(
@@ -43,6 +45,11 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                         "illegal column naming".to_string(),
                     ));
                 }
+                if col_name.eq_ignore_ascii_case(DELETED_AT_COLUMN) {
+                    return Err(DatabaseError::InvalidColumn(format!(
+                        "{col_name} is reserved for soft delete"
+                    )));
+                }
             }
         }
         let mut columns: Vec<ColumnCatalog> = columns
@@ -57,11 +64,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                     is_primary,
                     ..
                 } => {
-                    for (i, column_name) in column_names
-                        .iter()
-                        .map(|ident| ident.value.to_lowercase())
-                        .enumerate()
-                    {
+                    for (i, column_name) in column_names.iter().map(fold_ident).enumerate() {
                         if let Some(column) = columns
                             .iter_mut()
                             .find(|column| column.name() == column_name)
@@ -89,22 +92,185 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             ));
         }
 
+        let (codec_options, with_options): (Vec<_>, Vec<_>) = with_options
+            .iter()
+            .cloned()
+            .partition(|option| option.name.value.eq_ignore_ascii_case("tuple_codec"));
+        let tuple_codec = Self::bind_tuple_codec(&codec_options)?;
+        let (audit_options, with_options): (Vec<_>, Vec<_>) =
+            with_options.into_iter().partition(|option| {
+                ["created_at", "updated_at", "row_version", "soft_delete"]
+                    .iter()
+                    .any(|name| option.name.value.eq_ignore_ascii_case(name))
+            });
+        let audit_columns = Self::bind_audit_columns(&columns, &audit_options)?;
+        let foreign = self.bind_foreign_table(&table_name, &with_options)?;
+
+        if audit_columns.soft_delete {
+            if foreign.is_some() {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "soft_delete on a foreign table".to_string(),
+                ));
+            }
+            columns.push(ColumnCatalog::new(
+                DELETED_AT_COLUMN.to_string(),
+                true,
+                ColumnDesc::new(LogicalType::TimeStamp(None, false), None, false, None)?,
+            ));
+        }
+
         Ok(LogicalPlan::new(
             Operator::CreateTable(CreateTableOperator {
                 table_name,
                 columns,
+                foreign,
+                tuple_codec,
+                audit_columns,
                 if_not_exists,
             }),
             Childrens::None,
         ))
     }
 
+    /// `WITH (tuple_codec = 'plain' | 'versioned')`, `versioned` by default.
+    fn bind_tuple_codec(options: &[SqlOption]) -> Result<TupleCodecKind, DatabaseError> {
+        let Some(SqlOption { value, .. }) = options.last() else {
+            return Ok(TupleCodecKind::Versioned);
+        };
+        match value {
+            Value::SingleQuotedString(name) | Value::DoubleQuotedString(name) => {
+                TupleCodecKind::from_name(&name.to_lowercase())
+            }
+            _ => None,
+        }
+        .ok_or_else(|| DatabaseError::UnsupportedStmt(format!("tuple_codec: {}", value)))
+    }
+
+    /// `WITH (created_at = 'c1', updated_at = 'c2', row_version = 'c3')` names the columns of the table
+    /// filled by INSERT and UPDATE, the times have to be a `DATETIME` or `TIMESTAMP`, the version an integer,
+    /// and `WITH (soft_delete = true)` adds the hidden column set by DELETE.
+    fn bind_audit_columns(
+        columns: &[ColumnCatalog],
+        options: &[SqlOption],
+    ) -> Result<AuditColumns, DatabaseError> {
+        let mut audit_columns = AuditColumns::default();
+
+        for SqlOption { name, value } in options {
+            if name.value.eq_ignore_ascii_case("soft_delete") {
+                audit_columns.soft_delete = match value {
+                    Value::Boolean(value) => *value,
+                    Value::SingleQuotedString(value) | Value::DoubleQuotedString(value) => {
+                        value.to_lowercase().parse().map_err(|_| {
+                            DatabaseError::InvalidValue(format!("soft_delete: {value}"))
+                        })?
+                    }
+                    value => {
+                        return Err(DatabaseError::InvalidValue(format!("soft_delete: {value}")))
+                    }
+                };
+                continue;
+            }
+            let column_name = match value {
+                Value::SingleQuotedString(value) | Value::DoubleQuotedString(value) => {
+                    value.to_lowercase()
+                }
+                value => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "table option value: {}",
+                        value
+                    )))
+                }
+            };
+            let column = columns
+                .iter()
+                .find(|column| column.name() == column_name)
+                .ok_or_else(|| DatabaseError::ColumnNotFound(column_name.clone()))?;
+            let option = name.value.to_lowercase();
+            let ty = column.datatype();
+            let is_valid = if option == "row_version" {
+                ty.is_signed_numeric() || ty.is_unsigned_numeric()
+            } else {
+                matches!(ty, LogicalType::DateTime | LogicalType::TimeStamp(..))
+            };
+
+            if !is_valid {
+                return Err(DatabaseError::InvalidColumn(format!(
+                    "{option} cannot be the column {column_name} of type {ty}"
+                )));
+            }
+            let audit_column = match option.as_str() {
+                "created_at" => &mut audit_columns.created_at,
+                "updated_at" => &mut audit_columns.updated_at,
+                _ => &mut audit_columns.row_version,
+            };
+            *audit_column = Some(column_name);
+        }
+        Ok(audit_columns)
+    }
+
+    /// `WITH (wrapper = '...', connection = '...', remote_table = '...')` maps the table to a remote source,
+    /// `remote_table` defaults to the name of the table.
+    fn bind_foreign_table(
+        &self,
+        table_name: &str,
+        with_options: &[SqlOption],
+    ) -> Result<Option<ForeignTable>, DatabaseError> {
+        if with_options.is_empty() {
+            return Ok(None);
+        }
+        let mut wrapper = None;
+        let mut connection = None;
+        let mut remote_table = None;
+
+        for SqlOption { name, value } in with_options {
+            let value = match value {
+                Value::SingleQuotedString(value) | Value::DoubleQuotedString(value) => {
+                    value.clone()
+                }
+                value => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "table option value: {}",
+                        value
+                    )))
+                }
+            };
+            match name.value.to_lowercase().as_str() {
+                "wrapper" => wrapper = Some(value.to_lowercase()),
+                "connection" => connection = Some(value),
+                "remote_table" => remote_table = Some(value),
+                option => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "table option: {}",
+                        option
+                    )))
+                }
+            }
+        }
+        let wrapper = wrapper.ok_or_else(|| {
+            DatabaseError::InvalidTable("foreign table requires the `wrapper` option".to_string())
+        })?;
+        if !self.context.foreign_data_wrappers.contains_key(&wrapper) {
+            return Err(DatabaseError::ForeignDataWrapperNotFound(wrapper));
+        }
+        let connection = connection.ok_or_else(|| {
+            DatabaseError::InvalidTable(
+                "foreign table requires the `connection` option".to_string(),
+            )
+        })?;
+
+        Ok(Some(ForeignTable {
+            wrapper,
+            connection,
+            remote_table: remote_table.unwrap_or_else(|| table_name.to_string()),
+        }))
+    }
+
     pub fn bind_column(
         &mut self,
         column_def: &ColumnDef,
         column_index: Option<usize>,
     ) -> Result<ColumnCatalog, DatabaseError> {
-        let column_name = column_def.name.value.to_lowercase();
+        let column_name = fold_ident(&column_def.name);
         let mut column_desc = ColumnDesc::new(
             LogicalType::try_from(column_def.data_type.clone())?,
             None,
@@ -179,6 +345,7 @@ mod tests {
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let foreign_data_wrappers = Default::default();
 
         let sql = "create table t1 (id int primary key, name varchar(10) null)";
         let mut binder = Binder::new(
@@ -188,6 +355,7 @@ mod tests {
                 &transaction,
                 &scala_functions,
                 &table_functions,
+                &foreign_data_wrappers,
                 Arc::new(AtomicUsize::new(0)),
             ),
             &[],
@@ -0,0 +1,39 @@
+use crate::binder::{fold_ident, Binder};
+use crate::catalog::task::Task;
+use crate::errors::DatabaseError;
+use crate::planner::operator::create_task::CreateTaskOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::scheduler::schedule::Schedule;
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use sqlparser::ast::{DataType, Ident, Statement};
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// `CREATE TASK`, as carried by the parser in `PREPARE`.
+    pub(crate) fn bind_create_task(
+        &mut self,
+        stmt: &Statement,
+        name: &Ident,
+        data_types: &[DataType],
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let [DataType::Custom(schedule, sql)] = data_types else {
+            return Err(DatabaseError::UnsupportedStmt(stmt.to_string()));
+        };
+        let (Some(schedule), [sql]) = (schedule.0.first(), sql.as_slice()) else {
+            return Err(DatabaseError::UnsupportedStmt(stmt.to_string()));
+        };
+        let _ = schedule.value.parse::<Schedule>()?;
+
+        Ok(LogicalPlan::new(
+            Operator::CreateTask(CreateTaskOperator {
+                task: Task {
+                    name: fold_ident(name),
+                    schedule: schedule.value.clone(),
+                    sql: sql.clone(),
+                },
+            }),
+            Childrens::None,
+        ))
+    }
+}
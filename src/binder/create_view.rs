@@ -1,4 +1,4 @@
-use crate::binder::{lower_case_name, lower_ident, Binder};
+use crate::binder::{fold_ident, fold_name, Binder};
 use crate::catalog::view::View;
 use crate::catalog::{ColumnCatalog, ColumnRef};
 use crate::errors::DatabaseError;
@@ -21,7 +21,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         columns: &[Ident],
         query: &Query,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let view_name = Arc::new(lower_case_name(name)?);
+        let view_name = Arc::new(fold_name(name)?);
         let mut plan = self.bind_query(query)?;
 
         if !columns.is_empty() {
@@ -32,7 +32,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                 .map(|(i, ident)| {
                     let mapping_column = &mapping_schema[i];
                     let mut column = ColumnCatalog::new(
-                        lower_ident(ident),
+                        fold_ident(ident),
                         mapping_column.nullable(),
                         mapping_column.desc().clone(),
                     );
@@ -1,11 +1,15 @@
-use crate::binder::{lower_case_name, Binder, Source};
+use crate::binder::batch_commit::check_batch_commit_hints;
+use crate::binder::{fold_ident, Binder, Source};
 use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
+use chrono::Utc;
 use itertools::Itertools;
 use sqlparser::ast::{Expr, TableAlias, TableFactor, TableWithJoins};
 use std::sync::Arc;
@@ -16,28 +20,46 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         from: &TableWithJoins,
         selection: &Option<Expr>,
     ) -> Result<LogicalPlan, DatabaseError> {
-        if let TableFactor::Table { name, alias, .. } = &from.relation {
-            let table_name = Arc::new(lower_case_name(name)?);
+        if let TableFactor::Table {
+            name,
+            alias,
+            with_hints,
+            ..
+        } = &from.relation
+        {
+            check_batch_commit_hints(with_hints)?;
+            let table_name = Arc::new(self.writable_table_name(name)?);
             let mut table_alias = None;
             let mut alias_idents = None;
 
             if let Some(TableAlias { name, columns }) = alias {
-                table_alias = Some(Arc::new(name.value.to_lowercase()));
+                table_alias = Some(Arc::new(fold_ident(name)));
                 alias_idents = Some(columns);
             }
+            let masking = self.context.masking;
             let Source::Table(table) = self
                 .context
                 .source_and_bind(table_name.clone(), table_alias.as_ref(), None, true)?
-                .ok_or(DatabaseError::TableNotFound)?
+                .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?
             else {
                 unreachable!()
             };
+            table.check_writable()?;
+            Self::check_unmasked(table, masking)?;
             let primary_keys = table
                 .primary_keys()
                 .iter()
                 .map(|(_, column)| column.clone())
                 .collect_vec();
             let mut plan = TableScanOperator::build(table_name.clone(), table, true);
+            // `DELETE ... WITH DELETED` removes the rows of a table in soft delete mode
+            let deleted_at = table
+                .deleted_at_column()
+                .filter(|_| !with_hints.iter().any(Self::is_deleted_hint))
+                .cloned();
+            if deleted_at.is_some() {
+                plan = Self::filter_deleted(plan, table);
+            }
 
             if let Some(alias_idents) = alias_idents {
                 plan =
@@ -47,6 +69,17 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             if let Some(predicate) = selection {
                 plan = self.bind_where(plan, predicate)?;
             }
+            if let Some(column) = deleted_at {
+                let deleted_at = DataValue::Time64(Utc::now().timestamp(), 0, false);
+
+                return Ok(LogicalPlan::new(
+                    Operator::Update(UpdateOperator {
+                        table_name,
+                        value_exprs: vec![(column, ScalarExpression::Constant(deleted_at))],
+                    }),
+                    Childrens::Only(plan),
+                ));
+            }
 
             Ok(LogicalPlan::new(
                 Operator::Delete(DeleteOperator {
@@ -13,6 +13,12 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
     ) -> LogicalPlan {
         self.context.step(QueryBindStep::Distinct);
 
-        AggregateOperator::build(children, vec![], select_list, true)
+        AggregateOperator::build(
+            children,
+            vec![],
+            select_list,
+            true,
+            self.context.aggregate_memory_limit,
+        )
     }
 }
@@ -1,4 +1,4 @@
-use crate::binder::{lower_ident, Binder};
+use crate::binder::{fold_ident, Binder};
 use crate::errors::DatabaseError;
 use crate::planner::operator::drop_index::DropIndexOperator;
 use crate::planner::operator::Operator;
@@ -20,8 +20,8 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             .ok_or(DatabaseError::InvalidTable(name.to_string()))?;
         let index_name = name.0.get(1).ok_or(DatabaseError::InvalidIndex)?;
 
-        let table_name = Arc::new(lower_ident(table_name));
-        let index_name = lower_ident(index_name);
+        let table_name = Arc::new(fold_ident(table_name));
+        let index_name = fold_ident(index_name);
 
         Ok(LogicalPlan::new(
             Operator::DropIndex(DropIndexOperator {
@@ -0,0 +1,24 @@
+use crate::binder::{fold_ident, Binder};
+use crate::errors::DatabaseError;
+use crate::planner::operator::drop_task::DropTaskOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use sqlparser::ast::Ident;
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    pub(crate) fn bind_drop_task(
+        &mut self,
+        name: &Ident,
+        if_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        Ok(LogicalPlan::new(
+            Operator::DropTask(DropTaskOperator {
+                task_name: fold_ident(name),
+                if_exists,
+            }),
+            Childrens::None,
+        ))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::{fold_name, Binder};
 use crate::errors::DatabaseError;
 use crate::planner::operator::drop_view::DropViewOperator;
 use crate::planner::operator::Operator;
@@ -14,7 +14,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         name: &ObjectName,
         if_exists: &bool,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let view_name = Arc::new(lower_case_name(name)?);
+        let view_name = Arc::new(fold_name(name)?);
 
         Ok(LogicalPlan::new(
             Operator::DropView(DropViewOperator {
@@ -4,22 +4,34 @@ use crate::expression;
 use crate::expression::agg::AggKind;
 use itertools::Itertools;
 use sqlparser::ast::{
-    BinaryOperator, CharLengthUnits, DataType, Expr, Function, FunctionArg, FunctionArgExpr, Ident,
-    Query, UnaryOperator, Value,
+    ArrayAgg, BinaryOperator, CharLengthUnits, DataType, Expr, Function, FunctionArg,
+    FunctionArgExpr, Ident, OrderByExpr, Query, UnaryOperator, Value,
 };
 use std::collections::HashMap;
+use std::mem;
 use std::slice;
 use std::sync::Arc;
 
-use super::{lower_ident, Binder, BinderContext, QueryBindStep, SubQueryType};
+use super::{fold_ident, Binder, BinderContext, QueryBindStep, SubQueryType};
 use crate::expression::function::scala::{ArcScalarFunctionImpl, ScalarFunction};
 use crate::expression::function::table::{ArcTableFunctionImpl, TableFunction};
 use crate::expression::function::FunctionSummary;
 use crate::expression::{AliasType, ScalarExpression};
-use crate::planner::{LogicalPlan, SchemaOutput};
+use crate::function::lenient_cast::LenientCast;
+use crate::function::match_against::MatchAgainst;
+use crate::function::nested::{NestedFunction, NestedKind, MAP_KEY, MAP_VALUE};
+use crate::function::similarity::Similarity;
+use crate::function::spatial::{SpatialFunction, SpatialKind};
+use crate::function::vector_distance::{VectorDistance, VectorMetric};
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan, SchemaOutput};
 use crate::storage::Transaction;
-use crate::types::value::{DataValue, Utf8Type};
+use crate::types::evaluator::EvaluatorFactory;
+use crate::types::value::{DataValue, StringCoercion, Utf8Type};
 use crate::types::{ColumnId, LogicalType};
+use crate::utils::{spatial, trigram};
+use ordered_float::OrderedFloat;
 
 macro_rules! try_alias {
     ($context:expr, $full_name:expr) => {
@@ -198,6 +210,22 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                 }
                 Ok(ScalarExpression::Tuple(bond_exprs))
             }
+            Expr::ArrayAgg(ArrayAgg {
+                distinct,
+                expr,
+                order_by,
+                limit: None,
+                within_group: false,
+            }) => self.bind_array_agg(*distinct, expr, order_by.as_deref().unwrap_or_default()),
+            Expr::Array(array) => {
+                let args = array
+                    .elem
+                    .iter()
+                    .map(|expr| self.bind_expr(expr))
+                    .try_collect()?;
+
+                Self::bind_nested_function(NestedKind::ListValue, args, Vec::new())
+            }
             Expr::Case {
                 operand,
                 conditions,
@@ -263,7 +291,13 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                 alias_column,
             )))),
         };
-        let alias_plan = self.bind_project(sub_query, vec![alias_expr.clone()])?;
+        // keeps the step, the other subqueries of a `WHERE` are bound after this one
+        let alias_plan = LogicalPlan::new(
+            Operator::Project(ProjectOperator {
+                exprs: vec![alias_expr.clone()],
+            }),
+            Childrens::Only(sub_query),
+        );
         Ok((alias_expr, alias_plan))
     }
 
@@ -272,27 +306,7 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
         in_ty: Option<LogicalType>,
         subquery: &Query,
     ) -> Result<(LogicalPlan, ScalarExpression), DatabaseError> {
-        let BinderContext {
-            table_cache,
-            view_cache,
-            transaction,
-            scala_functions,
-            table_functions,
-            temp_table_id,
-            ..
-        } = &self.context;
-        let mut binder = Binder::new(
-            BinderContext::new(
-                table_cache,
-                view_cache,
-                *transaction,
-                scala_functions,
-                table_functions,
-                temp_table_id.clone(),
-            ),
-            self.args,
-            Some(self),
-        );
+        let mut binder = Binder::new(self.context.child(), self.args, Some(self));
         let mut sub_query = binder.bind_query(subquery)?;
         let sub_query_schema = sub_query.output_schema();
 
@@ -351,8 +365,8 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
         bind_table_name: Option<String>,
     ) -> Result<ScalarExpression, DatabaseError> {
         let full_name = match idents {
-            [column] => (None, lower_ident(column)),
-            [table, column] => (Some(lower_ident(table)), lower_ident(column)),
+            [column] => (None, fold_ident(column)),
+            [table, column] => (Some(fold_ident(table)), fold_ident(column)),
             _ => {
                 return Err(DatabaseError::InvalidColumn(
                     idents
@@ -424,9 +438,86 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
         right: &Expr,
         op: &BinaryOperator,
     ) -> Result<ScalarExpression, DatabaseError> {
-        let left_expr = Box::new(self.bind_expr(left)?);
-        let right_expr = Box::new(self.bind_expr(right)?);
+        let mut left_expr = Box::new(self.bind_expr(left)?);
+        let mut right_expr = Box::new(self.bind_expr(right)?);
+
+        // `a % b` of strings is whether they are similar, as in `pg_trgm`
+        if matches!(op, BinaryOperator::Modulo)
+            && Self::is_text(&left_expr.return_type())
+            && Self::is_text(&right_expr.return_type())
+        {
+            return Ok(ScalarExpression::Binary {
+                op: expression::BinaryOperator::GtEq,
+                left_expr: Box::new(Self::bind_similarity(vec![*left_expr, *right_expr])?),
+                right_expr: Box::new(ScalarExpression::Constant(DataValue::Float64(
+                    OrderedFloat(trigram::SIMILARITY_THRESHOLD),
+                ))),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            });
+        }
+        // `<->`, `<=>` and `<#>` of vectors are their distances, as in `pgvector`
+        let is_vector =
+            |expr: &ScalarExpression| matches!(expr.return_type(), LogicalType::Vector(_));
+        if is_vector(&left_expr) || is_vector(&right_expr) {
+            let metric = match op {
+                BinaryOperator::PGRegexMatch => Some(VectorMetric::L2),
+                BinaryOperator::Spaceship => Some(VectorMetric::Cosine),
+                BinaryOperator::PGRegexNotMatch => Some(VectorMetric::NegativeInnerProduct),
+                _ => None,
+            };
+            if let Some(metric) = metric {
+                return Self::bind_vector_distance(metric, vec![*left_expr, *right_expr]);
+            }
+        }
+        // a text computed or compared with a number is cast to the type of the number
+        if matches!(
+            op,
+            BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::Gt
+                | BinaryOperator::Lt
+                | BinaryOperator::GtEq
+                | BinaryOperator::LtEq
+                | BinaryOperator::Eq
+                | BinaryOperator::NotEq
+        ) {
+            let (left_ty, right_ty) = (left_expr.return_type(), right_expr.return_type());
 
+            if Self::is_text(&left_ty) && right_ty.is_numeric() {
+                left_expr = Box::new(self.bind_string_coercion(*left_expr, right_ty));
+            } else if left_ty.is_numeric() && Self::is_text(&right_ty) {
+                right_expr = Box::new(self.bind_string_coercion(*right_expr, left_ty));
+            }
+        }
+        // the arithmetic of dates has its own types, see `EvaluatorFactory::temporal_binary_create`
+        if matches!(
+            op,
+            BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+        ) {
+            let arithmetic_op: expression::BinaryOperator = (op.clone()).try_into()?;
+
+            if let Some(temporal) = EvaluatorFactory::temporal_binary_create(
+                &left_expr.return_type(),
+                &right_expr.return_type(),
+                arithmetic_op,
+            )? {
+                return Ok(ScalarExpression::Binary {
+                    op: arithmetic_op,
+                    left_expr,
+                    right_expr,
+                    evaluator: None,
+                    ty: temporal.ty,
+                });
+            }
+        }
         let ty = match op {
             BinaryOperator::Plus
             | BinaryOperator::Minus
@@ -487,17 +578,19 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
     }
 
     fn bind_function(&mut self, func: &Function) -> Result<ScalarExpression, DatabaseError> {
-        if !matches!(self.context.step_now(), QueryBindStep::From) {
-            return Err(DatabaseError::UnsupportedStmt(
-                "`TableFunction` cannot bind in non-From step".to_string(),
-            ));
-        }
         let mut args = Vec::with_capacity(func.args.len());
+        let mut arg_names = Vec::with_capacity(func.args.len());
 
         for arg in func.args.iter() {
             let arg_expr = match arg {
-                FunctionArg::Named { arg, .. } => arg,
-                FunctionArg::Unnamed(arg) => arg,
+                FunctionArg::Named { name, arg } => {
+                    arg_names.push(Some(fold_ident(name)));
+                    arg
+                }
+                FunctionArg::Unnamed(arg) => {
+                    arg_names.push(None);
+                    arg
+                }
             };
             match arg_expr {
                 FunctionArgExpr::Expr(expr) => args.push(self.bind_expr(expr)?),
@@ -510,7 +603,8 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                 }
             }
         }
-        let function_name = func.name.to_string().to_lowercase();
+        // a qualified name, e.g. `geo.distance`, names the function registered under it
+        let function_name = func.name.0.iter().map(fold_ident).join(".");
 
         match function_name.as_str() {
             "count" => {
@@ -575,6 +669,62 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                     ty: LogicalType::Double,
                 });
             }
+            "bool_and" | "every" | "bool_or" | "any" | "some" => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of bool_and() or bool_or() parameters",
+                        "1",
+                    ));
+                }
+                if !matches!(
+                    args[0].return_type(),
+                    LogicalType::Boolean | LogicalType::SqlNull
+                ) {
+                    return Err(DatabaseError::MisMatch(
+                        "bool_and() or bool_or() parameter type",
+                        "boolean",
+                    ));
+                }
+                let kind = if matches!(function_name.as_str(), "bool_and" | "every") {
+                    AggKind::BoolAnd
+                } else {
+                    AggKind::BoolOr
+                };
+
+                return Ok(ScalarExpression::AggCall {
+                    distinct: func.distinct,
+                    kind,
+                    args,
+                    ty: LogicalType::Boolean,
+                });
+            }
+            "first_value" | "first" | "last_value" | "last" | "arbitrary" | "any_value" => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of first_value() or last_value() parameters",
+                        "1",
+                    ));
+                }
+                let ty = args[0].return_type();
+                let is_arbitrary = matches!(function_name.as_str(), "arbitrary" | "any_value");
+                let order_by = if is_arbitrary {
+                    Vec::new()
+                } else {
+                    self.bind_agg_order_by(&func.order_by, &mut args)?
+                };
+                let kind = match function_name.as_str() {
+                    "first_value" | "first" => AggKind::FirstValue(order_by),
+                    "last_value" | "last" => AggKind::LastValue(order_by),
+                    _ => AggKind::Arbitrary,
+                };
+
+                return Ok(ScalarExpression::AggCall {
+                    distinct: func.distinct,
+                    kind,
+                    args,
+                    ty,
+                });
+            }
             "if" => {
                 if args.len() != 3 {
                     return Err(DatabaseError::MisMatch("number of if() parameters", "3"));
@@ -646,6 +796,61 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                 }
                 return Ok(ScalarExpression::Coalesce { exprs: args, ty });
             }
+            MatchAgainst::NAME => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of match_against() parameters",
+                        "2",
+                    ));
+                }
+                let is_text_column = matches!(&args[0], ScalarExpression::ColumnRef(column) if Self::is_text(column.datatype()));
+                let is_query =
+                    matches!(&args[1], ScalarExpression::Constant(DataValue::Utf8 { .. }));
+                if !is_text_column || !is_query {
+                    return Err(DatabaseError::MisMatch(
+                        "match_against() parameters",
+                        "a text column and a constant query",
+                    ));
+                }
+                return Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+                    args,
+                    inner: ArcScalarFunctionImpl(MatchAgainst::new()),
+                }));
+            }
+            Similarity::NAME => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of similarity() parameters",
+                        "2",
+                    ));
+                }
+                return Self::bind_similarity(args);
+            }
+            name if VectorMetric::from_name(name).is_some() => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of vector distance parameters",
+                        "2",
+                    ));
+                }
+                return Self::bind_vector_distance(VectorMetric::from_name(name).unwrap(), args);
+            }
+            name if SpatialKind::from_name(name).is_some() => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of spatial function parameters",
+                        "2",
+                    ));
+                }
+                return Self::bind_spatial_function(SpatialKind::from_name(name).unwrap(), args);
+            }
+            name if NestedKind::from_name(name).is_some() => {
+                return Self::bind_nested_function(
+                    NestedKind::from_name(name).unwrap(),
+                    args,
+                    arg_names,
+                );
+            }
             _ => (),
         }
         let arg_types = args.iter().map(ScalarExpression::return_type).collect_vec();
@@ -653,20 +858,358 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
             name: function_name,
             arg_types,
         };
-        if let Some(function) = self.context.scala_functions.get(&summary) {
+        if let Some((candidate, function)) =
+            Self::resolve_function(self.context.scala_functions, &summary)?
+        {
             return Ok(ScalarExpression::ScalaFunction(ScalarFunction {
-                args,
+                args: Self::promote_args(args, &candidate.arg_types),
                 inner: ArcScalarFunctionImpl(function.clone()),
             }));
         }
-        if let Some(function) = self.context.table_functions.get(&summary) {
+        if let Some((candidate, function)) =
+            Self::resolve_function(self.context.table_functions, &summary)?
+        {
+            if !matches!(self.context.step_now(), QueryBindStep::From) {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`TableFunction` cannot bind in non-From step".to_string(),
+                ));
+            }
             return Ok(ScalarExpression::TableFunction(TableFunction {
-                args,
+                args: Self::promote_args(args, &candidate.arg_types),
                 inner: ArcTableFunctionImpl(function.clone()),
             }));
         }
 
-        Err(DatabaseError::FunctionNotFound(summary.name))
+        Err(DatabaseError::FunctionNotFound(summary.to_string()))
+    }
+
+    /// Picks the overload of a function called with the arguments of `summary`: the signature
+    /// matching them exactly, otherwise among those of the same arity whose parameters all
+    /// accept their argument by promotion, the most specific one, i.e. the one whose parameters
+    /// could be promoted to those of every other. Two of them left is an ambiguity.
+    fn resolve_function<'f, F: ?Sized>(
+        functions: &'f ahash::HashMap<FunctionSummary, Arc<F>>,
+        summary: &FunctionSummary,
+    ) -> Result<Option<(&'f FunctionSummary, &'f Arc<F>)>, DatabaseError> {
+        if let Some(found) = functions.get_key_value(summary) {
+            return Ok(Some(found));
+        }
+        let accepts = |from: &[LogicalType], to: &[LogicalType]| {
+            from.len() == to.len()
+                && from
+                    .iter()
+                    .zip(to)
+                    .all(|(from, to)| Self::is_promotable(from, to))
+        };
+        let candidates = functions
+            .iter()
+            .filter(|(candidate, _)| {
+                candidate.name == summary.name && accepts(&summary.arg_types, &candidate.arg_types)
+            })
+            .collect_vec();
+        let mut best = candidates
+            .iter()
+            .filter(|(candidate, _)| {
+                candidates
+                    .iter()
+                    .all(|(other, _)| accepts(&candidate.arg_types, &other.arg_types))
+            })
+            .collect_vec();
+
+        match best.len() {
+            0 if candidates.is_empty() => Ok(None),
+            1 => Ok(best.pop().copied()),
+            _ => Err(DatabaseError::AmbiguousFunction(format!(
+                "{}, candidates: {}",
+                summary,
+                candidates
+                    .iter()
+                    .map(|(candidate, _)| candidate.to_string())
+                    .sorted()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    fn is_promotable(from: &LogicalType, to: &LogicalType) -> bool {
+        LogicalType::can_implicit_cast(from, to)
+            // any string fits an unbounded varchar
+            || matches!(
+                (from, to),
+                (
+                    LogicalType::Char(..) | LogicalType::Varchar(..),
+                    LogicalType::Varchar(None, _)
+                )
+            )
+    }
+
+    /// Casts the arguments to the parameter types of the overload they were resolved to.
+    fn promote_args(
+        args: Vec<ScalarExpression>,
+        arg_types: &[LogicalType],
+    ) -> Vec<ScalarExpression> {
+        args.into_iter()
+            .zip(arg_types)
+            .map(|(arg, ty)| {
+                if &arg.return_type() == ty {
+                    arg
+                } else {
+                    ScalarExpression::TypeCast {
+                        expr: Box::new(arg),
+                        ty: ty.clone(),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Binds the `ORDER BY` keys inside an aggregate call behind its argument,
+    /// the two being wrapped into a tuple when there are keys.
+    /// `ARRAY_AGG(expr [ORDER BY ...])`, parsed apart from the other functions.
+    fn bind_array_agg(
+        &mut self,
+        distinct: bool,
+        expr: &Expr,
+        order_by: &[OrderByExpr],
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let mut args = vec![self.bind_expr(expr)?];
+        let ty = LogicalType::Array(Box::new(args[0].return_type()));
+        let order_by = self.bind_agg_order_by(order_by, &mut args)?;
+
+        Ok(ScalarExpression::AggCall {
+            distinct,
+            kind: AggKind::ArrayAgg(order_by),
+            args,
+            ty,
+        })
+    }
+
+    fn bind_agg_order_by(
+        &mut self,
+        order_by: &[OrderByExpr],
+        args: &mut Vec<ScalarExpression>,
+    ) -> Result<Vec<(bool, bool)>, DatabaseError> {
+        let mut sort_fields = Vec::with_capacity(order_by.len());
+
+        if order_by.is_empty() {
+            return Ok(sort_fields);
+        }
+        for OrderByExpr {
+            expr,
+            asc,
+            nulls_first,
+        } in order_by.iter()
+        {
+            args.push(self.bind_expr(expr)?);
+            sort_fields.push((
+                asc.map_or(true, |asc| asc),
+                nulls_first.map_or(false, |first| first),
+            ));
+        }
+        *args = vec![ScalarExpression::Tuple(mem::take(args))];
+
+        Ok(sort_fields)
+    }
+
+    /// Casts a text to the number `ty` as the [`StringCoercion`] of the database does.
+    fn bind_string_coercion(&self, expr: ScalarExpression, ty: LogicalType) -> ScalarExpression {
+        match self.context.string_coercion {
+            StringCoercion::Strict => ScalarExpression::TypeCast {
+                expr: Box::new(expr),
+                ty,
+            },
+            StringCoercion::Lenient => ScalarExpression::ScalaFunction(ScalarFunction {
+                args: vec![expr],
+                inner: ArcScalarFunctionImpl(LenientCast::new(ty)),
+            }),
+        }
+    }
+
+    fn is_text(ty: &LogicalType) -> bool {
+        matches!(ty, LogicalType::Varchar(..) | LogicalType::Char(..))
+    }
+
+    fn bind_similarity(args: Vec<ScalarExpression>) -> Result<ScalarExpression, DatabaseError> {
+        if !args.iter().all(|arg| Self::is_text(&arg.return_type())) {
+            return Err(DatabaseError::MisMatch(
+                "similarity() parameters",
+                "two strings",
+            ));
+        }
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: ArcScalarFunctionImpl(Similarity::new()),
+        }))
+    }
+
+    /// Binds the distance of two vectors, a string constant such as `'[1, 2, 3]'` is read as a vector
+    /// of the dimension of the other argument.
+    fn bind_vector_distance(
+        metric: VectorMetric,
+        args: Vec<ScalarExpression>,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let Some(dimension) = args.iter().find_map(|arg| match arg.return_type() {
+            LogicalType::Vector(dimension) => Some(dimension),
+            _ => None,
+        }) else {
+            return Err(DatabaseError::MisMatch(
+                "vector distance parameters",
+                "vectors",
+            ));
+        };
+        let ty = LogicalType::Vector(dimension);
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                ScalarExpression::Constant(value) if Self::is_text(&value.logical_type()) => {
+                    Ok(ScalarExpression::Constant(value.cast(&ty)?))
+                }
+                arg if arg.return_type() == ty => Ok(arg),
+                _ => Err(DatabaseError::MisMatch(
+                    "vector distance parameters",
+                    "vectors of the same dimension",
+                )),
+            })
+            .try_collect()?;
+
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: ArcScalarFunctionImpl(VectorDistance::new(metric, dimension)),
+        }))
+    }
+
+    /// Binds `st_point` over two numbers and the others over points or boxes,
+    /// given as text constants too.
+    fn bind_spatial_function(
+        kind: SpatialKind,
+        args: Vec<ScalarExpression>,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let args: Vec<ScalarExpression> = args
+            .into_iter()
+            .map(|arg| match (kind, arg) {
+                (SpatialKind::Point, arg) if arg.return_type().is_numeric() => {
+                    Ok(ScalarExpression::TypeCast {
+                        expr: Box::new(arg),
+                        ty: LogicalType::Double,
+                    })
+                }
+                (SpatialKind::Point, _) => {
+                    Err(DatabaseError::MisMatch("st_point() parameters", "numbers"))
+                }
+                (
+                    SpatialKind::MakeBox,
+                    ScalarExpression::Constant(DataValue::Utf8 { value, .. }),
+                ) => Ok(ScalarExpression::Constant(spatial::parse_point(&value)?)),
+                (SpatialKind::MakeBox, arg) if arg.return_type() == LogicalType::Point => Ok(arg),
+                (SpatialKind::MakeBox, _) => {
+                    Err(DatabaseError::MisMatch("st_makebox() parameters", "points"))
+                }
+                (_, ScalarExpression::Constant(DataValue::Utf8 { value, .. })) => {
+                    Ok(ScalarExpression::Constant(spatial::parse(&value)?))
+                }
+                (_, arg) if matches!(arg.return_type(), LogicalType::Point | LogicalType::Box) => {
+                    Ok(arg)
+                }
+                _ => Err(DatabaseError::MisMatch(
+                    "spatial function parameters",
+                    "points or boxes",
+                )),
+            })
+            .try_collect()?;
+        let arg_types = args.iter().map(ScalarExpression::return_type).collect_vec();
+
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: ArcScalarFunctionImpl(SpatialFunction::new(kind, arg_types)),
+        }))
+    }
+
+    /// Binds `list_value` over the elements cast to the widest of them, `struct_pack` over
+    /// arguments named by `name => expr` or by the column given, and `map` over two arrays.
+    fn bind_nested_function(
+        kind: NestedKind,
+        args: Vec<ScalarExpression>,
+        arg_names: Vec<Option<String>>,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let (args, ty) = match kind {
+            NestedKind::ListValue => {
+                let mut element = LogicalType::SqlNull;
+
+                for arg in args.iter() {
+                    element = LogicalType::max_logical_type(&element, &arg.return_type())?;
+                }
+                let args = args
+                    .into_iter()
+                    .map(|arg| {
+                        if arg.return_type() == element {
+                            arg
+                        } else {
+                            ScalarExpression::TypeCast {
+                                expr: Box::new(arg),
+                                ty: element.clone(),
+                            }
+                        }
+                    })
+                    .collect_vec();
+
+                (args, LogicalType::Array(Box::new(element)))
+            }
+            NestedKind::StructPack => {
+                let mut fields: Vec<(String, LogicalType)> = Vec::with_capacity(args.len());
+
+                for (arg, name) in args.iter().zip(arg_names) {
+                    let name = match (name, arg) {
+                        (Some(name), _) => name,
+                        (None, ScalarExpression::ColumnRef(column)) => column.name().to_string(),
+                        _ => {
+                            return Err(DatabaseError::MisMatch(
+                                "struct_pack() parameters",
+                                "named arguments",
+                            ))
+                        }
+                    };
+                    if fields.iter().any(|(field, _)| field == &name) {
+                        return Err(DatabaseError::DuplicateColumn(name));
+                    }
+                    fields.push((name, arg.return_type()));
+                }
+
+                (args, LogicalType::Struct(fields))
+            }
+            NestedKind::Map => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch("number of map() parameters", "2"));
+                }
+                let (LogicalType::Array(key), LogicalType::Array(value)) =
+                    (args[0].return_type(), args[1].return_type())
+                else {
+                    return Err(DatabaseError::MisMatch("map() parameters", "two arrays"));
+                };
+                let ty = LogicalType::Array(Box::new(LogicalType::Struct(vec![
+                    (MAP_KEY.to_string(), *key),
+                    (MAP_VALUE.to_string(), *value),
+                ])));
+
+                (args, ty)
+            }
+        };
+        let arg_types = args.iter().map(ScalarExpression::return_type).collect_vec();
+        let is_constant = args.iter().all(|arg| match arg {
+            ScalarExpression::Constant(_) => true,
+            ScalarExpression::TypeCast { expr, .. } => {
+                matches!(expr.as_ref(), ScalarExpression::Constant(_))
+            }
+            _ => false,
+        });
+        let function = ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: ArcScalarFunctionImpl(NestedFunction::new(kind, arg_types, ty)),
+        });
+        // literals such as `ARRAY[1, 2]` are folded so that they can be inserted as values
+        if is_constant {
+            return Ok(ScalarExpression::Constant(function.eval(None)?));
+        }
+        Ok(function)
     }
 
     fn return_type(
@@ -0,0 +1,138 @@
+use crate::binder::Binder;
+use crate::errors::DatabaseError;
+use crate::function::fake::Fake;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use itertools::Itertools;
+use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, Ident, TableAlias, Value};
+
+pub(crate) const FAKER: &str = "faker";
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// `faker(rows, 'column kind, ...')`, generated rows of random values of the kinds:
+    /// - `serial`: the row number from 1
+    /// - `int(low, high)`: an integer in `[low, high]`, by default `[0, 100]`
+    /// - `double(low, high)`: a double in `[low, high)`, by default `[0, 1)`
+    /// - `bool`, `uuid`, and the texts of `fake(kind)`, e.g. `name`, `email` or `city`
+    ///
+    /// It is planned as the projection of the random functions over `numbers(rows)`.
+    pub(crate) fn bind_faker(
+        &mut self,
+        args: &[FunctionArg],
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let [FunctionArg::Unnamed(FunctionArgExpr::Expr(rows)), FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
+            spec,
+        ))))] = args
+        else {
+            return Err(DatabaseError::MisMatch(
+                "the rows and a constant spec",
+                "the arguments of faker",
+            ));
+        };
+        let quote = self.context.parser_options.dialect.quote_style();
+        let items = split_faker_spec(spec)
+            .into_iter()
+            .map(|column| {
+                let (name, kind) = column.split_once(char::is_whitespace).ok_or_else(|| {
+                    DatabaseError::InvalidValue(format!("faker() column: {column}"))
+                })?;
+                let expr = faker_expr(kind.trim()).ok_or_else(|| {
+                    DatabaseError::InvalidValue(format!("faker() column: {column}"))
+                })?;
+
+                Ok(format!("{expr} AS {}", Ident::with_quote(quote, name)))
+            })
+            .try_collect::<_, Vec<_>, DatabaseError>()?;
+        if items.is_empty() {
+            return Err(DatabaseError::MisMatch(
+                "at least one column",
+                "the spec of faker",
+            ));
+        }
+        let sql = format!("SELECT {} FROM table(numbers({rows}))", items.join(", "));
+
+        self.bind_rewritten_query(sql, FAKER, alias)
+    }
+}
+
+fn split_faker_spec(spec: &str) -> Vec<&str> {
+    let mut columns = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+
+    for (i, c) in spec.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                columns.push(spec[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    columns.push(spec[start..].trim());
+    columns.retain(|column| !column.is_empty());
+    columns
+}
+
+fn faker_expr(kind: &str) -> Option<String> {
+    let (kind, args) = match kind.split_once('(') {
+        Some((kind, args)) => (
+            kind.trim().to_lowercase(),
+            args.strip_suffix(')')?
+                .split(',')
+                .map(str::trim)
+                .collect_vec(),
+        ),
+        None => (kind.to_lowercase(), vec![]),
+    };
+
+    Some(match (kind.as_str(), args.as_slice()) {
+        ("serial", []) => "number + 1".to_string(),
+        ("int" | "integer", []) => "random(0, 100)".to_string(),
+        ("int" | "integer", [low, high]) => format!(
+            "random({}, {})",
+            low.parse::<i32>().ok()?,
+            high.parse::<i32>().ok()?
+        ),
+        ("double" | "float", []) => "random()".to_string(),
+        ("double" | "float", [low, high]) => {
+            let (low, high) = (low.parse::<f64>().ok()?, high.parse::<f64>().ok()?);
+
+            format!(
+                "CAST({low} AS DOUBLE) + random() * CAST({} AS DOUBLE)",
+                high - low
+            )
+        }
+        ("bool" | "boolean", []) => "random() < 0.5".to_string(),
+        ("uuid", []) => "uuid()".to_string(),
+        (kind, []) if Fake::KINDS.contains(&kind) => {
+            format!("fake({})", Value::SingleQuotedString(kind.to_string()))
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::faker::{faker_expr, split_faker_spec};
+
+    #[test]
+    fn test_faker_spec() {
+        assert_eq!(
+            split_faker_spec("id serial, age int(18, 90), score double(0.5, 1), "),
+            vec!["id serial", "age int(18, 90)", "score double(0.5, 1)"]
+        );
+        assert_eq!(faker_expr("serial").unwrap(), "number + 1");
+        assert_eq!(faker_expr("int(18, 90)").unwrap(), "random(18, 90)");
+        assert_eq!(
+            faker_expr("double(0.5, 1)").unwrap(),
+            "CAST(0.5 AS DOUBLE) + random() * CAST(0.5 AS DOUBLE)"
+        );
+        assert_eq!(faker_expr("email").unwrap(), "fake('email')");
+        assert_eq!(faker_expr("int(a, 1)"), None);
+        assert_eq!(faker_expr("phone"), None);
+    }
+}
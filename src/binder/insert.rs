@@ -1,16 +1,18 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::{Binder, Source};
+use crate::catalog::TableName;
 use crate::errors::DatabaseError;
 use crate::expression::simplify::ConstantCalculator;
 use crate::expression::visitor_mut::VisitorMut;
-use crate::expression::ScalarExpression;
+use crate::expression::{AliasType, ScalarExpression};
 use crate::planner::operator::insert::InsertOperator;
+use crate::planner::operator::project::ProjectOperator;
 use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::Transaction;
 use crate::types::tuple::SchemaRef;
 use crate::types::value::DataValue;
-use sqlparser::ast::{Expr, Ident, ObjectName};
+use sqlparser::ast::{Expr, Ident, ObjectName, Query};
 use std::slice;
 use std::sync::Arc;
 
@@ -25,42 +27,9 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
     ) -> Result<LogicalPlan, DatabaseError> {
         // FIXME: Make it better to detect the current BindStep
         self.context.allow_default = true;
-        let table_name = Arc::new(lower_case_name(name)?);
-
-        let source = self
-            .context
-            .source_and_bind(table_name.clone(), None, None, false)?
-            .ok_or(DatabaseError::TableNotFound)?;
-        let mut _schema_ref = None;
+        let table_name = Arc::new(self.writable_table_name(name)?);
         let values_len = expr_rows[0].len();
-
-        if idents.is_empty() {
-            let schema_buf = self.table_schema_buf.entry(table_name.clone()).or_default();
-            let temp_schema_ref = source.schema_ref(schema_buf);
-            if values_len > temp_schema_ref.len() {
-                return Err(DatabaseError::ValuesLenMismatch(
-                    temp_schema_ref.len(),
-                    values_len,
-                ));
-            }
-            _schema_ref = Some(temp_schema_ref);
-        } else {
-            let mut columns = Vec::with_capacity(idents.len());
-            for ident in idents {
-                match self.bind_column_ref_from_identifiers(
-                    slice::from_ref(ident),
-                    Some(table_name.to_string()),
-                )? {
-                    ScalarExpression::ColumnRef(catalog) => columns.push(catalog),
-                    _ => return Err(DatabaseError::UnsupportedStmt(ident.to_string())),
-                }
-            }
-            if values_len != columns.len() {
-                return Err(DatabaseError::ValuesLenMismatch(columns.len(), values_len));
-            }
-            _schema_ref = Some(Arc::new(columns));
-        }
-        let schema_ref = _schema_ref.ok_or(DatabaseError::ColumnsEmpty)?;
+        let schema_ref = self.bind_insert_columns(&table_name, idents, values_len)?;
         let mut rows = Vec::with_capacity(expr_rows.len());
 
         for expr_row in expr_rows {
@@ -109,6 +78,96 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         ))
     }
 
+    /// `INSERT INTO t [(columns)] <query>`, the columns of the query fill the ones of `t` in
+    /// order, cast to their types.
+    pub(crate) fn bind_insert_query(
+        &mut self,
+        name: &ObjectName,
+        idents: &[Ident],
+        query: &Query,
+        is_overwrite: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let table_name = Arc::new(self.writable_table_name(name)?);
+        let mut input = self.bind_query(query)?;
+        let input_schema = input.output_schema().clone();
+        let schema_ref = self.bind_insert_columns(&table_name, idents, input_schema.len())?;
+
+        let exprs = input_schema
+            .iter()
+            .zip(schema_ref.iter())
+            .map(|(input_column, column)| {
+                let mut expr = ScalarExpression::ColumnRef(input_column.clone());
+
+                if input_column.datatype() != column.datatype() {
+                    expr = ScalarExpression::TypeCast {
+                        expr: Box::new(expr),
+                        ty: column.datatype().clone(),
+                    };
+                }
+                ScalarExpression::Alias {
+                    expr: Box::new(expr),
+                    alias: AliasType::Expr(Box::new(ScalarExpression::ColumnRef(column.clone()))),
+                }
+            })
+            .collect();
+        let input = LogicalPlan::new(
+            Operator::Project(ProjectOperator { exprs }),
+            Childrens::Only(input),
+        );
+
+        Ok(LogicalPlan::new(
+            Operator::Insert(InsertOperator {
+                table_name,
+                is_overwrite,
+                is_mapping_by_name: false,
+            }),
+            Childrens::Only(input),
+        ))
+    }
+
+    /// The columns of the table that the `values_len` values of each inserted row fill.
+    fn bind_insert_columns(
+        &mut self,
+        table_name: &TableName,
+        idents: &[Ident],
+        values_len: usize,
+    ) -> Result<SchemaRef, DatabaseError> {
+        let source = self
+            .context
+            .source_and_bind(table_name.clone(), None, None, false)?
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        if let Source::Table(table) = &source {
+            table.check_writable()?;
+        }
+
+        if idents.is_empty() {
+            let schema_buf = self.table_schema_buf.entry(table_name.clone()).or_default();
+            let schema_ref = source.schema_ref(schema_buf);
+            if values_len > schema_ref.len() {
+                return Err(DatabaseError::ValuesLenMismatch(
+                    schema_ref.len(),
+                    values_len,
+                ));
+            }
+            Ok(schema_ref)
+        } else {
+            let mut columns = Vec::with_capacity(idents.len());
+            for ident in idents {
+                match self.bind_column_ref_from_identifiers(
+                    slice::from_ref(ident),
+                    Some(table_name.to_string()),
+                )? {
+                    ScalarExpression::ColumnRef(catalog) => columns.push(catalog),
+                    _ => return Err(DatabaseError::UnsupportedStmt(ident.to_string())),
+                }
+            }
+            if values_len != columns.len() {
+                return Err(DatabaseError::ValuesLenMismatch(columns.len(), values_len));
+            }
+            Ok(Arc::new(columns))
+        }
+    }
+
     pub(crate) fn bind_values(
         &mut self,
         rows: Vec<Vec<DataValue>>,
@@ -1,40 +1,60 @@
 pub mod aggregate;
 mod alter_table;
 mod analyze;
+pub(crate) mod batch_commit;
+mod check_table;
+mod common_table;
 pub mod copy;
 mod create_index;
 mod create_table;
+mod create_task;
 mod create_view;
 mod delete;
 mod describe;
 mod distinct;
 mod drop_index;
 mod drop_table;
+mod drop_task;
 mod drop_view;
 mod explain;
 pub mod expr;
+mod faker;
 mod insert;
+mod pivot;
+mod reindex;
 mod select;
 mod show_table;
+mod show_table_status;
+mod show_task;
 mod show_view;
 mod truncate;
 mod update;
+mod vacuum;
 
-use sqlparser::ast::{Ident, ObjectName, ObjectType, SetExpr, Statement};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use sqlparser::ast::{AddDropSync, Ident, ObjectName, ObjectType, SetExpr, Statement};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
+pub(crate) use crate::binder::common_table::renew_shared_rows;
+use crate::binder::common_table::CommonTable;
+use crate::binder::copy::CopyLoadOptions;
 use crate::catalog::view::View;
 use crate::catalog::{ColumnRef, TableCatalog, TableName};
-use crate::db::{ScalaFunctions, TableFunctions};
+use crate::db::{AttachedDatabases, ForeignDataWrappers, ScalaFunctions, TableFunctions};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::masking::MaskingPolicy;
+use crate::parser::ParserOptions;
+use crate::planner::operator::aggregate::DEFAULT_AGGREGATE_MEMORY_LIMIT;
 use crate::planner::operator::join::JoinType;
+use crate::planner::operator::recursive_union::RecursionLimits;
 use crate::planner::{LogicalPlan, SchemaOutput};
-use crate::storage::{TableCache, Transaction, ViewCache};
+use crate::process::StatementStatistics;
+use crate::storage::{TableCache, TableVersion, Transaction, ViewCache};
 use crate::types::tuple::SchemaRef;
-use crate::types::value::DataValue;
+use crate::types::value::{DataValue, StringCoercion};
 
 pub enum InputRefType {
     AggCall,
@@ -50,15 +70,22 @@ pub enum CommandType {
 pub fn command_type(stmt: &Statement) -> Result<CommandType, DatabaseError> {
     match stmt {
         Statement::CreateTable { .. }
+        | Statement::CreateDatabase { .. }
         | Statement::CreateIndex { .. }
         | Statement::CreateView { .. }
         | Statement::AlterTable { .. }
-        | Statement::Drop { .. } => Ok(CommandType::DDL),
+        | Statement::Drop { .. }
+        | Statement::Msck { repair: true, .. }
+        | Statement::Prepare { .. }
+        | Statement::Deallocate { .. } => Ok(CommandType::DDL),
         Statement::Query(_)
         | Statement::Explain { .. }
         | Statement::ExplainTable { .. }
         | Statement::ShowTables { .. }
-        | Statement::ShowVariable { .. } => Ok(CommandType::DQL),
+        | Statement::ShowVariable { .. }
+        | Statement::SetVariable { .. }
+        | Statement::Kill { .. }
+        | Statement::Msck { repair: false, .. } => Ok(CommandType::DQL),
         Statement::Analyze { .. }
         | Statement::Truncate { .. }
         | Statement::Update { .. }
@@ -94,12 +121,60 @@ pub enum SubQueryType {
 pub enum Source<'a> {
     Table(&'a TableCatalog),
     View(&'a View),
+    // the columns of a reference to a common table expression, named by its aliases
+    CommonTable(SchemaRef),
+}
+
+/// The names a statement looked up as tables or views while it was bound, whose versions tell
+/// whether its plan is still the one it would be bound and optimized into, see
+/// [`Transaction::table_version`].
+#[derive(Debug, Default)]
+pub(crate) struct PlanDependencies {
+    names: RefCell<BTreeSet<TableName>>,
+    // the plan reads what no version follows, e.g. the tables of an attached database
+    unversioned: Cell<bool>,
+}
+
+impl PlanDependencies {
+    fn add(&self, name: &TableName) {
+        self.names.borrow_mut().insert(name.clone());
+    }
+
+    pub(crate) fn set_unversioned(&self) {
+        self.unversioned.set(true);
+    }
+
+    /// The versions of the names looked up and of the tables scanned by the plan, e.g. through
+    /// a view, or `None` if the plan reads what they do not follow.
+    pub(crate) fn versions<T: Transaction>(
+        self,
+        plan: &LogicalPlan,
+        transaction: &T,
+    ) -> Result<Option<Vec<(TableName, TableVersion)>>, DatabaseError> {
+        if self.unversioned.get() {
+            return Ok(None);
+        }
+        let mut names = self.names.into_inner();
+        names.extend(plan.referenced_table());
+
+        names
+            .into_iter()
+            .map(|name| {
+                let version = transaction.table_version(&name)?;
+                Ok((name, version))
+            })
+            .collect::<Result<_, DatabaseError>>()
+            .map(Some)
+    }
 }
 
 #[derive(Clone)]
 pub struct BinderContext<'a, T: Transaction> {
     pub(crate) scala_functions: &'a ScalaFunctions,
     pub(crate) table_functions: &'a TableFunctions,
+    pub(crate) foreign_data_wrappers: &'a ForeignDataWrappers,
+    // databases attached by `ATTACH DATABASE`, whose tables are read as `database.table`
+    pub(crate) attached_databases: Option<&'a AttachedDatabases>,
     pub(crate) table_cache: &'a TableCache,
     pub(crate) view_cache: &'a ViewCache,
     pub(crate) transaction: &'a T,
@@ -119,6 +194,22 @@ pub struct BinderContext<'a, T: Transaction> {
 
     temp_table_id: Arc<AtomicUsize>,
     pub(crate) allow_default: bool,
+    // fraction of the rows sampled by `ANALYZE`
+    pub(crate) statistics_sample_fraction: f64,
+    // ties of each `SELECT` are broken by all of its columns
+    pub(crate) deterministic_results: bool,
+    // how the texts compared or computed with numbers are cast to them
+    pub(crate) string_coercion: StringCoercion,
+    pub(crate) copy_load_options: CopyLoadOptions,
+    pub(crate) recursion_limits: RecursionLimits,
+    pub(crate) aggregate_memory_limit: usize,
+    pub(crate) statement_statistics: Option<&'a StatementStatistics>,
+    // the role of the statement and the policy masking the columns it reads
+    pub(crate) masking: Option<(&'a str, &'a dyn MaskingPolicy)>,
+    // how the SQL a statement is rewritten into, e.g. by `PIVOT`, is parsed as the statement was
+    pub(crate) parser_options: ParserOptions,
+    // records the tables and views looked up, for the plan to be cached
+    pub(crate) dependencies: Option<&'a PlanDependencies>,
 }
 
 impl Source<'_> {
@@ -133,6 +224,9 @@ impl Source<'_> {
                 .get_or_insert_with(|| view.plan.output_schema_direct())
                 .columns()
                 .find(|column| column.name() == name),
+            Source::CommonTable(schema_ref) => {
+                schema_ref.iter().find(|column| column.name() == name)
+            }
         }
         .cloned()
     }
@@ -142,17 +236,21 @@ impl Source<'_> {
         schema_buf: &'a mut Option<SchemaOutput>,
     ) -> Box<dyn Iterator<Item = &'a ColumnRef> + 'a> {
         match self {
-            Source::Table(table) => Box::new(table.columns()),
+            Source::Table(table) => Box::new(table.visible_columns()),
             Source::View(view) => Box::new(
                 schema_buf
                     .get_or_insert_with(|| view.plan.output_schema_direct())
                     .columns(),
             ),
+            Source::CommonTable(schema_ref) => Box::new(schema_ref.iter()),
         }
     }
 
     pub(crate) fn schema_ref(&self, schema_buf: &mut Option<SchemaOutput>) -> SchemaRef {
         match self {
+            Source::Table(table) if table.deleted_at_column().is_some() => {
+                Arc::new(table.visible_columns().cloned().collect())
+            }
             Source::Table(table) => table.schema_ref().clone(),
             Source::View(view) => {
                 match schema_buf.get_or_insert_with(|| view.plan.output_schema_direct()) {
@@ -160,6 +258,7 @@ impl Source<'_> {
                     SchemaOutput::SchemaRef(schema_ref) => schema_ref.clone(),
                 }
             }
+            Source::CommonTable(schema_ref) => schema_ref.clone(),
         }
     }
 }
@@ -171,11 +270,14 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         transaction: &'a T,
         scala_functions: &'a ScalaFunctions,
         table_functions: &'a TableFunctions,
+        foreign_data_wrappers: &'a ForeignDataWrappers,
         temp_table_id: Arc<AtomicUsize>,
     ) -> Self {
         BinderContext {
             scala_functions,
             table_functions,
+            foreign_data_wrappers,
+            attached_databases: None,
             table_cache,
             view_cache,
             transaction,
@@ -189,9 +291,41 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
             sub_queries: Default::default(),
             temp_table_id,
             allow_default: false,
+            statistics_sample_fraction: 1.0,
+            deterministic_results: false,
+            string_coercion: StringCoercion::Strict,
+            copy_load_options: Default::default(),
+            recursion_limits: Default::default(),
+            aggregate_memory_limit: DEFAULT_AGGREGATE_MEMORY_LIMIT,
+            statement_statistics: None,
+            masking: None,
+            parser_options: ParserOptions::default(),
+            dependencies: None,
         }
     }
 
+    /// A context over the same catalogs for a query bound on its own, as a subquery is.
+    pub fn child(&self) -> Self {
+        let mut context = BinderContext::new(
+            self.table_cache,
+            self.view_cache,
+            self.transaction,
+            self.scala_functions,
+            self.table_functions,
+            self.foreign_data_wrappers,
+            self.temp_table_id.clone(),
+        );
+        context.attached_databases = self.attached_databases;
+        context.string_coercion = self.string_coercion;
+        context.statement_statistics = self.statement_statistics;
+        context.recursion_limits = self.recursion_limits;
+        context.aggregate_memory_limit = self.aggregate_memory_limit;
+        context.masking = self.masking;
+        context.parser_options = self.parser_options;
+        context.dependencies = self.dependencies;
+        context
+    }
+
     pub fn temp_table(&mut self) -> TableName {
         Arc::new(format!(
             "_temp_table_{}_",
@@ -222,19 +356,37 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         self.sub_queries.remove(&self.bind_step)
     }
 
+    /// Records the table or view looked up by the name, even if there is none: one created
+    /// with it changes the plan.
+    fn depend_on(&self, name: &TableName) {
+        if let Some(dependencies) = self.dependencies {
+            dependencies.add(name);
+        }
+    }
+
+    pub(crate) fn set_unversioned(&self) {
+        if let Some(dependencies) = self.dependencies {
+            dependencies.set_unversioned();
+        }
+    }
+
     pub fn table(&self, table_name: TableName) -> Result<Option<&TableCatalog>, DatabaseError> {
         if let Some(real_name) = self.table_aliases.get(table_name.as_ref()) {
+            self.depend_on(real_name);
             self.transaction.table(self.table_cache, real_name.clone())
         } else {
+            self.depend_on(&table_name);
             self.transaction.table(self.table_cache, table_name)
         }
     }
 
     pub fn view(&self, view_name: TableName) -> Result<Option<&View>, DatabaseError> {
         if let Some(real_name) = self.table_aliases.get(view_name.as_ref()) {
+            self.depend_on(real_name);
             self.transaction
                 .view(self.table_cache, self.view_cache, real_name.clone())
         } else {
+            self.depend_on(&view_name);
             self.transaction
                 .view(self.table_cache, self.view_cache, view_name.clone())
         }
@@ -249,6 +401,11 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         only_table: bool,
     ) -> Result<Option<Source>, DatabaseError> {
         let mut source = None;
+        self.depend_on(
+            self.table_aliases
+                .get(table_name.as_ref())
+                .unwrap_or(&table_name),
+        );
 
         source = if let Some(real_name) = self.table_aliases.get(table_name.as_ref()) {
             self.transaction.table(self.table_cache, real_name.clone())
@@ -322,6 +479,8 @@ pub struct Binder<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>>
     table_schema_buf: HashMap<TableName, Option<SchemaOutput>>,
     args: &'a A,
     with_pk: Option<TableName>,
+    // the common tables of the `WITH` clauses being bound
+    common_tables: Vec<Arc<CommonTable>>,
     pub(crate) parent: Option<&'b Binder<'a, 'b, T, A>>,
 }
 
@@ -336,10 +495,27 @@ impl<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '
             table_schema_buf: Default::default(),
             args,
             with_pk: None,
+            common_tables: vec![],
             parent,
         }
     }
 
+    /// Names the table a statement writes to, the tables of attached databases are read-only.
+    fn writable_table_name(&self, name: &ObjectName) -> Result<String, DatabaseError> {
+        if let [database, _] = name.0.as_slice() {
+            let database = fold_ident(database);
+
+            if self
+                .context
+                .attached_databases
+                .is_some_and(|attached_databases| attached_databases.contains_key(&database))
+            {
+                return Err(DatabaseError::ReadOnlyAttachedDatabase(database));
+            }
+        }
+        fold_name(name)
+    }
+
     pub fn with_pk(&mut self, table_name: TableName) {
         self.with_pk = Some(table_name);
     }
@@ -360,8 +536,11 @@ impl<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '
                 columns,
                 constraints,
                 if_not_exists,
+                with_options,
                 ..
-            } => self.bind_create_table(name, columns, constraints, *if_not_exists)?,
+            } => {
+                self.bind_create_table(name, columns, constraints, with_options, *if_not_exists)?
+            }
             Statement::Drop {
                 object_type,
                 names,
@@ -391,14 +570,10 @@ impl<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '
                 overwrite,
                 ..
             } => {
-                // TODO: support body on Insert
                 if let SetExpr::Values(values) = source.body.as_ref() {
                     self.bind_insert(table_name, columns, &values.rows, *overwrite, false)?
                 } else {
-                    return Err(DatabaseError::UnsupportedStmt(format!(
-                        "insert body: {:#?}",
-                        source.body
-                    )));
+                    self.bind_insert_query(table_name, columns, source, *overwrite)?
                 }
             }
             Statement::Update {
@@ -424,13 +599,43 @@ impl<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '
                     self.bind_delete(table, selection)?
                 }
             }
-            Statement::Analyze { table_name, .. } => self.bind_analyze(table_name)?,
+            Statement::Analyze {
+                table_name,
+                for_columns,
+                columns,
+                ..
+            } => self.bind_analyze(table_name, *for_columns, columns)?,
             Statement::Truncate { table_name, .. } => self.bind_truncate(table_name)?,
+            Statement::Msck {
+                table_name,
+                repair: true,
+                partition_action: Some(AddDropSync::SYNC),
+            } => self.bind_vacuum(table_name)?,
+            Statement::Msck {
+                table_name,
+                repair: true,
+                ..
+            } => self.bind_reindex(table_name)?,
+            Statement::Msck {
+                table_name,
+                repair: false,
+                ..
+            } => self.bind_check_table(table_name)?,
             Statement::ShowTables { .. } => self.bind_show_tables()?,
-            Statement::ShowVariable { variable } => match &variable[0].value.to_lowercase()[..] {
-                "views" => self.bind_show_views()?,
-                _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
-            },
+            Statement::ShowVariable { variable } => {
+                let variable = variable
+                    .iter()
+                    .map(|ident| ident.value.to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                match &variable[..] {
+                    "views" => self.bind_show_views()?,
+                    "tasks" => self.bind_show_tasks()?,
+                    "table status" => self.bind_show_table_status()?,
+                    _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+                }
+            }
             Statement::Copy {
                 source,
                 to,
@@ -453,8 +658,15 @@ impl<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '
                 columns,
                 if_not_exists,
                 unique,
-                ..
-            } => self.bind_create_index(table_name, name, columns, *if_not_exists, *unique)?,
+                using,
+            } => self.bind_create_index(
+                table_name,
+                name,
+                columns,
+                *if_not_exists,
+                *unique,
+                using.as_ref(),
+            )?,
             Statement::CreateView {
                 or_replace,
                 name,
@@ -462,6 +674,10 @@ impl<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '
                 query,
                 ..
             } => self.bind_create_view(or_replace, name, columns, query)?,
+            Statement::Prepare {
+                name, data_types, ..
+            } => self.bind_create_task(stmt, name, data_types)?,
+            Statement::Deallocate { name, prepare } => self.bind_drop_task(name, *prepare)?,
             _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
         };
         Ok(plan)
@@ -497,14 +713,20 @@ impl<'a, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '
     }
 }
 
-fn lower_ident(ident: &Ident) -> String {
-    ident.value.to_lowercase()
+/// The name an identifier refers to, folded to lower case unless it is quoted, see
+/// [`IdentifierCase`](crate::parser::IdentifierCase).
+pub(crate) fn fold_ident(ident: &Ident) -> String {
+    if ident.quote_style.is_some() {
+        ident.value.clone()
+    } else {
+        ident.value.to_lowercase()
+    }
 }
 
-/// Convert an object name into lower case
-fn lower_case_name(name: &ObjectName) -> Result<String, DatabaseError> {
+/// The name of a single part object name, see [`fold_ident`].
+pub(crate) fn fold_name(name: &ObjectName) -> Result<String, DatabaseError> {
     if name.0.len() == 1 {
-        return Ok(lower_ident(&name.0[0]));
+        return Ok(fold_ident(&name.0[0]));
     }
     Err(DatabaseError::InvalidTable(name.to_string()))
 }
@@ -518,10 +740,12 @@ pub(crate) fn is_valid_identifier(s: &str) -> bool {
 #[cfg(test)]
 pub mod test {
     use crate::binder::{is_valid_identifier, Binder, BinderContext};
-    use crate::catalog::{ColumnCatalog, ColumnDesc, TableCatalog};
+    use crate::catalog::{AuditColumns, ColumnCatalog, ColumnDesc, TableCatalog};
+    use crate::db::DataBaseBuilder;
     use crate::errors::DatabaseError;
     use crate::planner::LogicalPlan;
     use crate::storage::rocksdb::RocksStorage;
+    use crate::storage::tuple_codec::TupleCodecKind;
     use crate::storage::{Storage, TableCache, Transaction, ViewCache};
     use crate::types::ColumnId;
     use crate::types::LogicalType::Integer;
@@ -541,8 +765,8 @@ pub mod test {
 
     impl<S: Storage> TableState<S> {
         pub(crate) fn plan<T: AsRef<str>>(&self, sql: T) -> Result<LogicalPlan, DatabaseError> {
-            let scala_functions = Default::default();
-            let table_functions = Default::default();
+            let (scala_functions, table_functions) = DataBaseBuilder::path(".").into_functions();
+            let foreign_data_wrappers = Default::default();
             let transaction = self.storage.transaction()?;
             let mut binder = Binder::new(
                 BinderContext::new(
@@ -551,6 +775,7 @@ pub mod test {
                     &transaction,
                     &scala_functions,
                     &table_functions,
+                    &foreign_data_wrappers,
                     Arc::new(AtomicUsize::new(0)),
                 ),
                 &[],
@@ -592,7 +817,7 @@ pub mod test {
         path: impl Into<PathBuf> + Send,
     ) -> Result<RocksStorage, DatabaseError> {
         let storage = RocksStorage::new(path)?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
 
         let _ = transaction.create_table(
             table_cache,
@@ -609,6 +834,8 @@ pub mod test {
                     ColumnDesc::new(Integer, None, true, None)?,
                 ),
             ],
+            TupleCodecKind::default(),
+            AuditColumns::default(),
             false,
         )?;
 
@@ -627,6 +854,8 @@ pub mod test {
                     ColumnDesc::new(Integer, None, false, None)?,
                 ),
             ],
+            TupleCodecKind::default(),
+            AuditColumns::default(),
             false,
         )?;
 
@@ -0,0 +1,259 @@
+use crate::binder::{fold_ident, Binder};
+use crate::errors::DatabaseError;
+use crate::parser::parse_sql_with_options;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use itertools::Itertools;
+use sqlparser::ast::{
+    BinaryOperator, Expr, FunctionArg, FunctionArgExpr, Ident, ObjectName, Statement, TableAlias,
+    TableFactor, Value,
+};
+use std::sync::Arc;
+
+pub(crate) const PIVOT: &str = "pivot";
+pub(crate) const UNPIVOT: &str = "unpivot";
+
+type PivotArg<'a> = (Option<&'a Ident>, &'a Expr);
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// `PIVOT` and `UNPIVOT`, as carried by the parser in the table functions
+    /// `pivot(source, agg(expr), column, name => value, ...)` and
+    /// `unpivot(source, value, name, column, ...)`.
+    ///
+    /// A pivot is planned as `agg(CASE WHEN column = value THEN expr END)` for each value grouped
+    /// by the other columns of the source, an unpivot as the union of a projection for each column.
+    pub(crate) fn bind_pivot(
+        &mut self,
+        function: &str,
+        args: &[FunctionArg],
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Named {
+                    name,
+                    arg: FunctionArgExpr::Expr(expr),
+                } => Ok((Some(name), expr)),
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok((None, expr)),
+                _ => Err(DatabaseError::UnsupportedStmt(format!(
+                    "{function}() argument: {arg}"
+                ))),
+            })
+            .try_collect::<_, Vec<_>, _>()?;
+        let [(None, source), (None, first), (None, second), values @ ..] = args.as_slice() else {
+            return Err(DatabaseError::MisMatch(
+                "source, expression, column and values",
+                "the arguments of pivot",
+            ));
+        };
+        if values.is_empty() {
+            return Err(DatabaseError::MisMatch(
+                "at least one value",
+                "the arguments of pivot",
+            ));
+        }
+        let source = match source {
+            Expr::Identifier(ident) => TableFactor::Table {
+                name: ObjectName(vec![ident.clone()]),
+                alias: None,
+                args: None,
+                with_hints: vec![],
+            },
+            Expr::CompoundIdentifier(idents) => TableFactor::Table {
+                name: ObjectName(idents.clone()),
+                alias: None,
+                args: None,
+                with_hints: vec![],
+            },
+            Expr::Subquery(subquery) => TableFactor::Derived {
+                lateral: false,
+                subquery: subquery.clone(),
+                alias: None,
+            },
+            _ => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "{function}() source: {source}"
+                )))
+            }
+        };
+        let sql = if function == PIVOT {
+            self.pivot_sql(&source, first, second, values)?
+        } else {
+            self.unpivot_sql(&source, first, second, values)?
+        };
+        self.bind_rewritten_query(sql, function, alias)
+    }
+
+    /// Binds the query a table function of the `FROM` clause is rewritten into as a table named
+    /// by the function or its alias.
+    pub(crate) fn bind_rewritten_query(
+        &mut self,
+        sql: String,
+        function: &str,
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let Some(Statement::Query(query)) =
+            parse_sql_with_options(sql, self.context.parser_options)?.pop()
+        else {
+            unreachable!()
+        };
+        let plan = Binder::new(self.context.child(), self.args, Some(self)).bind_query(&query)?;
+
+        let table_alias =
+            Arc::new(alias.map_or(function.to_string(), |alias| fold_ident(&alias.name)));
+        let table_name = plan
+            .referenced_table()
+            .pop()
+            .unwrap_or_else(|| table_alias.clone());
+        let alias_column = alias.map_or(&[][..], |alias| alias.columns.as_slice());
+        let mut plan = self.bind_alias(plan, alias_column, table_alias, table_name)?;
+
+        // the columns are also referenced without the table, as those of the source are
+        let names = plan
+            .output_schema()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect_vec();
+        if let Operator::Project(op) = &plan.operator {
+            for (name, expr) in names.into_iter().zip(op.exprs.iter()) {
+                self.context.add_alias(None, name, expr.clone());
+            }
+        }
+        Ok(plan)
+    }
+
+    fn pivot_sql(
+        &self,
+        source: &TableFactor,
+        agg: &Expr,
+        column: &Expr,
+        values: &[PivotArg],
+    ) -> Result<String, DatabaseError> {
+        let Expr::Function(function) = agg else {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "pivot() aggregate: {agg}"
+            )));
+        };
+        let arg = match function.args.as_slice() {
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))] => expr.clone(),
+            [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)] => {
+                Expr::Value(Value::Number("1".to_string(), false))
+            }
+            _ => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "pivot() aggregate: {agg}"
+                )))
+            }
+        };
+        let mut binder = Binder::new(self.context.child(), self.args, Some(self));
+        let mut source_plan = binder.bind_single_table_ref(source, None)?;
+        let mut pivoted = binder.bind_expr(column)?.referenced_columns(true);
+        pivoted.append(&mut binder.bind_expr(&arg)?.referenced_columns(true));
+
+        let quote = self.context.parser_options.dialect.quote_style();
+        let groups = source_plan
+            .output_schema()
+            .iter()
+            .filter(|column| {
+                pivoted
+                    .iter()
+                    .all(|pivoted| pivoted.name() != column.name())
+            })
+            .map(|column| Ident::with_quote(quote, column.name()).to_string())
+            .collect_vec();
+        let mut items = groups.clone();
+
+        for (name, value) in values {
+            let name = match (name, value) {
+                (Some(name), _) => fold_ident(name),
+                (None, Expr::Value(Value::SingleQuotedString(value))) => value.clone(),
+                (None, value) => value.to_string(),
+            };
+            let mut function = function.clone();
+            function.args = vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Case {
+                operand: None,
+                conditions: vec![Expr::BinaryOp {
+                    left: Box::new(column.clone()),
+                    op: BinaryOperator::Eq,
+                    right: Box::new((*value).clone()),
+                }],
+                results: vec![arg.clone()],
+                else_result: None,
+            }))];
+            items.push(format!(
+                "{} AS {}",
+                Expr::Function(function),
+                Ident::with_quote(quote, name)
+            ));
+        }
+        let mut sql = format!("SELECT {} FROM {source}", items.join(", "));
+
+        if !groups.is_empty() {
+            sql.push_str(&format!(" GROUP BY {}", groups.join(", ")));
+        }
+        Ok(sql)
+    }
+
+    fn unpivot_sql(
+        &self,
+        source: &TableFactor,
+        value: &Expr,
+        name: &Expr,
+        columns: &[PivotArg],
+    ) -> Result<String, DatabaseError> {
+        let (Expr::Identifier(value), Expr::Identifier(name)) = (value, name) else {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "unpivot() value and name: {value}, {name}"
+            )));
+        };
+        let mut binder = Binder::new(self.context.child(), self.args, Some(self));
+        let mut source_plan = binder.bind_single_table_ref(source, None)?;
+        let mut ty = None;
+        let mut unpivoted = Vec::with_capacity(columns.len());
+
+        for (label, column) in columns {
+            let Expr::Identifier(ident) = column else {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "unpivot() column: {column}"
+                )));
+            };
+            let column_ty = binder.bind_expr(column)?.return_type();
+            if ty.get_or_insert_with(|| column_ty.clone()) != &column_ty {
+                return Err(DatabaseError::MisMatch(
+                    "columns of the same type",
+                    "the columns of unpivot",
+                ));
+            }
+            unpivoted.push((
+                fold_ident(ident),
+                label.map_or(fold_ident(ident), fold_ident),
+            ));
+        }
+        let quote = self.context.parser_options.dialect.quote_style();
+        let others = source_plan
+            .output_schema()
+            .iter()
+            .filter(|column| unpivoted.iter().all(|(name, _)| name != column.name()))
+            .map(|column| format!("{}, ", Ident::with_quote(quote, column.name())))
+            .join("");
+        let (value, name) = (
+            Ident::with_quote(quote, fold_ident(value)),
+            Ident::with_quote(quote, fold_ident(name)),
+        );
+
+        Ok(unpivoted
+            .into_iter()
+            .map(|(column, label)| {
+                let column = Ident::with_quote(quote, column);
+
+                format!(
+                    "SELECT {others}{} AS {name}, {column} AS {value} FROM {source} WHERE {column} IS NOT NULL",
+                    Value::SingleQuotedString(label)
+                )
+            })
+            .join(" UNION ALL "))
+    }
+}
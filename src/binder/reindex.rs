@@ -0,0 +1,55 @@
+use crate::binder::{fold_ident, Binder, Source};
+use crate::errors::DatabaseError;
+use crate::planner::operator::reindex::ReindexOperator;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::Transaction;
+use crate::types::index::IndexType;
+use crate::types::value::DataValue;
+use sqlparser::ast::ObjectName;
+use std::sync::Arc;
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// `REINDEX TABLE t` rebuilds every secondary index of the table and `REINDEX INDEX t.idx` only the given one,
+    /// the primary key is the table itself and has nothing to rebuild.
+    pub(crate) fn bind_reindex(&mut self, name: &ObjectName) -> Result<LogicalPlan, DatabaseError> {
+        let table_name = name
+            .0
+            .first()
+            .ok_or(DatabaseError::InvalidTable(name.to_string()))?;
+        let table_name = Arc::new(fold_ident(table_name));
+        let index_name = name.0.get(1).map(fold_ident);
+
+        let table = self
+            .context
+            .source_and_bind(table_name.clone(), None, None, true)?
+            .and_then(|source| {
+                if let Source::Table(table) = source {
+                    Some(table)
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let mut index_metas = table.indexes.clone();
+
+        if let Some(index_name) = index_name {
+            index_metas.retain(|index_meta| index_meta.name == index_name);
+
+            if index_metas.is_empty() {
+                return Err(DatabaseError::InvalidIndex);
+            }
+        }
+        index_metas.retain(|index_meta| !matches!(index_meta.ty, IndexType::PrimaryKey { .. }));
+
+        let scan_op = TableScanOperator::build(table_name.clone(), table, true);
+        Ok(LogicalPlan::new(
+            Operator::Reindex(ReindexOperator {
+                table_name,
+                index_metas,
+            }),
+            Childrens::Only(scan_op),
+        ))
+    }
+}
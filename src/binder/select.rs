@@ -9,44 +9,62 @@ use crate::{
             filter::FilterOperator, join::JoinOperator as LJoinOperator, limit::LimitOperator,
             project::ProjectOperator, Operator,
         },
-        operator::{join::JoinType, table_scan::TableScanOperator},
+        operator::{
+            join::JoinType,
+            table_scan::{SampleMethod, TableSample, TableScanOperator},
+        },
     },
     types::value::DataValue,
 };
 
-use super::{
-    lower_case_name, lower_ident, Binder, BinderContext, QueryBindStep, Source, SubQueryType,
-};
+use super::batch_commit::check_batch_commit_hints;
+use super::common_table::renew_shared_rows;
+use super::faker::FAKER;
+use super::pivot::{PIVOT, UNPIVOT};
+use super::{fold_ident, fold_name, Binder, BinderContext, QueryBindStep, Source, SubQueryType};
 
-use crate::catalog::{ColumnCatalog, ColumnRef, ColumnSummary, TableName};
+use crate::catalog::{
+    ColumnCatalog, ColumnRef, ColumnSummary, TableCatalog, TableName, DELETED_AT_COLUMN,
+};
 use crate::errors::DatabaseError;
 use crate::execution::dql::join::joins_nullable;
 use crate::expression::agg::AggKind;
-use crate::expression::{AliasType, BinaryOperator};
+use crate::expression::function::scala::{ArcScalarFunctionImpl, ScalarFunction};
+use crate::expression::visitor::Visitor;
+use crate::expression::{AliasType, BinaryOperator, UnaryOperator};
+use crate::fdw::ArcForeignDataWrapper;
+use crate::function::mask::{MaskEmail, MaskPartial};
+use crate::function::match_against::MatchAgainst;
+use crate::masking::{Masking, MaskingPolicy};
 use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::foreign_scan::ForeignScanOperator;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::{Childrens, LogicalPlan, SchemaOutput};
+use crate::process::{STAT_STATEMENTS, STAT_STATEMENTS_TABLE};
 use crate::storage::Transaction;
 use crate::types::tuple::{Schema, SchemaRef};
 use crate::types::value::Utf8Type;
 use crate::types::{ColumnId, LogicalType};
 use itertools::Itertools;
+use ordered_float::OrderedFloat;
 use sqlparser::ast::{
-    CharLengthUnits, Distinct, Expr, Ident, Join, JoinConstraint, JoinOperator, Offset,
-    OrderByExpr, Query, Select, SelectInto, SelectItem, SetExpr, SetOperator, SetQuantifier,
-    TableAlias, TableFactor, TableWithJoins,
+    CharLengthUnits, Distinct, Expr, FunctionArg, FunctionArgExpr, Ident, Join, JoinConstraint,
+    JoinOperator, Offset, OrderByExpr, Query, Select, SelectInto, SelectItem, SetExpr, SetOperator,
+    SetQuantifier, TableAlias, TableFactor, TableWithJoins,
 };
 
 impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, 'b, T, A> {
     pub(crate) fn bind_query(&mut self, query: &Query) -> Result<LogicalPlan, DatabaseError> {
         let origin_step = self.context.step_now();
 
-        if let Some(_with) = &query.with {
-            // TODO support with clause.
+        let common_tables_len = self.common_tables.len();
+
+        if let Some(with) = &query.with {
+            self.bind_with(with)?;
         }
 
         let mut plan = match query.body.borrow() {
@@ -66,13 +84,26 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             }
         }?;
 
-        let limit = &query.limit;
+        let mut limit = &query.limit;
         let offset = &query.offset;
 
+        // `SELECT TOP n` of the generic dialect, see `SqlDialect`
+        if let SetExpr::Select(select) = query.body.borrow() {
+            if let Some(top) = &select.top {
+                if top.percent || top.with_ties || limit.is_some() {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "`{}` with `PERCENT`, `WITH TIES` or `LIMIT`",
+                        top
+                    )));
+                }
+                limit = &top.quantity;
+            }
+        }
         if limit.is_some() || offset.is_some() {
             plan = self.bind_limit(plan, limit, offset)?;
         }
 
+        self.common_tables.truncate(common_tables_len);
         self.context.step(origin_step);
         Ok(plan)
     }
@@ -112,10 +143,43 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         }
 
         let mut having_orderby = (None, None);
+        // `ORDER BY ALL` orders by each of the selected columns in turn
+        let order_by_all = match orderby {
+            [OrderByExpr {
+                expr: Expr::Identifier(ident),
+                asc,
+                nulls_first,
+            }] if ident.quote_style.is_none() && ident.value.eq_ignore_ascii_case("all") => {
+                Some((asc.unwrap_or(true), nulls_first.unwrap_or(false)))
+            }
+            _ => None,
+        };
+        let orderby = if order_by_all.is_some() {
+            &[][..]
+        } else {
+            orderby
+        };
 
         if select.having.is_some() || !orderby.is_empty() {
             having_orderby = self.extract_having_orderby_aggregate(&select.having, orderby)?;
         }
+        if let Some((asc, nulls_first)) = order_by_all {
+            having_orderby.1 = Some(
+                select_list
+                    .iter()
+                    .map(|expr| SortField::new(expr.clone(), asc, nulls_first))
+                    .collect_vec(),
+            );
+        }
+        if self.context.deterministic_results && !select_list.is_empty() {
+            let sort_fields = having_orderby.1.get_or_insert_with(Vec::new);
+
+            for expr in select_list.iter() {
+                if sort_fields.iter().all(|field| &field.expr != expr) {
+                    sort_fields.push(SortField::new(expr.clone(), true, false));
+                }
+            }
+        }
 
         if !self.context.agg_calls.is_empty() || !self.context.group_by_exprs.is_empty() {
             plan = self.bind_aggregate(
@@ -144,7 +208,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         if let Some(SelectInto { name, .. }) = &select.into {
             plan = LogicalPlan::new(
                 Operator::Insert(InsertOperator {
-                    table_name: Arc::new(lower_case_name(name)?),
+                    table_name: Arc::new(fold_name(name)?),
                     is_overwrite: false,
                     is_mapping_by_name: true,
                 }),
@@ -252,16 +316,74 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         Ok(plan)
     }
 
-    fn bind_single_table_ref(
+    pub(crate) fn bind_single_table_ref(
         &mut self,
         table: &TableFactor,
         joint_type: Option<JoinType>,
     ) -> Result<LogicalPlan, DatabaseError> {
         let plan = match table {
-            TableFactor::Table { name, alias, .. } => {
-                let table_name = lower_case_name(name)?;
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                ..
+            } if name.0.len() == 1
+                && [PIVOT, UNPIVOT].contains(&fold_ident(&name.0[0]).as_str()) =>
+            {
+                self.bind_pivot(&fold_ident(&name.0[0]), args, alias.as_ref())?
+            }
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                ..
+            } if name.0.len() == 1 && fold_ident(&name.0[0]) == FAKER => {
+                self.bind_faker(args, alias.as_ref())?
+            }
+            TableFactor::Table {
+                name,
+                alias,
+                with_hints,
+                ..
+            } => {
+                let with_deleted = with_hints.iter().any(Self::is_deleted_hint);
+                let sample = self.bind_table_sample(with_hints)?;
+                let common_table = match name.0.as_slice() {
+                    [name] => self.common_table(&fold_ident(name)),
+                    _ => None,
+                };
 
-                self._bind_single_table_ref(joint_type, &table_name, alias.as_ref())?
+                if let Some(common_table) = common_table {
+                    if sample.is_some() || with_deleted {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "table hints on the common table expression {}",
+                            name
+                        )));
+                    }
+                    self.bind_common_table(&common_table, alias.as_ref(), joint_type)?
+                } else if let [database, table] = name.0.as_slice() {
+                    if sample.is_some() {
+                        return Err(DatabaseError::UnsupportedStmt(
+                            "TABLESAMPLE on the tables of attached databases".to_string(),
+                        ));
+                    }
+                    self.bind_attached_table_ref(
+                        joint_type,
+                        &fold_ident(database),
+                        &fold_ident(table),
+                        alias.as_ref(),
+                    )?
+                } else {
+                    let table_name = fold_name(name)?;
+
+                    self._bind_single_table_ref(
+                        joint_type,
+                        &table_name,
+                        alias.as_ref(),
+                        sample,
+                        with_deleted,
+                    )?
+                }
             }
             TableFactor::Derived {
                 subquery, alias, ..
@@ -279,7 +401,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                             "Implement virtual tables for multiple table aliases".to_string(),
                         ));
                     }
-                    let table_alias = Arc::new(name.value.to_lowercase());
+                    let table_alias = Arc::new(fold_ident(name));
 
                     plan =
                         self.bind_alias(plan, alias_column, table_alias, tables.pop().unwrap())?;
@@ -298,7 +420,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                         columns: alias_column,
                     }) = alias
                     {
-                        table_alias = Some(Arc::new(name.value.to_lowercase()));
+                        table_alias = Some(Arc::new(fold_ident(name)));
 
                         plan = self.bind_alias(
                             plan,
@@ -342,7 +464,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         } else {
             alias_column
                 .iter()
-                .map(lower_ident)
+                .map(fold_ident)
                 .zip(input_schema.iter().cloned())
                 .collect_vec()
         };
@@ -379,25 +501,64 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         join_type: Option<JoinType>,
         table: &str,
         alias: Option<&TableAlias>,
+        mut sample: Option<TableSample>,
+        with_deleted: bool,
     ) -> Result<LogicalPlan, DatabaseError> {
         let table_name = Arc::new(table.to_string());
         let mut table_alias = None;
         let mut alias_idents = None;
 
         if let Some(TableAlias { name, columns }) = alias {
-            table_alias = Some(Arc::new(name.value.to_lowercase()));
+            table_alias = Some(Arc::new(fold_ident(name)));
             alias_idents = Some(columns);
         }
 
         let with_pk = self.is_scan_with_pk(&table_name);
-        let source = self
-            .context
-            .source_and_bind(table_name.clone(), table_alias.as_ref(), join_type, false)?
-            .ok_or(DatabaseError::SourceNotFound)?;
+        let (foreign_data_wrappers, masking) =
+            (self.context.foreign_data_wrappers, self.context.masking);
+        let source = self.context.source_and_bind(
+            table_name.clone(),
+            table_alias.as_ref(),
+            join_type,
+            false,
+        )?;
         let mut plan = match source {
-            Source::Table(table) => TableScanOperator::build(table_name.clone(), table, with_pk),
-            Source::View(view) => LogicalPlan::clone(&view.plan),
+            Some(Source::Table(table)) => {
+                if let Some(foreign) = &table.foreign {
+                    let wrapper = foreign_data_wrappers.get(&foreign.wrapper).ok_or_else(|| {
+                        DatabaseError::ForeignDataWrapperNotFound(foreign.wrapper.clone())
+                    })?;
+                    ForeignScanOperator::build(
+                        table_name.clone(),
+                        table,
+                        foreign.clone(),
+                        ArcForeignDataWrapper(wrapper.clone()),
+                    )
+                } else {
+                    let mut plan = TableScanOperator::build(table_name.clone(), table, with_pk);
+
+                    if let Operator::TableScan(op) = &mut plan.operator {
+                        op.sample = sample.take();
+                    }
+                    if !with_deleted {
+                        plan = Self::filter_deleted(plan, table);
+                    }
+                    Self::mask_columns(plan, table, masking)?
+                }
+            }
+            Some(Source::View(view)) => {
+                let mut plan = LogicalPlan::clone(&view.plan);
+                renew_shared_rows(&mut plan);
+                plan
+            }
+            Some(Source::CommonTable(_)) => unreachable!(),
+            None => self.bind_stat_statements(&table_name, table_alias.clone(), join_type)?,
         };
+        if sample.is_some() {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "TABLESAMPLE on {table_name}, which is not a table"
+            )));
+        }
 
         if let Some(idents) = alias_idents {
             plan = self.bind_alias(plan, idents, table_alias.unwrap(), table_name.clone())?;
@@ -405,6 +566,268 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         Ok(plan)
     }
 
+    /// `stat_statements`, the statistics of the statements finished on the database, is read by
+    /// the name unless a table or view takes it.
+    fn bind_stat_statements(
+        &mut self,
+        table_name: &TableName,
+        table_alias: Option<TableName>,
+        join_type: Option<JoinType>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let Some(statistics) = self
+            .context
+            .statement_statistics
+            .filter(|_| table_name.as_str() == STAT_STATEMENTS)
+        else {
+            return Err(DatabaseError::SourceNotFound(table_name.to_string()));
+        };
+        // the rows are those of the statements finished by the time it is bound
+        self.context.set_unversioned();
+        let table = &*STAT_STATEMENTS_TABLE;
+        self.context.bind_table.insert(
+            (table_name.clone(), table_alias, join_type),
+            Source::Table(table),
+        );
+
+        Ok(self.bind_values(statistics.rows(), table.schema_ref().clone()))
+    }
+
+    /// Reads the columns of the table the masking policy masks for the role of the statement
+    /// through their masking functions, by projections over the scan keeping their names.
+    ///
+    /// The masked values are computed as temporary columns first, an alias of a column read by
+    /// its input would be read as the value of that column.
+    pub(crate) fn mask_columns(
+        mut plan: LogicalPlan,
+        table: &TableCatalog,
+        masking: Option<(&str, &dyn MaskingPolicy)>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let Some((role, policy)) = masking else {
+            return Ok(plan);
+        };
+        let mut masked_exprs = Vec::new();
+        let exprs = plan
+            .output_schema()
+            .iter()
+            .map(|column| {
+                let column_ref = ScalarExpression::ColumnRef(column.clone());
+                let Some(masking) = column
+                    .table_name()
+                    .filter(|table_name| *table_name == table.name())
+                    .and_then(|_| policy.masking(role, table.name(), column.name()))
+                else {
+                    masked_exprs.push(column_ref.clone());
+                    return Ok(column_ref);
+                };
+                let is_text = matches!(
+                    column.datatype(),
+                    LogicalType::Char(..) | LogicalType::Varchar(..)
+                );
+                let expr = match masking {
+                    Masking::Null => ScalarExpression::Constant(DataValue::Null),
+                    masking if !is_text => {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "column: {} of type {} cannot be masked by {}",
+                            column.name(),
+                            column.datatype(),
+                            masking
+                        )))
+                    }
+                    Masking::Email => ScalarExpression::ScalaFunction(ScalarFunction {
+                        args: vec![column_ref.clone()],
+                        inner: ArcScalarFunctionImpl(MaskEmail::new()),
+                    }),
+                    Masking::Partial { keep_last } => {
+                        ScalarExpression::ScalaFunction(ScalarFunction {
+                            args: vec![
+                                column_ref.clone(),
+                                ScalarExpression::Constant(DataValue::Int32(
+                                    keep_last.min(i32::MAX as usize) as i32,
+                                )),
+                            ],
+                            inner: ArcScalarFunctionImpl(MaskPartial::new()),
+                        })
+                    }
+                };
+                let mut masked_column = ColumnCatalog::clone(column);
+                masked_column.set_ref_table(table.name.clone(), ColumnId::new(), true);
+                let masked_ref = ScalarExpression::ColumnRef(ColumnRef::from(masked_column));
+
+                masked_exprs.push(ScalarExpression::Alias {
+                    expr: Box::new(expr),
+                    alias: AliasType::Expr(Box::new(masked_ref.clone())),
+                });
+                Ok(ScalarExpression::Alias {
+                    expr: Box::new(masked_ref),
+                    alias: AliasType::Expr(Box::new(column_ref)),
+                })
+            })
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+        if exprs == masked_exprs {
+            return Ok(plan);
+        }
+        let plan = LogicalPlan::new(
+            Operator::Project(ProjectOperator {
+                exprs: masked_exprs,
+            }),
+            Childrens::Only(plan),
+        );
+
+        Ok(LogicalPlan::new(
+            Operator::Project(ProjectOperator { exprs }),
+            Childrens::Only(plan),
+        ))
+    }
+
+    /// The statements of a role cannot write the tables it reads masked, whose rows they could
+    /// find by their values otherwise.
+    pub(crate) fn check_unmasked(
+        table: &TableCatalog,
+        masking: Option<(&str, &dyn MaskingPolicy)>,
+    ) -> Result<(), DatabaseError> {
+        let Some((role, policy)) = masking else {
+            return Ok(());
+        };
+        if table
+            .columns()
+            .any(|column| policy.masking(role, table.name(), column.name()).is_some())
+        {
+            return Err(DatabaseError::MaskedTable {
+                role: role.to_string(),
+                table: table.name().to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Hides the rows deleted from a table in soft delete mode.
+    pub(crate) fn filter_deleted(plan: LogicalPlan, table: &TableCatalog) -> LogicalPlan {
+        let Some(column) = table.deleted_at_column() else {
+            return plan;
+        };
+        let predicate = ScalarExpression::IsNull {
+            negated: false,
+            expr: Box::new(ScalarExpression::ColumnRef(column.clone())),
+        };
+        FilterOperator::build(predicate, plan, false)
+    }
+
+    /// `WITH DELETED`, as carried by the parser in the table hint `WITH (deleted)`,
+    /// also reads the rows deleted from a table in soft delete mode.
+    pub(crate) fn is_deleted_hint(hint: &Expr) -> bool {
+        matches!(hint, Expr::Identifier(ident) if ident.value.eq_ignore_ascii_case("deleted"))
+    }
+
+    /// `*` leaves out the hidden column of soft delete, which is only read by its name.
+    fn is_hidden_column(expr: &ScalarExpression) -> bool {
+        matches!(
+            expr.unpack_alias_ref(),
+            ScalarExpression::ColumnRef(column) if column.name() == DELETED_AT_COLUMN
+        )
+    }
+
+    /// `TABLESAMPLE`, as carried by the parser in the table hints
+    /// `WITH (method(percentage), repeatable(seed))`.
+    fn bind_table_sample(&mut self, hints: &[Expr]) -> Result<Option<TableSample>, DatabaseError> {
+        let mut sample = None;
+        let mut seed = None;
+
+        check_batch_commit_hints(hints)?;
+        for hint in hints {
+            if Self::is_deleted_hint(hint) {
+                continue;
+            }
+            let Expr::Function(function) = hint else {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "table hint: {hint}"
+                )));
+            };
+            let [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] = function.args.as_slice()
+            else {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "table hint: {hint}"
+                )));
+            };
+            let ScalarExpression::Constant(value) = self.bind_expr(arg)? else {
+                return Err(DatabaseError::InvalidValue(arg.to_string()));
+            };
+            let method = match fold_name(&function.name)?.as_str() {
+                "bernoulli" => SampleMethod::Bernoulli,
+                "system" => SampleMethod::System,
+                "repeatable" => {
+                    let DataValue::Int64(value) = value.cast(&LogicalType::Bigint)? else {
+                        return Err(DatabaseError::InvalidValue(arg.to_string()));
+                    };
+                    seed = Some(value as u64);
+                    continue;
+                }
+                _ => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "table hint: {hint}"
+                    )))
+                }
+            };
+            let DataValue::Float64(percentage) = value.cast(&LogicalType::Double)? else {
+                return Err(DatabaseError::InvalidValue(arg.to_string()));
+            };
+            if !(0.0..=100.0).contains(&percentage.0) {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "sample percentage: {percentage}"
+                )));
+            }
+            sample = Some((method, percentage));
+        }
+
+        Ok(sample.map(|(method, percentage)| TableSample {
+            method,
+            percentage,
+            seed,
+        }))
+    }
+
+    /// `database.table` reads the table of a database attached by `ATTACH DATABASE`.
+    fn bind_attached_table_ref(
+        &mut self,
+        join_type: Option<JoinType>,
+        database: &str,
+        table: &str,
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let attached = self
+            .context
+            .attached_databases
+            .and_then(|attached_databases| attached_databases.get(database))
+            .ok_or_else(|| DatabaseError::AttachedDatabaseNotFound(database.to_string()))?;
+        // the catalog of the attached database has no version in this one
+        self.context.set_unversioned();
+        let table_name = Arc::new(table.to_string());
+        let mut table_alias = None;
+        let mut alias_idents = None;
+
+        if let Some(TableAlias { name, columns }) = alias {
+            table_alias = Some(Arc::new(fold_ident(name)));
+            alias_idents = Some(columns);
+        }
+        let table_catalog = attached
+            .table(table_name.clone())?
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        self.context.bind_table.insert(
+            (table_name.clone(), table_alias.clone(), join_type),
+            Source::Table(table_catalog),
+        );
+        let mut plan = ForeignScanOperator::build(
+            table_name.clone(),
+            table_catalog,
+            attached.foreign_table(table),
+            ArcForeignDataWrapper(attached.clone()),
+        );
+
+        if let Some(idents) = alias_idents {
+            plan = self.bind_alias(plan, idents, table_alias.unwrap(), table_name)?;
+        }
+        Ok(plan)
+    }
+
     /// Normalize select item.
     ///
     /// - Qualified name, e.g. `SELECT t.a FROM t`
@@ -423,7 +846,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                 SelectItem::UnnamedExpr(expr) => select_items.push(self.bind_expr(expr)?),
                 SelectItem::ExprWithAlias { expr, alias } => {
                     let expr = self.bind_expr(expr)?;
-                    let alias_name = alias.value.to_lowercase();
+                    let alias_name = fold_ident(alias);
 
                     self.context
                         .add_alias(None, alias_name.clone(), expr.clone());
@@ -436,7 +859,9 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                 SelectItem::Wildcard(_) => {
                     if let Operator::Project(op) = &plan.operator {
                         for expr in op.exprs.iter() {
-                            select_items.push(expr.clone());
+                            if !Self::is_hidden_column(expr) {
+                                select_items.push(expr.clone());
+                            }
                         }
                         continue;
                     }
@@ -455,7 +880,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                     }
                 }
                 SelectItem::QualifiedWildcard(table_name, _) => {
-                    let table_name = Arc::new(lower_case_name(table_name)?);
+                    let table_name = Arc::new(fold_name(table_name)?);
                     let schema_buf = self.table_schema_buf.entry(table_name.clone()).or_default();
 
                     Self::bind_table_column_refs(
@@ -488,6 +913,9 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                     && matches!(join_used.map(|used| used.contains(column_name)), Some(true))
             };
         for (_, alias_expr) in context.expr_aliases.iter().filter(|(_, expr)| {
+            if Self::is_hidden_column(expr) {
+                return false;
+            }
             if let ScalarExpression::ColumnRef(col) = expr.unpack_alias_ref() {
                 let column_name = col.name();
 
@@ -513,10 +941,10 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
 
         source = context.table(table_name.clone())?.map(Source::Table);
         if source.is_none() {
-            source = context.view(table_name)?.map(Source::View);
+            source = context.view(table_name.clone())?.map(Source::View);
         }
         for column in source
-            .ok_or(DatabaseError::SourceNotFound)?
+            .ok_or_else(|| DatabaseError::SourceNotFound(table_name.to_string()))?
             .columns(schema_buf)
         {
             let column_name = column.name();
@@ -558,21 +986,29 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             transaction,
             scala_functions,
             table_functions,
+            foreign_data_wrappers,
+            attached_databases,
             temp_table_id,
+            recursion_limits,
+            aggregate_memory_limit,
+            masking,
             ..
         } = &self.context;
-        let mut binder = Binder::new(
-            BinderContext::new(
-                table_cache,
-                view_cache,
-                *transaction,
-                scala_functions,
-                table_functions,
-                temp_table_id.clone(),
-            ),
-            self.args,
-            Some(self),
+        let mut context = BinderContext::new(
+            table_cache,
+            view_cache,
+            *transaction,
+            scala_functions,
+            table_functions,
+            foreign_data_wrappers,
+            temp_table_id.clone(),
         );
+        context.attached_databases = *attached_databases;
+        context.recursion_limits = *recursion_limits;
+        context.aggregate_memory_limit = *aggregate_memory_limit;
+        context.masking = *masking;
+        context.dependencies = self.context.dependencies;
+        let mut binder = Binder::new(context, self.args, Some(self));
         let mut right = binder.bind_single_table_ref(relation, Some(join_type))?;
         self.extend(binder.context);
 
@@ -593,7 +1029,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
     ) -> Result<LogicalPlan, DatabaseError> {
         self.context.step(QueryBindStep::Where);
 
-        let predicate = self.bind_expr(predicate)?;
+        let predicate = Self::match_against_predicate(self.bind_expr(predicate)?);
 
         if let Some(sub_queries) = self.context.sub_queries_at_now() {
             for sub_query in sub_queries {
@@ -618,6 +1054,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                             }],
                             vec![],
                             false,
+                            self.context.aggregate_memory_limit,
                         );
                         let filter = FilterOperator::build(
                             ScalarExpression::Binary {
@@ -697,6 +1134,51 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         Ok(FilterOperator::build(predicate, children, false))
     }
 
+    /// `WHERE MATCH (column) AGAINST ('query')` keeps the rows relevant to the query,
+    /// i.e. with a positive score, which is also what the full-text index is scanned by.
+    fn match_against_predicate(expr: ScalarExpression) -> ScalarExpression {
+        match expr {
+            ScalarExpression::ScalaFunction(function)
+                if function.summary().name == MatchAgainst::NAME =>
+            {
+                ScalarExpression::Binary {
+                    op: BinaryOperator::Gt,
+                    left_expr: Box::new(ScalarExpression::ScalaFunction(function)),
+                    right_expr: Box::new(ScalarExpression::Constant(DataValue::Float64(
+                        OrderedFloat(0.0),
+                    ))),
+                    evaluator: None,
+                    ty: LogicalType::Boolean,
+                }
+            }
+            ScalarExpression::Binary {
+                op: op @ (BinaryOperator::And | BinaryOperator::Or),
+                left_expr,
+                right_expr,
+                evaluator,
+                ty,
+            } => ScalarExpression::Binary {
+                op,
+                left_expr: Box::new(Self::match_against_predicate(*left_expr)),
+                right_expr: Box::new(Self::match_against_predicate(*right_expr)),
+                evaluator,
+                ty,
+            },
+            ScalarExpression::Unary {
+                op: UnaryOperator::Not,
+                expr,
+                evaluator,
+                ty,
+            } => ScalarExpression::Unary {
+                op: UnaryOperator::Not,
+                expr: Box::new(Self::match_against_predicate(*expr)),
+                evaluator,
+                ty,
+            },
+            expr => expr,
+        }
+    }
+
     fn bind_having(
         &mut self,
         children: LogicalPlan,
@@ -728,6 +1210,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             Operator::Sort(SortOperator {
                 sort_fields,
                 limit: None,
+                presorted: 0,
             }),
             Childrens::Only(children),
         )
@@ -869,12 +1352,12 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                         .map(|column| ScalarExpression::ColumnRef(column.clone()))
                 };
                 for ident in idents {
-                    let name = lower_ident(ident);
+                    let name = fold_ident(ident);
                     if let (Some(left_column), Some(right_column)) = (
                         fn_column(left_schema, &name),
                         fn_column(right_schema, &name),
                     ) {
-                        on_keys.push((left_column, right_column));
+                        on_keys.push(Self::join_keys(left_column, right_column));
                     } else {
                         return Err(DatabaseError::InvalidColumn("not found column".to_string()))?;
                     }
@@ -898,7 +1381,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                         left_schema.iter().find(|column| column.name() == *name),
                         right_schema.iter().find(|column| column.name() == *name),
                     ) {
-                        on_keys.push((
+                        on_keys.push(Self::join_keys(
                             ScalarExpression::ColumnRef(left_column.clone()),
                             ScalarExpression::ColumnRef(right_column.clone()),
                         ));
@@ -912,6 +1395,33 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         }
     }
 
+    /// The keys are hashed and compared by value, so numeric keys of different types
+    /// are both cast to the type they are compared in, e.g. `Integer = UInteger` as `Bigint`.
+    fn join_keys(
+        left_key: ScalarExpression,
+        right_key: ScalarExpression,
+    ) -> (ScalarExpression, ScalarExpression) {
+        let (left_ty, right_ty) = (left_key.return_type(), right_key.return_type());
+
+        if left_ty == right_ty || !left_ty.is_numeric() || !right_ty.is_numeric() {
+            return (left_key, right_key);
+        }
+        let Ok(ty) = LogicalType::max_logical_type(&left_ty, &right_ty) else {
+            return (left_key, right_key);
+        };
+        let fn_cast = |key: ScalarExpression, key_ty: LogicalType| {
+            if key_ty == ty {
+                key
+            } else {
+                ScalarExpression::TypeCast {
+                    expr: Box::new(key),
+                    ty: ty.clone(),
+                }
+            }
+        };
+        (fn_cast(left_key, left_ty), fn_cast(right_key, right_ty))
+    }
+
     /// for sqlrs
     /// original idea from datafusion planner.rs
     /// Extracts equijoin ON condition be a single Eq or multiple conjunctive Eqs
@@ -937,6 +1447,15 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             |left_schema: &Schema, right_schema: &Schema, summary: &ColumnSummary| {
                 fn_contains(left_schema, summary) || fn_contains(right_schema, summary)
             };
+        let fn_all_contains = |expr: &ScalarExpression| -> Result<bool, DatabaseError> {
+            let mut columns = JoinColumns::default();
+            columns.visit(expr)?;
+
+            Ok(columns
+                .0
+                .iter()
+                .all(|column| fn_or_contains(left_schema, right_schema, column.summary())))
+        };
 
         match expr.unpack_alias() {
             ScalarExpression::Binary {
@@ -955,13 +1474,13 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                                 if fn_contains(left_schema, l.summary())
                                     && fn_contains(right_schema, r.summary())
                                 {
-                                    accum.push((*left_expr, *right_expr));
+                                    accum.push(Self::join_keys(*left_expr, *right_expr));
                                 } else if fn_contains(left_schema, r.summary())
                                     && fn_contains(right_schema, l.summary())
                                 {
-                                    accum.push((*right_expr, *left_expr));
+                                    accum.push(Self::join_keys(*right_expr, *left_expr));
                                 } else if fn_or_contains(left_schema, right_schema, l.summary())
-                                    || fn_or_contains(left_schema, right_schema, r.summary())
+                                    && fn_or_contains(left_schema, right_schema, r.summary())
                                 {
                                     accum_filter.push(ScalarExpression::Binary {
                                         left_expr,
@@ -986,11 +1505,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                             }
                             _other => {
                                 // example: baz > 1
-                                if left_expr.referenced_columns(true).iter().all(|column| {
-                                    fn_or_contains(left_schema, right_schema, column.summary())
-                                }) && right_expr.referenced_columns(true).iter().all(|column| {
-                                    fn_or_contains(left_schema, right_schema, column.summary())
-                                }) {
+                                if fn_all_contains(&left_expr)? && fn_all_contains(&right_expr)? {
                                     accum_filter.push(ScalarExpression::Binary {
                                         left_expr,
                                         right_expr,
@@ -1029,11 +1544,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                         });
                     }
                     _ => {
-                        if left_expr.referenced_columns(true).iter().all(|column| {
-                            fn_or_contains(left_schema, right_schema, column.summary())
-                        }) && right_expr.referenced_columns(true).iter().all(|column| {
-                            fn_or_contains(left_schema, right_schema, column.summary())
-                        }) {
+                        if fn_all_contains(&left_expr)? && fn_all_contains(&right_expr)? {
                             accum_filter.push(ScalarExpression::Binary {
                                 left_expr,
                                 right_expr,
@@ -1046,11 +1557,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                 }
             }
             expr => {
-                if expr
-                    .referenced_columns(true)
-                    .iter()
-                    .all(|column| fn_or_contains(left_schema, right_schema, column.summary()))
-                {
+                if fn_all_contains(&expr)? {
                     // example: baz > 1
                     accum_filter.push(expr);
                 }
@@ -1061,6 +1568,28 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
     }
 }
 
+/// Columns read by a join condition, where the column of a subquery is the one its alias names
+#[derive(Default)]
+struct JoinColumns<'a>(Vec<&'a ColumnRef>);
+
+impl<'a> Visitor<'a> for JoinColumns<'a> {
+    fn visit_column_ref(&mut self, column: &'a ColumnRef) -> Result<(), DatabaseError> {
+        self.0.push(column);
+        Ok(())
+    }
+
+    fn visit_alias(
+        &mut self,
+        expr: &'a ScalarExpression,
+        ty: &'a AliasType,
+    ) -> Result<(), DatabaseError> {
+        match ty {
+            AliasType::Expr(alias_expr) => self.visit(alias_expr),
+            AliasType::Name(_) => self.visit(expr),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::binder::test::build_t1_table;
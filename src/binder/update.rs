@@ -1,4 +1,4 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::Binder;
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::update::UpdateOperator;
@@ -20,7 +20,11 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         // FIXME: Make it better to detect the current BindStep
         self.context.allow_default = true;
         if let TableFactor::Table { name, .. } = &to.relation {
-            let table_name = Arc::new(lower_case_name(name)?);
+            let table_name = Arc::new(self.writable_table_name(name)?);
+            if let Some(table) = self.context.table(table_name.clone())? {
+                table.check_writable()?;
+                Self::check_unmasked(table, self.context.masking)?;
+            }
             self.with_pk(table_name.clone());
 
             let mut plan = self.bind_table_ref(to)?;
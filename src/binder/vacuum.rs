@@ -0,0 +1,36 @@
+use crate::binder::Binder;
+use crate::errors::DatabaseError;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::vacuum::VacuumOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::tuple_codec::TupleCodecKind;
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use sqlparser::ast::ObjectName;
+use std::sync::Arc;
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// `VACUUM [TABLE] t` writes the rows of a versioned table again with its current columns,
+    /// which reclaims the values of the dropped columns, the table is only read if it has
+    /// been altered since it was created or last vacuumed.
+    pub(crate) fn bind_vacuum(&mut self, name: &ObjectName) -> Result<LogicalPlan, DatabaseError> {
+        let table_name = Arc::new(self.writable_table_name(name)?);
+        let table = self
+            .context
+            .table(table_name.clone())?
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        table.check_writable()?;
+
+        let childrens =
+            if table.tuple_codec == TupleCodecKind::Versioned && table.schema_versions.len() > 1 {
+                Childrens::Only(TableScanOperator::build(table_name.clone(), table, true))
+            } else {
+                Childrens::None
+            };
+        Ok(LogicalPlan::new(
+            Operator::Vacuum(VacuumOperator { table_name }),
+            childrens,
+        ))
+    }
+}
@@ -46,7 +46,7 @@ pub enum ColumnRelation {
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, ReferenceSerialization)]
 pub struct ColumnSummary {
-    pub name: String,
+    pub name: Arc<str>,
     pub relation: ColumnRelation,
 }
 
@@ -66,7 +66,7 @@ impl ColumnCatalog {
     pub fn new(column_name: String, nullable: bool, column_desc: ColumnDesc) -> ColumnCatalog {
         ColumnCatalog {
             summary: ColumnSummary {
-                name: column_name,
+                name: Arc::from(column_name),
                 relation: ColumnRelation::None,
             },
             nullable,
@@ -92,7 +92,7 @@ impl ColumnCatalog {
     pub(crate) fn new_dummy(column_name: String) -> ColumnCatalog {
         ColumnCatalog {
             summary: ColumnSummary {
-                name: column_name,
+                name: Arc::from(column_name),
                 relation: ColumnRelation::None,
             },
             nullable: true,
@@ -142,7 +142,7 @@ impl ColumnCatalog {
     }
 
     pub fn set_name(&mut self, name: String) {
-        self.summary.name = name;
+        self.summary.name = Arc::from(name);
     }
 
     pub fn set_ref_table(&mut self, table_name: TableName, column_id: ColumnId, is_temp: bool) {
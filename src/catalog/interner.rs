@@ -0,0 +1,106 @@
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+const MIN_SHRINK_LEN: usize = 1024;
+
+/// Shares one `Arc<str>` per distinct name of the columns of the tables of a storage, so that
+/// the names cloned across catalogs, expressions and plans point at the same allocation, see
+/// [`Transaction::interner`](crate::storage::Transaction::interner).
+///
+/// Only the names are interned: the strings of the tuples are decoded into the `String` of
+/// [`DataValue::Utf8`](crate::types::value::DataValue::Utf8), owned by each value.
+///
+/// The strings no longer referenced but by the interner, e.g. of the dropped columns, are
+/// released once it doubled in size since it was last shrunk.
+#[derive(Debug)]
+pub struct StringInterner {
+    strings: Mutex<InternedStrings>,
+}
+
+#[derive(Debug)]
+struct InternedStrings {
+    set: HashSet<Arc<str>>,
+    shrink_at: usize,
+}
+
+impl Default for StringInterner {
+    fn default() -> Self {
+        StringInterner {
+            strings: Mutex::new(InternedStrings {
+                set: HashSet::new(),
+                shrink_at: MIN_SHRINK_LEN,
+            }),
+        }
+    }
+}
+
+impl InternedStrings {
+    fn shrink(&mut self) {
+        self.set.retain(|string| Arc::strong_count(string) > 1);
+        self.shrink_at = (self.set.len() * 2).max(MIN_SHRINK_LEN);
+    }
+}
+
+impl StringInterner {
+    pub fn intern(&self, string: &str) -> Arc<str> {
+        let mut strings = self.strings.lock();
+
+        if let Some(interned) = strings.set.get(string) {
+            return interned.clone();
+        }
+        if strings.set.len() >= strings.shrink_at {
+            strings.shrink();
+        }
+        let interned: Arc<str> = Arc::from(string);
+        strings.set.insert(interned.clone());
+        interned
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.lock().set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.lock().set.is_empty()
+    }
+
+    /// Release the strings which are only referenced by the interner itself.
+    pub fn shrink(&self) {
+        self.strings.lock().shrink();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::interner::{StringInterner, MIN_SHRINK_LEN};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_intern() {
+        let interner = StringInterner::default();
+
+        let c1 = interner.intern("c1");
+        let c1_again = interner.intern(String::from("c1").as_str());
+        let c2 = interner.intern("c2");
+
+        assert!(Arc::ptr_eq(&c1, &c1_again));
+        assert!(!Arc::ptr_eq(&c1, &c2));
+        assert_eq!(interner.len(), 2);
+
+        drop(c2);
+        interner.shrink();
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.intern("c1").as_ref(), "c1");
+
+        // the strings dropped are released as the interner grows
+        let kept = (0..MIN_SHRINK_LEN)
+            .map(|i| interner.intern(&format!("kept_{i}")))
+            .collect::<Vec<_>>();
+        for i in 0..MIN_SHRINK_LEN * 4 {
+            interner.intern(&format!("dropped_{i}"));
+        }
+        assert!(interner.len() <= (kept.len() + 1) * 2);
+        assert!(Arc::ptr_eq(&kept[0], &interner.intern("kept_0")));
+    }
+}
@@ -4,5 +4,7 @@ pub(crate) use self::column::*;
 pub(crate) use self::table::*;
 
 pub mod column;
+pub mod interner;
 pub mod table;
+pub mod task;
 pub mod view;
@@ -1,10 +1,14 @@
 use crate::catalog::{ColumnCatalog, ColumnRef, ColumnRelation};
 use crate::errors::DatabaseError;
+use crate::storage::tuple_codec::{SchemaVersion, TupleCodecKind};
 use crate::types::index::{IndexMeta, IndexMetaRef, IndexType};
 use crate::types::tuple::SchemaRef;
+use crate::types::value::DataValue;
 use crate::types::{ColumnId, LogicalType};
+use chrono::Utc;
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
+use sqlparser::ast::CharLengthUnits;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::{slice, vec};
@@ -17,7 +21,7 @@ pub type PrimaryKeyIndices = Arc<Vec<usize>>;
 pub struct TableCatalog {
     pub(crate) name: TableName,
     /// Mapping from column names to column ids
-    column_idxs: BTreeMap<String, (ColumnId, usize)>,
+    column_idxs: BTreeMap<Arc<str>, (ColumnId, usize)>,
     columns: BTreeMap<ColumnId, usize>,
     pub(crate) indexes: Vec<IndexMetaRef>,
 
@@ -25,12 +29,123 @@ pub struct TableCatalog {
     primary_keys: Vec<(usize, ColumnRef)>,
     primary_key_indices: PrimaryKeyIndices,
     primary_key_type: Option<LogicalType>,
+    /// The remote source of a foreign table, which holds no local data
+    pub(crate) foreign: Option<ForeignTable>,
+    pub(crate) tuple_codec: TupleCodecKind,
+    /// The columns after each ADD/DROP COLUMN, indexed by the version stored in the rows
+    pub(crate) schema_versions: Vec<SchemaVersion>,
+    /// The columns dropped since the last VACUUM, whose values are still in the old rows
+    pub(crate) dropped_columns: Vec<(String, LogicalType)>,
+    pub(crate) audit_columns: AuditColumns,
+}
+
+/// The hidden column DELETE sets instead of removing the rows of a table in soft delete mode
+pub(crate) const DELETED_AT_COLUMN: &str = "__deleted_at";
+
+/// The columns filled by INSERT, UPDATE and DELETE themselves,
+/// chosen by `CREATE TABLE ... WITH (created_at = 'c1', updated_at = 'c2', row_version = 'c3', soft_delete = true)`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub struct AuditColumns {
+    /// Set to the time of the INSERT when no value is given
+    pub created_at: Option<String>,
+    /// Set to the time of the INSERT or UPDATE when no value is given
+    pub updated_at: Option<String>,
+    /// Starts at 1 and is incremented by each UPDATE of the row,
+    /// so `UPDATE ... WHERE version = ?` only applies to the row it was read at
+    pub row_version: Option<String>,
+    /// DELETE sets the time in the hidden column [`DELETED_AT_COLUMN`], whose rows are only read
+    /// `WITH DELETED` and removed by `PURGE TABLE`
+    pub soft_delete: bool,
+}
+
+/// Maps a table to a table of a remote source read through the named foreign data wrapper
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub struct ForeignTable {
+    pub wrapper: String,
+    pub connection: String,
+    pub remote_table: String,
 }
 
 //TODO: can add some like Table description and other information as attributes
 #[derive(Debug, Clone, PartialEq, ReferenceSerialization)]
 pub struct TableMeta {
     pub(crate) table_name: TableName,
+    pub(crate) foreign: Option<ForeignTable>,
+    pub(crate) tuple_codec: TupleCodecKind,
+    pub(crate) schema_versions: Vec<SchemaVersion>,
+    pub(crate) dropped_columns: Vec<(String, LogicalType)>,
+    pub(crate) audit_columns: AuditColumns,
+}
+
+impl AuditColumns {
+    /// Clears the columns dropped from the table.
+    pub(crate) fn remove(&mut self, column_name: &str) {
+        for column in [
+            &mut self.created_at,
+            &mut self.updated_at,
+            &mut self.row_version,
+        ] {
+            if column.as_deref() == Some(column_name) {
+                *column = None;
+            }
+        }
+        if column_name == DELETED_AT_COLUMN {
+            self.soft_delete = false;
+        }
+    }
+
+    /// The value of a column of a new row given none.
+    pub(crate) fn insert_value(
+        &self,
+        column: &ColumnCatalog,
+    ) -> Result<Option<DataValue>, DatabaseError> {
+        let name = Some(column.name());
+
+        if self.created_at.as_deref() == name || self.updated_at.as_deref() == name {
+            Self::now(column.datatype()).map(Some)
+        } else if self.row_version.as_deref() == name {
+            Self::next_version(&DataValue::Null, column.datatype()).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Whether an UPDATE writes the column even if it does not set it.
+    pub(crate) fn is_updated(&self, column_name: &str) -> bool {
+        self.updated_at.as_deref() == Some(column_name)
+            || self.row_version.as_deref() == Some(column_name)
+    }
+
+    /// The value of a column of a row written again by an UPDATE that does not set it.
+    pub(crate) fn update_value(
+        &self,
+        column: &ColumnCatalog,
+        value: &DataValue,
+    ) -> Result<Option<DataValue>, DatabaseError> {
+        let name = Some(column.name());
+
+        if self.updated_at.as_deref() == name {
+            Self::now(column.datatype()).map(Some)
+        } else if self.row_version.as_deref() == name {
+            Self::next_version(value, column.datatype()).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The current time as a value of a `created_at` or `updated_at` column.
+    fn now(ty: &LogicalType) -> Result<DataValue, DatabaseError> {
+        DataValue::Time64(Utc::now().timestamp(), 0, false).cast(ty)
+    }
+
+    /// The version of a row after it is written, 1 for a new row.
+    fn next_version(version: &DataValue, ty: &LogicalType) -> Result<DataValue, DatabaseError> {
+        let version = match version.clone().cast(&LogicalType::Bigint)? {
+            DataValue::Int64(version) => version.checked_add(1).ok_or(DatabaseError::OverFlow)?,
+            _ => 1,
+        };
+        DataValue::Int64(version).cast(ty)
+    }
 }
 
 impl TableCatalog {
@@ -38,12 +153,35 @@ impl TableCatalog {
         &self.name
     }
 
+    /// Foreign tables are only read through their wrapper.
+    pub(crate) fn check_writable(&self) -> Result<(), DatabaseError> {
+        if self.foreign.is_some() {
+            return Err(DatabaseError::ReadOnlyForeignTable(self.name.to_string()));
+        }
+        Ok(())
+    }
+
     pub(crate) fn get_unique_index(&self, col_id: &ColumnId) -> Option<&IndexMetaRef> {
         self.indexes
             .iter()
             .find(|meta| matches!(meta.ty, IndexType::Unique) && &meta.column_ids[0] == col_id)
     }
 
+    /// The hidden column of a table in soft delete mode.
+    pub(crate) fn deleted_at_column(&self) -> Option<&ColumnRef> {
+        if self.audit_columns.soft_delete {
+            self.get_column_by_name(DELETED_AT_COLUMN)
+        } else {
+            None
+        }
+    }
+
+    /// The columns but the hidden one, as `*` and INSERT without column names see them.
+    pub(crate) fn visible_columns(&self) -> impl Iterator<Item = &ColumnRef> {
+        self.columns()
+            .filter(|column| !self.audit_columns.soft_delete || column.name() != DELETED_AT_COLUMN)
+    }
+
     #[allow(dead_code)]
     pub(crate) fn get_column_by_id(&self, id: &ColumnId) -> Option<&ColumnRef> {
         self.columns.get(id).map(|i| &self.schema_ref[*i])
@@ -95,6 +233,29 @@ impl TableCatalog {
             .collect_vec()
     }
 
+    /// Records the current columns, but `dropped`, as the version the rows are written with.
+    pub(crate) fn push_schema_version(&mut self, dropped: Option<&ColumnId>) {
+        let version = self
+            .columns()
+            .filter_map(|column| {
+                let column_id = column.id()?;
+                (Some(&column_id) != dropped).then(|| (column_id, column.datatype().clone()))
+            })
+            .collect_vec();
+        self.schema_versions.push(version);
+    }
+
+    pub(crate) fn meta(&self) -> TableMeta {
+        TableMeta {
+            table_name: self.name.clone(),
+            foreign: self.foreign.clone(),
+            tuple_codec: self.tuple_codec,
+            schema_versions: self.schema_versions.clone(),
+            dropped_columns: self.dropped_columns.clone(),
+            audit_columns: self.audit_columns.clone(),
+        }
+    }
+
     /// Add a column to the table catalog.
     pub(crate) fn add_column(
         &mut self,
@@ -113,7 +274,7 @@ impl TableCatalog {
         };
 
         self.column_idxs
-            .insert(col.name().to_string(), (col_id, self.schema_ref.len()));
+            .insert(col.summary().name.clone(), (col_id, self.schema_ref.len()));
         self.columns.insert(col_id, self.schema_ref.len());
 
         let mut schema = Vec::clone(&self.schema_ref);
@@ -163,7 +324,16 @@ impl TableCatalog {
                 .clone();
             val_tys.push(val_ty)
         }
-        let value_ty = if val_tys.len() == 1 {
+        let value_ty = if matches!(ty, IndexType::FullText | IndexType::Trigram) {
+            // the tokens or trigrams, which are shorter than the text
+            LogicalType::Varchar(None, CharLengthUnits::Characters)
+        } else if matches!(ty, IndexType::Vector) {
+            // the list of the nearest centroid
+            LogicalType::UInteger
+        } else if matches!(ty, IndexType::ZOrder) {
+            // the Z-order code of the point
+            LogicalType::UBigint
+        } else if val_tys.len() == 1 {
             val_tys.pop().unwrap()
         } else {
             LogicalType::Tuple(val_tys)
@@ -198,6 +368,11 @@ impl TableCatalog {
             primary_keys: vec![],
             primary_key_indices: Default::default(),
             primary_key_type: None,
+            foreign: None,
+            tuple_codec: TupleCodecKind::default(),
+            schema_versions: vec![],
+            dropped_columns: vec![],
+            audit_columns: AuditColumns::default(),
         };
         let mut generator = Generator::new();
         for col_catalog in columns.into_iter() {
@@ -210,6 +385,7 @@ impl TableCatalog {
 
         table_catalog.primary_keys = primary_keys;
         table_catalog.primary_key_indices = primary_key_indices;
+        table_catalog.push_schema_version(None);
 
         Ok(table_catalog)
     }
@@ -218,6 +394,7 @@ impl TableCatalog {
         name: TableName,
         column_refs: Vec<ColumnRef>,
         indexes: Vec<IndexMetaRef>,
+        meta: Option<TableMeta>,
     ) -> Result<TableCatalog, DatabaseError> {
         let mut column_idxs = BTreeMap::new();
         let mut columns = BTreeMap::new();
@@ -227,11 +404,22 @@ impl TableCatalog {
                 "column does not belong to table".to_string(),
             ))?;
 
-            column_idxs.insert(column_ref.name().to_string(), (column_id, i));
+            column_idxs.insert(column_ref.summary().name.clone(), (column_id, i));
             columns.insert(column_id, i);
         }
         let schema_ref = Arc::new(column_refs.clone());
         let (primary_keys, primary_key_indices) = Self::build_primary_keys(&schema_ref);
+        let (foreign, tuple_codec, schema_versions, dropped_columns, audit_columns) = meta
+            .map(|meta| {
+                (
+                    meta.foreign,
+                    meta.tuple_codec,
+                    meta.schema_versions,
+                    meta.dropped_columns,
+                    meta.audit_columns,
+                )
+            })
+            .unwrap_or_default();
 
         Ok(TableCatalog {
             name,
@@ -242,6 +430,11 @@ impl TableCatalog {
             primary_keys,
             primary_key_indices,
             primary_key_type: None,
+            foreign,
+            tuple_codec,
+            schema_versions,
+            dropped_columns,
+            audit_columns,
         })
     }
 
@@ -270,12 +463,6 @@ impl TableCatalog {
     }
 }
 
-impl TableMeta {
-    pub(crate) fn empty(table_name: TableName) -> Self {
-        TableMeta { table_name }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
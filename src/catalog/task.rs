@@ -0,0 +1,19 @@
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// A statement run by the scheduler whenever the time matches its cron `schedule`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, ReferenceSerialization)]
+pub struct Task {
+    pub name: String,
+    pub schedule: String,
+    pub sql: String,
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Task {} '{}': {}", self.name, self.schedule, self.sql)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use crate::db::{Database, DatabaseIter};
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::filter::FilterOperator;
+use crate::planner::operator::limit::LimitOperator;
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::sort::{SortField, SortOperator};
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::Storage;
+
+/// A plan built step by step without SQL, from the scan of [`Database::table`].
+///
+/// The expressions of the steps refer to the columns of the rows before them, see
+/// [`DataFrame::column`]:
+///
+/// ```ignore
+/// let mut df = database.table("t1")?;
+/// let a = df.column("a")?;
+/// let rows = df
+///     .filter(ScalarExpression::Binary {
+///         op: BinaryOperator::Gt,
+///         left_expr: Box::new(a.clone()),
+///         right_expr: Box::new(ScalarExpression::Constant(DataValue::Int32(1))),
+///         evaluator: None,
+///         ty: LogicalType::Boolean,
+///     })
+///     .project(vec![a])
+///     .limit(None, Some(10))
+///     .execute()?;
+/// ```
+pub struct DataFrame<'a, S: Storage> {
+    database: &'a Database<S>,
+    plan: LogicalPlan,
+}
+
+impl<'a, S: Storage> DataFrame<'a, S> {
+    pub(crate) fn new(database: &'a Database<S>, plan: LogicalPlan) -> Self {
+        DataFrame { database, plan }
+    }
+
+    /// The column named `name` of the rows of the plan so far.
+    pub fn column(&mut self, name: &str) -> Result<ScalarExpression, DatabaseError> {
+        self.plan
+            .output_schema()
+            .iter()
+            .find(|column| column.name() == name)
+            .map(|column| ScalarExpression::ColumnRef(column.clone()))
+            .ok_or_else(|| DatabaseError::ColumnNotFound(name.to_string()))
+    }
+
+    /// Keeps the rows the predicate is true for.
+    pub fn filter(self, predicate: ScalarExpression) -> Self {
+        self.map(|plan| FilterOperator::build(predicate, plan, false))
+    }
+
+    pub fn project(self, exprs: Vec<ScalarExpression>) -> Self {
+        self.map(|plan| {
+            LogicalPlan::new(
+                Operator::Project(ProjectOperator { exprs }),
+                Childrens::Only(plan),
+            )
+        })
+    }
+
+    /// Rows of the aggregate calls followed by the expressions grouped by.
+    pub fn aggregate(
+        self,
+        groupby_exprs: Vec<ScalarExpression>,
+        agg_calls: Vec<ScalarExpression>,
+    ) -> Self {
+        let aggregate_memory_limit = self.database.state.aggregate_memory_limit();
+
+        self.map(|plan| {
+            AggregateOperator::build(
+                plan,
+                agg_calls,
+                groupby_exprs,
+                false,
+                aggregate_memory_limit,
+            )
+        })
+    }
+
+    pub fn sort(self, sort_fields: Vec<SortField>) -> Self {
+        self.map(|plan| {
+            LogicalPlan::new(
+                Operator::Sort(SortOperator {
+                    sort_fields,
+                    limit: None,
+                    presorted: 0,
+                }),
+                Childrens::Only(plan),
+            )
+        })
+    }
+
+    pub fn limit(self, offset: Option<usize>, limit: Option<usize>) -> Self {
+        self.map(|plan| LimitOperator::build(offset, limit, plan))
+    }
+
+    /// The plan built so far, before it is optimized.
+    pub fn plan(&self) -> &LogicalPlan {
+        &self.plan
+    }
+
+    /// Optimizes the plan and runs it, as the statements given as SQL.
+    pub fn execute(self) -> Result<DatabaseIter<'a, S>, DatabaseError> {
+        self.database.execute_plan(self.plan)
+    }
+
+    fn map(self, f: impl FnOnce(LogicalPlan) -> LogicalPlan) -> Self {
+        DataFrame {
+            database: self.database,
+            plan: f(self.plan),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::expression::agg::AggKind;
+    use crate::expression::{BinaryOperator, ScalarExpression};
+    use crate::planner::operator::sort::SortField;
+    use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dataframe() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values (0, 1), (1, 1), (2, 2)")?
+            .done()?;
+
+        let mut df = kite_sql.table("t1")?;
+        let (a, b) = (df.column("a")?, df.column("b")?);
+        assert!(matches!(
+            df.column("c"),
+            Err(DatabaseError::ColumnNotFound(_))
+        ));
+        let iter = df
+            .filter(ScalarExpression::Binary {
+                op: BinaryOperator::Gt,
+                left_expr: Box::new(a.clone()),
+                right_expr: Box::new(ScalarExpression::Constant(DataValue::Int32(0))),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            })
+            .sort(vec![SortField::new(a.clone(), false, false)])
+            .project(vec![b.clone()])
+            .limit(None, Some(1))
+            .execute()?;
+        let rows: Vec<_> = iter
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .try_collect()?;
+        assert_eq!(rows, vec![vec![DataValue::Int32(2)]]);
+
+        let count = ScalarExpression::AggCall {
+            distinct: false,
+            kind: AggKind::Count,
+            args: vec![a],
+            ty: LogicalType::Integer,
+        };
+        let iter = kite_sql
+            .table("t1")?
+            .aggregate(vec![b.clone()], vec![count.clone()])
+            .sort(vec![SortField::new(b.clone(), true, false)])
+            .project(vec![b, count])
+            .execute()?;
+        assert_eq!(iter.schema().len(), 2);
+        let rows: Vec<_> = iter
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .try_collect()?;
+        assert_eq!(
+            rows,
+            vec![
+                vec![DataValue::Int32(1), DataValue::Int32(2)],
+                vec![DataValue::Int32(2), DataValue::Int32(1)],
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -1,41 +1,82 @@
-use crate::binder::{command_type, Binder, BinderContext, CommandType};
+use crate::binder::batch_commit::BatchCommit;
+use crate::binder::copy::CopyLoadOptions;
+use crate::binder::{
+    command_type, fold_name, renew_shared_rows, Binder, BinderContext, CommandType,
+    PlanDependencies,
+};
+use crate::catalog::{ColumnCatalog, ColumnRef, TableName};
+use crate::dataframe::DataFrame;
 use crate::errors::DatabaseError;
+use crate::execution::custom::{plan_custom_executors, CustomExecutor};
 use crate::execution::{build_write, Executor};
 use crate::expression::function::scala::ScalarFunctionImpl;
 use crate::expression::function::table::TableFunctionImpl;
 use crate::expression::function::FunctionSummary;
+use crate::fdw::attached::AttachedDatabase;
+#[cfg(feature = "postgres_fdw")]
+use crate::fdw::postgres::PostgresDataWrapper;
+use crate::fdw::ForeignDataWrapper;
 use crate::function::char_length::CharLength;
 use crate::function::current_date::CurrentDate;
 use crate::function::current_timestamp::CurrentTimeStamp;
+use crate::function::fake::Fake;
 use crate::function::lower::Lower;
+use crate::function::mask::{MaskEmail, MaskPartial};
 use crate::function::numbers::Numbers;
 use crate::function::octet_length::OctetLength;
+use crate::function::random::{Random, RandomRange, SessionRng, SetSeed};
+use crate::function::round::Round;
 use crate::function::upper::Upper;
+use crate::function::uuid::Uuid;
+use crate::masking::MaskingPolicy;
+use crate::optimizer::core::rule::NormalizationRule;
 use crate::optimizer::heuristic::batch::HepBatchStrategy;
 use crate::optimizer::heuristic::optimizer::HepOptimizer;
 use crate::optimizer::rule::implementation::ImplementationRuleImpl;
-use crate::optimizer::rule::normalization::NormalizationRuleImpl;
-use crate::parser::parse_sql;
+use crate::optimizer::rule::normalization::{ArcNormalizationRule, NormalizationRuleImpl};
+use crate::parser::{parse_sql_with_options, IdentifierCase, ParserOptions, SqlDialect};
+use crate::planner::operator::aggregate::DEFAULT_AGGREGATE_MEMORY_LIMIT;
+use crate::planner::operator::recursive_union::RecursionLimits;
+use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::LogicalPlan;
-use crate::storage::rocksdb::RocksStorage;
-use crate::storage::{StatisticsMetaCache, Storage, TableCache, Transaction, ViewCache};
+use crate::process::{HeavyQueryScope, ProcessGuard, ProcessList};
+use crate::scheduler::Scheduler;
+use crate::storage::rocksdb::{
+    IsolationLevel, RocksStorage, StorageOptions, WalOptions, WriteThrottle,
+};
+use crate::storage::{
+    StatisticsMetaCache, Storage, TableCache, TableVersion, Transaction, ViewCache, WritePressure,
+};
+#[cfg(feature = "substrait")]
+use crate::substrait::consumer::Consumer;
+#[cfg(feature = "substrait")]
+use crate::substrait::producer::Producer;
 use crate::types::tuple::{SchemaRef, Tuple};
-use crate::types::value::DataValue;
+use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::{DataValue, StringCoercion, Utf8Type};
 use crate::utils::lru::SharedLruCache;
 use ahash::HashMap;
+use itertools::Itertools;
 use parking_lot::lock_api::{ArcRwLockReadGuard, ArcRwLockWriteGuard};
 use parking_lot::{RawRwLock, RwLock};
-use std::hash::RandomState;
+use sqlparser::ast::{CharLengthUnits, Expr, ObjectName, ObjectType, Value};
+use std::hash::{Hash, RandomState};
+use std::iter;
 use std::marker::PhantomData;
 use std::mem;
 use std::ops::{Coroutine, CoroutineState};
 use std::path::PathBuf;
 use std::pin::Pin;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub(crate) type ScalaFunctions = HashMap<FunctionSummary, Arc<dyn ScalarFunctionImpl>>;
 pub(crate) type TableFunctions = HashMap<FunctionSummary, Arc<dyn TableFunctionImpl>>;
+pub(crate) type ForeignDataWrappers = HashMap<String, Arc<dyn ForeignDataWrapper>>;
+pub(crate) type CustomExecutors = Vec<Arc<dyn CustomExecutor>>;
+pub(crate) type NormalizationRules = Vec<NormalizationRuleImpl>;
+pub(crate) type AttachedDatabases = HashMap<String, Arc<AttachedDatabase>>;
 
 pub type Statement = sqlparser::ast::Statement;
 
@@ -45,10 +86,94 @@ pub(crate) enum MetaDataLock {
     Write(ArcRwLockWriteGuard<RawRwLock, ()>),
 }
 
+/// Entries the caches of a database keep, set by `DataBaseBuilder::cache_options`.
+///
+/// A cache is split into shards locked one by one, its entries are rounded up to a multiple of
+/// them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CacheOptions {
+    /// Statistics of the indexes, read to estimate the cost of the plans
+    pub statistics_meta: usize,
+    /// Catalogs of the tables
+    pub table: usize,
+    /// Plans of the views
+    pub view: usize,
+    /// Plans of the queries, checked against the versions of the tables they read
+    pub plan: usize,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        CacheOptions {
+            statistics_meta: 256,
+            table: 48,
+            view: 12,
+            plan: 64,
+        }
+    }
+}
+
+/// The lookups of a cache since the database was opened, see [`Database::cache_stats`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    fn new<K: Hash + Eq, V>(cache: &SharedLruCache<K, V>) -> Self {
+        CacheStats {
+            hits: cache.hits(),
+            misses: cache.misses(),
+            entries: cache.len(),
+            capacity: cache.capacity(),
+        }
+    }
+
+    /// The part of the lookups that found their entry, `0` before the first lookup.
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DatabaseCacheStats {
+    pub statistics_meta: CacheStats,
+    pub table: CacheStats,
+    pub view: CacheStats,
+    pub plan: CacheStats,
+}
+
 pub struct DataBaseBuilder {
     path: PathBuf,
     scala_functions: ScalaFunctions,
     table_functions: TableFunctions,
+    foreign_data_wrappers: ForeignDataWrappers,
+    custom_executors: CustomExecutors,
+    normalization_rules: NormalizationRules,
+    masking_policy: Option<Arc<dyn MaskingPolicy>>,
+    statistics_sample_fraction: f64,
+    deterministic_results: bool,
+    string_coercion: StringCoercion,
+    dialect: SqlDialect,
+    identifier_case: IdentifierCase,
+    copy_load_options: CopyLoadOptions,
+    recursion_limits: RecursionLimits,
+    aggregate_memory_limit: usize,
+    lock_timeout: Duration,
+    heavy_query_cost: Option<usize>,
+    max_heavy_queries: usize,
+    replication_log: Option<usize>,
+    cache_options: CacheOptions,
+    storage_options: StorageOptions,
+    read_only: bool,
 }
 
 impl DataBaseBuilder {
@@ -57,6 +182,25 @@ impl DataBaseBuilder {
             path: path.into(),
             scala_functions: Default::default(),
             table_functions: Default::default(),
+            foreign_data_wrappers: Default::default(),
+            custom_executors: Default::default(),
+            normalization_rules: Default::default(),
+            masking_policy: None,
+            statistics_sample_fraction: 1.0,
+            deterministic_results: false,
+            string_coercion: StringCoercion::Strict,
+            dialect: SqlDialect::PostgreSql,
+            identifier_case: IdentifierCase::Fold,
+            copy_load_options: Default::default(),
+            recursion_limits: Default::default(),
+            aggregate_memory_limit: DEFAULT_AGGREGATE_MEMORY_LIMIT,
+            lock_timeout: Duration::from_secs(10),
+            heavy_query_cost: None,
+            max_heavy_queries: 1,
+            replication_log: None,
+            cache_options: Default::default(),
+            storage_options: Default::default(),
+            read_only: false,
         };
         builder = builder.register_scala_function(CharLength::new("char_length".to_lowercase()));
         builder =
@@ -64,9 +208,24 @@ impl DataBaseBuilder {
         builder = builder.register_scala_function(CurrentDate::new());
         builder = builder.register_scala_function(CurrentTimeStamp::new());
         builder = builder.register_scala_function(Lower::new());
+        builder = builder.register_scala_function(MaskEmail::new());
+        builder = builder.register_scala_function(MaskPartial::new());
         builder = builder.register_scala_function(OctetLength::new());
+        builder = builder.register_scala_function(Round::new());
+        builder = builder.register_scala_function(Round::with_places());
         builder = builder.register_scala_function(Upper::new());
+        // the random functions of a database share its generator, reseeded by `setseed(n)`
+        let rng = SessionRng::default();
+        builder = builder.register_scala_function(Fake::new(rng.clone()));
+        builder = builder.register_scala_function(Random::new(rng.clone()));
+        builder = builder.register_scala_function(RandomRange::new(rng.clone()));
+        builder = builder.register_scala_function(SetSeed::new(rng.clone()));
+        builder = builder.register_scala_function(Uuid::new(rng));
         builder = builder.register_table_function(Numbers::new());
+        #[cfg(feature = "postgres_fdw")]
+        {
+            builder = builder.register_foreign_data_wrapper(Arc::new(PostgresDataWrapper));
+        }
         builder
     }
 
@@ -84,33 +243,359 @@ impl DataBaseBuilder {
         self
     }
 
+    /// The functions registered so far, for the tests binding statements without a database.
+    #[cfg(test)]
+    pub(crate) fn into_functions(self) -> (ScalaFunctions, TableFunctions) {
+        (self.scala_functions, self.table_functions)
+    }
+
+    pub fn register_foreign_data_wrapper(mut self, wrapper: Arc<dyn ForeignDataWrapper>) -> Self {
+        self.foreign_data_wrappers
+            .insert(wrapper.name().to_string(), wrapper);
+        self
+    }
+
+    /// Runs the operators of the plans the executor matches by it, the executors registered
+    /// first are matched first.
+    pub fn register_executor(mut self, executor: Arc<dyn CustomExecutor>) -> Self {
+        self.custom_executors.push(executor);
+        self
+    }
+
+    /// Rewrites the bound plans by the rule ahead of the built-in rules, e.g. to add the filter
+    /// of the tenant to the scans of its tables.
+    ///
+    /// The rules registered are applied in order until the plan stops changing, so a rule must
+    /// leave a plan it has rewritten unchanged.
+    pub fn register_normalization_rule(
+        mut self,
+        rule: Arc<dyn NormalizationRule + Send + Sync>,
+    ) -> Self {
+        self.normalization_rules
+            .push(NormalizationRuleImpl::Custom(ArcNormalizationRule(rule)));
+        self
+    }
+
+    /// Decides the columns the role of [`Database::run_as`] reads masked, see [`crate::masking`].
+    pub fn masking_policy(mut self, policy: Arc<dyn MaskingPolicy>) -> Self {
+        self.masking_policy = Some(policy);
+        self
+    }
+
+    /// Fraction of the rows `ANALYZE` samples to build the statistics, in `(0, 1]`.
+    pub fn statistics_sample_fraction(mut self, fraction: f64) -> Self {
+        self.statistics_sample_fraction = fraction;
+        self
+    }
+
+    /// Orders the results of every `SELECT` by all of its columns after its own `ORDER BY`, so that
+    /// rows tied or unordered come out the same on each run, e.g. for the assertions of tests.
+    pub fn deterministic_results(mut self, deterministic_results: bool) -> Self {
+        self.deterministic_results = deterministic_results;
+        self
+    }
+
+    /// How a text compared or computed with a number is cast to it, e.g. in `'12abc' + 1`:
+    /// [`StringCoercion::Strict`] by default fails on the text, [`StringCoercion::Lenient`]
+    /// reads the number it starts with, `12`, or `NULL` if it does not start with one.
+    pub fn string_coercion(mut self, string_coercion: StringCoercion) -> Self {
+        self.string_coercion = string_coercion;
+        self
+    }
+
+    /// The dialect of the SQL run on the database, [`SqlDialect::PostgreSql`] by default, also set
+    /// by `SET dialect = 'mysql'`.
+    pub fn dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// How the identifiers name the tables and columns, by default [`IdentifierCase::Fold`] to
+    /// lower case unless they are quoted.
+    pub fn identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+
+    /// Threads casting and encoding the rows read by `COPY FROM`, by default as many as the
+    /// available parallelism, while the statement writes them in the order of the file.
+    pub fn copy_threads(mut self, threads: usize) -> Self {
+        self.copy_load_options.threads = threads.max(1);
+        self
+    }
+
+    /// Whether `COPY FROM` writes no index entries with the rows and rebuilds the indexes of the
+    /// table once they are written, as `REINDEX TABLE` does, which is faster for large loads.
+    pub fn copy_defer_indexes(mut self, defer_indexes: bool) -> Self {
+        self.copy_load_options.defer_indexes = defer_indexes;
+        self
+    }
+
+    /// Iterations of the recursive term of a `WITH RECURSIVE` query giving new rows, `1000` by
+    /// default, a query going deeper fails instead of running on a cycle of its data.
+    pub fn max_recursion_depth(mut self, depth: usize) -> Self {
+        self.recursion_limits.depth = depth;
+        self
+    }
+
+    /// Rows of a `WITH RECURSIVE` query, `1000000` by default, a query giving more fails.
+    pub fn max_recursion_rows(mut self, rows: usize) -> Self {
+        self.recursion_limits.rows = rows;
+        self
+    }
+
+    /// Bytes of the groups of a `GROUP BY` or a `DISTINCT` kept in memory, their keys and the
+    /// states of their aggregate functions by an estimate, `64MiB` by default. The rows of the
+    /// groups found once the memory is taken are spilled to temporary files and aggregated once
+    /// the input is read, while the groups in memory keep taking theirs.
+    pub fn aggregate_memory_limit(mut self, bytes: usize) -> Self {
+        self.aggregate_memory_limit = bytes.max(1);
+        self
+    }
+
+    /// How long a DDL statement waits for the statements and transactions reading the tables to
+    /// end, `10s` by default, before it fails with [`DatabaseError::LockTimeout`]. The other
+    /// statements and transactions wait as long for a DDL statement or transaction to end.
+    ///
+    /// The results of a statement hold the metadata lock until they are dropped, so that the
+    /// schema does not change under its scans.
+    pub fn lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Statements whose plan is estimated to read at least `cost` tuples are heavy, at most
+    /// [`max_heavy_queries`](Self::max_heavy_queries) of them execute at once and their scans yield
+    /// the thread more often, so that point lookups stay fast while large scans run.
+    ///
+    /// The estimate comes from the statistics of `ANALYZE`, a full scan of a table not analyzed is heavy.
+    pub fn heavy_query_cost(mut self, cost: usize) -> Self {
+        self.heavy_query_cost = Some(cost);
+        self
+    }
+
+    /// Heavy statements executing at once, the others wait for one of them to finish, `1` by default.
+    ///
+    /// A heavy statement run by a thread still reading the rows of another one does not wait, it
+    /// shares the slot of the thread.
+    pub fn max_heavy_queries(mut self, max_heavy_queries: usize) -> Self {
+        self.max_heavy_queries = max_heavy_queries;
+        self
+    }
+
+    /// Keeps the changes of the last `batches` transactions committed, so that
+    /// [`Database::publish`] can stream them to followers. A follower falling further behind
+    /// catches up by a snapshot of the database.
+    pub fn replication_log(mut self, batches: usize) -> Self {
+        self.replication_log = Some(batches);
+        self
+    }
+
+    /// Entries of the caches of the table catalogs, view plans, index statistics and query
+    /// plans, see [`CacheOptions`] for the defaults and [`Database::cache_stats`] for their hits.
+    pub fn cache_options(mut self, cache_options: CacheOptions) -> Self {
+        self.cache_options = cache_options;
+        self
+    }
+
+    /// Only runs the statements that read, the others fail with
+    /// [`DatabaseError::ReadOnlyDatabase`], and runs no task of `CREATE TASK`.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Bytes of the block cache of the storage, `40MiB` by default.
+    pub fn block_cache_size(mut self, bytes: usize) -> Self {
+        self.storage_options.block_cache_size = bytes;
+        self
+    }
+
+    /// Threads of the storage flushing and compacting in the background.
+    pub fn parallelism(mut self, threads: usize) -> Self {
+        self.storage_options.parallelism = Some(threads.max(1));
+        self
+    }
+
+    /// Keys between two keys stored whole in the data and index blocks of the storage, see
+    /// [`StorageOptions::key_restart_interval`].
+    pub fn key_restart_interval(mut self, interval: usize) -> Self {
+        self.storage_options.key_restart_interval = Some(interval.max(1));
+        self
+    }
+
+    /// How the write-ahead log keeps the commits, see [`WalOptions`].
+    pub fn wal_options(mut self, wal: WalOptions) -> Self {
+        self.storage_options.wal = wal;
+        self
+    }
+
+    /// What the transactions see of the others, [`IsolationLevel::ReadCommitted`] by default.
+    pub fn isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
+        self.storage_options.isolation_level = isolation_level;
+        self
+    }
+
+    /// When the writes fail with [`DatabaseError::Throttled`] instead of stalling in the
+    /// storage, see [`WriteThrottle`].
+    pub fn write_throttle(mut self, write_throttle: WriteThrottle) -> Self {
+        self.storage_options.write_throttle = write_throttle;
+        self
+    }
+
     pub fn build(self) -> Result<Database<RocksStorage>, DatabaseError> {
-        let storage = RocksStorage::new(self.path)?;
-        let meta_cache = SharedLruCache::new(256, 8, RandomState::new())?;
-        let table_cache = SharedLruCache::new(48, 4, RandomState::new())?;
-        let view_cache = SharedLruCache::new(12, 4, RandomState::new())?;
+        let mut storage = RocksStorage::with_options(self.path.clone(), &self.storage_options)?;
+        match self.replication_log {
+            Some(0) => {
+                return Err(DatabaseError::InvalidValue(
+                    "replication log must keep at least 1 batch".to_string(),
+                ))
+            }
+            Some(batches) => storage = storage.with_change_log(batches),
+            None => (),
+        }
+        self.build_with_storage(storage)
+    }
+
+    /// Builds the database on a storage opened by the caller, e.g. one wrapping a [`RocksStorage`].
+    ///
+    /// The path, the options of RocksDB and the replication log are left to the storage.
+    pub fn build_with_storage<S: Storage + Send + Sync + 'static>(
+        self,
+        storage: S,
+    ) -> Result<Database<S>, DatabaseError> {
+        if !(self.statistics_sample_fraction > 0.0 && self.statistics_sample_fraction <= 1.0) {
+            return Err(DatabaseError::InvalidValue(format!(
+                "statistics sample fraction must be in (0, 1], got {}",
+                self.statistics_sample_fraction
+            )));
+        }
+        if self.max_heavy_queries == 0 {
+            return Err(DatabaseError::InvalidValue(
+                "max heavy queries must be at least 1".to_string(),
+            ));
+        }
+        let CacheOptions {
+            statistics_meta,
+            table,
+            view,
+            plan,
+        } = self.cache_options;
+        if statistics_meta == 0 || table == 0 || view == 0 || plan == 0 {
+            return Err(DatabaseError::InvalidValue(
+                "caches must keep at least 1 entry".to_string(),
+            ));
+        }
+        let meta_cache =
+            SharedLruCache::new(statistics_meta.next_multiple_of(8), 8, RandomState::new())?;
+        let table_cache = SharedLruCache::new(table.next_multiple_of(4), 4, RandomState::new())?;
+        let view_cache = SharedLruCache::new(view.next_multiple_of(4), 4, RandomState::new())?;
+        let plan_cache = SharedLruCache::new(plan.next_multiple_of(4), 4, RandomState::new())?;
+
+        let mdl: Arc<RwLock<()>> = Default::default();
+        let state = Arc::new(State {
+            scala_functions: self.scala_functions,
+            table_functions: self.table_functions,
+            foreign_data_wrappers: self.foreign_data_wrappers,
+            custom_executors: self.custom_executors,
+            normalization_rules: self.normalization_rules,
+            masking_policy: self.masking_policy,
+            attached_databases: Default::default(),
+            statistics_sample_fraction: self.statistics_sample_fraction,
+            deterministic_results: self.deterministic_results,
+            string_coercion: self.string_coercion,
+            dialect: RwLock::new(self.dialect),
+            identifier_case: self.identifier_case,
+            copy_load_options: self.copy_load_options,
+            recursion_limits: self.recursion_limits,
+            aggregate_memory_limit: self.aggregate_memory_limit,
+            lock_timeout: self.lock_timeout,
+            processes: ProcessList::new(self.heavy_query_cost, self.max_heavy_queries),
+            read_only: self.read_only,
+            replica: AtomicBool::new(false),
+            meta_cache,
+            table_cache,
+            view_cache,
+            plan_cache,
+            _p: Default::default(),
+        });
+        let scheduler = (!self.read_only).then(|| {
+            Scheduler::start(Database {
+                storage: storage.clone(),
+                mdl: mdl.clone(),
+                state: state.clone(),
+                _scheduler: None,
+            })
+        });
 
         Ok(Database {
             storage,
-            mdl: Default::default(),
-            state: Arc::new(State {
-                scala_functions: self.scala_functions,
-                table_functions: self.table_functions,
-                meta_cache,
-                table_cache,
-                view_cache,
-                _p: Default::default(),
-            }),
+            mdl,
+            state,
+            _scheduler: scheduler,
         })
     }
 }
 
+/// The caches a statement reads and writes the catalogs and statistics through, those of the
+/// database or those a DDL transaction stages until it commits.
+pub(crate) type Caches<'a> = (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache);
+
+/// A query by the values of its placeholders and the role running it, which its plan is bound
+/// with.
+#[derive(PartialEq, Eq, Hash)]
+struct PlanKey {
+    statement: Statement,
+    params: Vec<(&'static str, DataValue)>,
+    role: Option<String>,
+}
+
+/// The optimized plan of a query and the versions of the tables and views it was bound to.
+#[derive(Clone)]
+struct CachedPlan {
+    plan: LogicalPlan,
+    versions: Vec<(TableName, TableVersion)>,
+}
+
+impl CachedPlan {
+    /// Whether the tables and views of the plan are still at the versions it was bound to.
+    fn is_fresh<T: Transaction>(&self, transaction: &T) -> Result<bool, DatabaseError> {
+        for (name, version) in self.versions.iter() {
+            if transaction.table_version(name)? != *version {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+type PlanCache = SharedLruCache<PlanKey, CachedPlan>;
+
 pub(crate) struct State<S> {
     scala_functions: ScalaFunctions,
     table_functions: TableFunctions,
+    foreign_data_wrappers: ForeignDataWrappers,
+    custom_executors: CustomExecutors,
+    normalization_rules: NormalizationRules,
+    masking_policy: Option<Arc<dyn MaskingPolicy>>,
+    attached_databases: RwLock<AttachedDatabases>,
+    statistics_sample_fraction: f64,
+    deterministic_results: bool,
+    string_coercion: StringCoercion,
+    dialect: RwLock<SqlDialect>,
+    identifier_case: IdentifierCase,
+    copy_load_options: CopyLoadOptions,
+    recursion_limits: RecursionLimits,
+    aggregate_memory_limit: usize,
+    lock_timeout: Duration,
+    processes: ProcessList,
+    read_only: bool,
+    // set while the database follows a primary, see `Database::follow`
+    replica: AtomicBool,
     meta_cache: StatisticsMetaCache,
     table_cache: TableCache,
     view_cache: ViewCache,
+    plan_cache: PlanCache,
     _p: PhantomData<S>,
 }
 
@@ -121,6 +606,9 @@ impl<S: Storage> State<S> {
     fn table_functions(&self) -> &TableFunctions {
         &self.table_functions
     }
+    fn foreign_data_wrappers(&self) -> &ForeignDataWrappers {
+        &self.foreign_data_wrappers
+    }
     pub(crate) fn meta_cache(&self) -> &StatisticsMetaCache {
         &self.meta_cache
     }
@@ -130,30 +618,82 @@ impl<S: Storage> State<S> {
     pub(crate) fn view_cache(&self) -> &ViewCache {
         &self.view_cache
     }
+    fn caches(&self) -> Caches<'_> {
+        (&self.table_cache, &self.view_cache, &self.meta_cache)
+    }
+    /// Empty caches of the same sizes as those of the database.
+    fn new_caches(&self) -> Result<(TableCache, ViewCache, StatisticsMetaCache), DatabaseError> {
+        Ok((
+            SharedLruCache::new(self.table_cache.capacity(), 4, RandomState::new())?,
+            SharedLruCache::new(self.view_cache.capacity(), 4, RandomState::new())?,
+            SharedLruCache::new(self.meta_cache.capacity(), 8, RandomState::new())?,
+        ))
+    }
+    fn plan_cache(&self) -> &PlanCache {
+        &self.plan_cache
+    }
+    pub(crate) fn aggregate_memory_limit(&self) -> usize {
+        self.aggregate_memory_limit
+    }
+    /// Drops the catalogs and statistics cached, they are read again from the storage.
+    fn clear_caches(&self) {
+        self.table_cache.clear();
+        self.view_cache.clear();
+        self.meta_cache.clear();
+        self.plan_cache.clear();
+    }
+    /// Returns whether the database was a replica already.
+    pub(crate) fn set_replica(&self, replica: bool) -> bool {
+        self.replica.swap(replica, Ordering::AcqRel)
+    }
+
+    /// A read-only database, or a follower whose data comes from the primary, only runs the
+    /// statements that read.
+    pub(crate) fn check_writable(&self, command_type: &CommandType) -> Result<(), DatabaseError> {
+        if matches!(command_type, CommandType::DQL) {
+            return Ok(());
+        }
+        if self.read_only {
+            return Err(DatabaseError::ReadOnlyDatabase);
+        }
+        if self.replica.load(Ordering::Acquire) {
+            return Err(DatabaseError::ReadOnlyReplica);
+        }
+        Ok(())
+    }
 
-    #[allow(clippy::too_many_arguments)]
-    pub(crate) fn build_plan<A: AsRef<[(&'static str, DataValue)]>>(
+    /// The plan of a statement bound by the settings of the database, before it is optimized.
+    pub(crate) fn bind_plan<A: AsRef<[(&'static str, DataValue)]>>(
+        &self,
+        transaction: &<S as Storage>::TransactionType<'_>,
+        (table_cache, view_cache, _): Caches<'_>,
         stmt: &Statement,
         params: A,
-        table_cache: &TableCache,
-        view_cache: &ViewCache,
-        meta_cache: &StatisticsMetaCache,
-        transaction: &<S as Storage>::TransactionType<'_>,
-        scala_functions: &ScalaFunctions,
-        table_functions: &TableFunctions,
+        role: Option<&str>,
+        dependencies: Option<&PlanDependencies>,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let mut binder = Binder::new(
-            BinderContext::new(
-                table_cache,
-                view_cache,
-                transaction,
-                scala_functions,
-                table_functions,
-                Arc::new(AtomicUsize::new(0)),
-            ),
-            &params,
-            None,
+        let attached_databases = self.attached_databases.read();
+        let mut context = BinderContext::new(
+            table_cache,
+            view_cache,
+            transaction,
+            self.scala_functions(),
+            self.table_functions(),
+            self.foreign_data_wrappers(),
+            Arc::new(AtomicUsize::new(0)),
         );
+        context.attached_databases = Some(&*attached_databases);
+        context.statistics_sample_fraction = self.statistics_sample_fraction;
+        context.deterministic_results = self.deterministic_results;
+        context.string_coercion = self.string_coercion;
+        context.copy_load_options = self.copy_load_options;
+        context.recursion_limits = self.recursion_limits;
+        context.aggregate_memory_limit = self.aggregate_memory_limit;
+        context.statement_statistics = Some(&self.processes.statistics);
+        context.masking = role.zip(self.masking_policy.as_deref());
+        context.parser_options = self.parser_options();
+        context.dependencies = dependencies;
+        let mut binder = Binder::new(context, &params, None);
         /// Build a logical plan.
         ///
         /// SELECT a,b FROM t1 ORDER BY a LIMIT 1;
@@ -164,15 +704,87 @@ impl<S: Storage> State<S> {
         let source_plan = binder.bind(stmt)?;
         // println!("source_plan plan: {:#?}", source_plan);
 
-        let best_plan = Self::default_optimizer(source_plan)
+        Ok(source_plan)
+    }
+
+    fn optimize(
+        &self,
+        transaction: &<S as Storage>::TransactionType<'_>,
+        meta_cache: &StatisticsMetaCache,
+        source_plan: LogicalPlan,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let mut best_plan = Self::default_optimizer(source_plan, &self.normalization_rules)
             .find_best(Some(&transaction.meta_loader(meta_cache)))?;
+        plan_custom_executors(&mut best_plan, &self.custom_executors);
         // println!("best_plan plan: {:#?}", best_plan);
 
         Ok(best_plan)
     }
 
-    pub(crate) fn default_optimizer(source_plan: LogicalPlan) -> HepOptimizer {
-        HepOptimizer::new(source_plan)
+    /// The plan of a query normalized by the rules that keep it portable, without the rewrites
+    /// into the scans and the physical options of [`State::default_optimizer`].
+    #[cfg(feature = "substrait")]
+    pub(crate) fn logical_plan(
+        &self,
+        transaction: &<S as Storage>::TransactionType<'_>,
+        stmt: &Statement,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let source_plan = self.bind_plan(transaction, self.caches(), stmt, [], None, None)?;
+        let mut optimizer = HepOptimizer::new(source_plan);
+
+        if !self.normalization_rules.is_empty() {
+            optimizer = optimizer.batch(
+                "Custom Rules".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                self.normalization_rules.clone(),
+            );
+        }
+        optimizer
+            .batch(
+                "Column Pruning".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::ColumnPruning],
+            )
+            .batch(
+                "Simplify Filter".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::SimplifyFilter,
+                    NormalizationRuleImpl::ConstantCalculation,
+                ],
+            )
+            .batch(
+                "Combine Operators".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![
+                    NormalizationRuleImpl::CollapseProject,
+                    NormalizationRuleImpl::CollapseGroupByAgg,
+                    NormalizationRuleImpl::CombineFilter,
+                ],
+            )
+            .batch(
+                "Expression Remapper".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::ExpressionRemapper],
+            )
+            .find_best::<S::TransactionType<'_>>(None)
+    }
+
+    pub(crate) fn default_optimizer(
+        source_plan: LogicalPlan,
+        normalization_rules: &NormalizationRules,
+    ) -> HepOptimizer {
+        let mut optimizer = HepOptimizer::new(source_plan);
+
+        // the rules of the embedder see the plan as bound, before its columns are pruned
+        if !normalization_rules.is_empty() {
+            optimizer = optimizer.batch(
+                "Custom Rules".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                normalization_rules.clone(),
+            );
+        }
+        optimizer
             .batch(
                 "Column Pruning".to_string(),
                 HepBatchStrategy::once_topdown(),
@@ -186,12 +798,30 @@ impl<S: Storage> State<S> {
                     NormalizationRuleImpl::ConstantCalculation,
                 ],
             )
+            .batch(
+                "Join Reduction".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![
+                    NormalizationRuleImpl::EliminateOuterJoin,
+                    NormalizationRuleImpl::SemiJoinReduction,
+                ],
+            )
+            .batch(
+                "Constraint Pruning".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![
+                    NormalizationRuleImpl::EliminateNotNullFilter,
+                    NormalizationRuleImpl::EliminateDistinct,
+                    NormalizationRuleImpl::EliminateDependentGroupBy,
+                ],
+            )
             .batch(
                 "Predicate Pushdown".to_string(),
                 HepBatchStrategy::fix_point_topdown(10),
                 vec![
                     NormalizationRuleImpl::PushPredicateThroughJoin,
                     NormalizationRuleImpl::PushPredicateIntoScan,
+                    NormalizationRuleImpl::PushPredicateIntoForeignScan,
                 ],
             )
             .batch(
@@ -200,7 +830,10 @@ impl<S: Storage> State<S> {
                 vec![
                     NormalizationRuleImpl::LimitProjectTranspose,
                     NormalizationRuleImpl::PushLimitThroughJoin,
+                    NormalizationRuleImpl::EliminateSort,
+                    NormalizationRuleImpl::PushSortIntoScan,
                     NormalizationRuleImpl::PushLimitIntoTableScan,
+                    NormalizationRuleImpl::PushNearestNeighbourIntoScan,
                 ],
             )
             .batch(
@@ -212,6 +845,11 @@ impl<S: Storage> State<S> {
                     NormalizationRuleImpl::CombineFilter,
                 ],
             )
+            .batch(
+                "Subquery Sharing".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::ShareRepeatedSubquery],
+            )
             .batch(
                 "Expression Remapper".to_string(),
                 HepBatchStrategy::once_topdown(),
@@ -225,16 +863,27 @@ impl<S: Storage> State<S> {
                 // DQL
                 ImplementationRuleImpl::SimpleAggregate,
                 ImplementationRuleImpl::GroupByAggregate,
+                ImplementationRuleImpl::CountStar,
+                ImplementationRuleImpl::MinMaxIndex,
                 ImplementationRuleImpl::Dummy,
                 ImplementationRuleImpl::Filter,
                 ImplementationRuleImpl::HashJoin,
                 ImplementationRuleImpl::Limit,
                 ImplementationRuleImpl::Projection,
                 ImplementationRuleImpl::SeqScan,
+                // ahead of the index scan, which reads as many keys of the primary key
+                ImplementationRuleImpl::MultiGet,
                 ImplementationRuleImpl::IndexScan,
+                ImplementationRuleImpl::SkipScan,
                 ImplementationRuleImpl::FunctionScan,
+                ImplementationRuleImpl::ForeignScan,
                 ImplementationRuleImpl::Sort,
+                ImplementationRuleImpl::IncrementalSort,
                 ImplementationRuleImpl::Values,
+                // Fusion
+                ImplementationRuleImpl::FilterScanFusion,
+                ImplementationRuleImpl::ProjectScanFusion,
+                ImplementationRuleImpl::ProjectFilterScanFusion,
                 // DML
                 ImplementationRuleImpl::Analyze,
                 ImplementationRuleImpl::CopyFromFile,
@@ -251,96 +900,633 @@ impl<S: Storage> State<S> {
             ])
     }
 
+    /// How the statements run on the database are parsed, by the dialect set at the moment.
+    pub(crate) fn parser_options(&self) -> ParserOptions {
+        ParserOptions {
+            dialect: *self.dialect.read(),
+            identifier_case: self.identifier_case,
+        }
+    }
+
     fn prepare<T: AsRef<str>>(&self, sql: T) -> Result<Statement, DatabaseError> {
-        let mut stmts = parse_sql(sql)?;
+        let mut stmts = parse_sql_with_options(sql, self.parser_options())?;
         stmts.pop().ok_or(DatabaseError::EmptyStatement)
     }
 
     fn execute<'a, A: AsRef<[(&'static str, DataValue)]>>(
         &'a self,
-        transaction: &'a mut S::TransactionType<'_>,
+        transaction: &'a S::TransactionType<'_>,
+        caches: Caches<'a>,
         stmt: &Statement,
         params: A,
+        role: Option<&str>,
+        process: &mut ProcessGuard<'_>,
     ) -> Result<(SchemaRef, Executor<'a>), DatabaseError> {
-        let mut plan = Self::build_plan(
-            stmt,
-            params,
-            self.table_cache(),
-            self.view_cache(),
-            self.meta_cache(),
-            transaction,
-            self.scala_functions(),
-            self.table_functions(),
-        )?;
+        match stmt {
+            Statement::CreateDatabase {
+                db_name,
+                location: Some(path),
+                ..
+            } => return self.attach_database(db_name, path),
+            Statement::Drop {
+                object_type: ObjectType::Schema,
+                names,
+                ..
+            } => return self.detach_database(names),
+            Statement::ShowVariable { variable }
+                if variable.len() == 1 && variable[0].value.eq_ignore_ascii_case("processlist") =>
+            {
+                return Ok(self.show_processlist())
+            }
+            Statement::ShowVariable { variable }
+                if variable.len() == 1 && variable[0].value.eq_ignore_ascii_case("dialect") =>
+            {
+                return Ok(Self::result("dialect", self.dialect.read().to_string()))
+            }
+            Statement::SetVariable {
+                variable, value, ..
+            } if variable.to_string().eq_ignore_ascii_case("dialect") => {
+                return self.set_dialect(value)
+            }
+            Statement::Kill { id, .. } => return self.kill(*id),
+            _ => (),
+        }
+        let source_plan = self.bind_plan(transaction, caches, stmt, params, role, None)?;
+        self.execute_plan(transaction, caches, source_plan, process)
+    }
+
+    /// Runs a query by its plan cached while the tables and views it was bound to keep their
+    /// versions, binding and optimizing it again otherwise.
+    ///
+    /// Only the statements of [`Database::execute`] read the cache: those of a transaction
+    /// may be bound to the catalogs it has not committed.
+    fn execute_query<'a, A: AsRef<[(&'static str, DataValue)]>>(
+        &'a self,
+        transaction: &'a S::TransactionType<'_>,
+        stmt: &Statement,
+        params: A,
+        role: Option<&str>,
+        process: &mut ProcessGuard<'_>,
+    ) -> Result<(SchemaRef, Executor<'a>), DatabaseError> {
+        let caches = self.caches();
+        let key = PlanKey {
+            statement: stmt.clone(),
+            params: params.as_ref().to_vec(),
+            role: role.map(str::to_string),
+        };
+        let mut plan = match self.plan_cache.get(&key).cloned() {
+            Some(cached) if cached.is_fresh(transaction)? => {
+                let mut plan = cached.plan;
+                // the rows shared within the plan are read again by each run of it
+                renew_shared_rows(&mut plan);
+                plan
+            }
+            _ => {
+                let dependencies = PlanDependencies::default();
+                let source_plan =
+                    self.bind_plan(transaction, caches, stmt, params, role, Some(&dependencies))?;
+                let versions = dependencies.versions(&source_plan, transaction)?;
+                let plan = self.optimize(transaction, self.meta_cache(), source_plan)?;
+
+                if let Some(versions) = versions {
+                    self.plan_cache.put(
+                        key,
+                        CachedPlan {
+                            plan: plan.clone(),
+                            versions,
+                        },
+                    );
+                }
+                plan
+            }
+        };
+        process.admit(&plan, &transaction.meta_loader(self.meta_cache()))?;
         let schema = plan.output_schema().clone();
-        let executor = build_write(
-            plan,
-            (&self.table_cache, &self.view_cache, &self.meta_cache),
-            transaction,
-        );
+        let executor = build_write(plan, caches, transaction);
 
         Ok((schema, executor))
     }
-}
-
-pub struct Database<S: Storage> {
-    pub(crate) storage: S,
-    mdl: Arc<RwLock<()>>,
-    pub(crate) state: Arc<State<S>>,
-}
-
-impl<S: Storage> Database<S> {
-    /// Run SQL queries.
-    pub fn run<T: AsRef<str>>(&self, sql: T) -> Result<DatabaseIter<'_, S>, DatabaseError> {
-        let statement = self.prepare(sql)?;
 
-        self.execute(&statement, &[])
-    }
+    /// Optimizes and runs a bound plan, of a statement or of a [`DataFrame`].
+    fn execute_plan<'a>(
+        &'a self,
+        transaction: &'a S::TransactionType<'_>,
+        caches: Caches<'a>,
+        source_plan: LogicalPlan,
+        process: &mut ProcessGuard<'_>,
+    ) -> Result<(SchemaRef, Executor<'a>), DatabaseError> {
+        let (_, _, meta_cache) = caches;
+        let mut plan = self.optimize(transaction, meta_cache, source_plan)?;
+        process.admit(&plan, &transaction.meta_loader(meta_cache))?;
+        let schema = plan.output_schema().clone();
+        let executor = build_write(plan, caches, transaction);
 
-    pub fn prepare<T: AsRef<str>>(&self, sql: T) -> Result<Statement, DatabaseError> {
-        self.state.prepare(sql)
+        Ok((schema, executor))
     }
 
-    fn execute<A: AsRef<[(&'static str, DataValue)]>>(
+    /// Executes a statement to its end, the batches of `batch_commit` read their rows by it.
+    fn execute_all<A: AsRef<[(&'static str, DataValue)]>>(
         &self,
-        statement: &Statement,
+        transaction: &S::TransactionType<'_>,
+        stmt: &Statement,
         params: A,
-    ) -> Result<DatabaseIter<S>, DatabaseError> {
-        let _guard = if matches!(command_type(statement)?, CommandType::DDL) {
-            MetaDataLock::Write(self.mdl.write_arc())
-        } else {
-            MetaDataLock::Read(self.mdl.read_arc())
-        };
-        let transaction = Box::into_raw(Box::new(self.storage.transaction()?));
-        let (schema, executor) =
-            self.state
-                .execute(unsafe { &mut (*transaction) }, statement, params)?;
-        let inner = Box::into_raw(Box::new(TransactionIter::new(schema, executor)));
-        Ok(DatabaseIter { transaction, inner })
+        role: Option<&str>,
+        process: &mut ProcessGuard<'_>,
+    ) -> Result<Vec<Tuple>, DatabaseError> {
+        let (_, mut executor) =
+            self.execute(transaction, self.caches(), stmt, params, role, process)?;
+        let mut tuples = Vec::new();
+
+        while let CoroutineState::Yielded(tuple) = Pin::new(&mut executor).resume(()) {
+            tuples.push(tuple?);
+        }
+        drop(executor);
+        // the next batch waits for a slot of the heavy queries again
+        process.heavy_query = None;
+
+        Ok(tuples)
     }
 
-    pub fn new_transaction(&self) -> Result<DBTransaction<S>, DatabaseError> {
-        let guard = self.mdl.read_arc();
-        let transaction = self.storage.transaction()?;
-        let state = self.state.clone();
+    /// `ATTACH DATABASE 'path' AS name` opens another database file whose tables are read as `name.table`.
+    fn attach_database<'a>(
+        &self,
+        name: &ObjectName,
+        path: &str,
+    ) -> Result<(SchemaRef, Executor<'a>), DatabaseError> {
+        let name = fold_name(name)?;
+        let mut attached_databases = self.attached_databases.write();
 
-        Ok(DBTransaction {
-            inner: transaction,
-            _guard: guard,
-            state,
+        if attached_databases.contains_key(&name) {
+            return Err(DatabaseError::AttachedDatabaseExists(name));
+        }
+        let database = AttachedDatabase::open(name.clone(), path.to_string())?;
+        attached_databases.insert(name.clone(), Arc::new(database));
+
+        Ok(Self::result("ATTACH DATABASE SUCCESS", name))
+    }
+
+    /// Plans already holding the detached database keep reading it until they finish.
+    fn detach_database<'a>(
+        &self,
+        names: &[ObjectName],
+    ) -> Result<(SchemaRef, Executor<'a>), DatabaseError> {
+        let [name] = names else {
+            return Err(DatabaseError::UnsupportedStmt(
+                "only Detach a single `Database` is allowed".to_string(),
+            ));
+        };
+        let name = fold_name(name)?;
+
+        if self.attached_databases.write().remove(&name).is_none() {
+            return Err(DatabaseError::AttachedDatabaseNotFound(name));
+        }
+        Ok(Self::result("DETACH DATABASE SUCCESS", name))
+    }
+
+    /// `SHOW PROCESSLIST` lists the statements running, itself included.
+    fn show_processlist<'a>(&self) -> (SchemaRef, Executor<'a>) {
+        let schema = Arc::new(
+            ["ID", "SQL", "START", "STATE", "ROWS"]
+                .into_iter()
+                .map(|column| ColumnRef::from(ColumnCatalog::new_dummy(column.to_string())))
+                .collect_vec(),
+        );
+        let processes = self.processes.processes();
+        let executor: Executor<'a> = Box::new(
+            #[coroutine]
+            move || {
+                for process in processes {
+                    let values = [
+                        process.id.to_string(),
+                        process.sql.clone(),
+                        process.start.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        process.state().to_string(),
+                        process.rows.load(Ordering::Relaxed).to_string(),
+                    ]
+                    .into_iter()
+                    .map(|value| DataValue::Utf8 {
+                        value,
+                        ty: Utf8Type::Variable(None),
+                        unit: CharLengthUnits::Characters,
+                    })
+                    .collect();
+
+                    yield Ok(Tuple::new(None, values));
+                }
+            },
+        );
+
+        (schema, executor)
+    }
+
+    /// `KILL <id>` cancels a statement listed by `SHOW PROCESSLIST`.
+    /// `SET dialect = 'mysql'`, the statements after it are parsed in the dialect.
+    fn set_dialect<'a>(&self, value: &[Expr]) -> Result<(SchemaRef, Executor<'a>), DatabaseError> {
+        let dialect = match value {
+            [Expr::Value(Value::SingleQuotedString(name))] => name.parse::<SqlDialect>()?,
+            [Expr::Identifier(ident)] => ident.value.parse::<SqlDialect>()?,
+            _ => {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "expected the name of a dialect, got: {}",
+                    value.iter().join(", ")
+                )))
+            }
+        };
+        *self.dialect.write() = dialect;
+        // the SQL a query is rewritten into is parsed by the dialect as well
+        self.plan_cache.clear();
+
+        Ok(Self::result("SET SUCCESS", dialect.to_string()))
+    }
+
+    fn kill<'a>(&self, id: u64) -> Result<(SchemaRef, Executor<'a>), DatabaseError> {
+        if !self.processes.kill(id) {
+            return Err(DatabaseError::ProcessNotFound(id));
+        }
+        Ok(Self::result("KILL SUCCESS", id.to_string()))
+    }
+
+    fn result<'a>(column: &str, message: String) -> (SchemaRef, Executor<'a>) {
+        let schema = Arc::new(vec![ColumnRef::from(ColumnCatalog::new_dummy(
+            column.to_string(),
+        ))]);
+        let executor: Executor<'a> = Box::new(
+            #[coroutine]
+            move || {
+                yield Ok(TupleBuilder::build_result(message));
+            },
+        );
+
+        (schema, executor)
+    }
+}
+
+pub struct Database<S: Storage> {
+    pub(crate) storage: S,
+    pub(crate) mdl: Arc<RwLock<()>>,
+    pub(crate) state: Arc<State<S>>,
+    // runs the tasks of `CREATE TASK`, only the database built by `DataBaseBuilder` owns it
+    _scheduler: Option<Scheduler>,
+}
+
+impl<S: Storage> Database<S> {
+    /// Run SQL queries.
+    pub fn run<T: AsRef<str>>(&self, sql: T) -> Result<DatabaseIter<'_, S>, DatabaseError> {
+        let statement = self.prepare(sql)?;
+
+        self.execute(&statement, &[])
+    }
+
+    pub fn prepare<T: AsRef<str>>(&self, sql: T) -> Result<Statement, DatabaseError> {
+        self.state.prepare(sql)
+    }
+
+    /// Runs a statement of [`Database::prepare`] with the values of its placeholders, e.g.
+    /// `("?1", value)`.
+    pub fn execute<A: AsRef<[(&'static str, DataValue)]>>(
+        &self,
+        statement: &Statement,
+        params: A,
+    ) -> Result<DatabaseIter<S>, DatabaseError> {
+        self.execute_with_role(statement, params, None)
+    }
+
+    /// Runs SQL as the role, whose statements read the columns the masking policy masks for it,
+    /// see [`DataBaseBuilder::masking_policy`].
+    ///
+    /// The role is set by the embedder for each session, the SQL it runs cannot change it.
+    pub fn run_as<T: AsRef<str>>(
+        &self,
+        role: &str,
+        sql: T,
+    ) -> Result<DatabaseIter<'_, S>, DatabaseError> {
+        let statement = self.prepare(sql)?;
+
+        self.execute_as(role, &statement, &[])
+    }
+
+    /// Same as [`Database::execute`], as the role of [`Database::run_as`].
+    pub fn execute_as<A: AsRef<[(&'static str, DataValue)]>>(
+        &self,
+        role: &str,
+        statement: &Statement,
+        params: A,
+    ) -> Result<DatabaseIter<S>, DatabaseError> {
+        self.execute_with_role(statement, params, Some(role))
+    }
+
+    fn execute_with_role<A: AsRef<[(&'static str, DataValue)]>>(
+        &self,
+        statement: &Statement,
+        params: A,
+        role: Option<&str>,
+    ) -> Result<DatabaseIter<S>, DatabaseError> {
+        let command_type = command_type(statement)?;
+        self.state.check_writable(&command_type)?;
+
+        let mdl = if matches!(command_type, CommandType::DDL) {
+            MetaDataLock::Write(self.write_mdl()?)
+        } else {
+            MetaDataLock::Read(self.read_mdl()?)
+        };
+        if let Some(batch_commit) = BatchCommit::from_statement(statement)? {
+            return self.execute_in_batches(statement, params.as_ref(), role, batch_commit, mdl);
+        }
+        let transaction = Box::into_raw(Box::new(self.storage.transaction()?));
+        let mut process = self.state.processes.register(statement.to_string());
+        let (schema, executor) = if matches!(statement, Statement::Query(_)) {
+            self.state.execute_query(
+                unsafe { &*transaction },
+                statement,
+                params,
+                role,
+                &mut process,
+            )?
+        } else {
+            self.state.execute(
+                unsafe { &*transaction },
+                self.state.caches(),
+                statement,
+                params,
+                role,
+                &mut process,
+            )?
+        };
+        let inner = Box::into_raw(Box::new(TransactionIter::new(schema, executor, process)));
+        Ok(DatabaseIter {
+            transaction,
+            inner,
+            _mdl: mdl,
+        })
+    }
+
+    /// Starts a plan built without SQL on the scan of the table, see [`DataFrame`].
+    ///
+    /// The plan is bound to the catalog of the table as it is now.
+    pub fn table(&self, table_name: &str) -> Result<DataFrame<'_, S>, DatabaseError> {
+        let transaction = self.storage.transaction()?;
+        let table_name: TableName = Arc::new(table_name.to_lowercase());
+        let table = transaction
+            .table(self.state.table_cache(), table_name.clone())?
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+
+        Ok(DataFrame::new(
+            self,
+            TableScanOperator::build(table_name, table, true),
+        ))
+    }
+
+    /// Runs the plan of a [`DataFrame`] as a statement that reads.
+    pub(crate) fn execute_plan(&self, plan: LogicalPlan) -> Result<DatabaseIter<S>, DatabaseError> {
+        let mdl = MetaDataLock::Read(self.read_mdl()?);
+        let transaction = Box::into_raw(Box::new(self.storage.transaction()?));
+        let mut process = self.state.processes.register(plan.explain(0));
+        let (schema, executor) = self.state.execute_plan(
+            unsafe { &*transaction },
+            self.state.caches(),
+            plan,
+            &mut process,
+        )?;
+        let inner = Box::into_raw(Box::new(TransactionIter::new(schema, executor, process)));
+        Ok(DatabaseIter {
+            transaction,
+            inner,
+            _mdl: mdl,
+        })
+    }
+
+    /// The plan of the query as a [Substrait](https://substrait.io) plan, encoded as protobuf.
+    #[cfg(feature = "substrait")]
+    pub fn to_substrait<T: AsRef<str>>(&self, sql: T) -> Result<Vec<u8>, DatabaseError> {
+        let statement = self.prepare(sql)?;
+        if !matches!(command_type(&statement)?, CommandType::DQL) {
+            return Err(DatabaseError::UnsupportedStmt(
+                "only queries can be exported to substrait".to_string(),
+            ));
+        }
+        let _mdl = self.read_mdl()?;
+        let transaction = self.storage.transaction()?;
+        let plan = self.state.logical_plan(&transaction, &statement)?;
+
+        Ok(prost::Message::encode_to_vec(
+            &Producer::default().produce(plan)?,
+        ))
+    }
+
+    /// Runs a [Substrait](https://substrait.io) plan encoded as protobuf, e.g. of
+    /// [`Database::to_substrait`], on the tables of the database.
+    #[cfg(feature = "substrait")]
+    pub fn execute_substrait(&self, bytes: &[u8]) -> Result<DatabaseIter<S>, DatabaseError> {
+        let plan = <substrait::proto::Plan as prost::Message>::decode(bytes)
+            .map_err(|err| DatabaseError::InvalidValue(err.to_string()))?;
+        let source_plan = {
+            let _mdl = self.read_mdl()?;
+            let transaction = self.storage.transaction()?;
+            Consumer::new(
+                &plan,
+                &transaction,
+                self.state.table_cache(),
+                self.state.aggregate_memory_limit(),
+            )
+            .consume()?
+        };
+
+        self.execute_plan(source_plan)
+    }
+
+    /// The metadata lock of the other statements, once the DDL statement or transaction holding
+    /// it ended, e.g. a transaction of [`Database::new_ddl_transaction`] on the same thread.
+    fn read_mdl(&self) -> Result<ArcRwLockReadGuard<RawRwLock, ()>, DatabaseError> {
+        let timeout = self.state.lock_timeout;
+
+        self.mdl
+            .try_read_arc_for(timeout)
+            .ok_or(DatabaseError::LockTimeout(timeout))
+    }
+
+    /// The metadata lock of a DDL statement, once the other statements ended.
+    pub(crate) fn write_mdl(&self) -> Result<ArcRwLockWriteGuard<RawRwLock, ()>, DatabaseError> {
+        let timeout = self.state.lock_timeout;
+
+        self.mdl
+            .try_write_arc_for(timeout)
+            .ok_or(DatabaseError::LockTimeout(timeout))
+    }
+
+    /// Runs `UPDATE`/`DELETE ... WITH (batch_commit = n)` as a transaction by batch of `n` rows,
+    /// see [`BatchCommit`]. The statement with the same text and parameters resumes after the
+    /// last batch committed by a run that failed or was cancelled.
+    fn execute_in_batches(
+        &self,
+        statement: &Statement,
+        params: &[(&'static str, DataValue)],
+        role: Option<&str>,
+        batch_commit: BatchCommit,
+        mdl: MetaDataLock,
+    ) -> Result<DatabaseIter<S>, DatabaseError> {
+        let checkpoint = iter::once(statement.to_string())
+            .chain(params.iter().map(|(name, value)| format!("{name}={value}")))
+            .join(" ");
+        let table_name = Arc::new(batch_commit.table_name()?);
+        let mut process = self.state.processes.register(statement.to_string());
+        let (mut rows, mut batches) = (0, 0);
+
+        loop {
+            if process.process.cancellation_token.is_cancelled() {
+                return Err(DatabaseError::Cancelled);
+            }
+            let transaction = self.storage.transaction()?;
+            let primary_keys = transaction
+                .table(self.state.table_cache(), table_name.clone())?
+                .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?
+                .primary_keys()
+                .iter()
+                .map(|(_, column)| column.name().to_string())
+                .collect_vec();
+            batch_commit.check(&primary_keys)?;
+
+            let after = transaction.batch_checkpoint(&checkpoint)?;
+            let options = self.state.parser_options();
+            let query = batch_commit.next_batch_query(&primary_keys, after.as_deref(), options)?;
+            let keys = self
+                .state
+                .execute_all(&transaction, &query, params, role, &mut process)?;
+            let Some(last) = keys.last() else {
+                transaction.remove_batch_checkpoint(&checkpoint)?;
+                transaction.commit()?;
+                break;
+            };
+            let batch = batch_commit.batch_statement(
+                &primary_keys,
+                after.as_deref(),
+                &last.values,
+                options,
+            );
+            self.state
+                .execute_all(&transaction, &batch, params, role, &mut process)?;
+
+            if keys.len() < batch_commit.size {
+                transaction.remove_batch_checkpoint(&checkpoint)?;
+            } else {
+                transaction.set_batch_checkpoint(&checkpoint, &last.values)?;
+            }
+            transaction.commit()?;
+            rows += keys.len();
+            batches += 1;
+
+            if keys.len() < batch_commit.size {
+                break;
+            }
+        }
+        let (schema, executor) =
+            State::<S>::result("BATCH COMMIT", format!("{rows} rows in {batches} batches"));
+        let transaction = Box::into_raw(Box::new(self.storage.transaction()?));
+        let inner = Box::into_raw(Box::new(TransactionIter::new(schema, executor, process)));
+        Ok(DatabaseIter {
+            transaction,
+            inner,
+            _mdl: mdl,
+        })
+    }
+
+    /// Hits and misses of the caches of the database, e.g. to size them by
+    /// [`DataBaseBuilder::cache_options`].
+    pub fn cache_stats(&self) -> DatabaseCacheStats {
+        DatabaseCacheStats {
+            statistics_meta: CacheStats::new(self.state.meta_cache()),
+            table: CacheStats::new(self.state.table_cache()),
+            view: CacheStats::new(self.state.view_cache()),
+            plan: CacheStats::new(self.state.plan_cache()),
+        }
+    }
+
+    /// How far the storage falls behind the writes, e.g. for a bulk loader to slow down before
+    /// its writes are throttled.
+    pub fn write_pressure(&self) -> Result<WritePressure, DatabaseError> {
+        self.storage.write_pressure()
+    }
+
+    /// The versions of the catalog and of the statistics of the table as committed, see
+    /// [`Transaction::table_version`].
+    pub fn table_version(&self, table_name: &str) -> Result<TableVersion, DatabaseError> {
+        self.storage.transaction()?.table_version(table_name)
+    }
+
+    pub fn new_transaction(&self) -> Result<DBTransaction<S>, DatabaseError> {
+        let guard = self.read_mdl()?;
+        let transaction = self.storage.transaction()?;
+        let state = self.state.clone();
+
+        Ok(DBTransaction {
+            inner: transaction,
+            caches: None,
+            mdl: MetaDataLock::Read(guard),
+            state,
+            role: None,
+        })
+    }
+
+    /// A transaction whose statements run as the role of [`Database::run_as`].
+    pub fn new_transaction_as(&self, role: &str) -> Result<DBTransaction<S>, DatabaseError> {
+        let mut transaction = self.new_transaction()?;
+        transaction.role = Some(role.to_string());
+
+        Ok(transaction)
+    }
+
+    /// A transaction whose statements may also change the schema, e.g. create a table with its
+    /// indexes and views, which the other statements see all at once when it commits.
+    ///
+    /// It holds the metadata lock until it ends, so that the other statements and transactions
+    /// wait for it.
+    pub fn new_ddl_transaction(&self) -> Result<DBTransaction<S>, DatabaseError> {
+        let guard = self.write_mdl()?;
+        let transaction = self.storage.transaction()?;
+        let state = self.state.clone();
+
+        Ok(DBTransaction {
+            inner: transaction,
+            caches: Some(CatalogCaches {
+                staged: state.new_caches()?,
+                is_dirty: false,
+            }),
+            mdl: MetaDataLock::Write(guard),
+            state,
+            role: None,
         })
     }
 }
 
+/// Cancels a running statement, e.g. from another thread.
+///
+/// The statement stops before its next tuple and fails with [`DatabaseError::Cancelled`],
+/// so that its changes are not committed.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
 pub trait ResultIter: Iterator<Item = Result<Tuple, DatabaseError>> {
     fn schema(&self) -> &SchemaRef;
 
+    fn cancellation_token(&self) -> CancellationToken;
+
     fn done(self) -> Result<(), DatabaseError>;
 }
 
 pub struct DatabaseIter<'a, S: Storage + 'a> {
     transaction: *mut S::TransactionType<'a>,
     inner: *mut TransactionIter<'a>,
+    // released once the transaction and the executor reading the tables are dropped
+    _mdl: MetaDataLock,
 }
 
 impl<S: Storage> Drop for DatabaseIter<'_, S> {
@@ -367,6 +1553,10 @@ impl<S: Storage> ResultIter for DatabaseIter<'_, S> {
         unsafe { (*self.inner).schema() }
     }
 
+    fn cancellation_token(&self) -> CancellationToken {
+        unsafe { (*self.inner).cancellation_token() }
+    }
+
     fn done(mut self) -> Result<(), DatabaseError> {
         unsafe {
             Box::from_raw(mem::replace(&mut self.inner, std::ptr::null_mut())).done()?;
@@ -380,8 +1570,38 @@ impl<S: Storage> ResultIter for DatabaseIter<'_, S> {
 
 pub struct DBTransaction<'a, S: Storage + 'a> {
     inner: S::TransactionType<'a>,
-    _guard: ArcRwLockReadGuard<RawRwLock, ()>,
+    // of the transactions of `Database::new_ddl_transaction` only
+    caches: Option<CatalogCaches>,
+    mdl: MetaDataLock,
     state: Arc<State<S>>,
+    // the role of `Database::new_transaction_as`
+    role: Option<String>,
+}
+
+/// The statements of a DDL transaction read and write the catalogs through caches of their own,
+/// so that the other transactions never read those it has not committed. Once it commits, the
+/// caches of the database drop the catalogs, read again as committed, while it still holds the
+/// metadata lock.
+struct CatalogCaches {
+    staged: (TableCache, ViewCache, StatisticsMetaCache),
+    // whether a DDL statement ran
+    is_dirty: bool,
+}
+
+impl CatalogCaches {
+    fn caches(&self) -> Caches<'_> {
+        let (table_cache, view_cache, meta_cache) = &self.staged;
+
+        (table_cache, view_cache, meta_cache)
+    }
+
+    fn clear(&self) {
+        let (table_cache, view_cache, meta_cache) = &self.staged;
+
+        table_cache.clear();
+        view_cache.clear();
+        meta_cache.clear();
+    }
 }
 
 impl<S: Storage> DBTransaction<'_, S> {
@@ -400,34 +1620,143 @@ impl<S: Storage> DBTransaction<'_, S> {
         statement: &Statement,
         params: A,
     ) -> Result<TransactionIter, DatabaseError> {
-        if matches!(command_type(statement)?, CommandType::DDL) {
+        let command_type = command_type(statement)?;
+        self.state.check_writable(&command_type)?;
+
+        let is_ddl = matches!(command_type, CommandType::DDL);
+        if is_ddl && !matches!(self.mdl, MetaDataLock::Write(_)) {
+            return Err(DatabaseError::UnsupportedStmt(
+                "`DDL` is only allowed within a transaction of `Database::new_ddl_transaction`"
+                    .to_string(),
+            ));
+        }
+        // the attached databases are not written by the transaction
+        if matches!(
+            statement,
+            Statement::CreateDatabase {
+                location: Some(_),
+                ..
+            } | Statement::Drop {
+                object_type: ObjectType::Schema,
+                ..
+            }
+        ) {
             return Err(DatabaseError::UnsupportedStmt(
-                "`DDL` is not allowed to execute within a transaction".to_string(),
+                "`ATTACH` and `DETACH` are not allowed to execute within a transaction".to_string(),
             ));
         }
-        let (schema, executor) = self.state.execute(&mut self.inner, statement, params)?;
-        Ok(TransactionIter::new(schema, executor))
+        if let Some(caches) = self.caches.as_mut().filter(|_| is_ddl) {
+            caches.is_dirty = true;
+        }
+        let mut process = self.state.processes.register(statement.to_string());
+        // a statement failing halfway undoes its writes, those of the statements before it stay
+        self.inner.set_savepoint()?;
+        let transaction = &self.inner;
+        let caches = self.caches.as_ref();
+
+        let (schema, executor) = match self.state.execute(
+            transaction,
+            caches.map_or_else(|| self.state.caches(), CatalogCaches::caches),
+            statement,
+            params,
+            self.role.as_deref(),
+            &mut process,
+        ) {
+            Ok(result) => result,
+            Err(err) => {
+                if let Err(rollback_err) = transaction.rollback_to_savepoint() {
+                    log::error!(
+                        "failed to roll back the statement failing with `{err}`: {rollback_err}"
+                    );
+                }
+                return Err(err);
+            }
+        };
+        // the catalogs cached by a failed DDL statement are read again, with the writes of the
+        // statements before it
+        let caches = caches.filter(|_| is_ddl);
+
+        Ok(
+            TransactionIter::new(schema, executor, process).with_savepoint(
+                Box::new(move || {
+                    if let Some(caches) = caches {
+                        caches.clear();
+                    }
+                    transaction.rollback_to_savepoint()
+                }),
+                Box::new(move || transaction.release_savepoint()),
+            ),
+        )
     }
 
     pub fn commit(self) -> Result<(), DatabaseError> {
         self.inner.commit()?;
+        if self.caches.is_some_and(|caches| caches.is_dirty) {
+            self.state.clear_caches();
+        }
 
         Ok(())
     }
 }
 
 pub struct TransactionIter<'a> {
-    executor: Executor<'a>,
+    // `None` once the statement is over
+    executor: Option<Executor<'a>>,
     schema: SchemaRef,
-    is_over: bool,
+    // cancelled by the `CancellationToken` or `KILL`
+    process: ProcessGuard<'a>,
+    // undoes the writes of the statement if it fails, within a `DBTransaction` only: the other
+    // statements run in a transaction of their own, which is not committed then
+    rollback: Option<Box<dyn FnOnce() -> Result<(), DatabaseError> + 'a>>,
+    // drops the savepoint of the statement once it ends otherwise
+    release: Option<Box<dyn FnOnce() -> Result<(), DatabaseError> + 'a>>,
 }
 
 impl<'a> TransactionIter<'a> {
-    fn new(schema: SchemaRef, executor: Executor<'a>) -> Self {
+    fn new(schema: SchemaRef, executor: Executor<'a>, process: ProcessGuard<'a>) -> Self {
         Self {
-            executor,
+            executor: Some(executor),
             schema,
-            is_over: false,
+            process,
+            rollback: None,
+            release: None,
+        }
+    }
+
+    fn with_savepoint(
+        mut self,
+        rollback: Box<dyn FnOnce() -> Result<(), DatabaseError> + 'a>,
+        release: Box<dyn FnOnce() -> Result<(), DatabaseError> + 'a>,
+    ) -> Self {
+        self.rollback = Some(rollback);
+        self.release = Some(release);
+        self
+    }
+
+    fn fail(&mut self, err: DatabaseError) -> DatabaseError {
+        // the scans of the executor must not outlive the writes they read
+        self.executor = None;
+        self.release = None;
+
+        if let Some(Err(rollback_err)) = self.rollback.take().map(|rollback| rollback()) {
+            log::error!("failed to roll back the statement failing with `{err}`: {rollback_err}");
+        }
+        err
+    }
+
+    fn release(&mut self) -> Result<(), DatabaseError> {
+        self.executor = None;
+        self.rollback = None;
+
+        self.release.take().map_or(Ok(()), |release| release())
+    }
+}
+
+impl Drop for TransactionIter<'_> {
+    fn drop(&mut self) {
+        // the statement not read to its end keeps the writes it made
+        if let Err(err) = self.release() {
+            log::error!("failed to release the savepoint of the statement: {err}");
         }
     }
 }
@@ -436,14 +1765,23 @@ impl Iterator for TransactionIter<'_> {
     type Item = Result<Tuple, DatabaseError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.is_over {
-            return None;
+        let executor = self.executor.as_mut()?;
+
+        if self.process.process.cancellation_token.is_cancelled() {
+            return Some(Err(self.fail(DatabaseError::Cancelled)));
         }
-        if let CoroutineState::Yielded(tuple) = Pin::new(&mut self.executor).resume(()) {
-            Some(tuple)
-        } else {
-            self.is_over = true;
-            None
+        let _scope = self
+            .process
+            .heavy_query
+            .is_some()
+            .then(HeavyQueryScope::enter);
+        match Pin::new(executor).resume(()) {
+            CoroutineState::Yielded(Ok(tuple)) => {
+                self.process.process.rows.fetch_add(1, Ordering::Relaxed);
+                Some(Ok(tuple))
+            }
+            CoroutineState::Yielded(Err(err)) => Some(Err(self.fail(err))),
+            CoroutineState::Complete(()) => self.release().err().map(Err),
         }
     }
 }
@@ -453,29 +1791,61 @@ impl ResultIter for TransactionIter<'_> {
         &self.schema
     }
 
+    fn cancellation_token(&self) -> CancellationToken {
+        self.process.process.cancellation_token.clone()
+    }
+
     fn done(mut self) -> Result<(), DatabaseError> {
         for result in self.by_ref() {
             let _ = result?;
         }
+        if self.process.process.cancellation_token.is_cancelled() {
+            return Err(DatabaseError::Cancelled);
+        }
         Ok(())
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test {
-    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
-    use crate::db::{DataBaseBuilder, DatabaseError, ResultIter};
-    use crate::storage::{Storage, TableCache, Transaction};
+    use crate::catalog::{AuditColumns, ColumnCatalog, ColumnDesc, ColumnRef, ForeignTable};
+    use crate::db::{
+        CacheOptions, DBTransaction, DataBaseBuilder, Database, DatabaseError, ResultIter,
+        Statement, TransactionIter,
+    };
+    use crate::errors::ErrorCategory;
+    use crate::execution::custom::CustomExecutor;
+    use crate::execution::Executor;
+    use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+    use crate::expression::function::FunctionSummary;
+    use crate::expression::{BinaryOperator, ScalarExpression};
+    use crate::fdw::{ForeignDataWrapper, ForeignFilter};
+    use crate::masking::{Masking, MaskingPolicy};
+    use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+    use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+    use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+    use crate::parser::{IdentifierCase, SqlDialect};
+    use crate::planner::operator::filter::FilterOperator;
+    use crate::planner::operator::Operator;
+    use crate::scheduler::run_due;
+    use crate::storage::rocksdb::{IsolationLevel, RocksStorage, WalOptions, WriteThrottle};
+    use crate::storage::tuple_codec::TupleCodecKind;
+    use crate::storage::{Storage, TableCache, TableVersion, Transaction};
     use crate::types::tuple::Tuple;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, StringCoercion, Utf8Type};
     use crate::types::LogicalType;
-    use chrono::{Datelike, Local};
-    use std::sync::Arc;
+    use chrono::{Datelike, Local, NaiveDateTime};
+    use itertools::Itertools;
+    use serde::{Deserialize, Serialize};
+    use sqlparser::ast::CharLengthUnits;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, LazyLock, Mutex};
+    use std::time::Duration;
     use tempfile::TempDir;
 
     pub(crate) fn build_table<T: Transaction>(
         table_cache: &TableCache,
-        transaction: &mut T,
+        transaction: &T,
     ) -> Result<(), DatabaseError> {
         let columns = vec![
             ColumnCatalog::new(
@@ -494,8 +1864,14 @@ pub(crate) mod test {
                 ColumnDesc::new(LogicalType::Integer, None, false, None).unwrap(),
             ),
         ];
-        let _ =
-            transaction.create_table(table_cache, Arc::new("t1".to_string()), columns, false)?;
+        let _ = transaction.create_table(
+            table_cache,
+            Arc::new("t1".to_string()),
+            columns,
+            TupleCodecKind::default(),
+            AuditColumns::default(),
+            false,
+        )?;
 
         Ok(())
     }
@@ -504,9 +1880,9 @@ pub(crate) mod test {
     fn test_run_sql() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let database = DataBaseBuilder::path(temp_dir.path()).build()?;
-        let mut transaction = database.storage.transaction()?;
+        let transaction = database.storage.transaction()?;
 
-        build_table(&database.state.table_cache(), &mut transaction)?;
+        build_table(database.state.table_cache(), &transaction)?;
         transaction.commit()?;
 
         for result in database.run("select * from t1")? {
@@ -591,8 +1967,8 @@ pub(crate) mod test {
 
             assert_eq!(
                 iter.next().unwrap()?.values[0].utf8().unwrap(),
-                "Projection [t1.a, t1.b] [Project]
-  Filter (t1.b > 0), Is Having: false [Filter]
+                "Projection [t1.a, t1.b] [FusedProject]
+  Filter (t1.b > 0), Is Having: false [FusedFilter]
     TableScan t1 -> [a, b] [SeqScan]"
             )
         }
@@ -615,7 +1991,7 @@ pub(crate) mod test {
                 iter.next().unwrap()?.values[0].utf8().unwrap(),
                 "Projection [(t1.a + 0), Max((t1.b + 0))] [Project]
   Aggregate [Max((t1.b + 0))] -> Group By [(t1.a + 0)] [HashAggregate]
-    Filter (t1.b > 1), Is Having: false [Filter]
+    Filter (t1.b > 1), Is Having: false [FusedFilter]
       TableScan t1 -> [a, b] [SeqScan]"
             )
         }
@@ -635,11 +2011,11 @@ pub(crate) mod test {
                 iter.next().unwrap()?.values[0].utf8().unwrap(),
                 "Projection [t1.a, t1.b, 9] [Project]
   LeftOuter Join Where (t1.a > 0) [NestLoopJoin]
-    Projection [t1.a, t1.b] [Project]
-      Filter (t1.b > 0), Is Having: false [Filter]
+    Projection [t1.a, t1.b] [FusedProject]
+      Filter (t1.b > 0), Is Having: false [FusedFilter]
         TableScan t1 -> [a, b] [SeqScan]
-    Projection [t1.a, t1.b] [Project]
-      Filter (t1.a > 1), Is Having: false [Filter]
+    Projection [t1.a, t1.b] [FusedProject]
+      Filter (t1.a > 1), Is Having: false [FusedFilter]
         TableScan t1 -> [a, b] [SeqScan]"
             )
         }
@@ -698,4 +2074,1693 @@ pub(crate) mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_transaction_reads_own_index_writes() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t1 (a int primary key, b int, c int)")?
+            .done()?;
+        kite_sql.run("create index b_index on t1 (b)")?.done()?;
+        kite_sql
+            .run("create unique index c_index on t1 (c)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(0, 0, 0), (1, 1, 1)")?
+            .done()?;
+
+        fn rows(iter: TransactionIter) -> Result<Vec<Vec<DataValue>>, DatabaseError> {
+            iter.map(|tuple| tuple.map(|tuple| tuple.values)).collect()
+        }
+        let row = |a, b, c| {
+            vec![
+                DataValue::Int32(a),
+                DataValue::Int32(b),
+                DataValue::Int32(c),
+            ]
+        };
+        let mut tx = kite_sql.new_transaction()?;
+
+        tx.run("insert into t1 values(2, 1, 2), (3, 3, 3)")?
+            .done()?;
+        assert_eq!(
+            rows(tx.run("select * from t1 where b = 1")?)?,
+            vec![row(1, 1, 1), row(2, 1, 2)]
+        );
+        assert_eq!(
+            rows(tx.run("select * from t1 where c >= 2")?)?,
+            vec![row(2, 1, 2), row(3, 3, 3)]
+        );
+
+        // the entries of the old keys are gone and those of the new ones are read
+        tx.run("update t1 set b = 4, c = 4 where a = 3")?.done()?;
+        tx.run("delete from t1 where a = 1")?.done()?;
+        assert_eq!(
+            rows(tx.run("select * from t1 where b = 1")?)?,
+            vec![row(2, 1, 2)]
+        );
+        assert!(rows(tx.run("select * from t1 where b = 3")?)?.is_empty());
+        assert_eq!(
+            rows(tx.run("select * from t1 where c > 0 and c < 10")?)?,
+            vec![row(2, 1, 2), row(3, 4, 4)]
+        );
+        assert_eq!(
+            rows(tx.run("select * from t1 where b >= 0 order by b desc")?)?,
+            vec![row(3, 4, 4), row(2, 1, 2), row(0, 0, 0)]
+        );
+
+        // the other statements read the committed rows only
+        let committed = kite_sql
+            .run("select * from t1 where b >= 0")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(committed, vec![row(0, 0, 0), row(1, 1, 1)]);
+
+        tx.commit()?;
+        let committed = kite_sql
+            .run("select * from t1 where c >= 0")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(committed, vec![row(0, 0, 0), row(2, 1, 2), row(3, 4, 4)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transaction_statement_rollback() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("create unique index b_index on t1 (b)")?
+            .done()?;
+
+        let mut tx = kite_sql.new_transaction()?;
+        tx.run("insert into t1 values(0, 0)")?.done()?;
+        // the rows and index entries written before the duplicate are undone
+        assert!(tx
+            .run("insert into t1 values(1, 1), (2, 2), (0, 3)")?
+            .done()
+            .is_err());
+        tx.run("insert into t1 values(3, 1), (4, 2)")?.done()?;
+        tx.commit()?;
+
+        let rows = kite_sql
+            .run("select * from t1")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![
+                vec![DataValue::Int32(0), DataValue::Int32(0)],
+                vec![DataValue::Int32(3), DataValue::Int32(1)],
+                vec![DataValue::Int32(4), DataValue::Int32(2)],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ddl_transaction() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        fn create(tx: &mut DBTransaction<RocksStorage>) -> Result<(), DatabaseError> {
+            tx.run("create table t1 (a int primary key, b int)")?
+                .done()?;
+            tx.run("create index b_index on t1 (b)")?.done()?;
+            tx.run("create view v1 as select b from t1")?.done()?;
+            tx.run("insert into t1 values(0, 1)")?.done()?;
+            Ok(())
+        }
+
+        // not committed: neither the table, its index nor the view is left
+        {
+            let mut tx = kite_sql.new_ddl_transaction()?;
+            create(&mut tx)?;
+            // the catalogs it wrote are not in the caches of the database
+            let name = Arc::new("t1".to_string());
+            assert!(kite_sql.state.table_cache().get(&name).is_none());
+            assert!(kite_sql
+                .state
+                .view_cache()
+                .get(&Arc::new("v1".to_string()))
+                .is_none());
+            let rows = tx
+                .run("select * from v1 where b = 1")?
+                .map(|tuple| tuple.map(|tuple| tuple.values))
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(rows, vec![vec![DataValue::Int32(1)]]);
+        }
+        assert!(kite_sql.run("select * from t1").is_err());
+        assert!(kite_sql.run("select * from v1").is_err());
+
+        let mut tx = kite_sql.new_ddl_transaction()?;
+        create(&mut tx)?;
+        // a failed statement leaves those before it
+        assert!(tx
+            .run("create index b_index on t1 (b)")
+            .and_then(|iter| iter.done())
+            .is_err());
+        tx.commit()?;
+
+        let rows = kite_sql
+            .run("select * from v1 where b = 1")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![vec![DataValue::Int32(1)]]);
+        assert!(kite_sql.new_transaction()?.run("drop table t1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lock_timeout() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .lock_timeout(Duration::from_millis(100))
+            .build()?;
+
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(0, 0), (1, 1)")?
+            .done()?;
+
+        // the table is not dropped under a scan still reading it
+        let mut iter = kite_sql.run("select * from t1")?;
+        assert!(iter.next().unwrap().is_ok());
+        assert!(matches!(
+            kite_sql.run("drop table t1"),
+            Err(DatabaseError::LockTimeout(_))
+        ));
+        assert!(iter.next().unwrap().is_ok());
+        drop(iter);
+
+        let tx = kite_sql.new_transaction()?;
+        assert!(matches!(
+            kite_sql.new_ddl_transaction(),
+            Err(DatabaseError::LockTimeout(_))
+        ));
+        drop(tx);
+
+        // the other statements of the thread holding a DDL transaction do not wait for it forever
+        let tx = kite_sql.new_ddl_transaction()?;
+        assert!(matches!(
+            kite_sql.run("select * from t1"),
+            Err(DatabaseError::LockTimeout(_))
+        ));
+        assert!(matches!(
+            kite_sql.new_transaction(),
+            Err(DatabaseError::LockTimeout(_))
+        ));
+        drop(tx);
+
+        kite_sql.run("drop table t1")?.done()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deterministic_results() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .deterministic_results(true)
+            .build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(0, 2), (1, 1), (2, 2), (3, 1)")?
+            .done()?;
+
+        let rows = kite_sql
+            .run("select v, id from t1 order by v desc")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![
+                vec![DataValue::Int32(2), DataValue::Int32(0)],
+                vec![DataValue::Int32(2), DataValue::Int32(2)],
+                vec![DataValue::Int32(1), DataValue::Int32(1)],
+                vec![DataValue::Int32(1), DataValue::Int32(3)],
+            ]
+        );
+        let rows = kite_sql
+            .run("select distinct v from t1")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![vec![DataValue::Int32(1)], vec![DataValue::Int32(2)]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recursion_limits() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .max_recursion_depth(3)
+            .max_recursion_rows(10)
+            .build()?;
+        let query = |sql: &str| -> Result<Vec<Vec<DataValue>>, DatabaseError> {
+            kite_sql
+                .run(sql)?
+                .map(|tuple| tuple.map(|tuple| tuple.values))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        assert_eq!(
+            query("with recursive r(n) as (select 1 union all select n + 1 from r where n < 4) select count(*) from r")?,
+            vec![vec![DataValue::Int32(4)]]
+        );
+        assert!(matches!(
+            query("with recursive r(n) as (select 1 union all select n + 1 from r where n < 5) select count(*) from r"),
+            Err(DatabaseError::RecursionDepthExceeded(name, 3)) if name == "r"
+        ));
+        assert!(matches!(
+            query("with recursive r(n) as (select 1 union all select n + 1 from r where n < 4), s(n) as (select n from r union all select n from r union all select n from r) select count(*) from s"),
+            Ok(rows) if rows == vec![vec![DataValue::Int32(12)]]
+        ));
+        kite_sql
+            .run("create table t1 (id int primary key)")?
+            .done()?;
+        kite_sql.run("insert into t1 values(0), (1)")?.done()?;
+        // doubles the rows on each iteration
+        assert!(matches!(
+            query("with recursive r(n) as (select 1 union all select n + 1 from r, t1 where n < 10) select count(*) from r"),
+            Err(DatabaseError::RecursionRowsExceeded(name, 10)) if name == "r"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_coercion() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let err = kite_sql
+            .run("select '12abc' + 1")
+            .and_then(|iter| iter.done())
+            .unwrap_err();
+        assert!(err.to_string().contains("'12abc'"));
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .string_coercion(StringCoercion::Lenient)
+            .build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v varchar)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(0, '12abc'), (1, 'abc'), (2, ' -3.5')")?
+            .done()?;
+
+        let rows = kite_sql
+            .run("select id, v + 1 from t1 where v < 20")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![
+                vec![DataValue::Int32(0), DataValue::Int32(13)],
+                vec![DataValue::Int32(2), DataValue::Int32(-2)],
+            ]
+        );
+        let rows = kite_sql
+            .run("select '12abc' + 1, 'abc' + 1")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![vec![DataValue::Int32(13), DataValue::Null]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dialect() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .dialect(SqlDialect::MySql)
+            .build()?;
+        kite_sql
+            .run("create table `t1` (a int primary key, b boolean)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values (0, true), (1, false), (2, false), (3, true)")?
+            .done()?;
+
+        let rows = kite_sql
+            .run("select a from t1 where b || a = 2 order by a limit 1, 2")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![vec![DataValue::Int32(2)], vec![DataValue::Int32(3)]]
+        );
+        assert!(kite_sql.run("select top 1 a from t1").is_err());
+
+        // the SQL the statements are rewritten into is parsed in the dialect too
+        let rows = kite_sql
+            .run("select b, x from (select a, b from t1) pivot (count(*) for a in (0 as x)) order by b")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![
+                vec![DataValue::Boolean(false), DataValue::Int32(0)],
+                vec![DataValue::Boolean(true), DataValue::Int32(1)]
+            ]
+        );
+        kite_sql
+            .run("create table t2 (`order` int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t2 values (0, 0), (1, 0), (2, 0)")?
+            .done()?;
+        kite_sql
+            .run("update t2 with (batch_commit = 2) set b = 1")?
+            .done()?;
+        let rows = kite_sql
+            .run("select b from t2")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![vec![DataValue::Int32(1)]; 3]);
+
+        kite_sql.run("set dialect = 'generic'")?.done()?;
+        let rows = kite_sql
+            .run("show dialect")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![vec![DataValue::from("generic".to_string())]]);
+        let rows = kite_sql
+            .run("select top 2 a from t1 order by a desc")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![vec![DataValue::Int32(3)], vec![DataValue::Int32(2)]]
+        );
+        let rows = kite_sql
+            .run("select 'a' || 'b'")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![vec![DataValue::from("ab".to_string())]]);
+
+        kite_sql.run("set dialect = postgresql")?.done()?;
+        assert!(kite_sql.run("select top 1 a from t1").is_err());
+        assert!(matches!(
+            kite_sql.run("set dialect = 'oracle'"),
+            Err(DatabaseError::InvalidValue(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_identifier_case() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run(r#"create table "MyTable" ("Id" int primary key, Name varchar)"#)?
+            .done()?;
+        kite_sql
+            .run(r#"insert into "MyTable" values (1, 'a')"#)?
+            .done()?;
+
+        let iter = kite_sql
+            .run(r#"select "Id", NAME as "Label" from "MyTable" as "T" where "T".name = 'a'"#)?;
+        let names = iter
+            .schema()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect_vec();
+        assert_eq!(names, vec!["Id", "Label"]);
+        let rows = iter
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![vec![DataValue::Int32(1), DataValue::from("a".to_string())]]
+        );
+        assert!(kite_sql.run("select id from MyTable").is_err());
+        assert!(kite_sql.run(r#"select id from "MyTable""#).is_err());
+        let tables = kite_sql
+            .run("show tables")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(tables, vec![vec![DataValue::from("MyTable".to_string())]]);
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .identifier_case(IdentifierCase::Lower)
+            .build()?;
+        kite_sql
+            .run(r#"create table "MyTable" ("Id" int primary key)"#)?
+            .done()?;
+        kite_sql.run("insert into mytable values (1)")?.done()?;
+        let rows = kite_sql
+            .run(r#"select "ID" from "MYTABLE""#)?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![vec![DataValue::Int32(1)]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_quoted_identifiers() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run(r#"create table "Order" ("from" int primary key, a int)"#)?
+            .done()?;
+
+        let mut iter = kite_sql.run(r#"explain select "from", a from "Order""#)?;
+        let plan = iter.next().unwrap()?.values[0].utf8().unwrap().to_string();
+        assert!(plan.contains(r#""Order" -> ["from", a]"#), "{plan}");
+        iter.done()?;
+
+        // the primary key named by a keyword is quoted in the queries of the batches
+        kite_sql
+            .run(r#"insert into "Order" values (1, 1), (2, 2), (3, 3)"#)?
+            .done()?;
+        kite_sql
+            .run(r#"update "Order" with (batch_commit = 2) set a = 0"#)?
+            .done()?;
+        let rows = kite_sql
+            .run(r#"select sum(a) from "Order""#)?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![vec![DataValue::Int32(0)]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_commit() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        let values = (0..25).map(|i| format!("({i}, 0)")).join(", ");
+        kite_sql
+            .run(format!("insert into t1 values {values}"))?
+            .done()?;
+
+        let result = kite_sql
+            .run("update t1 with (batch_commit = 10) set b = b + 1 where a < 24")?
+            .map(|tuple| tuple.map(|tuple| tuple.values))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            result,
+            vec![vec![DataValue::Utf8 {
+                value: "24 rows in 3 batches".to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }]]
+        );
+        // the sum of b and the count of rows
+        let sum_b = |kite_sql: &Database<RocksStorage>| -> Result<(i32, usize), DatabaseError> {
+            let values = kite_sql
+                .run("select b from t1")?
+                .map(|tuple| Ok(tuple?.values[0].i32().unwrap()))
+                .collect::<Result<Vec<_>, DatabaseError>>()?;
+            Ok((values.iter().sum(), values.len()))
+        };
+        assert_eq!(sum_b(&kite_sql)?, (24, 25));
+
+        // a run stopped after the row 9 resumes from the row 10
+        let sql = "update t1 with (batch_commit = 10) set b = b + 1";
+        let transaction = kite_sql.storage.transaction()?;
+        transaction
+            .set_batch_checkpoint(&kite_sql.prepare(sql)?.to_string(), &[DataValue::Int32(9)])?;
+        transaction.commit()?;
+        kite_sql.run(sql)?.done()?;
+        assert_eq!(sum_b(&kite_sql)?, (39, 25));
+        // and the statement finished starts over
+        kite_sql.run(sql)?.done()?;
+        assert_eq!(sum_b(&kite_sql)?, (64, 25));
+
+        assert!(kite_sql
+            .run("update t1 with (batch_commit = 10) set a = a + 100")
+            .and_then(|iter| iter.done())
+            .is_err());
+        let mut transaction = kite_sql.new_transaction()?;
+        assert!(transaction
+            .run("delete from t1 with (batch_commit = 10)")
+            .and_then(|iter| iter.done())
+            .is_err());
+        drop(transaction);
+
+        kite_sql
+            .run("delete from t1 with (batch_commit = 7) where b > 2")?
+            .done()?;
+        assert_eq!(sum_b(&kite_sql)?, (22, 11));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_processlist() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(0, 0), (1, 1), (2, 2)")?
+            .done()?;
+
+        let mut iter = kite_sql.run("select * from t1")?;
+        assert!(iter.next().unwrap().is_ok());
+
+        let processes = kite_sql
+            .run("show processlist")?
+            .map(|tuple| {
+                tuple.map(|tuple| tuple.values.iter().map(ToString::to_string).collect_vec())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0][1], "SELECT * FROM t1");
+        assert_eq!(processes[0][3], "sending");
+        assert_eq!(processes[0][4], "1");
+        assert_eq!(processes[1][1], "SHOW processlist");
+
+        let id = &processes[0][0];
+        kite_sql.run(format!("kill {id}"))?.done()?;
+        assert!(matches!(iter.next(), Some(Err(DatabaseError::Cancelled))));
+        drop(iter);
+
+        assert!(matches!(
+            kite_sql.run(format!("kill {id}")),
+            Err(DatabaseError::ProcessNotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stat_statements() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(0, 0), (1, 1), (2, 2)")?
+            .done()?;
+        kite_sql.run("select * from t1 where id > 0")?.done()?;
+        kite_sql.run("select * from t1 where id > 1")?.done()?;
+
+        let mut iter = kite_sql.run(
+            "select calls, rows, min_ms <= max_ms from stat_statements where query = 'SELECT * FROM t1 WHERE id > ?'",
+        )?;
+        assert_eq!(
+            iter.next().unwrap()?.values,
+            vec![
+                DataValue::Int64(2),
+                DataValue::Int64(3),
+                DataValue::Boolean(true)
+            ]
+        );
+        assert!(iter.next().is_none());
+        drop(iter);
+
+        // a table of the name takes the place of the statistics
+        kite_sql
+            .run("create table stat_statements (id int primary key)")?
+            .done()?;
+        assert!(kite_sql
+            .run("select * from stat_statements")?
+            .next()
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_heavy_queries() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .heavy_query_cost(100)
+            .build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v int)")?
+            .done()?;
+        for i in 0..101 {
+            kite_sql
+                .run(format!("insert into t1 values({i}, {i})"))?
+                .done()?;
+        }
+        let is_heavy = |sql: &str| -> Result<bool, DatabaseError> {
+            let mut transaction = kite_sql.new_transaction()?;
+            let iter = transaction.run(sql)?;
+            Ok(iter.process.heavy_query.is_some())
+        };
+
+        // a full scan of a table not analyzed
+        assert!(is_heavy("select * from t1")?);
+        assert!(!is_heavy("select * from t1 limit 2")?);
+
+        kite_sql.run("analyze table t1")?.done()?;
+        assert!(is_heavy("select * from t1")?);
+        assert!(!is_heavy("select * from t1 where id = 1")?);
+        assert!(!is_heavy("select * from t1 where id < 50")?);
+
+        // a point lookup does not wait for the heavy statement running
+        let mut heavy = kite_sql.run("select * from t1")?;
+        assert!(heavy.next().is_some());
+        kite_sql.run("select * from t1 where id = 1")?.done()?;
+        // nor does a heavy statement of the thread reading the rows of the one running
+        let mut nested = kite_sql.run("select * from t1")?;
+        assert!(nested.next().is_some());
+        nested.done()?;
+        heavy.done()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_attach_database() -> Result<(), DatabaseError> {
+        let attached_dir = TempDir::new().expect("unable to create temporary working directory");
+        {
+            let kite_sql = DataBaseBuilder::path(attached_dir.path()).build()?;
+            kite_sql
+                .run("create table t2 (id int primary key, v int)")?
+                .done()?;
+            kite_sql
+                .run("insert into t2 values(0, 10), (1, 11), (2, 12)")?
+                .done()?;
+        }
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(1, 1), (2, 2)")?
+            .done()?;
+        kite_sql
+            .run(format!(
+                "attach database '{}' as other",
+                attached_dir.path().display()
+            ))?
+            .done()?;
+
+        let mut iter = kite_sql
+            .run("select t1.v, t.v from t1 join other.t2 t on t1.id = t.id where t.id > 1")?;
+        assert_eq!(
+            iter.next().unwrap()?.values,
+            vec![DataValue::Int32(2), DataValue::Int32(12)]
+        );
+        assert!(iter.next().is_none());
+        drop(iter);
+
+        assert!(matches!(
+            kite_sql.run("insert into other.t2 values(3, 13)"),
+            Err(DatabaseError::ReadOnlyAttachedDatabase(_))
+        ));
+        kite_sql.run("detach database other")?.done()?;
+        assert!(matches!(
+            kite_sql.run("select * from other.t2"),
+            Err(DatabaseError::AttachedDatabaseNotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_task() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values(0, 0), (1, 1), (2, 2)")?
+            .done()?;
+        kite_sql
+            .run("create task ttl schedule '0 0 29 2 *' as delete from t1 where id < 2;")?
+            .done()?;
+        let iter = kite_sql.run("create task ttl schedule '@daily' as delete from t1")?;
+        assert!(matches!(iter.done(), Err(DatabaseError::TaskExists(_))));
+        assert!(matches!(
+            kite_sql.run("create task bad schedule '* * *' as delete from t1"),
+            Err(DatabaseError::InvalidSchedule(_))
+        ));
+
+        let mut iter = kite_sql.run("show tasks")?;
+        assert_eq!(
+            iter.next().unwrap()?.values,
+            vec![
+                DataValue::Utf8 {
+                    value: "ttl".to_string(),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                },
+                DataValue::Utf8 {
+                    value: "0 0 29 2 *".to_string(),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                },
+                DataValue::Utf8 {
+                    value: "delete from t1 where id < 2".to_string(),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                },
+            ]
+        );
+        assert!(iter.next().is_none());
+        drop(iter);
+
+        // the schedule only matches a leap day, so the scheduler thread does not run it meanwhile
+        let time = |s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        assert!(run_due(&kite_sql, &time("2024-02-28 00:00"))?.is_empty());
+        let results = run_due(&kite_sql, &time("2024-02-29 00:00"))?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "ttl");
+        assert!(results[0].1.is_ok());
+
+        let mut iter = kite_sql.run("select id from t1")?;
+        assert_eq!(iter.next().unwrap()?.values, vec![DataValue::Int32(2)]);
+        assert!(iter.next().is_none());
+        drop(iter);
+
+        kite_sql.run("drop task ttl")?.done()?;
+        kite_sql.run("drop task if exists ttl")?.done()?;
+        assert!(run_due(&kite_sql, &time("2024-02-29 00:00"))?.is_empty());
+
+        Ok(())
+    }
+
+    static PUSHED_FILTERS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    /// Serves `(id, id * 10)` for `id` in `0..5` and applies the pushed down filters on `id`
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Remote;
+
+    #[typetag::serde]
+    impl ForeignDataWrapper for Remote {
+        fn name(&self) -> &str {
+            "remote"
+        }
+
+        fn scan(
+            &self,
+            table: &ForeignTable,
+            columns: &[ColumnRef],
+            filters: &[ForeignFilter],
+        ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError> {
+            assert_eq!(table.connection, "memory");
+            assert_eq!(table.remote_table, "numbers");
+            PUSHED_FILTERS
+                .lock()
+                .unwrap()
+                .extend(filters.iter().map(ToString::to_string));
+
+            let columns = columns
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect::<Vec<_>>();
+            let filters = filters.to_vec();
+            Ok(Box::new((0..5).filter_map(move |id| {
+                let id_value = DataValue::Int32(id);
+                let is_match = filters.iter().all(|filter| match filter.op {
+                    BinaryOperator::Gt => id_value > filter.value,
+                    BinaryOperator::Eq => id_value == filter.value,
+                    _ => true,
+                });
+                is_match.then(|| {
+                    let values = columns
+                        .iter()
+                        .map(|name| match name.as_str() {
+                            "id" => DataValue::Int32(id),
+                            _ => DataValue::Int32(id * 10),
+                        })
+                        .collect();
+                    Ok(Tuple::new(None, values))
+                })
+            })))
+        }
+    }
+
+    #[test]
+    fn test_read_only() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        {
+            let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+            kite_sql
+                .run("create table t1 (a int primary key, b int)")?
+                .done()?;
+            kite_sql.run("insert into t1 values(0, 0)")?.done()?;
+        }
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .read_only(true)
+            .build()?;
+
+        let mut iter = kite_sql.run("select * from t1")?;
+        assert_eq!(
+            iter.next().unwrap()?.values,
+            vec![DataValue::Int32(0), DataValue::Int32(0)]
+        );
+        drop(iter);
+        assert!(matches!(
+            kite_sql.run("insert into t1 values(1, 1)"),
+            Err(DatabaseError::ReadOnlyDatabase)
+        ));
+        assert!(matches!(
+            kite_sql.run("drop table t1"),
+            Err(DatabaseError::ReadOnlyDatabase)
+        ));
+        assert!(matches!(
+            kite_sql.new_transaction()?.run("delete from t1"),
+            Err(DatabaseError::ReadOnlyDatabase)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_sqlstate() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int not null, c int)")?
+            .done()?;
+        kite_sql
+            .run("create unique index c_index on t1 (c)")?
+            .done()?;
+        kite_sql.run("insert into t1 values (0, 0, 0)")?.done()?;
+        let run = |sql: &str| kite_sql.run(sql).and_then(|iter| iter.done()).unwrap_err();
+
+        let err = run("insert into t1 values (0, 1, 1)");
+        assert_eq!(err.sqlstate(), "23505");
+        assert_eq!(err.category(), ErrorCategory::IntegrityConstraint);
+        assert_eq!(err.table(), Some("t1"));
+        assert_eq!(err.constraint(), Some("PRIMARY KEY"));
+
+        let err = run("insert into t1 values (1, 1, 0)");
+        assert_eq!(err.sqlstate(), "23505");
+        assert_eq!(err.constraint(), Some("c_index"));
+
+        let err = run("insert into t1 values (2, null, 2)");
+        assert_eq!(err.sqlstate(), "23502");
+        assert_eq!(err.column(), Some("b"));
+
+        let err = run("create table t1 (a int primary key)");
+        assert_eq!(err.sqlstate(), "42P07");
+        assert_eq!(err.category(), ErrorCategory::DuplicateObject);
+        assert_eq!(err.table(), Some("t1"));
+
+        let err = run("select * from t2");
+        assert_eq!(err.sqlstate(), "42P01");
+        assert_eq!(err.category(), ErrorCategory::UndefinedObject);
+        assert_eq!(err.table(), Some("t2"));
+
+        let err = run("analyze table t3");
+        assert_eq!(err.sqlstate(), "42P01");
+        assert_eq!(err.table(), Some("t3"));
+        assert_eq!(err.to_string(), "table: t3 not found");
+
+        assert_eq!(run("select 1 / 0 +").category(), ErrorCategory::Syntax);
+        assert_eq!(run("select 1 % 0").sqlstate(), "22012");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_volatile_functions() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("create table t2 (c int primary key, d int)")?
+            .done()?;
+        let query = |sql: &str| -> Result<Vec<Tuple>, DatabaseError> {
+            let mut iter = kite_sql.run(sql)?;
+            let tuples = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+            iter.done()?;
+            Ok(tuples)
+        };
+
+        // neither folded into one value, nor merged into one aggregation
+        let randoms = query("select random() from table(numbers(10)) a")?;
+        assert_eq!(
+            randoms
+                .iter()
+                .map(|tuple| &tuple.values[0])
+                .unique()
+                .count(),
+            10
+        );
+        let sums = query("select sum(random()), sum(random()) from table(numbers(10)) a")?;
+        assert_ne!(sums[0].values[0], sums[0].values[1]);
+
+        // kept above the join rather than pushed into one of its sides
+        let plan = query("explain select * from t1 join t2 on a = c where random() < 0.5")?;
+        let plan = plan[0].values[0].to_string();
+        assert!(
+            plan.lines()
+                .nth(1)
+                .unwrap()
+                .trim_start()
+                .starts_with("Filter"),
+            "{plan}"
+        );
+
+        Ok(())
+    }
+
+    /// Returns the signature it was registered with, to tell which overload was picked.
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Overload {
+        summary: FunctionSummary,
+    }
+
+    impl Overload {
+        fn new(name: &str, arg_types: Vec<LogicalType>) -> Arc<Self> {
+            Arc::new(Overload {
+                summary: FunctionSummary {
+                    name: name.to_string(),
+                    arg_types,
+                },
+            })
+        }
+    }
+
+    #[typetag::serde]
+    impl ScalarFunctionImpl for Overload {
+        fn eval(
+            &self,
+            _: &[ScalarExpression],
+            _: Option<(&Tuple, &[ColumnRef])>,
+        ) -> Result<DataValue, DatabaseError> {
+            Ok(DataValue::Utf8 {
+                value: self.summary.to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            })
+        }
+
+        fn monotonicity(&self) -> Option<FuncMonotonicity> {
+            None
+        }
+
+        fn return_type(&self) -> &LogicalType {
+            &LogicalType::Varchar(None, CharLengthUnits::Characters)
+        }
+
+        fn summary(&self) -> &FunctionSummary {
+            &self.summary
+        }
+    }
+
+    #[test]
+    fn test_function_overloading() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .register_scala_function(Overload::new("pick", vec![LogicalType::Bigint]))
+            .register_scala_function(Overload::new("pick", vec![LogicalType::Double]))
+            .register_scala_function(Overload::new(
+                "pick",
+                vec![LogicalType::Double, LogicalType::Bigint],
+            ))
+            .register_scala_function(Overload::new(
+                "pick",
+                vec![LogicalType::Bigint, LogicalType::Double],
+            ))
+            .register_scala_function(Overload::new("geo.pick", vec![LogicalType::Integer]))
+            .build()?;
+        let query = |sql: &str| -> Result<Vec<DataValue>, DatabaseError> {
+            let mut iter = kite_sql.run(sql)?;
+            let tuple = iter.next().unwrap()?;
+            iter.done()?;
+            Ok(tuple.values)
+        };
+        let picked =
+            |sql: &str| -> Result<String, DatabaseError> { Ok(query(sql)?[0].to_string()) };
+
+        assert_eq!(
+            query("select round(2.567, 2), round(2.5), round(-3)")?,
+            vec![
+                DataValue::Float64(2.57.into()),
+                DataValue::Float64(3.0.into()),
+                DataValue::Float64((-3.0).into()),
+            ]
+        );
+        // the most specific overload the argument promotes to
+        assert_eq!(picked("select pick(1)")?, "pick(Bigint)");
+        assert_eq!(picked("select pick(1.5)")?, "pick(Double)");
+        assert_eq!(picked("select pick(1.5, 1)")?, "pick(Double, Bigint)");
+        assert_eq!(picked("select geo.pick(1)")?, "geo.pick(Integer)");
+
+        let err = query("select pick(1, 1)").unwrap_err();
+        assert!(matches!(err, DatabaseError::AmbiguousFunction(_)));
+        assert_eq!(err.sqlstate(), "42725");
+        let err = query("select pick('a')").unwrap_err();
+        assert!(matches!(err, DatabaseError::FunctionNotFound(_)));
+        assert!(matches!(
+            query("select pick(1, 1, 1)"),
+            Err(DatabaseError::FunctionNotFound(_))
+        ));
+        assert!(matches!(
+            query("select geo.pick(1.5)"),
+            Err(DatabaseError::FunctionNotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeatable_read() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .isolation_level(IsolationLevel::RepeatableRead)
+            .wal_options(WalOptions {
+                sync: true,
+                ..Default::default()
+            })
+            .parallelism(2)
+            .build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql.run("insert into t1 values(0, 0)")?.done()?;
+
+        let mut tx = kite_sql.new_transaction()?;
+        let select_b = |tx: &mut DBTransaction<RocksStorage>| -> Result<_, DatabaseError> {
+            let mut iter = tx.run("select b from t1 where a = 0")?;
+            let values = iter.next().unwrap()?.values;
+            drop(iter);
+
+            Ok(values)
+        };
+        assert_eq!(select_b(&mut tx)?, vec![DataValue::Int32(0)]);
+        kite_sql.run("update t1 set b = 1 where a = 0")?.done()?;
+        // the transaction still reads the value as of its beginning
+        assert_eq!(select_b(&mut tx)?, vec![DataValue::Int32(0)]);
+
+        tx.run("update t1 set b = 2 where a = 0")?.done()?;
+        assert!(tx.commit().is_err());
+
+        let mut iter = kite_sql.run("select b from t1")?;
+        assert_eq!(iter.next().unwrap()?.values, vec![DataValue::Int32(1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_version() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        assert_eq!(kite_sql.table_version("t1")?, TableVersion::default());
+
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        let created = kite_sql.table_version("t1")?;
+        assert!(created.catalog > 0);
+
+        // ANALYZE builds a histogram of 100 buckets, which needs as many rows
+        for i in 0..101 {
+            kite_sql
+                .run(format!("insert into t1 values ({i}, {i})"))?
+                .done()?;
+        }
+        kite_sql.run("select * from t1")?.done()?;
+        assert_eq!(kite_sql.table_version("t1")?, created);
+
+        kite_sql.run("analyze table t1")?.done()?;
+        let analyzed = kite_sql.table_version("t1")?;
+        assert_eq!(analyzed.catalog, created.catalog);
+        assert!(analyzed.statistics > created.statistics);
+
+        kite_sql.run("create index b_index on t1 (b)")?.done()?;
+        let indexed = kite_sql.table_version("t1")?;
+        assert!(indexed.catalog > analyzed.catalog);
+
+        kite_sql.run("drop index t1.b_index")?.done()?;
+        assert!(kite_sql.table_version("t1")?.catalog > indexed.catalog);
+
+        // a rolled back DDL statement leaves the versions as they were
+        let dropped = kite_sql.table_version("t1")?;
+        let mut tx = kite_sql.new_ddl_transaction()?;
+        tx.run("alter table t1 add column c int")?.done()?;
+        drop(tx);
+        assert_eq!(kite_sql.table_version("t1")?, dropped);
+
+        // the versions of a table created again go on from those of the one dropped
+        kite_sql.run("drop table t1")?.done()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        assert!(kite_sql.table_version("t1")?.catalog > dropped.catalog + 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_cache() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        // ANALYZE builds a histogram of 100 buckets, which needs as many rows
+        for i in 0..200 {
+            kite_sql
+                .run(format!("insert into t1 values ({i}, {})", i % 50))?
+                .done()?;
+        }
+        kite_sql.run("create index b_index on t1 (b)")?.done()?;
+        kite_sql.run("analyze table t1")?.done()?;
+
+        let rows = |statement: &Statement, params: &[(&'static str, DataValue)]| {
+            kite_sql
+                .execute(statement, params)?
+                .map(|tuple| tuple.map(|tuple| tuple.values))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let explain = kite_sql.prepare("explain select a from t1 where b = 1")?;
+        assert!(rows(&explain, &[])?[0][0]
+            .utf8()
+            .unwrap()
+            .contains("IndexScan By b_index"));
+
+        let statement = kite_sql.prepare("select a from t1 where b = ?1")?;
+        let b_1 = [("?1", DataValue::Int32(1))];
+        let expected = [1, 51, 101, 151]
+            .map(|a| vec![DataValue::Int32(a)])
+            .to_vec();
+        assert_eq!(rows(&statement, &b_1)?, expected);
+        assert_eq!(rows(&statement, &b_1)?, expected);
+        let stats = kite_sql.cache_stats().plan;
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+
+        // another value of the placeholder is bound on its own
+        assert_eq!(rows(&statement, &[("?1", DataValue::Int32(0))])?.len(), 4);
+        assert_eq!(kite_sql.cache_stats().plan.misses, 2);
+
+        // the plan reading the index dropped, which has no keys left, is bound again
+        kite_sql.run("drop index t1.b_index")?.done()?;
+        assert_eq!(rows(&statement, &b_1)?, expected);
+
+        let statement = kite_sql.prepare("select * from t1 where a = 1")?;
+        assert_eq!(
+            rows(&statement, &[])?,
+            vec![vec![DataValue::Int32(1), DataValue::Int32(1)]]
+        );
+        kite_sql
+            .run("alter table t1 add column c int default 7")?
+            .done()?;
+        assert_eq!(
+            rows(&statement, &[])?,
+            vec![vec![
+                DataValue::Int32(1),
+                DataValue::Int32(1),
+                DataValue::Int32(7)
+            ]]
+        );
+
+        // and so is the plan of a view created again
+        kite_sql.run("create view v1 as select a from t1")?.done()?;
+        let statement = kite_sql.prepare("select * from v1 where a = 2")?;
+        assert_eq!(rows(&statement, &[])?, vec![vec![DataValue::Int32(2)]]);
+        kite_sql.run("drop view v1")?.done()?;
+        kite_sql
+            .run("create view v1 as select a, c from t1")?
+            .done()?;
+        let expected = vec![vec![DataValue::Int32(2), DataValue::Int32(7)]];
+        assert_eq!(rows(&statement, &[])?, expected);
+
+        // a DDL statement rolled back leaves the plan as it was
+        let mut tx = kite_sql.new_ddl_transaction()?;
+        tx.run("drop view v1")?.done()?;
+        drop(tx);
+        assert_eq!(rows(&statement, &[])?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_throttle() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .write_throttle(WriteThrottle {
+                table_writes_per_second: BTreeMap::from([
+                    ("t1".to_string(), 1),
+                    ("t3".to_string(), 0),
+                ]),
+                ..Default::default()
+            })
+            .build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("create table t2 (a int primary key, b int)")?
+            .done()?;
+        kite_sql.run("insert into t1 values (0, 0)")?.done()?;
+
+        let err = kite_sql
+            .run("insert into t1 values (1, 1)")
+            .and_then(|iter| iter.done())
+            .unwrap_err();
+        assert_eq!(err.table(), Some("t1"));
+        assert_eq!(err.category(), ErrorCategory::Throttled);
+        assert!(matches!(
+            err,
+            DatabaseError::Throttled { reason, .. } if reason == "more than 1 writes per second"
+        ));
+        // the other tables are not throttled
+        for i in 0..3 {
+            kite_sql
+                .run(format!("insert into t2 values ({i}, {i})"))?
+                .done()?;
+        }
+        kite_sql
+            .run("create table t3 (a int primary key, b int)")?
+            .done()?;
+        assert!(matches!(
+            kite_sql
+                .run("insert into t3 values (0, 0)")
+                .and_then(|iter| iter.done()),
+            Err(DatabaseError::Throttled { .. })
+        ));
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .write_throttle(WriteThrottle {
+                max_wal_size: Some(0),
+                ..Default::default()
+            })
+            .build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        assert!(kite_sql.write_pressure()?.wal_size > 0);
+        assert!(matches!(
+            kite_sql
+                .run("insert into t1 values (0, 0)")
+                .and_then(|iter| iter.done()),
+            Err(DatabaseError::Throttled { .. })
+        ));
+
+        Ok(())
+    }
+
+    struct AnalystPolicy;
+
+    impl MaskingPolicy for AnalystPolicy {
+        fn masking(&self, role: &str, table: &str, column: &str) -> Option<Masking> {
+            if role != "analyst" || table != "users" {
+                return None;
+            }
+            match column {
+                "email" => Some(Masking::Email),
+                "card" => Some(Masking::Partial { keep_last: 4 }),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_masking() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .masking_policy(Arc::new(AnalystPolicy))
+            .build()?;
+        let rows = |role: Option<&str>, sql: &str| -> Result<Vec<Vec<String>>, DatabaseError> {
+            match role {
+                Some(role) => kite_sql.run_as(role, sql)?,
+                None => kite_sql.run(sql)?,
+            }
+            .map(|tuple| tuple.map(|tuple| tuple.values.iter().map(ToString::to_string).collect()))
+            .collect()
+        };
+        kite_sql
+            .run("create table users (id int primary key, email varchar, card varchar)")?
+            .done()?;
+        kite_sql
+            .run("insert into users values (1, 'john.doe@example.com', '4111111111111111')")?
+            .done()?;
+        kite_sql
+            .run("create view masked_users as select id, mask_email(email) as email from users")?
+            .done()?;
+
+        assert_eq!(
+            rows(
+                None,
+                "select mask_email('a.b@c.d'), mask_partial('secret', 2)"
+            )?,
+            vec![vec!["a**@c.d", "****et"]]
+        );
+
+        let analyst = Some("analyst");
+        assert_eq!(
+            rows(analyst, "select * from users")?,
+            vec![vec!["1", "j*******@example.com", "************1111"]]
+        );
+        // the filters read the masked values as well
+        assert!(rows(
+            analyst,
+            "select id from users where email = 'john.doe@example.com'"
+        )?
+        .is_empty());
+        assert!(matches!(
+            kite_sql.run_as("analyst", "update users set card = '0' where id = 1"),
+            Err(DatabaseError::MaskedTable { .. })
+        ));
+        assert!(matches!(
+            kite_sql.run_as("analyst", "delete from users where id = 1"),
+            Err(DatabaseError::MaskedTable { .. })
+        ));
+        // the SQL of a session cannot change its role
+        assert!(kite_sql.run_as("analyst", "set role none").is_err());
+
+        // the files written by COPY TO hold the masked values
+        let path = temp_dir.path().join("users.csv");
+        kite_sql
+            .run_as("analyst", format!("copy users to '{}'", path.display()))?
+            .done()?;
+        let content = std::fs::read_to_string(&path).expect("unable to read the copied file");
+        assert!(content.contains("j*******@example.com"));
+        assert!(!content.contains("john.doe@example.com"));
+        assert!(!content.contains("4111111111111111"));
+
+        let mut tx = kite_sql.new_transaction_as("analyst")?;
+        let mut iter = tx.run("select email from users")?;
+        assert_eq!(
+            iter.next().unwrap()?.values[0].to_string(),
+            "j*******@example.com"
+        );
+        iter.done()?;
+        tx.commit()?;
+
+        // the other sessions read the table as stored
+        assert_eq!(
+            rows(None, "select email from users")?,
+            vec![vec!["john.doe@example.com"]]
+        );
+        assert_eq!(
+            rows(None, "select email from masked_users")?,
+            vec![vec!["j*******@example.com"]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_options() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        assert!(matches!(
+            DataBaseBuilder::path(temp_dir.path())
+                .cache_options(CacheOptions {
+                    view: 0,
+                    ..Default::default()
+                })
+                .build(),
+            Err(DatabaseError::InvalidValue(_))
+        ));
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .cache_options(CacheOptions {
+                table: 5,
+                ..Default::default()
+            })
+            .build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v int)")?
+            .done()?;
+        for _ in 0..3 {
+            kite_sql.run("select * from t1")?.done()?;
+        }
+
+        let stats = kite_sql.cache_stats();
+        assert_eq!(stats.table.capacity, 8);
+        assert_eq!(stats.table.entries, 1);
+        assert!(stats.table.hits >= 2);
+        assert!(stats.table.hit_ratio() > 0.0);
+        assert_eq!(stats.view.capacity, 12);
+        assert_eq!(stats.statistics_meta.capacity, 256);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_show_table_status() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, v varchar)")?
+            .done()?;
+        kite_sql.run("create index v_index on t1 (v)")?.done()?;
+        kite_sql
+            .run("insert into t1 values (0, 'a'), (1, 'b'), (2, null)")?
+            .done()?;
+
+        let tuples = kite_sql
+            .run("show table status")?
+            .collect::<Result<Vec<_>, _>>()?;
+        let utf8 = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+        assert_eq!(tuples.len(), 2);
+        assert_eq!(
+            tuples[0].values[..3],
+            [utf8("t1"), DataValue::Null, DataValue::UInt64(3)]
+        );
+        assert_eq!(
+            tuples[1].values[..3],
+            [utf8("t1"), utf8("v_index"), DataValue::UInt64(3)]
+        );
+        for tuple in tuples {
+            assert!(matches!(tuple.values[3], DataValue::UInt64(bytes) if bytes > 0));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_foreign_table() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .register_foreign_data_wrapper(Arc::new(Remote))
+            .build()?;
+
+        kite_sql
+            .run("create table t1 (id int primary key, v int) with (wrapper = 'remote', connection = 'memory', remote_table = 'numbers')")?
+            .done()?;
+
+        let mut iter = kite_sql.run("select v from t1 where id > 2")?;
+        assert_eq!(iter.next().unwrap()?.values, vec![DataValue::Int32(30)]);
+        assert_eq!(iter.next().unwrap()?.values, vec![DataValue::Int32(40)]);
+        assert!(iter.next().is_none());
+        drop(iter);
+        assert_eq!(*PUSHED_FILTERS.lock().unwrap(), vec!["id > 2".to_string()]);
+
+        assert!(matches!(
+            kite_sql.run("insert into t1 values(5, 50)"),
+            Err(DatabaseError::ReadOnlyForeignTable(_))
+        ));
+        assert!(matches!(
+            kite_sql.run("create table t2 (id int primary key) with (wrapper = 'unknown', connection = 'memory')"),
+            Err(DatabaseError::ForeignDataWrapperNotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    /// Serves `(id, id * 10)` for `id` in `0..4` as the rows of `t_remote`
+    #[derive(Debug, Serialize, Deserialize)]
+    struct RemoteScan;
+
+    #[typetag::serde]
+    impl CustomExecutor for RemoteScan {
+        fn name(&self) -> &str {
+            "remote_scan"
+        }
+
+        fn matches(&self, operator: &Operator) -> bool {
+            matches!(operator, Operator::TableScan(op) if op.table_name().as_str() == "t_remote")
+        }
+
+        fn execute<'a>(&self, operator: Operator, _: Vec<Executor<'a>>) -> Executor<'a> {
+            let Operator::TableScan(op) = operator else {
+                unreachable!()
+            };
+            let columns = op
+                .columns()
+                .map(|column| column.name().to_string())
+                .collect_vec();
+            let (offset, limit) = op.limit();
+
+            Box::new(
+                #[coroutine]
+                move || {
+                    for id in (0..4).skip(offset.unwrap_or(0)).take(limit.unwrap_or(4)) {
+                        let values = columns
+                            .iter()
+                            .map(|name| match name.as_str() {
+                                "id" => DataValue::Int32(id),
+                                _ => DataValue::Int32(id * 10),
+                            })
+                            .collect();
+                        yield Ok(Tuple::new(None, values));
+                    }
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_custom_executor() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .register_executor(Arc::new(RemoteScan))
+            .build()?;
+
+        kite_sql
+            .run("create table t_remote (id int primary key, v int)")?
+            .done()?;
+        kite_sql
+            .run("create table t_local (id int primary key, v int)")?
+            .done()?;
+        kite_sql.run("insert into t_local values(1, 1)")?.done()?;
+
+        let mut iter = kite_sql.run("explain select v from t_remote where id > 1")?;
+        let plan = iter.next().unwrap()?.values[0].utf8().unwrap().to_string();
+        assert!(plan.contains("[Custom remote_scan]"), "{plan}");
+        assert!(!plan.contains("Fused"), "{plan}");
+        iter.done()?;
+
+        let rows = |sql: &str| -> Result<Vec<Vec<DataValue>>, DatabaseError> {
+            kite_sql
+                .run(sql)?
+                .map(|tuple| tuple.map(|tuple| tuple.values))
+                .try_collect()
+        };
+        assert_eq!(
+            rows("select v from t_remote where id > 1")?,
+            vec![vec![DataValue::Int32(20)], vec![DataValue::Int32(30)]]
+        );
+        assert_eq!(
+            rows("select count(*) from t_remote")?,
+            vec![vec![DataValue::Int32(4)]]
+        );
+        assert_eq!(
+            rows("select id from t_remote limit 1 offset 2")?,
+            vec![vec![DataValue::Int32(2)]]
+        );
+        assert_eq!(
+            rows("select t_remote.v from t_remote join t_local on t_remote.id = t_local.id")?,
+            vec![vec![DataValue::Int32(10)]]
+        );
+        assert_eq!(
+            rows("select v from t_local")?,
+            vec![vec![DataValue::Int32(1)]]
+        );
+
+        Ok(())
+    }
+
+    static TENANT_SCAN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+        predicate: |op| matches!(op, Operator::TableScan(op) if op.table_name().as_str() == "t_tenant"),
+        children: PatternChildrenPredicate::None,
+    });
+
+    /// Keeps the rows of `t_tenant` of the tenant `1`
+    struct TenantFilter;
+
+    impl MatchPattern for TenantFilter {
+        fn pattern(&self) -> &Pattern {
+            &TENANT_SCAN
+        }
+    }
+
+    impl NormalizationRule for TenantFilter {
+        fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+            let Operator::TableScan(op) = graph.operator(node_id) else {
+                return Ok(());
+            };
+            let column = op
+                .columns()
+                .find(|column| column.name() == "tenant")
+                .cloned()
+                .unwrap();
+            let predicate = ScalarExpression::Binary {
+                op: BinaryOperator::Eq,
+                left_expr: Box::new(ScalarExpression::ColumnRef(column)),
+                right_expr: Box::new(ScalarExpression::Constant(DataValue::Int32(1))),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            };
+            let parent_id = graph.parent_id(node_id).unwrap();
+
+            if matches!(graph.operator(parent_id), Operator::Filter(op) if op.predicate == predicate)
+            {
+                return Ok(());
+            }
+            graph.add_node(
+                parent_id,
+                Some(node_id),
+                Operator::Filter(FilterOperator {
+                    predicate,
+                    is_optimized: false,
+                    having: false,
+                }),
+            );
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_normalization_rule() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .register_normalization_rule(Arc::new(TenantFilter))
+            .build()?;
+
+        kite_sql
+            .run("create table t_tenant (id int primary key, tenant int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t_tenant values (1, 1), (2, 2), (3, 1)")?
+            .done()?;
+
+        let rows = |sql: &str| -> Result<Vec<Vec<DataValue>>, DatabaseError> {
+            kite_sql
+                .run(sql)?
+                .map(|tuple| tuple.map(|tuple| tuple.values))
+                .try_collect()
+        };
+        assert_eq!(
+            rows("select id from t_tenant")?,
+            vec![vec![DataValue::Int32(1)], vec![DataValue::Int32(3)]]
+        );
+        assert_eq!(
+            rows("select count(*) from t_tenant where id > 1")?,
+            vec![vec![DataValue::Int32(1)]]
+        );
+        assert_eq!(
+            rows("select a.id from t_tenant a join t_tenant b on a.id = b.id")?,
+            vec![vec![DataValue::Int32(1)], vec![DataValue::Int32(3)]]
+        );
+
+        Ok(())
+    }
 }
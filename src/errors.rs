@@ -1,4 +1,5 @@
 use crate::expression::{BinaryOperator, UnaryOperator};
+use crate::storage::fault::Fault;
 use crate::types::tuple::TupleId;
 use crate::types::LogicalType;
 use chrono::ParseError;
@@ -11,6 +12,12 @@ use std::string::FromUtf8Error;
 pub enum DatabaseError {
     #[error("agg miss: {0}")]
     AggMiss(String),
+    #[error("function: {0} is ambiguous")]
+    AmbiguousFunction(String),
+    #[error("database: {0} is already attached")]
+    AttachedDatabaseExists(String),
+    #[error("database: {0} is not attached")]
+    AttachedDatabaseNotFound(String),
     #[error("bindcode: {0}")]
     Bincode(
         #[source]
@@ -19,8 +26,12 @@ pub enum DatabaseError {
     ),
     #[error("cache size overflow")]
     CacheSizeOverFlow,
+    #[error("statement cancelled")]
+    Cancelled,
     #[error("cast fail: {from} -> {to}")]
     CastFail { from: LogicalType, to: LogicalType },
+    #[error("cast fail: '{value}' is not a valid {to}")]
+    CastValueFail { value: String, to: LogicalType },
     #[error("channel close")]
     ChannelClose,
     #[error("columns empty")]
@@ -39,18 +50,34 @@ pub enum DatabaseError {
     DefaultNotColumnRef,
     #[error("default does not exist")]
     DefaultNotExist,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("column: {0} was dropped with another type, vacuum the table before adding it again")]
+    DroppedColumn(String),
     #[error("column: {0} already exists")]
     DuplicateColumn(String),
     #[error("table or view: {0} hash already exists")]
     DuplicateSourceHash(String),
     #[error("index: {0} already exists")]
     DuplicateIndex(String),
-    #[error("duplicate primary key")]
-    DuplicatePrimaryKey,
-    #[error("the column has been declared unique and the value already exists")]
-    DuplicateUniqueValue,
+    #[error("duplicate primary key of table: {table}")]
+    DuplicatePrimaryKey { table: String },
+    #[error("the column has been declared unique and the value already exists in table: {table}")]
+    DuplicateUniqueValue {
+        table: String,
+        /// The unique index, named by the statements writing to it
+        index: Option<String>,
+    },
+    #[error("foreign data wrapper: {0} not found")]
+    ForeignDataWrapperNotFound(String),
+    #[error("foreign data wrapper: {0}")]
+    ForeignDataWrapper(String),
     #[error("function: {0} not found")]
     FunctionNotFound(String),
+    #[error(
+        "function: {0} is not registered, register it with `DataBaseBuilder::register_scala_function`"
+    )]
+    FunctionNotRegistered(String),
     #[error("empty plan")]
     EmptyPlan,
     #[error("sql statement is empty")]
@@ -65,10 +92,14 @@ pub enum DatabaseError {
     ),
     #[error("can not compare two types: {0} and {1}")]
     Incomparable(LogicalType, LogicalType),
+    #[error("injected fault: {0:?}")]
+    InjectedFault(Fault),
     #[error("invalid column: {0}")]
     InvalidColumn(String),
     #[error("invalid index")]
     InvalidIndex,
+    #[error("invalid schedule: {0}")]
+    InvalidSchedule(String),
     #[error("invalid table: {0}")]
     InvalidTable(String),
     #[error("invalid type")]
@@ -81,6 +112,8 @@ pub enum DatabaseError {
         #[from]
         std::io::Error,
     ),
+    #[error("the schema is still read by statements or transactions after {0:?}, see `DataBaseBuilder::lock_timeout`")]
+    LockTimeout(std::time::Duration),
     #[error("{0} and {1} do not match")]
     MisMatch(&'static str, &'static str),
     #[error("add column must be nullable or specify a default value")]
@@ -89,10 +122,12 @@ pub enum DatabaseError {
     ParametersNotFound(String),
     #[error("no transaction begin")]
     NoTransactionBegin,
-    #[error("cannot be null")]
-    NotNull,
+    #[error("column: {0} cannot be null")]
+    NotNull(String),
     #[error("over flow")]
     OverFlow,
+    #[error("parquet: {0}")]
+    Parquet(String),
     #[error("parser bool: {0}")]
     ParseBool(
         #[source]
@@ -117,6 +152,12 @@ pub enum DatabaseError {
         #[from]
         ParseIntError,
     ),
+    #[error("parser numeric: {0}")]
+    ParseNumeric(
+        #[source]
+        #[from]
+        bigdecimal::ParseBigDecimalError,
+    ),
     #[error("parser sql: {0}")]
     ParserSql(
         #[source]
@@ -127,6 +168,26 @@ pub enum DatabaseError {
     PrimaryKeyNotFound,
     #[error("primaryKey only allows single or multiple values")]
     PrimaryKeyTooManyLayers,
+    #[error("process: {0} not found")]
+    ProcessNotFound(u64),
+    #[error("attached database: {0} is read-only")]
+    ReadOnlyAttachedDatabase(String),
+    #[error("the database is read-only, see `DataBaseBuilder::read_only`")]
+    ReadOnlyDatabase,
+    #[error("foreign table: {0} is read-only")]
+    ReadOnlyForeignTable(String),
+    #[error("the database follows a primary and is read-only, drop its `Follower` to write")]
+    ReadOnlyReplica,
+    #[error("recursive query: {0} went deeper than {1} iterations, raise `DataBaseBuilder::max_recursion_depth` or stop the cycles by a `CYCLE` clause")]
+    RecursionDepthExceeded(String, usize),
+    #[error(
+        "recursive query: {0} gave more than {1} rows, raise `DataBaseBuilder::max_recursion_rows`"
+    )]
+    RecursionRowsExceeded(String, usize),
+    #[error("replication: {0}")]
+    Replication(String),
+    #[error("the database keeps no replication log, see `DataBaseBuilder::replication_log`")]
+    ReplicationLogNotFound,
     #[error("rocksdb: {0}")]
     RocksDB(
         #[source]
@@ -135,12 +196,18 @@ pub enum DatabaseError {
     ),
     #[error("the number of caches cannot be divisible by the number of shards")]
     SharedNotAlign,
-    #[error("the table or view not found")]
-    SourceNotFound,
-    #[error("the table already exists")]
-    TableExists,
-    #[error("the table not found")]
-    TableNotFound,
+    #[error("the database is stored in format version {found}, newer than {expected}: open it by the version of KiteSQL that wrote it")]
+    StorageFormatMismatch { found: u32, expected: u32 },
+    #[error("the table or view: {0} not found")]
+    SourceNotFound(String),
+    #[error("table: {0} already exists")]
+    TableExists(String),
+    #[error("table: {0} not found")]
+    TableNotFound(String),
+    #[error("task: {0} already exists")]
+    TaskExists(String),
+    #[error("task: {0} not found")]
+    TaskNotFound(String),
     #[error("transaction already exists")]
     TransactionAlreadyExists,
     #[error("try from decimal: {0}")]
@@ -173,10 +240,216 @@ pub enum DatabaseError {
         #[from]
         Utf8Error,
     ),
+    #[error("role: {role} reads table: {table} masked and cannot write it")]
+    MaskedTable { role: String, table: String },
+    #[error("writes to table: {table} are throttled, {reason}, retry later")]
+    Throttled { table: String, reason: String },
     #[error("values length not match, expect {0}, got {1}")]
     ValuesLenMismatch(usize, usize),
-    #[error("the view already exists")]
-    ViewExists,
-    #[error("the view not found")]
-    ViewNotFound,
+    #[error("view: {0} already exists")]
+    ViewExists(String),
+    #[error("view: {0} not found")]
+    ViewNotFound(String),
+}
+
+/// The class of a [`DatabaseError`], given by its SQLSTATE, for the errors to be handled
+/// without matching each variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// The statement cannot be parsed or uses a feature not supported
+    Syntax,
+    /// A table, column, function or another object named by the statement does not exist
+    UndefinedObject,
+    /// The object to create already exists
+    DuplicateObject,
+    /// The statement does not fit the types or the definitions of its objects
+    Semantic,
+    /// A write would break a primary key, a unique index or a `NOT NULL` column
+    IntegrityConstraint,
+    /// A value is invalid or out of range, e.g. a failed cast or a division by zero
+    Data,
+    /// The transaction cannot run the statement, e.g. the database is read-only
+    TransactionState,
+    /// The statement was cancelled or went over a limit of the database
+    Cancelled,
+    /// The storage falls behind the writes, the statement can be retried once it catches up
+    Throttled,
+    /// The storage, a file or a remote source failed
+    System,
+    /// A bug of the database
+    Internal,
+}
+
+impl DatabaseError {
+    /// The SQLSTATE of the error, as PostgreSQL would report it.
+    pub fn sqlstate(&self) -> &'static str {
+        match self {
+            DatabaseError::ParserSql(_) | DatabaseError::EmptyStatement => "42601",
+            DatabaseError::ValuesLenMismatch(..) => "42601",
+            DatabaseError::UnsupportedStmt(_) => "0A000",
+
+            DatabaseError::TableNotFound(_)
+            | DatabaseError::SourceNotFound(_)
+            | DatabaseError::ViewNotFound(_) => "42P01",
+            DatabaseError::ColumnNotFound(_) | DatabaseError::ColumnIdNotFound(_) => "42703",
+            DatabaseError::FunctionNotFound(_)
+            | DatabaseError::FunctionNotRegistered(_)
+            | DatabaseError::UnsupportedUnaryOperator(..)
+            | DatabaseError::UnsupportedBinaryOperator(..) => "42883",
+            DatabaseError::AmbiguousFunction(_) => "42725",
+            DatabaseError::MaskedTable { .. } => "42501",
+            DatabaseError::ParametersNotFound(_) => "42P02",
+            DatabaseError::AttachedDatabaseNotFound(_)
+            | DatabaseError::ForeignDataWrapperNotFound(_)
+            | DatabaseError::TaskNotFound(_)
+            | DatabaseError::ProcessNotFound(_)
+            | DatabaseError::DefaultNotExist => "42704",
+
+            DatabaseError::TableExists(_)
+            | DatabaseError::ViewExists(_)
+            | DatabaseError::DuplicateSourceHash(_) => "42P07",
+            DatabaseError::DuplicateColumn(_) | DatabaseError::DroppedColumn(_) => "42701",
+            DatabaseError::DuplicateIndex(_)
+            | DatabaseError::TaskExists(_)
+            | DatabaseError::AttachedDatabaseExists(_) => "42710",
+
+            DatabaseError::CastFail { .. }
+            | DatabaseError::Incomparable(..)
+            | DatabaseError::InvalidType
+            | DatabaseError::MisMatch(..) => "42804",
+            DatabaseError::ColumnsEmpty
+            | DatabaseError::DefaultNotColumnRef
+            | DatabaseError::InvalidColumn(_)
+            | DatabaseError::InvalidIndex
+            | DatabaseError::InvalidTable(_)
+            | DatabaseError::NeedNullAbleOrDefault
+            | DatabaseError::PrimaryKeyNotFound
+            | DatabaseError::PrimaryKeyTooManyLayers => "42P16",
+
+            DatabaseError::DuplicatePrimaryKey { .. }
+            | DatabaseError::DuplicateUniqueValue { .. } => "23505",
+            DatabaseError::NotNull(_) => "23502",
+
+            DatabaseError::DivisionByZero => "22012",
+            DatabaseError::OverFlow
+            | DatabaseError::TryFromInt(_)
+            | DatabaseError::TryFromDecimal(_) => "22003",
+            DatabaseError::CastValueFail { .. }
+            | DatabaseError::ParseBool(_)
+            | DatabaseError::ParseDate(_)
+            | DatabaseError::ParseFloat(_)
+            | DatabaseError::ParseInt(_)
+            | DatabaseError::ParseNumeric(_) => "22P02",
+            DatabaseError::TooLong => "22001",
+            DatabaseError::Utf8(_) | DatabaseError::FromUtf8Error(_) => "22021",
+            DatabaseError::Csv(_) => "22P04",
+            DatabaseError::InvalidValue(_)
+            | DatabaseError::InvalidSchedule(_)
+            | DatabaseError::TooManyBuckets(..)
+            | DatabaseError::CacheSizeOverFlow
+            | DatabaseError::SharedNotAlign => "22023",
+
+            DatabaseError::NoTransactionBegin => "25P01",
+            DatabaseError::TransactionAlreadyExists => "25001",
+            DatabaseError::ReadOnlyAttachedDatabase(_)
+            | DatabaseError::ReadOnlyDatabase
+            | DatabaseError::ReadOnlyForeignTable(_)
+            | DatabaseError::ReadOnlyReplica => "25006",
+            DatabaseError::ReplicationLogNotFound => "55000",
+            DatabaseError::LockTimeout(_) => "55P03",
+
+            DatabaseError::Cancelled => "57014",
+            DatabaseError::RecursionDepthExceeded(..)
+            | DatabaseError::RecursionRowsExceeded(..) => "54001",
+            DatabaseError::Throttled { .. } => "53000",
+
+            DatabaseError::IO(_) | DatabaseError::RocksDB(_) | DatabaseError::Parquet(_) => "58030",
+            DatabaseError::InjectedFault(_) | DatabaseError::StorageFormatMismatch { .. } => {
+                "58000"
+            }
+            DatabaseError::ForeignDataWrapper(_) => "HV000",
+            DatabaseError::Replication(_) => "08000",
+
+            DatabaseError::AggMiss(_)
+            | DatabaseError::Bincode(_)
+            | DatabaseError::ChannelClose
+            | DatabaseError::EmptyPlan
+            | DatabaseError::EvaluatorNotFound
+            | DatabaseError::TupleIdNotFound(_) => "XX000",
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self.sqlstate() {
+            "42601" | "0A000" => ErrorCategory::Syntax,
+            "42P01" | "42703" | "42883" | "42P02" | "42704" => ErrorCategory::UndefinedObject,
+            "42P07" | "42701" | "42710" => ErrorCategory::DuplicateObject,
+            sqlstate if sqlstate.starts_with("42") => ErrorCategory::Semantic,
+            sqlstate if sqlstate.starts_with("23") => ErrorCategory::IntegrityConstraint,
+            sqlstate if sqlstate.starts_with("22") => ErrorCategory::Data,
+            sqlstate if sqlstate.starts_with("25") || sqlstate.starts_with("55") => {
+                ErrorCategory::TransactionState
+            }
+            sqlstate if sqlstate.starts_with("57") || sqlstate.starts_with("54") => {
+                ErrorCategory::Cancelled
+            }
+            sqlstate if sqlstate.starts_with("53") => ErrorCategory::Throttled,
+            "XX000" => ErrorCategory::Internal,
+            _ => ErrorCategory::System,
+        }
+    }
+
+    /// The table, view or foreign table the error is about, if it names one.
+    pub fn table(&self) -> Option<&str> {
+        match self {
+            DatabaseError::DuplicatePrimaryKey { table }
+            | DatabaseError::DuplicateUniqueValue { table, .. }
+            | DatabaseError::TableExists(table)
+            | DatabaseError::ViewExists(table)
+            | DatabaseError::ViewNotFound(table)
+            | DatabaseError::TableNotFound(table)
+            | DatabaseError::SourceNotFound(table)
+            | DatabaseError::DuplicateSourceHash(table)
+            | DatabaseError::InvalidTable(table)
+            | DatabaseError::ReadOnlyForeignTable(table)
+            | DatabaseError::Throttled { table, .. }
+            | DatabaseError::MaskedTable { table, .. } => Some(table),
+            _ => None,
+        }
+    }
+
+    /// The column the error is about, if it names one.
+    pub fn column(&self) -> Option<&str> {
+        match self {
+            DatabaseError::ColumnNotFound(column)
+            | DatabaseError::DroppedColumn(column)
+            | DatabaseError::DuplicateColumn(column)
+            | DatabaseError::InvalidColumn(column)
+            | DatabaseError::NotNull(column) => Some(column),
+            _ => None,
+        }
+    }
+
+    /// The index whose constraint the error breaks, `PRIMARY KEY` for the primary key.
+    pub fn constraint(&self) -> Option<&str> {
+        match self {
+            DatabaseError::DuplicatePrimaryKey { .. } => Some("PRIMARY KEY"),
+            DatabaseError::DuplicateUniqueValue { index, .. } => index.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Names the index of a [`DatabaseError::DuplicateUniqueValue`], raised by the storage which
+    /// only knows its id.
+    pub(crate) fn with_index(self, name: &str) -> Self {
+        match self {
+            DatabaseError::DuplicateUniqueValue { table, index: None } => {
+                DatabaseError::DuplicateUniqueValue {
+                    table,
+                    index: Some(name.to_string()),
+                }
+            }
+            err => err,
+        }
+    }
 }
@@ -0,0 +1,99 @@
+use crate::execution::Executor;
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::planner::{Childrens, LogicalPlan};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A physical operator of the embedder, registered with `DataBaseBuilder::register_executor`.
+///
+/// It runs the logical operators it matches in place of the built-in executors, e.g. to read a
+/// table from another service.
+#[typetag::serde(tag = "executor")]
+pub trait CustomExecutor: Debug + Send + Sync {
+    /// The name the plans show the executor by.
+    fn name(&self) -> &str;
+
+    /// Whether the operator of the optimized plan is run by this executor.
+    fn matches(&self, operator: &Operator) -> bool;
+
+    /// Streams the rows of `operator` with the values of its output schema in order,
+    /// `inputs` are the executors of the children of the operator.
+    ///
+    /// The limit pushed down to a scan, see `TableScanOperator::limit`, is left to the executor.
+    fn execute<'a>(&self, operator: Operator, inputs: Vec<Executor<'a>>) -> Executor<'a>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArcCustomExecutor(pub Arc<dyn CustomExecutor>);
+
+impl Deref for ArcCustomExecutor {
+    type Target = dyn CustomExecutor;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for ArcCustomExecutor {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for ArcCustomExecutor {}
+
+impl Hash for ArcCustomExecutor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+    }
+}
+
+/// Hands the operators of the plan to the first of `executors` matching them.
+pub(crate) fn plan_custom_executors(plan: &mut LogicalPlan, executors: &[Arc<dyn CustomExecutor>]) {
+    if executors.is_empty() {
+        return;
+    }
+    match plan.childrens.as_mut() {
+        Childrens::Only(child) => plan_custom_executors(child, executors),
+        Childrens::Twins { left, right } => {
+            plan_custom_executors(left, executors);
+            plan_custom_executors(right, executors);
+        }
+        Childrens::None => (),
+    }
+    if let Some(executor) = executors
+        .iter()
+        .find(|executor| executor.matches(&plan.operator))
+    {
+        plan.physical_option = Some(PhysicalOption::Custom(ArcCustomExecutor(executor.clone())));
+        return;
+    }
+    // the fused scans and the aggregates answered by the indexes read the table themselves,
+    // above a custom executor they run on its rows instead
+    if !reads_custom(plan) {
+        return;
+    }
+    match plan.physical_option {
+        Some(PhysicalOption::FusedFilter) => plan.physical_option = Some(PhysicalOption::Filter),
+        Some(PhysicalOption::FusedProject) => plan.physical_option = Some(PhysicalOption::Project),
+        Some(PhysicalOption::CountStar | PhysicalOption::MinMaxIndex) => {
+            plan.physical_option = Some(PhysicalOption::SimpleAggregate)
+        }
+        _ => (),
+    }
+}
+
+/// Whether the plan reads the rows of a custom executor, through filters and projections.
+fn reads_custom(plan: &LogicalPlan) -> bool {
+    match plan.childrens.as_ref() {
+        Childrens::Only(child) => match (&child.operator, &child.physical_option) {
+            (_, Some(PhysicalOption::Custom(_))) => true,
+            (Operator::Filter(_) | Operator::Project(_), _) => reads_custom(child),
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -1,6 +1,7 @@
 use crate::errors::DatabaseError;
 use crate::execution::{build_read, Executor, WriteExecutor};
 use crate::planner::LogicalPlan;
+use crate::storage::tuple_codec::TupleCodecEnum;
 use crate::storage::{StatisticsMetaCache, TableCache, ViewCache};
 use crate::types::index::{Index, IndexType};
 use crate::types::tuple::Tuple;
@@ -15,83 +16,83 @@ use std::pin::Pin;
 
 pub struct AddColumn {
     op: AddColumnOperator,
-    input: LogicalPlan,
+    /// The scan of the rows to rewrite, `None` when only the catalog changes
+    input: Option<LogicalPlan>,
 }
 
-impl From<(AddColumnOperator, LogicalPlan)> for AddColumn {
-    fn from((op, input): (AddColumnOperator, LogicalPlan)) -> Self {
+impl From<(AddColumnOperator, Option<LogicalPlan>)> for AddColumn {
+    fn from((op, input): (AddColumnOperator, Option<LogicalPlan>)) -> Self {
         Self { op, input }
     }
 }
 
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddColumn {
     fn execute_mut(
-        mut self,
+        self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
+                let AddColumn { op, input } = self;
                 let AddColumnOperator {
                     table_name,
                     column,
                     if_not_exists,
-                } = &self.op;
+                } = &op;
 
                 let mut unique_values = column.desc().is_unique().then(Vec::new);
                 let mut tuples = Vec::new();
-                let schema = self.input.output_schema();
-                let mut types = Vec::with_capacity(schema.len() + 1);
 
-                for column_ref in schema.iter() {
-                    types.push(column_ref.datatype().clone());
-                }
-                types.push(column.datatype().clone());
-
-                let mut coroutine = build_read(self.input, cache, transaction);
+                if let Some(input) = input {
+                    let mut coroutine = build_read(input, cache, transaction);
 
-                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                    let mut tuple: Tuple = throw!(tuple);
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        let mut tuple: Tuple = throw!(tuple);
 
-                    if let Some(value) = throw!(column.default_value()) {
-                        if let Some(unique_values) = &mut unique_values {
-                            unique_values.push((
-                                throw!(tuple.pk.clone().ok_or(DatabaseError::PrimaryKeyNotFound)),
-                                value.clone(),
-                            ));
+                        if let Some(value) = throw!(column.default_value()) {
+                            if let Some(unique_values) = &mut unique_values {
+                                unique_values.push((
+                                    throw!(tuple
+                                        .pk
+                                        .clone()
+                                        .ok_or(DatabaseError::PrimaryKeyNotFound)),
+                                    value.clone(),
+                                ));
+                            }
+                            tuple.values.push(value);
+                        } else {
+                            tuple.values.push(DataValue::Null);
                         }
-                        tuple.values.push(value);
-                    } else {
-                        tuple.values.push(DataValue::Null);
+                        tuples.push(tuple);
                     }
-                    tuples.push(tuple);
+                    drop(coroutine);
                 }
-                drop(coroutine);
+                let col_id =
+                    throw!(transaction.add_column(cache.0, table_name, column, *if_not_exists));
+                let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                    .cloned()
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
+
+                // the rows are rewritten with the new column, by the codec of the altered table
+                if !tuples.is_empty() {
+                    let tuple_codec = throw!(TupleCodecEnum::instance(&table));
 
-                for tuple in tuples {
-                    throw!(unsafe { &mut (*transaction) }
-                        .append_tuple(table_name, tuple, &types, true));
+                    for tuple in tuples {
+                        throw!(transaction.append_tuple(table_name, tuple, &tuple_codec, true));
+                    }
                 }
-                let col_id = throw!(unsafe { &mut (*transaction) }.add_column(
-                    cache.0,
-                    table_name,
-                    column,
-                    *if_not_exists
-                ));
 
                 // Unique Index
-                if let (Some(unique_values), Some(unique_meta)) = (
-                    unique_values,
-                    throw!(unsafe { &mut (*transaction) }.table(cache.0, table_name.clone()))
-                        .and_then(|table| table.get_unique_index(&col_id))
-                        .cloned(),
-                ) {
+                if let (Some(unique_values), Some(unique_meta)) =
+                    (unique_values, table.get_unique_index(&col_id).cloned())
+                {
                     for (tuple_id, value) in unique_values {
                         let index = Index::new(unique_meta.id, &value, IndexType::Unique);
-                        throw!(
-                            unsafe { &mut (*transaction) }.add_index(table_name, index, &tuple_id)
-                        );
+                        throw!(transaction
+                            .add_index(table_name, index, &tuple_id)
+                            .map_err(|err| err.with_index(&unique_meta.name)));
                     }
                 }
 
@@ -1,3 +1,4 @@
+use crate::catalog::TableName;
 use crate::execution::dql::projection::Projection;
 use crate::execution::DatabaseError;
 use crate::execution::{build_read, Executor, WriteExecutor};
@@ -6,15 +7,24 @@ use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
-use crate::types::index::Index;
-use crate::types::tuple::Tuple;
-use crate::types::tuple_builder::TupleBuilder;
-use crate::types::value::DataValue;
+use crate::types::index::{Index, IndexId, IndexType};
+use crate::types::tuple::{Tuple, TupleId};
+use crate::types::value::{DataValue, Utf8Type};
 use crate::types::ColumnId;
+use crate::utils::vector;
+use itertools::Itertools;
+use sqlparser::ast::CharLengthUnits;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
 
+const DEFAULT_BATCH_SIZE: usize = 4096;
+
+/// Builds the index in batches of [`DEFAULT_BATCH_SIZE`] tuples and reports the rows indexed after each batch,
+/// the build can be cancelled between batches. The table stays readable meanwhile, as other transactions keep
+/// reading the last committed tuples without the index.
+///
+/// The vectors of an IVF index are all read before the first batch, to train the lists they are kept in.
 pub struct CreateIndex {
     op: CreateIndexOperator,
     input: LogicalPlan,
@@ -30,7 +40,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
     fn execute_mut(
         mut self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -52,10 +62,10 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                     })
                     .unzip();
                 let schema = self.input.output_schema().clone();
-                let index_id = match unsafe { &mut (*transaction) }.add_index_meta(
+                let index_id = match transaction.add_index_meta(
                     cache.0,
                     &table_name,
-                    index_name,
+                    index_name.clone(),
                     column_ids,
                     ty,
                 ) {
@@ -69,7 +79,16 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                     }
                     err => throw!(err),
                 };
+                let _eviction = TableCacheEviction {
+                    table_cache: cache.0,
+                    table_name: table_name.clone(),
+                };
                 let mut coroutine = build_read(self.input, cache, transaction);
+                let mut batch = Vec::with_capacity(DEFAULT_BATCH_SIZE);
+                let mut rows = 0;
+                // the lists of an IVF index are trained over all the vectors before any is indexed
+                let is_vector = matches!(ty, IndexType::Vector);
+                let mut vectors = Vec::new();
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let tuple: Tuple = throw!(tuple);
@@ -81,20 +100,178 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                     ))) else {
                         continue;
                     };
-                    let tuple_id = if let Some(tuple_id) = tuple.pk.as_ref() {
-                        tuple_id
-                    } else {
+                    let Some(tuple_id) = tuple.pk else {
                         continue;
                     };
-                    let index = Index::new(index_id, &value, ty);
-                    throw!(unsafe { &mut (*transaction) }.add_index(
-                        table_name.as_str(),
-                        index,
-                        tuple_id
-                    ));
+                    if is_vector {
+                        vectors.push((value, tuple_id));
+                        continue;
+                    }
+                    batch.push((value, tuple_id));
+
+                    if batch.len() == DEFAULT_BATCH_SIZE {
+                        rows += batch.len();
+                        throw!(Self::flush(
+                            transaction,
+                            &table_name,
+                            &index_name,
+                            index_id,
+                            ty,
+                            &batch
+                        ));
+                        batch.clear();
+                        yield Ok(Self::result_tuple(rows, false));
+                    }
+                }
+                drop(coroutine);
+                if is_vector {
+                    let centroids = vector::train(
+                        &vectors
+                            .iter()
+                            .filter_map(|(value, _)| match value {
+                                DataValue::Vector(components) => Some(components.as_slice()),
+                                _ => None,
+                            })
+                            .collect_vec(),
+                        vector::lists(vectors.len()),
+                    );
+                    throw!(transaction.save_vector_centroids(&table_name, index_id, &centroids));
+
+                    while rows < vectors.len() {
+                        let end = vectors.len().min(rows + DEFAULT_BATCH_SIZE);
+                        throw!(Self::flush(
+                            transaction,
+                            &table_name,
+                            &index_name,
+                            index_id,
+                            ty,
+                            &vectors[rows..end]
+                        ));
+                        rows = end;
+                        if rows < vectors.len() {
+                            yield Ok(Self::result_tuple(rows, false));
+                        }
+                    }
                 }
-                yield Ok(TupleBuilder::build_result("1".to_string()));
+                rows += batch.len();
+                throw!(Self::flush(
+                    transaction,
+                    &table_name,
+                    &index_name,
+                    index_id,
+                    ty,
+                    &batch
+                ));
+
+                yield Ok(Self::result_tuple(rows, true));
             },
         )
     }
 }
+
+/// The build reads the table through the transaction, which caches the uncommitted index meta,
+/// so it is evicted once the build ends, fails or is cancelled.
+struct TableCacheEviction<'a> {
+    table_cache: &'a TableCache,
+    table_name: TableName,
+}
+
+impl Drop for TableCacheEviction<'_> {
+    fn drop(&mut self) {
+        self.table_cache.remove(&self.table_name);
+    }
+}
+
+impl CreateIndex {
+    fn flush<T: Transaction>(
+        transaction: &T,
+        table_name: &str,
+        index_name: &str,
+        index_id: IndexId,
+        ty: IndexType,
+        batch: &[(DataValue, TupleId)],
+    ) -> Result<(), DatabaseError> {
+        for (value, tuple_id) in batch {
+            transaction
+                .add_index(table_name, Index::new(index_id, value, ty), tuple_id)
+                .map_err(|err| err.with_index(index_name))?;
+        }
+        Ok(())
+    }
+
+    fn result_tuple(rows: usize, is_done: bool) -> Tuple {
+        let success = if is_done {
+            DataValue::Utf8 {
+                value: "1".to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }
+        } else {
+            DataValue::Null
+        };
+
+        Tuple::new(None, vec![success, DataValue::UInt64(rows as u64)])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::execution::ddl::create_index::DEFAULT_BATCH_SIZE;
+    use crate::types::tuple::Tuple;
+    use crate::types::value::DataValue;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_batched_build_and_cancellation() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t_build (a int primary key, b int)")?
+            .done()?;
+        let values = (0..DEFAULT_BATCH_SIZE * 2 + 1)
+            .map(|i| format!("({i}, {i})"))
+            .join(", ");
+        kite_sql
+            .run(format!("insert into t_build values {values}"))?
+            .done()?;
+
+        let mut iter = kite_sql.run("create index b_index on t_build (b)")?;
+        let tuples = iter.by_ref().collect::<Result<Vec<Tuple>, _>>()?;
+        iter.done()?;
+
+        assert_eq!(tuples.len(), 3);
+        assert_eq!(
+            tuples[0].values,
+            vec![
+                DataValue::Null,
+                DataValue::UInt64(DEFAULT_BATCH_SIZE as u64)
+            ]
+        );
+        assert_eq!(
+            tuples[1].values[1],
+            DataValue::UInt64(DEFAULT_BATCH_SIZE as u64 * 2)
+        );
+        assert!(!tuples[2].values[0].is_null());
+        assert_eq!(
+            tuples[2].values[1],
+            DataValue::UInt64(DEFAULT_BATCH_SIZE as u64 * 2 + 1)
+        );
+
+        let mut iter = kite_sql.run("create unique index c_index on t_build (b)")?;
+        assert!(iter.next().unwrap().is_ok());
+
+        iter.cancellation_token().cancel();
+        assert!(matches!(iter.next(), Some(Err(DatabaseError::Cancelled))));
+        assert!(matches!(iter.done(), Err(DatabaseError::Cancelled)));
+
+        kite_sql
+            .run("create unique index c_index on t_build (b)")?
+            .done()?;
+
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateTable {
     fn execute_mut(
         self,
         (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -26,15 +26,29 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateTable {
                 let CreateTableOperator {
                     table_name,
                     columns,
+                    foreign,
+                    tuple_codec,
+                    audit_columns,
                     if_not_exists,
                 } = self.op;
 
-                let _ = throw!(unsafe { &mut (*transaction) }.create_table(
-                    table_cache,
-                    table_name.clone(),
-                    columns,
-                    if_not_exists
-                ));
+                let _ = throw!(match foreign {
+                    Some(foreign) => transaction.create_foreign_table(
+                        table_cache,
+                        table_name.clone(),
+                        columns,
+                        foreign,
+                        if_not_exists
+                    ),
+                    None => transaction.create_table(
+                        table_cache,
+                        table_name.clone(),
+                        columns,
+                        tuple_codec,
+                        audit_columns,
+                        if_not_exists
+                    ),
+                });
 
                 yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
             },
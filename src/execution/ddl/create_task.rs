@@ -0,0 +1,35 @@
+use crate::execution::{Executor, WriteExecutor};
+use crate::planner::operator::create_task::CreateTaskOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct CreateTask {
+    op: CreateTaskOperator,
+}
+
+impl From<CreateTaskOperator> for CreateTask {
+    fn from(op: CreateTaskOperator) -> Self {
+        CreateTask { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateTask {
+    fn execute_mut(
+        self,
+        _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let CreateTaskOperator { task } = self.op;
+
+                let result_tuple = TupleBuilder::build_result(task.name.clone());
+                throw!(transaction.create_task(task));
+
+                yield Ok(result_tuple);
+            },
+        )
+    }
+}
@@ -2,6 +2,7 @@ use crate::errors::DatabaseError;
 use crate::execution::{build_read, Executor, WriteExecutor};
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
 use crate::planner::LogicalPlan;
+use crate::storage::tuple_codec::PlainTupleCodec;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::tuple::Tuple;
@@ -12,31 +13,38 @@ use std::pin::Pin;
 
 pub struct DropColumn {
     op: DropColumnOperator,
-    input: LogicalPlan,
+    /// The scan of the rows of a plain table to rewrite, `None` when only the catalog changes
+    input: Option<LogicalPlan>,
 }
 
-impl From<(DropColumnOperator, LogicalPlan)> for DropColumn {
-    fn from((op, input): (DropColumnOperator, LogicalPlan)) -> Self {
+impl From<(DropColumnOperator, Option<LogicalPlan>)> for DropColumn {
+    fn from((op, input): (DropColumnOperator, Option<LogicalPlan>)) -> Self {
         Self { op, input }
     }
 }
 
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropColumn {
     fn execute_mut(
-        mut self,
+        self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let DropColumnOperator {
-                    table_name,
-                    column_name,
-                    if_exists,
-                } = self.op;
+                let DropColumn {
+                    op:
+                        DropColumnOperator {
+                            table_name,
+                            column_name,
+                            if_exists,
+                        },
+                    input,
+                } = self;
 
-                let tuple_columns = self.input.output_schema();
+                let tuple_columns = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                    .map(|table| table.schema_ref().clone())
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
                 if let Some((column_index, is_primary)) = tuple_columns
                     .iter()
                     .enumerate()
@@ -48,38 +56,38 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropColumn {
                             "drop of primary key column is not allowed.".to_owned(),
                         )));
                     }
-                    let mut tuples = Vec::new();
-                    let mut types = Vec::with_capacity(tuple_columns.len() - 1);
+                    if let Some(input) = input {
+                        let mut tuples = Vec::new();
+                        let mut types = Vec::with_capacity(tuple_columns.len() - 1);
 
-                    for (i, column_ref) in tuple_columns.iter().enumerate() {
-                        if i == column_index {
-                            continue;
+                        for (i, column_ref) in tuple_columns.iter().enumerate() {
+                            if i == column_index {
+                                continue;
+                            }
+                            types.push(column_ref.datatype().clone());
                         }
-                        types.push(column_ref.datatype().clone());
-                    }
-                    let mut coroutine = build_read(self.input, cache, transaction);
+                        let mut coroutine = build_read(input, cache, transaction);
 
-                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                        let mut tuple: Tuple = throw!(tuple);
-                        let _ = tuple.values.remove(column_index);
+                        while let CoroutineState::Yielded(tuple) =
+                            Pin::new(&mut coroutine).resume(())
+                        {
+                            let mut tuple: Tuple = throw!(tuple);
+                            let _ = tuple.values.remove(column_index);
 
-                        tuples.push(tuple);
-                    }
-                    drop(coroutine);
-                    for tuple in tuples {
-                        throw!(unsafe { &mut (*transaction) }.append_tuple(
-                            &table_name,
-                            tuple,
-                            &types,
-                            true
-                        ));
+                            tuples.push(tuple);
+                        }
+                        drop(coroutine);
+                        let tuple_codec = PlainTupleCodec::new(types);
+                        for tuple in tuples {
+                            throw!(transaction.append_tuple(
+                                &table_name,
+                                tuple,
+                                &tuple_codec,
+                                true
+                            ));
+                        }
                     }
-                    throw!(unsafe { &mut (*transaction) }.drop_column(
-                        cache.0,
-                        cache.2,
-                        &table_name,
-                        &column_name
-                    ));
+                    throw!(transaction.drop_column(cache.0, cache.2, &table_name, &column_name));
 
                     yield Ok(TupleBuilder::build_result("1".to_string()));
                 } else if if_exists {
@@ -18,7 +18,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropIndex {
     fn execute_mut(
         self,
         (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -29,12 +29,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropIndex {
                     if_exists,
                 } = self.op;
 
-                throw!(unsafe { &mut (*transaction) }.drop_index(
-                    table_cache,
-                    table_name,
-                    &index_name,
-                    if_exists
-                ));
+                throw!(transaction.drop_index(table_cache, table_name, &index_name, if_exists));
 
                 yield Ok(TupleBuilder::build_result(index_name.to_string()));
             },
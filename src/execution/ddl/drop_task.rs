@@ -0,0 +1,37 @@
+use crate::execution::{Executor, WriteExecutor};
+use crate::planner::operator::drop_task::DropTaskOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct DropTask {
+    op: DropTaskOperator,
+}
+
+impl From<DropTaskOperator> for DropTask {
+    fn from(op: DropTaskOperator) -> Self {
+        DropTask { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropTask {
+    fn execute_mut(
+        self,
+        _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let DropTaskOperator {
+                    task_name,
+                    if_exists,
+                } = self.op;
+
+                throw!(transaction.drop_task(&task_name, if_exists));
+
+                yield Ok(TupleBuilder::build_result(task_name));
+            },
+        )
+    }
+}
@@ -1,9 +1,13 @@
 pub mod add_column;
 pub(crate) mod create_index;
 pub(crate) mod create_table;
+pub(crate) mod create_task;
 pub(crate) mod create_view;
 pub(crate) mod drop_column;
 pub(crate) mod drop_index;
 pub(crate) mod drop_table;
+pub(crate) mod drop_task;
 pub(crate) mod drop_view;
+pub(crate) mod reindex;
 pub(crate) mod truncate;
+pub(crate) mod vacuum;
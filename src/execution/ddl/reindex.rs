@@ -0,0 +1,173 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::projection::Projection;
+use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::planner::operator::reindex::ReindexOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::index::Index;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use sqlparser::ast::CharLengthUnits;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+/// Clears the entries of the indexes and writes them again from the tuples of the table,
+/// all within the statement's transaction, so a failed rebuild leaves the old entries in place
+/// and running it again yields the same indexes.
+pub struct Reindex {
+    op: ReindexOperator,
+    input: LogicalPlan,
+}
+
+impl From<(ReindexOperator, LogicalPlan)> for Reindex {
+    fn from((op, input): (ReindexOperator, LogicalPlan)) -> Self {
+        Self { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Reindex {
+    fn execute_mut(
+        mut self,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let ReindexOperator {
+                    table_name,
+                    index_metas,
+                } = self.op;
+
+                let schema = self.input.output_schema().clone();
+                let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                    .cloned()
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
+                let mut indexes = Vec::with_capacity(index_metas.len());
+
+                for index_meta in index_metas {
+                    throw!(transaction.clear_index(table_name.as_str(), index_meta.id));
+                    let column_exprs = throw!(index_meta.column_exprs(&table));
+                    indexes.push((index_meta, column_exprs, 0_u64));
+                }
+                if !indexes.is_empty() {
+                    let mut coroutine = build_read(self.input, cache, transaction);
+
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        let tuple: Tuple = throw!(tuple);
+                        let Some(tuple_id) = tuple.pk.as_ref() else {
+                            continue;
+                        };
+
+                        for (index_meta, column_exprs, rows) in indexes.iter_mut() {
+                            let Some(value) = DataValue::values_to_tuple(throw!(
+                                Projection::projection(&tuple, column_exprs, &schema)
+                            )) else {
+                                continue;
+                            };
+                            throw!(transaction
+                                .add_index(
+                                    table_name.as_str(),
+                                    Index::new(index_meta.id, &value, index_meta.ty),
+                                    tuple_id
+                                )
+                                .map_err(|err| err.with_index(&index_meta.name)));
+                            *rows += 1;
+                        }
+                    }
+                }
+                for (index_meta, _, rows) in indexes {
+                    yield Ok(Tuple::new(
+                        None,
+                        vec![
+                            DataValue::Utf8 {
+                                value: index_meta.name.clone(),
+                                ty: Utf8Type::Variable(None),
+                                unit: CharLengthUnits::Characters,
+                            },
+                            DataValue::UInt64(rows),
+                        ],
+                    ));
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::storage::{Storage, Transaction};
+    use crate::types::tuple::Tuple;
+    use crate::types::value::DataValue;
+    use itertools::Itertools;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reindex_repairs_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t_reindex (a int primary key, b int, c int)")?
+            .done()?;
+        kite_sql
+            .run("create index b_index on t_reindex (b)")?
+            .done()?;
+        kite_sql
+            .run("create unique index c_index on t_reindex (c)")?
+            .done()?;
+        let values = (0..200)
+            .map(|i| format!("({i}, {}, {i})", i % 10))
+            .join(", ");
+        kite_sql
+            .run(format!("insert into t_reindex values {values}"))?
+            .done()?;
+        kite_sql.run("analyze table t_reindex")?.done()?;
+
+        let query = |sql: &str| -> Result<Vec<Tuple>, DatabaseError> {
+            let mut iter = kite_sql.run(sql)?;
+            let tuples = iter.by_ref().collect::<Result<Vec<Tuple>, _>>()?;
+            iter.done()?;
+            Ok(tuples)
+        };
+
+        let transaction = kite_sql.storage.transaction()?;
+        let table = transaction
+            .table(
+                kite_sql.state.table_cache(),
+                Arc::new("t_reindex".to_string()),
+            )?
+            .cloned()
+            .unwrap();
+        for index_meta in table.indexes.iter() {
+            transaction.clear_index("t_reindex", index_meta.id)?;
+        }
+        transaction.commit()?;
+        assert!(query("select a from t_reindex where b = 1")?.is_empty());
+
+        for _ in 0..2 {
+            let tuples = query("reindex index t_reindex.b_index")?;
+            assert_eq!(tuples.len(), 1);
+            assert_eq!(tuples[0].values[1], DataValue::UInt64(200));
+        }
+        assert_eq!(query("select a from t_reindex where b = 1")?.len(), 20);
+        assert!(query("select a from t_reindex where c = 2")?.is_empty());
+
+        let tuples = query("reindex table t_reindex")?;
+        assert_eq!(tuples.len(), 2);
+        assert_eq!(query("select a from t_reindex where c = 2")?.len(), 1);
+
+        assert!(matches!(
+            query("reindex index t_reindex.not_exists"),
+            Err(DatabaseError::InvalidIndex)
+        ));
+        assert!(query("reindex index b_index").is_err());
+
+        Ok(())
+    }
+}
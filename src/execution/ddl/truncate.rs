@@ -18,14 +18,16 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Truncate {
     fn execute_mut(
         self,
         _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
                 let TruncateOperator { table_name } = self.op;
 
-                throw!(unsafe { &mut (*transaction) }.drop_data(&table_name));
+                throw!(transaction.drop_data(&table_name));
+                throw!(transaction.reset_tuple_count(&table_name));
+                throw!(transaction.bump_statistics_version(&table_name));
 
                 yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
             },
@@ -0,0 +1,66 @@
+use crate::errors::DatabaseError;
+use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::planner::operator::vacuum::VacuumOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::tuple_codec::TupleCodecEnum;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+/// Reads the rows with the schema versions they were written with, then leaves only the current
+/// version and writes them again with it, the values of the dropped columns go with the old rows.
+pub struct Vacuum {
+    op: VacuumOperator,
+    /// The scan of the rows to rewrite, `None` when the table has a single schema version
+    input: Option<LogicalPlan>,
+}
+
+impl From<(VacuumOperator, Option<LogicalPlan>)> for Vacuum {
+    fn from((op, input): (VacuumOperator, Option<LogicalPlan>)) -> Self {
+        Self { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Vacuum {
+    fn execute_mut(
+        self,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let Vacuum {
+                    op: VacuumOperator { table_name },
+                    input,
+                } = self;
+                let mut tuples = Vec::new();
+
+                if let Some(input) = input {
+                    let mut coroutine = build_read(input, cache, transaction);
+
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        tuples.push(throw!(tuple));
+                    }
+                }
+                throw!(transaction.reset_schema_versions(cache.0, &table_name));
+
+                let rows = tuples.len();
+                if !tuples.is_empty() {
+                    let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                        .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
+                    let tuple_codec = throw!(TupleCodecEnum::instance(table));
+
+                    for tuple in tuples {
+                        throw!(transaction.append_tuple(&table_name, tuple, &tuple_codec, true));
+                    }
+                }
+                yield Ok(Tuple::new(None, vec![DataValue::UInt64(rows as u64)]));
+            },
+        )
+    }
+}
@@ -2,16 +2,20 @@ use crate::catalog::TableName;
 use crate::errors::DatabaseError;
 use crate::execution::dql::projection::Projection;
 use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::expression::ScalarExpression;
 use crate::optimizer::core::histogram::HistogramBuilder;
 use crate::optimizer::core::statistics_meta::StatisticsMeta;
 use crate::planner::operator::analyze::AnalyzeOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
-use crate::types::index::IndexMetaRef;
-use crate::types::tuple::Tuple;
+use crate::types::index::{IndexId, IndexMetaRef, IndexType};
+use crate::types::tuple::{Schema, Tuple};
 use crate::types::value::{DataValue, Utf8Type};
 use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use sqlparser::ast::CharLengthUnits;
 use std::collections::HashSet;
 use std::ffi::OsStr;
@@ -25,12 +29,15 @@ use std::sync::Arc;
 use std::{fmt, fs};
 
 const DEFAULT_NUM_OF_BUCKETS: usize = 100;
+const DEFAULT_PROGRESS_INTERVAL: usize = 1024;
 const DEFAULT_STATISTICS_META_PATH: &str = "kite_sql_statistics_metas";
 
 pub struct Analyze {
     table_name: TableName,
     input: LogicalPlan,
     index_metas: Vec<IndexMetaRef>,
+    is_incremental: bool,
+    sample_fraction: f64,
 }
 
 impl From<(AnalyzeOperator, LogicalPlan)> for Analyze {
@@ -39,6 +46,8 @@ impl From<(AnalyzeOperator, LogicalPlan)> for Analyze {
             AnalyzeOperator {
                 table_name,
                 index_metas,
+                is_incremental,
+                sample_fraction,
             },
             input,
         ): (AnalyzeOperator, LogicalPlan),
@@ -47,6 +56,8 @@ impl From<(AnalyzeOperator, LogicalPlan)> for Analyze {
             table_name,
             input,
             index_metas,
+            is_incremental,
+            sample_fraction: sample_fraction.0,
         }
     }
 }
@@ -55,7 +66,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
     fn execute_mut(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -63,50 +74,115 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
                 let Analyze {
                     table_name,
                     mut input,
-                    index_metas,
+                    mut index_metas,
+                    is_incremental,
+                    sample_fraction,
                 } = self;
 
                 let schema = input.output_schema().clone();
+                let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                    .cloned()
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
+                let modified_columns =
+                    throw!(transaction.statistics_modified_columns(table_name.as_str()));
+
+                if is_incremental {
+                    let mut stale_index_metas = Vec::with_capacity(index_metas.len());
+
+                    for index_meta in index_metas {
+                        let is_modified = index_meta
+                            .column_ids
+                            .iter()
+                            .any(|column_id| modified_columns.contains(column_id));
+
+                        if is_modified
+                            || throw!(
+                                transaction.table_meta_path(table_name.as_str(), index_meta.id)
+                            )
+                            .is_none()
+                        {
+                            stale_index_metas.push(index_meta);
+                        }
+                    }
+                    index_metas = stale_index_metas;
+                }
                 let mut builders = Vec::with_capacity(index_metas.len());
-                let table = throw!(throw!(
-                    unsafe { &mut (*transaction) }.table(cache.0, table_name.clone())
-                )
-                .cloned()
-                .ok_or(DatabaseError::TableNotFound));
 
-                for index in table.indexes() {
+                for index in index_metas.iter() {
+                    let column_names = index
+                        .column_ids
+                        .iter()
+                        .filter_map(|column_id| table.get_column_by_id(column_id))
+                        .map(|column| column.name())
+                        .join(", ");
                     builders.push((
                         index.id,
+                        index.ty,
+                        column_names,
                         throw!(index.column_exprs(&table)),
                         HistogramBuilder::new(index, None),
                     ));
                 }
+                let scan_columns = table
+                    .columns()
+                    .filter(|column| {
+                        index_metas.iter().any(|index_meta| {
+                            column
+                                .id()
+                                .is_some_and(|column_id| index_meta.column_ids.contains(&column_id))
+                        })
+                    })
+                    .map(|column| column.name())
+                    .join(", ");
+                let mut rows = 0;
+
+                if !builders.is_empty() {
+                    let sample_size = if sample_fraction < 1.0 {
+                        let count = throw!(transaction.count_tuples(table_name.as_str()));
+                        Some(Self::sample_size(count, sample_fraction))
+                    } else {
+                        None
+                    };
+                    let mut reservoir = Vec::with_capacity(sample_size.unwrap_or(0));
+                    let mut rng = StdRng::from_entropy();
+                    let mut coroutine = build_read(input, cache, transaction);
+
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        let tuple = throw!(tuple);
+
+                        // Reservoir sampling: the i-th tuple replaces a sampled one with probability k / (i + 1)
+                        match sample_size {
+                            Some(k) if rows < k => reservoir.push((rows, tuple)),
+                            Some(k) => {
+                                let j = rng.gen_range(0..=rows);
+
+                                if j < k {
+                                    reservoir[j] = (rows, tuple);
+                                }
+                            }
+                            None => throw!(Self::append(&mut builders, &tuple, &schema)),
+                        }
+                        rows += 1;
 
-                let mut coroutine = build_read(input, cache, transaction);
-
-                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                    let tuple = throw!(tuple);
-
-                    for (_, exprs, builder) in builders.iter_mut() {
-                        let values = throw!(Projection::projection(&tuple, exprs, &schema));
-
-                        if values.len() == 1 {
-                            throw!(builder.append(&values[0]));
-                        } else {
-                            throw!(builder.append(&Arc::new(DataValue::Tuple(values, false))));
+                        if rows % DEFAULT_PROGRESS_INTERVAL == 0 {
+                            yield Ok(Self::progress_tuple(rows, Some(scan_columns.clone()), None));
                         }
                     }
+                    drop(coroutine);
+
+                    // keep the physical order of the samples for the correlation of the histograms
+                    reservoir.sort_by_key(|(i, _)| *i);
+                    for (_, tuple) in reservoir {
+                        throw!(Self::append(&mut builders, &tuple, &schema));
+                    }
                 }
-                drop(coroutine);
-                let mut values = Vec::with_capacity(builders.len());
+                let mut results = Vec::with_capacity(builders.len());
                 let dir_path = Self::build_statistics_meta_path(&table_name);
                 // For DEBUG
                 // println!("Statistics Path: {:#?}", dir_path);
                 throw!(fs::create_dir_all(&dir_path).map_err(DatabaseError::IO));
 
-                let mut active_index_paths = HashSet::new();
-
-                for (index_id, _, builder) in builders {
+                for (index_id, _, column_names, _, builder) in builders {
                     let index_file = OsStr::new(&index_id.to_string()).to_os_string();
                     let path = dir_path.join(&index_file);
                     let temp_path = path.with_extension("tmp");
@@ -116,23 +192,37 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
                     let meta = StatisticsMeta::new(histogram, sketch);
 
                     throw!(meta.to_file(&temp_path));
-                    values.push(DataValue::Utf8 {
-                        value: path_str.clone(),
-                        ty: Utf8Type::Variable(None),
-                        unit: CharLengthUnits::Characters,
-                    });
-                    throw!(unsafe { &mut (*transaction) }.save_table_meta(
-                        cache.2,
-                        &table_name,
-                        path_str,
-                        meta
+                    results.push(Self::progress_tuple(
+                        rows,
+                        Some(column_names),
+                        Some(path_str.clone()),
                     ));
+                    throw!(transaction.save_table_meta(cache.2, &table_name, path_str, meta));
                     throw!(fs::rename(&temp_path, &path).map_err(DatabaseError::IO));
+                }
 
-                    active_index_paths.insert(index_file);
+                // a column is up to date once every index covering it has been rebuilt
+                for column_id in modified_columns {
+                    let is_analyzed = table
+                        .indexes()
+                        .filter(|index_meta| index_meta.column_ids.contains(&column_id))
+                        .all(|index_meta| {
+                            index_metas
+                                .iter()
+                                .any(|analyzed| analyzed.id == index_meta.id)
+                        });
+                    if is_analyzed {
+                        throw!(
+                            transaction.clear_statistics_modified(table_name.as_str(), &column_id)
+                        );
+                    }
                 }
 
                 // clean expired index
+                let mut active_index_paths = table
+                    .indexes()
+                    .map(|index_meta| OsStr::new(&index_meta.id.to_string()).to_os_string())
+                    .collect::<HashSet<_>>();
                 for entry in throw!(fs::read_dir(dir_path).map_err(DatabaseError::IO)) {
                     let entry: DirEntry = throw!(entry.map_err(DatabaseError::IO));
 
@@ -141,13 +231,68 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
                     }
                 }
 
-                yield Ok(Tuple::new(None, values));
+                for result in results {
+                    yield Ok(result);
+                }
             },
         )
     }
 }
 
 impl Analyze {
+    fn append(
+        builders: &mut [(
+            IndexId,
+            IndexType,
+            String,
+            Vec<ScalarExpression>,
+            HistogramBuilder,
+        )],
+        tuple: &Tuple,
+        schema: &Schema,
+    ) -> Result<(), DatabaseError> {
+        for (_, ty, _, exprs, builder) in builders.iter_mut() {
+            let values = Projection::projection(tuple, exprs, schema)?;
+
+            if let Some(entries) = ty.index_entries(&values[0]) {
+                // as the index, the statistics count the entries of the value
+                for entry in entries {
+                    builder.append(&entry)?;
+                }
+            } else if values.len() == 1 {
+                builder.append(&values[0])?;
+            } else {
+                builder.append(&Arc::new(DataValue::Tuple(values, false)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rows processed so far, the columns being collected and the statistics file once written.
+    fn progress_tuple(rows: usize, columns: Option<String>, path: Option<String>) -> Tuple {
+        let utf8 = |value| DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        Tuple::new(
+            None,
+            vec![
+                DataValue::UInt64(rows as u64),
+                columns.map(utf8).unwrap_or(DataValue::Null),
+                path.map(utf8).unwrap_or(DataValue::Null),
+            ],
+        )
+    }
+
+    /// Samples at least as many rows as there are buckets, so that a small fraction still builds a histogram.
+    fn sample_size(count: usize, sample_fraction: f64) -> usize {
+        ((count as f64 * sample_fraction).ceil() as usize)
+            .max(DEFAULT_NUM_OF_BUCKETS)
+            .min(count)
+    }
+
     pub fn build_statistics_meta_path(table_name: &TableName) -> PathBuf {
         dirs::home_dir()
             .expect("Your system does not have a Config directory!")
@@ -161,6 +306,12 @@ impl fmt::Display for AnalyzeOperator {
         let indexes = self.index_metas.iter().map(|index| &index.name).join(", ");
 
         write!(f, "Analyze {} -> [{}]", self.table_name, indexes)?;
+        if self.is_incremental {
+            write!(f, ", Incremental")?;
+        }
+        if self.sample_fraction < OrderedFloat(1.0) {
+            write!(f, ", Sample Fraction: {}", self.sample_fraction)?;
+        }
 
         Ok(())
     }
@@ -170,9 +321,16 @@ impl fmt::Display for AnalyzeOperator {
 mod test {
     use crate::db::{DataBaseBuilder, ResultIter};
     use crate::errors::DatabaseError;
-    use crate::execution::dml::analyze::{DEFAULT_NUM_OF_BUCKETS, DEFAULT_STATISTICS_META_PATH};
+    use crate::execution::dml::analyze::{
+        DEFAULT_NUM_OF_BUCKETS, DEFAULT_PROGRESS_INTERVAL, DEFAULT_STATISTICS_META_PATH,
+    };
     use crate::optimizer::core::statistics_meta::StatisticsMeta;
     use crate::storage::rocksdb::RocksTransaction;
+    use crate::storage::{Storage, Transaction};
+    use crate::types::tuple::Tuple;
+    use crate::types::value::{DataValue, Utf8Type};
+    use itertools::Itertools;
+    use sqlparser::ast::CharLengthUnits;
     use std::ffi::OsStr;
     use std::fs;
     use tempfile::TempDir;
@@ -181,6 +339,9 @@ mod test {
     fn test_analyze() -> Result<(), DatabaseError> {
         test_statistics_meta()?;
         test_clean_expired_index()?;
+        test_sampled_statistics()?;
+        test_incremental_statistics()?;
+        test_progress_and_cancellation()?;
 
         Ok(())
     }
@@ -276,4 +437,174 @@ mod test {
 
         Ok(())
     }
+
+    fn test_sampled_statistics() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .statistics_sample_fraction(0.2)
+            .build()?;
+
+        kite_sql
+            .run("create table t_sampled (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("create index b_index on t_sampled (b)")?
+            .done()?;
+        let values = (0..1000).map(|i| format!("({i}, {})", i % 20)).join(", ");
+        kite_sql
+            .run(format!("insert into t_sampled values {values}"))?
+            .done()?;
+        kite_sql.run("analyze table t_sampled")?.done()?;
+
+        let dir_path = dirs::home_dir()
+            .expect("Your system does not have a Config directory!")
+            .join(DEFAULT_STATISTICS_META_PATH)
+            .join("t_sampled");
+
+        for index_file in ["0", "1"] {
+            let statistics_meta =
+                StatisticsMeta::from_file::<RocksTransaction>(&dir_path.join(index_file))?;
+
+            assert_eq!(statistics_meta.histogram().values_len(), 200);
+        }
+        assert!(DataBaseBuilder::path(temp_dir.path())
+            .statistics_sample_fraction(0.0)
+            .build()
+            .is_err());
+
+        Ok(())
+    }
+
+    fn test_incremental_statistics() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let analyzed_files = |sql: &str| -> Result<Vec<String>, DatabaseError> {
+            let mut iter = kite_sql.run(sql)?;
+            let tuples = iter.by_ref().collect::<Result<Vec<Tuple>, _>>()?;
+            iter.done()?;
+
+            Ok(tuples
+                .iter()
+                .map(|tuple| {
+                    let DataValue::Utf8 { value, .. } = &tuple.values[2] else {
+                        unreachable!()
+                    };
+                    value.rsplit('/').next().unwrap().to_string()
+                })
+                .collect_vec())
+        };
+
+        kite_sql
+            .run("create table t_incremental (a int primary key, b int, c int)")?
+            .done()?;
+        kite_sql
+            .run("create index b_index on t_incremental (b)")?
+            .done()?;
+        let values = (0..DEFAULT_NUM_OF_BUCKETS + 1)
+            .map(|i| format!("({i}, {i}, {i})"))
+            .join(", ");
+        kite_sql
+            .run(format!("insert into t_incremental values {values}"))?
+            .done()?;
+
+        assert_eq!(
+            analyzed_files("analyze table t_incremental for columns")?,
+            vec!["0", "1"]
+        );
+        assert!(analyzed_files("analyze table t_incremental for columns")?.is_empty());
+
+        kite_sql.run("update t_incremental set c = c + 1")?.done()?;
+        assert!(analyzed_files("analyze table t_incremental for columns")?.is_empty());
+
+        kite_sql.run("update t_incremental set b = b + 1")?.done()?;
+        assert_eq!(
+            analyzed_files("analyze table t_incremental for columns")?,
+            vec!["1"]
+        );
+
+        kite_sql
+            .run("create index c_index on t_incremental (c)")?
+            .done()?;
+        assert_eq!(
+            analyzed_files("analyze table t_incremental for columns")?,
+            vec!["2"]
+        );
+        assert_eq!(
+            analyzed_files("analyze table t_incremental for columns a, c")?,
+            vec!["0", "2"]
+        );
+
+        let dir_path = dirs::home_dir()
+            .expect("Your system does not have a Config directory!")
+            .join(DEFAULT_STATISTICS_META_PATH)
+            .join("t_incremental");
+        let file_names = fs::read_dir(&dir_path)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(file_names.len(), 3);
+
+        Ok(())
+    }
+
+    fn test_progress_and_cancellation() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t_progress (a int primary key, b int, c int)")?
+            .done()?;
+        kite_sql
+            .run("create index b_index on t_progress (b)")?
+            .done()?;
+        let values = (0..DEFAULT_PROGRESS_INTERVAL * 2 + 1)
+            .map(|i| format!("({i}, {i}, {i})"))
+            .join(", ");
+        kite_sql
+            .run(format!("insert into t_progress values {values}"))?
+            .done()?;
+
+        let mut iter = kite_sql.run("analyze table t_progress")?;
+        let tuples = iter.by_ref().collect::<Result<Vec<Tuple>, _>>()?;
+        iter.done()?;
+
+        let rows = (DEFAULT_PROGRESS_INTERVAL * 2 + 1) as u64;
+        let utf8 = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+        assert_eq!(tuples.len(), 4);
+        assert_eq!(
+            tuples[0].values[..2],
+            [
+                DataValue::UInt64(DEFAULT_PROGRESS_INTERVAL as u64),
+                utf8("a, b")
+            ]
+        );
+        assert_eq!(tuples[0].values[2], DataValue::Null);
+        assert_eq!(
+            tuples[1].values[0],
+            DataValue::UInt64(DEFAULT_PROGRESS_INTERVAL as u64 * 2)
+        );
+        assert_eq!(tuples[2].values[..2], [DataValue::UInt64(rows), utf8("a")]);
+        assert_eq!(tuples[3].values[..2], [DataValue::UInt64(rows), utf8("b")]);
+        assert!(!tuples[3].values[2].is_null());
+
+        kite_sql
+            .run("create index c_index on t_progress (c)")?
+            .done()?;
+        let mut iter = kite_sql.run("analyze table t_progress for columns c")?;
+        assert!(iter.next().unwrap().is_ok());
+
+        iter.cancellation_token().cancel();
+        assert!(matches!(iter.next(), Some(Err(DatabaseError::Cancelled))));
+        assert!(iter.next().is_none());
+        assert!(matches!(iter.done(), Err(DatabaseError::Cancelled)));
+
+        let transaction = kite_sql.storage.transaction()?;
+        assert!(transaction.table_meta_path("t_progress", 1)?.is_some());
+        assert!(transaction.table_meta_path("t_progress", 2)?.is_none());
+
+        Ok(())
+    }
 }
@@ -1,64 +1,196 @@
-use crate::binder::copy::FileFormat;
-use crate::catalog::PrimaryKeyIndices;
+use crate::binder::copy::{ExtSource, FileFormat};
+use crate::catalog::{PrimaryKeyIndices, TableName};
 use crate::errors::DatabaseError;
+use crate::execution::ddl::reindex::Reindex;
+use crate::execution::dql::projection::Projection;
 use crate::execution::{Executor, WriteExecutor};
+use crate::expression::ScalarExpression;
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
-use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::planner::operator::reindex::ReindexOperator;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::storage::table_codec::TableCodec;
+use crate::storage::tuple_codec::TupleCodecEnum;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache, WriteBatch};
 use crate::throw;
-use crate::types::tuple::{types, Tuple};
+use crate::types::index::{Index, IndexType};
+use crate::types::tuple::{SchemaRef, Tuple, TupleId};
 use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::DataValue;
+use csv::StringRecord;
+use itertools::Itertools;
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::BufReader;
-use std::sync::mpsc;
-use std::sync::mpsc::Sender;
+use std::mem;
+use std::ops::{Coroutine, CoroutineState};
+use std::pin::Pin;
+use std::sync::mpsc::{Receiver, Sender, SyncSender};
+use std::sync::{mpsc, Arc};
 use std::thread;
 
+/// Records read from the file at once and handed over to a thread to cast and encode.
+const CHUNK_ROWS: usize = 1024;
+
+/// Loads a file into a table: a thread reads the records in chunks, the threads of
+/// [`CopyLoadOptions::threads`](crate::binder::copy::CopyLoadOptions) cast and encode them, and
+/// the statement writes the encoded chunks in the order of the file with the entries of the indexes,
+/// or rebuilds the indexes once all of them are written when they are deferred.
 pub struct CopyFromFile {
     op: CopyFromFileOperator,
-    size: usize,
 }
 
 impl From<CopyFromFileOperator> for CopyFromFile {
     fn from(op: CopyFromFileOperator) -> Self {
-        CopyFromFile { op, size: 0 }
+        CopyFromFile { op }
     }
 }
 
+/// The tuples of a chunk and, when the indexes are not deferred, the values of the indexes of
+/// each of them.
+struct EncodedChunk {
+    batch: WriteBatch,
+    index_values: Vec<(TupleId, Vec<Option<DataValue>>)>,
+}
+
+/// What the threads share to cast and encode the chunks.
+struct ChunkEncoder {
+    table_name: TableName,
+    schema_ref: SchemaRef,
+    pk_indices: PrimaryKeyIndices,
+    tuple_codec: TupleCodecEnum,
+    /// The columns of each index written along with the tuples
+    index_exprs: Vec<Vec<ScalarExpression>>,
+    /// The field read as a NULL
+    null: Option<String>,
+}
+
+type Chunk<T> = (usize, T);
+
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CopyFromFile {
     fn execute_mut(
         self,
-        (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let types = types(&self.op.schema_ref);
-                let (tx, rx) = mpsc::channel();
-                let (tx1, rx1) = mpsc::channel();
+                let CopyFromFileOperator {
+                    table: table_name,
+                    source,
+                    schema_ref,
+                    load,
+                } = self.op;
+                throw!(transaction.throttle_write(&table_name));
+                let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                    .cloned()
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
+                let index_metas = table
+                    .indexes()
+                    .filter(|index_meta| !matches!(index_meta.ty, IndexType::PrimaryKey { .. }))
+                    .cloned()
+                    .collect_vec();
+                let mut index_exprs = Vec::new();
+                if !load.defer_indexes {
+                    for index_meta in index_metas.iter() {
+                        index_exprs.push(throw!(index_meta.column_exprs(&table)));
+                    }
+                }
+                let encoder = Arc::new(ChunkEncoder {
+                    table_name: table_name.clone(),
+                    schema_ref: schema_ref.clone(),
+                    pk_indices: table.primary_keys_indices().clone(),
+                    tuple_codec: throw!(TupleCodecEnum::instance(&table)),
+                    index_exprs,
+                    null: match &source.format {
+                        FileFormat::Csv { null, .. } => null.clone(),
+                        FileFormat::Parquet { .. } => None,
+                    },
+                });
+
                 // # Cancellation
-                // When this stream is dropped, the `rx` is dropped, the spawned task will fail to send to
-                // `tx`, then the task will finish.
-                let table = throw!(throw!(
-                    unsafe { &mut (*transaction) }.table(table_cache, self.op.table.clone())
-                )
-                .ok_or(DatabaseError::TableNotFound));
-                let primary_keys_indices = table.primary_keys_indices().clone();
-                let handle = thread::spawn(|| self.read_file_blocking(tx, primary_keys_indices));
-                let mut size = 0_usize;
-                while let Ok(chunk) = rx.recv() {
-                    throw!(unsafe { &mut (*transaction) }.append_tuple(
-                        table.name(),
-                        chunk,
-                        &types,
-                        false
-                    ));
-                    size += 1;
+                // When this stream is dropped, the `encoded_rx` is dropped, the threads fail to send
+                // the chunks they encoded and finish, then the reader fails to hand over its chunks.
+                let (chunk_tx, chunk_rx) = mpsc::sync_channel(load.threads * 2);
+                let (encoded_tx, encoded_rx) = mpsc::sync_channel(load.threads * 2);
+                let chunk_rx = Arc::new(Mutex::new(chunk_rx));
+                let column_count = schema_ref.len();
+                let reader =
+                    thread::spawn(move || Self::read_file_blocking(source, column_count, chunk_tx));
+                let encoders = (0..load.threads)
+                    .map(|_| {
+                        let (encoder, chunk_rx, encoded_tx) =
+                            (encoder.clone(), chunk_rx.clone(), encoded_tx.clone());
+                        thread::spawn(move || encoder.encode_blocking(&chunk_rx, encoded_tx))
+                    })
+                    .collect_vec();
+                drop(encoded_tx);
+
+                // the chunks are encoded out of order, and written in order
+                let mut encoded = BTreeMap::new();
+                let (mut next, mut size) = (0, 0_usize);
+                while let Ok((seq, chunk)) = encoded_rx.recv() {
+                    encoded.insert(seq, chunk);
+
+                    while let Some(chunk) = encoded.remove(&next) {
+                        let EncodedChunk {
+                            batch,
+                            index_values,
+                        } = throw!(chunk);
+                        size += batch.len();
+                        throw!(transaction.write_batch(batch, false));
+
+                        for (tuple_id, values) in index_values {
+                            for (index_meta, value) in index_metas.iter().zip(values) {
+                                let Some(value) = value else {
+                                    continue;
+                                };
+                                throw!(transaction
+                                    .add_index(
+                                        &table_name,
+                                        Index::new(index_meta.id, &value, index_meta.ty),
+                                        &tuple_id
+                                    )
+                                    .map_err(|err| err.with_index(&index_meta.name)));
+                            }
+                        }
+                        next += 1;
+                    }
+                }
+                throw!(reader.join().unwrap());
+                for encoder in encoders {
+                    encoder.join().unwrap();
                 }
-                throw!(handle.join().unwrap());
 
-                let handle = thread::spawn(move || return_result(size, tx1));
-                while let Ok(chunk) = rx1.recv() {
+                if size > 0 {
+                    if load.defer_indexes && !index_metas.is_empty() {
+                        let scan = TableScanOperator::build(table_name.clone(), &table, true);
+                        let mut coroutine = Reindex::from((
+                            ReindexOperator {
+                                table_name: table_name.clone(),
+                                index_metas,
+                            },
+                            scan,
+                        ))
+                        .execute_mut(cache, transaction);
+
+                        while let CoroutineState::Yielded(tuple) =
+                            Pin::new(&mut coroutine).resume(())
+                        {
+                            throw!(tuple);
+                        }
+                    }
+                    let column_ids = table
+                        .columns()
+                        .filter_map(|column| column.id())
+                        .collect_vec();
+                    throw!(transaction.mark_statistics_modified(&table_name, &column_ids));
+                }
+
+                let (tx, rx) = mpsc::channel();
+                let handle = thread::spawn(move || return_result(size, tx));
+                while let Ok(chunk) = rx.recv() {
                     yield Ok(chunk);
                 }
                 throw!(handle.join().unwrap())
@@ -70,33 +202,41 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CopyFromFile {
 impl CopyFromFile {
     /// Read records from file using blocking IO.
     ///
-    /// The read data chunks will be sent through `tx`.
+    /// The records are sent through `chunk_tx` in chunks numbered in the order of the file.
     fn read_file_blocking(
-        mut self,
-        tx: Sender<Tuple>,
-        pk_indices: PrimaryKeyIndices,
+        source: ExtSource,
+        column_count: usize,
+        chunk_tx: SyncSender<Chunk<Vec<StringRecord>>>,
     ) -> Result<(), DatabaseError> {
-        let file = File::open(self.op.source.path)?;
+        let file = File::open(source.path)?;
         let mut buf_reader = BufReader::new(file);
-        let mut reader = match self.op.source.format {
+        let mut reader = match source.format {
             FileFormat::Csv {
                 delimiter,
                 quote,
                 escape,
                 header,
+                ..
             } => csv::ReaderBuilder::new()
                 .delimiter(delimiter as u8)
                 .quote(quote as u8)
                 .escape(escape.map(|c| c as u8))
                 .has_headers(header)
                 .from_reader(&mut buf_reader),
+            FileFormat::Parquet { .. } => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "COPY FROM of a file other than CSV".to_string(),
+                ))
+            }
         };
-
-        let column_count = self.op.schema_ref.len();
-        let tuple_builder = TupleBuilder::new(&self.op.schema_ref, Some(&pk_indices));
+        let send = |seq: usize, records: Vec<StringRecord>| {
+            chunk_tx
+                .send((seq, records))
+                .map_err(|_| DatabaseError::ChannelClose)
+        };
+        let (mut seq, mut records) = (0, Vec::with_capacity(CHUNK_ROWS));
 
         for record in reader.records() {
-            // read records and push raw str rows into data chunk builder
             let record = record?;
 
             if !(record.len() == column_count
@@ -104,15 +244,80 @@ impl CopyFromFile {
             {
                 return Err(DatabaseError::MisMatch("columns", "values"));
             }
+            records.push(record);
 
-            self.size += 1;
-            tx.send(tuple_builder.build_with_row(record.iter())?)
-                .map_err(|_| DatabaseError::ChannelClose)?;
+            if records.len() == CHUNK_ROWS {
+                send(
+                    seq,
+                    mem::replace(&mut records, Vec::with_capacity(CHUNK_ROWS)),
+                )?;
+                seq += 1;
+            }
+        }
+        if !records.is_empty() {
+            send(seq, records)?;
         }
         Ok(())
     }
 }
 
+impl ChunkEncoder {
+    /// Encodes the chunks handed over by the reader until it has read the whole file.
+    fn encode_blocking(
+        &self,
+        chunk_rx: &Mutex<Receiver<Chunk<Vec<StringRecord>>>>,
+        encoded_tx: SyncSender<Chunk<Result<EncodedChunk, DatabaseError>>>,
+    ) {
+        loop {
+            let Ok((seq, records)) = chunk_rx.lock().recv() else {
+                return;
+            };
+            if encoded_tx.send((seq, self.encode(records))).is_err() {
+                return;
+            }
+        }
+    }
+
+    fn encode(&self, records: Vec<StringRecord>) -> Result<EncodedChunk, DatabaseError> {
+        let table_codec = TableCodec::default();
+        let tuple_builder = TupleBuilder::new(&self.schema_ref, Some(&self.pk_indices));
+        let mut chunk = EncodedChunk {
+            batch: WriteBatch::default(),
+            index_values: Vec::new(),
+        };
+
+        for record in records.iter() {
+            let mut tuple = tuple_builder.build_with_row(
+                record.iter().take(self.schema_ref.len()),
+                self.null.as_deref(),
+            )?;
+
+            if !self.index_exprs.is_empty() {
+                let tuple_id = tuple.pk.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
+                let values = self
+                    .index_exprs
+                    .iter()
+                    .map(|exprs| {
+                        Ok(DataValue::values_to_tuple(Projection::projection(
+                            &tuple,
+                            exprs,
+                            &self.schema_ref,
+                        )?))
+                    })
+                    .try_collect::<_, Vec<_>, DatabaseError>()?;
+                chunk.index_values.push((tuple_id, values));
+            }
+            chunk.batch.push_tuple(
+                &table_codec,
+                &self.table_name,
+                &mut tuple,
+                &self.tuple_codec,
+            )?;
+        }
+        Ok(chunk)
+    }
+}
+
 fn return_result(size: usize, tx: Sender<Tuple>) -> Result<(), DatabaseError> {
     let tuple = TupleBuilder::build_result(format!("import {} rows", size));
 
@@ -123,7 +328,7 @@ fn return_result(size: usize, tx: Sender<Tuple>) -> Result<(), DatabaseError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::binder::copy::ExtSource;
+    use crate::binder::copy::{CopyLoadOptions, ExtSource};
     use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef, ColumnRelation, ColumnSummary};
     use crate::db::{DataBaseBuilder, ResultIter};
     use crate::errors::DatabaseError;
@@ -147,7 +352,7 @@ mod tests {
         let columns = vec![
             ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "a".to_string(),
+                    name: "a".into(),
                     relation: ColumnRelation::Table {
                         column_id: Ulid::new(),
                         table_name: Arc::new("t1".to_string()),
@@ -160,7 +365,7 @@ mod tests {
             )),
             ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "b".to_string(),
+                    name: "b".into(),
                     relation: ColumnRelation::Table {
                         column_id: Ulid::new(),
                         table_name: Arc::new("t1".to_string()),
@@ -173,7 +378,7 @@ mod tests {
             )),
             ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c".to_string(),
+                    name: "c".into(),
                     relation: ColumnRelation::Table {
                         column_id: Ulid::new(),
                         table_name: Arc::new("t1".to_string()),
@@ -200,21 +405,20 @@ mod tests {
                     quote: '"',
                     escape: None,
                     header: false,
+                    null: None,
                 },
             },
             schema_ref: Arc::new(columns),
+            load: CopyLoadOptions::default(),
         };
-        let executor = CopyFromFile {
-            op: op.clone(),
-            size: 0,
-        };
+        let executor = CopyFromFile::from(op.clone());
 
         let temp_dir = TempDir::new().unwrap();
         let db = DataBaseBuilder::path(temp_dir.path()).build()?;
         db.run("create table test_copy (a int primary key, b float, c varchar(10))")?
             .done()?;
         let storage = db.storage;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
 
         let mut coroutine = executor.execute_mut(
             (
@@ -222,7 +426,7 @@ mod tests {
                 db.state.view_cache(),
                 db.state.meta_cache(),
             ),
-            &mut transaction,
+            &transaction,
         );
         let tuple = match Pin::new(&mut coroutine).resume(()) {
             CoroutineState::Yielded(tuple) => tuple,
@@ -236,4 +440,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_copy_in_parallel() -> Result<(), DatabaseError> {
+        let rows = CHUNK_ROWS * 4 + 7;
+        let mut file = tempfile::NamedTempFile::new().expect("failed to create temp file");
+        for i in 0..rows {
+            writeln!(file, "{i},{},{}", i % 10, i).expect("failed to write file");
+        }
+
+        for defer_indexes in [false, true] {
+            let temp_dir = TempDir::new().unwrap();
+            let db = DataBaseBuilder::path(temp_dir.path())
+                .copy_threads(3)
+                .copy_defer_indexes(defer_indexes)
+                .build()?;
+            db.run("create table t1 (a int primary key, b int, c int)")?
+                .done()?;
+            db.run("create index b_index on t1 (b)")?.done()?;
+            db.run("create unique index c_index on t1 (c)")?.done()?;
+
+            let mut iter = db.run(format!("copy t1 from '{}'", file.path().display()))?;
+            assert_eq!(
+                iter.next().unwrap()?,
+                TupleBuilder::build_result(format!("import {} rows", rows))
+            );
+            iter.done()?;
+
+            let mut iter = db.run("select a from t1 where b = 3")?;
+            let tuples = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+            iter.done()?;
+            assert_eq!(tuples.len(), rows / 10);
+
+            let mut iter = db.run("select a from t1 where c = 4099")?;
+            let tuples = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+            iter.done()?;
+            assert_eq!(tuples[0].values, vec![DataValue::Int32(4099)]);
+
+            // the rows loaded again conflict with their primary keys
+            assert!(db
+                .run(format!("copy t1 from '{}'", file.path().display()))
+                .and_then(|iter| iter.done())
+                .is_err());
+        }
+
+        Ok(())
+    }
 }
@@ -6,10 +6,22 @@ use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::DataValue;
+#[cfg(feature = "parquet")]
+use crate::utils::parquet_writer::ParquetWriter;
+use itertools::Itertools;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 
+/// The directory of the rows whose column of `PARTITION BY` is `NULL`, as Hive names it.
+const NULL_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
 pub struct CopyToFile {
     op: CopyToFileOperator,
     input: LogicalPlan,
@@ -21,71 +33,185 @@ impl From<(CopyToFileOperator, LogicalPlan)> for CopyToFile {
     }
 }
 
+/// The file of a partition.
+enum FileWriter {
+    /// The writer and the field of a NULL
+    Csv(csv::Writer<File>, Option<String>),
+    #[cfg(feature = "parquet")]
+    Parquet(ParquetWriter),
+}
+
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for CopyToFile {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let mut writer = throw!(self.create_writer());
-                let CopyToFile { input, .. } = self;
+                let CopyToFile { op, input } = self;
+                let mut writers = HashMap::new();
 
                 let mut coroutine = build_read(input, cache, transaction);
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let tuple = throw!(tuple);
+                    let (partition, values): (Vec<_>, Vec<_>) = tuple
+                        .values
+                        .into_iter()
+                        .enumerate()
+                        .partition(|(i, _)| op.partition_by.contains(i));
+                    let partition = op
+                        .partition_by
+                        .iter()
+                        .map(|i| {
+                            let (_, value) = partition.iter().find(|(j, _)| i == j).unwrap();
+                            format!(
+                                "{}={}",
+                                escape_partition(op.schema_ref[*i].name()),
+                                partition_value(value)
+                            )
+                        })
+                        .collect_vec();
+                    let values = values.into_iter().map(|(_, value)| value).collect_vec();
 
-                    throw!(writer
-                        .write_record(
-                            tuple
-                                .values
-                                .iter()
-                                .map(|v| v.to_string())
-                                .collect::<Vec<_>>()
-                        )
-                        .map_err(DatabaseError::from));
+                    let writer = match writers.entry(partition) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        Entry::Vacant(entry) => {
+                            let path = throw!(op.file_path(entry.key()));
+                            entry.insert(throw!(op.create_writer(&path)))
+                        }
+                    };
+                    throw!(writer.write(values));
                 }
+                drop(coroutine);
 
-                throw!(writer.flush().map_err(DatabaseError::from));
+                // the header of the rows, or an empty file, when there are none
+                if writers.is_empty() && op.partition_by.is_empty() {
+                    let path = throw!(op.file_path(&[]));
+                    writers.insert(vec![], throw!(op.create_writer(&path)));
+                }
+                for (_, writer) in writers {
+                    throw!(writer.finish());
+                }
 
-                yield Ok(TupleBuilder::build_result(format!("{}", self.op)));
+                yield Ok(TupleBuilder::build_result(format!("{}", op)));
             },
         )
     }
 }
 
-impl CopyToFile {
-    fn create_writer(&self) -> Result<csv::Writer<std::fs::File>, DatabaseError> {
-        let mut writer = match self.op.target.format {
+impl CopyToFileOperator {
+    /// The target file, or `part-0.<format>` under the directories of the partition when the
+    /// target is a directory, which it is with `PARTITION BY` or a path ending with `/`.
+    fn file_path(&self, partition: &[String]) -> Result<PathBuf, DatabaseError> {
+        let path = &self.target.path;
+        let is_dir = !self.partition_by.is_empty()
+            || path
+                .to_string_lossy()
+                .ends_with(['/', std::path::MAIN_SEPARATOR])
+            || path.is_dir();
+        if !is_dir {
+            return Ok(path.clone());
+        }
+        let mut dir = path.clone();
+        dir.extend(partition);
+        fs::create_dir_all(&dir)?;
+
+        Ok(dir.join(format!("part-0.{}", self.target.format.extension())))
+    }
+
+    fn create_writer(&self, path: &Path) -> Result<FileWriter, DatabaseError> {
+        let columns = self
+            .schema_ref
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.partition_by.contains(i))
+            .map(|(_, column)| column)
+            .collect_vec();
+
+        Ok(match self.target.format {
             FileFormat::Csv {
                 delimiter,
                 quote,
                 header,
+                ref null,
                 ..
-            } => csv::WriterBuilder::new()
-                .delimiter(delimiter as u8)
-                .quote(quote as u8)
-                .has_headers(header)
-                .from_path(self.op.target.path.clone())?,
-        };
+            } => {
+                let mut writer = csv::WriterBuilder::new()
+                    .delimiter(delimiter as u8)
+                    .quote(quote as u8)
+                    .has_headers(header)
+                    .from_path(path)?;
 
-        if let FileFormat::Csv { header: true, .. } = self.op.target.format {
-            let headers = self
-                .op
-                .schema_ref
-                .iter()
-                .map(|c| c.name())
-                .collect::<Vec<_>>();
-            writer.write_record(headers)?;
+                if header {
+                    writer.write_record(columns.iter().map(|column| column.name()))?;
+                }
+                FileWriter::Csv(writer, null.clone())
+            }
+            #[cfg(feature = "parquet")]
+            FileFormat::Parquet { compression } => {
+                FileWriter::Parquet(ParquetWriter::try_new(path, &columns, compression)?)
+            }
+            #[cfg(not(feature = "parquet"))]
+            FileFormat::Parquet { .. } => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "FORMAT parquet without the feature `parquet`".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+impl FileWriter {
+    fn write(&mut self, values: Vec<DataValue>) -> Result<(), DatabaseError> {
+        match self {
+            FileWriter::Csv(writer, null) => {
+                writer.write_record(values.iter().map(|value| match null {
+                    Some(null) if value.is_null() => null.clone(),
+                    _ => value.to_string(),
+                }))?
+            }
+            #[cfg(feature = "parquet")]
+            FileWriter::Parquet(writer) => writer.write(values)?,
         }
+        Ok(())
+    }
 
-        Ok(writer)
+    fn finish(self) -> Result<(), DatabaseError> {
+        match self {
+            FileWriter::Csv(mut writer, _) => writer.flush()?,
+            #[cfg(feature = "parquet")]
+            FileWriter::Parquet(writer) => writer.close()?,
+        }
+        Ok(())
     }
 }
 
+fn partition_value(value: &DataValue) -> String {
+    if value.is_null() {
+        NULL_PARTITION.to_string()
+    } else {
+        escape_partition(&value.to_string())
+    }
+}
+
+/// Escapes the characters that can not be in the name of a directory as Hive does, e.g. `/`
+/// is `%2F`.
+fn escape_partition(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if c.is_control() || "\"#%'*/:=?\\{[]^".contains(c) {
+            escaped.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,7 +234,7 @@ mod tests {
         let columns = vec![
             ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "a".to_string(),
+                    name: "a".into(),
                     relation: ColumnRelation::Table {
                         column_id: Ulid::new(),
                         table_name: Arc::new("t1".to_string()),
@@ -121,7 +247,7 @@ mod tests {
             )),
             ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "b".to_string(),
+                    name: "b".into(),
                     relation: ColumnRelation::Table {
                         column_id: Ulid::new(),
                         table_name: Arc::new("t1".to_string()),
@@ -134,7 +260,7 @@ mod tests {
             )),
             ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c".to_string(),
+                    name: "c".into(),
                     relation: ColumnRelation::Table {
                         column_id: Ulid::new(),
                         table_name: Arc::new("t1".to_string()),
@@ -163,9 +289,11 @@ mod tests {
                     quote: '"',
                     escape: None,
                     header: true,
+                    null: None,
                 },
             },
             schema_ref: Arc::new(columns),
+            partition_by: vec![],
         };
 
         let temp_dir = TempDir::new().unwrap();
@@ -177,7 +305,7 @@ mod tests {
         db.run("insert into t1 values (3, 2.1, 'Kite')")?.done()?;
 
         let storage = db.storage;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table = transaction
             .table(&db.state.table_cache(), Arc::new("t1".to_string()))?
             .unwrap();
@@ -192,7 +320,7 @@ mod tests {
                 db.state.view_cache(),
                 db.state.meta_cache(),
             ),
-            &mut transaction,
+            &transaction,
         );
 
         let tuple = match Pin::new(&mut coroutine).resume(()) {
@@ -220,4 +348,53 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_copy_to_partitioned() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = DataBaseBuilder::path(temp_dir.path()).build()?;
+        db.run("create table t1 (a int primary key, b varchar(10), c int)")?
+            .done()?;
+        db.run("insert into t1 values (1, 'x', 10), (2, 'y', 20), (3, 'x', 30), (4, null, 40)")?
+            .done()?;
+
+        let out_dir = TempDir::new()?;
+        db.run(format!(
+            "copy (select a, b, c from t1 where a > 0) to '{}' (partition by (b), header true)",
+            out_dir.path().display()
+        ))?
+        .done()?;
+
+        let read = |partition: &str| -> Result<Vec<Vec<String>>, DatabaseError> {
+            let mut rdr =
+                csv::Reader::from_path(out_dir.path().join(partition).join("part-0.csv"))?;
+            assert_eq!(rdr.headers()?.clone(), vec!["a", "c"]);
+
+            rdr.records()
+                .map(|record| Ok(record?.iter().map(str::to_string).collect_vec()))
+                .try_collect::<_, Vec<_>, DatabaseError>()
+        };
+        assert_eq!(read("b=x")?, vec![vec!["1", "10"], vec!["3", "30"]]);
+        assert_eq!(read("b=y")?, vec![vec!["2", "20"]]);
+        assert_eq!(
+            read(&format!("b={}", NULL_PARTITION))?,
+            vec![vec!["4", "40"]]
+        );
+        assert!(db
+            .run(format!(
+                "copy (select b from t1) to '{}' (partition by (b))",
+                out_dir.path().display()
+            ))
+            .and_then(|iter| iter.done())
+            .is_err());
+        assert!(db
+            .run(format!(
+                "copy (select a from t1) to '{}' (format csv, compression zstd)",
+                out_dir.path().display()
+            ))
+            .and_then(|iter| iter.done())
+            .is_err());
+
+        Ok(())
+    }
 }
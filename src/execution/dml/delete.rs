@@ -11,6 +11,7 @@ use crate::types::index::{Index, IndexId, IndexType};
 use crate::types::tuple::Tuple;
 use crate::types::tuple_builder::TupleBuilder;
 use crate::types::value::DataValue;
+use itertools::Itertools;
 use std::collections::HashMap;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
@@ -31,7 +32,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
     fn execute_mut(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -40,13 +41,13 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
                     table_name,
                     mut input,
                 } = self;
+                throw!(transaction.throttle_write(&table_name));
 
                 let schema = input.output_schema().clone();
-                let table = throw!(throw!(
-                    unsafe { &mut (*transaction) }.table(cache.0, table_name.clone())
-                )
-                .ok_or(DatabaseError::TableNotFound));
+                let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
                 let mut indexes: HashMap<IndexId, Value> = HashMap::new();
+                let mut is_modified = false;
 
                 let mut coroutine = build_read(input, cache, transaction);
 
@@ -90,7 +91,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
                         ) in indexes.iter_mut()
                         {
                             for value in values {
-                                throw!(unsafe { &mut (*transaction) }.del_index(
+                                throw!(transaction.del_index(
                                     &table_name,
                                     &Index::new(*index_id, value, *index_ty),
                                     tuple_id,
@@ -98,10 +99,19 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
                             }
                         }
 
-                        throw!(unsafe { &mut (*transaction) }.remove_tuple(&table_name, tuple_id));
+                        throw!(transaction.remove_tuple(&table_name, tuple_id));
+                        is_modified = true;
                     }
                 }
                 drop(coroutine);
+
+                if is_modified {
+                    let column_ids = table
+                        .columns()
+                        .filter_map(|column| column.id())
+                        .collect_vec();
+                    throw!(transaction.mark_statistics_modified(&table_name, &column_ids));
+                }
                 yield Ok(TupleBuilder::build_result("1".to_string()));
             },
         )
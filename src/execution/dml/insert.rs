@@ -4,6 +4,7 @@ use crate::execution::dql::projection::Projection;
 use crate::execution::{build_read, Executor, WriteExecutor};
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::LogicalPlan;
+use crate::storage::tuple_codec::TupleCodecEnum;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::index::Index;
@@ -64,7 +65,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
     fn execute_mut(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -75,6 +76,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                     is_overwrite,
                     is_mapping_by_name,
                 } = self;
+                throw!(transaction.throttle_write(&table_name));
 
                 let schema = input.output_schema().clone();
 
@@ -85,12 +87,18 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                     .map(|(_, col)| col.key(is_mapping_by_name))
                     .collect_vec();
                 if primary_keys.is_empty() {
-                    throw!(Err(DatabaseError::NotNull))
+                    let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                        .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
+                    let column = table
+                        .primary_keys()
+                        .iter()
+                        .map(|(_, column)| column.name())
+                        .join(", ");
+                    throw!(Err(DatabaseError::NotNull(column)))
                 }
 
                 if let Some(table_catalog) =
-                    throw!(unsafe { &mut (*transaction) }.table(cache.0, table_name.clone()))
-                        .cloned()
+                    throw!(transaction.table(cache.0, table_name.clone())).cloned()
                 {
                     let mut index_metas = Vec::new();
                     for index_meta in table_catalog.indexes() {
@@ -98,8 +106,9 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                         index_metas.push((index_meta, exprs));
                     }
 
-                    let types = table_catalog.types();
+                    let tuple_codec = throw!(TupleCodecEnum::instance(&table_catalog));
                     let pk_indices = table_catalog.primary_keys_indices();
+                    let mut is_modified = false;
                     let mut coroutine = build_read(input, cache, transaction);
 
                     while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
@@ -115,13 +124,20 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                             let value = {
                                 let mut value = tuple_map.remove(&col.key(is_mapping_by_name));
 
+                                if matches!(value, None | Some(DataValue::Null)) {
+                                    if let Some(audit_value) =
+                                        throw!(table_catalog.audit_columns.insert_value(col))
+                                    {
+                                        value = Some(audit_value);
+                                    }
+                                }
                                 if value.is_none() {
                                     value = throw!(col.default_value());
                                 }
                                 value.unwrap_or(DataValue::Null)
                             };
                             if value.is_null() && !col.nullable() {
-                                yield Err(DatabaseError::NotNull);
+                                yield Err(DatabaseError::NotNull(col.name().to_string()));
                                 return;
                             }
                             values.push(value)
@@ -137,20 +153,27 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                             let tuple_id =
                                 throw!(tuple.pk.as_ref().ok_or(DatabaseError::PrimaryKeyNotFound));
                             let index = Index::new(index_meta.id, &value, index_meta.ty);
-                            throw!(unsafe { &mut (*transaction) }.add_index(
-                                &table_name,
-                                index,
-                                tuple_id
-                            ));
+                            throw!(transaction
+                                .add_index(&table_name, index, tuple_id)
+                                .map_err(|err| err.with_index(&index_meta.name)));
                         }
-                        throw!(unsafe { &mut (*transaction) }.append_tuple(
+                        throw!(transaction.append_tuple(
                             &table_name,
                             tuple,
-                            &types,
+                            &tuple_codec,
                             is_overwrite
                         ));
+                        is_modified = true;
                     }
                     drop(coroutine);
+
+                    if is_modified {
+                        let column_ids = table_catalog
+                            .columns()
+                            .filter_map(|column| column.id())
+                            .collect_vec();
+                        throw!(transaction.mark_statistics_modified(&table_name, &column_ids));
+                    }
                 }
                 yield Ok(TupleBuilder::build_result("1".to_string()));
             },
@@ -1,22 +1,81 @@
 use crate::catalog::{ColumnRef, TableName};
 use crate::errors::DatabaseError;
 use crate::execution::dql::projection::Projection;
+use crate::execution::spill::SpillFile;
 use crate::execution::{build_read, Executor, WriteExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::LogicalPlan;
+use crate::storage::tuple_codec::TupleCodecEnum;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::index::Index;
-use crate::types::tuple::types;
-use crate::types::tuple::Tuple;
+use crate::types::tuple::{Tuple, TupleId};
 use crate::types::tuple_builder::TupleBuilder;
 use crate::types::value::DataValue;
+use itertools::Itertools;
 use std::collections::HashMap;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
 
+/// Bytes of the rows read by an `UPDATE` before it writes them kept in memory, by their estimate.
+const MATERIALIZED_MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+
+type SpilledTuple = (Option<TupleId>, Vec<DataValue>);
+
+/// The rows to update, all read before any is written, the rows past `memory_limit` bytes are
+/// spilled to a temporary file.
+struct MaterializedRows {
+    rows: Vec<Tuple>,
+    spill: Option<SpillFile<SpilledTuple>>,
+}
+
+impl MaterializedRows {
+    fn read(input: &mut Executor, memory_limit: usize) -> Result<Self, DatabaseError> {
+        let mut materialized = MaterializedRows {
+            rows: Vec::new(),
+            spill: None,
+        };
+        let mut memory = 0;
+
+        while let CoroutineState::Yielded(tuple) = Pin::new(&mut *input).resume(()) {
+            let tuple = tuple?;
+
+            if memory < memory_limit {
+                memory += tuple
+                    .values
+                    .iter()
+                    .map(DataValue::estimated_size)
+                    .sum::<usize>();
+                materialized.rows.push(tuple);
+                continue;
+            }
+            let spill = match &mut materialized.spill {
+                Some(spill) => spill,
+                None => materialized.spill.insert(SpillFile::new()?),
+            };
+            spill.write(&(tuple.pk, tuple.values))?;
+        }
+        Ok(materialized)
+    }
+
+    fn into_rows(
+        self,
+    ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError> {
+        let spilled = self.spill.map(SpillFile::into_reader).transpose()?;
+
+        Ok(Box::new(
+            self.rows.into_iter().map(Ok).chain(
+                spilled
+                    .into_iter()
+                    .flatten()
+                    .map(|row| row.map(|(pk, values)| Tuple::new(pk, values))),
+            ),
+        ))
+    }
+}
+
 pub struct Update {
     table_name: TableName,
     value_exprs: Vec<(ColumnRef, ScalarExpression)>,
@@ -45,7 +104,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
     fn execute_mut(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -55,6 +114,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                     value_exprs,
                     mut input,
                 } = self;
+                throw!(transaction.throttle_write(&table_name));
 
                 let mut exprs_map = HashMap::with_capacity(value_exprs.len());
                 for (column, expr) in value_exprs {
@@ -62,11 +122,9 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                 }
 
                 let input_schema = input.output_schema().clone();
-                let types = types(&input_schema);
 
                 if let Some(table_catalog) =
-                    throw!(unsafe { &mut (*transaction) }.table(cache.0, table_name.clone()))
-                        .cloned()
+                    throw!(transaction.table(cache.0, table_name.clone())).cloned()
                 {
                     let mut index_metas = Vec::new();
                     for index_meta in table_catalog.indexes() {
@@ -74,9 +132,44 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                         index_metas.push((index_meta, exprs));
                     }
 
+                    // the scan of the input reads the keys written by the transaction, so that it
+                    // would meet again the rows moved ahead of it in an index it reads, e.g. by
+                    // `UPDATE t SET b = b + 1 WHERE b > 0` on an index of `b`: the rows are all
+                    // read before any is written
+                    let is_materialized = table_catalog.columns().any(|column| {
+                        let is_indexed = column.id().is_some_and(|id| {
+                            index_metas
+                                .iter()
+                                .any(|(index_meta, _)| index_meta.column_ids.contains(&id))
+                        });
+                        is_indexed
+                            && (exprs_map.contains_key(&column.id())
+                                || table_catalog.audit_columns.is_updated(column.name()))
+                    });
+                    let tuple_codec = throw!(TupleCodecEnum::instance(&table_catalog));
+                    let mut is_modified = false;
                     let mut coroutine = build_read(input, cache, transaction);
+                    let mut materialized = if is_materialized {
+                        let rows = throw!(MaterializedRows::read(
+                            &mut coroutine,
+                            MATERIALIZED_MEMORY_LIMIT
+                        ));
+                        Some(throw!(rows.into_rows()))
+                    } else {
+                        None
+                    };
 
-                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    loop {
+                        let tuple = match materialized.as_mut() {
+                            Some(rows) => rows.next(),
+                            None => match Pin::new(&mut coroutine).resume(()) {
+                                CoroutineState::Yielded(tuple) => Some(tuple),
+                                CoroutineState::Complete(()) => None,
+                            },
+                        };
+                        let Some(tuple) = tuple else {
+                            break;
+                        };
                         let mut tuple: Tuple = throw!(tuple);
 
                         let mut is_overwrite = true;
@@ -90,15 +183,16 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                                 continue;
                             };
                             let index = Index::new(index_meta.id, &value, index_meta.ty);
-                            throw!(unsafe { &mut (*transaction) }.del_index(
-                                &table_name,
-                                &index,
-                                &old_pk
-                            ));
+                            throw!(transaction.del_index(&table_name, &index, &old_pk));
                         }
                         for (i, column) in input_schema.iter().enumerate() {
                             if let Some(expr) = exprs_map.get(&column.id()) {
                                 tuple.values[i] = throw!(expr.eval(Some((&tuple, &input_schema))));
+                            } else if let Some(value) = throw!(table_catalog
+                                .audit_columns
+                                .update_value(column, &tuple.values[i]))
+                            {
+                                tuple.values[i] = value;
                             }
                         }
 
@@ -110,9 +204,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                             throw!(tuple.pk.as_ref().ok_or(DatabaseError::PrimaryKeyNotFound));
 
                         if new_pk != &old_pk {
-                            throw!(
-                                unsafe { &mut (*transaction) }.remove_tuple(&table_name, &old_pk)
-                            );
+                            throw!(transaction.remove_tuple(&table_name, &old_pk));
                             is_overwrite = false;
                         }
                         for (index_meta, exprs) in index_metas.iter() {
@@ -122,24 +214,101 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                                 continue;
                             };
                             let index = Index::new(index_meta.id, &value, index_meta.ty);
-                            throw!(unsafe { &mut (*transaction) }.add_index(
-                                &table_name,
-                                index,
-                                new_pk
-                            ));
+                            throw!(transaction
+                                .add_index(&table_name, index, new_pk)
+                                .map_err(|err| err.with_index(&index_meta.name)));
                         }
+                        // the values of the scalar subqueries of `WHERE` follow the ones of the row
+                        tuple.values.truncate(table_catalog.columns_len());
 
-                        throw!(unsafe { &mut (*transaction) }.append_tuple(
+                        throw!(transaction.append_tuple(
                             &table_name,
                             tuple,
-                            &types,
+                            &tuple_codec,
                             is_overwrite
                         ));
+                        is_modified = true;
                     }
                     drop(coroutine);
+
+                    if is_modified {
+                        let column_ids = exprs_map.keys().flatten().cloned().collect_vec();
+                        throw!(transaction.mark_statistics_modified(&table_name, &column_ids));
+                    }
                 }
                 yield Ok(TupleBuilder::build_result("1".to_string()));
             },
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::execution::dml::update::MaterializedRows;
+    use crate::execution::Executor;
+    use crate::types::tuple::Tuple;
+    use crate::types::value::DataValue;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_materialized_rows() -> Result<(), DatabaseError> {
+        let tuples = (0..100)
+            .map(|i| Tuple::new(Some(DataValue::Int32(i)), vec![DataValue::Int32(i)]))
+            .collect_vec();
+        let rows = tuples.clone();
+        let mut input: Executor = Box::new(
+            #[coroutine]
+            move || {
+                for tuple in rows {
+                    yield Ok(tuple);
+                }
+            },
+        );
+        // the rows past the memory of 10 of them are spilled
+        let memory_limit = DataValue::Int32(0).estimated_size() * 10;
+        let materialized = MaterializedRows::read(&mut input, memory_limit)?;
+
+        assert_eq!(materialized.rows.len(), 10);
+        assert_eq!(
+            materialized.spill.as_ref().map(|spill| spill.rows),
+            Some(90)
+        );
+        assert_eq!(
+            materialized.into_rows()?.try_collect::<_, Vec<_>, _>()?,
+            tuples
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_moved_rows() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, b int)")?
+            .done()?;
+        kite_sql.run("create index b_index on t1 (b)")?.done()?;
+        kite_sql
+            .run("insert into t1 select number, number from table(numbers(1000))")?
+            .done()?;
+        kite_sql.run("analyze table t1")?.done()?;
+
+        // the rows read by the index of `b` are moved ahead of the scan, and updated once
+        kite_sql
+            .run("update t1 set b = b + 10 where b > 900")?
+            .done()?;
+        let rows = kite_sql
+            .run("select id, b from t1 where b != id and b - id != 10")?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert!(rows.is_empty());
+        let mut iter = kite_sql.run("select count(*) from t1 where b > 1000")?;
+        assert_eq!(iter.next().unwrap()?.values, vec![DataValue::Int32(9)]);
+        iter.done()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::{compare_order_by, split_order_by, Accumulator};
+use crate::types::value::DataValue;
+use std::mem;
+
+/// Collects the values of the group, nulls included, in the order of the `ORDER BY` keys
+/// of the call or in the order received when there are no keys.
+pub struct ArrayAggAccumulator {
+    order_by: Vec<(bool, bool)>,
+    values: Vec<(Vec<DataValue>, DataValue)>,
+    // bytes of the values and their keys
+    values_size: usize,
+}
+
+impl ArrayAggAccumulator {
+    pub fn new(order_by: Vec<(bool, bool)>) -> Self {
+        Self {
+            order_by,
+            values: Vec::new(),
+            values_size: 0,
+        }
+    }
+}
+
+impl Accumulator for ArrayAggAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        let (value, keys) = split_order_by(&self.order_by, value)?;
+        self.values_size += value.estimated_size()
+            + keys.iter().map(DataValue::estimated_size).sum::<usize>()
+            + mem::size_of::<Vec<DataValue>>();
+        self.values.push((keys, value));
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<DataValue, DatabaseError> {
+        if self.values.is_empty() {
+            return Ok(DataValue::Null);
+        }
+        let mut values = self.values.iter().collect::<Vec<_>>();
+        if !self.order_by.is_empty() {
+            values.sort_by(|(keys_1, _), (keys_2, _)| {
+                compare_order_by(&self.order_by, keys_1, keys_2)
+            });
+        }
+
+        Ok(DataValue::Array(
+            values.into_iter().map(|(_, value)| value.clone()).collect(),
+        ))
+    }
+
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>() + self.values_size
+    }
+}
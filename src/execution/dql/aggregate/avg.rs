@@ -4,6 +4,9 @@ use crate::execution::dql::aggregate::Accumulator;
 use crate::expression::BinaryOperator;
 use crate::types::evaluator::EvaluatorFactory;
 use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use bigdecimal::BigDecimal;
+use std::mem;
 
 pub struct AvgAccumulator {
     inner: Option<SumAccumulator>,
@@ -45,7 +48,9 @@ impl Accumulator for AvgAccumulator {
         if self.count == 0 {
             return Ok(DataValue::Null);
         }
-        let quantity = if value_ty.is_signed_numeric() {
+        let quantity = if value_ty == LogicalType::Numeric {
+            DataValue::Numeric(BigDecimal::from(self.count as u64))
+        } else if value_ty.is_signed_numeric() {
             DataValue::Int64(self.count as i64)
         } else {
             DataValue::UInt32(self.count as u32)
@@ -58,4 +63,12 @@ impl Accumulator for AvgAccumulator {
         let evaluator = EvaluatorFactory::binary_create(quantity_ty, BinaryOperator::Divide)?;
         evaluator.0.binary_eval(&value, &quantity)
     }
+
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>()
+            + self
+                .inner
+                .as_ref()
+                .map_or(0, SumAccumulator::estimated_size)
+    }
 }
@@ -0,0 +1,42 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::value::DataValue;
+
+pub struct BoolAndOrAccumulator {
+    inner: Option<bool>,
+    is_and: bool,
+}
+
+impl BoolAndOrAccumulator {
+    pub fn new(is_and: bool) -> Self {
+        Self {
+            inner: None,
+            is_and,
+        }
+    }
+}
+
+impl Accumulator for BoolAndOrAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        if value.is_null() {
+            return Ok(());
+        }
+        let DataValue::Boolean(value) = value else {
+            return Err(DatabaseError::InvalidType);
+        };
+        self.inner = Some(match self.inner {
+            Some(inner) if self.is_and => inner && *value,
+            Some(inner) => inner || *value,
+            None => *value,
+        });
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<DataValue, DatabaseError> {
+        Ok(self
+            .inner
+            .map(DataValue::Boolean)
+            .unwrap_or(DataValue::Null))
+    }
+}
@@ -3,6 +3,7 @@ use crate::execution::dql::aggregate::Accumulator;
 use crate::types::value::DataValue;
 use ahash::RandomState;
 use std::collections::HashSet;
+use std::mem;
 
 pub struct CountAccumulator {
     result: i32,
@@ -30,19 +31,23 @@ impl Accumulator for CountAccumulator {
 
 pub struct DistinctCountAccumulator {
     distinct_values: HashSet<DataValue, RandomState>,
+    // bytes of the distinct values
+    values_size: usize,
 }
 
 impl DistinctCountAccumulator {
     pub fn new() -> Self {
         Self {
             distinct_values: HashSet::default(),
+            values_size: 0,
         }
     }
 }
 
 impl Accumulator for DistinctCountAccumulator {
     fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
-        if !value.is_null() {
+        if !value.is_null() && !self.distinct_values.contains(value) {
+            self.values_size += value.estimated_size();
             self.distinct_values.insert(value.clone());
         }
 
@@ -52,4 +57,8 @@ impl Accumulator for DistinctCountAccumulator {
     fn evaluate(&self) -> Result<DataValue, DatabaseError> {
         Ok(DataValue::Int32(self.distinct_values.len() as i32))
     }
+
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>() + self.values_size
+    }
 }
@@ -0,0 +1,55 @@
+use crate::catalog::TableName;
+use crate::execution::{Executor, ReadExecutor};
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+
+/// Answers `SELECT COUNT(*) FROM t` by counting the keys of the table.
+pub struct CountStarExecutor {
+    table_name: TableName,
+    agg_len: usize,
+}
+
+impl From<(AggregateOperator, LogicalPlan)> for CountStarExecutor {
+    fn from(
+        (AggregateOperator { agg_calls, .. }, input): (AggregateOperator, LogicalPlan),
+    ) -> Self {
+        let Operator::TableScan(scan_op) = input.operator else {
+            unreachable!("`CountStar` must be on a table scan")
+        };
+
+        CountStarExecutor {
+            table_name: scan_op.table_name,
+            agg_len: agg_calls.len(),
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for CountStarExecutor {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let CountStarExecutor {
+                    table_name,
+                    agg_len,
+                } = self;
+
+                let count = throw!(transaction.count_tuples(table_name.as_str()));
+
+                yield Ok(Tuple::new(
+                    None,
+                    vec![DataValue::Int32(count as i32); agg_len],
+                ));
+            },
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::{compare_order_by, split_order_by, Accumulator};
+use crate::types::value::DataValue;
+use std::cmp::Ordering;
+use std::mem;
+
+/// Keeps the value of the row ordered first by the `ORDER BY` keys of the call,
+/// or the first (last) row received when there are no keys.
+pub struct FirstLastValueAccumulator {
+    order_by: Vec<(bool, bool)>,
+    is_last: bool,
+    inner: Option<(Vec<DataValue>, DataValue)>,
+}
+
+impl FirstLastValueAccumulator {
+    pub fn new(order_by: Vec<(bool, bool)>, is_last: bool) -> Self {
+        Self {
+            order_by,
+            is_last,
+            inner: None,
+        }
+    }
+}
+
+impl Accumulator for FirstLastValueAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        let (value, keys) = split_order_by(&self.order_by, value)?;
+        let is_replace = match &self.inner {
+            Some((inner_keys, _)) => {
+                let ordering = compare_order_by(&self.order_by, &keys, inner_keys);
+
+                if self.is_last {
+                    ordering != Ordering::Less
+                } else {
+                    ordering == Ordering::Less
+                }
+            }
+            None => true,
+        };
+        if is_replace {
+            self.inner = Some((keys, value));
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<DataValue, DatabaseError> {
+        Ok(self
+            .inner
+            .as_ref()
+            .map(|(_, value)| value.clone())
+            .unwrap_or(DataValue::Null))
+    }
+
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>()
+            + self.inner.as_ref().map_or(0, |(keys, value)| {
+                keys.iter().map(DataValue::estimated_size).sum::<usize>() + value.estimated_size()
+            })
+    }
+}
@@ -1,5 +1,7 @@
 use crate::errors::DatabaseError;
 use crate::execution::dql::aggregate::{create_accumulators, Accumulator};
+use crate::execution::dql::key_map::KeyMap;
+use crate::execution::spill::SpillFile;
 use crate::execution::{build_read, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
@@ -8,15 +10,26 @@ use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::tuple::Tuple;
 use crate::types::value::DataValue;
-use ahash::{HashMap, HashMapExt};
+use crate::types::LogicalType;
+use ahash::RandomState;
 use itertools::Itertools;
-use std::collections::hash_map::Entry;
+use std::iter;
 use std::ops::{Coroutine, CoroutineState};
 use std::pin::Pin;
 
+/// Partitions of the rows of the groups spilled by an aggregation.
+const SPILL_PARTITIONS: u64 = 16;
+/// Levels of partitions after which the groups of a partition are aggregated in memory, whatever
+/// their number, their keys hashing to the same partition at every level.
+const MAX_SPILL_LEVEL: u64 = 8;
+
+/// The group keys and the arguments of the calls of a row.
+type AggRow = (Vec<DataValue>, Vec<DataValue>);
+
 pub struct HashAggExecutor {
     agg_calls: Vec<ScalarExpression>,
     groupby_exprs: Vec<ScalarExpression>,
+    memory_limit: usize,
     input: LogicalPlan,
 }
 
@@ -26,6 +39,7 @@ impl From<(AggregateOperator, LogicalPlan)> for HashAggExecutor {
             AggregateOperator {
                 agg_calls,
                 groupby_exprs,
+                aggregate_memory_limit,
                 ..
             },
             input,
@@ -34,16 +48,82 @@ impl From<(AggregateOperator, LogicalPlan)> for HashAggExecutor {
         HashAggExecutor {
             agg_calls,
             groupby_exprs,
+            memory_limit: aggregate_memory_limit,
             input,
         }
     }
 }
 
+/// Aggregates `rows` in groups whose keys and accumulators take at most `memory_limit` bytes by
+/// their estimate, the rows of the groups found once the memory is taken are spilled to partitions
+/// by the hash of their keys, and aggregated the same way at the next level.
+///
+/// The state of a group is not split: the groups already in memory keep taking their rows, so
+/// that only the groups that fit are aggregated at a level.
+fn aggregate(
+    rows: impl Iterator<Item = Result<AggRow, DatabaseError>>,
+    agg_calls: &[ScalarExpression],
+    group_types: &[LogicalType],
+    memory_limit: usize,
+    level: u64,
+    spills: &mut Vec<(u64, SpillFile<AggRow>)>,
+) -> Result<KeyMap<Vec<Box<dyn Accumulator>>>, DatabaseError> {
+    let mut group_hash_accs: KeyMap<Vec<Box<dyn Accumulator>>> = KeyMap::new(group_types);
+    // bytes of the keys and the accumulators of the groups in memory
+    let mut memory = 0;
+    let mut partitions: Vec<SpillFile<AggRow>> = Vec::new();
+    // the groups of a partition are split by another hash at the next level
+    let hasher = RandomState::with_seeds(level, level, level, level);
+
+    for row in rows {
+        let (group_keys, values) = row?;
+
+        if memory >= memory_limit
+            && level < MAX_SPILL_LEVEL
+            && group_hash_accs.get_mut(&group_keys).is_none()
+        {
+            if partitions.is_empty() {
+                partitions = (0..SPILL_PARTITIONS)
+                    .map(|_| SpillFile::new())
+                    .try_collect()?;
+            }
+            let partition = hasher.hash_one(&group_keys) % SPILL_PARTITIONS;
+            partitions[partition as usize].write(&(group_keys, values))?;
+            continue;
+        }
+        let keys_size = group_keys
+            .iter()
+            .map(DataValue::estimated_size)
+            .sum::<usize>();
+        let mut is_new = false;
+        let accs = group_hash_accs.get_or_try_insert_with(group_keys, || {
+            is_new = true;
+            create_accumulators(agg_calls)
+        })?;
+        if is_new {
+            memory += keys_size;
+        }
+        for (acc, value) in accs.iter_mut().zip_eq(values.iter()) {
+            let size = if is_new { 0 } else { acc.estimated_size() };
+            acc.update_value(value)?;
+            memory = memory + acc.estimated_size() - size;
+        }
+    }
+    spills.extend(
+        partitions
+            .into_iter()
+            .filter(|file| file.rows > 0)
+            .map(|file| (level + 1, file)),
+    );
+
+    Ok(group_hash_accs)
+}
+
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashAggExecutor {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -51,53 +131,74 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashAggExecutor {
                 let HashAggExecutor {
                     agg_calls,
                     groupby_exprs,
+                    memory_limit,
                     mut input,
                 } = self;
 
                 let schema_ref = input.output_schema().clone();
-                let mut group_hash_accs: HashMap<Vec<DataValue>, Vec<Box<dyn Accumulator>>> =
-                    HashMap::new();
+                let group_types = groupby_exprs
+                    .iter()
+                    .map(ScalarExpression::return_type)
+                    .collect_vec();
+                let mut spills = Vec::new();
 
                 let mut coroutine = build_read(input, cache, transaction);
+                let rows = iter::from_fn(|| {
+                    let CoroutineState::Yielded(result) = Pin::new(&mut coroutine).resume(())
+                    else {
+                        return None;
+                    };
+                    Some(result.and_then(|tuple| {
+                        let mut values = Vec::with_capacity(agg_calls.len());
 
-                while let CoroutineState::Yielded(result) = Pin::new(&mut coroutine).resume(()) {
-                    let tuple = throw!(result);
-                    let mut values = Vec::with_capacity(agg_calls.len());
-
-                    for expr in agg_calls.iter() {
-                        if let ScalarExpression::AggCall { args, .. } = expr {
-                            if args.len() > 1 {
-                                throw!(Err(DatabaseError::UnsupportedStmt("currently aggregate functions only support a single Column as a parameter".to_string())))
+                        for expr in agg_calls.iter() {
+                            if let ScalarExpression::AggCall { args, .. } = expr {
+                                if args.len() > 1 {
+                                    return Err(DatabaseError::UnsupportedStmt("currently aggregate functions only support a single Column as a parameter".to_string()));
+                                }
+                                values.push(args[0].eval(Some((&tuple, &schema_ref)))?);
+                            } else {
+                                unreachable!()
                             }
-                            values.push(throw!(args[0].eval(Some((&tuple, &schema_ref)))));
-                        } else {
-                            unreachable!()
                         }
+                        let group_keys: Vec<DataValue> = groupby_exprs
+                            .iter()
+                            .map(|expr| expr.eval(Some((&tuple, &schema_ref))))
+                            .try_collect()?;
+
+                        Ok((group_keys, values))
+                    }))
+                });
+                let mut group_hash_accs = throw!(aggregate(
+                    rows,
+                    &agg_calls,
+                    &group_types,
+                    memory_limit,
+                    0,
+                    &mut spills,
+                ));
+
+                loop {
+                    for (group_keys, accs) in group_hash_accs.into_entries() {
+                        // Tips: Accumulator First
+                        let values: Vec<DataValue> = throw!(accs
+                            .iter()
+                            .map(|acc| acc.evaluate())
+                            .chain(group_keys.into_iter().map(Ok))
+                            .try_collect());
+                        yield Ok(Tuple::new(None, values));
                     }
-                    let group_keys: Vec<DataValue> = throw!(groupby_exprs
-                        .iter()
-                        .map(|expr| expr.eval(Some((&tuple, &schema_ref))))
-                        .try_collect());
-
-                    let entry = match group_hash_accs.entry(group_keys) {
-                        Entry::Occupied(entry) => entry.into_mut(),
-                        Entry::Vacant(entry) => {
-                            entry.insert(throw!(create_accumulators(&agg_calls)))
-                        }
+                    let Some((level, file)) = spills.pop() else {
+                        break;
                     };
-                    for (acc, value) in entry.iter_mut().zip_eq(values.iter()) {
-                        throw!(acc.update_value(value));
-                    }
-                }
-
-                for (group_keys, accs) in group_hash_accs {
-                    // Tips: Accumulator First
-                    let values: Vec<DataValue> = throw!(accs
-                        .iter()
-                        .map(|acc| acc.evaluate())
-                        .chain(group_keys.into_iter().map(Ok))
-                        .try_collect());
-                    yield Ok(Tuple::new(None, values));
+                    group_hash_accs = throw!(aggregate(
+                        throw!(file.into_reader()),
+                        &agg_calls,
+                        &group_types,
+                        memory_limit,
+                        level,
+                        &mut spills,
+                    ));
                 }
             },
         )
@@ -108,21 +209,22 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashAggExecutor {
 mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
     use crate::errors::DatabaseError;
-    use crate::execution::dql::aggregate::hash_agg::HashAggExecutor;
+    use crate::execution::dql::aggregate::hash_agg::{aggregate, HashAggExecutor};
     use crate::execution::dql::test::build_integers;
     use crate::execution::{try_collect, ReadExecutor};
     use crate::expression::agg::AggKind;
     use crate::expression::ScalarExpression;
-    use crate::planner::operator::aggregate::AggregateOperator;
+    use crate::planner::operator::aggregate::{AggregateOperator, DEFAULT_AGGREGATE_MEMORY_LIMIT};
     use crate::planner::operator::values::ValuesOperator;
     use crate::planner::operator::Operator;
     use crate::planner::{Childrens, LogicalPlan};
     use crate::storage::rocksdb::RocksStorage;
     use crate::storage::Storage;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
     use crate::types::LogicalType;
     use crate::utils::lru::SharedLruCache;
     use itertools::Itertools;
+    use sqlparser::ast::CharLengthUnits;
     use std::hash::RandomState;
     use std::sync::Arc;
     use tempfile::TempDir;
@@ -135,7 +237,7 @@ mod test {
 
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path()).unwrap();
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let desc = ColumnDesc::new(LogicalType::Integer, None, false, None)?;
 
         let t1_schema = Arc::new(vec![
@@ -153,6 +255,7 @@ mod test {
                 ty: LogicalType::Integer,
             }],
             is_distinct: false,
+            aggregate_memory_limit: DEFAULT_AGGREGATE_MEMORY_LIMIT,
         };
 
         let input = LogicalPlan {
@@ -188,7 +291,7 @@ mod test {
 
         let tuples = try_collect(
             HashAggExecutor::from((operator, input))
-                .execute((&table_cache, &view_cache, &meta_cache), &mut transaction),
+                .execute((&table_cache, &view_cache, &meta_cache), &transaction),
         )?;
 
         assert_eq!(tuples.len(), 2);
@@ -200,4 +303,110 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_hash_agg_spill() -> Result<(), DatabaseError> {
+        let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+        let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path()).unwrap();
+        let transaction = storage.transaction()?;
+        let desc = ColumnDesc::new(LogicalType::Integer, None, false, None)?;
+
+        let t1_schema = Arc::new(vec![
+            ColumnRef::from(ColumnCatalog::new("c1".to_string(), true, desc.clone())),
+            ColumnRef::from(ColumnCatalog::new("c2".to_string(), true, desc.clone())),
+        ]);
+
+        // the groups taking 1000 bytes of the 100 are kept in memory, the others are spilled over
+        // several levels
+        let operator = AggregateOperator {
+            groupby_exprs: vec![ScalarExpression::ColumnRef(t1_schema[0].clone())],
+            agg_calls: vec![ScalarExpression::AggCall {
+                distinct: false,
+                kind: AggKind::Sum,
+                args: vec![ScalarExpression::ColumnRef(t1_schema[1].clone())],
+                ty: LogicalType::Integer,
+            }],
+            is_distinct: false,
+            aggregate_memory_limit: 1_000,
+        };
+        let input = LogicalPlan {
+            operator: Operator::Values(ValuesOperator {
+                rows: (0..1_000)
+                    .map(|i| vec![DataValue::Int32(i % 100), DataValue::Int32(i)])
+                    .collect(),
+                schema_ref: t1_schema.clone(),
+            }),
+            childrens: Box::new(Childrens::None),
+            physical_option: None,
+            _output_schema_ref: None,
+        };
+
+        let tuples = try_collect(
+            HashAggExecutor::from((operator, input))
+                .execute((&table_cache, &view_cache, &meta_cache), &transaction),
+        )?;
+        let vec_values = tuples
+            .into_iter()
+            .map(|tuple| tuple.values)
+            .sorted_by_key(|values| match values[1] {
+                DataValue::Int32(group) => group,
+                _ => unreachable!(),
+            })
+            .collect_vec();
+
+        assert_eq!(
+            vec_values,
+            (0..100)
+                .map(|group| build_integers(vec![Some(group * 10 + 4_500), Some(group)]))
+                .collect_vec()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_agg_spill_by_bytes() -> Result<(), DatabaseError> {
+        let agg_calls = vec![ScalarExpression::AggCall {
+            distinct: false,
+            kind: AggKind::Count,
+            args: vec![ScalarExpression::Constant(DataValue::Int32(1))],
+            ty: LogicalType::Integer,
+        }];
+        let rows = |key: fn(i32) -> DataValue| {
+            (0..20).map(move |i| Ok((vec![key(i)], vec![DataValue::Int32(1)])))
+        };
+        let wide_key = |i: i32| DataValue::Utf8 {
+            value: format!("{i:01000}"),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+        let varchar = [LogicalType::Varchar(None, CharLengthUnits::Characters)];
+
+        // the same groups fit in memory by their number, not by the bytes of their keys
+        let mut spills = Vec::new();
+        let groups = aggregate(
+            rows(DataValue::Int32),
+            &agg_calls,
+            &[LogicalType::Integer],
+            10_000,
+            0,
+            &mut spills,
+        )?;
+        assert_eq!(groups.len(), 20);
+        assert!(spills.is_empty());
+
+        let groups = aggregate(rows(wide_key), &agg_calls, &varchar, 10_000, 0, &mut spills)?;
+        assert!(groups.len() <= 10);
+        assert_eq!(
+            spills.iter().map(|(_, file)| file.rows).sum::<usize>(),
+            20 - groups.len()
+        );
+        assert!(spills.iter().all(|(level, _)| *level == 1));
+
+        Ok(())
+    }
 }
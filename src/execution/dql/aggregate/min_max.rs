@@ -3,6 +3,7 @@ use crate::execution::dql::aggregate::Accumulator;
 use crate::expression::BinaryOperator;
 use crate::types::evaluator::EvaluatorFactory;
 use crate::types::value::DataValue;
+use std::mem;
 
 pub struct MinMaxAccumulator {
     inner: Option<DataValue>,
@@ -43,4 +44,8 @@ impl Accumulator for MinMaxAccumulator {
     fn evaluate(&self) -> Result<DataValue, DatabaseError> {
         Ok(self.inner.clone().unwrap_or(DataValue::Null))
     }
+
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>() + self.inner.as_ref().map_or(0, DataValue::estimated_size)
+    }
 }
@@ -0,0 +1,108 @@
+use crate::errors::DatabaseError;
+use crate::execution::{Executor, ReadExecutor};
+use crate::expression::agg::AggKind;
+use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::{IndexIter, Iter, StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::index::IndexMetaRef;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use std::collections::Bound;
+
+/// Answers `SELECT MIN(c), MAX(c) FROM t` by reading the boundaries of the index on `c`.
+pub struct MinMaxIndexExecutor {
+    agg_calls: Vec<ScalarExpression>,
+    scan_op: TableScanOperator,
+    index_meta: IndexMetaRef,
+}
+
+impl From<(AggregateOperator, LogicalPlan)> for MinMaxIndexExecutor {
+    fn from(
+        (AggregateOperator { agg_calls, .. }, input): (AggregateOperator, LogicalPlan),
+    ) -> Self {
+        let Operator::TableScan(scan_op) = input.operator else {
+            unreachable!("`MinMaxIndex` must be on a table scan")
+        };
+        let Some(index_meta) = scan_op.single_column_index().cloned() else {
+            unreachable!("`MinMaxIndex` must be on an indexed column")
+        };
+
+        MinMaxIndexExecutor {
+            agg_calls,
+            scan_op,
+            index_meta,
+        }
+    }
+}
+
+impl MinMaxIndexExecutor {
+    /// The first non-null value of the index, `NULL` if there is none.
+    fn boundary<T: Transaction>(mut iter: IndexIter<'_, T>) -> Result<DataValue, DatabaseError> {
+        // Tips: `NULL` sorts first in the index
+        while let Some(mut tuple) = iter.next_tuple()? {
+            let value = tuple.values.swap_remove(0);
+
+            if !value.is_null() {
+                return Ok(value);
+            }
+        }
+        Ok(DataValue::Null)
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for MinMaxIndexExecutor {
+    fn execute(
+        self,
+        (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let MinMaxIndexExecutor {
+                    agg_calls,
+                    scan_op,
+                    index_meta,
+                } = self;
+
+                let mut min = None;
+                let mut max = None;
+                let mut values = Vec::with_capacity(agg_calls.len());
+
+                for expr in agg_calls.iter() {
+                    let ScalarExpression::AggCall { kind, .. } = expr else {
+                        unreachable!()
+                    };
+                    let is_max = matches!(kind, AggKind::Max);
+                    let boundary = if is_max { &mut max } else { &mut min };
+
+                    if boundary.is_none() {
+                        let iter = throw!(transaction.read_by_index(
+                            table_cache,
+                            scan_op.table_name.clone(),
+                            (None, None),
+                            scan_op.columns.clone(),
+                            index_meta.clone(),
+                            vec![Range::Scope {
+                                min: Bound::Unbounded,
+                                max: Bound::Unbounded,
+                            }],
+                            false,
+                        ));
+                        let iter = if is_max { iter.reverse() } else { iter };
+
+                        *boundary = Some(throw!(Self::boundary(iter)));
+                    }
+                    values.extend(boundary.clone());
+                }
+
+                yield Ok(Tuple::new(None, values));
+            },
+        )
+    }
+}
@@ -1,19 +1,29 @@
+mod array_agg;
 mod avg;
+mod bool_and_or;
 mod count;
+pub mod count_star;
+mod first_last_value;
 pub mod hash_agg;
 mod min_max;
+pub mod min_max_index;
 pub mod simple_agg;
 mod sum;
 
 use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::array_agg::ArrayAggAccumulator;
 use crate::execution::dql::aggregate::avg::AvgAccumulator;
+use crate::execution::dql::aggregate::bool_and_or::BoolAndOrAccumulator;
 use crate::execution::dql::aggregate::count::{CountAccumulator, DistinctCountAccumulator};
+use crate::execution::dql::aggregate::first_last_value::FirstLastValueAccumulator;
 use crate::execution::dql::aggregate::min_max::MinMaxAccumulator;
 use crate::execution::dql::aggregate::sum::{DistinctSumAccumulator, SumAccumulator};
 use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
 use crate::types::value::DataValue;
 use itertools::Itertools;
+use std::cmp::Ordering;
+use std::mem;
 
 /// Tips: Idea for sqlrs
 /// An accumulator represents a stateful object that lives throughout the evaluation of multiple
@@ -24,6 +34,11 @@ pub trait Accumulator: Send + Sync {
 
     /// returns its value based on its current state.
     fn evaluate(&self) -> Result<DataValue, DatabaseError>;
+
+    /// bytes of its state by an estimate, weighed against the memory limit of the hash aggregation.
+    fn estimated_size(&self) -> usize {
+        mem::size_of_val(self)
+    }
 }
 
 fn create_accumulator(expr: &ScalarExpression) -> Result<Box<dyn Accumulator>, DatabaseError> {
@@ -39,6 +54,18 @@ fn create_accumulator(expr: &ScalarExpression) -> Result<Box<dyn Accumulator>, D
             (AggKind::Min, _) => Box::new(MinMaxAccumulator::new(false)),
             (AggKind::Max, _) => Box::new(MinMaxAccumulator::new(true)),
             (AggKind::Avg, _) => Box::new(AvgAccumulator::new()),
+            (AggKind::BoolAnd, _) => Box::new(BoolAndOrAccumulator::new(true)),
+            (AggKind::BoolOr, _) => Box::new(BoolAndOrAccumulator::new(false)),
+            (AggKind::FirstValue(order_by), _) => {
+                Box::new(FirstLastValueAccumulator::new(order_by.clone(), false))
+            }
+            (AggKind::LastValue(order_by), _) => {
+                Box::new(FirstLastValueAccumulator::new(order_by.clone(), true))
+            }
+            (AggKind::Arbitrary, _) => Box::new(FirstLastValueAccumulator::new(Vec::new(), false)),
+            (AggKind::ArrayAgg(order_by), _) => {
+                Box::new(ArrayAggAccumulator::new(order_by.clone()))
+            }
         })
     } else {
         unreachable!(
@@ -48,6 +75,57 @@ fn create_accumulator(expr: &ScalarExpression) -> Result<Box<dyn Accumulator>, D
     }
 }
 
+/// Splits the argument of a call with `ORDER BY` keys, a tuple of the value followed by the keys.
+fn split_order_by(
+    order_by: &[(bool, bool)],
+    value: &DataValue,
+) -> Result<(DataValue, Vec<DataValue>), DatabaseError> {
+    if order_by.is_empty() {
+        return Ok((value.clone(), Vec::new()));
+    }
+    let DataValue::Tuple(mut values, _) = value.clone() else {
+        return Err(DatabaseError::InvalidType);
+    };
+    Ok((values.remove(0), values))
+}
+
+/// Orders the keys of two rows by the `(asc, nulls_first)` of each `ORDER BY` key of the call.
+fn compare_order_by(
+    order_by: &[(bool, bool)],
+    keys_1: &[DataValue],
+    keys_2: &[DataValue],
+) -> Ordering {
+    let fn_nulls_first = |nulls_first: bool| {
+        if nulls_first {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    };
+
+    for ((value_1, value_2), (asc, nulls_first)) in
+        keys_1.iter().zip(keys_2.iter()).zip(order_by.iter())
+    {
+        let ordering = match (value_1.is_null(), value_2.is_null()) {
+            (false, true) => fn_nulls_first(*nulls_first),
+            (true, false) => fn_nulls_first(*nulls_first).reverse(),
+            (true, true) => Ordering::Equal,
+            (false, false) => {
+                let ordering = value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal);
+                if *asc {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
 pub(crate) fn create_accumulators(
     exprs: &[ScalarExpression],
 ) -> Result<Vec<Box<dyn Accumulator>>, DatabaseError> {
@@ -6,6 +6,7 @@ use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use ahash::RandomState;
 use std::collections::HashSet;
+use std::mem;
 
 pub struct SumAccumulator {
     result: DataValue,
@@ -39,10 +40,16 @@ impl Accumulator for SumAccumulator {
     fn evaluate(&self) -> Result<DataValue, DatabaseError> {
         Ok(self.result.clone())
     }
+
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>() + self.result.estimated_size()
+    }
 }
 
 pub struct DistinctSumAccumulator {
     distinct_values: HashSet<DataValue, RandomState>,
+    // bytes of the distinct values
+    values_size: usize,
     inner: SumAccumulator,
 }
 
@@ -50,6 +57,7 @@ impl DistinctSumAccumulator {
     pub fn new(ty: &LogicalType) -> Result<Self, DatabaseError> {
         Ok(Self {
             distinct_values: HashSet::default(),
+            values_size: 0,
             inner: SumAccumulator::new(ty)?,
         })
     }
@@ -58,6 +66,7 @@ impl DistinctSumAccumulator {
 impl Accumulator for DistinctSumAccumulator {
     fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
         if !self.distinct_values.contains(value) {
+            self.values_size += value.estimated_size();
             self.distinct_values.insert(value.clone());
             self.inner.update_value(value)?;
         }
@@ -68,4 +77,8 @@ impl Accumulator for DistinctSumAccumulator {
     fn evaluate(&self) -> Result<DataValue, DatabaseError> {
         self.inner.evaluate()
     }
+
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>() + self.values_size + self.inner.estimated_size()
+    }
 }
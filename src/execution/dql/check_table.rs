@@ -0,0 +1,249 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::projection::Projection;
+use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::planner::operator::check_table::CheckTableOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::table_codec::TableCodec;
+use crate::storage::{InnerIter, StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::index::{Index, IndexMetaRef, IndexType};
+use crate::types::tuple::{Tuple, TupleId};
+use crate::types::value::{DataValue, Utf8Type};
+use sqlparser::ast::CharLengthUnits;
+use std::collections::BTreeMap;
+use std::ops::CoroutineState;
+use std::ops::{Bound, Coroutine};
+use std::pin::Pin;
+
+const MISSING: &str = "missing";
+const DANGLING: &str = "dangling";
+const MISMATCHED: &str = "mismatched";
+
+/// Verifies that every index holds exactly the entries derived from the tuples of the table,
+/// and yields a row for each entry that is missing from the index, dangling without a tuple,
+/// or pointing to a different tuple.
+///
+/// The expected entries of an index are kept in memory while its key range is walked.
+pub struct CheckTable {
+    op: CheckTableOperator,
+    input: LogicalPlan,
+}
+
+impl From<(CheckTableOperator, LogicalPlan)> for CheckTable {
+    fn from((op, input): (CheckTableOperator, LogicalPlan)) -> Self {
+        Self { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for CheckTable {
+    fn execute(
+        mut self,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let CheckTableOperator {
+                    table_name,
+                    index_metas,
+                } = self.op;
+
+                let schema = self.input.output_schema().clone();
+                let table = throw!(throw!(transaction.table(cache.0, table_name.clone()))
+                    .cloned()
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string())));
+                let mut indexes = Vec::with_capacity(index_metas.len());
+
+                for index_meta in index_metas {
+                    let column_exprs = throw!(index_meta.column_exprs(&table));
+                    indexes.push((index_meta, column_exprs, BTreeMap::new()));
+                }
+                if !indexes.is_empty() {
+                    let table_codec = unsafe { &*transaction.table_codec() };
+                    let mut coroutine = build_read(self.input, cache, transaction);
+
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        let tuple: Tuple = throw!(tuple);
+                        let Some(tuple_id) = tuple.pk.as_ref() else {
+                            continue;
+                        };
+
+                        for (index_meta, column_exprs, expected) in indexes.iter_mut() {
+                            let Some(value) = DataValue::values_to_tuple(throw!(
+                                Projection::projection(&tuple, column_exprs, &schema)
+                            )) else {
+                                continue;
+                            };
+                            let (ty, values) = if matches!(index_meta.ty, IndexType::Vector) {
+                                let list = throw!(transaction.vector_list(
+                                    table_name.as_str(),
+                                    index_meta.id,
+                                    &value
+                                ));
+                                (IndexType::Normal, list.into_iter().collect())
+                            } else {
+                                let values = index_meta
+                                    .ty
+                                    .index_entries(&value)
+                                    .unwrap_or_else(|| vec![value]);
+                                (index_meta.ty, values)
+                            };
+                            for value in values {
+                                let (key, _) = throw!(table_codec.encode_index(
+                                    table_name.as_str(),
+                                    &Index::new(index_meta.id, &value, ty),
+                                    tuple_id
+                                ));
+                                expected.insert(key.to_vec(), tuple_id.clone());
+                            }
+                        }
+                    }
+                }
+                for (index_meta, _, mut expected) in indexes {
+                    let mut problems = Vec::new();
+                    {
+                        let (min, max) = throw!(unsafe { &*transaction.table_codec() }
+                            .index_bound(table_name.as_str(), index_meta.id));
+                        let mut iter =
+                            throw!(transaction.range(Bound::Included(min), Bound::Included(max)));
+
+                        while let Some((key, value)) = throw!(iter.try_next()) {
+                            let tuple_id = throw!(TableCodec::decode_index(&value));
+
+                            match expected.remove(key.as_slice()) {
+                                Some(expected_id) if expected_id == tuple_id => (),
+                                Some(expected_id) => problems.push(Self::problem(
+                                    &index_meta,
+                                    expected_id,
+                                    MISMATCHED,
+                                )),
+                                None => {
+                                    problems.push(Self::problem(&index_meta, tuple_id, DANGLING))
+                                }
+                            }
+                        }
+                    }
+                    for tuple in problems {
+                        yield Ok(tuple);
+                    }
+                    for tuple_id in expected.into_values() {
+                        yield Ok(Self::problem(&index_meta, tuple_id, MISSING));
+                    }
+                }
+            },
+        )
+    }
+}
+
+impl CheckTable {
+    fn problem(index_meta: &IndexMetaRef, tuple_id: TupleId, problem: &str) -> Tuple {
+        let utf8 = |value: String| DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        Tuple::new(
+            None,
+            vec![
+                utf8(index_meta.name.clone()),
+                tuple_id,
+                utf8(problem.to_string()),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::storage::{Storage, Transaction};
+    use crate::types::index::Index;
+    use crate::types::tuple::Tuple;
+    use crate::types::value::DataValue;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_table() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t_check (a int primary key, b int, c int)")?
+            .done()?;
+        kite_sql
+            .run("create index b_index on t_check (b)")?
+            .done()?;
+        kite_sql
+            .run("create unique index c_index on t_check (c)")?
+            .done()?;
+        kite_sql
+            .run("insert into t_check values (0, 0, 0), (1, 1, 1), (2, 2, 2), (3, 3, 3)")?
+            .done()?;
+
+        let check = || -> Result<Vec<Vec<String>>, DatabaseError> {
+            let mut iter = kite_sql.run("check table t_check")?;
+            let tuples = iter.by_ref().collect::<Result<Vec<Tuple>, _>>()?;
+            iter.done()?;
+            Ok(tuples
+                .into_iter()
+                .map(|tuple| tuple.values.iter().map(|value| value.to_string()).collect())
+                .collect())
+        };
+        assert!(check()?.is_empty());
+
+        let transaction = kite_sql.storage.transaction()?;
+        let table = transaction
+            .table(
+                kite_sql.state.table_cache(),
+                Arc::new("t_check".to_string()),
+            )?
+            .cloned()
+            .unwrap();
+        let b_index = table
+            .indexes
+            .iter()
+            .find(|index| index.name == "b_index")
+            .unwrap();
+        let c_index = table
+            .indexes
+            .iter()
+            .find(|index| index.name == "c_index")
+            .unwrap();
+
+        transaction.del_index(
+            "t_check",
+            &Index::new(b_index.id, &DataValue::Int32(1), b_index.ty),
+            &DataValue::Int32(1),
+        )?;
+        transaction.add_index(
+            "t_check",
+            Index::new(b_index.id, &DataValue::Int32(9), b_index.ty),
+            &DataValue::Int32(9),
+        )?;
+        let (key, value) = unsafe { &*transaction.table_codec() }.encode_index(
+            "t_check",
+            &Index::new(c_index.id, &DataValue::Int32(2), c_index.ty),
+            &DataValue::Int32(3),
+        )?;
+        transaction.set(key, value)?;
+        transaction.commit()?;
+
+        assert_eq!(
+            check()?,
+            vec![
+                vec!["b_index", "9", "dangling"],
+                vec!["b_index", "1", "missing"],
+                vec!["c_index", "2", "mismatched"],
+            ]
+        );
+
+        kite_sql.run("reindex table t_check")?.done()?;
+        assert!(check()?.is_empty());
+
+        Ok(())
+    }
+}
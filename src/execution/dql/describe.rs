@@ -43,15 +43,13 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Describe {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let table = throw!(throw!(
-                    unsafe { &mut (*transaction) }.table(cache.0, self.table_name.clone())
-                )
-                .ok_or(DatabaseError::TableNotFound));
+                let table = throw!(throw!(transaction.table(cache.0, self.table_name.clone()))
+                    .ok_or_else(|| DatabaseError::TableNotFound(self.table_name.to_string())));
                 let key_fn = |column: &ColumnCatalog| {
                     if column.desc().is_primary() {
                         PRIMARY_KEY_TYPE.clone()
@@ -62,7 +60,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Describe {
                     }
                 };
 
-                for column in table.columns() {
+                for column in table.visible_columns() {
                     let datatype = column.datatype();
                     let default = column
                         .desc()
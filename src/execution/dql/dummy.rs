@@ -8,7 +8,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Dummy {
     fn execute(
         self,
         _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        _: *mut T,
+        _: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -19,7 +19,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Explain {
     fn execute(
         self,
         _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        _: *mut T,
+        _: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
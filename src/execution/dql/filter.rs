@@ -23,7 +23,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Filter {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -0,0 +1,41 @@
+use crate::execution::{Executor, ReadExecutor};
+use crate::planner::operator::foreign_scan::ForeignScanOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use itertools::Itertools;
+
+pub struct ForeignScan {
+    op: ForeignScanOperator,
+}
+
+impl From<ForeignScanOperator> for ForeignScan {
+    fn from(op: ForeignScanOperator) -> Self {
+        ForeignScan { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ForeignScan {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        _: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let ForeignScanOperator {
+                    columns,
+                    foreign,
+                    wrapper,
+                    filters,
+                    ..
+                } = self.op;
+                let columns = columns.into_values().collect_vec();
+
+                for tuple in throw!(wrapper.scan(&foreign, &columns, &filters)) {
+                    yield tuple;
+                }
+            },
+        )
+    }
+}
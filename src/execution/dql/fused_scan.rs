@@ -0,0 +1,539 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::projection::Projection;
+use crate::execution::dql::runtime_filter::RuntimeFilter;
+use crate::execution::{Executor, ReadExecutor};
+use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::planner::LogicalPlan;
+use crate::process::heavy_query_yield;
+use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::index::{IndexInfo, IndexType};
+use itertools::Itertools;
+use std::collections::Bound;
+
+/// Evaluates an optional predicate and projection directly inside the table scan,
+/// so simple `Project -> Filter -> TableScan` pipelines run in a single coroutine.
+pub(crate) struct FusedScan {
+    predicate: Option<ScalarExpression>,
+    exprs: Option<Vec<ScalarExpression>>,
+    runtime_filter: Option<RuntimeFilter>,
+    input: LogicalPlan,
+}
+
+impl
+    From<(
+        Option<ScalarExpression>,
+        Option<Vec<ScalarExpression>>,
+        LogicalPlan,
+    )> for FusedScan
+{
+    fn from(
+        (predicate, exprs, input): (
+            Option<ScalarExpression>,
+            Option<Vec<ScalarExpression>>,
+            LogicalPlan,
+        ),
+    ) -> Self {
+        debug_assert!(matches!(input.operator, Operator::TableScan(_)));
+
+        FusedScan {
+            predicate,
+            exprs,
+            runtime_filter: None,
+            input,
+        }
+    }
+}
+
+impl FusedScan {
+    /// Unpack a plan which reads a table directly, fused or not, into a `FusedScan`.
+    pub(crate) fn try_from_plan(plan: LogicalPlan) -> Result<Self, LogicalPlan> {
+        match (&plan.operator, &plan.physical_option) {
+            (_, Some(PhysicalOption::Custom(_))) => Err(plan),
+            (Operator::TableScan(_), _) => Ok(FusedScan::from((None, None, plan))),
+            (Operator::Filter(_), Some(PhysicalOption::FusedFilter)) => {
+                let LogicalPlan {
+                    operator: Operator::Filter(op),
+                    childrens,
+                    ..
+                } = plan
+                else {
+                    unreachable!()
+                };
+                Ok(FusedScan::from((
+                    Some(op.predicate),
+                    None,
+                    childrens.pop_only(),
+                )))
+            }
+            (Operator::Project(_), Some(PhysicalOption::FusedProject)) => {
+                let LogicalPlan {
+                    operator: Operator::Project(op),
+                    childrens,
+                    ..
+                } = plan
+                else {
+                    unreachable!()
+                };
+                let (predicate, input) = match childrens.pop_only() {
+                    LogicalPlan {
+                        operator: Operator::Filter(filter_op),
+                        childrens,
+                        ..
+                    } => (Some(filter_op.predicate), childrens.pop_only()),
+                    input => (None, input),
+                };
+                Ok(FusedScan::from((predicate, Some(op.exprs), input)))
+            }
+            _ => Err(plan),
+        }
+    }
+
+    /// Returns false and drops the filter if its keys can not be evaluated on the scanned tuples.
+    pub(crate) fn push_runtime_filter(&mut self, runtime_filter: RuntimeFilter) -> bool {
+        // Tips: without projection the scanned tuples are exactly the probe tuples
+        if self.exprs.is_some() && !runtime_filter.is_applicable(self.input.output_schema()) {
+            return false;
+        }
+        self.runtime_filter = Some(runtime_filter);
+        true
+    }
+
+    /// Narrow a scan without index range down to `[min, max]` of the build side join keys,
+    /// if the key column is indexed and the statistics show the range is worth an index scan.
+    pub(crate) fn tighten_range<T: Transaction>(
+        &mut self,
+        runtime_filter: &RuntimeFilter,
+        loader: &StatisticMetaLoader<'_, T>,
+    ) -> Result<bool, DatabaseError> {
+        if matches!(
+            self.input.physical_option,
+            Some(
+                PhysicalOption::IndexScan(IndexInfo { range: Some(_), .. })
+                    | PhysicalOption::MultiGet(_)
+                    | PhysicalOption::SkipScan(_)
+            )
+        ) {
+            return Ok(false);
+        }
+        let (Some((min, max)), Some(column)) =
+            (runtime_filter.bounds(), runtime_filter.key_column())
+        else {
+            return Ok(false);
+        };
+        let Operator::TableScan(scan_op) = &self.input.operator else {
+            unreachable!()
+        };
+        let Some(column_id) = column.id().filter(|_| {
+            scan_op
+                .columns
+                .values()
+                .any(|scan_column| scan_column.summary() == column.summary())
+        }) else {
+            return Ok(false);
+        };
+        let Some(meta) = scan_op
+            .index_infos
+            .iter()
+            .find(|index_info| index_info.meta.column_ids == [column_id])
+            .map(|index_info| index_info.meta.clone())
+        else {
+            return Ok(false);
+        };
+        let (Ok(min), Ok(max)) = (
+            min.clone().cast(&meta.value_ty),
+            max.clone().cast(&meta.value_ty),
+        ) else {
+            return Ok(false);
+        };
+        let range = Range::Scope {
+            min: Bound::Included(min),
+            max: Bound::Included(max),
+        };
+        let is_primary_key = matches!(meta.ty, IndexType::PrimaryKey { .. });
+
+        let is_selective = match loader.load(&scan_op.table_name, meta.id)? {
+            Some(statistics_meta) => {
+                let mut row_count = statistics_meta.collect_count(&range)?;
+
+                if !is_primary_key {
+                    // need to return table query(non-covering index)
+                    row_count *= 2;
+                }
+                row_count < statistics_meta.histogram().values_len()
+            }
+            // Tips: a range of the primary key never reads more than the full scan
+            None => is_primary_key,
+        };
+        if is_selective {
+            self.input.physical_option = Some(PhysicalOption::IndexScan(IndexInfo {
+                meta,
+                range: Some(range),
+                skip_range: None,
+            }));
+        }
+        Ok(is_selective)
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for FusedScan {
+    fn execute(
+        self,
+        (table_cache, _, meta_cache): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let FusedScan {
+                    predicate,
+                    exprs,
+                    runtime_filter,
+                    mut input,
+                } = self;
+
+                let schema = input.output_schema().clone();
+                let LogicalPlan {
+                    operator,
+                    physical_option,
+                    ..
+                } = input;
+                let Operator::TableScan(TableScanOperator {
+                    table_name,
+                    columns,
+                    limit,
+                    with_pk,
+                    reverse,
+                    ..
+                }) = operator
+                else {
+                    unreachable!()
+                };
+
+                // the index range fully read, whose row count corrects its estimate
+                let mut feedback = None;
+                let mut iter: Box<dyn Iter + 'a> = match physical_option {
+                    Some(PhysicalOption::IndexScan(IndexInfo {
+                        meta,
+                        range: Some(range),
+                        ..
+                    })) => {
+                        if matches!(limit, (None, None)) {
+                            feedback = Some((table_name.clone(), meta.id, range.clone()));
+                        }
+                        let ranges = match range {
+                            Range::SortedRanges(ranges) => ranges,
+                            range => vec![range],
+                        };
+                        let iter = throw!(transaction.read_by_index(
+                            table_cache,
+                            table_name,
+                            limit,
+                            columns,
+                            meta,
+                            ranges,
+                            with_pk,
+                        ));
+                        Box::new(if reverse { iter.reverse() } else { iter })
+                    }
+                    Some(PhysicalOption::MultiGet(IndexInfo {
+                        meta,
+                        range: Some(range),
+                        ..
+                    })) => {
+                        let mut keys = match range {
+                            Range::SortedRanges(ranges) => ranges,
+                            range => vec![range],
+                        }
+                        .into_iter()
+                        .filter_map(|range| match range {
+                            Range::Eq(key) => Some(key),
+                            _ => None,
+                        })
+                        .collect_vec();
+                        if reverse {
+                            keys.reverse();
+                        }
+                        Box::new(throw!(transaction.read_by_keys(
+                            table_cache,
+                            table_name,
+                            limit,
+                            columns,
+                            meta,
+                            keys,
+                            with_pk,
+                        )))
+                    }
+                    Some(PhysicalOption::SkipScan(IndexInfo {
+                        meta,
+                        skip_range: Some(range),
+                        ..
+                    })) => Box::new(throw!(transaction.read_by_skip(
+                        table_cache,
+                        table_name,
+                        limit,
+                        columns,
+                        meta,
+                        range,
+                        with_pk,
+                    ))),
+                    _ => Box::new(throw!(transaction.read(
+                        table_cache,
+                        table_name,
+                        limit,
+                        columns,
+                        with_pk,
+                        reverse
+                    ))),
+                };
+
+                let mut row_count = 0;
+
+                while let Some(tuple) = throw!(iter.next_tuple()) {
+                    heavy_query_yield();
+                    row_count += 1;
+                    if let Some(runtime_filter) = &runtime_filter {
+                        if !throw!(runtime_filter.check(&tuple, &schema)) {
+                            continue;
+                        }
+                    }
+                    if let Some(predicate) = &predicate {
+                        if !throw!(throw!(predicate.eval(Some((&tuple, &schema)))).is_true()) {
+                            continue;
+                        }
+                    }
+                    if let Some(exprs) = &exprs {
+                        yield Projection::projection_owned(tuple, exprs, &schema);
+                    } else {
+                        yield Ok(tuple);
+                    }
+                }
+                drop(iter);
+
+                if let Some((table_name, index_id, range)) = feedback {
+                    throw!(StatisticMetaLoader::new(transaction, meta_cache).feedback(
+                        &table_name,
+                        index_id,
+                        &range,
+                        row_count
+                    ));
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, Database, ResultIter, State};
+    use crate::errors::DatabaseError;
+    use crate::execution::dql::fused_scan::FusedScan;
+    use crate::execution::dql::runtime_filter::RuntimeFilter;
+    use crate::execution::{build_read, try_collect, ReadExecutor};
+    use crate::expression::ScalarExpression;
+    use crate::planner::operator::PhysicalOption;
+    use crate::planner::{Childrens, LogicalPlan};
+    use crate::storage::rocksdb::{RocksStorage, RocksTransaction};
+    use crate::storage::{Storage, Transaction};
+    use crate::types::value::DataValue;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    fn best_plan(
+        kite_sql: &Database<RocksStorage>,
+        transaction: &RocksTransaction,
+        sql: &str,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let state = &kite_sql.state;
+        let statement = kite_sql.prepare(sql)?;
+        let source_plan = state.bind_plan(
+            transaction,
+            (state.table_cache(), state.view_cache(), state.meta_cache()),
+            &statement,
+            [],
+            None,
+            None,
+        )?;
+
+        State::<RocksStorage>::default_optimizer(source_plan, &Default::default())
+            .find_best(Some(&transaction.meta_loader(state.meta_cache())))
+    }
+
+    // the same plan run by the standalone operators
+    fn unfuse(plan: &mut LogicalPlan) {
+        match plan.physical_option {
+            Some(PhysicalOption::FusedFilter) => {
+                plan.physical_option = Some(PhysicalOption::Filter)
+            }
+            Some(PhysicalOption::FusedProject) => {
+                plan.physical_option = Some(PhysicalOption::Project)
+            }
+            _ => (),
+        }
+        match plan.childrens.as_mut() {
+            Childrens::None => (),
+            Childrens::Only(child) => unfuse(child),
+            Childrens::Twins { left, right } => {
+                unfuse(left);
+                unfuse(right);
+            }
+        }
+    }
+
+    fn execute(
+        kite_sql: &Database<RocksStorage>,
+        transaction: &RocksTransaction,
+        plan: LogicalPlan,
+    ) -> Result<Vec<Vec<DataValue>>, DatabaseError> {
+        let state = &kite_sql.state;
+        let tuples = try_collect(build_read(
+            plan,
+            (state.table_cache(), state.view_cache(), state.meta_cache()),
+            transaction,
+        ))?;
+
+        Ok(tuples.into_iter().map(|tuple| tuple.values).collect_vec())
+    }
+
+    fn build_tables() -> Result<(TempDir, Database<RocksStorage>), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t1 (a int primary key, b int, c varchar)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values (0, 0, 'a'), (1, null, 'b'), (2, 2, null), (3, 3, 'd'), (4, null, null), (5, 5, 'f')")?
+            .done()?;
+        kite_sql
+            .run("create table t2 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t2 values (0, 2), (1, null), (2, 3), (3, 9)")?
+            .done()?;
+
+        Ok((temp_dir, kite_sql))
+    }
+
+    #[test]
+    fn test_fused_same_as_unfused() -> Result<(), DatabaseError> {
+        let (_temp_dir, kite_sql) = build_tables()?;
+        let transaction = kite_sql.storage.transaction()?;
+
+        let assert_same = |mut plan: LogicalPlan,
+                           option: PhysicalOption|
+         -> Result<Vec<Vec<DataValue>>, DatabaseError> {
+            assert_eq!(plan.physical_option, Some(option));
+            let fused = execute(&kite_sql, &transaction, plan.clone())?;
+            unfuse(&mut plan);
+            assert_eq!(fused, execute(&kite_sql, &transaction, plan)?);
+
+            Ok(fused)
+        };
+
+        // Filter -> TableScan
+        let plan = best_plan(&kite_sql, &transaction, "select * from t1 where b > 1")?
+            .childrens
+            .pop_only();
+        let rows = assert_same(plan, PhysicalOption::FusedFilter)?;
+        assert_eq!(rows.len(), 3);
+
+        // Project -> TableScan
+        let plan = best_plan(&kite_sql, &transaction, "select a, b + 1, c from t1")?;
+        let rows = assert_same(plan, PhysicalOption::FusedProject)?;
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[1][..2], [DataValue::Int32(1), DataValue::Null]);
+        assert_eq!(rows[2][2], DataValue::Null);
+
+        // Project -> Filter -> TableScan
+        let plan = best_plan(
+            &kite_sql,
+            &transaction,
+            "select a, c from t1 where b > 1 or c is null",
+        )?;
+        let rows = assert_same(plan, PhysicalOption::FusedProject)?;
+        assert_eq!(
+            rows.iter().map(|row| row[0].clone()).collect_vec(),
+            vec![
+                DataValue::Int32(2),
+                DataValue::Int32(3),
+                DataValue::Int32(4),
+                DataValue::Int32(5)
+            ]
+        );
+
+        // a filter dropping every row
+        let plan = best_plan(&kite_sql, &transaction, "select a from t1 where b > 100")?;
+        assert!(assert_same(plan, PhysicalOption::FusedProject)?.is_empty());
+
+        // fused, the probe side of the hash join also reads through the runtime filter
+        let plan = best_plan(
+            &kite_sql,
+            &transaction,
+            "select t1.a, t2.a from t2 join t1 on t1.b = t2.b where t1.c is not null or t1.a = 4",
+        )?;
+        let rows = assert_same(plan, PhysicalOption::Project)?;
+        assert_eq!(rows, vec![vec![DataValue::Int32(3), DataValue::Int32(2)]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fused_runtime_filter() -> Result<(), DatabaseError> {
+        let (_temp_dir, kite_sql) = build_tables()?;
+        let transaction = kite_sql.storage.transaction()?;
+        let state = &kite_sql.state;
+        let caches = (state.table_cache(), state.view_cache(), state.meta_cache());
+
+        for (sql, build_keys) in [
+            ("select a, b from t1 where c is not null", vec![2, 3, 5]),
+            ("select a, b from t1 where c is not null", vec![100]),
+            ("select a, b from t1", vec![0, 2]),
+        ] {
+            let mut plan = best_plan(&kite_sql, &transaction, sql)?;
+            let mut unfused = plan.clone();
+            unfuse(&mut unfused);
+            let expected = execute(&kite_sql, &transaction, unfused)?;
+
+            let b = plan
+                .output_schema()
+                .iter()
+                .find(|column| column.name() == "b")
+                .cloned()
+                .unwrap();
+            let build_keys = build_keys
+                .into_iter()
+                .map(|key| vec![DataValue::Int32(key)])
+                .collect_vec();
+            let runtime_filter = RuntimeFilter::new(
+                vec![ScalarExpression::ColumnRef(b)],
+                build_keys.len(),
+                build_keys.iter().map(Vec::as_slice),
+            );
+            let Ok(mut fused) = FusedScan::try_from_plan(plan) else {
+                unreachable!()
+            };
+            assert!(fused.push_runtime_filter(runtime_filter));
+            let rows = try_collect(fused.execute(caches, &transaction))?
+                .into_iter()
+                .map(|tuple| tuple.values)
+                .collect_vec();
+
+            // the filter may keep a few rows the join drops, but none it keeps and no null key
+            let matched = |row: &&Vec<DataValue>| build_keys.contains(&vec![row[1].clone()]);
+            assert!(rows
+                .iter()
+                .all(|row| expected.contains(row) && !row[1].is_null()));
+            assert_eq!(
+                rows.iter().filter(matched).collect_vec(),
+                expected.iter().filter(matched).collect_vec()
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
+use crate::execution::dql::sample::Sampler;
 use crate::execution::{Executor, ReadExecutor};
 use crate::expression::range_detacher::Range;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
 use crate::planner::operator::table_scan::TableScanOperator;
+use crate::process::heavy_query_yield;
 use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::index::IndexMetaRef;
@@ -8,20 +11,15 @@ use crate::types::index::IndexMetaRef;
 pub(crate) struct IndexScan {
     op: TableScanOperator,
     index_by: IndexMetaRef,
-    ranges: Vec<Range>,
+    range: Range,
 }
 
 impl From<(TableScanOperator, IndexMetaRef, Range)> for IndexScan {
     fn from((op, index_by, range): (TableScanOperator, IndexMetaRef, Range)) -> Self {
-        let ranges = match range {
-            Range::SortedRanges(ranges) => ranges,
-            range => vec![range],
-        };
-
         IndexScan {
             op,
             index_by,
-            ranges,
+            range,
         }
     }
 }
@@ -29,8 +27,8 @@ impl From<(TableScanOperator, IndexMetaRef, Range)> for IndexScan {
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexScan {
     fn execute(
         self,
-        (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        (table_cache, _, meta_cache): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -40,22 +38,51 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexScan {
                     columns,
                     limit,
                     with_pk,
+                    sample,
+                    reverse,
                     ..
                 } = self.op;
+                let mut sampler = sample.map(Sampler::from);
+                // a scan stopped by its limit reads a part of the range only
+                let is_full = matches!(limit, (None, None));
+                let index_id = self.index_by.id;
+                let ranges = match self.range.clone() {
+                    Range::SortedRanges(ranges) => ranges,
+                    range => vec![range],
+                };
 
-                let mut iter = throw!(unsafe { &(*transaction) }.read_by_index(
+                let mut iter = throw!(transaction.read_by_index(
                     table_cache,
-                    table_name,
+                    table_name.clone(),
                     limit,
                     columns,
                     self.index_by,
-                    self.ranges,
+                    ranges,
                     with_pk,
                 ));
+                if reverse {
+                    iter = iter.reverse();
+                }
+                let mut row_count = 0;
 
                 while let Some(tuple) = throw!(iter.next_tuple()) {
+                    heavy_query_yield();
+                    row_count += 1;
+                    if sampler.as_mut().is_some_and(|sampler| !sampler.keep()) {
+                        continue;
+                    }
                     yield Ok(tuple);
                 }
+                drop(iter);
+
+                if is_full {
+                    throw!(StatisticMetaLoader::new(transaction, meta_cache).feedback(
+                        &table_name,
+                        index_id,
+                        &self.range,
+                        row_count
+                    ));
+                }
             },
         )
     }
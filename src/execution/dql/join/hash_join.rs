@@ -1,15 +1,19 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::execution::dql::fused_scan::FusedScan;
 use crate::execution::dql::join::joins_nullable;
+use crate::execution::dql::key_map::KeyMap;
+use crate::execution::dql::runtime_filter::RuntimeFilter;
 use crate::execution::{build_read, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
 use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::tuple::{Schema, Tuple};
 use crate::types::value::{DataValue, NULL_VALUE};
-use ahash::{HashMap, HashMapExt};
+use crate::types::LogicalType;
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use std::ops::Coroutine;
@@ -54,6 +58,37 @@ impl HashJoin {
         Ok(values)
     }
 
+    /// Push a Bloom filter and the min/max of the build side keys into the probe side scan if possible.
+    fn build_probe<'a, T: Transaction + 'a>(
+        mut probe_input: LogicalPlan,
+        probe_keys: &[ScalarExpression],
+        ty: &JoinType,
+        build_map: &KeyMap<(Vec<Tuple>, bool, bool)>,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Result<Executor<'a>, DatabaseError> {
+        // Tips: RightOuter and Full join still output the probe tuples that match nothing
+        if matches!(
+            ty,
+            JoinType::Inner | JoinType::LeftOuter | JoinType::LeftSemi | JoinType::LeftAnti
+        ) {
+            match FusedScan::try_from_plan(probe_input) {
+                Ok(mut scan) => {
+                    let runtime_filter =
+                        RuntimeFilter::new(probe_keys.to_vec(), build_map.len(), build_map.keys());
+                    let loader = StatisticMetaLoader::new(transaction, cache.2);
+
+                    scan.tighten_range(&runtime_filter, &loader)?;
+                    scan.push_runtime_filter(runtime_filter);
+
+                    return Ok(scan.execute(cache, transaction));
+                }
+                Err(plan) => probe_input = plan,
+            }
+        }
+        Ok(build_read(probe_input, cache, transaction))
+    }
+
     pub(crate) fn filter(
         mut tuple: Tuple,
         schema: &Schema,
@@ -93,7 +128,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -147,9 +182,21 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
                 // 1.construct hashtable, one hash key may contains multiple rows indices.
                 // 2.merged all left tuples.
                 let mut coroutine = build_read(left_input, cache, transaction);
-                let mut build_map = HashMap::new();
-                let build_map_ptr: *mut HashMap<Vec<DataValue>, (Vec<Tuple>, bool, bool)> =
-                    &mut build_map;
+                let key_types = on_left_keys
+                    .iter()
+                    .zip(on_right_keys.iter())
+                    .map(|(left_key, right_key)| {
+                        let ty = left_key.return_type();
+                        // Tips: keys of different types must not be widened into the same integer
+                        if ty == right_key.return_type() {
+                            ty
+                        } else {
+                            LogicalType::SqlNull
+                        }
+                    })
+                    .collect_vec();
+                let mut build_map = KeyMap::new(&key_types);
+                let build_map_ptr: *mut KeyMap<(Vec<Tuple>, bool, bool)> = &mut build_map;
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let tuple: Tuple = throw!(tuple);
@@ -161,15 +208,21 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
 
                     unsafe {
                         (*build_map_ptr)
-                            .entry(values)
-                            .or_insert_with(|| (Vec::new(), false, false))
+                            .get_or_insert_with(values, || (Vec::new(), false, false))
                             .0
                             .push(tuple);
                     }
                 }
 
                 // probe phase
-                let mut coroutine = build_read(right_input, cache, transaction);
+                let mut coroutine = throw!(Self::build_probe(
+                    right_input,
+                    &on_right_keys,
+                    &ty,
+                    &build_map,
+                    cache,
+                    transaction,
+                ));
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let tuple: Tuple = throw!(tuple);
@@ -251,7 +304,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
                 // left drop
                 match ty {
                     JoinType::LeftOuter | JoinType::Full => {
-                        for (_, (left_tuples, is_used, _)) in build_map {
+                        for (_, (left_tuples, is_used, _)) in build_map.into_entries() {
                             if is_used {
                                 continue;
                             }
@@ -266,7 +319,8 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
                     JoinType::LeftSemi | JoinType::LeftAnti => {
                         let is_left_semi = matches!(ty, JoinType::LeftSemi);
 
-                        for (_, (left_tuples, mut is_used, is_filtered)) in build_map {
+                        for (_, (left_tuples, mut is_used, is_filtered)) in build_map.into_entries()
+                        {
                             if is_left_semi {
                                 is_used = !is_used;
                             }
@@ -410,7 +464,7 @@ mod test {
     fn test_inner_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -424,7 +478,7 @@ mod test {
             join_type: JoinType::Inner,
         };
         let executor = HashJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         assert_eq!(tuples.len(), 3);
@@ -449,7 +503,7 @@ mod test {
     fn test_left_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -466,7 +520,7 @@ mod test {
         {
             let executor = HashJoin::from((op.clone(), left.clone(), right.clone()));
             let tuples = try_collect(
-                executor.execute((&table_cache, &view_cache, &meta_cache), &mut transaction),
+                executor.execute((&table_cache, &view_cache, &meta_cache), &transaction),
             )?;
 
             assert_eq!(tuples.len(), 4);
@@ -493,7 +547,7 @@ mod test {
             let mut executor = HashJoin::from((op.clone(), left.clone(), right.clone()));
             executor.ty = JoinType::LeftSemi;
             let mut tuples = try_collect(
-                executor.execute((&table_cache, &view_cache, &meta_cache), &mut transaction),
+                executor.execute((&table_cache, &view_cache, &meta_cache), &transaction),
             )?;
 
             let arena = Bump::new();
@@ -518,7 +572,7 @@ mod test {
             let mut executor = HashJoin::from((op, left, right));
             executor.ty = JoinType::LeftAnti;
             let tuples = try_collect(
-                executor.execute((&table_cache, &view_cache, &meta_cache), &mut transaction),
+                executor.execute((&table_cache, &view_cache, &meta_cache), &transaction),
             )?;
 
             assert_eq!(tuples.len(), 1);
@@ -535,7 +589,7 @@ mod test {
     fn test_right_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -549,7 +603,7 @@ mod test {
             join_type: JoinType::RightOuter,
         };
         let executor = HashJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         assert_eq!(tuples.len(), 4);
@@ -578,7 +632,7 @@ mod test {
     fn test_full_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -592,7 +646,7 @@ mod test {
             join_type: JoinType::Full,
         };
         let executor = HashJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         assert_eq!(tuples.len(), 5);
@@ -4,6 +4,7 @@
 use super::joins_nullable;
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::execution::dql::key_map::KeyMap;
 use crate::execution::dql::projection::Projection;
 use crate::execution::{build_read, Executor, ReadExecutor};
 use crate::expression::ScalarExpression;
@@ -63,8 +64,20 @@ impl EqualCondition {
 
         Ok(left_values == right_values)
     }
+
+    fn left_values(&self, left_tuple: &Tuple) -> Result<Vec<DataValue>, DatabaseError> {
+        Projection::projection(left_tuple, &self.on_left_keys, &self.left_schema)
+    }
+
+    fn right_values(&self, right_tuple: &Tuple) -> Result<Vec<DataValue>, DatabaseError> {
+        Projection::projection(right_tuple, &self.on_right_keys, &self.right_schema)
+    }
 }
 
+/// Once a complete pass over the inner table yields at least this many tuples,
+/// the inner tuples are hashed by the equivalent condition instead of being rescanned.
+const ADAPTIVE_HASH_THRESHOLD: usize = 1024;
+
 /// NestedLoopJoin using nested loop join algorithm to execute a join operation.
 /// One input will be selected to be the inner table and the other will be the outer
 /// | JoinType                       |  Inner-table   |   Outer-table  |
@@ -74,6 +87,9 @@ impl EqualCondition {
 /// | Right/RightSemi/RightAnti/Full |    left        |      right     |
 /// |--------------------------------|----------------|----------------|
 /// | Full                           |    left        |      right     |
+///
+/// When there is an equivalent condition and the inner table turns out to be large,
+/// the executor switches to probing a hash table of the inner tuples built mid-flight.
 pub struct NestedLoopJoin {
     left_input: LogicalPlan,
     right_input: LogicalPlan,
@@ -81,6 +97,7 @@ pub struct NestedLoopJoin {
     ty: JoinType,
     filter: Option<ScalarExpression>,
     eq_cond: EqualCondition,
+    hash_threshold: usize,
 }
 
 impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for NestedLoopJoin {
@@ -121,6 +138,7 @@ impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for NestedLoopJoin {
             output_schema_ref,
             filter,
             eq_cond,
+            hash_threshold: ADAPTIVE_HASH_THRESHOLD,
         }
     }
 }
@@ -129,7 +147,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -141,13 +159,18 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
                     output_schema_ref,
                     filter,
                     eq_cond,
-                    ..
+                    hash_threshold,
                 } = self;
 
                 let right_schema_len = eq_cond.right_schema.len();
                 let mut left_coroutine = build_read(left_input, cache, transaction);
                 let mut bitmap: Option<FixedBitSet> = None;
                 let mut first_matches = Vec::new();
+                // Tips: Full join marks the matched inner tuples by their scan position
+                let mut is_adaptive = !eq_cond.on_left_keys.is_empty()
+                    && !matches!(ty, JoinType::Full | JoinType::Cross);
+                let mut inner_tuples = Vec::new();
+                let mut hash_table: Option<KeyMap<Vec<usize>>> = None;
 
                 while let CoroutineState::Yielded(left_tuple) =
                     Pin::new(&mut left_coroutine).resume(())
@@ -155,13 +178,46 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
                     let left_tuple: Tuple = throw!(left_tuple);
                     let mut has_matched = false;
 
-                    let mut right_coroutine = build_read(right_input.clone(), cache, transaction);
+                    let (mut right_coroutine, mut candidates) = match hash_table.as_mut() {
+                        Some(hash_table) => {
+                            let left_values = throw!(eq_cond.left_values(&left_tuple));
+                            let candidates = hash_table
+                                .get_mut(&left_values)
+                                .cloned()
+                                .unwrap_or_default();
+                            (None, candidates.into_iter())
+                        }
+                        None => (
+                            Some(build_read(right_input.clone(), cache, transaction)),
+                            Vec::new().into_iter(),
+                        ),
+                    };
+                    let mut is_scanned = false;
                     let mut right_idx = 0;
+                    if is_adaptive {
+                        inner_tuples.clear();
+                    }
 
-                    while let CoroutineState::Yielded(right_tuple) =
-                        Pin::new(&mut right_coroutine).resume(())
-                    {
-                        let right_tuple: Tuple = throw!(right_tuple);
+                    loop {
+                        let right_tuple = match right_coroutine.as_mut() {
+                            Some(right_coroutine) => match Pin::new(right_coroutine).resume(()) {
+                                CoroutineState::Yielded(right_tuple) => {
+                                    let right_tuple: Tuple = throw!(right_tuple);
+                                    if is_adaptive {
+                                        inner_tuples.push(right_tuple.clone());
+                                    }
+                                    right_tuple
+                                }
+                                CoroutineState::Complete(()) => {
+                                    is_scanned = true;
+                                    break;
+                                }
+                            },
+                            None => match candidates.next() {
+                                Some(i) => inner_tuples[i].clone(),
+                                None => break,
+                            },
+                        };
 
                         let tuple = match (
                             filter.as_ref(),
@@ -226,6 +282,25 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
                         }
                         right_idx += 1;
                     }
+                    if is_adaptive && is_scanned {
+                        if inner_tuples.len() >= hash_threshold {
+                            let mut table = KeyMap::new(
+                                &eq_cond
+                                    .on_right_keys
+                                    .iter()
+                                    .map(ScalarExpression::return_type)
+                                    .collect_vec(),
+                            );
+                            for (i, right_tuple) in inner_tuples.iter().enumerate() {
+                                let right_values = throw!(eq_cond.right_values(right_tuple));
+                                table.get_or_insert_with(right_values, Vec::new).push(i);
+                            }
+                            hash_table = Some(table);
+                        } else {
+                            inner_tuples = Vec::new();
+                        }
+                        is_adaptive = false;
+                    }
 
                     if matches!(self.ty, JoinType::Full) && bitmap.is_none() {
                         bitmap = Some(FixedBitSet::with_capacity(right_idx));
@@ -539,11 +614,65 @@ mod test {
         assert!(expected.is_empty());
     }
 
+    #[test]
+    fn test_nested_adaptive_hash_join() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let transaction = storage.transaction()?;
+        let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+        let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+
+        for join_type in [
+            JoinType::Inner,
+            JoinType::LeftOuter,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightOuter,
+        ] {
+            for with_filter in [false, true] {
+                let fn_execute = |hash_threshold: usize, transaction: &_| {
+                    let (keys, left, right, filter) = build_join_values(true);
+                    let op = JoinOperator {
+                        on: JoinCondition::On {
+                            on: keys,
+                            filter: with_filter.then_some(filter),
+                        },
+                        join_type,
+                    };
+                    let mut executor = NestedLoopJoin::from((op, left, right));
+                    executor.hash_threshold = hash_threshold;
+
+                    try_collect(
+                        executor.execute((&table_cache, &view_cache, &meta_cache), transaction),
+                    )
+                    .map(|tuples| {
+                        tuples
+                            .into_iter()
+                            .map(|tuple| format!("{:?}", tuple.values))
+                            .sorted()
+                            .collect_vec()
+                    })
+                };
+                // Tips: threshold 0 switches to the hash table right after the first pass
+                assert_eq!(
+                    fn_execute(0, &transaction)?,
+                    fn_execute(usize::MAX, &transaction)?,
+                    "{:?} with filter: {}",
+                    join_type,
+                    with_filter
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_nested_inner_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -556,7 +685,7 @@ mod test {
             join_type: JoinType::Inner,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(1);
@@ -572,7 +701,7 @@ mod test {
     fn test_nested_left_out_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -585,7 +714,7 @@ mod test {
             join_type: JoinType::LeftOuter,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         assert_eq!(
@@ -613,7 +742,7 @@ mod test {
     fn test_nested_cross_join_with_on() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -626,7 +755,7 @@ mod test {
             join_type: JoinType::Cross,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(1);
@@ -643,7 +772,7 @@ mod test {
     fn test_nested_cross_join_without_filter() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -656,7 +785,7 @@ mod test {
             join_type: JoinType::Cross,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(3);
@@ -676,7 +805,7 @@ mod test {
     fn test_nested_cross_join_without_on() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -689,7 +818,7 @@ mod test {
             join_type: JoinType::Cross,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         assert_eq!(tuples.len(), 16);
@@ -701,7 +830,7 @@ mod test {
     fn test_nested_left_semi_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -714,7 +843,7 @@ mod test {
             join_type: JoinType::LeftSemi,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(1);
@@ -729,7 +858,7 @@ mod test {
     fn test_nested_left_anti_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -742,7 +871,7 @@ mod test {
             join_type: JoinType::LeftAnti,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(3);
@@ -759,7 +888,7 @@ mod test {
     fn test_nested_right_out_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -772,7 +901,7 @@ mod test {
             join_type: JoinType::RightOuter,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         let mut expected_set = HashSet::with_capacity(4);
@@ -794,7 +923,7 @@ mod test {
     fn test_nested_full_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
@@ -807,7 +936,7 @@ mod test {
             join_type: JoinType::Full,
         };
         let executor = NestedLoopJoin::from((op, left, right))
-            .execute((&table_cache, &view_cache, &meta_cache), &mut transaction);
+            .execute((&table_cache, &view_cache, &meta_cache), &transaction);
         let tuples = try_collect(executor)?;
 
         assert_eq!(
@@ -0,0 +1,164 @@
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use ahash::{HashMap, HashMapExt};
+use std::collections::hash_map::Entry;
+use std::convert::Infallible;
+
+/// Hash table keyed by the evaluated key values of HashAgg and HashJoin.
+///
+/// A single fixed-width integer key is hashed directly as `u64` instead of hashing
+/// the whole `Vec<DataValue>`; keys that can not be widened (e.g. `NULL`) fall back to `others`.
+pub(crate) enum KeyMap<V> {
+    Integer {
+        map: HashMap<u64, (DataValue, V)>,
+        others: HashMap<Vec<DataValue>, V>,
+    },
+    Generic(HashMap<Vec<DataValue>, V>),
+}
+
+impl<V> KeyMap<V> {
+    pub(crate) fn new(key_types: &[LogicalType]) -> Self {
+        match key_types {
+            [ty] if ty.is_signed_numeric() || ty.is_unsigned_numeric() => KeyMap::Integer {
+                map: HashMap::new(),
+                others: HashMap::new(),
+            },
+            _ => KeyMap::Generic(HashMap::new()),
+        }
+    }
+
+    fn integer_key(keys: &[DataValue]) -> Option<u64> {
+        let [key] = keys else {
+            return None;
+        };
+        Some(match key {
+            DataValue::Int8(v) => *v as i64 as u64,
+            DataValue::Int16(v) => *v as i64 as u64,
+            DataValue::Int32(v) => *v as i64 as u64,
+            DataValue::Int64(v) => *v as u64,
+            DataValue::UInt8(v) => *v as u64,
+            DataValue::UInt16(v) => *v as u64,
+            DataValue::UInt32(v) => *v as u64,
+            DataValue::UInt64(v) => *v,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn get_or_insert_with(
+        &mut self,
+        keys: Vec<DataValue>,
+        default: impl FnOnce() -> V,
+    ) -> &mut V {
+        match self.get_or_try_insert_with(keys, || Ok::<_, Infallible>(default())) {
+            Ok(value) => value,
+            Err(err) => match err {},
+        }
+    }
+
+    pub(crate) fn get_or_try_insert_with<E>(
+        &mut self,
+        mut keys: Vec<DataValue>,
+        default: impl FnOnce() -> Result<V, E>,
+    ) -> Result<&mut V, E> {
+        Ok(match self {
+            KeyMap::Integer { map, others } => match Self::integer_key(&keys) {
+                Some(key) => match map.entry(key) {
+                    Entry::Occupied(entry) => &mut entry.into_mut().1,
+                    Entry::Vacant(entry) => &mut entry.insert((keys.swap_remove(0), default()?)).1,
+                },
+                None => match others.entry(keys) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(default()?),
+                },
+            },
+            KeyMap::Generic(map) => match map.entry(keys) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(default()?),
+            },
+        })
+    }
+
+    pub(crate) fn get_mut(&mut self, keys: &[DataValue]) -> Option<&mut V> {
+        match self {
+            KeyMap::Integer { map, others } => match Self::integer_key(keys) {
+                Some(key) => map.get_mut(&key).map(|(_, value)| value),
+                None => others.get_mut(keys),
+            },
+            KeyMap::Generic(map) => map.get_mut(keys),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            KeyMap::Integer { map, others } => map.len() + others.len(),
+            KeyMap::Generic(map) => map.len(),
+        }
+    }
+
+    pub(crate) fn keys(&self) -> Box<dyn Iterator<Item = &[DataValue]> + '_> {
+        match self {
+            KeyMap::Integer { map, others } => Box::new(
+                map.values()
+                    .map(|(key, _)| std::slice::from_ref(key))
+                    .chain(others.keys().map(Vec::as_slice)),
+            ),
+            KeyMap::Generic(map) => Box::new(map.keys().map(Vec::as_slice)),
+        }
+    }
+
+    pub(crate) fn into_entries(self) -> Box<dyn Iterator<Item = (Vec<DataValue>, V)>>
+    where
+        V: 'static,
+    {
+        match self {
+            KeyMap::Integer { map, others } => Box::new(
+                map.into_values()
+                    .map(|(key, value)| (vec![key], value))
+                    .chain(others),
+            ),
+            KeyMap::Generic(map) => Box::new(map.into_iter()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::execution::dql::key_map::KeyMap;
+    use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_integer_key_map() {
+        let mut key_map = KeyMap::new(&[LogicalType::Integer]);
+        assert!(matches!(key_map, KeyMap::Integer { .. }));
+
+        *key_map.get_or_insert_with(vec![DataValue::Int32(-1)], || 0) += 1;
+        *key_map.get_or_insert_with(vec![DataValue::Int32(-1)], || 0) += 1;
+        *key_map.get_or_insert_with(vec![DataValue::Int32(2)], || 0) += 1;
+        *key_map.get_or_insert_with(vec![DataValue::Null], || 0) += 1;
+
+        assert_eq!(key_map.get_mut(&[DataValue::Int32(-1)]), Some(&mut 2));
+        assert_eq!(key_map.get_mut(&[DataValue::Null]), Some(&mut 1));
+        assert_eq!(key_map.get_mut(&[DataValue::Int32(3)]), None);
+
+        let entries = key_map
+            .into_entries()
+            .sorted_by_key(|(_, count)| *count)
+            .collect_vec();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2], (vec![DataValue::Int32(-1)], 2));
+    }
+
+    #[test]
+    fn test_generic_key_map() {
+        let mut key_map = KeyMap::new(&[LogicalType::Integer, LogicalType::Integer]);
+        assert!(matches!(key_map, KeyMap::Generic(_)));
+
+        let keys = vec![DataValue::Int32(1), DataValue::Int32(2)];
+        key_map.get_or_insert_with(keys.clone(), Vec::new).push(1);
+        key_map.get_or_insert_with(keys.clone(), Vec::new).push(2);
+
+        assert_eq!(key_map.get_mut(&keys), Some(&mut vec![1, 2]));
+    }
+}
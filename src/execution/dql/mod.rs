@@ -1,16 +1,28 @@
 pub(crate) mod aggregate;
+pub(crate) mod check_table;
 pub(crate) mod describe;
 pub(crate) mod dummy;
 pub(crate) mod explain;
 pub(crate) mod filter;
+pub(crate) mod foreign_scan;
 pub(crate) mod function_scan;
+pub(crate) mod fused_scan;
 pub(crate) mod index_scan;
 pub(crate) mod join;
+pub(crate) mod key_map;
 pub(crate) mod limit;
+pub(crate) mod multi_get;
 pub(crate) mod projection;
+pub(crate) mod recursive_union;
+pub(crate) mod runtime_filter;
+pub(crate) mod sample;
 pub(crate) mod seq_scan;
+pub(crate) mod shared_scan;
 pub(crate) mod show_table;
+pub(crate) mod show_table_status;
+pub(crate) mod show_task;
 pub(crate) mod show_view;
+pub(crate) mod skip_scan;
 pub(crate) mod sort;
 pub(crate) mod union;
 pub(crate) mod values;
@@ -0,0 +1,81 @@
+use crate::execution::dql::sample::Sampler;
+use crate::execution::{Executor, ReadExecutor};
+use crate::expression::range_detacher::Range;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::process::heavy_query_yield;
+use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::index::IndexMetaRef;
+use crate::types::value::DataValue;
+
+/// Reads the tuples of the keys of the primary key in one batch, e.g. for `pk IN (...)`.
+pub(crate) struct MultiGet {
+    op: TableScanOperator,
+    index_by: IndexMetaRef,
+    keys: Vec<DataValue>,
+}
+
+impl From<(TableScanOperator, IndexMetaRef, Range)> for MultiGet {
+    fn from((op, index_by, range): (TableScanOperator, IndexMetaRef, Range)) -> Self {
+        let ranges = match range {
+            Range::SortedRanges(ranges) => ranges,
+            range => vec![range],
+        };
+        let keys = ranges
+            .into_iter()
+            .map(|range| match range {
+                Range::Eq(key) => key,
+                _ => unreachable!("multi get of a range"),
+            })
+            .collect();
+
+        MultiGet { op, index_by, keys }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for MultiGet {
+    fn execute(
+        self,
+        (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let TableScanOperator {
+                    table_name,
+                    columns,
+                    limit,
+                    with_pk,
+                    sample,
+                    reverse,
+                    ..
+                } = self.op;
+                let mut sampler = sample.map(Sampler::from);
+                let mut keys = self.keys;
+
+                if reverse {
+                    keys.reverse();
+                }
+
+                let mut iter = throw!(transaction.read_by_keys(
+                    table_cache,
+                    table_name,
+                    limit,
+                    columns,
+                    self.index_by,
+                    keys,
+                    with_pk
+                ));
+
+                while let Some(tuple) = throw!(iter.next_tuple()) {
+                    heavy_query_yield();
+                    if sampler.as_mut().is_some_and(|sampler| !sampler.keep()) {
+                        continue;
+                    }
+                    yield Ok(tuple);
+                }
+            },
+        )
+    }
+}
@@ -8,6 +8,7 @@ use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::tuple::Tuple;
 use crate::types::value::DataValue;
+use std::mem;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
@@ -27,7 +28,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Projection {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -38,8 +39,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Projection {
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let tuple = throw!(tuple);
-                    let values = throw!(Self::projection(&tuple, &exprs, &schema));
-                    yield Ok(Tuple::new(tuple.pk, values));
+                    yield Self::projection_owned(tuple, &exprs, &schema);
                 }
             },
         )
@@ -59,4 +59,105 @@ impl Projection {
         }
         Ok(values)
     }
+
+    /// Same as [`Projection::projection`], but the values of the tuple the expressions read as
+    /// they are, e.g. its columns or their aliases, are moved into the output instead of copied.
+    pub fn projection_owned(
+        tuple: Tuple,
+        exprs: &[ScalarExpression],
+        schema: &[ColumnRef],
+    ) -> Result<Tuple, DatabaseError> {
+        let mut values = Vec::with_capacity(exprs.len());
+        let mut positions = Vec::with_capacity(exprs.len());
+
+        for expr in exprs.iter() {
+            let position = expr
+                .tuple_position(schema)
+                .filter(|i| *i < tuple.values.len());
+            values.push(match position {
+                Some(_) => DataValue::Null,
+                None => expr.eval(Some((&tuple, schema)))?,
+            });
+            positions.push(position);
+        }
+        let Tuple {
+            pk,
+            values: mut inputs,
+        } = tuple;
+        // Tips: a value read more than once is copied for all but its first reader
+        for (i, position) in positions.iter().enumerate().rev() {
+            let Some(pos) = position else {
+                continue;
+            };
+            values[i] = if positions[..i].contains(position) {
+                inputs[*pos].clone()
+            } else {
+                mem::replace(&mut inputs[*pos], DataValue::Null)
+            };
+        }
+        Ok(Tuple::new(pk, values))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::catalog::{ColumnCatalog, ColumnRef};
+    use crate::errors::DatabaseError;
+    use crate::execution::dql::projection::Projection;
+    use crate::expression::{AliasType, ScalarExpression};
+    use crate::types::tuple::Tuple;
+    use crate::types::value::{DataValue, Utf8Type};
+    use sqlparser::ast::CharLengthUnits;
+    use std::borrow::Cow;
+
+    fn utf8(value: &str) -> DataValue {
+        DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        }
+    }
+
+    fn as_ptr(value: &DataValue) -> *const u8 {
+        match value {
+            DataValue::Utf8 { value, .. } => value.as_ptr(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_projection_without_copy() -> Result<(), DatabaseError> {
+        let schema = vec![
+            ColumnRef::from(ColumnCatalog::new_dummy("a".to_string())),
+            ColumnRef::from(ColumnCatalog::new_dummy("b".to_string())),
+        ];
+        let tuple = Tuple::new(None, vec![utf8("kite"), utf8("sql")]);
+        let (a, b) = (as_ptr(&tuple.values[0]), as_ptr(&tuple.values[1]));
+
+        let column_a = ScalarExpression::ColumnRef(schema[0].clone());
+        let column_b = ScalarExpression::ColumnRef(schema[1].clone());
+        assert!(matches!(
+            column_a.eval_cow(Some((&tuple, &schema)))?,
+            Cow::Borrowed(value) if as_ptr(value) == a
+        ));
+
+        let exprs = vec![
+            ScalarExpression::Alias {
+                expr: Box::new(column_b.clone()),
+                alias: AliasType::Name("c".to_string()),
+            },
+            column_a.clone(),
+            column_b,
+            ScalarExpression::Constant(DataValue::Int32(1)),
+        ];
+        let expected = Projection::projection(&tuple, &exprs, &schema)?;
+        let projected = Projection::projection_owned(tuple, &exprs, &schema)?;
+
+        assert_eq!(projected.values, expected);
+        assert_eq!(as_ptr(&projected.values[0]), b);
+        assert_eq!(as_ptr(&projected.values[1]), a);
+        assert_ne!(as_ptr(&projected.values[2]), b);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,128 @@
+use crate::errors::DatabaseError;
+use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::planner::operator::recursive_union::{RecursiveUnionOperator, WorkTableScanOperator};
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use std::collections::HashSet;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+pub struct RecursiveUnion {
+    op: RecursiveUnionOperator,
+    anchor: LogicalPlan,
+    recursive: LogicalPlan,
+}
+
+impl From<(RecursiveUnionOperator, LogicalPlan, LogicalPlan)> for RecursiveUnion {
+    fn from((op, anchor, recursive): (RecursiveUnionOperator, LogicalPlan, LogicalPlan)) -> Self {
+        RecursiveUnion {
+            op,
+            anchor,
+            recursive,
+        }
+    }
+}
+
+impl RecursiveUnion {
+    /// The values by which a row repeats one given before: its `CYCLE` columns, all of them for
+    /// `UNION`, none for `UNION ALL`.
+    fn key(op: &RecursiveUnionOperator, tuple: &Tuple) -> Option<Vec<DataValue>> {
+        if !op.cycle.is_empty() {
+            Some(op.cycle.iter().map(|i| tuple.values[*i].clone()).collect())
+        } else if !op.is_all {
+            Some(tuple.values.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for RecursiveUnion {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let RecursiveUnion {
+                    op,
+                    anchor,
+                    recursive,
+                } = self;
+                let mut given = HashSet::new();
+                let mut rows = 0;
+                let mut depth = 0;
+                let mut input = anchor;
+
+                loop {
+                    let mut coroutine = build_read(input, cache, transaction);
+                    let mut iteration = Vec::new();
+
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        let tuple = throw!(tuple);
+
+                        if let Some(key) = Self::key(&op, &tuple) {
+                            if !given.insert(key) {
+                                continue;
+                            }
+                        }
+                        if depth > op.limits.depth {
+                            throw!(Err(DatabaseError::RecursionDepthExceeded(
+                                op.name.clone(),
+                                op.limits.depth
+                            )))
+                        }
+                        rows += 1;
+                        if rows > op.limits.rows {
+                            throw!(Err(DatabaseError::RecursionRowsExceeded(
+                                op.name.clone(),
+                                op.limits.rows
+                            )))
+                        }
+                        iteration.push(tuple.clone());
+                        yield Ok(tuple);
+                    }
+                    if iteration.is_empty() {
+                        break;
+                    }
+                    depth += 1;
+                    op.work_table.set(iteration);
+                    input = recursive.clone();
+                }
+            },
+        )
+    }
+}
+
+pub struct WorkTableScan {
+    op: WorkTableScanOperator,
+}
+
+impl From<WorkTableScanOperator> for WorkTableScan {
+    fn from(op: WorkTableScanOperator) -> Self {
+        WorkTableScan { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for WorkTableScan {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        _: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                for tuple in self.op.work_table.tuples() {
+                    yield Ok(tuple);
+                }
+            },
+        )
+    }
+}
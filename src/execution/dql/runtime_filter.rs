@@ -0,0 +1,127 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::visitor::Visitor;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::{Schema, Tuple};
+use crate::types::value::DataValue;
+use crate::utils::bloom_filter::BloomFilter;
+use std::cmp::Ordering;
+
+/// Filter built from the build side of a HashJoin at runtime and pushed into the probe side scan,
+/// so that probe tuples whose join keys can not match are skipped before reaching the join.
+pub(crate) struct RuntimeFilter {
+    keys: Vec<ScalarExpression>,
+    bloom: BloomFilter,
+    /// min and max of the build side keys, only for a single join key
+    bounds: Option<(DataValue, DataValue)>,
+}
+
+impl RuntimeFilter {
+    pub(crate) fn new<'a>(
+        keys: Vec<ScalarExpression>,
+        capacity: usize,
+        build_keys: impl Iterator<Item = &'a [DataValue]>,
+    ) -> Self {
+        let mut bloom = BloomFilter::with_capacity(capacity);
+        let mut bounds: Option<(DataValue, DataValue)> = None;
+        let mut is_comparable = keys.len() == 1;
+
+        for key in build_keys {
+            bloom.insert(key);
+
+            match (key, &mut bounds) {
+                ([value], _) if !is_comparable || value.is_null() => (),
+                ([value], None) => bounds = Some((value.clone(), value.clone())),
+                ([value], Some((min, max))) => {
+                    match (value.partial_cmp(min), value.partial_cmp(max)) {
+                        (Some(Ordering::Less), _) => *min = value.clone(),
+                        (_, Some(Ordering::Greater)) => *max = value.clone(),
+                        (Some(_), Some(_)) => (),
+                        _ => is_comparable = false,
+                    }
+                }
+                _ => is_comparable = false,
+            }
+        }
+        RuntimeFilter {
+            keys,
+            bloom,
+            bounds: bounds.filter(|_| is_comparable),
+        }
+    }
+
+    pub(crate) fn bounds(&self) -> Option<&(DataValue, DataValue)> {
+        self.bounds.as_ref()
+    }
+
+    /// The column of the single join key, if the key is a plain column.
+    pub(crate) fn key_column(&self) -> Option<&ColumnRef> {
+        let [key] = self.keys.as_slice() else {
+            return None;
+        };
+        let key = match key.unpack_alias_ref() {
+            ScalarExpression::Reference { expr, .. } => expr.unpack_alias_ref(),
+            key => key,
+        };
+        if let ScalarExpression::ColumnRef(column) = key {
+            Some(column)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the keys can be evaluated on tuples of `schema` instead of the probe output,
+    /// i.e. they only use columns of `schema` and no positional references.
+    pub(crate) fn is_applicable(&self, schema: &Schema) -> bool {
+        let mut visitor = KeyColumns::default();
+
+        for key in self.keys.iter() {
+            if visitor.visit(key).is_err() {
+                return false;
+            }
+        }
+        !visitor.has_reference
+            && visitor.columns.iter().all(|column| {
+                schema
+                    .iter()
+                    .any(|schema_column| schema_column.summary() == column.summary())
+            })
+    }
+
+    pub(crate) fn check(&self, tuple: &Tuple, schema: &Schema) -> Result<bool, DatabaseError> {
+        let mut values = Vec::with_capacity(self.keys.len());
+
+        for key in self.keys.iter() {
+            let value = key.eval_cow(Some((tuple, schema)))?;
+            // Tips: null never matches the equivalent condition of a join
+            if value.is_null() {
+                return Ok(false);
+            }
+            values.push(value);
+        }
+        if let (Some((min, max)), [value]) = (&self.bounds, values.as_slice()) {
+            if **value < *min || **value > *max {
+                return Ok(false);
+            }
+        }
+        Ok(self.bloom.contains(values.as_slice()))
+    }
+}
+
+#[derive(Default)]
+struct KeyColumns<'a> {
+    columns: Vec<&'a ColumnRef>,
+    has_reference: bool,
+}
+
+impl<'a> Visitor<'a> for KeyColumns<'a> {
+    fn visit_column_ref(&mut self, column: &'a ColumnRef) -> Result<(), DatabaseError> {
+        self.columns.push(column);
+        Ok(())
+    }
+
+    fn visit_reference(&mut self, _: &'a ScalarExpression, _: usize) -> Result<(), DatabaseError> {
+        self.has_reference = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::planner::operator::table_scan::{SampleMethod, TableSample};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Rows adjacent in key order that `TABLESAMPLE SYSTEM` keeps or skips together.
+const SYSTEM_BLOCK_ROWS: usize = 64;
+
+/// Decides which of the rows read by a scan are in a `TABLESAMPLE`.
+pub(crate) struct Sampler {
+    method: SampleMethod,
+    probability: f64,
+    rng: StdRng,
+    rows: usize,
+    keep_block: bool,
+}
+
+impl From<TableSample> for Sampler {
+    fn from(sample: TableSample) -> Self {
+        let rng = match sample.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Sampler {
+            method: sample.method,
+            probability: (sample.percentage.0 / 100.0).clamp(0.0, 1.0),
+            rng,
+            rows: 0,
+            keep_block: false,
+        }
+    }
+}
+
+impl Sampler {
+    /// Whether the next row read is kept.
+    pub(crate) fn keep(&mut self) -> bool {
+        match self.method {
+            SampleMethod::Bernoulli => self.rng.gen_bool(self.probability),
+            SampleMethod::System => {
+                if self.rows % SYSTEM_BLOCK_ROWS == 0 {
+                    self.keep_block = self.rng.gen_bool(self.probability);
+                }
+                self.rows += 1;
+                self.keep_block
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::execution::dql::sample::{Sampler, SYSTEM_BLOCK_ROWS};
+    use crate::planner::operator::table_scan::{SampleMethod, TableSample};
+    use itertools::Itertools;
+    use ordered_float::OrderedFloat;
+
+    fn sample(method: SampleMethod, percentage: f64, seed: Option<u64>) -> Vec<bool> {
+        let mut sampler = Sampler::from(TableSample {
+            method,
+            percentage: OrderedFloat(percentage),
+            seed,
+        });
+
+        (0..SYSTEM_BLOCK_ROWS * 16)
+            .map(|_| sampler.keep())
+            .collect_vec()
+    }
+
+    #[test]
+    fn test_sampler() {
+        for method in [SampleMethod::Bernoulli, SampleMethod::System] {
+            assert!(sample(method, 100.0, None).into_iter().all(|keep| keep));
+            assert!(sample(method, 0.0, None).into_iter().all(|keep| !keep));
+            assert_eq!(
+                sample(method, 50.0, Some(42)),
+                sample(method, 50.0, Some(42))
+            );
+        }
+        let blocks = sample(SampleMethod::System, 50.0, Some(7));
+
+        assert!(blocks
+            .chunks(SYSTEM_BLOCK_ROWS)
+            .all(|block| block.iter().all_equal()));
+    }
+}
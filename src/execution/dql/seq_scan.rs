@@ -1,5 +1,7 @@
+use crate::execution::dql::sample::Sampler;
 use crate::execution::{Executor, ReadExecutor};
 use crate::planner::operator::table_scan::TableScanOperator;
+use crate::process::heavy_query_yield;
 use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 
@@ -17,7 +19,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SeqScan {
     fn execute(
         self,
         (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -27,18 +29,26 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SeqScan {
                     columns,
                     limit,
                     with_pk,
+                    sample,
+                    reverse,
                     ..
                 } = self.op;
+                let mut sampler = sample.map(Sampler::from);
 
-                let mut iter = throw!(unsafe { &mut (*transaction) }.read(
+                let mut iter = throw!(transaction.read(
                     table_cache,
                     table_name,
                     limit,
                     columns,
-                    with_pk
+                    with_pk,
+                    reverse
                 ));
 
                 while let Some(tuple) = throw!(iter.next_tuple()) {
+                    heavy_query_yield();
+                    if sampler.as_mut().is_some_and(|sampler| !sampler.keep()) {
+                        continue;
+                    }
                     yield Ok(tuple);
                 }
             },
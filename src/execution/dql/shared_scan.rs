@@ -0,0 +1,50 @@
+use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::planner::operator::shared_scan::{SharedScanOperator, SharedTuples};
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+pub struct SharedScan {
+    tuples: SharedTuples,
+    input: LogicalPlan,
+}
+
+impl From<(SharedScanOperator, LogicalPlan)> for SharedScan {
+    fn from((SharedScanOperator { tuples, .. }, input): (SharedScanOperator, LogicalPlan)) -> Self {
+        SharedScan { tuples, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SharedScan {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let SharedScan { tuples, input } = self;
+
+                // the first of the scans reads all the rows before yielding any of them
+                if tuples.get().is_none() {
+                    let mut coroutine = build_read(input, cache, transaction);
+                    let mut buf = Vec::new();
+
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        buf.push(throw!(tuple));
+                    }
+                    tuples.set(buf);
+                }
+                let len = tuples.get().map_or(0, Vec::len);
+
+                for i in 0..len {
+                    yield Ok(tuples.get().unwrap()[i].clone());
+                }
+            },
+        )
+    }
+}
@@ -12,14 +12,14 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ShowTables {
     fn execute(
         self,
         _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let metas = throw!(unsafe { &mut (*transaction) }.table_metas());
+                let metas = throw!(transaction.table_metas());
 
-                for TableMeta { table_name } in metas {
+                for TableMeta { table_name, .. } in metas {
                     let values = vec![DataValue::Utf8 {
                         value: table_name.to_string(),
                         ty: Utf8Type::Variable(None),
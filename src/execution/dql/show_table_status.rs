@@ -0,0 +1,69 @@
+use crate::catalog::TableMeta;
+use crate::execution::{Executor, ReadExecutor};
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::index::IndexType;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use sqlparser::ast::CharLengthUnits;
+
+/// The rows of each table and the entries of each of its indexes, with the bytes they take in
+/// the storage before compression, e.g. to find what takes the space of a database.
+///
+/// The tuples of a table are its primary key index, so the row of the table has no index name.
+pub struct ShowTableStatus;
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ShowTableStatus {
+    fn execute(
+        self,
+        (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let utf8 = |value: &str| DataValue::Utf8 {
+                    value: value.to_string(),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                };
+                let metas = throw!(transaction.table_metas());
+
+                for TableMeta { table_name, .. } in metas {
+                    let Some(table) = throw!(transaction.table(table_cache, table_name.clone()))
+                    else {
+                        continue;
+                    };
+                    let (rows, bytes) = throw!(transaction.table_usage(&table_name));
+
+                    yield Ok(Tuple::new(
+                        None,
+                        vec![
+                            utf8(&table_name),
+                            DataValue::Null,
+                            DataValue::UInt64(rows),
+                            DataValue::UInt64(bytes),
+                        ],
+                    ));
+                    for index in table.indexes() {
+                        if matches!(index.ty, IndexType::PrimaryKey { .. }) {
+                            continue;
+                        }
+                        let (entries, bytes) =
+                            throw!(transaction.index_usage(&table_name, index.id));
+
+                        yield Ok(Tuple::new(
+                            None,
+                            vec![
+                                utf8(&table_name),
+                                utf8(&index.name),
+                                DataValue::UInt64(entries),
+                                DataValue::UInt64(bytes),
+                            ],
+                        ));
+                    }
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,42 @@
+use crate::catalog::task::Task;
+use crate::execution::{Executor, ReadExecutor};
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use sqlparser::ast::CharLengthUnits;
+
+pub struct ShowTasks;
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ShowTasks {
+    fn execute(
+        self,
+        _: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let tasks = throw!(transaction.tasks());
+
+                for Task {
+                    name,
+                    schedule,
+                    sql,
+                } in tasks
+                {
+                    let values = [name, schedule, sql]
+                        .into_iter()
+                        .map(|value| DataValue::Utf8 {
+                            value,
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        })
+                        .collect();
+
+                    yield Ok(Tuple::new(None, values));
+                }
+            },
+        )
+    }
+}
@@ -12,12 +12,12 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ShowViews {
     fn execute(
         self,
         (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let metas = throw!(unsafe { &mut (*transaction) }.views(table_cache));
+                let metas = throw!(transaction.views(table_cache));
 
                 for View { name, .. } in metas {
                     let values = vec![DataValue::Utf8 {
@@ -0,0 +1,66 @@
+use crate::execution::dql::sample::Sampler;
+use crate::execution::{Executor, ReadExecutor};
+use crate::expression::range_detacher::Range;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::process::heavy_query_yield;
+use crate::storage::{Iter, StatisticsMetaCache, TableCache, Transaction, ViewCache};
+use crate::throw;
+use crate::types::index::IndexMetaRef;
+
+/// Reads a composite index by the range of its second column, for each value of the leading one.
+pub(crate) struct SkipScan {
+    op: TableScanOperator,
+    index_by: IndexMetaRef,
+    range: Range,
+}
+
+impl From<(TableScanOperator, IndexMetaRef, Range)> for SkipScan {
+    fn from((op, index_by, range): (TableScanOperator, IndexMetaRef, Range)) -> Self {
+        SkipScan {
+            op,
+            index_by,
+            range,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SkipScan {
+    fn execute(
+        self,
+        (table_cache, _, _): (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let TableScanOperator {
+                    table_name,
+                    columns,
+                    limit,
+                    with_pk,
+                    sample,
+                    ..
+                } = self.op;
+                let mut sampler = sample.map(Sampler::from);
+
+                let mut iter = throw!(transaction.read_by_skip(
+                    table_cache,
+                    table_name,
+                    limit,
+                    columns,
+                    self.index_by,
+                    self.range,
+                    with_pk
+                ));
+
+                while let Some(tuple) = throw!(iter.next_tuple()) {
+                    heavy_query_yield();
+                    if sampler.as_mut().is_some_and(|sampler| !sampler.keep()) {
+                        continue;
+                    }
+                    yield Ok(tuple);
+                }
+            },
+        )
+    }
+}
@@ -2,12 +2,16 @@ use crate::errors::DatabaseError;
 use crate::execution::{build_read, Executor, ReadExecutor};
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::LogicalPlan;
+use crate::storage::memcomparable::{self, KeyOrder};
 use crate::storage::table_codec::BumpBytes;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::throw;
 use crate::types::tuple::{Schema, Tuple};
+use crate::types::value::DataValue;
 use bumpalo::Bump;
+use itertools::Itertools;
 use std::cmp::Ordering;
+use std::mem;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
@@ -102,6 +106,142 @@ pub(crate) fn radix_sort<'a, T, A: AsRef<[u8]>>(
     result
 }
 
+type ValueComparator = fn(&DataValue, &DataValue) -> Ordering;
+
+macro_rules! typed_comparator {
+    ($pattern_1:pat, $pattern_2:pat, $v1:ident, $v2:ident) => {
+        |value_1: &DataValue, value_2: &DataValue| match (value_1, value_2) {
+            ($pattern_1, $pattern_2) => $v1.cmp($v2),
+            (value_1, value_2) => value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal),
+        }
+    };
+    ($variant:ident) => {
+        typed_comparator!(DataValue::$variant(v1), DataValue::$variant(v2), v1, v2)
+    };
+}
+
+fn generic_comparator(value_1: &DataValue, value_2: &DataValue) -> Ordering {
+    value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal)
+}
+
+/// Per-field comparators resolved once from the value types of the sort keys,
+/// so comparing two rows no longer dispatches through the generic `partial_cmp`.
+pub(crate) struct TupleComparator {
+    fields: Vec<(ValueComparator, bool, bool)>,
+}
+
+impl TupleComparator {
+    /// `sample` returns any non-null value of the x-th sort field, used to pick its comparator.
+    pub(crate) fn new<'a>(
+        sort_fields: &[SortField],
+        sample: impl Fn(usize) -> Option<&'a DataValue>,
+    ) -> Self {
+        let fields = sort_fields
+            .iter()
+            .enumerate()
+            .map(
+                |(
+                    x,
+                    SortField {
+                        asc, nulls_first, ..
+                    },
+                )| {
+                    (
+                        sample(x).map_or(
+                            generic_comparator as ValueComparator,
+                            Self::value_comparator,
+                        ),
+                        *asc,
+                        *nulls_first,
+                    )
+                },
+            )
+            .collect();
+
+        TupleComparator { fields }
+    }
+
+    fn value_comparator(value: &DataValue) -> ValueComparator {
+        match value {
+            DataValue::Boolean(_) => typed_comparator!(Boolean),
+            DataValue::Float32(_) => typed_comparator!(Float32),
+            DataValue::Float64(_) => typed_comparator!(Float64),
+            DataValue::Int8(_) => typed_comparator!(Int8),
+            DataValue::Int16(_) => typed_comparator!(Int16),
+            DataValue::Int32(_) => typed_comparator!(Int32),
+            DataValue::Int64(_) => typed_comparator!(Int64),
+            DataValue::UInt8(_) => typed_comparator!(UInt8),
+            DataValue::UInt16(_) => typed_comparator!(UInt16),
+            DataValue::UInt32(_) => typed_comparator!(UInt32),
+            DataValue::UInt64(_) => typed_comparator!(UInt64),
+            DataValue::Int128(_) => typed_comparator!(Int128),
+            DataValue::UInt128(_) => typed_comparator!(UInt128),
+            DataValue::Date32(_) => typed_comparator!(Date32),
+            DataValue::Date64(_) => typed_comparator!(Date64),
+            DataValue::Decimal(_) => typed_comparator!(Decimal),
+            DataValue::Numeric(_) => typed_comparator!(Numeric),
+            DataValue::Utf8 { .. } => typed_comparator!(
+                DataValue::Utf8 { value: v1, .. },
+                DataValue::Utf8 { value: v2, .. },
+                v1,
+                v2
+            ),
+            DataValue::Time32(..) => {
+                typed_comparator!(DataValue::Time32(v1, ..), DataValue::Time32(v2, ..), v1, v2)
+            }
+            DataValue::Time64(..) => {
+                typed_comparator!(DataValue::Time64(v1, ..), DataValue::Time64(v2, ..), v1, v2)
+            }
+            DataValue::Null
+            | DataValue::Tuple(..)
+            | DataValue::Vector(_)
+            | DataValue::Point(..)
+            | DataValue::Box(..)
+            | DataValue::Array(_)
+            | DataValue::Struct(_) => generic_comparator,
+        }
+    }
+
+    /// Compare two rows, `value_1(x)` and `value_2(x)` return the x-th sort key of each row.
+    #[inline]
+    pub(crate) fn compare<'a, 'b>(
+        &self,
+        value_1: impl Fn(usize) -> &'a DataValue,
+        value_2: impl Fn(usize) -> &'b DataValue,
+    ) -> Ordering {
+        let fn_nulls_first = |nulls_first: bool| {
+            if nulls_first {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        };
+
+        for (x, (comparator, asc, nulls_first)) in self.fields.iter().enumerate() {
+            let value_1 = value_1(x);
+            let value_2 = value_2(x);
+
+            let ordering = match (value_1.is_null(), value_2.is_null()) {
+                (false, true) => fn_nulls_first(*nulls_first),
+                (true, false) => fn_nulls_first(*nulls_first).reverse(),
+                (true, true) => Ordering::Equal,
+                (false, false) => {
+                    let ordering = comparator(value_1, value_2);
+                    if *asc {
+                        ordering
+                    } else {
+                        ordering.reverse()
+                    }
+                }
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
 pub enum SortBy {
     Radix,
     Fast,
@@ -122,7 +262,7 @@ impl SortBy {
                 for (i, tuple) in tuples.0.iter().enumerate() {
                     debug_assert!(tuple.is_some());
 
-                    let mut full_key = BumpVec::new_in(arena);
+                    let mut full_key = BumpBytes::new_in(arena);
 
                     for SortField {
                         expr,
@@ -130,18 +270,17 @@ impl SortBy {
                         asc,
                     } in sort_fields
                     {
-                        let mut key = BumpBytes::new_in(arena);
                         let tuple = tuple.as_ref().map(|(_, tuple)| tuple).unwrap();
+                        let order = KeyOrder {
+                            asc: *asc,
+                            nulls_first: *nulls_first,
+                        };
 
-                        expr.eval(Some((tuple, schema)))?
-                            .memcomparable_encode(&mut key)?;
-                        if !asc {
-                            for byte in key.iter_mut() {
-                                *byte ^= 0xFF;
-                            }
-                        }
-                        key.push(if *nulls_first { u8::MIN } else { u8::MAX });
-                        full_key.extend(key);
+                        memcomparable::encode_key(
+                            &expr.eval(Some((tuple, schema)))?,
+                            order,
+                            &mut full_key,
+                        )?;
                     }
                     sort_keys.push((i, full_key))
                 }
@@ -154,16 +293,9 @@ impl SortBy {
                 }))
             }
             SortBy::Fast => {
-                let fn_nulls_first = |nulls_first: bool| {
-                    if nulls_first {
-                        Ordering::Greater
-                    } else {
-                        Ordering::Less
-                    }
-                };
                 // Extract the results of calculating SortFields to avoid double calculation
                 // of data during comparison
-                let mut eval_values = vec![Vec::with_capacity(sort_fields.len()); tuples.len()];
+                let mut eval_values = vec![Vec::with_capacity(tuples.len()); sort_fields.len()];
 
                 for (x, SortField { expr, .. }) in sort_fields.iter().enumerate() {
                     for tuple in tuples.0.iter() {
@@ -173,6 +305,9 @@ impl SortBy {
                         eval_values[x].push(expr.eval(Some((tuple, schema)))?);
                     }
                 }
+                let comparator = TupleComparator::new(sort_fields, |x| {
+                    eval_values[x].iter().find(|value| !value.is_null())
+                });
 
                 tuples.0.sort_by(|tuple_1, tuple_2| {
                     debug_assert!(tuple_1.is_some());
@@ -180,36 +315,8 @@ impl SortBy {
 
                     let (i_1, _) = tuple_1.as_ref().unwrap();
                     let (i_2, _) = tuple_2.as_ref().unwrap();
-                    let mut ordering = Ordering::Equal;
 
-                    for (
-                        x,
-                        SortField {
-                            asc, nulls_first, ..
-                        },
-                    ) in sort_fields.iter().enumerate()
-                    {
-                        let value_1 = &eval_values[x][*i_1];
-                        let value_2 = &eval_values[x][*i_2];
-
-                        ordering = match (value_1.is_null(), value_2.is_null()) {
-                            (false, true) => fn_nulls_first(*nulls_first),
-                            (true, false) => fn_nulls_first(*nulls_first).reverse(),
-                            _ => {
-                                let mut ordering =
-                                    value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal);
-                                if !*asc {
-                                    ordering = ordering.reverse();
-                                }
-                                ordering
-                            }
-                        };
-                        if ordering != Ordering::Equal {
-                            break;
-                        }
-                    }
-
-                    ordering
+                    comparator.compare(|x| &eval_values[x][*i_1], |x| &eval_values[x][*i_2])
                 });
                 drop(eval_values);
 
@@ -232,7 +339,14 @@ pub struct Sort {
 }
 
 impl From<(SortOperator, LogicalPlan)> for Sort {
-    fn from((SortOperator { sort_fields, limit }, input): (SortOperator, LogicalPlan)) -> Self {
+    fn from(
+        (
+            SortOperator {
+                sort_fields, limit, ..
+            },
+            input,
+        ): (SortOperator, LogicalPlan),
+    ) -> Self {
         Sort {
             arena: Default::default(),
             sort_fields,
@@ -246,7 +360,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Sort {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
@@ -290,18 +404,143 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Sort {
     }
 }
 
+/// Sorts the rows of a run of an [`IncrementalSort`].
+fn sort_run(
+    schema: &Schema,
+    sort_fields: &[SortField],
+    run: Vec<Tuple>,
+) -> Result<Vec<Tuple>, DatabaseError> {
+    let arena = Bump::new();
+    let sort_by = if run.len() > 256 {
+        SortBy::Radix
+    } else {
+        SortBy::Fast
+    };
+    let mut tuples = NullableVec::with_capacity(run.len(), &arena);
+    for (offset, tuple) in run.into_iter().enumerate() {
+        tuples.put((offset, tuple));
+    }
+    let tuples = sort_by
+        .sorted_tuples(&arena, schema, sort_fields, tuples)?
+        .collect();
+
+    Ok(tuples)
+}
+
+/// Sorts an input already ordered by the leading sort fields, one run of equal values of them at
+/// a time, so that the rows of a run are given as soon as it ends and a limit stops reading the
+/// input once reached.
+pub struct IncrementalSort {
+    sort_fields: Vec<SortField>,
+    presorted: usize,
+    limit: Option<usize>,
+    input: LogicalPlan,
+}
+
+impl From<(SortOperator, LogicalPlan)> for IncrementalSort {
+    fn from(
+        (
+            SortOperator {
+                sort_fields,
+                limit,
+                presorted,
+            },
+            input,
+        ): (SortOperator, LogicalPlan),
+    ) -> Self {
+        IncrementalSort {
+            sort_fields,
+            presorted,
+            limit,
+            input,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IncrementalSort {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let IncrementalSort {
+                    mut sort_fields,
+                    presorted,
+                    limit,
+                    mut input,
+                } = self;
+
+                let schema = input.output_schema().clone();
+                let run_fields = sort_fields.split_off(presorted);
+                let mut limit = limit.unwrap_or(usize::MAX);
+                let mut run = Vec::new();
+                let mut run_keys = Vec::new();
+
+                let mut coroutine = build_read(input, cache, transaction);
+
+                loop {
+                    let (tuple, keys) = match Pin::new(&mut coroutine).resume(()) {
+                        CoroutineState::Yielded(tuple) => {
+                            let tuple = throw!(tuple);
+                            let keys: Vec<DataValue> = throw!(sort_fields
+                                .iter()
+                                .map(|field| field.expr.eval(Some((&tuple, &schema))))
+                                .try_collect());
+                            (Some(tuple), keys)
+                        }
+                        CoroutineState::Complete(()) => (None, Vec::new()),
+                    };
+                    if !run.is_empty() && (tuple.is_none() || keys != run_keys) {
+                        for tuple in throw!(sort_run(&schema, &run_fields, mem::take(&mut run))) {
+                            if limit == 0 {
+                                return;
+                            }
+                            limit -= 1;
+                            yield Ok(tuple);
+                        }
+                    }
+                    let Some(tuple) = tuple else {
+                        break;
+                    };
+                    if limit == 0 {
+                        return;
+                    }
+                    run_keys = keys;
+                    run.push(tuple);
+                }
+            },
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
     use crate::errors::DatabaseError;
-    use crate::execution::dql::sort::{radix_sort, BumpVec, NullableVec, SortBy};
+    use crate::execution::dql::sort::{
+        radix_sort, BumpVec, IncrementalSort, NullableVec, SortBy, TupleComparator,
+    };
+    use crate::execution::dql::test::build_integers;
+    use crate::execution::{try_collect, ReadExecutor};
     use crate::expression::ScalarExpression;
-    use crate::planner::operator::sort::SortField;
+    use crate::planner::operator::sort::{SortField, SortOperator};
+    use crate::planner::operator::values::ValuesOperator;
+    use crate::planner::operator::Operator;
+    use crate::planner::{Childrens, LogicalPlan};
+    use crate::storage::rocksdb::RocksStorage;
+    use crate::storage::Storage;
     use crate::types::tuple::Tuple;
     use crate::types::value::DataValue;
     use crate::types::LogicalType;
+    use crate::utils::lru::SharedLruCache;
     use bumpalo::Bump;
+    use std::cmp::Ordering;
+    use std::hash::RandomState;
     use std::sync::Arc;
+    use tempfile::TempDir;
 
     #[test]
     fn test_radix_sort() {
@@ -319,6 +558,37 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_tuple_comparator() {
+        let sort_field = |pos: usize, asc: bool| SortField {
+            expr: ScalarExpression::Reference {
+                expr: Box::new(ScalarExpression::Empty),
+                pos,
+            },
+            asc,
+            nulls_first: false,
+        };
+        let row_1 = [DataValue::from("a".to_string()), DataValue::Int64(1)];
+        let row_2 = [DataValue::from("b".to_string()), DataValue::Int64(2)];
+        let row_3 = [DataValue::from("b".to_string()), DataValue::Null];
+
+        let comparator = TupleComparator::new(&[sort_field(0, true), sort_field(1, false)], |x| {
+            Some(&row_1[x])
+        });
+        assert_eq!(
+            comparator.compare(|x| &row_1[x], |x| &row_2[x]),
+            Ordering::Less
+        );
+        assert_eq!(
+            comparator.compare(|x| &row_2[x], |x| &row_3[x]),
+            Ordering::Less
+        );
+        assert_eq!(
+            comparator.compare(|x| &row_3[x], |x| &row_3[x]),
+            Ordering::Equal
+        );
+    }
+
     #[test]
     fn test_single_value_desc_and_null_first() -> Result<(), DatabaseError> {
         let fn_sort_fields = |asc: bool, nulls_first: bool| {
@@ -717,4 +987,72 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_incremental_sort() -> Result<(), DatabaseError> {
+        let meta_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+        let view_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let transaction = storage.transaction()?;
+        let desc = ColumnDesc::new(LogicalType::Integer, None, false, None)?;
+
+        let schema = Arc::new(vec![
+            ColumnRef::from(ColumnCatalog::new("c1".to_string(), true, desc.clone())),
+            ColumnRef::from(ColumnCatalog::new("c2".to_string(), true, desc.clone())),
+        ]);
+        let sort = |limit| {
+            let input = LogicalPlan {
+                operator: Operator::Values(ValuesOperator {
+                    // ordered by `c1` only
+                    rows: [(0, 2), (0, 1), (1, 3), (1, 0), (1, 2), (2, 1)]
+                        .into_iter()
+                        .map(|(c1, c2)| build_integers(vec![Some(c1), Some(c2)]))
+                        .collect(),
+                    schema_ref: schema.clone(),
+                }),
+                childrens: Box::new(Childrens::None),
+                physical_option: None,
+                _output_schema_ref: None,
+            };
+            let operator = SortOperator {
+                sort_fields: vec![
+                    SortField::new(ScalarExpression::ColumnRef(schema[0].clone()), true, true),
+                    SortField::new(ScalarExpression::ColumnRef(schema[1].clone()), false, true),
+                ],
+                limit,
+                presorted: 1,
+            };
+
+            try_collect(
+                IncrementalSort::from((operator, input))
+                    .execute((&table_cache, &view_cache, &meta_cache), &transaction),
+            )
+            .map(|tuples| {
+                tuples
+                    .into_iter()
+                    .map(|tuple| tuple.values)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(
+            sort(None)?,
+            [(0, 2), (0, 1), (1, 3), (1, 2), (1, 0), (2, 1)]
+                .into_iter()
+                .map(|(c1, c2)| build_integers(vec![Some(c1), Some(c2)]))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            sort(Some(3))?,
+            [(0, 2), (0, 1), (1, 3)]
+                .into_iter()
+                .map(|(c1, c2)| build_integers(vec![Some(c1), Some(c2)]))
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
 }
@@ -1,45 +1,62 @@
+pub mod custom;
 pub(crate) mod ddl;
 pub(crate) mod dml;
 pub(crate) mod dql;
 pub(crate) mod marco;
+pub(crate) mod spill;
 
 use self::ddl::add_column::AddColumn;
 use self::dql::join::nested_loop_join::NestedLoopJoin;
 use crate::errors::DatabaseError;
 use crate::execution::ddl::create_index::CreateIndex;
 use crate::execution::ddl::create_table::CreateTable;
+use crate::execution::ddl::create_task::CreateTask;
 use crate::execution::ddl::create_view::CreateView;
 use crate::execution::ddl::drop_column::DropColumn;
 use crate::execution::ddl::drop_index::DropIndex;
 use crate::execution::ddl::drop_table::DropTable;
+use crate::execution::ddl::drop_task::DropTask;
 use crate::execution::ddl::drop_view::DropView;
+use crate::execution::ddl::reindex::Reindex;
 use crate::execution::ddl::truncate::Truncate;
+use crate::execution::ddl::vacuum::Vacuum;
 use crate::execution::dml::analyze::Analyze;
 use crate::execution::dml::copy_from_file::CopyFromFile;
 use crate::execution::dml::copy_to_file::CopyToFile;
 use crate::execution::dml::delete::Delete;
 use crate::execution::dml::insert::Insert;
 use crate::execution::dml::update::Update;
+use crate::execution::dql::aggregate::count_star::CountStarExecutor;
 use crate::execution::dql::aggregate::hash_agg::HashAggExecutor;
+use crate::execution::dql::aggregate::min_max_index::MinMaxIndexExecutor;
 use crate::execution::dql::aggregate::simple_agg::SimpleAggExecutor;
+use crate::execution::dql::check_table::CheckTable;
 use crate::execution::dql::describe::Describe;
 use crate::execution::dql::dummy::Dummy;
 use crate::execution::dql::explain::Explain;
 use crate::execution::dql::filter::Filter;
+use crate::execution::dql::foreign_scan::ForeignScan;
 use crate::execution::dql::function_scan::FunctionScan;
+use crate::execution::dql::fused_scan::FusedScan;
 use crate::execution::dql::index_scan::IndexScan;
 use crate::execution::dql::join::hash_join::HashJoin;
 use crate::execution::dql::limit::Limit;
+use crate::execution::dql::multi_get::MultiGet;
 use crate::execution::dql::projection::Projection;
+use crate::execution::dql::recursive_union::{RecursiveUnion, WorkTableScan};
 use crate::execution::dql::seq_scan::SeqScan;
+use crate::execution::dql::shared_scan::SharedScan;
 use crate::execution::dql::show_table::ShowTables;
+use crate::execution::dql::show_table_status::ShowTableStatus;
+use crate::execution::dql::show_task::ShowTasks;
 use crate::execution::dql::show_view::ShowViews;
-use crate::execution::dql::sort::Sort;
+use crate::execution::dql::skip_scan::SkipScan;
+use crate::execution::dql::sort::{IncrementalSort, Sort};
 use crate::execution::dql::union::Union;
 use crate::execution::dql::values::Values;
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::{Operator, PhysicalOption};
-use crate::planner::LogicalPlan;
+use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::types::index::IndexInfo;
 use crate::types::tuple::Tuple;
@@ -52,7 +69,7 @@ pub trait ReadExecutor<'a, T: Transaction + 'a> {
     fn execute(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a>;
 }
 
@@ -60,18 +77,31 @@ pub trait WriteExecutor<'a, T: Transaction + 'a> {
     fn execute_mut(
         self,
         cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-        transaction: *mut T,
+        transaction: &'a T,
     ) -> Executor<'a>;
 }
 
 pub fn build_read<'a, T: Transaction + 'a>(
     plan: LogicalPlan,
     cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-    transaction: *mut T,
+    transaction: &'a T,
 ) -> Executor<'a> {
+    if matches!(plan.physical_option, Some(PhysicalOption::Custom(_))) {
+        return build_custom(plan, cache, transaction);
+    }
+    if matches!(
+        plan.physical_option,
+        Some(PhysicalOption::FusedFilter | PhysicalOption::FusedProject)
+    ) {
+        let Ok(scan) = FusedScan::try_from_plan(plan) else {
+            unreachable!()
+        };
+        return scan.execute(cache, transaction);
+    }
     let LogicalPlan {
         operator,
         childrens,
+        physical_option,
         ..
     } = plan;
 
@@ -80,7 +110,11 @@ pub fn build_read<'a, T: Transaction + 'a>(
         Operator::Aggregate(op) => {
             let input = childrens.pop_only();
 
-            if op.groupby_exprs.is_empty() {
+            if physical_option == Some(PhysicalOption::CountStar) {
+                CountStarExecutor::from((op, input)).execute(cache, transaction)
+            } else if physical_option == Some(PhysicalOption::MinMaxIndex) {
+                MinMaxIndexExecutor::from((op, input)).execute(cache, transaction)
+            } else if op.groupby_exprs.is_empty() {
                 SimpleAggExecutor::from((op, input)).execute(cache, transaction)
             } else {
                 HashAggExecutor::from((op, input)).execute(cache, transaction)
@@ -96,7 +130,7 @@ pub fn build_read<'a, T: Transaction + 'a>(
 
             match &op.on {
                 JoinCondition::On { on, .. }
-                    if !on.is_empty() && plan.physical_option == Some(PhysicalOption::HashJoin) =>
+                    if !on.is_empty() && physical_option == Some(PhysicalOption::HashJoin) =>
                 {
                     HashJoin::from((op, left_input, right_input)).execute(cache, transaction)
                 }
@@ -110,22 +144,34 @@ pub fn build_read<'a, T: Transaction + 'a>(
 
             Projection::from((op, input)).execute(cache, transaction)
         }
-        Operator::TableScan(op) => {
-            if let Some(PhysicalOption::IndexScan(IndexInfo {
+        Operator::TableScan(op) => match physical_option {
+            Some(PhysicalOption::IndexScan(IndexInfo {
                 meta,
                 range: Some(range),
-            })) = plan.physical_option
-            {
-                IndexScan::from((op, meta, range)).execute(cache, transaction)
-            } else {
-                SeqScan::from(op).execute(cache, transaction)
-            }
-        }
+                ..
+            })) => IndexScan::from((op, meta, range)).execute(cache, transaction),
+            Some(PhysicalOption::MultiGet(IndexInfo {
+                meta,
+                range: Some(range),
+                ..
+            })) => MultiGet::from((op, meta, range)).execute(cache, transaction),
+            Some(PhysicalOption::SkipScan(IndexInfo {
+                meta,
+                skip_range: Some(range),
+                ..
+            })) => SkipScan::from((op, meta, range)).execute(cache, transaction),
+            _ => SeqScan::from(op).execute(cache, transaction),
+        },
         Operator::FunctionScan(op) => FunctionScan::from(op).execute(cache, transaction),
+        Operator::ForeignScan(op) => ForeignScan::from(op).execute(cache, transaction),
         Operator::Sort(op) => {
             let input = childrens.pop_only();
 
-            Sort::from((op, input)).execute(cache, transaction)
+            if physical_option == Some(PhysicalOption::IncrementalSort) {
+                IncrementalSort::from((op, input)).execute(cache, transaction)
+            } else {
+                Sort::from((op, input)).execute(cache, transaction)
+            }
         }
         Operator::Limit(op) => {
             let input = childrens.pop_only();
@@ -134,27 +180,74 @@ pub fn build_read<'a, T: Transaction + 'a>(
         }
         Operator::Values(op) => Values::from(op).execute(cache, transaction),
         Operator::ShowTable => ShowTables.execute(cache, transaction),
+        Operator::ShowTableStatus => ShowTableStatus.execute(cache, transaction),
         Operator::ShowView => ShowViews.execute(cache, transaction),
+        Operator::ShowTask => ShowTasks.execute(cache, transaction),
         Operator::Explain => {
             let input = childrens.pop_only();
 
             Explain::from(input).execute(cache, transaction)
         }
         Operator::Describe(op) => Describe::from(op).execute(cache, transaction),
+        Operator::CheckTable(op) => {
+            let input = childrens.pop_only();
+
+            CheckTable::from((op, input)).execute(cache, transaction)
+        }
         Operator::Union(_) => {
             let (left_input, right_input) = childrens.pop_twins();
 
             Union::from((left_input, right_input)).execute(cache, transaction)
         }
+        Operator::SharedScan(op) => {
+            let input = childrens.pop_only();
+
+            SharedScan::from((op, input)).execute(cache, transaction)
+        }
+        Operator::RecursiveUnion(op) => {
+            let (anchor, recursive) = childrens.pop_twins();
+
+            RecursiveUnion::from((op, anchor, recursive)).execute(cache, transaction)
+        }
+        Operator::WorkTableScan(op) => WorkTableScan::from(op).execute(cache, transaction),
         _ => unreachable!(),
     }
 }
 
+/// Runs the operator by the executor of the embedder planned for it, on the rows of its children.
+fn build_custom<'a, T: Transaction + 'a>(
+    plan: LogicalPlan,
+    cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
+    transaction: &'a T,
+) -> Executor<'a> {
+    let LogicalPlan {
+        operator,
+        childrens,
+        physical_option: Some(PhysicalOption::Custom(executor)),
+        ..
+    } = plan
+    else {
+        unreachable!()
+    };
+    let inputs = match *childrens {
+        Childrens::None => vec![],
+        Childrens::Only(input) => vec![build_read(input, cache, transaction)],
+        Childrens::Twins { left, right } => vec![
+            build_read(left, cache, transaction),
+            build_read(right, cache, transaction),
+        ],
+    };
+    executor.execute(operator, inputs)
+}
+
 pub fn build_write<'a, T: Transaction + 'a>(
     plan: LogicalPlan,
     cache: (&'a TableCache, &'a ViewCache, &'a StatisticsMetaCache),
-    transaction: *mut T,
+    transaction: &'a T,
 ) -> Executor<'a> {
+    if matches!(plan.physical_option, Some(PhysicalOption::Custom(_))) {
+        return build_custom(plan, cache, transaction);
+    }
     let LogicalPlan {
         operator,
         childrens,
@@ -179,11 +272,17 @@ pub fn build_write<'a, T: Transaction + 'a>(
             Delete::from((op, input)).execute_mut(cache, transaction)
         }
         Operator::AddColumn(op) => {
-            let input = childrens.pop_only();
+            let input = match *childrens {
+                Childrens::Only(input) => Some(input),
+                _ => None,
+            };
             AddColumn::from((op, input)).execute_mut(cache, transaction)
         }
         Operator::DropColumn(op) => {
-            let input = childrens.pop_only();
+            let input = match *childrens {
+                Childrens::Only(input) => Some(input),
+                _ => None,
+            };
             DropColumn::from((op, input)).execute_mut(cache, transaction)
         }
         Operator::CreateTable(op) => CreateTable::from(op).execute_mut(cache, transaction),
@@ -193,10 +292,24 @@ pub fn build_write<'a, T: Transaction + 'a>(
             CreateIndex::from((op, input)).execute_mut(cache, transaction)
         }
         Operator::CreateView(op) => CreateView::from(op).execute_mut(cache, transaction),
+        Operator::CreateTask(op) => CreateTask::from(op).execute_mut(cache, transaction),
         Operator::DropTable(op) => DropTable::from(op).execute_mut(cache, transaction),
         Operator::DropView(op) => DropView::from(op).execute_mut(cache, transaction),
+        Operator::DropTask(op) => DropTask::from(op).execute_mut(cache, transaction),
         Operator::DropIndex(op) => DropIndex::from(op).execute_mut(cache, transaction),
+        Operator::Reindex(op) => {
+            let input = childrens.pop_only();
+
+            Reindex::from((op, input)).execute_mut(cache, transaction)
+        }
         Operator::Truncate(op) => Truncate::from(op).execute_mut(cache, transaction),
+        Operator::Vacuum(op) => {
+            let input = match *childrens {
+                Childrens::Only(input) => Some(input),
+                _ => None,
+            };
+            Vacuum::from((op, input)).execute_mut(cache, transaction)
+        }
         Operator::CopyFromFile(op) => CopyFromFile::from(op).execute_mut(cache, transaction),
         Operator::CopyToFile(op) => {
             let input = childrens.pop_only();
@@ -0,0 +1,73 @@
+use crate::errors::DatabaseError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::{env, fs};
+use ulid::Ulid;
+
+/// A temporary file of the rows an executor cannot keep in memory, removed once dropped.
+pub(crate) struct SpillFile<T> {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    pub(crate) rows: usize,
+    _p: PhantomData<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> SpillFile<T> {
+    pub(crate) fn new() -> Result<Self, DatabaseError> {
+        let path = env::temp_dir().join(format!("kite_sql_{}.spill", Ulid::new()));
+        let writer = BufWriter::new(File::create(&path)?);
+
+        Ok(SpillFile {
+            path,
+            writer,
+            rows: 0,
+            _p: PhantomData,
+        })
+    }
+
+    pub(crate) fn write(&mut self, row: &T) -> Result<(), DatabaseError> {
+        bincode::serialize_into(&mut self.writer, row)?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    pub(crate) fn into_reader(mut self) -> Result<SpillReader<T>, DatabaseError> {
+        self.writer.flush()?;
+
+        Ok(SpillReader {
+            reader: BufReader::new(File::open(&self.path)?),
+            remaining: self.rows,
+            _file: self,
+        })
+    }
+}
+
+/// Reads the rows of a [`SpillFile`] in the order written, the file is removed once they are read.
+pub(crate) struct SpillReader<T> {
+    reader: BufReader<File>,
+    remaining: usize,
+    _file: SpillFile<T>,
+}
+
+impl<T: DeserializeOwned> Iterator for SpillReader<T> {
+    type Item = Result<T, DatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        Some(bincode::deserialize_from(&mut self.reader).map_err(DatabaseError::from))
+    }
+}
+
+impl<T> Drop for SpillFile<T> {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
@@ -1,12 +1,22 @@
 use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ReferenceSerialization)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
 pub enum AggKind {
     Avg,
     Max,
     Min,
     Sum,
     Count,
+    BoolAnd,
+    BoolOr,
+    /// `(asc, nulls_first)` of each `ORDER BY` key inside the call,
+    /// the keys follow the value in a tuple as the only argument.
+    FirstValue(Vec<(bool, bool)>),
+    LastValue(Vec<(bool, bool)>),
+    Arbitrary,
+    ArrayAgg(Vec<(bool, bool)>),
 }
 
 impl AggKind {
@@ -17,6 +27,23 @@ impl AggKind {
             AggKind::Min => false,
             AggKind::Sum => true,
             AggKind::Count => true,
+            AggKind::BoolAnd => false,
+            AggKind::BoolOr => false,
+            AggKind::FirstValue(_) => false,
+            AggKind::LastValue(_) => false,
+            AggKind::Arbitrary => false,
+            AggKind::ArrayAgg(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for AggKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AggKind::FirstValue(_) => write!(f, "FirstValue"),
+            AggKind::LastValue(_) => write!(f, "LastValue"),
+            AggKind::ArrayAgg(_) => write!(f, "ArrayAgg"),
+            kind => write!(f, "{:?}", kind),
         }
     }
 }
@@ -6,9 +6,9 @@ use crate::types::evaluator::EvaluatorFactory;
 use crate::types::tuple::Tuple;
 use crate::types::value::{DataValue, Utf8Type};
 use crate::types::LogicalType;
-use itertools::Itertools;
 use regex::Regex;
 use sqlparser::ast::{CharLengthUnits, TrimWhereField};
+use std::borrow::Cow;
 use std::cmp;
 use std::cmp::Ordering;
 
@@ -23,6 +23,58 @@ macro_rules! eval_to_num {
 }
 
 impl ScalarExpression {
+    /// The position of the value of the tuple the expression reads as it is, e.g. that of the
+    /// column it refers to.
+    pub(crate) fn tuple_position(&self, schema: &[ColumnRef]) -> Option<usize> {
+        match self {
+            ScalarExpression::ColumnRef(col) => schema
+                .iter()
+                .position(|tul_col| tul_col.summary() == col.summary()),
+            ScalarExpression::Alias { expr, alias } => schema
+                .iter()
+                .position(|tul_col| match alias {
+                    AliasType::Name(alias) => {
+                        tul_col.table_name().is_none() && tul_col.name() == alias
+                    }
+                    AliasType::Expr(alias_expr) => {
+                        alias_expr.output_column().summary() == tul_col.summary()
+                    }
+                })
+                .or_else(|| expr.tuple_position(schema)),
+            ScalarExpression::Reference { pos, .. } => Some(*pos),
+            _ => None,
+        }
+    }
+
+    /// Same as [`ScalarExpression::eval`], but the constants and the values of the tuple read
+    /// as they are, e.g. a column or its alias, are borrowed rather than copied.
+    pub fn eval_cow<'a>(
+        &'a self,
+        tuple: Option<(&'a Tuple, &[ColumnRef])>,
+    ) -> Result<Cow<'a, DataValue>, DatabaseError> {
+        match self {
+            ScalarExpression::Constant(val) => Ok(Cow::Borrowed(val)),
+            ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Alias { .. }
+            | ScalarExpression::Reference { .. } => {
+                let Some((tuple, schema)) = tuple else {
+                    return Ok(Cow::Owned(DataValue::Null));
+                };
+                match (self.tuple_position(schema), self) {
+                    (Some(i), _) => Ok(tuple
+                        .values
+                        .get(i)
+                        .map_or(Cow::Owned(DataValue::Null), Cow::Borrowed)),
+                    (None, ScalarExpression::Alias { expr, .. }) => {
+                        expr.eval_cow(Some((tuple, schema)))
+                    }
+                    (None, _) => Ok(Cow::Owned(DataValue::Null)),
+                }
+            }
+            _ => self.eval(tuple).map(Cow::Owned),
+        }
+    }
+
     pub fn eval(&self, tuple: Option<(&Tuple, &[ColumnRef])>) -> Result<DataValue, DatabaseError> {
         let check_cast = |value: DataValue, return_type: &LogicalType| {
             if value.logical_type() != *return_type {
@@ -32,40 +84,10 @@ impl ScalarExpression {
         };
 
         match self {
-            ScalarExpression::Constant(val) => Ok(val.clone()),
-            ScalarExpression::ColumnRef(col) => {
-                let Some((tuple, schema)) = tuple else {
-                    return Ok(DataValue::Null);
-                };
-                let value = schema
-                    .iter()
-                    .find_position(|tul_col| tul_col.summary() == col.summary())
-                    .map(|(i, _)| tuple.values[i].clone())
-                    .unwrap_or(DataValue::Null);
-
-                Ok(value)
-            }
-            ScalarExpression::Alias { expr, alias } => {
-                let Some((tuple, schema)) = tuple else {
-                    return Ok(DataValue::Null);
-                };
-                if let Some(value) = schema
-                    .iter()
-                    .find_position(|tul_col| match alias {
-                        AliasType::Name(alias) => {
-                            tul_col.table_name().is_none() && tul_col.name() == alias
-                        }
-                        AliasType::Expr(alias_expr) => {
-                            alias_expr.output_column().summary() == tul_col.summary()
-                        }
-                    })
-                    .map(|(i, _)| tuple.values[i].clone())
-                {
-                    return Ok(value.clone());
-                }
-
-                expr.eval(Some((tuple, schema)))
-            }
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Alias { .. }
+            | ScalarExpression::Reference { .. } => self.eval_cow(tuple).map(Cow::into_owned),
             ScalarExpression::TypeCast { expr, ty, .. } => Ok(expr.eval(tuple)?.cast(ty)?),
             ScalarExpression::Binary {
                 left_expr,
@@ -73,8 +95,8 @@ impl ScalarExpression {
                 evaluator,
                 ..
             } => {
-                let left = left_expr.eval(tuple)?;
-                let right = right_expr.eval(tuple)?;
+                let left = left_expr.eval_cow(tuple)?;
+                let right = right_expr.eval_cow(tuple)?;
 
                 evaluator
                     .as_ref()
@@ -83,7 +105,7 @@ impl ScalarExpression {
                     .binary_eval(&left, &right)
             }
             ScalarExpression::IsNull { expr, negated } => {
-                let mut is_null = expr.eval(tuple)?.is_null();
+                let mut is_null = expr.eval_cow(tuple)?.is_null();
                 if *negated {
                     is_null = !is_null;
                 }
@@ -94,13 +116,13 @@ impl ScalarExpression {
                 args,
                 negated,
             } => {
-                let value = expr.eval(tuple)?;
+                let value = expr.eval_cow(tuple)?;
                 if value.is_null() {
                     return Ok(DataValue::Null);
                 }
                 let mut is_in = false;
                 for arg in args {
-                    let arg_value = arg.eval(tuple)?;
+                    let arg_value = arg.eval_cow(tuple)?;
 
                     if arg_value.is_null() {
                         return Ok(DataValue::Null);
@@ -118,13 +140,13 @@ impl ScalarExpression {
             ScalarExpression::Unary {
                 expr, evaluator, ..
             } => {
-                let value = expr.eval(tuple)?;
+                let value = expr.eval_cow(tuple)?;
 
-                Ok(evaluator
+                evaluator
                     .as_ref()
                     .ok_or(DatabaseError::EvaluatorNotFound)?
                     .0
-                    .unary_eval(&value))
+                    .unary_eval(&value)
             }
             ScalarExpression::AggCall { .. } => {
                 unreachable!("must use `NormalizationRuleImpl::ExpressionRemapper`")
@@ -135,9 +157,9 @@ impl ScalarExpression {
                 right_expr,
                 negated,
             } => {
-                let value = expr.eval(tuple)?;
-                let left = left_expr.eval(tuple)?;
-                let right = right_expr.eval(tuple)?;
+                let value = expr.eval_cow(tuple)?;
+                let left = left_expr.eval_cow(tuple)?;
+                let right = right_expr.eval_cow(tuple)?;
 
                 let mut is_between = match (
                     value.partial_cmp(&left).map(Ordering::is_ge),
@@ -249,12 +271,6 @@ impl ScalarExpression {
                     Ok(DataValue::Null)
                 }
             }
-            ScalarExpression::Reference { pos, .. } => {
-                let Some((tuple, _)) = tuple else {
-                    return Ok(DataValue::Null);
-                };
-                Ok(tuple.values.get(*pos).cloned().unwrap_or(DataValue::Null))
-            }
             ScalarExpression::Tuple(exprs) => {
                 let mut values = Vec::with_capacity(exprs.len());
 
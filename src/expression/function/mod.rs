@@ -1,5 +1,8 @@
 use crate::types::LogicalType;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Formatter;
 
 pub mod scala;
 pub mod table;
@@ -9,3 +12,9 @@ pub struct FunctionSummary {
     pub name: String,
     pub arg_types: Vec<LogicalType>,
 }
+
+impl fmt::Display for FunctionSummary {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}({})", self.name, self.arg_types.iter().join(", "))
+    }
+}
@@ -18,6 +18,17 @@ use std::sync::Arc;
 /// - `Some(false)` monotonically decreasing
 pub type FuncMonotonicity = Vec<Option<bool>>;
 
+/// How the result of a function varies between its calls with the same arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuncVolatility {
+    /// Always the same result, e.g. `lower(s)`, called on constants it is folded into one
+    Immutable,
+    /// The same result within a statement, e.g. `current_date()`
+    Stable,
+    /// Another result on each call, e.g. `random()`, never folded nor merged with another call
+    Volatile,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArcScalarFunctionImpl(pub Arc<dyn ScalarFunctionImpl>);
 
@@ -63,6 +74,12 @@ pub trait ScalarFunctionImpl: Debug + Send + Sync {
     fn return_type(&self) -> &LogicalType;
 
     fn summary(&self) -> &FunctionSummary;
+
+    /// Functions are volatile unless they declare otherwise, since folding or merging calls of
+    /// a volatile function changes the results.
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Volatile
+    }
 }
 
 impl ScalarFunction {
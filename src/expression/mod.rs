@@ -1,7 +1,7 @@
 use self::agg::AggKind;
 use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
 use crate::errors::DatabaseError;
-use crate::expression::function::scala::ScalarFunction;
+use crate::expression::function::scala::{FuncVolatility, ScalarFunction};
 use crate::expression::function::table::TableFunction;
 use crate::expression::visitor::{walk_expr, Visitor};
 use crate::expression::visitor_mut::{walk_mut_expr, VisitorMut};
@@ -14,8 +14,11 @@ use sqlparser::ast::TrimWhereField;
 use sqlparser::ast::{
     BinaryOperator as SqlBinaryOperator, CharLengthUnits, UnaryOperator as SqlUnaryOperator,
 };
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
+use std::rc::Rc;
 use std::{fmt, mem};
 
 pub mod agg;
@@ -133,9 +136,16 @@ pub enum ScalarExpression {
     },
 }
 
+/// Replaces the expressions computed by the child with references to its output.
+///
+/// The calls of a volatile function are not merged: each of them takes another output giving
+/// the same expression, e.g. `sum(random()), sum(random())` read two aggregations. A
+/// [`TryReference`] is cloned to rewrite the expressions of an operator, its clones share the
+/// outputs taken.
 #[derive(Clone)]
 pub struct TryReference<'a> {
     output_exprs: &'a [ScalarExpression],
+    taken: Rc<RefCell<HashSet<usize>>>,
 }
 
 impl<'a> VisitorMut<'a> for TryReference<'a> {
@@ -145,12 +155,21 @@ impl<'a> VisitorMut<'a> for TryReference<'a> {
 
         let fn_output_column = |expr: &ScalarExpression| expr.output_column();
         let self_column = fn_output_column(&clone_expr);
-
-        *expr = if let Some((pos, _)) = self
+        let is_volatile = clone_expr.has_volatile_function();
+        let position = self
             .output_exprs
             .iter()
-            .find_position(|expr| self_column.summary() == fn_output_column(expr).summary())
-        {
+            .enumerate()
+            .find_map(|(pos, expr)| {
+                (self_column.summary() == fn_output_column(expr).summary()
+                    && !(is_volatile && self.taken.borrow().contains(&pos)))
+                .then_some(pos)
+            });
+        if is_volatile {
+            self.taken.borrow_mut().extend(position);
+        }
+
+        *expr = if let Some(pos) = position {
             ScalarExpression::Reference {
                 expr: Box::new(clone_expr),
                 pos,
@@ -164,7 +183,10 @@ impl<'a> VisitorMut<'a> for TryReference<'a> {
 
 impl<'a> TryReference<'a> {
     pub fn new(output_exprs: &'a [ScalarExpression]) -> TryReference<'a> {
-        TryReference { output_exprs }
+        TryReference {
+            output_exprs,
+            taken: Default::default(),
+        }
     }
 }
 
@@ -189,6 +211,7 @@ impl VisitorMut<'_> for BindEvaluator {
                     LogicalType::USmallint => LogicalType::Smallint,
                     LogicalType::UInteger => LogicalType::Integer,
                     LogicalType::UBigint => LogicalType::Bigint,
+                    LogicalType::UHugeint => LogicalType::Hugeint,
                     _ => unreachable!(),
                 },
             }
@@ -209,8 +232,6 @@ impl VisitorMut<'_> for BindEvaluator {
         self.visit(left_expr)?;
         self.visit(right_expr)?;
 
-        let ty =
-            LogicalType::max_logical_type(&left_expr.return_type(), &right_expr.return_type())?;
         let fn_cast = |expr: &mut ScalarExpression, ty: LogicalType| {
             if expr.return_type() != ty {
                 *expr = ScalarExpression::TypeCast {
@@ -219,6 +240,19 @@ impl VisitorMut<'_> for BindEvaluator {
                 }
             }
         };
+        if let Some(temporal) = EvaluatorFactory::temporal_binary_create(
+            &left_expr.return_type(),
+            &right_expr.return_type(),
+            *op,
+        )? {
+            fn_cast(left_expr, temporal.left_ty);
+            fn_cast(right_expr, temporal.right_ty);
+            *evaluator = Some(temporal.evaluator);
+
+            return Ok(());
+        }
+        let ty =
+            LogicalType::max_logical_type(&left_expr.return_type(), &right_expr.return_type())?;
         fn_cast(left_expr, ty.clone());
         fn_cast(right_expr, ty.clone());
 
@@ -257,6 +291,71 @@ impl Visitor<'_> for HasCountStar {
     }
 }
 
+/// Marks expressions that should not be evaluated inline by a fused scan,
+/// e.g. user defined functions and aggregations.
+#[derive(Default)]
+pub struct HasComplexExpr {
+    pub value: bool,
+}
+
+impl Visitor<'_> for HasComplexExpr {
+    fn visit_agg(
+        &mut self,
+        _distinct: bool,
+        _kind: &'_ AggKind,
+        _args: &'_ [ScalarExpression],
+        _ty: &'_ LogicalType,
+    ) -> Result<(), DatabaseError> {
+        self.value = true;
+        Ok(())
+    }
+
+    fn visit_scala_function(&mut self, _: &'_ ScalarFunction) -> Result<(), DatabaseError> {
+        self.value = true;
+        Ok(())
+    }
+
+    fn visit_table_function(&mut self, _: &'_ TableFunction) -> Result<(), DatabaseError> {
+        self.value = true;
+        Ok(())
+    }
+
+    fn visit(&mut self, expr: &'_ ScalarExpression) -> Result<(), DatabaseError> {
+        if !self.value {
+            walk_expr(self, expr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the calls of a [`FuncVolatility::Volatile`] function.
+#[derive(Default)]
+pub struct HasVolatileFunction {
+    pub value: bool,
+}
+
+impl Visitor<'_> for HasVolatileFunction {
+    fn visit_scala_function(
+        &mut self,
+        scalar_function: &'_ ScalarFunction,
+    ) -> Result<(), DatabaseError> {
+        if scalar_function.inner.volatility() == FuncVolatility::Volatile {
+            self.value = true;
+        }
+        for arg in &scalar_function.args {
+            self.visit(arg)?;
+        }
+        Ok(())
+    }
+
+    fn visit(&mut self, expr: &'_ ScalarExpression) -> Result<(), DatabaseError> {
+        if !self.value {
+            walk_expr(self, expr)?;
+        }
+        Ok(())
+    }
+}
+
 impl ScalarExpression {
     pub fn unpack_alias(self) -> ScalarExpression {
         if let ScalarExpression::Alias {
@@ -477,6 +576,12 @@ impl ScalarExpression {
         exprs
     }
 
+    pub fn has_volatile_function(&self) -> bool {
+        let mut visitor = HasVolatileFunction::default();
+
+        visitor.visit(self).is_ok() && visitor.value
+    }
+
     pub fn has_table_ref_column(&self) -> bool {
         match self {
             ScalarExpression::Constant(_) => false,
@@ -732,7 +837,7 @@ impl ScalarExpression {
                     }
                 };
                 format!(
-                    "{:?}({}{})",
+                    "{}({}{})",
                     kind,
                     op(kind.allow_distinct(), *distinct),
                     args_str
@@ -1067,10 +1172,10 @@ mod test {
 
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
 
-        build_table(&table_cache, &mut transaction)?;
+        build_table(&table_cache, &transaction)?;
 
         let mut cursor = Cursor::new(Vec::new());
         let mut reference_tables = ReferenceTables::new();
@@ -1107,7 +1212,7 @@ mod test {
             &mut cursor,
             ScalarExpression::ColumnRef(ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c3".to_string(),
+                    name: "c3".into(),
                     relation: ColumnRelation::Table {
                         column_id: c3_column_id,
                         table_name: Arc::new("t1".to_string()),
@@ -1125,7 +1230,7 @@ mod test {
             &mut cursor,
             ScalarExpression::ColumnRef(ColumnRef::from(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c4".to_string(),
+                    name: "c4".into(),
                     relation: ColumnRelation::None,
                 },
                 false,
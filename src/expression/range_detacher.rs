@@ -1,10 +1,11 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::{BinaryOperator, ScalarExpression};
-use crate::types::value::{DataValue, NULL_VALUE};
-use crate::types::ColumnId;
+use crate::types::value::{DataValue, Utf8Type, NULL_VALUE};
+use crate::types::{ColumnId, LogicalType};
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
+use sqlparser::ast::CharLengthUnits;
 use std::cmp::Ordering;
 use std::collections::Bound;
 use std::fmt::Formatter;
@@ -188,7 +189,10 @@ impl<'a> RangeDetacher<'a> {
                 right_expr,
                 op,
                 ..
-            } => match (self.detach(left_expr)?, self.detach(right_expr)?) {
+            } => match (
+                self.detach_operand(*op, left_expr)?,
+                self.detach_operand(*op, right_expr)?,
+            ) {
                 (Some(left_binary), Some(right_binary)) => {
                     Self::merge_binary(*op, left_binary, right_binary)
                 }
@@ -253,6 +257,10 @@ impl<'a> RangeDetacher<'a> {
                 | ScalarExpression::Reference { .. }
                 | ScalarExpression::Empty => unreachable!(),
             },
+            // a filter that is always false or NULL takes no row
+            ScalarExpression::Constant(DataValue::Boolean(false) | DataValue::Null) => {
+                Some(Range::Dummy)
+            }
             ScalarExpression::Constant(_) | ScalarExpression::ColumnRef(_) => None,
             // FIXME: support [RangeDetacher::_detach]
             ScalarExpression::Tuple(_)
@@ -714,8 +722,12 @@ impl<'a> RangeDetacher<'a> {
         if !Self::_is_belong(self.table_name, &col) || col.id() != Some(*self.column_id) {
             return Ok(None);
         }
-        if &val.logical_type() != col.datatype() {
-            val = val.cast(col.datatype())?
+        match (op, col.datatype()) {
+            (BinaryOperator::Like(escape_char), LogicalType::Varchar(len, unit)) if !is_flip => {
+                return Ok(Self::like_range(val, escape_char, *len, *unit));
+            }
+            (BinaryOperator::Like(_) | BinaryOperator::NotLike(_), _) => return Ok(None),
+            _ => (),
         }
         if is_flip {
             op = match op {
@@ -726,6 +738,68 @@ impl<'a> RangeDetacher<'a> {
                 source_op => source_op,
             };
         }
+        if &val.logical_type() != col.datatype() {
+            let cast_val = match (val.clone(), col.datatype()) {
+                // the keys are the bytes of the strings, a longer one than the column holds still
+                // bounds them
+                (DataValue::Utf8 { value, .. }, LogicalType::Varchar(len, unit)) => {
+                    DataValue::Utf8 {
+                        value,
+                        ty: Utf8Type::Variable(*len),
+                        unit: *unit,
+                    }
+                }
+                (DataValue::Utf8 { value, .. }, LogicalType::Char(len, unit)) => DataValue::Utf8 {
+                    value,
+                    ty: Utf8Type::Fixed(*len),
+                    unit: *unit,
+                },
+                (_, ty) => match val.clone().cast(ty) {
+                    Ok(val) => val,
+                    Err(DatabaseError::OverFlow | DatabaseError::TryFromInt(_))
+                        if ty.is_numeric() =>
+                    {
+                        return Self::out_of_bounds_range(op, val);
+                    }
+                    Err(err) => return Err(err),
+                },
+            };
+            // compared in a wider type than the column's, the value may fall between two of the
+            // column, e.g. `c_int < 1.5` is `c_int <= 1` and `c_int = 1.5` matches no row
+            if let Ok(compared_ty) =
+                LogicalType::max_logical_type(&val.logical_type(), col.datatype())
+            {
+                if &compared_ty != col.datatype() {
+                    let ordering = cast_val
+                        .clone()
+                        .cast(&compared_ty)
+                        .ok()
+                        .zip(val.cast(&compared_ty).ok())
+                        .and_then(|(cast_val, val)| cast_val.partial_cmp(&val));
+
+                    op = match (op, ordering) {
+                        (op, Some(Ordering::Equal) | None) => op,
+                        (BinaryOperator::Eq | BinaryOperator::Spaceship, _) => {
+                            return Ok(Some(Range::Dummy));
+                        }
+                        (BinaryOperator::Gt | BinaryOperator::GtEq, Some(Ordering::Less)) => {
+                            BinaryOperator::Gt
+                        }
+                        (BinaryOperator::Gt | BinaryOperator::GtEq, Some(Ordering::Greater)) => {
+                            BinaryOperator::GtEq
+                        }
+                        (BinaryOperator::Lt | BinaryOperator::LtEq, Some(Ordering::Less)) => {
+                            BinaryOperator::LtEq
+                        }
+                        (BinaryOperator::Lt | BinaryOperator::LtEq, Some(Ordering::Greater)) => {
+                            BinaryOperator::Lt
+                        }
+                        (op, _) => op,
+                    };
+                }
+            }
+            val = cast_val;
+        }
         Ok(match op {
             BinaryOperator::Gt => Some(Range::Scope {
                 min: Bound::Excluded(val.clone()),
@@ -748,8 +822,105 @@ impl<'a> RangeDetacher<'a> {
         })
     }
 
+    /// e.g. `c_u8 > -1` matches every value of the column and `c_u8 < -1` none of them.
+    fn out_of_bounds_range(
+        op: BinaryOperator,
+        val: DataValue,
+    ) -> Result<Option<Range>, DatabaseError> {
+        let is_below = val
+            .cast(&LogicalType::Double)?
+            .double()
+            .is_some_and(|val| val < 0.0);
+
+        Ok(match (op, is_below) {
+            (BinaryOperator::Gt | BinaryOperator::GtEq, true)
+            | (BinaryOperator::Lt | BinaryOperator::LtEq, false) => None,
+            (
+                BinaryOperator::Eq
+                | BinaryOperator::Spaceship
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq,
+                _,
+            ) => Some(Range::Dummy),
+            _ => None,
+        })
+    }
+
+    /// `c1 like 'abc%'` only matches strings in `['abc', 'abd')`,
+    /// and a pattern without wildcards matches the unescaped pattern itself.
+    fn like_range(
+        pattern: DataValue,
+        escape_char: Option<char>,
+        len: Option<u32>,
+        unit: CharLengthUnits,
+    ) -> Option<Range> {
+        let DataValue::Utf8 { value: pattern, .. } = pattern else {
+            return None;
+        };
+        let mut prefix = String::new();
+        let mut chars = pattern.chars();
+        let mut is_exact = true;
+
+        while let Some(c) = chars.next() {
+            if Some(c) == escape_char {
+                if let Some(next_char) = chars.next() {
+                    prefix.push(next_char);
+                }
+            } else if c == '%' || c == '_' {
+                is_exact = false;
+                break;
+            } else {
+                prefix.push(c);
+            }
+        }
+        let utf8 = |value| DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(len),
+            unit,
+        };
+        if is_exact {
+            return Some(Range::Eq(utf8(prefix)));
+        }
+        if prefix.is_empty() {
+            return None;
+        }
+        // the smallest string greater than every string starting with the prefix
+        let mut upper = prefix.clone();
+        let max = loop {
+            let Some(c) = upper.pop() else {
+                break Bound::Unbounded;
+            };
+            if let Some(next_char) = (c as u32 + 1..=char::MAX as u32).find_map(char::from_u32) {
+                upper.push(next_char);
+                break Bound::Excluded(utf8(upper));
+            }
+        };
+
+        Some(Range::Scope {
+            min: Bound::Included(utf8(prefix)),
+            max,
+        })
+    }
+
     /// check if: `c1 > c2 or c1 > 1` or `c2 > 1 or c1 > 1`
     /// this case it makes no sense to just extract c1 > 1
+    /// Detaches a side of a binary expression, a constant being a range only as a side of `AND`
+    /// or `OR`, where a `false` or NULL one takes no row.
+    fn detach_operand(
+        &mut self,
+        op: BinaryOperator,
+        expr: &ScalarExpression,
+    ) -> Result<Option<Range>, DatabaseError> {
+        if matches!(expr, ScalarExpression::Constant(_))
+            && !matches!(op, BinaryOperator::And | BinaryOperator::Or)
+        {
+            return Ok(None);
+        }
+        self.detach(expr)
+    }
+
     fn check_or(&mut self, op: &BinaryOperator, binary: Range) -> Option<Range> {
         if matches!(op, BinaryOperator::Or) {
             return None;
@@ -792,8 +963,10 @@ impl fmt::Display for Range {
 #[cfg(test)]
 mod test {
     use crate::binder::test::build_t1_table;
+    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
     use crate::errors::DatabaseError;
     use crate::expression::range_detacher::{Range, RangeDetacher};
+    use crate::expression::BinaryOperator;
     use crate::optimizer::heuristic::batch::HepBatchStrategy;
     use crate::optimizer::heuristic::optimizer::HepOptimizer;
     use crate::optimizer::rule::normalization::NormalizationRuleImpl;
@@ -803,8 +976,13 @@ mod test {
     use crate::storage::rocksdb::RocksTransaction;
     use crate::types::evaluator::tuple::TupleLtBinaryEvaluator;
     use crate::types::evaluator::BinaryEvaluator;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
+    use crate::types::LogicalType;
+    use rust_decimal::Decimal;
+    use sqlparser::ast::CharLengthUnits;
     use std::ops::Bound;
+    use std::sync::Arc;
+    use ulid::Ulid;
 
     fn plan_filter(plan: LogicalPlan) -> Result<Option<FilterOperator>, DatabaseError> {
         let best_plan = HepOptimizer::new(plan.clone())
@@ -1298,6 +1476,176 @@ mod test {
     }
 
     // Tips: `null` should be First
+    #[test]
+    fn test_detach_like_cases() {
+        let utf8 = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+        let like_range = |pattern: &str, escape_char: Option<char>| {
+            RangeDetacher::like_range(
+                utf8(pattern),
+                escape_char,
+                None,
+                CharLengthUnits::Characters,
+            )
+        };
+
+        assert_eq!(
+            like_range("abc%", None),
+            Some(Range::Scope {
+                min: Bound::Included(utf8("abc")),
+                max: Bound::Excluded(utf8("abd")),
+            })
+        );
+        assert_eq!(
+            like_range("ab_d%", None),
+            Some(Range::Scope {
+                min: Bound::Included(utf8("ab")),
+                max: Bound::Excluded(utf8("ac")),
+            })
+        );
+        assert_eq!(
+            like_range("a@%b%", Some('@')),
+            Some(Range::Scope {
+                min: Bound::Included(utf8("a%b")),
+                max: Bound::Excluded(utf8("a%c")),
+            })
+        );
+        assert_eq!(
+            like_range("a\u{10FFFF}%", None),
+            Some(Range::Scope {
+                min: Bound::Included(utf8("a\u{10FFFF}")),
+                max: Bound::Excluded(utf8("b")),
+            })
+        );
+        assert_eq!(
+            like_range("\u{10FFFF}%", None),
+            Some(Range::Scope {
+                min: Bound::Included(utf8("\u{10FFFF}")),
+                max: Bound::Unbounded,
+            })
+        );
+        assert_eq!(like_range("abc", None), Some(Range::Eq(utf8("abc"))));
+        assert_eq!(like_range("%abc", None), None);
+        assert_eq!(like_range("_abc", None), None);
+        assert_eq!(
+            RangeDetacher::like_range(DataValue::Null, None, None, CharLengthUnits::Characters),
+            None
+        );
+    }
+
+    #[test]
+    fn test_detach_cast_cases() -> Result<(), DatabaseError> {
+        let column_id = Ulid::new();
+        let range = |ty: LogicalType, op: BinaryOperator, val: DataValue| {
+            let mut column = ColumnCatalog::new(
+                "c".to_string(),
+                true,
+                ColumnDesc::new(ty, None, false, None)?,
+            );
+            column.set_ref_table(Arc::new("t1".to_string()), column_id, false);
+
+            RangeDetacher::new("t1", &column_id).new_range(op, ColumnRef::from(column), val, false)
+        };
+        let utf8 = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+        let date = utf8("2024-01-01").cast(&LogicalType::Date)?;
+        let noon = utf8("2024-01-01 12:00:00").cast(&LogicalType::DateTime)?;
+
+        // compared as doubles
+        assert_eq!(
+            range(
+                LogicalType::Integer,
+                BinaryOperator::Lt,
+                DataValue::Float64(1.5.into())
+            )?,
+            Some(Range::Scope {
+                min: Bound::Unbounded,
+                max: Bound::Included(DataValue::Int32(1)),
+            })
+        );
+        assert_eq!(
+            range(
+                LogicalType::Integer,
+                BinaryOperator::GtEq,
+                DataValue::Float64((-1.5).into())
+            )?,
+            Some(Range::Scope {
+                min: Bound::Included(DataValue::Int32(-1)),
+                max: Bound::Unbounded,
+            })
+        );
+        assert_eq!(
+            range(
+                LogicalType::Integer,
+                BinaryOperator::Eq,
+                DataValue::Float64(1.5.into())
+            )?,
+            Some(Range::Dummy)
+        );
+        // compared as date times
+        assert_eq!(
+            range(LogicalType::Date, BinaryOperator::GtEq, noon.clone())?,
+            Some(Range::Scope {
+                min: Bound::Excluded(date.clone()),
+                max: Bound::Unbounded,
+            })
+        );
+        assert_eq!(
+            range(LogicalType::Date, BinaryOperator::Lt, noon)?,
+            Some(Range::Scope {
+                min: Bound::Unbounded,
+                max: Bound::Included(date.clone()),
+            })
+        );
+        // compared as dates
+        assert_eq!(
+            range(
+                LogicalType::Date,
+                BinaryOperator::Gt,
+                utf8("2024-01-01 12:00:00")
+            )?,
+            Some(Range::Scope {
+                min: Bound::Excluded(date.clone()),
+                max: Bound::Unbounded,
+            })
+        );
+        assert_eq!(
+            range(
+                LogicalType::Decimal(Some(10), Some(2)),
+                BinaryOperator::Gt,
+                DataValue::Int32(1)
+            )?,
+            Some(Range::Scope {
+                min: Bound::Excluded(DataValue::Decimal(Decimal::new(1, 0))),
+                max: Bound::Unbounded,
+            })
+        );
+        // longer than the column
+        assert_eq!(
+            range(
+                LogicalType::Varchar(Some(3), CharLengthUnits::Characters),
+                BinaryOperator::Lt,
+                utf8("abcd")
+            )?,
+            Some(Range::Scope {
+                min: Bound::Unbounded,
+                max: Bound::Excluded(DataValue::Utf8 {
+                    value: "abcd".to_string(),
+                    ty: Utf8Type::Variable(Some(3)),
+                    unit: CharLengthUnits::Characters,
+                }),
+            })
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_detach_null_cases() -> Result<(), DatabaseError> {
         let table_state = build_t1_table()?;
@@ -1309,7 +1657,7 @@ mod test {
                 .detach(&op.predicate)?
                 .unwrap();
             println!("c1 = null => c1: {}", range);
-            assert_eq!(range, Range::Eq(DataValue::Null))
+            assert_eq!(range, Range::Dummy)
         }
         {
             let plan = table_state.plan("select * from t1 where c1 = null or c1 = 1")?;
@@ -1318,13 +1666,7 @@ mod test {
                 .detach(&op.predicate)?
                 .unwrap();
             println!("c1 = null or c1 = 1 => c1: {}", range);
-            assert_eq!(
-                range,
-                Range::SortedRanges(vec![
-                    Range::Eq(DataValue::Null),
-                    Range::Eq(DataValue::Int32(1))
-                ])
-            )
+            assert_eq!(range, Range::Eq(DataValue::Int32(1)))
         }
         {
             let plan = table_state.plan("select * from t1 where c1 = null or c1 < 5")?;
@@ -1351,13 +1693,10 @@ mod test {
             println!("c1 = null or (c1 > 1 and c1 < 5) => c1: {}", range);
             assert_eq!(
                 range,
-                Range::SortedRanges(vec![
-                    Range::Eq(DataValue::Null),
-                    Range::Scope {
-                        min: Bound::Excluded(DataValue::Int32(1)),
-                        max: Bound::Excluded(DataValue::Int32(5)),
-                    },
-                ])
+                Range::Scope {
+                    min: Bound::Excluded(DataValue::Int32(1)),
+                    max: Bound::Excluded(DataValue::Int32(5)),
+                }
             )
         }
         {
@@ -1367,7 +1706,7 @@ mod test {
                 .detach(&op.predicate)?
                 .unwrap();
             println!("c1 = null and c1 < 5 => c1: {}", range);
-            assert_eq!(range, Range::Eq(DataValue::Null))
+            assert_eq!(range, Range::Dummy)
         }
         {
             let plan =
@@ -1386,7 +1725,7 @@ mod test {
             let range = RangeDetacher::new("t1", table_state.column_id_by_name("c1"))
                 .detach(&op.predicate)?;
             println!("c1 != null => c1: {:#?}", range);
-            assert_eq!(range, None)
+            assert_eq!(range, Some(Range::Dummy))
         }
         {
             let plan = table_state.plan("select * from t1 where c1 = null or c1 != 1")?;
@@ -1402,7 +1741,13 @@ mod test {
             let range = RangeDetacher::new("t1", table_state.column_id_by_name("c1"))
                 .detach(&op.predicate)?;
             println!("c1 != null or c1 < 5 => c1: {:#?}", range);
-            assert_eq!(range, None)
+            assert_eq!(
+                range,
+                Some(Range::Scope {
+                    min: Bound::Unbounded,
+                    max: Bound::Excluded(DataValue::Int32(5)),
+                })
+            )
         }
         {
             let plan =
@@ -1411,7 +1756,13 @@ mod test {
             let range = RangeDetacher::new("t1", table_state.column_id_by_name("c1"))
                 .detach(&op.predicate)?;
             println!("c1 != null or (c1 > 1 and c1 < 5) => c1: {:#?}", range);
-            assert_eq!(range, None)
+            assert_eq!(
+                range,
+                Some(Range::Scope {
+                    min: Bound::Excluded(DataValue::Int32(1)),
+                    max: Bound::Excluded(DataValue::Int32(5)),
+                })
+            )
         }
         {
             let plan = table_state.plan("select * from t1 where c1 != null and c1 < 5")?;
@@ -1420,13 +1771,7 @@ mod test {
                 .detach(&op.predicate)?
                 .unwrap();
             println!("c1 != null and c1 < 5 => c1: {}", range);
-            assert_eq!(
-                range,
-                Range::Scope {
-                    min: Bound::Unbounded,
-                    max: Bound::Excluded(DataValue::Int32(5)),
-                }
-            )
+            assert_eq!(range, Range::Dummy)
         }
         {
             let plan =
@@ -1436,13 +1781,7 @@ mod test {
                 .detach(&op.predicate)?
                 .unwrap();
             println!("c1 != null and (c1 > 1 and c1 < 5) => c1: {}", range);
-            assert_eq!(
-                range,
-                Range::Scope {
-                    min: Bound::Excluded(DataValue::Int32(1)),
-                    max: Bound::Excluded(DataValue::Int32(5)),
-                }
-            )
+            assert_eq!(range, Range::Dummy)
         }
         {
             let plan = table_state.plan("select * from t1 where (c1 = null or (c1 < 2 and c1 > 0) or (c1 < 6 and c1 > 4)) or ((c1 < 3 and c1 > 1) or (c1 < 7 and c1 > 5))")?;
@@ -1454,7 +1793,6 @@ mod test {
             assert_eq!(
                 range,
                 Range::SortedRanges(vec![
-                    Range::Eq(DataValue::Null),
                     Range::Scope {
                         min: Bound::Excluded(DataValue::Int32(0)),
                         max: Bound::Excluded(DataValue::Int32(3)),
@@ -1476,7 +1814,6 @@ mod test {
             assert_eq!(
                 range,
                 Range::SortedRanges(vec![
-                    Range::Eq(DataValue::Null),
                     Range::Scope {
                         min: Bound::Excluded(DataValue::Int32(0)),
                         max: Bound::Excluded(DataValue::Int32(3)),
@@ -1,10 +1,12 @@
-use crate::catalog::ColumnRef;
+use crate::catalog::{ColumnRef, ColumnSummary};
 use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncVolatility, ScalarFunction};
 use crate::expression::visitor_mut::{walk_mut_expr, VisitorMut};
 use crate::expression::{BinaryOperator, ScalarExpression, UnaryOperator};
 use crate::types::evaluator::EvaluatorFactory;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
+use std::collections::HashSet;
 use std::mem;
 
 #[derive(Debug)]
@@ -44,11 +46,11 @@ impl VisitorMut<'_> for ConstantCalculator {
 
                 if let ScalarExpression::Constant(unary_val) = arg_expr.as_ref() {
                     let value = if let Some(evaluator) = evaluator {
-                        evaluator.0.unary_eval(unary_val)
+                        evaluator.0.unary_eval(unary_val)?
                     } else {
                         EvaluatorFactory::unary_create(ty.clone(), *op)?
                             .0
-                            .unary_eval(unary_val)
+                            .unary_eval(unary_val)?
                     };
                     let _ = mem::replace(expr, ScalarExpression::Constant(value));
                 }
@@ -59,10 +61,25 @@ impl VisitorMut<'_> for ConstantCalculator {
                 right_expr,
                 ..
             } => {
-                let ty = LogicalType::max_logical_type(
-                    &left_expr.return_type(),
-                    &right_expr.return_type(),
-                )?;
+                let (left_ty, right_ty, temporal_evaluator) =
+                    match EvaluatorFactory::temporal_binary_create(
+                        &left_expr.return_type(),
+                        &right_expr.return_type(),
+                        *op,
+                    )? {
+                        Some(temporal) => (
+                            temporal.left_ty,
+                            temporal.right_ty,
+                            Some(temporal.evaluator),
+                        ),
+                        None => {
+                            let ty = LogicalType::max_logical_type(
+                                &left_expr.return_type(),
+                                &right_expr.return_type(),
+                            )?;
+                            (ty.clone(), ty, None)
+                        }
+                    };
                 self.visit(left_expr)?;
                 self.visit(right_expr)?;
 
@@ -71,18 +88,36 @@ impl VisitorMut<'_> for ConstantCalculator {
                     ScalarExpression::Constant(right_val),
                 ) = (left_expr.as_mut(), right_expr.as_mut())
                 {
-                    let evaluator = EvaluatorFactory::binary_create(ty.clone(), *op)?;
+                    let evaluator = match temporal_evaluator {
+                        Some(evaluator) => evaluator,
+                        None => EvaluatorFactory::binary_create(left_ty.clone(), *op)?,
+                    };
 
-                    if left_val.logical_type() != ty {
-                        *left_val = left_val.clone().cast(&ty)?;
+                    if left_val.logical_type() != left_ty {
+                        *left_val = left_val.clone().cast(&left_ty)?;
                     }
-                    if right_val.logical_type() != ty {
-                        *right_val = right_val.clone().cast(&ty)?;
+                    if right_val.logical_type() != right_ty {
+                        *right_val = right_val.clone().cast(&right_ty)?;
                     }
                     let value = evaluator.0.binary_eval(left_val, right_val)?;
                     let _ = mem::replace(expr, ScalarExpression::Constant(value));
                 }
             }
+            ScalarExpression::ScalaFunction(ScalarFunction { args, inner }) => {
+                for arg in args.iter_mut() {
+                    self.visit(arg)?;
+                }
+                // a stable function, e.g. `current_date()`, is left to the execution of the
+                // statement, which may not be the one planning it
+                if inner.volatility() == FuncVolatility::Immutable
+                    && args
+                        .iter()
+                        .all(|arg| matches!(arg, ScalarExpression::Constant(_)))
+                {
+                    let value = inner.eval(args, None)?;
+                    let _ = mem::replace(expr, ScalarExpression::Constant(value));
+                }
+            }
             _ => walk_mut_expr(self, expr)?,
         }
 
@@ -93,6 +128,8 @@ impl VisitorMut<'_> for ConstantCalculator {
 #[derive(Debug, Default)]
 pub struct Simplify {
     replaces: Vec<Replace>,
+    /// The columns known to hold no NULL where the expression is evaluated
+    not_null_columns: HashSet<ColumnSummary>,
 }
 
 impl VisitorMut<'_> for Simplify {
@@ -129,7 +166,21 @@ impl VisitorMut<'_> for Simplify {
                 // `(c1 - 1) and (c1 + 2)` cannot fix!
                 self.fix_expr(right_expr, left_expr, op)?;
 
-                if Self::is_arithmetic(op) {
+                if let Some(folded) = Self::fold_binary(*op, left_expr, right_expr) {
+                    let _ = mem::replace(expr, folded);
+                    return Ok(());
+                }
+
+                // the arithmetic of dates is left in place, e.g. `d + 30 > '2024-03-15'` is not
+                // `d > '2024-03-15' - 30` until the text is read as a date
+                let is_temporal = EvaluatorFactory::temporal_binary_create(
+                    &left_expr.return_type(),
+                    &right_expr.return_type(),
+                    *op,
+                )?
+                .is_some();
+
+                if Self::is_arithmetic(op) && !is_temporal {
                     match (left_expr.unpack_col(false), right_expr.unpack_col(false)) {
                         (Some(col), None) => {
                             self.replaces.push(Replace::Binary(ReplaceBinary {
@@ -191,6 +242,37 @@ impl VisitorMut<'_> for Simplify {
                         expr,
                         ScalarExpression::Constant(DataValue::Boolean(val.is_null())),
                     );
+                } else if let ScalarExpression::IsNull {
+                    negated,
+                    expr: arg_expr,
+                } = expr
+                {
+                    if self.is_not_null(arg_expr) {
+                        let value = DataValue::Boolean(*negated);
+                        let _ = mem::replace(expr, ScalarExpression::Constant(value));
+                    }
+                }
+            }
+            ScalarExpression::Coalesce { exprs, ty } => {
+                for arg in exprs.iter_mut() {
+                    self.visit(arg)?;
+                }
+                // the arguments after one that is never NULL are never reached
+                if let Some(i) = exprs.iter().position(|arg| self.is_not_null(arg)) {
+                    exprs.truncate(i + 1);
+                }
+                if exprs.len() == 1 {
+                    let ty = ty.clone();
+                    let arg = exprs.remove(0);
+                    let arg = if arg.return_type() == ty {
+                        arg
+                    } else {
+                        ScalarExpression::TypeCast {
+                            expr: Box::new(arg),
+                            ty,
+                        }
+                    };
+                    let _ = mem::replace(expr, arg);
                 }
             }
             ScalarExpression::In {
@@ -281,6 +363,63 @@ impl VisitorMut<'_> for Simplify {
 }
 
 impl Simplify {
+    /// Simplifies also with the columns holding no NULL, e.g. `c1 IS NULL` into `false`.
+    pub fn with_not_null_columns(not_null_columns: HashSet<ColumnSummary>) -> Self {
+        Simplify {
+            replaces: Vec::new(),
+            not_null_columns,
+        }
+    }
+
+    fn is_not_null(&self, expr: &ScalarExpression) -> bool {
+        match expr.unpack_alias_ref() {
+            ScalarExpression::ColumnRef(column) => self.not_null_columns.contains(column.summary()),
+            ScalarExpression::Constant(value) => !value.is_null(),
+            _ => false,
+        }
+    }
+
+    /// The binary expressions whose value does not depend on one of their sides: a comparison
+    /// with NULL is NULL, `x AND false` is false and `x OR true` is true, while `x AND true` and
+    /// `x OR false` are `x`.
+    fn fold_binary(
+        op: BinaryOperator,
+        left_expr: &mut ScalarExpression,
+        right_expr: &mut ScalarExpression,
+    ) -> Option<ScalarExpression> {
+        let is_null = |expr: &ScalarExpression| expr.unpack_val().is_some_and(|val| val.is_null());
+        let as_bool = |expr: &ScalarExpression| match expr.unpack_val() {
+            Some(DataValue::Boolean(value)) => Some(value),
+            _ => None,
+        };
+
+        match op {
+            BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Gt
+            | BinaryOperator::GtEq
+            | BinaryOperator::Lt
+            | BinaryOperator::LtEq
+                if is_null(left_expr) || is_null(right_expr) =>
+            {
+                Some(ScalarExpression::Constant(DataValue::Null))
+            }
+            BinaryOperator::And | BinaryOperator::Or => {
+                let absorbing = op == BinaryOperator::Or;
+
+                match (as_bool(left_expr), as_bool(right_expr)) {
+                    (Some(value), _) | (_, Some(value)) if value == absorbing => {
+                        Some(ScalarExpression::Constant(DataValue::Boolean(value)))
+                    }
+                    (Some(_), _) => Some(mem::replace(right_expr, ScalarExpression::Empty)),
+                    (_, Some(_)) => Some(mem::replace(left_expr, ScalarExpression::Empty)),
+                    (None, None) => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     fn is_arithmetic(op: &mut BinaryOperator) -> bool {
         matches!(
             op,
@@ -442,12 +581,13 @@ impl ScalarExpression {
             } => {
                 let value = expr.unpack_val()?;
                 let unary_value = if let Some(evaluator) = evaluator {
-                    evaluator.0.unary_eval(&value)
+                    evaluator.0.unary_eval(&value).ok()?
                 } else {
                     EvaluatorFactory::unary_create(ty.clone(), *op)
                         .ok()?
                         .0
                         .unary_eval(&value)
+                        .ok()?
                 };
                 Some(unary_value)
             }
@@ -461,6 +601,19 @@ impl ScalarExpression {
             } => {
                 let mut left = left_expr.unpack_val()?;
                 let mut right = right_expr.unpack_val()?;
+                if let Ok(Some(temporal)) = EvaluatorFactory::temporal_binary_create(
+                    &left.logical_type(),
+                    &right.logical_type(),
+                    *op,
+                ) {
+                    return temporal
+                        .evaluator
+                        .binary_eval(
+                            &left.cast(&temporal.left_ty).ok()?,
+                            &right.cast(&temporal.right_ty).ok()?,
+                        )
+                        .ok();
+                }
                 if &left.logical_type() != ty {
                     left = left.cast(ty).ok()?;
                 }
@@ -0,0 +1,143 @@
+use crate::catalog::{ColumnRef, ForeignTable, TableCatalog, TableName};
+use crate::errors::DatabaseError;
+use crate::fdw::{ForeignDataWrapper, ForeignFilter};
+use crate::storage::rocksdb::RocksStorage;
+use crate::storage::{Iter, Storage, TableCache, Transaction};
+use crate::types::tuple::Tuple;
+use crate::utils::lru::SharedLruCache;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::hash::RandomState;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A KiteSQL database file attached with `ATTACH DATABASE 'path' AS name`,
+/// its tables are read as `name.table` and cannot be written.
+#[derive(Serialize, Deserialize)]
+pub struct AttachedDatabase {
+    name: String,
+    path: String,
+    // Not kept by serialized plans, which then fail to scan as detached
+    #[serde(skip)]
+    inner: Option<(RocksStorage, TableCache)>,
+}
+
+impl AttachedDatabase {
+    pub(crate) fn open(name: String, path: String) -> Result<Self, DatabaseError> {
+        if !Path::new(&path).exists() {
+            return Err(DatabaseError::InvalidValue(format!(
+                "database file: {} does not exist",
+                path
+            )));
+        }
+        let storage = RocksStorage::new(&path)?;
+        let table_cache = SharedLruCache::new(48, 4, RandomState::new())?;
+
+        Ok(AttachedDatabase {
+            name,
+            path,
+            inner: Some((storage, table_cache)),
+        })
+    }
+
+    fn inner(&self) -> Result<&(RocksStorage, TableCache), DatabaseError> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| DatabaseError::AttachedDatabaseNotFound(self.name.clone()))
+    }
+
+    pub(crate) fn table(
+        &self,
+        table_name: TableName,
+    ) -> Result<Option<&TableCatalog>, DatabaseError> {
+        let (storage, table_cache) = self.inner()?;
+
+        if table_cache.get(&table_name).is_none()
+            && storage
+                .transaction()?
+                .table(table_cache, table_name.clone())?
+                .is_none()
+        {
+            return Ok(None);
+        }
+        Ok(table_cache.get(&table_name))
+    }
+
+    pub(crate) fn foreign_table(&self, table_name: &str) -> ForeignTable {
+        ForeignTable {
+            wrapper: self.name.clone(),
+            connection: self.path.clone(),
+            remote_table: table_name.to_string(),
+        }
+    }
+}
+
+impl Debug for AttachedDatabase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttachedDatabase")
+            .field("name", &self.name)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+#[typetag::serde]
+impl ForeignDataWrapper for AttachedDatabase {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn scan(
+        &self,
+        table: &ForeignTable,
+        columns: &[ColumnRef],
+        filters: &[ForeignFilter],
+    ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError> {
+        let (storage, table_cache) = self.inner()?;
+        let table_name = Arc::new(table.remote_table.clone());
+        let transaction = storage.transaction()?;
+        let table_catalog = transaction
+            .table(table_cache, table_name.clone())?
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let mut projection = BTreeMap::new();
+
+        for column in columns {
+            let i = table_catalog
+                .columns()
+                .position(|table_column| table_column.id() == column.id())
+                .ok_or_else(|| DatabaseError::ColumnNotFound(column.name().to_string()))?;
+            projection.insert(i, column.clone());
+        }
+        let filters = filters
+            .iter()
+            .map(|filter| {
+                let i = columns
+                    .iter()
+                    .position(|column| column.summary() == filter.column.summary());
+                (i, filter)
+            })
+            .collect::<Vec<_>>();
+        // The transaction of the attached storage cannot outlive the scan, so the rows are read up front
+        let mut tuples = Vec::new();
+        let mut iter = transaction.read(
+            table_cache,
+            table_name,
+            (None, None),
+            projection,
+            false,
+            false,
+        )?;
+
+        while let Some(tuple) = iter.next_tuple()? {
+            if filters
+                .iter()
+                .all(|(i, filter)| i.map_or(true, |i| filter.eval(&tuple.values[i])))
+            {
+                tuples.push(tuple);
+            }
+        }
+        Ok(Box::new(tuples.into_iter().map(Ok)))
+    }
+}
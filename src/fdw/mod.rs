@@ -0,0 +1,143 @@
+pub mod attached;
+#[cfg(feature = "postgres_fdw")]
+pub mod postgres;
+
+use crate::catalog::{ColumnRef, ForeignTable};
+use crate::errors::DatabaseError;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use kite_sql_serde_macros::ReferenceSerialization;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Reads the tables of a remote source for the foreign tables created
+/// with `CREATE TABLE ... WITH (wrapper = '<name>', connection = '...', remote_table = '...')`.
+#[typetag::serde(tag = "wrapper")]
+pub trait ForeignDataWrapper: Debug + Send + Sync {
+    /// The name the foreign tables refer to the wrapper by.
+    fn name(&self) -> &str;
+
+    /// Streams the rows of the remote table with the values of `columns` in order.
+    ///
+    /// `filters` may be applied by the remote source to reduce the rows sent back,
+    /// the rows are filtered again locally.
+    fn scan(
+        &self,
+        table: &ForeignTable,
+        columns: &[ColumnRef],
+        filters: &[ForeignFilter],
+    ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArcForeignDataWrapper(pub Arc<dyn ForeignDataWrapper>);
+
+impl Deref for ArcForeignDataWrapper {
+    type Target = dyn ForeignDataWrapper;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for ArcForeignDataWrapper {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for ArcForeignDataWrapper {}
+
+impl Hash for ArcForeignDataWrapper {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+    }
+}
+
+/// A `column <op> constant` conjunct of the filter above a foreign scan.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub struct ForeignFilter {
+    pub column: ColumnRef,
+    pub op: BinaryOperator,
+    pub value: DataValue,
+}
+
+impl ForeignFilter {
+    pub(crate) fn from_expr(expr: &ScalarExpression) -> Option<Self> {
+        let ScalarExpression::Binary {
+            op,
+            left_expr,
+            right_expr,
+            ..
+        } = expr
+        else {
+            return None;
+        };
+        let (column, op, value) =
+            match (left_expr.unpack_alias_ref(), right_expr.unpack_alias_ref()) {
+                (ScalarExpression::ColumnRef(column), ScalarExpression::Constant(value)) => {
+                    (column, *op, value)
+                }
+                (ScalarExpression::Constant(value), ScalarExpression::ColumnRef(column)) => {
+                    let op = match op {
+                        BinaryOperator::Gt => BinaryOperator::Lt,
+                        BinaryOperator::GtEq => BinaryOperator::LtEq,
+                        BinaryOperator::Lt => BinaryOperator::Gt,
+                        BinaryOperator::LtEq => BinaryOperator::GtEq,
+                        op => *op,
+                    };
+                    (column, op, value)
+                }
+                _ => return None,
+            };
+        if value.is_null()
+            || !matches!(
+                op,
+                BinaryOperator::Eq
+                    | BinaryOperator::NotEq
+                    | BinaryOperator::Gt
+                    | BinaryOperator::GtEq
+                    | BinaryOperator::Lt
+                    | BinaryOperator::LtEq
+            )
+        {
+            return None;
+        }
+        Some(ForeignFilter {
+            column: column.clone(),
+            op,
+            value: value.clone(),
+        })
+    }
+
+    /// Whether the value of the filtered column may satisfy the filter,
+    /// values that cannot be compared are kept for the local recheck.
+    pub fn eval(&self, value: &DataValue) -> bool {
+        if value.is_null() {
+            return false;
+        }
+        let Some(ordering) = value.partial_cmp(&self.value) else {
+            return true;
+        };
+        match self.op {
+            BinaryOperator::Eq => ordering.is_eq(),
+            BinaryOperator::NotEq => ordering.is_ne(),
+            BinaryOperator::Gt => ordering.is_gt(),
+            BinaryOperator::GtEq => ordering.is_ge(),
+            BinaryOperator::Lt => ordering.is_lt(),
+            BinaryOperator::LtEq => ordering.is_le(),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for ForeignFilter {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.column.name(), self.op, self.value)
+    }
+}
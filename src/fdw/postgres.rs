@@ -0,0 +1,167 @@
+use crate::catalog::{ColumnRef, ForeignTable};
+use crate::errors::DatabaseError;
+use crate::fdw::{ForeignDataWrapper, ForeignFilter};
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use itertools::Itertools;
+use postgres::{Client, NoTls, SimpleQueryMessage};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::CharLengthUnits;
+use std::collections::VecDeque;
+
+const FETCH_BATCH_SIZE: usize = 1024;
+const CURSOR_NAME: &str = "kite_sql_foreign_scan";
+
+/// Reads the tables of a remote Postgres, or of a KiteSQL server speaking the Postgres protocol.
+///
+/// `connection` takes a libpq style connection string, e.g. `host=127.0.0.1 port=5432 user=postgres`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostgresDataWrapper;
+
+#[typetag::serde]
+impl ForeignDataWrapper for PostgresDataWrapper {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    fn scan(
+        &self,
+        table: &ForeignTable,
+        columns: &[ColumnRef],
+        filters: &[ForeignFilter],
+    ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError> {
+        let mut client = Client::connect(&table.connection, NoTls).map_err(remote_error)?;
+        // Values are read as text and cast locally, so the remote types only have to be castable
+        let projection = if columns.is_empty() {
+            "1".to_string()
+        } else {
+            columns
+                .iter()
+                .map(|column| format!("{}::text", quote_identifier(column.name())))
+                .join(", ")
+        };
+        let mut sql = format!(
+            "DECLARE {} NO SCROLL CURSOR FOR SELECT {} FROM {}",
+            CURSOR_NAME, projection, table.remote_table
+        );
+        if !filters.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(
+                &filters
+                    .iter()
+                    .map(|filter| {
+                        format!(
+                            "{} {} {}",
+                            quote_identifier(filter.column.name()),
+                            filter.op,
+                            quote_literal(&filter.value)
+                        )
+                    })
+                    .join(" AND "),
+            );
+        }
+        client.batch_execute("BEGIN").map_err(remote_error)?;
+        client.batch_execute(&sql).map_err(remote_error)?;
+
+        Ok(Box::new(PostgresScan {
+            client,
+            types: columns
+                .iter()
+                .map(|column| column.datatype().clone())
+                .collect_vec(),
+            buffer: VecDeque::new(),
+            is_finished: false,
+        }))
+    }
+}
+
+/// Streams the rows of the cursor in batches of `FETCH_BATCH_SIZE`.
+struct PostgresScan {
+    client: Client,
+    types: Vec<LogicalType>,
+    buffer: VecDeque<Tuple>,
+    is_finished: bool,
+}
+
+impl PostgresScan {
+    fn fetch(&mut self) -> Result<(), DatabaseError> {
+        let messages = self
+            .client
+            .simple_query(&format!("FETCH {} FROM {}", FETCH_BATCH_SIZE, CURSOR_NAME))
+            .map_err(remote_error)?;
+
+        for message in messages {
+            match message {
+                SimpleQueryMessage::Row(row) => {
+                    let mut values = Vec::with_capacity(self.types.len());
+
+                    for (i, ty) in self.types.iter().enumerate() {
+                        values.push(match row.try_get(i).map_err(remote_error)? {
+                            Some(value) => text_to_value(value, ty)?,
+                            None => DataValue::Null,
+                        });
+                    }
+                    self.buffer.push_back(Tuple::new(None, values));
+                }
+                SimpleQueryMessage::CommandComplete(count) => {
+                    if count < FETCH_BATCH_SIZE as u64 {
+                        self.is_finished = true;
+                    }
+                }
+                _ => (),
+            }
+        }
+        if self.is_finished {
+            self.client.batch_execute("COMMIT").map_err(remote_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for PostgresScan {
+    type Item = Result<Tuple, DatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.buffer.is_empty() && !self.is_finished {
+            if let Err(err) = self.fetch() {
+                self.is_finished = true;
+                return Some(Err(err));
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+fn text_to_value(text: &str, ty: &LogicalType) -> Result<DataValue, DatabaseError> {
+    // Postgres prints booleans as `t` and `f`
+    if let LogicalType::Boolean = ty {
+        return match text {
+            "t" | "true" => Ok(DataValue::Boolean(true)),
+            "f" | "false" => Ok(DataValue::Boolean(false)),
+            _ => Err(DatabaseError::ForeignDataWrapper(format!(
+                "invalid boolean: {}",
+                text
+            ))),
+        };
+    }
+    DataValue::Utf8 {
+        value: text.to_string(),
+        ty: Utf8Type::Variable(None),
+        unit: CharLengthUnits::Characters,
+    }
+    .cast(ty)
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// The literal is left untyped, so Postgres resolves it to the type of the compared column.
+fn quote_literal(value: &DataValue) -> String {
+    format!("'{}'", value.to_string().replace('\'', "''"))
+}
+
+fn remote_error<E: ToString>(err: E) -> DatabaseError {
+    DatabaseError::ForeignDataWrapper(err.to_string())
+}
@@ -0,0 +1,131 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::random::SessionRng;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+
+const FIRST_NAMES: [&str; 16] = [
+    "Alice", "Bob", "Carol", "David", "Emma", "Frank", "Grace", "Henry", "Iris", "Jack", "Kate",
+    "Liam", "Mia", "Noah", "Olivia", "Peter",
+];
+const LAST_NAMES: [&str; 16] = [
+    "Anderson", "Brown", "Clark", "Davis", "Evans", "Garcia", "Harris", "Johnson", "King", "Lee",
+    "Miller", "Nguyen", "Smith", "Taylor", "Walker", "Wilson",
+];
+const CITIES: [&str; 12] = [
+    "Amsterdam",
+    "Berlin",
+    "Chicago",
+    "Dublin",
+    "Lisbon",
+    "London",
+    "Madrid",
+    "Paris",
+    "Seoul",
+    "Shanghai",
+    "Sydney",
+    "Tokyo",
+];
+const WORDS: [&str; 16] = [
+    "apple", "breeze", "cloud", "delta", "ember", "forest", "granite", "harbor", "island",
+    "jungle", "lantern", "meadow", "nectar", "orbit", "pebble", "quartz",
+];
+
+/// `fake(kind)`: a random text of a kind, one of `first_name`, `last_name`, `name`, `email`,
+/// `city` and `word`, used by the columns of `faker(rows, spec)`
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Fake {
+    summary: FunctionSummary,
+    #[serde(skip)]
+    rng: SessionRng,
+}
+
+impl Fake {
+    pub(crate) const KINDS: [&'static str; 6] =
+        ["first_name", "last_name", "name", "email", "city", "word"];
+
+    #[allow(unused_mut)]
+    pub(crate) fn new(rng: SessionRng) -> Arc<Self> {
+        let function_name = "fake".to_lowercase();
+        let arg_types = vec![LogicalType::Varchar(None, CharLengthUnits::Characters)];
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name,
+                arg_types,
+            },
+            rng,
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Fake {
+    #[allow(unused_variables, clippy::redundant_closure_call)]
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let kind = exprs[0].eval(tuples)?;
+        let Some(kind) = kind.utf8() else {
+            return Ok(DataValue::Null);
+        };
+        let value = self.rng.with(|rng| {
+            let mut pick = |values: &[&str]| values.choose(rng).unwrap().to_string();
+
+            Ok(match kind {
+                "first_name" => pick(&FIRST_NAMES),
+                "last_name" => pick(&LAST_NAMES),
+                "name" => format!("{} {}", pick(&FIRST_NAMES), pick(&LAST_NAMES)),
+                "email" => {
+                    let (first, last) = (pick(&FIRST_NAMES), pick(&LAST_NAMES));
+
+                    format!(
+                        "{}.{}{}@example.com",
+                        first.to_lowercase(),
+                        last.to_lowercase(),
+                        rng.gen_range(1..1000)
+                    )
+                }
+                "city" => pick(&CITIES),
+                "word" => pick(&WORDS),
+                kind => {
+                    return Err(DatabaseError::InvalidValue(format!(
+                        "fake() kind: {kind}, expected one of {}",
+                        Fake::KINDS.join(", ")
+                    )))
+                }
+            })
+        })?;
+
+        Ok(DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,65 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+
+/// The implicit cast of a text to the number it is compared or computed with when the strings are
+/// coerced leniently, see [`DataValue::cast_lenient`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct LenientCast {
+    summary: FunctionSummary,
+    ty: LogicalType,
+}
+
+impl LenientCast {
+    pub(crate) const NAME: &'static str = "lenient_cast";
+
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        let arg_types = vec![LogicalType::Varchar(None, CharLengthUnits::Characters)];
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: Self::NAME.to_string(),
+                arg_types,
+            },
+            ty,
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for LenientCast {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        exprs[0].eval(tuples)?.cast_lenient(&self.ty)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.ty
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
@@ -1,6 +1,7 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
 use crate::expression::function::scala::ScalarFunctionImpl;
 use crate::expression::function::FunctionSummary;
 use crate::expression::ScalarExpression;
@@ -60,4 +61,8 @@ impl ScalarFunctionImpl for Lower {
     fn summary(&self) -> &FunctionSummary {
         &self.summary
     }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
 }
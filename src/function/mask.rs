@@ -0,0 +1,154 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::iter;
+use std::sync::Arc;
+
+/// `mask_email(s)`: keeps the first character and the domain of an email, e.g.
+/// `j*******@example.com`, the texts without `@` are masked whole.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MaskEmail {
+    summary: FunctionSummary,
+}
+
+impl MaskEmail {
+    pub(crate) const NAME: &'static str = "mask_email";
+
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: Self::NAME.to_string(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters)],
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for MaskEmail {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let mut value = exprs[0].eval(tuples)?;
+        if !matches!(value.logical_type(), LogicalType::Varchar(_, _)) {
+            value = value.cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?;
+        }
+        if let DataValue::Utf8 { value, .. } = &mut value {
+            *value = mask_email(value);
+        }
+        Ok(value)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
+
+/// `mask_partial(s, keep_last)`: keeps the last `keep_last` characters of a text, e.g.
+/// `************1111`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MaskPartial {
+    summary: FunctionSummary,
+}
+
+impl MaskPartial {
+    pub(crate) const NAME: &'static str = "mask_partial";
+
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: Self::NAME.to_string(),
+                arg_types: vec![
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    LogicalType::Integer,
+                ],
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for MaskPartial {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let mut value = exprs[0].eval(tuples)?;
+        if !matches!(value.logical_type(), LogicalType::Varchar(_, _)) {
+            value = value.cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?;
+        }
+        let DataValue::Int32(keep_last) = exprs[1].eval(tuples)?.cast(&LogicalType::Integer)?
+        else {
+            return Ok(DataValue::Null);
+        };
+        if let DataValue::Utf8 { value, .. } = &mut value {
+            *value = mask_partial(value, keep_last.max(0) as usize);
+        }
+        Ok(value)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
+
+fn mask_email(email: &str) -> String {
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return email.chars().map(|_| '*').collect();
+    };
+    let mut chars = local.chars();
+
+    chars
+        .next()
+        .into_iter()
+        .chain(chars.map(|_| '*'))
+        .chain(iter::once('@'))
+        .chain(domain.chars())
+        .collect()
+}
+
+fn mask_partial(text: &str, keep_last: usize) -> String {
+    let masked = text.chars().count().saturating_sub(keep_last);
+
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| if i < masked { '*' } else { c })
+        .collect()
+}
@@ -0,0 +1,75 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use crate::utils::full_text;
+use ordered_float::OrderedFloat;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+
+/// `MATCH (column) AGAINST ('query')`, the relevance of the text of the column to the query,
+/// which is `0` when none of the query terms occur in it.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MatchAgainst {
+    summary: FunctionSummary,
+}
+
+impl MatchAgainst {
+    pub(crate) const NAME: &'static str = "match_against";
+
+    pub(crate) fn new() -> Arc<Self> {
+        let arg_types = vec![
+            LogicalType::Varchar(None, CharLengthUnits::Characters),
+            LogicalType::Varchar(None, CharLengthUnits::Characters),
+        ];
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: Self::NAME.to_string(),
+                arg_types,
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for MatchAgainst {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let text = exprs[0].eval(tuples)?;
+        let query = exprs[1].eval(tuples)?;
+
+        match (text, query) {
+            (DataValue::Utf8 { value: text, .. }, DataValue::Utf8 { value: query, .. }) => Ok(
+                DataValue::Float64(OrderedFloat(full_text::score(&text, &query))),
+            ),
+            _ => Ok(DataValue::Null),
+        }
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
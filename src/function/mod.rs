@@ -1,7 +1,18 @@
 pub(crate) mod char_length;
 pub(crate) mod current_date;
 pub(crate) mod current_timestamp;
+pub(crate) mod fake;
+pub(crate) mod lenient_cast;
 pub(crate) mod lower;
+pub(crate) mod mask;
+pub(crate) mod match_against;
+pub(crate) mod nested;
 pub(crate) mod numbers;
 pub(crate) mod octet_length;
+pub(crate) mod random;
+pub(crate) mod round;
+pub(crate) mod similarity;
+pub(crate) mod spatial;
 pub(crate) mod upper;
+pub(crate) mod uuid;
+pub(crate) mod vector_distance;
@@ -0,0 +1,136 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+pub(crate) const MAP_KEY: &str = "key";
+pub(crate) const MAP_VALUE: &str = "value";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum NestedKind {
+    /// `list_value(a, ...)`, also written `ARRAY[a, ...]`
+    ListValue,
+    /// `struct_pack(name => a, ...)`
+    StructPack,
+    /// `map(keys, values)`, the entries of two arrays as an array of `(key, value)` structs
+    Map,
+}
+
+impl NestedKind {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            NestedKind::ListValue => "list_value",
+            NestedKind::StructPack => "struct_pack",
+            NestedKind::Map => "map",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        [
+            NestedKind::ListValue,
+            NestedKind::StructPack,
+            NestedKind::Map,
+        ]
+        .into_iter()
+        .find(|kind| kind.name() == name)
+    }
+}
+
+/// The constructors of arrays, structs and maps, the arguments being cast by the binder
+/// to the element or field types of `ty`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct NestedFunction {
+    summary: FunctionSummary,
+    pub(crate) kind: NestedKind,
+    ty: LogicalType,
+}
+
+impl NestedFunction {
+    pub(crate) fn new(kind: NestedKind, arg_types: Vec<LogicalType>, ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: kind.name().to_string(),
+                arg_types,
+            },
+            kind,
+            ty,
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for NestedFunction {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        match self.kind {
+            NestedKind::ListValue => Ok(DataValue::Array(
+                exprs
+                    .iter()
+                    .map(|expr| expr.eval(tuples))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            NestedKind::StructPack => {
+                let LogicalType::Struct(fields) = &self.ty else {
+                    unreachable!()
+                };
+                let mut values = Vec::with_capacity(fields.len());
+                for ((name, _), expr) in fields.iter().zip(exprs) {
+                    values.push((name.clone(), expr.eval(tuples)?));
+                }
+                Ok(DataValue::Struct(values))
+            }
+            NestedKind::Map => {
+                let (DataValue::Array(keys), DataValue::Array(values)) =
+                    (exprs[0].eval(tuples)?, exprs[1].eval(tuples)?)
+                else {
+                    return Ok(DataValue::Null);
+                };
+                if keys.len() != values.len() {
+                    return Err(DatabaseError::MisMatch(
+                        "map() parameters",
+                        "arrays of the same length",
+                    ));
+                }
+                Ok(DataValue::Array(
+                    keys.into_iter()
+                        .zip(values)
+                        .map(|(key, value)| {
+                            DataValue::Struct(vec![
+                                (MAP_KEY.to_string(), key),
+                                (MAP_VALUE.to_string(), value),
+                            ])
+                        })
+                        .collect(),
+                ))
+            }
+        }
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.ty
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
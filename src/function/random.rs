@@ -0,0 +1,268 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use ordered_float::OrderedFloat;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// The random number generator shared by the random functions of a database,
+/// `setseed(n)` makes the values generated after it reproducible.
+#[derive(Clone)]
+pub(crate) struct SessionRng(Arc<Mutex<StdRng>>);
+
+impl Default for SessionRng {
+    fn default() -> Self {
+        SessionRng(Arc::new(Mutex::new(StdRng::from_entropy())))
+    }
+}
+
+impl Debug for SessionRng {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SessionRng")
+    }
+}
+
+impl SessionRng {
+    pub(crate) fn seed(&self, seed: u64) {
+        *self.0.lock() = StdRng::seed_from_u64(seed);
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut StdRng) -> R) -> R {
+        f(&mut self.0.lock())
+    }
+}
+
+/// `random()`: a double in `[0, 1)`
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Random {
+    summary: FunctionSummary,
+    #[serde(skip)]
+    rng: SessionRng,
+}
+
+/// `random(low, high)`: an integer in `[low, high]`
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RandomRange {
+    summary: FunctionSummary,
+    #[serde(skip)]
+    rng: SessionRng,
+}
+
+/// `setseed(n)`: reseeds the random functions of the database
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SetSeed {
+    summary: FunctionSummary,
+    #[serde(skip)]
+    rng: SessionRng,
+}
+
+impl Random {
+    #[allow(unused_mut)]
+    pub(crate) fn new(rng: SessionRng) -> Arc<Self> {
+        let function_name = "random".to_lowercase();
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name,
+                arg_types: Vec::new(),
+            },
+            rng,
+        })
+    }
+}
+
+impl RandomRange {
+    #[allow(unused_mut)]
+    pub(crate) fn new(rng: SessionRng) -> Arc<Self> {
+        let function_name = "random".to_lowercase();
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name,
+                arg_types: vec![LogicalType::Integer, LogicalType::Integer],
+            },
+            rng,
+        })
+    }
+}
+
+impl SetSeed {
+    #[allow(unused_mut)]
+    pub(crate) fn new(rng: SessionRng) -> Arc<Self> {
+        let function_name = "setseed".to_lowercase();
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name,
+                arg_types: vec![LogicalType::Integer],
+            },
+            rng,
+        })
+    }
+}
+
+fn eval_i32(
+    expr: &ScalarExpression,
+    tuple: Option<(&Tuple, &[ColumnRef])>,
+) -> Result<Option<i32>, DatabaseError> {
+    let mut value = expr.eval(tuple)?;
+
+    if value.logical_type() != LogicalType::Integer {
+        value = value.cast(&LogicalType::Integer)?;
+    }
+    Ok(value.i32())
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Random {
+    #[allow(unused_variables, clippy::redundant_closure_call)]
+    fn eval(
+        &self,
+        _: &[ScalarExpression],
+        _: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        Ok(DataValue::Float64(OrderedFloat(
+            self.rng.with(|rng| rng.gen::<f64>()),
+        )))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for RandomRange {
+    #[allow(unused_variables, clippy::redundant_closure_call)]
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(low), Some(high)) = (eval_i32(&exprs[0], tuples)?, eval_i32(&exprs[1], tuples)?)
+        else {
+            return Ok(DataValue::Null);
+        };
+        if low > high {
+            return Err(DatabaseError::InvalidValue(format!(
+                "random({low}, {high}): the low bound is greater than the high bound"
+            )));
+        }
+        Ok(DataValue::Int32(
+            self.rng.with(|rng| rng.gen_range(low..=high)),
+        ))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Integer
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for SetSeed {
+    #[allow(unused_variables, clippy::redundant_closure_call)]
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let seed = eval_i32(&exprs[0], tuples)?.ok_or_else(|| {
+            DatabaseError::InvalidValue("the seed of setseed() cannot be null".to_string())
+        })?;
+        self.rng.seed(seed as u64);
+
+        Ok(DataValue::Null)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::SqlNull
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::DatabaseError;
+    use crate::expression::function::scala::ScalarFunctionImpl;
+    use crate::expression::ScalarExpression;
+    use crate::function::random::{Random, RandomRange, SessionRng, SetSeed};
+    use crate::types::value::DataValue;
+
+    #[test]
+    fn test_random_with_seed() -> Result<(), DatabaseError> {
+        let rng = SessionRng::default();
+        let random = Random::new(rng.clone());
+        let random_range = RandomRange::new(rng.clone());
+        let set_seed = SetSeed::new(rng);
+        let seed = [ScalarExpression::Constant(DataValue::Int32(42))];
+        let range = [
+            ScalarExpression::Constant(DataValue::Int32(-3)),
+            ScalarExpression::Constant(DataValue::Int32(3)),
+        ];
+
+        let mut runs = Vec::new();
+        for _ in 0..2 {
+            set_seed.eval(&seed, None)?;
+            let mut values = Vec::new();
+            for _ in 0..16 {
+                values.push(random.eval(&[], None)?);
+                values.push(random_range.eval(&range, None)?);
+            }
+            runs.push(values);
+        }
+        assert_eq!(runs[0], runs[1]);
+
+        for value in &runs[0] {
+            match value {
+                DataValue::Float64(value) => assert!((0.0..1.0).contains(&value.0)),
+                DataValue::Int32(value) => assert!((-3..=3).contains(value)),
+                _ => unreachable!(),
+            }
+        }
+        assert!(random_range
+            .eval(
+                &[
+                    ScalarExpression::Constant(DataValue::Int32(1)),
+                    ScalarExpression::Constant(DataValue::Int32(0)),
+                ],
+                None,
+            )
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use ordered_float::OrderedFloat;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `round(x)` and `round(x, n)`: rounds `x` half away from zero, to `n` decimal places
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Round {
+    summary: FunctionSummary,
+}
+
+impl Round {
+    pub(crate) fn new() -> Arc<Self> {
+        Self::with_arg_types(vec![LogicalType::Double])
+    }
+
+    pub(crate) fn with_places() -> Arc<Self> {
+        Self::with_arg_types(vec![LogicalType::Double, LogicalType::Integer])
+    }
+
+    fn with_arg_types(arg_types: Vec<LogicalType>) -> Arc<Self> {
+        let function_name = "round".to_lowercase();
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name,
+                arg_types,
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Round {
+    #[allow(unused_variables, clippy::redundant_closure_call)]
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let mut value = exprs[0].eval(tuples)?;
+        if value.logical_type() != LogicalType::Double {
+            value = value.cast(&LogicalType::Double)?;
+        }
+        let DataValue::Float64(OrderedFloat(x)) = value else {
+            return Ok(DataValue::Null);
+        };
+        let places = match exprs.get(1) {
+            Some(expr) => {
+                let mut places = expr.eval(tuples)?;
+                if places.logical_type() != LogicalType::Integer {
+                    places = places.cast(&LogicalType::Integer)?;
+                }
+                match places.i32() {
+                    Some(places) => places,
+                    None => return Ok(DataValue::Null),
+                }
+            }
+            None => 0,
+        };
+        let scale = 10_f64.powi(places);
+
+        Ok(DataValue::Float64(OrderedFloat(
+            (x * scale).round() / scale,
+        )))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
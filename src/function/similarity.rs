@@ -0,0 +1,75 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use crate::utils::trigram;
+use ordered_float::OrderedFloat;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+
+/// `similarity(a, b)`, the share of the trigrams of the two strings they have in common,
+/// from `0` for none to `1` for the same words.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Similarity {
+    summary: FunctionSummary,
+}
+
+impl Similarity {
+    pub(crate) const NAME: &'static str = "similarity";
+
+    pub(crate) fn new() -> Arc<Self> {
+        let arg_types = vec![
+            LogicalType::Varchar(None, CharLengthUnits::Characters),
+            LogicalType::Varchar(None, CharLengthUnits::Characters),
+        ];
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: Self::NAME.to_string(),
+                arg_types,
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Similarity {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let a = exprs[0].eval(tuples)?;
+        let b = exprs[1].eval(tuples)?;
+
+        match (a, b) {
+            (DataValue::Utf8 { value: a, .. }, DataValue::Utf8 { value: b, .. }) => Ok(
+                DataValue::Float64(OrderedFloat(trigram::similarity(&a, &b))),
+            ),
+            _ => Ok(DataValue::Null),
+        }
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
@@ -0,0 +1,148 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use crate::utils::spatial;
+use ordered_float::OrderedFloat;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum SpatialKind {
+    /// `st_point(x, y)`
+    Point,
+    /// `st_makebox(a, b)` of two opposite corners
+    MakeBox,
+    /// `st_distance(a, b)`
+    Distance,
+    /// `st_contains(a, b)`, whether `b` lies in `a`
+    Contains,
+    /// `st_within(a, b)`, whether `a` lies in `b`
+    Within,
+    /// `st_intersects(a, b)`, whether `a` and `b` overlap
+    Intersects,
+}
+
+impl SpatialKind {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            SpatialKind::Point => "st_point",
+            SpatialKind::MakeBox => "st_makebox",
+            SpatialKind::Distance => "st_distance",
+            SpatialKind::Contains => "st_contains",
+            SpatialKind::Within => "st_within",
+            SpatialKind::Intersects => "st_intersects",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        [
+            SpatialKind::Point,
+            SpatialKind::MakeBox,
+            SpatialKind::Distance,
+            SpatialKind::Contains,
+            SpatialKind::Within,
+            SpatialKind::Intersects,
+        ]
+        .into_iter()
+        .find(|kind| kind.name() == name)
+    }
+}
+
+/// The constructors of points and boxes, and the predicates and distance between them,
+/// a point being taken as a box of no size.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SpatialFunction {
+    summary: FunctionSummary,
+    pub(crate) kind: SpatialKind,
+}
+
+impl SpatialFunction {
+    pub(crate) fn new(kind: SpatialKind, arg_types: Vec<LogicalType>) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: kind.name().to_string(),
+                arg_types,
+            },
+            kind,
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for SpatialFunction {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let a = exprs[0].eval(tuples)?;
+        let b = exprs[1].eval(tuples)?;
+
+        match self.kind {
+            SpatialKind::Point => {
+                let (Some(x), Some(y)) = (a.double(), b.double()) else {
+                    return Ok(DataValue::Null);
+                };
+                Ok(DataValue::Point(OrderedFloat(x), OrderedFloat(y)))
+            }
+            SpatialKind::MakeBox => {
+                let (DataValue::Point(x1, y1), DataValue::Point(x2, y2)) = (a, b) else {
+                    return Ok(DataValue::Null);
+                };
+                Ok(DataValue::Box(
+                    x1.min(x2),
+                    y1.min(y2),
+                    x1.max(x2),
+                    y1.max(y2),
+                ))
+            }
+            SpatialKind::Distance
+            | SpatialKind::Contains
+            | SpatialKind::Within
+            | SpatialKind::Intersects => {
+                let (Some(a), Some(b)) = (spatial::extent(&a), spatial::extent(&b)) else {
+                    return Ok(DataValue::Null);
+                };
+                Ok(match self.kind {
+                    SpatialKind::Distance => {
+                        DataValue::Float64(OrderedFloat(spatial::distance(&a, &b)))
+                    }
+                    SpatialKind::Contains => DataValue::Boolean(spatial::contains(&a, &b)),
+                    SpatialKind::Within => DataValue::Boolean(spatial::contains(&b, &a)),
+                    _ => DataValue::Boolean(spatial::intersects(&a, &b)),
+                })
+            }
+        }
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        match self.kind {
+            SpatialKind::Point => &LogicalType::Point,
+            SpatialKind::MakeBox => &LogicalType::Box,
+            SpatialKind::Distance => &LogicalType::Double,
+            SpatialKind::Contains | SpatialKind::Within | SpatialKind::Intersects => {
+                &LogicalType::Boolean
+            }
+        }
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
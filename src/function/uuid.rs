@@ -0,0 +1,77 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::random::SessionRng;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use rand::Rng;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+
+/// `uuid()`: a random (version 4) UUID, e.g. `0f8fad5b-d9cb-469f-a165-70867728950e`
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Uuid {
+    summary: FunctionSummary,
+    #[serde(skip)]
+    rng: SessionRng,
+}
+
+impl Uuid {
+    #[allow(unused_mut)]
+    pub(crate) fn new(rng: SessionRng) -> Arc<Self> {
+        let function_name = "uuid".to_lowercase();
+
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name,
+                arg_types: Vec::new(),
+            },
+            rng,
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Uuid {
+    #[allow(unused_variables, clippy::redundant_closure_call)]
+    fn eval(
+        &self,
+        _: &[ScalarExpression],
+        _: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let mut bytes = self.rng.with(|rng| rng.gen::<[u8; 16]>());
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let mut value = String::with_capacity(36);
+        for (i, byte) in bytes.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                value.push('-');
+            }
+            value.push_str(&format!("{byte:02x}"));
+        }
+        Ok(DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,110 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::FuncVolatility;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use crate::utils::vector;
+use ordered_float::OrderedFloat;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum VectorMetric {
+    /// `l2_distance(a, b)` or `a <-> b`
+    L2,
+    /// `cosine_distance(a, b)` or `a <=> b`
+    Cosine,
+    /// `inner_product(a, b)`
+    InnerProduct,
+    /// `a <#> b`, so that the nearest vectors sort first as for the other distances
+    NegativeInnerProduct,
+}
+
+impl VectorMetric {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            VectorMetric::L2 => "l2_distance",
+            VectorMetric::Cosine => "cosine_distance",
+            VectorMetric::InnerProduct => "inner_product",
+            VectorMetric::NegativeInnerProduct => "negative_inner_product",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        [
+            VectorMetric::L2,
+            VectorMetric::Cosine,
+            VectorMetric::InnerProduct,
+            VectorMetric::NegativeInnerProduct,
+        ]
+        .into_iter()
+        .find(|metric| metric.name() == name)
+    }
+}
+
+/// The distance between two vectors of the same dimension by one of the [`VectorMetric`]s.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct VectorDistance {
+    summary: FunctionSummary,
+    pub(crate) metric: VectorMetric,
+}
+
+impl VectorDistance {
+    pub(crate) fn new(metric: VectorMetric, dimension: u32) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: metric.name().to_string(),
+                arg_types: vec![
+                    LogicalType::Vector(dimension),
+                    LogicalType::Vector(dimension),
+                ],
+            },
+            metric,
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for VectorDistance {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<(&Tuple, &[ColumnRef])>,
+    ) -> Result<DataValue, DatabaseError> {
+        let a = exprs[0].eval(tuples)?;
+        let b = exprs[1].eval(tuples)?;
+
+        let (DataValue::Vector(a), DataValue::Vector(b)) = (a, b) else {
+            return Ok(DataValue::Null);
+        };
+        let distance = match self.metric {
+            VectorMetric::L2 => vector::l2_distance(&a, &b),
+            VectorMetric::Cosine => vector::cosine_distance(&a, &b),
+            VectorMetric::InnerProduct => vector::inner_product(&a, &b),
+            VectorMetric::NegativeInnerProduct => -vector::inner_product(&a, &b),
+        };
+        Ok(DataValue::Float64(OrderedFloat(distance)))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn volatility(&self) -> FuncVolatility {
+        FuncVolatility::Immutable
+    }
+}
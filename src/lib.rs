@@ -19,6 +19,9 @@
 //! - UInteger
 //! - Bigint
 //! - UBigint
+//! - Hugeint
+//! - UHugeint
+//! - Numeric
 //! - Float
 //! - Double
 //! - Char
@@ -27,6 +30,8 @@
 //! - DateTime
 //! - Time
 //! - Tuple
+//! - Array
+//! - Struct
 //!
 //! support optimistic transaction with the
 //! [`Database::new_transaction`](db::Database::new_transaction) method.
@@ -96,19 +101,28 @@
 #![feature(random)]
 extern crate core;
 
+pub mod backup;
 pub mod binder;
 pub mod catalog;
+pub mod dataframe;
 pub mod db;
 pub mod errors;
 pub mod execution;
 pub mod expression;
+pub mod fdw;
 mod function;
 #[cfg(feature = "macros")]
 pub mod macros;
-mod optimizer;
+pub mod masking;
+pub mod optimizer;
 pub mod parser;
 pub mod planner;
+mod process;
+pub mod replication;
+mod scheduler;
 pub mod serdes;
 pub mod storage;
+#[cfg(feature = "substrait")]
+pub mod substrait;
 pub mod types;
 pub(crate) mod utils;
@@ -0,0 +1,37 @@
+//! Masking of the columns read by a role, set by the embedder for each session with
+//! `Database::run_as` or `Database::new_transaction_as`, see `DataBaseBuilder::masking_policy`.
+//!
+//! The statements of a role read the columns its policy masks through the masking functions
+//! `mask_email(s)` and `mask_partial(s, keep_last)`, in their filters and joins as well, and
+//! cannot update nor delete the rows of their tables. A view reads its tables as they were read
+//! by the statement creating it, so a view applying the masking functions exposes a table to
+//! the roles reading it masked.
+use std::fmt;
+use std::fmt::Formatter;
+
+/// How a column is read masked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Masking {
+    /// `mask_email(column)`, the first character and the domain of the emails
+    Email,
+    /// `mask_partial(column, keep_last)`, the last `keep_last` characters
+    Partial { keep_last: usize },
+    /// `NULL` in place of the values, for the columns of any type
+    Null,
+}
+
+impl fmt::Display for Masking {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Masking::Email => write!(f, "mask_email"),
+            Masking::Partial { .. } => write!(f, "mask_partial"),
+            Masking::Null => write!(f, "NULL"),
+        }
+    }
+}
+
+/// Decides the columns each role reads masked.
+pub trait MaskingPolicy: Send + Sync {
+    /// How the role reads the column of the table, `None` to read it as stored.
+    fn masking(&self, role: &str, table: &str, column: &str) -> Option<Masking>;
+}
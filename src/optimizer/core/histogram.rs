@@ -33,6 +33,8 @@ pub struct Histogram {
     index_id: IndexId,
 
     number_of_distinct_value: usize,
+    // distinct values of the leading column of a composite index, for its skip scan
+    number_of_distinct_leading_value: usize,
     null_count: usize,
     values_len: usize,
 
@@ -150,6 +152,7 @@ impl HistogramBuilder {
         }
         let mut corr_xy_sum = 0.0;
         let mut number_of_distinct_value = 0;
+        let mut number_of_distinct_leading_value = 0;
         let mut last_value: Option<DataValue> = None;
 
         for (i, index) in sorted_indices.into_iter().enumerate() {
@@ -157,6 +160,13 @@ impl HistogramBuilder {
             sketch.increment(&value);
 
             if let None | Some(true) = last_value.as_ref().map(|last_value| last_value != &value) {
+                let leading = |value: &DataValue| match value {
+                    DataValue::Tuple(values, _) => values.first().cloned(),
+                    value => Some(value.clone()),
+                };
+                if last_value.as_ref().map(leading) != Some(leading(&value)) {
+                    number_of_distinct_leading_value += 1;
+                }
                 last_value = Some(value.clone());
                 number_of_distinct_value += 1;
             }
@@ -179,6 +189,7 @@ impl HistogramBuilder {
             Histogram {
                 index_id,
                 number_of_distinct_value,
+                number_of_distinct_leading_value,
                 null_count,
                 values_len,
                 buckets,
@@ -259,6 +270,10 @@ impl Histogram {
         self.values_len
     }
 
+    pub fn number_of_distinct_leading_value(&self) -> usize {
+        self.number_of_distinct_leading_value
+    }
+
     pub fn collect_count(
         &self,
         ranges: &[Range],
@@ -307,23 +322,11 @@ impl Histogram {
             let value = match value.logical_type() {
                 LogicalType::Varchar(..) | LogicalType::Char(..) => match value {
                     DataValue::Utf8 { value, .. } => {
-                        if prefix_len > value.len() {
-                            return Ok(0.0);
-                        }
-
-                        let mut val = 0u64;
-                        for (i, char) in value
-                            .get(prefix_len..prefix_len + 8)
-                            .unwrap()
-                            .chars()
-                            .enumerate()
-                        {
-                            if value.len() - prefix_len > i {
-                                val += (val << 8) + char as u64;
-                            } else {
-                                val += val << 8;
-                            }
-                        }
+                        // the 8 bytes after the common prefix, as a big-endian number keeps the order of strings
+                        let mut bytes = value.as_bytes().iter().skip(prefix_len);
+                        let val = (0..8).fold(0u64, |val, _| {
+                            (val << 8) + *bytes.next().unwrap_or(&0) as u64
+                        });
 
                         Some(val as f64)
                     }
@@ -358,9 +361,12 @@ impl Histogram {
                 | LogicalType::UInteger
                 | LogicalType::Bigint
                 | LogicalType::UBigint
+                | LogicalType::Hugeint
+                | LogicalType::UHugeint
                 | LogicalType::Float
                 | LogicalType::Double
-                | LogicalType::Decimal(_, _) => value.clone().cast(&LogicalType::Double)?.double(),
+                | LogicalType::Decimal(_, _)
+                | LogicalType::Numeric => value.clone().cast(&LogicalType::Double)?.double(),
                 LogicalType::Tuple(_) => match value {
                     DataValue::Tuple(values, _) => {
                         let mut float = 0.0;
@@ -380,6 +386,12 @@ impl Histogram {
                     DataValue::Null => None,
                     _ => unreachable!(),
                 },
+                // vectors, shapes and nested values have no order to interpolate in
+                LogicalType::Vector(_)
+                | LogicalType::Point
+                | LogicalType::Box
+                | LogicalType::Array(_)
+                | LogicalType::Struct(_) => None,
             }
             .unwrap_or(0.0);
             Ok::<f64, DatabaseError>(value)
@@ -515,8 +527,9 @@ mod tests {
     use crate::expression::range_detacher::Range;
     use crate::optimizer::core::histogram::{Bucket, HistogramBuilder};
     use crate::types::index::{IndexMeta, IndexType};
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
     use crate::types::LogicalType;
+    use sqlparser::ast::CharLengthUnits;
     use std::ops::Bound;
     use std::sync::Arc;
     use ulid::Ulid;
@@ -863,4 +876,49 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_collect_count_on_utf8() -> Result<(), DatabaseError> {
+        let mut builder = HistogramBuilder::new(&index_meta(), Some(100));
+        let utf8 = |value: String| DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        for i in 0..100 {
+            builder.append(&utf8(format!("k{:02}", i)))?;
+        }
+        let (histogram, sketch) = builder.build(10)?;
+
+        // `like 'k1%'`
+        let count = histogram.collect_count(
+            &[Range::Scope {
+                min: Bound::Included(utf8("k1".to_string())),
+                max: Bound::Excluded(utf8("k2".to_string())),
+            }],
+            &sketch,
+        )?;
+        assert!((10..=12).contains(&count), "count: {count}");
+
+        let count = histogram.collect_count(
+            &[Range::Scope {
+                min: Bound::Included(utf8("k15".to_string())),
+                max: Bound::Included(utf8("k54".to_string())),
+            }],
+            &sketch,
+        )?;
+        assert!((36..=44).contains(&count), "count: {count}");
+
+        let count = histogram.collect_count(
+            &[Range::Scope {
+                min: Bound::Included(utf8("k".to_string())),
+                max: Bound::Excluded(utf8("l".to_string())),
+            }],
+            &sketch,
+        )?;
+        assert_eq!(count, 100);
+
+        Ok(())
+    }
 }
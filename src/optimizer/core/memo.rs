@@ -130,6 +130,7 @@ mod tests {
         };
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let foreign_data_wrappers = Default::default();
         let mut binder = Binder::new(
             BinderContext::new(
                 database.state.table_cache(),
@@ -137,6 +138,7 @@ mod tests {
                 &transaction,
                 &scala_functions,
                 &table_functions,
+                &foreign_data_wrappers,
                 Arc::new(AtomicUsize::new(0)),
             ),
             &[],
@@ -212,6 +214,7 @@ mod tests {
                         max: Bound::Unbounded,
                     }
                 ])),
+                skip_range: None,
             }))
         );
 
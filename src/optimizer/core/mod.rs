@@ -1,6 +1,6 @@
 pub(crate) mod cm_sketch;
 pub(crate) mod histogram;
 pub(crate) mod memo;
-pub(crate) mod pattern;
-pub(crate) mod rule;
+pub mod pattern;
+pub mod rule;
 pub(crate) mod statistics_meta;
@@ -15,7 +15,7 @@ pub trait NormalizationRule: MatchPattern {
     fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError>;
 }
 
-pub trait ImplementationRule<T: Transaction>: MatchPattern {
+pub(crate) trait ImplementationRule<T: Transaction>: MatchPattern {
     fn to_expression(
         &self,
         op: &Operator,
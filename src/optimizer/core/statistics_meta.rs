@@ -4,12 +4,14 @@ use crate::expression::range_detacher::Range;
 use crate::optimizer::core::cm_sketch::CountMinSketch;
 use crate::optimizer::core::histogram::Histogram;
 use crate::serdes::{ReferenceSerialization, ReferenceTables};
-use crate::storage::{StatisticsMetaCache, Transaction};
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::types::index::IndexId;
 use crate::types::value::DataValue;
 use kite_sql_serde_macros::ReferenceSerialization;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::fs::OpenOptions;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::slice;
 
@@ -42,6 +44,69 @@ impl<'a, T: Transaction> StatisticMetaLoader<'a, T> {
             Ok(None)
         }
     }
+
+    /// Keeps the rows read by a full scan of `range` on the index, if the index has statistics.
+    pub fn feedback(
+        &self,
+        table_name: &TableName,
+        index_id: IndexId,
+        range: &Range,
+        actual: usize,
+    ) -> Result<(), DatabaseError> {
+        if let Some(statistics_meta) = self.load(table_name, index_id)? {
+            statistics_meta.feedback(range, actual)?;
+        }
+        Ok(())
+    }
+}
+
+/// A count read by a scan replaces the estimate of its range once they differ by this factor.
+const MISESTIMATE_FACTOR: usize = 2;
+/// Ranges corrected per index, the corrections are dropped all at once past it.
+const MAX_CORRECTED_RANGES: usize = 1024;
+
+/// The row counts read by the index scans of the ranges the statistics misestimated, which the
+/// next estimates of the same ranges return instead.
+#[derive(Debug, Default)]
+pub struct RowCountFeedback(Mutex<HashMap<Range, usize>>);
+
+impl RowCountFeedback {
+    fn get(&self, range: &Range) -> Option<usize> {
+        self.0.lock().get(range).copied()
+    }
+
+    fn record(&self, range: &Range, estimated: usize, actual: usize) {
+        let mut corrections = self.0.lock();
+
+        if actual > estimated * MISESTIMATE_FACTOR || actual * MISESTIMATE_FACTOR < estimated {
+            if corrections.len() >= MAX_CORRECTED_RANGES && !corrections.contains_key(range) {
+                corrections.clear();
+            }
+            corrections.insert(range.clone(), actual);
+        } else {
+            corrections.remove(range);
+        }
+    }
+}
+
+// the counts belong to the loaded statistics, an `ANALYZE` writing new ones drops them
+impl ReferenceSerialization for RowCountFeedback {
+    fn encode<W: Write>(
+        &self,
+        _: &mut W,
+        _: bool,
+        _: &mut ReferenceTables,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn decode<T: Transaction, R: Read>(
+        _: &mut R,
+        _: Option<(&T, &TableCache)>,
+        _: &ReferenceTables,
+    ) -> Result<Self, DatabaseError> {
+        Ok(RowCountFeedback::default())
+    }
 }
 
 #[derive(Debug, ReferenceSerialization)]
@@ -49,6 +114,7 @@ pub struct StatisticsMeta {
     index_id: IndexId,
     histogram: Histogram,
     cm_sketch: CountMinSketch<DataValue>,
+    feedback: RowCountFeedback,
 }
 
 impl StatisticsMeta {
@@ -57,6 +123,7 @@ impl StatisticsMeta {
             index_id: histogram.index_id(),
             histogram,
             cm_sketch,
+            feedback: RowCountFeedback::default(),
         }
     }
     pub fn index_id(&self) -> IndexId {
@@ -66,7 +133,25 @@ impl StatisticsMeta {
         &self.histogram
     }
 
+    /// The rows of the index in `range`: the count last read by a scan of it if the histogram
+    /// misestimated it, else the estimate of the histogram.
     pub fn collect_count(&self, range: &Range) -> Result<usize, DatabaseError> {
+        if let Some(count) = self.feedback.get(range) {
+            return Ok(count);
+        }
+        self.estimate_count(range)
+    }
+
+    /// Keeps the rows read by a full scan of `range`, to correct its next estimates if the
+    /// histogram is off by more than [`MISESTIMATE_FACTOR`].
+    pub fn feedback(&self, range: &Range, actual: usize) -> Result<(), DatabaseError> {
+        let estimated = self.estimate_count(range)?;
+        self.feedback.record(range, estimated, actual);
+
+        Ok(())
+    }
+
+    fn estimate_count(&self, range: &Range) -> Result<usize, DatabaseError> {
         let mut count = 0;
 
         let ranges = if let Range::SortedRanges(ranges) = range {
@@ -105,6 +190,7 @@ impl StatisticsMeta {
 #[cfg(test)]
 mod tests {
     use crate::errors::DatabaseError;
+    use crate::expression::range_detacher::Range;
     use crate::optimizer::core::histogram::HistogramBuilder;
     use crate::optimizer::core::statistics_meta::StatisticsMeta;
     use crate::storage::rocksdb::RocksTransaction;
@@ -165,4 +251,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_row_count_feedback() -> Result<(), DatabaseError> {
+        let index = IndexMeta {
+            id: 0,
+            column_ids: vec![Ulid::new()],
+            table_name: Arc::new("t1".to_string()),
+            pk_ty: LogicalType::Integer,
+            value_ty: LogicalType::Integer,
+            name: "pk_c1".to_string(),
+            ty: IndexType::PrimaryKey { is_multiple: false },
+        };
+        let mut builder = HistogramBuilder::new(&index, Some(100));
+
+        for i in 0..100 {
+            builder.append(&Arc::new(DataValue::Int32(i)))?;
+        }
+        let (histogram, sketch) = builder.build(4)?;
+        let statistics_meta = StatisticsMeta::new(histogram, sketch);
+
+        let range = Range::Eq(DataValue::Int32(7));
+        let estimated = statistics_meta.collect_count(&range)?;
+
+        // close enough to the estimate: kept as is
+        statistics_meta.feedback(&range, estimated)?;
+        assert_eq!(statistics_meta.collect_count(&range)?, estimated);
+
+        // misestimated: the count read replaces the estimate of the range only
+        statistics_meta.feedback(&range, estimated * 10 + 10)?;
+        assert_eq!(statistics_meta.collect_count(&range)?, estimated * 10 + 10);
+        assert_eq!(
+            statistics_meta.collect_count(&Range::Eq(DataValue::Int32(8)))?,
+            statistics_meta.estimate_count(&Range::Eq(DataValue::Int32(8)))?
+        );
+
+        // estimated well again, e.g. after the rows were deleted
+        statistics_meta.feedback(&range, estimated)?;
+        assert_eq!(statistics_meta.collect_count(&range)?, estimated);
+
+        Ok(())
+    }
 }
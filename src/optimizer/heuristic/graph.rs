@@ -173,7 +173,7 @@ impl HepGraph {
             .map(|edge| edge.target())
     }
 
-    pub fn into_plan(mut self, memo: Option<&Memo>) -> Option<LogicalPlan> {
+    pub(crate) fn into_plan(mut self, memo: Option<&Memo>) -> Option<LogicalPlan> {
         self.build_childrens(self.root_index, memo)
     }
 
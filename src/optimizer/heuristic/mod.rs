@@ -1,4 +1,4 @@
 pub(crate) mod batch;
-pub(crate) mod graph;
+pub mod graph;
 pub(crate) mod matcher;
-pub mod optimizer;
+pub(crate) mod optimizer;
@@ -1,4 +1,6 @@
 use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::ScalarExpression;
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
@@ -28,6 +30,77 @@ static SIMPLE_AGGREGATE_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
     children: PatternChildrenPredicate::None,
 });
 
+static COUNT_STAR_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| {
+        if let Operator::Aggregate(op) = op {
+            return op.groupby_exprs.is_empty()
+                && !op.agg_calls.is_empty()
+                && op.agg_calls.iter().all(is_count_star);
+        }
+        false
+    },
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: |op| matches!(op, Operator::TableScan(op) if op.limit == (None, None) && op.sample.is_none()),
+        children: PatternChildrenPredicate::None,
+    }]),
+});
+
+static MIN_MAX_INDEX_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| {
+        if let Operator::Aggregate(op) = op {
+            return op.groupby_exprs.is_empty()
+                && !op.agg_calls.is_empty()
+                && op.agg_calls.iter().all(is_min_max_column);
+        }
+        false
+    },
+    // Tips: the scan only reads the aggregated column after the column pruning
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: |op| {
+            matches!(
+                op,
+                Operator::TableScan(op) if op.limit == (None, None)
+                    && op.sample.is_none()
+                    && op.single_column_index().is_some()
+            )
+        },
+        children: PatternChildrenPredicate::None,
+    }]),
+});
+
+/// `MIN(c)` or `MAX(c)` of a plain column.
+fn is_min_max_column(expr: &ScalarExpression) -> bool {
+    let ScalarExpression::AggCall {
+        kind: AggKind::Min | AggKind::Max,
+        args,
+        ..
+    } = expr
+    else {
+        return false;
+    };
+    let [arg] = args.as_slice() else {
+        return false;
+    };
+    let arg = match arg.unpack_alias_ref() {
+        ScalarExpression::Reference { expr, .. } => expr.unpack_alias_ref(),
+        arg => arg,
+    };
+    matches!(arg, ScalarExpression::ColumnRef(_))
+}
+
+/// `COUNT(*)` or `COUNT(<non-null constant>)`, which counts every tuple.
+fn is_count_star(expr: &ScalarExpression) -> bool {
+    matches!(
+        expr,
+        ScalarExpression::AggCall {
+            distinct: false,
+            kind: AggKind::Count,
+            args,
+            ..
+        } if matches!(args.as_slice(), [ScalarExpression::Constant(value)] if !value.is_null())
+    )
+}
+
 #[derive(Clone)]
 pub struct GroupByAggregateImplementation;
 
@@ -44,3 +117,59 @@ single_mapping!(
     SIMPLE_AGGREGATE_PATTERN,
     PhysicalOption::SimpleAggregate
 );
+
+/// Aggregate(COUNT(*)) -> TableScan
+///
+/// Counts the keys of the table instead of scanning and decoding every tuple.
+pub struct CountStarImplementation;
+
+impl MatchPattern for CountStarImplementation {
+    fn pattern(&self) -> &Pattern {
+        &COUNT_STAR_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for CountStarImplementation {
+    fn to_expression(
+        &self,
+        _: &Operator,
+        _: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        // Tips: always cheaper than `SimpleAggregate`, which has no cost
+        group_expr.append_expr(Expression {
+            op: PhysicalOption::CountStar,
+            cost: Some(0),
+        });
+
+        Ok(())
+    }
+}
+
+/// Aggregate(MIN/MAX) -> TableScan
+///
+/// Seeks the first and the last non-null entries of the index on the aggregated column
+/// instead of aggregating every tuple.
+pub struct MinMaxIndexImplementation;
+
+impl MatchPattern for MinMaxIndexImplementation {
+    fn pattern(&self) -> &Pattern {
+        &MIN_MAX_INDEX_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for MinMaxIndexImplementation {
+    fn to_expression(
+        &self,
+        _: &Operator,
+        _: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        group_expr.append_expr(Expression {
+            op: PhysicalOption::MinMaxIndex,
+            cost: Some(0),
+        });
+
+        Ok(())
+    }
+}
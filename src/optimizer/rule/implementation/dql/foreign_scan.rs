@@ -0,0 +1,23 @@
+use crate::errors::DatabaseError;
+use crate::optimizer::core::memo::{Expression, GroupExpression};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::single_mapping;
+use crate::storage::Transaction;
+use std::sync::LazyLock;
+
+static FOREIGN_SCAN_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::ForeignScan(_)),
+    children: PatternChildrenPredicate::None,
+});
+
+#[derive(Clone)]
+pub struct ForeignScanImplementation;
+
+single_mapping!(
+    ForeignScanImplementation,
+    FOREIGN_SCAN_PATTERN,
+    PhysicalOption::ForeignScan
+);
@@ -0,0 +1,104 @@
+use crate::errors::DatabaseError;
+use crate::expression::visitor::Visitor;
+use crate::expression::{HasComplexExpr, ScalarExpression};
+use crate::optimizer::core::memo::{Expression, GroupExpression};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::storage::Transaction;
+use std::sync::LazyLock;
+
+fn is_simple(expr: &ScalarExpression) -> bool {
+    let mut visitor = HasComplexExpr::default();
+
+    visitor.visit(expr).is_ok() && !visitor.value
+}
+
+fn is_simple_filter(op: &Operator) -> bool {
+    matches!(op, Operator::Filter(op) if !op.having && is_simple(&op.predicate))
+}
+
+fn is_simple_project(op: &Operator) -> bool {
+    matches!(op, Operator::Project(op) if op.exprs.iter().all(is_simple))
+}
+
+fn table_scan_pattern() -> Pattern {
+    Pattern {
+        predicate: |op| matches!(op, Operator::TableScan(op) if op.sample.is_none()),
+        children: PatternChildrenPredicate::None,
+    }
+}
+
+static FILTER_SCAN_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: is_simple_filter,
+    children: PatternChildrenPredicate::Predicate(vec![table_scan_pattern()]),
+});
+
+static PROJECT_SCAN_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: is_simple_project,
+    children: PatternChildrenPredicate::Predicate(vec![table_scan_pattern()]),
+});
+
+static PROJECT_FILTER_SCAN_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: is_simple_project,
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: is_simple_filter,
+        children: PatternChildrenPredicate::Predicate(vec![table_scan_pattern()]),
+    }]),
+});
+
+macro_rules! fusion_mapping {
+    ($ty:ty, $pattern:expr, $option:expr) => {
+        impl MatchPattern for $ty {
+            fn pattern(&self) -> &Pattern {
+                &$pattern
+            }
+        }
+
+        impl<T: Transaction> ImplementationRule<T> for $ty {
+            fn to_expression(
+                &self,
+                _: &Operator,
+                _: &StatisticMetaLoader<'_, T>,
+                group_expr: &mut GroupExpression,
+            ) -> Result<(), DatabaseError> {
+                // Tips: always cheaper than the standalone operator, which has no cost
+                group_expr.append_expr(Expression {
+                    op: $option,
+                    cost: Some(0),
+                });
+
+                Ok(())
+            }
+        }
+    };
+}
+
+/// Filter -> TableScan
+#[derive(Clone)]
+pub struct FilterScanFusion;
+
+/// Project -> TableScan
+#[derive(Clone)]
+pub struct ProjectScanFusion;
+
+/// Project -> Filter -> TableScan
+#[derive(Clone)]
+pub struct ProjectFilterScanFusion;
+
+fusion_mapping!(
+    FilterScanFusion,
+    FILTER_SCAN_PATTERN,
+    PhysicalOption::FusedFilter
+);
+fusion_mapping!(
+    ProjectScanFusion,
+    PROJECT_SCAN_PATTERN,
+    PhysicalOption::FusedProject
+);
+fusion_mapping!(
+    ProjectFilterScanFusion,
+    PROJECT_FILTER_SCAN_PATTERN,
+    PhysicalOption::FusedProject
+);
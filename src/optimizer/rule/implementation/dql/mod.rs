@@ -1,7 +1,9 @@
 pub(crate) mod aggregate;
 pub(crate) mod dummy;
 pub(crate) mod filter;
+pub(crate) mod foreign_scan;
 pub(crate) mod function_scan;
+pub(crate) mod fusion;
 pub(crate) mod join;
 pub(crate) mod limit;
 pub(crate) mod projection;
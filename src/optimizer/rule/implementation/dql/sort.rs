@@ -9,7 +9,12 @@ use crate::storage::Transaction;
 use std::sync::LazyLock;
 
 static SORT_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
-    predicate: |op| matches!(op, Operator::Sort(_)),
+    predicate: |op| matches!(op, Operator::Sort(op) if op.presorted == 0),
+    children: PatternChildrenPredicate::None,
+});
+
+static INCREMENTAL_SORT_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Sort(op) if op.presorted > 0),
     children: PatternChildrenPredicate::None,
 });
 
@@ -17,3 +22,12 @@ static SORT_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
 pub struct SortImplementation;
 
 single_mapping!(SortImplementation, SORT_PATTERN, PhysicalOption::Sort);
+
+#[derive(Clone)]
+pub struct IncrementalSortImplementation;
+
+single_mapping!(
+    IncrementalSortImplementation,
+    INCREMENTAL_SORT_PATTERN,
+    PhysicalOption::IncrementalSort
+);
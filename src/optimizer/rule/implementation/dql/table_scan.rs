@@ -1,4 +1,5 @@
 use crate::errors::DatabaseError;
+use crate::expression::range_detacher::Range;
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
@@ -8,6 +9,9 @@ use crate::storage::Transaction;
 use crate::types::index::IndexType;
 use std::sync::LazyLock;
 
+// a skip scan is only worth it over a leading column of at most a sixteenth of distinct values
+const SKIP_SCAN_DISTINCT_RATIO: usize = 16;
+
 static TABLE_SCAN_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
     predicate: |op| matches!(op, Operator::TableScan(_)),
     children: PatternChildrenPredicate::None,
@@ -73,7 +77,10 @@ impl<T: Transaction> ImplementationRule<T> for IndexScanImplementation {
                 }
                 let mut cost = None;
 
-                if let Some(range) = &index_info.range {
+                if matches!(index_info.meta.ty, IndexType::Vector) {
+                    // only probed by a nearest-neighbour search, which reads a few lists of the index
+                    cost = Some(0);
+                } else if let Some(range) = &index_info.range {
                     if let Some(statistics_meta) =
                         loader.load(&scan_op.table_name, index_info.meta.id)?
                     {
@@ -99,3 +106,87 @@ impl<T: Transaction> ImplementationRule<T> for IndexScanImplementation {
         }
     }
 }
+
+pub struct MultiGetImplementation;
+
+impl MatchPattern for MultiGetImplementation {
+    fn pattern(&self) -> &Pattern {
+        &TABLE_SCAN_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for MultiGetImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        _: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        if let Operator::TableScan(scan_op) = op {
+            for index_info in scan_op.index_infos.iter() {
+                if !matches!(index_info.meta.ty, IndexType::PrimaryKey { .. }) {
+                    continue;
+                }
+                // e.g. `pk IN (1, 2, 3)`, a point read for each key without seeking a range
+                if let Some(Range::SortedRanges(ranges)) = &index_info.range {
+                    if ranges.iter().all(|range| matches!(range, Range::Eq(_))) {
+                        group_expr.append_expr(Expression {
+                            op: PhysicalOption::MultiGet(index_info.clone()),
+                            cost: Some(ranges.len()),
+                        })
+                    }
+                }
+            }
+
+            Ok(())
+        } else {
+            unreachable!("invalid operator!")
+        }
+    }
+}
+
+pub struct SkipScanImplementation;
+
+impl MatchPattern for SkipScanImplementation {
+    fn pattern(&self) -> &Pattern {
+        &TABLE_SCAN_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for SkipScanImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        loader: &StatisticMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        if let Operator::TableScan(scan_op) = op {
+            for index_info in scan_op.index_infos.iter() {
+                if index_info.range.is_some() || index_info.skip_range.is_none() {
+                    continue;
+                }
+                // only chosen by the distinct values of the leading column, as `ANALYZE` counts them
+                let Some(statistics_meta) = loader.load(&scan_op.table_name, index_info.meta.id)?
+                else {
+                    continue;
+                };
+                let histogram = statistics_meta.histogram();
+                let distinct = histogram.number_of_distinct_leading_value();
+
+                if distinct * SKIP_SCAN_DISTINCT_RATIO > histogram.values_len() {
+                    continue;
+                }
+                // a seek of the next leading value and one of the range within it for each value,
+                // each reading the tuple from the table (non-covering index)
+                group_expr.append_expr(Expression {
+                    op: PhysicalOption::SkipScan(index_info.clone()),
+                    cost: Some(distinct * 4),
+                })
+            }
+
+            Ok(())
+        } else {
+            unreachable!("invalid operator!")
+        }
+    }
+}
@@ -20,17 +20,24 @@ use crate::optimizer::rule::implementation::dml::delete::DeleteImplementation;
 use crate::optimizer::rule::implementation::dml::insert::InsertImplementation;
 use crate::optimizer::rule::implementation::dml::update::UpdateImplementation;
 use crate::optimizer::rule::implementation::dql::aggregate::{
-    GroupByAggregateImplementation, SimpleAggregateImplementation,
+    CountStarImplementation, GroupByAggregateImplementation, MinMaxIndexImplementation,
+    SimpleAggregateImplementation,
 };
 use crate::optimizer::rule::implementation::dql::dummy::DummyImplementation;
 use crate::optimizer::rule::implementation::dql::filter::FilterImplementation;
+use crate::optimizer::rule::implementation::dql::foreign_scan::ForeignScanImplementation;
 use crate::optimizer::rule::implementation::dql::function_scan::FunctionScanImplementation;
+use crate::optimizer::rule::implementation::dql::fusion::{
+    FilterScanFusion, ProjectFilterScanFusion, ProjectScanFusion,
+};
 use crate::optimizer::rule::implementation::dql::join::JoinImplementation;
 use crate::optimizer::rule::implementation::dql::limit::LimitImplementation;
 use crate::optimizer::rule::implementation::dql::projection::ProjectionImplementation;
-use crate::optimizer::rule::implementation::dql::sort::SortImplementation;
+use crate::optimizer::rule::implementation::dql::sort::{
+    IncrementalSortImplementation, SortImplementation,
+};
 use crate::optimizer::rule::implementation::dql::table_scan::{
-    IndexScanImplementation, SeqScanImplementation,
+    IndexScanImplementation, MultiGetImplementation, SeqScanImplementation, SkipScanImplementation,
 };
 use crate::optimizer::rule::implementation::dql::values::ValuesImplementation;
 use crate::planner::operator::Operator;
@@ -41,6 +48,8 @@ pub enum ImplementationRuleImpl {
     // DQL
     GroupByAggregate,
     SimpleAggregate,
+    CountStar,
+    MinMaxIndex,
     Dummy,
     Filter,
     HashJoin,
@@ -48,9 +57,17 @@ pub enum ImplementationRuleImpl {
     Projection,
     SeqScan,
     FunctionScan,
+    ForeignScan,
     IndexScan,
+    MultiGet,
+    SkipScan,
     Sort,
+    IncrementalSort,
     Values,
+    // Fusion
+    FilterScanFusion,
+    ProjectScanFusion,
+    ProjectFilterScanFusion,
     // DML
     Analyze,
     CopyFromFile,
@@ -71,6 +88,8 @@ impl MatchPattern for ImplementationRuleImpl {
         match self {
             ImplementationRuleImpl::GroupByAggregate => GroupByAggregateImplementation.pattern(),
             ImplementationRuleImpl::SimpleAggregate => SimpleAggregateImplementation.pattern(),
+            ImplementationRuleImpl::CountStar => CountStarImplementation.pattern(),
+            ImplementationRuleImpl::MinMaxIndex => MinMaxIndexImplementation.pattern(),
             ImplementationRuleImpl::Dummy => DummyImplementation.pattern(),
             ImplementationRuleImpl::Filter => FilterImplementation.pattern(),
             ImplementationRuleImpl::HashJoin => JoinImplementation.pattern(),
@@ -78,9 +97,16 @@ impl MatchPattern for ImplementationRuleImpl {
             ImplementationRuleImpl::Projection => ProjectionImplementation.pattern(),
             ImplementationRuleImpl::SeqScan => SeqScanImplementation.pattern(),
             ImplementationRuleImpl::IndexScan => IndexScanImplementation.pattern(),
+            ImplementationRuleImpl::MultiGet => MultiGetImplementation.pattern(),
+            ImplementationRuleImpl::SkipScan => SkipScanImplementation.pattern(),
             ImplementationRuleImpl::FunctionScan => FunctionScanImplementation.pattern(),
+            ImplementationRuleImpl::ForeignScan => ForeignScanImplementation.pattern(),
             ImplementationRuleImpl::Sort => SortImplementation.pattern(),
+            ImplementationRuleImpl::IncrementalSort => IncrementalSortImplementation.pattern(),
             ImplementationRuleImpl::Values => ValuesImplementation.pattern(),
+            ImplementationRuleImpl::FilterScanFusion => FilterScanFusion.pattern(),
+            ImplementationRuleImpl::ProjectScanFusion => ProjectScanFusion.pattern(),
+            ImplementationRuleImpl::ProjectFilterScanFusion => ProjectFilterScanFusion.pattern(),
             ImplementationRuleImpl::CopyFromFile => CopyFromFileImplementation.pattern(),
             ImplementationRuleImpl::CopyToFile => CopyToFileImplementation.pattern(),
             ImplementationRuleImpl::Delete => DeleteImplementation.pattern(),
@@ -110,6 +136,12 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::SimpleAggregate => {
                 SimpleAggregateImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::CountStar => {
+                CountStarImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::MinMaxIndex => {
+                MinMaxIndexImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::Dummy => {
                 DummyImplementation.to_expression(operator, loader, group_expr)?
             }
@@ -131,15 +163,36 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::IndexScan => {
                 IndexScanImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::MultiGet => {
+                MultiGetImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::SkipScan => {
+                SkipScanImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::FunctionScan => {
                 FunctionScanImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::ForeignScan => {
+                ForeignScanImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::Sort => {
                 SortImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::IncrementalSort => {
+                IncrementalSortImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::Values => {
                 ValuesImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::FilterScanFusion => {
+                FilterScanFusion.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::ProjectScanFusion => {
+                ProjectScanFusion.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::ProjectFilterScanFusion => {
+                ProjectFilterScanFusion.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::CopyFromFile => {
                 CopyFromFileImplementation.to_expression(operator, loader, group_expr)?
             }
@@ -34,13 +34,19 @@ macro_rules! trans_references {
 
 impl ColumnPruning {
     fn clear_exprs(column_references: &HashSet<&ColumnSummary>, exprs: &mut Vec<ScalarExpression>) {
+        // Tips: only the output of an expression can be referenced above, the columns used
+        // inside it must not keep it alive, or unused composite expressions pull extra columns
         exprs.retain(|expr| {
-            if column_references.contains(expr.output_column().summary()) {
-                return true;
+            let mut expr = expr;
+            loop {
+                if column_references.contains(expr.output_column().summary()) {
+                    return true;
+                }
+                match expr {
+                    ScalarExpression::Alias { expr: inner, .. } => expr = inner,
+                    _ => return false,
+                }
             }
-            expr.referenced_columns(false)
-                .iter()
-                .any(|column| column_references.contains(column.summary()))
         })
     }
 
@@ -106,6 +112,12 @@ impl ColumnPruning {
                         .retain(|_, column| column_references.contains(column.summary()));
                 }
             }
+            Operator::ForeignScan(op) => {
+                if !all_referenced {
+                    op.columns
+                        .retain(|_, column| column_references.contains(column.summary()));
+                }
+            }
             Operator::Sort(_)
             | Operator::Limit(_)
             | Operator::Join(_)
@@ -123,8 +135,23 @@ impl ColumnPruning {
                     Self::_apply(copy_references, all_referenced, child_id, graph)?;
                 }
             }
+            // the rows are shared by the scans of the other references, which may read other columns
+            Operator::SharedScan(_) => {
+                if let Some(child_id) = graph.eldest_child_at(node_id) {
+                    Self::_apply(column_references, true, child_id, graph)?;
+                }
+            }
+            // the rows of each iteration are read by the next one as all the columns of the anchor
+            Operator::RecursiveUnion(_) => {
+                for child_id in graph.children_at(node_id).collect_vec() {
+                    Self::_apply(column_references.clone(), true, child_id, graph)?;
+                }
+            }
             // Last Operator
-            Operator::Dummy | Operator::Values(_) | Operator::FunctionScan(_) => (),
+            Operator::Dummy
+            | Operator::Values(_)
+            | Operator::FunctionScan(_)
+            | Operator::WorkTableScan(_) => (),
             Operator::Explain => {
                 if let Some(child_id) = graph.eldest_child_at(node_id) {
                     Self::_apply(column_references, true, child_id, graph)?;
@@ -150,17 +177,24 @@ impl ColumnPruning {
             Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateTask(_)
             | Operator::DropTable(_)
             | Operator::DropView(_)
+            | Operator::DropTask(_)
             | Operator::DropIndex(_)
+            | Operator::Reindex(_)
             | Operator::Truncate(_)
+            | Operator::Vacuum(_)
             | Operator::ShowTable
+            | Operator::ShowTableStatus
             | Operator::ShowView
+            | Operator::ShowTask
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_)
             | Operator::AddColumn(_)
             | Operator::DropColumn(_)
-            | Operator::Describe(_) => (),
+            | Operator::Describe(_)
+            | Operator::CheckTable(_) => (),
         }
 
         Ok(())
@@ -256,4 +290,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_column_pruning_composite_expr() -> Result<(), DatabaseError> {
+        let table_state = build_t1_table()?;
+        let plan = table_state.plan("select c1 from (select c1, c1 + c2 as s from t1) x")?;
+
+        let mut best_plan = HepOptimizer::new(plan)
+            .batch(
+                "test_column_pruning".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::ColumnPruning],
+            )
+            .find_best::<RocksTransaction>(None)?;
+
+        // the unused `c1 + c2` must not keep `c2` in the scan
+        loop {
+            if let Operator::TableScan(op) = &best_plan.operator {
+                let columns = op
+                    .columns
+                    .values()
+                    .map(|column| column.name().to_string())
+                    .collect::<Vec<_>>();
+                assert_eq!(columns, vec!["c1".to_string()]);
+                break;
+            }
+            best_plan = best_plan.childrens.pop_only();
+        }
+
+        Ok(())
+    }
 }
@@ -40,6 +40,12 @@ impl ExpressionRemapper {
             left_len = output_exprs.len();
             output_exprs.append(&mut second_output_exprs);
         }
+        // the second input of a union is read by its own expressions
+        if let Operator::Union(_) | Operator::RecursiveUnion(_) = graph.operator(node_id) {
+            if let Some(child_id) = graph.youngest_child_at(node_id) {
+                Self::_apply(&mut Vec::new(), child_id, graph)?;
+            }
+        }
         let operator = graph.operator_mut(node_id);
         match operator {
             Operator::Join(op) => {
@@ -59,16 +65,18 @@ impl ExpressionRemapper {
                 return Ok(());
             }
             Operator::Aggregate(op) => {
+                let try_reference = TryReference::new(output_exprs);
                 for expr in op.agg_calls.iter_mut().chain(op.groupby_exprs.iter_mut()) {
-                    TryReference::new(output_exprs).visit(expr)?;
+                    try_reference.clone().visit(expr)?;
                 }
             }
             Operator::Filter(op) => {
                 TryReference::new(output_exprs).visit(&mut op.predicate)?;
             }
             Operator::Project(op) => {
+                let try_reference = TryReference::new(output_exprs);
                 for expr in op.exprs.iter_mut() {
-                    TryReference::new(output_exprs).visit(expr)?;
+                    try_reference.clone().visit(expr)?;
                 }
             }
             Operator::Sort(op) => {
@@ -88,12 +96,16 @@ impl ExpressionRemapper {
             }
             Operator::Dummy
             | Operator::TableScan(_)
+            | Operator::ForeignScan(_)
             | Operator::Limit(_)
             | Operator::Values(_)
             | Operator::ShowTable
+            | Operator::ShowTableStatus
             | Operator::ShowView
+            | Operator::ShowTask
             | Operator::Explain
             | Operator::Describe(_)
+            | Operator::CheckTable(_)
             | Operator::Insert(_)
             | Operator::Delete(_)
             | Operator::Analyze(_)
@@ -102,13 +114,20 @@ impl ExpressionRemapper {
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateTask(_)
             | Operator::DropTable(_)
             | Operator::DropView(_)
+            | Operator::DropTask(_)
             | Operator::DropIndex(_)
+            | Operator::Reindex(_)
             | Operator::Truncate(_)
+            | Operator::Vacuum(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_)
-            | Operator::Union(_) => (),
+            | Operator::Union(_)
+            | Operator::SharedScan(_)
+            | Operator::RecursiveUnion(_)
+            | Operator::WorkTableScan(_) => (),
         }
         if let Some(exprs) = operator.output_exprs() {
             *output_exprs = exprs;
@@ -143,7 +162,9 @@ impl EvaluatorBind {
             Self::_apply(child_id, graph)?;
         }
         // for join
-        if let Operator::Join(_) = graph.operator(node_id) {
+        if let Operator::Join(_) | Operator::Union(_) | Operator::RecursiveUnion(_) =
+            graph.operator(node_id)
+        {
             if let Some(child_id) = graph.youngest_child_at(node_id) {
                 Self::_apply(child_id, graph)?;
             }
@@ -197,12 +218,16 @@ impl EvaluatorBind {
             }
             Operator::Dummy
             | Operator::TableScan(_)
+            | Operator::ForeignScan(_)
             | Operator::Limit(_)
             | Operator::Values(_)
             | Operator::ShowTable
+            | Operator::ShowTableStatus
             | Operator::ShowView
+            | Operator::ShowTask
             | Operator::Explain
             | Operator::Describe(_)
+            | Operator::CheckTable(_)
             | Operator::Insert(_)
             | Operator::Delete(_)
             | Operator::Analyze(_)
@@ -211,13 +236,20 @@ impl EvaluatorBind {
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateTask(_)
             | Operator::DropTable(_)
             | Operator::DropView(_)
+            | Operator::DropTask(_)
             | Operator::DropIndex(_)
+            | Operator::Reindex(_)
             | Operator::Truncate(_)
+            | Operator::Vacuum(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_)
-            | Operator::Union(_) => (),
+            | Operator::Union(_)
+            | Operator::SharedScan(_)
+            | Operator::RecursiveUnion(_)
+            | Operator::WorkTableScan(_) => (),
         }
 
         Ok(())
@@ -0,0 +1,354 @@
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::AliasType;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::properties::{
+    determined_columns, is_unique, not_null_columns,
+};
+use crate::optimizer::rule::normalization::pushdown_predicates::{
+    reduce_filters, split_conjunctive_predicates,
+};
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::Operator;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+static ELIMINATE_NOT_NULL_FILTER_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Filter(filter_op) if !filter_op.having),
+    children: PatternChildrenPredicate::None,
+});
+
+static ELIMINATE_DEPENDENT_GROUP_BY_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Aggregate(agg_op) if !agg_op.is_distinct && agg_op.groupby_exprs.len() > 1),
+    children: PatternChildrenPredicate::None,
+});
+
+static ELIMINATE_DISTINCT_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Aggregate(agg_op) if agg_op.agg_calls.is_empty() && !agg_op.groupby_exprs.is_empty()),
+    children: PatternChildrenPredicate::None,
+});
+
+/// Removes `column IS NOT NULL` from a filter when the column is `NOT NULL` in its table and
+/// not padded with `NULL` by an outer join below the filter.
+pub struct EliminateNotNullFilter;
+
+impl MatchPattern for EliminateNotNullFilter {
+    fn pattern(&self) -> &Pattern {
+        &ELIMINATE_NOT_NULL_FILTER_RULE
+    }
+}
+
+impl NormalizationRule for EliminateNotNullFilter {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Filter(filter_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        let Some(child_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let not_null = not_null_columns(graph, child_id);
+        let conjuncts = split_conjunctive_predicates(&filter_op.predicate);
+        let len = conjuncts.len();
+        let conjuncts = conjuncts
+            .into_iter()
+            .filter(|conjunct| {
+                !matches!(
+                    conjunct.unpack_alias_ref(),
+                    ScalarExpression::IsNull { negated: true, expr }
+                        if matches!(expr.unpack_alias_ref(), ScalarExpression::ColumnRef(column) if not_null.contains(column.summary()))
+                )
+            })
+            .collect_vec();
+        if conjuncts.len() == len {
+            return Ok(());
+        }
+
+        match reduce_filters(conjuncts, filter_op.having) {
+            Some(new_filter_op) => graph.replace_node(node_id, Operator::Filter(new_filter_op)),
+            None => {
+                let _ = graph.remove_node(node_id, false);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `DISTINCT`, or a `GROUP BY` without aggregations, by a projection of its columns
+/// when they cover a unique key of its input, whose rows are then already distinct.
+pub struct EliminateDistinct;
+
+impl MatchPattern for EliminateDistinct {
+    fn pattern(&self) -> &Pattern {
+        &ELIMINATE_DISTINCT_RULE
+    }
+}
+
+impl NormalizationRule for EliminateDistinct {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Aggregate(agg_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        let Some(child_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let keys = agg_op
+            .groupby_exprs
+            .iter()
+            .map(|expr| expr.output_column().summary().clone())
+            .collect();
+
+        if is_unique(graph, child_id, &keys, &HashSet::new()) {
+            let exprs = agg_op.groupby_exprs.clone();
+
+            graph.replace_node(node_id, Operator::Project(ProjectOperator { exprs }));
+        }
+        Ok(())
+    }
+}
+
+/// Removes the `GROUP BY` expressions functionally determined by the others, e.g. the columns of
+/// a table grouped by its primary key, which are read by `arbitrary` on the groups instead.
+///
+/// A projection above the aggregation keeps its output as it was.
+pub struct EliminateDependentGroupBy;
+
+impl MatchPattern for EliminateDependentGroupBy {
+    fn pattern(&self) -> &Pattern {
+        &ELIMINATE_DEPENDENT_GROUP_BY_RULE
+    }
+}
+
+impl NormalizationRule for EliminateDependentGroupBy {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Aggregate(agg_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        let Some(child_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let mut groupby_exprs = agg_op.groupby_exprs.clone();
+        let mut dependent_exprs = Vec::new();
+
+        // the expressions grouped by last are removed first
+        for i in (0..groupby_exprs.len()).rev() {
+            if groupby_exprs.len() < 2 {
+                break;
+            }
+            let keys = groupby_exprs
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, expr)| expr.output_column().summary().clone())
+                .collect();
+            let determined = determined_columns(graph, child_id, &keys);
+            let columns = groupby_exprs[i].referenced_columns(true);
+
+            if !columns.is_empty()
+                && columns
+                    .iter()
+                    .all(|column| determined.contains(column.summary()))
+            {
+                dependent_exprs.push(groupby_exprs.remove(i));
+            }
+        }
+        if dependent_exprs.is_empty() {
+            return Ok(());
+        }
+        let mut agg_calls = agg_op.agg_calls.clone();
+        let mut exprs = agg_op.agg_calls.clone();
+        let aggregate_memory_limit = agg_op.aggregate_memory_limit;
+
+        for expr in agg_op.groupby_exprs.iter() {
+            if !dependent_exprs.contains(expr) {
+                exprs.push(expr.clone());
+                continue;
+            }
+            let agg_call = ScalarExpression::AggCall {
+                distinct: false,
+                kind: AggKind::Arbitrary,
+                args: vec![expr.clone()],
+                ty: expr.return_type(),
+            };
+            agg_calls.push(agg_call.clone());
+            exprs.push(ScalarExpression::Alias {
+                expr: Box::new(agg_call),
+                alias: AliasType::Expr(Box::new(expr.clone())),
+            });
+        }
+        graph.add_node(
+            node_id,
+            Some(child_id),
+            Operator::Aggregate(AggregateOperator {
+                groupby_exprs,
+                agg_calls,
+                is_distinct: false,
+                aggregate_memory_limit,
+            }),
+        );
+        graph.replace_node(node_id, Operator::Project(ProjectOperator { exprs }));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::build_t1_table;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::aggregate::AggregateOperator;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn optimize(sql: &str, rule: NormalizationRuleImpl) -> Result<LogicalPlan, DatabaseError> {
+        let table_state = build_t1_table()?;
+        let plan = table_state.plan(sql)?;
+
+        HepOptimizer::new(plan)
+            .batch(
+                "test_constraint_pruning".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![rule],
+            )
+            .find_best::<RocksTransaction>(None)
+    }
+
+    fn has_operator(plan: &LogicalPlan, predicate: fn(&Operator) -> bool) -> bool {
+        predicate(&plan.operator)
+            || plan
+                .childrens
+                .iter()
+                .any(|child| has_operator(child, predicate))
+    }
+
+    #[test]
+    fn test_eliminate_not_null_filter() -> Result<(), DatabaseError> {
+        let has_filter = |sql| -> Result<bool, DatabaseError> {
+            let plan = optimize(sql, NormalizationRuleImpl::EliminateNotNullFilter)?;
+
+            Ok(has_operator(&plan, |op| matches!(op, Operator::Filter(_))))
+        };
+
+        assert!(!has_filter("select c1 from t1 where c2 is not null")?);
+        assert!(has_filter(
+            "select c1 from t1 where c2 is not null and c1 > 1"
+        )?);
+        assert!(!has_filter(
+            "select * from t1 join t2 on c1 = c3 where c4 is not null"
+        )?);
+        // padded with `NULL` by the left join
+        assert!(has_filter(
+            "select * from t1 left join t2 on c1 = c3 where c4 is not null"
+        )?);
+        assert!(has_filter("select c1 from t1 where c2 is null")?);
+
+        let plan = optimize(
+            "select c1 from t1 where c2 is not null and c1 > 1",
+            NormalizationRuleImpl::EliminateNotNullFilter,
+        )?;
+        assert!(!plan.explain(0).contains("is not null"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_eliminate_dependent_group_by() -> Result<(), DatabaseError> {
+        fn find_aggregate(plan: &LogicalPlan) -> Option<&AggregateOperator> {
+            match &plan.operator {
+                Operator::Aggregate(agg_op) => Some(agg_op),
+                _ => plan.childrens.iter().find_map(find_aggregate),
+            }
+        }
+        let group_by_len = |sql| -> Result<(usize, usize), DatabaseError> {
+            let plan = optimize(sql, NormalizationRuleImpl::EliminateDependentGroupBy)?;
+            let agg_op = find_aggregate(&plan).unwrap();
+
+            Ok((agg_op.groupby_exprs.len(), agg_op.agg_calls.len()))
+        };
+
+        assert_eq!(
+            group_by_len("select c3, c4, count(*) from t2 group by c3")?,
+            (1, 2)
+        );
+        assert_eq!(
+            group_by_len("select c3, c4 + 1, count(*) from t2 group by c3")?,
+            (1, 2)
+        );
+        assert_eq!(
+            group_by_len("select c3, c4, count(*) from t2 group by c3, c4")?,
+            (1, 2)
+        );
+        assert_eq!(
+            group_by_len("select c4, count(*) from t2 where c3 = 1 group by c4")?,
+            (1, 1)
+        );
+        assert_eq!(
+            group_by_len("select c1, c3, c4, count(*) from t1 join t2 on c1 = c3 group by c1, c3")?,
+            (1, 3)
+        );
+        assert_eq!(
+            group_by_len(
+                "select c1, c3, count(*) from t1 left join t2 on c1 = c3 group by c1, c3"
+            )?,
+            (2, 1)
+        );
+
+        let plan = optimize(
+            "select c3, c4, count(*) from t2 group by c3",
+            NormalizationRuleImpl::EliminateDependentGroupBy,
+        )?;
+        assert!(matches!(plan.operator, Operator::Project(_)));
+        assert!(matches!(
+            plan.childrens.iter().next().map(|child| &child.operator),
+            Some(Operator::Project(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_eliminate_distinct() -> Result<(), DatabaseError> {
+        let has_aggregate = |sql| -> Result<bool, DatabaseError> {
+            let plan = optimize(sql, NormalizationRuleImpl::EliminateDistinct)?;
+
+            Ok(has_operator(&plan, |op| {
+                matches!(op, Operator::Aggregate(_))
+            }))
+        };
+
+        // the primary key
+        assert!(!has_aggregate("select distinct c1, c2 from t1")?);
+        assert!(!has_aggregate("select c1 from t1 group by c1")?);
+        // the unique index of c2
+        assert!(!has_aggregate("select distinct c2 from t1 where c1 > 1")?);
+        assert!(has_aggregate("select distinct c4 from t2")?);
+        // equal to a constant, the primary key reads at most one row
+        assert!(!has_aggregate("select distinct c4 from t2 where c3 = 1")?);
+        // each row of t1 matches at most one row of t2 by its primary key
+        assert!(!has_aggregate(
+            "select distinct c1, c4 from t1 join t2 on c2 = c3"
+        )?);
+        assert!(!has_aggregate(
+            "select distinct c1 from t1 left join t2 on c2 = c3"
+        )?);
+        assert!(has_aggregate(
+            "select distinct c1 from t1 join t2 on c2 = c4"
+        )?);
+        assert!(has_aggregate(
+            "select distinct c4 from t1 join t2 on c2 = c3"
+        )?);
+        assert!(!has_aggregate(
+            "select distinct c1 from t1 join t2 on c2 = c4 where c3 = 1"
+        )?);
+
+        Ok(())
+    }
+}
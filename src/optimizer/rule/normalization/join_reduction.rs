@@ -0,0 +1,342 @@
+use crate::catalog::ColumnSummary;
+use crate::errors::DatabaseError;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::planner::operator::join::{JoinCondition, JoinType};
+use crate::planner::operator::Operator;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+static SEMI_JOIN_REDUCTION_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Aggregate(agg_op) if agg_op.agg_calls.is_empty()),
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: |op| {
+            matches!(
+                op,
+                Operator::Join(join_op) if join_op.join_type == JoinType::Inner
+                    && matches!(&join_op.on, JoinCondition::On { on, .. } if !on.is_empty())
+            )
+        },
+        children: PatternChildrenPredicate::None,
+    }]),
+});
+
+static ELIMINATE_OUTER_JOIN_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Filter(filter_op) if !filter_op.having),
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: |op| {
+            matches!(
+                op,
+                Operator::Join(join_op) if matches!(
+                    join_op.join_type,
+                    JoinType::LeftOuter | JoinType::RightOuter | JoinType::Full
+                )
+            )
+        },
+        children: PatternChildrenPredicate::None,
+    }]),
+});
+
+/// All columns referenced by the operators of the subtree rooted at `node_id`.
+fn subtree_columns(graph: &HepGraph, node_id: HepNodeId) -> HashSet<ColumnSummary> {
+    graph
+        .nodes_iter(Some(node_id))
+        .flat_map(|id| graph.operator(id).referenced_columns(true))
+        .map(|column| column.summary().clone())
+        .collect()
+}
+
+/// Converts an inner join into a left semi join when the right side is only used to check
+/// existence, i.e. the `DISTINCT`/`GROUP BY` above it only uses columns of the left side.
+///
+/// The duplicate elimination above makes both results the same, while the semi join
+/// emits each left tuple at most once, no matter how many right tuples match it.
+pub struct SemiJoinReduction;
+
+impl MatchPattern for SemiJoinReduction {
+    fn pattern(&self) -> &Pattern {
+        &SEMI_JOIN_REDUCTION_RULE
+    }
+}
+
+impl NormalizationRule for SemiJoinReduction {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Aggregate(agg_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        let Some(join_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let group_columns = agg_op
+            .groupby_exprs
+            .iter()
+            .flat_map(|expr| expr.referenced_columns(true))
+            .collect_vec();
+        let join_childs = graph.children_at(join_id).collect_vec();
+        if join_childs.len() != 2 {
+            return Ok(());
+        }
+        let left_columns = subtree_columns(graph, join_childs[0]);
+        let right_columns = subtree_columns(graph, join_childs[1]);
+
+        if group_columns
+            .iter()
+            .map(|column| column.summary())
+            .all(|summary| left_columns.contains(summary) && !right_columns.contains(summary))
+        {
+            if let Operator::Join(join_op) = graph.operator_mut(join_id) {
+                join_op.join_type = JoinType::LeftSemi;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether `expr` evaluates to `NULL` when any column of `columns` it uses is `NULL`
+/// and it uses at least one of them.
+fn is_strict(expr: &ScalarExpression, columns: &HashSet<ColumnSummary>) -> bool {
+    match expr {
+        ScalarExpression::ColumnRef(column) => columns.contains(column.summary()),
+        ScalarExpression::Alias { expr, .. }
+        | ScalarExpression::TypeCast { expr, .. }
+        | ScalarExpression::Unary { expr, .. } => is_strict(expr, columns),
+        ScalarExpression::Binary {
+            op,
+            left_expr,
+            right_expr,
+            ..
+        } => {
+            !matches!(
+                op,
+                BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Spaceship
+            ) && (is_strict(left_expr, columns) || is_strict(right_expr, columns))
+        }
+        _ => false,
+    }
+}
+
+/// Whether the predicate can not be `true` when all columns of `columns` are `NULL`,
+/// i.e. it filters out the tuples padded with `NULL` by an outer join.
+fn is_null_rejecting(predicate: &ScalarExpression, columns: &HashSet<ColumnSummary>) -> bool {
+    match predicate {
+        ScalarExpression::Alias { expr, .. } => is_null_rejecting(expr, columns),
+        ScalarExpression::Binary {
+            op: BinaryOperator::And,
+            left_expr,
+            right_expr,
+            ..
+        } => is_null_rejecting(left_expr, columns) || is_null_rejecting(right_expr, columns),
+        ScalarExpression::Binary {
+            op: BinaryOperator::Or,
+            left_expr,
+            right_expr,
+            ..
+        } => is_null_rejecting(left_expr, columns) && is_null_rejecting(right_expr, columns),
+        ScalarExpression::Binary {
+            op:
+                BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Gt
+                | BinaryOperator::Lt
+                | BinaryOperator::GtEq
+                | BinaryOperator::LtEq
+                | BinaryOperator::Like(_)
+                | BinaryOperator::NotLike(_),
+            left_expr,
+            right_expr,
+            ..
+        } => is_strict(left_expr, columns) || is_strict(right_expr, columns),
+        ScalarExpression::IsNull {
+            negated: true,
+            expr,
+        }
+        | ScalarExpression::In { expr, .. }
+        | ScalarExpression::Between { expr, .. } => is_strict(expr, columns),
+        _ => false,
+    }
+}
+
+/// Converts an outer join into an inner join when the filter above it rejects the tuples
+/// padded with `NULL` for the outer side, e.g. `LEFT JOIN ... WHERE right.c = 5`.
+///
+/// A full join only keeps the outer sides whose padded tuples survive the filter.
+pub struct EliminateOuterJoin;
+
+impl MatchPattern for EliminateOuterJoin {
+    fn pattern(&self) -> &Pattern {
+        &ELIMINATE_OUTER_JOIN_RULE
+    }
+}
+
+impl NormalizationRule for EliminateOuterJoin {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Filter(filter_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        let Some(join_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let Operator::Join(join_op) = graph.operator(join_id) else {
+            return Ok(());
+        };
+        let join_childs = graph.children_at(join_id).collect_vec();
+        if join_childs.len() != 2 {
+            return Ok(());
+        }
+        let rejects_left = matches!(join_op.join_type, JoinType::RightOuter | JoinType::Full)
+            && is_null_rejecting(
+                &filter_op.predicate,
+                &subtree_columns(graph, join_childs[0]),
+            );
+        let rejects_right = matches!(join_op.join_type, JoinType::LeftOuter | JoinType::Full)
+            && is_null_rejecting(
+                &filter_op.predicate,
+                &subtree_columns(graph, join_childs[1]),
+            );
+
+        let join_type = match (join_op.join_type, rejects_left, rejects_right) {
+            (JoinType::LeftOuter, _, true)
+            | (JoinType::RightOuter, true, _)
+            | (JoinType::Full, true, true) => JoinType::Inner,
+            (JoinType::Full, true, false) => JoinType::LeftOuter,
+            (JoinType::Full, false, true) => JoinType::RightOuter,
+            _ => return Ok(()),
+        };
+        if let Operator::Join(join_op) = graph.operator_mut(join_id) {
+            join_op.join_type = join_type;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::build_t1_table;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::join::JoinType;
+    use crate::planner::operator::Operator;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn join_type(sql: &str, rule: NormalizationRuleImpl) -> Result<JoinType, DatabaseError> {
+        let table_state = build_t1_table()?;
+        let plan = table_state.plan(sql)?;
+
+        let best_plan = HepOptimizer::new(plan)
+            .batch(
+                "test_join_reduction".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![rule],
+            )
+            .find_best::<RocksTransaction>(None)?;
+        let mut plan = best_plan;
+        loop {
+            if let Operator::Join(op) = &plan.operator {
+                return Ok(op.join_type);
+            }
+            plan = plan.childrens.pop_only();
+        }
+    }
+
+    #[test]
+    fn test_semi_join_reduction() -> Result<(), DatabaseError> {
+        assert_eq!(
+            join_type(
+                "select distinct c1, c2 from t1 join t2 on c1 = c3",
+                NormalizationRuleImpl::SemiJoinReduction
+            )?,
+            JoinType::LeftSemi
+        );
+        assert_eq!(
+            join_type(
+                "select c1 from t1 join t2 on c1 = c3 group by c1",
+                NormalizationRuleImpl::SemiJoinReduction
+            )?,
+            JoinType::LeftSemi
+        );
+        // the right side is used by the output
+        assert_eq!(
+            join_type(
+                "select distinct c1, c4 from t1 join t2 on c1 = c3",
+                NormalizationRuleImpl::SemiJoinReduction
+            )?,
+            JoinType::Inner
+        );
+        // duplicates are observable by the aggregation
+        assert_eq!(
+            join_type(
+                "select count(c1) from t1 join t2 on c1 = c3",
+                NormalizationRuleImpl::SemiJoinReduction
+            )?,
+            JoinType::Inner
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_eliminate_outer_join() -> Result<(), DatabaseError> {
+        let rule = || NormalizationRuleImpl::EliminateOuterJoin;
+
+        assert_eq!(
+            join_type(
+                "select * from t1 left join t2 on c1 = c3 where c4 = 5",
+                rule()
+            )?,
+            JoinType::Inner
+        );
+        assert_eq!(
+            join_type(
+                "select * from t1 right join t2 on c1 = c3 where c2 + 1 > 5 and c4 = 1",
+                rule()
+            )?,
+            JoinType::Inner
+        );
+        assert_eq!(
+            join_type(
+                "select * from t1 full join t2 on c1 = c3 where c2 is not null",
+                rule()
+            )?,
+            JoinType::LeftOuter
+        );
+        assert_eq!(
+            join_type(
+                "select * from t1 full join t2 on c1 = c3 where c2 = 1 and c4 in (1, 2)",
+                rule()
+            )?,
+            JoinType::Inner
+        );
+        // the padded tuples may pass the filter
+        assert_eq!(
+            join_type(
+                "select * from t1 left join t2 on c1 = c3 where c4 is null",
+                rule()
+            )?,
+            JoinType::LeftOuter
+        );
+        assert_eq!(
+            join_type(
+                "select * from t1 left join t2 on c1 = c3 where c4 = 5 or c2 = 1",
+                rule()
+            )?,
+            JoinType::LeftOuter
+        );
+        // only filters the preserved side
+        assert_eq!(
+            join_type(
+                "select * from t1 left join t2 on c1 = c3 where c2 = 1",
+                rule()
+            )?,
+            JoinType::LeftOuter
+        );
+
+        Ok(())
+    }
+}
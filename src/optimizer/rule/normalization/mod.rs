@@ -10,42 +10,83 @@ use crate::optimizer::rule::normalization::combine_operators::{
 use crate::optimizer::rule::normalization::compilation_in_advance::{
     EvaluatorBind, ExpressionRemapper,
 };
+use crate::optimizer::rule::normalization::constraint_pruning::{
+    EliminateDependentGroupBy, EliminateDistinct, EliminateNotNullFilter,
+};
+use crate::optimizer::rule::normalization::join_reduction::{
+    EliminateOuterJoin, SemiJoinReduction,
+};
 use crate::optimizer::rule::normalization::pushdown_limit::{
-    LimitProjectTranspose, PushLimitIntoScan, PushLimitThroughJoin,
+    EliminateSort, LimitProjectTranspose, PushLimitIntoScan, PushLimitThroughJoin,
+    PushNearestNeighbourIntoScan, PushSortIntoScan,
 };
+use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateIntoForeignScan;
 use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateIntoScan;
 use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateThroughJoin;
+use crate::optimizer::rule::normalization::shared_subquery::ShareRepeatedSubquery;
 use crate::optimizer::rule::normalization::simplification::ConstantCalculation;
 use crate::optimizer::rule::normalization::simplification::SimplifyFilter;
 
+use std::fmt;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
 mod column_pruning;
 mod combine_operators;
 mod compilation_in_advance;
+mod constraint_pruning;
+mod join_reduction;
+mod properties;
 mod pushdown_limit;
 mod pushdown_predicates;
+mod shared_subquery;
 mod simplification;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum NormalizationRuleImpl {
     ColumnPruning,
     // Combine operators
     CollapseProject,
     CollapseGroupByAgg,
     CombineFilter,
+    // Join reduction
+    EliminateOuterJoin,
+    SemiJoinReduction,
+    // Constraint pruning
+    EliminateNotNullFilter,
+    EliminateDistinct,
+    EliminateDependentGroupBy,
     // PushDown limit
     LimitProjectTranspose,
     PushLimitThroughJoin,
     PushLimitIntoTableScan,
+    PushNearestNeighbourIntoScan,
+    PushSortIntoScan,
+    EliminateSort,
     // PushDown predicates
     PushPredicateThroughJoin,
     // Tips: need to be used with `SimplifyFilter`
     PushPredicateIntoScan,
+    PushPredicateIntoForeignScan,
     // Simplification
     SimplifyFilter,
     ConstantCalculation,
+    // Subquery sharing
+    ShareRepeatedSubquery,
     // CompilationInAdvance
     ExpressionRemapper,
     EvaluatorBind,
+    // Registered by the embedder
+    Custom(ArcNormalizationRule),
+}
+
+#[derive(Clone)]
+pub struct ArcNormalizationRule(pub Arc<dyn NormalizationRule + Send + Sync>);
+
+impl fmt::Debug for ArcNormalizationRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ArcNormalizationRule")
+    }
 }
 
 impl MatchPattern for NormalizationRuleImpl {
@@ -55,15 +96,30 @@ impl MatchPattern for NormalizationRuleImpl {
             NormalizationRuleImpl::CollapseProject => CollapseProject.pattern(),
             NormalizationRuleImpl::CollapseGroupByAgg => CollapseGroupByAgg.pattern(),
             NormalizationRuleImpl::CombineFilter => CombineFilter.pattern(),
+            NormalizationRuleImpl::EliminateOuterJoin => EliminateOuterJoin.pattern(),
+            NormalizationRuleImpl::SemiJoinReduction => SemiJoinReduction.pattern(),
+            NormalizationRuleImpl::EliminateNotNullFilter => EliminateNotNullFilter.pattern(),
+            NormalizationRuleImpl::EliminateDistinct => EliminateDistinct.pattern(),
+            NormalizationRuleImpl::EliminateDependentGroupBy => EliminateDependentGroupBy.pattern(),
             NormalizationRuleImpl::LimitProjectTranspose => LimitProjectTranspose.pattern(),
             NormalizationRuleImpl::PushLimitThroughJoin => PushLimitThroughJoin.pattern(),
             NormalizationRuleImpl::PushLimitIntoTableScan => PushLimitIntoScan.pattern(),
+            NormalizationRuleImpl::PushSortIntoScan => PushSortIntoScan.pattern(),
+            NormalizationRuleImpl::EliminateSort => EliminateSort.pattern(),
+            NormalizationRuleImpl::PushNearestNeighbourIntoScan => {
+                PushNearestNeighbourIntoScan.pattern()
+            }
             NormalizationRuleImpl::PushPredicateThroughJoin => PushPredicateThroughJoin.pattern(),
             NormalizationRuleImpl::PushPredicateIntoScan => PushPredicateIntoScan.pattern(),
+            NormalizationRuleImpl::PushPredicateIntoForeignScan => {
+                PushPredicateIntoForeignScan.pattern()
+            }
             NormalizationRuleImpl::SimplifyFilter => SimplifyFilter.pattern(),
             NormalizationRuleImpl::ConstantCalculation => ConstantCalculation.pattern(),
+            NormalizationRuleImpl::ShareRepeatedSubquery => ShareRepeatedSubquery.pattern(),
             NormalizationRuleImpl::ExpressionRemapper => ExpressionRemapper.pattern(),
             NormalizationRuleImpl::EvaluatorBind => EvaluatorBind.pattern(),
+            NormalizationRuleImpl::Custom(rule) => rule.0.pattern(),
         }
     }
 }
@@ -75,6 +131,15 @@ impl NormalizationRule for NormalizationRuleImpl {
             NormalizationRuleImpl::CollapseProject => CollapseProject.apply(node_id, graph),
             NormalizationRuleImpl::CollapseGroupByAgg => CollapseGroupByAgg.apply(node_id, graph),
             NormalizationRuleImpl::CombineFilter => CombineFilter.apply(node_id, graph),
+            NormalizationRuleImpl::EliminateOuterJoin => EliminateOuterJoin.apply(node_id, graph),
+            NormalizationRuleImpl::SemiJoinReduction => SemiJoinReduction.apply(node_id, graph),
+            NormalizationRuleImpl::EliminateNotNullFilter => {
+                EliminateNotNullFilter.apply(node_id, graph)
+            }
+            NormalizationRuleImpl::EliminateDistinct => EliminateDistinct.apply(node_id, graph),
+            NormalizationRuleImpl::EliminateDependentGroupBy => {
+                EliminateDependentGroupBy.apply(node_id, graph)
+            }
             NormalizationRuleImpl::LimitProjectTranspose => {
                 LimitProjectTranspose.apply(node_id, graph)
             }
@@ -84,6 +149,11 @@ impl NormalizationRule for NormalizationRuleImpl {
             NormalizationRuleImpl::PushLimitIntoTableScan => {
                 PushLimitIntoScan.apply(node_id, graph)
             }
+            NormalizationRuleImpl::PushNearestNeighbourIntoScan => {
+                PushNearestNeighbourIntoScan.apply(node_id, graph)
+            }
+            NormalizationRuleImpl::PushSortIntoScan => PushSortIntoScan.apply(node_id, graph),
+            NormalizationRuleImpl::EliminateSort => EliminateSort.apply(node_id, graph),
             NormalizationRuleImpl::PushPredicateThroughJoin => {
                 PushPredicateThroughJoin.apply(node_id, graph)
             }
@@ -91,9 +161,16 @@ impl NormalizationRule for NormalizationRuleImpl {
             NormalizationRuleImpl::PushPredicateIntoScan => {
                 PushPredicateIntoScan.apply(node_id, graph)
             }
+            NormalizationRuleImpl::PushPredicateIntoForeignScan => {
+                PushPredicateIntoForeignScan.apply(node_id, graph)
+            }
             NormalizationRuleImpl::ConstantCalculation => ConstantCalculation.apply(node_id, graph),
+            NormalizationRuleImpl::ShareRepeatedSubquery => {
+                ShareRepeatedSubquery.apply(node_id, graph)
+            }
             NormalizationRuleImpl::ExpressionRemapper => ExpressionRemapper.apply(node_id, graph),
             NormalizationRuleImpl::EvaluatorBind => EvaluatorBind.apply(node_id, graph),
+            NormalizationRuleImpl::Custom(rule) => rule.0.apply(node_id, graph),
         }
     }
 }
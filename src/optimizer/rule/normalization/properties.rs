@@ -0,0 +1,353 @@
+//! The properties of the rows given by a node of the plan, derived from the nodes below it, for
+//! the rules to reason about: the columns that are never `NULL`, the keys that are unique, the
+//! columns a key determines and the order of the rows.
+use crate::catalog::ColumnSummary;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::pushdown_predicates::split_conjunctive_predicates;
+use crate::planner::operator::join::{JoinCondition, JoinType};
+use crate::planner::operator::sort::SortField;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::types::index::IndexType;
+use itertools::Itertools;
+use std::collections::HashSet;
+
+/// A column the rows are ordered by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SortKey {
+    pub(super) column: ColumnSummary,
+    pub(super) asc: bool,
+    pub(super) nulls_first: bool,
+}
+
+/// The columns of the output of the subtree that can not be `NULL`, by the `NOT NULL` of the
+/// columns of the tables, except those padded with `NULL` by an outer join.
+pub(super) fn not_null_columns(graph: &HepGraph, node_id: HepNodeId) -> HashSet<ColumnSummary> {
+    let childrens = graph.children_at(node_id).collect_vec();
+
+    match graph.operator(node_id) {
+        Operator::TableScan(scan_op) => scan_op
+            .columns
+            .values()
+            .filter(|column| !column.nullable())
+            .map(|column| column.summary().clone())
+            .collect(),
+        Operator::Filter(_) | Operator::Project(_) | Operator::Sort(_) | Operator::Limit(_) => {
+            not_null_columns(graph, childrens[0])
+        }
+        Operator::Join(join_op) => {
+            let [left_id, right_id] = childrens[..] else {
+                return HashSet::new();
+            };
+            let (left_nullable, right_nullable) = match join_op.join_type {
+                JoinType::Inner | JoinType::Cross => (false, false),
+                JoinType::LeftOuter | JoinType::LeftSemi | JoinType::LeftAnti => (false, true),
+                JoinType::RightOuter => (true, false),
+                JoinType::Full => (true, true),
+            };
+            let mut columns = HashSet::new();
+            if !left_nullable {
+                columns.extend(not_null_columns(graph, left_id));
+            }
+            if !right_nullable {
+                columns.extend(not_null_columns(graph, right_id));
+            }
+            columns
+        }
+        _ => HashSet::new(),
+    }
+}
+
+/// The columns equal to a constant by the conjuncts of a predicate.
+fn constant_columns(predicate: &ScalarExpression) -> Vec<ColumnSummary> {
+    split_conjunctive_predicates(predicate)
+        .into_iter()
+        .filter_map(|conjunct| {
+            let ScalarExpression::Binary {
+                op: BinaryOperator::Eq,
+                left_expr,
+                right_expr,
+                ..
+            } = conjunct.unpack_alias()
+            else {
+                return None;
+            };
+            match (left_expr.unpack_alias_ref(), right_expr.unpack_alias_ref()) {
+                (ScalarExpression::ColumnRef(column), expr)
+                | (expr, ScalarExpression::ColumnRef(column))
+                    if expr.referenced_columns(true).is_empty() && !expr.has_agg_call() =>
+                {
+                    Some(column.summary().clone())
+                }
+                _ => None,
+            }
+        })
+        .collect_vec()
+}
+
+/// Whether `keys` and `fixed` cover the primary key or a unique index of `NOT NULL` columns of
+/// the table.
+fn is_scan_unique(
+    scan_op: &TableScanOperator,
+    keys: &HashSet<ColumnSummary>,
+    fixed: &HashSet<ColumnSummary>,
+) -> bool {
+    let covers = |column_ids: &[_]| {
+        column_ids.iter().all(|column_id| {
+            scan_op.columns.values().any(|column| {
+                column.id().as_ref() == Some(column_id)
+                    && (keys.contains(column.summary()) || fixed.contains(column.summary()))
+            })
+        })
+    };
+    let is_not_null = |column_ids: &[_]| {
+        scan_op.columns.values().all(|column| {
+            !column.id().is_some_and(|id| column_ids.contains(&id)) || !column.nullable()
+        })
+    };
+    covers(&scan_op.primary_keys[..])
+        || scan_op.index_infos.iter().any(|index_info| {
+            matches!(index_info.meta.ty, IndexType::Unique)
+                && is_not_null(&index_info.meta.column_ids[..])
+                && covers(&index_info.meta.column_ids[..])
+        })
+}
+
+/// The columns of the output of the subtree functionally determined by `keys`: those of each
+/// table whose key `keys` cover, besides `keys` themselves.
+///
+/// The rows of a table padded with `NULL` by an outer join have a `NULL` key and `NULL` columns,
+/// which keeps the columns determined.
+pub(super) fn determined_columns(
+    graph: &HepGraph,
+    node_id: HepNodeId,
+    keys: &HashSet<ColumnSummary>,
+) -> HashSet<ColumnSummary> {
+    let childrens = graph.children_at(node_id).collect_vec();
+
+    match graph.operator(node_id) {
+        Operator::TableScan(scan_op) => {
+            if is_scan_unique(scan_op, keys, &HashSet::new()) {
+                scan_op
+                    .columns
+                    .values()
+                    .map(|column| column.summary().clone())
+                    .chain(keys.iter().cloned())
+                    .collect()
+            } else {
+                keys.clone()
+            }
+        }
+        Operator::Filter(filter_op) => {
+            let mut keys = keys.clone();
+            keys.extend(constant_columns(&filter_op.predicate));
+
+            determined_columns(graph, childrens[0], &keys)
+        }
+        Operator::Project(_) | Operator::Sort(_) | Operator::Limit(_) => {
+            determined_columns(graph, childrens[0], keys)
+        }
+        Operator::Join(join_op) => {
+            let [left_id, right_id] = childrens[..] else {
+                return keys.clone();
+            };
+            let mut keys = keys.clone();
+            // the columns of an inner join are equal to those they are joined on
+            if let (JoinType::Inner, JoinCondition::On { on, .. }) =
+                (join_op.join_type, &join_op.on)
+            {
+                for (left_expr, right_expr) in on {
+                    let left_column = left_expr.output_column().summary().clone();
+                    let right_column = right_expr.output_column().summary().clone();
+
+                    if keys.contains(&left_column) {
+                        keys.insert(right_column);
+                    } else if keys.contains(&right_column) {
+                        keys.insert(left_column);
+                    }
+                }
+            }
+            let mut columns = determined_columns(graph, left_id, &keys);
+            columns.extend(determined_columns(graph, right_id, &keys));
+            columns
+        }
+        _ => keys.clone(),
+    }
+}
+
+/// Whether no two rows of the output of the subtree are equal on `keys`, given that the columns
+/// of `fixed` are equal to a constant, by the primary keys and the unique indexes of the tables.
+///
+/// A unique key equal to a constant makes a scan read at most one row, e.g. `WHERE pk = 1`, and
+/// a join on a unique key of a side matches each row of the other side at most once, which keeps
+/// the rows of the output as unique as the rows of that other side.
+pub(super) fn is_unique(
+    graph: &HepGraph,
+    node_id: HepNodeId,
+    keys: &HashSet<ColumnSummary>,
+    fixed: &HashSet<ColumnSummary>,
+) -> bool {
+    let childrens = graph.children_at(node_id).collect_vec();
+
+    match graph.operator(node_id) {
+        Operator::TableScan(scan_op) => is_scan_unique(scan_op, keys, fixed),
+        Operator::Filter(filter_op) => {
+            let mut fixed = fixed.clone();
+            fixed.extend(constant_columns(&filter_op.predicate));
+
+            is_unique(graph, childrens[0], keys, &fixed)
+        }
+        Operator::Project(_) | Operator::Sort(_) | Operator::Limit(_) => {
+            is_unique(graph, childrens[0], keys, fixed)
+        }
+        Operator::Aggregate(agg_op) => agg_op.groupby_exprs.iter().all(|expr| {
+            let summary = expr.output_column().summary().clone();
+
+            keys.contains(&summary) || fixed.contains(&summary)
+        }),
+        Operator::Join(join_op) => {
+            let [left_id, right_id] = childrens[..] else {
+                return false;
+            };
+            // the columns of each side equal to the other side
+            let (left_on, right_on): (HashSet<_>, HashSet<_>) = match &join_op.on {
+                JoinCondition::On { on, .. } => on
+                    .iter()
+                    .map(|(left_expr, right_expr)| {
+                        (
+                            left_expr.output_column().summary().clone(),
+                            right_expr.output_column().summary().clone(),
+                        )
+                    })
+                    .unzip(),
+                JoinCondition::None => (HashSet::new(), HashSet::new()),
+            };
+
+            match join_op.join_type {
+                JoinType::LeftSemi | JoinType::LeftAnti => is_unique(graph, left_id, keys, fixed),
+                JoinType::Inner | JoinType::LeftOuter | JoinType::Cross => {
+                    let is_left_unique = is_unique(graph, left_id, keys, fixed);
+                    let is_right_unique = is_unique(graph, right_id, keys, fixed);
+
+                    (is_left_unique
+                        && (is_right_unique || is_unique(graph, right_id, &right_on, fixed)))
+                        || (join_op.join_type != JoinType::LeftOuter
+                            && is_right_unique
+                            && is_unique(graph, left_id, &left_on, fixed))
+                }
+                JoinType::RightOuter | JoinType::Full => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// The order of the rows of a scan read by its primary key, from the last key down if `reverse`.
+pub(super) fn scan_ordering(scan_op: &TableScanOperator, reverse: bool) -> Vec<SortKey> {
+    scan_op
+        .primary_keys
+        .iter()
+        .map_while(|column_id| {
+            scan_op
+                .columns
+                .values()
+                .find(|column| column.id() == Some(*column_id))
+        })
+        .map(|column| SortKey {
+            column: column.summary().clone(),
+            asc: !reverse,
+            // the NULLs are below the values of the keys
+            nulls_first: !reverse,
+        })
+        .collect()
+}
+
+/// The order of the rows of the output of the subtree, by its leading columns.
+///
+/// A scan keeps the order of its primary key only when no other index may be chosen to read it,
+/// as `PushSortIntoScan` leaves it, and the joins and the aggregations give no order.
+pub(super) fn ordering(graph: &HepGraph, node_id: HepNodeId) -> Vec<SortKey> {
+    let childrens = graph.children_at(node_id).collect_vec();
+
+    match graph.operator(node_id) {
+        Operator::TableScan(scan_op) => {
+            if scan_op
+                .index_infos
+                .iter()
+                .all(|index_info| matches!(index_info.meta.ty, IndexType::PrimaryKey { .. }))
+            {
+                scan_ordering(scan_op, scan_op.reverse)
+            } else {
+                Vec::new()
+            }
+        }
+        Operator::Filter(_) | Operator::Limit(_) => ordering(graph, childrens[0]),
+        Operator::Project(project_op) => {
+            let mut keys = ordering(graph, childrens[0]);
+            let len = keys
+                .iter()
+                .take_while(|key| {
+                    project_op.exprs.iter().any(|expr| {
+                        matches!(expr, ScalarExpression::ColumnRef(column) if column.summary() == &key.column)
+                    })
+                })
+                .count();
+            keys.truncate(len);
+            keys
+        }
+        Operator::Sort(sort_op) => sort_op
+            .sort_fields
+            .iter()
+            .map_while(|field| match field.expr.unpack_alias_ref() {
+                ScalarExpression::ColumnRef(column) => Some(SortKey {
+                    column: column.summary().clone(),
+                    asc: field.asc,
+                    nulls_first: field.nulls_first,
+                }),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// How many of the leading `sort_fields` the rows ordered by `keys` are sorted by, the NULLs of
+/// the columns of `not_null` being nowhere.
+pub(super) fn sorted_prefix(
+    keys: &[SortKey],
+    sort_fields: &[SortField],
+    not_null: &HashSet<ColumnSummary>,
+) -> usize {
+    keys.iter()
+        .zip(sort_fields.iter())
+        .take_while(|(key, field)| {
+            key.asc == field.asc
+                && matches!(
+                    field.expr.unpack_alias_ref(),
+                    ScalarExpression::ColumnRef(column)
+                        if column.summary() == &key.column
+                            && (key.nulls_first == field.nulls_first
+                                || not_null.contains(&key.column))
+                )
+        })
+        .count()
+}
+
+/// Whether the rows of the output of the subtree ordered by `keys` are sorted by all of the
+/// `sort_fields`, as they are by a prefix of them that is a unique key.
+pub(super) fn is_sorted_by(
+    graph: &HepGraph,
+    node_id: HepNodeId,
+    keys: &[SortKey],
+    sort_fields: &[SortField],
+) -> bool {
+    let prefix = sorted_prefix(keys, sort_fields, &not_null_columns(graph, node_id));
+    let unique_keys = keys[..prefix]
+        .iter()
+        .map(|key| key.column.clone())
+        .collect();
+
+    prefix == sort_fields.len()
+        || (prefix > 0 && is_unique(graph, node_id, &unique_keys, &HashSet::new()))
+}
@@ -1,10 +1,21 @@
 use crate::errors::DatabaseError;
+use crate::expression::function::scala::ScalarFunction;
+use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::function::vector_distance::VectorMetric;
 use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::pattern::PatternChildrenPredicate;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::properties::{
+    is_sorted_by, not_null_columns, ordering, scan_ordering, sorted_prefix,
+};
 use crate::planner::operator::join::JoinType;
+use crate::planner::operator::limit::LimitOperator;
 use crate::planner::operator::Operator;
+use crate::types::index::{IndexInfo, IndexType};
+use crate::types::value::DataValue;
+use crate::types::ColumnId;
 use itertools::Itertools;
 use std::sync::LazyLock;
 
@@ -32,6 +43,27 @@ static PUSH_LIMIT_INTO_TABLE_SCAN_RULE: LazyLock<Pattern> = LazyLock::new(|| Pat
     }]),
 });
 
+static PUSH_SORT_INTO_SCAN_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Sort(_)),
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: |op| matches!(op, Operator::TableScan(_) | Operator::Filter(_)),
+        children: PatternChildrenPredicate::None,
+    }]),
+});
+
+static ELIMINATE_SORT_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Sort(_)),
+    children: PatternChildrenPredicate::None,
+});
+
+static PUSH_NEAREST_NEIGHBOUR_INTO_SCAN_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Limit(_)),
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: |op| matches!(op, Operator::Sort(_)),
+        children: PatternChildrenPredicate::None,
+    }]),
+});
+
 pub struct LimitProjectTranspose;
 
 impl MatchPattern for LimitProjectTranspose {
@@ -110,9 +142,12 @@ impl NormalizationRule for PushLimitIntoScan {
                 let mut is_apply = false;
                 let limit = (limit_op.offset, limit_op.limit);
 
+                // the limit of a sampled scan is taken from the rows it keeps
                 if let Operator::TableScan(scan_op) = graph.operator_mut(child_index) {
-                    scan_op.limit = limit;
-                    is_apply = true;
+                    if scan_op.sample.is_none() {
+                        scan_op.limit = limit;
+                        is_apply = true;
+                    }
                 }
                 if is_apply {
                     graph.remove_node(node_id, false);
@@ -124,6 +159,187 @@ impl NormalizationRule for PushLimitIntoScan {
     }
 }
 
+/// Drops a `Sort` by the primary key right above its `Scan`, or above the `Filter` of it, as the scan
+/// reads the rows in the order of the primary key, from the last key down for `DESC`, so that
+/// `ORDER BY pk DESC LIMIT 10` only reads the last ten rows.
+///
+/// A `Sort` by a prefix of the primary key followed by other fields, e.g. `ORDER BY pk_1, c` of a
+/// primary key `(pk_1, pk_2)`, is kept to sort the rows within the runs of equal prefixes only.
+pub struct PushSortIntoScan;
+
+impl MatchPattern for PushSortIntoScan {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_SORT_INTO_SCAN_RULE
+    }
+}
+
+impl NormalizationRule for PushSortIntoScan {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Sort(sort_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        if sort_op.presorted > 0 {
+            return Ok(());
+        }
+        let Some(child_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let mut scan_id = child_id;
+        if matches!(graph.operator(scan_id), Operator::Filter(_)) {
+            let Some(child_id) = graph.eldest_child_at(scan_id) else {
+                return Ok(());
+            };
+            scan_id = child_id;
+        }
+        let Operator::TableScan(scan_op) = graph.operator(scan_id) else {
+            return Ok(());
+        };
+        let asc = sort_op.sort_fields[0].asc;
+        // the order of the scan read by its primary key, in the direction of the first field
+        let keys = scan_ordering(scan_op, !asc);
+        let presorted = sorted_prefix(
+            &keys,
+            &sort_op.sort_fields,
+            &not_null_columns(graph, child_id),
+        );
+        if presorted == 0 {
+            return Ok(());
+        }
+        let is_sorted = is_sorted_by(graph, child_id, &keys, &sort_op.sort_fields);
+        let limit = sort_op.limit;
+
+        if let Operator::TableScan(scan_op) = graph.operator_mut(scan_id) {
+            scan_op.reverse = !asc;
+            // only the primary key keeps the order, e.g. a range of it
+            scan_op
+                .index_infos
+                .retain(|index_info| matches!(index_info.meta.ty, IndexType::PrimaryKey { .. }));
+        }
+        if !is_sorted {
+            if let Operator::Sort(sort_op) = graph.operator_mut(node_id) {
+                sort_op.presorted = presorted;
+            }
+            return Ok(());
+        }
+        remove_sort(graph, node_id, limit);
+
+        Ok(())
+    }
+}
+
+/// Removes a `Sort` whose input is in its order, keeping its limit.
+fn remove_sort(graph: &mut HepGraph, node_id: HepNodeId, limit: Option<usize>) {
+    if limit.is_some() {
+        graph.replace_node(
+            node_id,
+            Operator::Limit(LimitOperator {
+                offset: None,
+                limit,
+            }),
+        );
+    } else {
+        graph.remove_node(node_id, false);
+    }
+}
+
+/// Drops a `Sort` whose input is already in its order, e.g. the rows of a subquery sorted the
+/// same way, or of a scan left to read its primary key by [`PushSortIntoScan`].
+pub struct EliminateSort;
+
+impl MatchPattern for EliminateSort {
+    fn pattern(&self) -> &Pattern {
+        &ELIMINATE_SORT_RULE
+    }
+}
+
+impl NormalizationRule for EliminateSort {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Sort(sort_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        let Some(child_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let keys = ordering(graph, child_id);
+
+        if !keys.is_empty() && is_sorted_by(graph, child_id, &keys, &sort_op.sort_fields) {
+            let limit = sort_op.limit;
+            remove_sort(graph, node_id, limit);
+        }
+
+        Ok(())
+    }
+}
+
+/// Probes the vector index of a column for `ORDER BY column <-> vector LIMIT k` right above
+/// its `Scan`, the `Sort` is kept to rank the rows of the probed lists exactly.
+pub struct PushNearestNeighbourIntoScan;
+
+impl PushNearestNeighbourIntoScan {
+    /// The column and the query vector of `column <-> vector`.
+    fn nearest_to(expr: &ScalarExpression) -> Option<(ColumnId, DataValue)> {
+        let ScalarExpression::ScalaFunction(ScalarFunction { args, inner }) =
+            expr.unpack_alias_ref()
+        else {
+            return None;
+        };
+        if inner.summary().name != VectorMetric::L2.name() {
+            return None;
+        }
+        match (args[0].unpack_alias_ref(), args[1].unpack_alias_ref()) {
+            (ScalarExpression::ColumnRef(column), ScalarExpression::Constant(query))
+            | (ScalarExpression::Constant(query), ScalarExpression::ColumnRef(column)) => {
+                if !matches!(query, DataValue::Vector(_)) {
+                    return None;
+                }
+                Some((column.id()?, query.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl MatchPattern for PushNearestNeighbourIntoScan {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_NEAREST_NEIGHBOUR_INTO_SCAN_RULE
+    }
+}
+
+impl NormalizationRule for PushNearestNeighbourIntoScan {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Some(sort_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let Operator::Sort(sort_op) = graph.operator(sort_id) else {
+            return Ok(());
+        };
+        let Some((column_id, query)) = sort_op
+            .sort_fields
+            .first()
+            .filter(|field| field.asc)
+            .and_then(|field| Self::nearest_to(&field.expr))
+        else {
+            return Ok(());
+        };
+        let Some(scan_id) = graph.eldest_child_at(sort_id) else {
+            return Ok(());
+        };
+        if let Operator::TableScan(scan_op) = graph.operator_mut(scan_id) {
+            for IndexInfo { meta, range, .. } in &mut scan_op.index_infos {
+                if range.is_none()
+                    && matches!(meta.ty, IndexType::Vector)
+                    && meta.column_ids[0] == column_id
+                {
+                    *range = Some(Range::Eq(query));
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::binder::test::build_t1_table;
@@ -218,4 +434,83 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_push_sort_into_scan() -> Result<(), DatabaseError> {
+        let table_state = build_t1_table()?;
+        let optimize = |sql: &str| {
+            HepOptimizer::new(table_state.plan(sql)?)
+                .batch(
+                    "test_push_sort_into_scan".to_string(),
+                    HepBatchStrategy::fix_point_topdown(10),
+                    vec![
+                        NormalizationRuleImpl::LimitProjectTranspose,
+                        NormalizationRuleImpl::PushSortIntoScan,
+                        NormalizationRuleImpl::PushLimitIntoTableScan,
+                    ],
+                )
+                .find_best::<RocksTransaction>(None)
+        };
+
+        let scan_op = optimize("select * from t1 order by c1 desc limit 2")?
+            .childrens
+            .pop_only();
+        if let Operator::TableScan(op) = &scan_op.operator {
+            assert!(op.reverse);
+            assert_eq!(op.limit, (None, Some(2)))
+        } else {
+            unreachable!("Should be a table scan operator")
+        }
+
+        // `c1` is the whole primary key, its rows are ordered by `c2` as well
+        let scan_op = optimize("select * from t1 order by c1 desc, c2")?
+            .childrens
+            .pop_only();
+        if let Operator::TableScan(op) = &scan_op.operator {
+            assert!(op.reverse);
+        } else {
+            unreachable!("Should be a table scan operator")
+        }
+
+        let sort_op = optimize("select * from t1 order by c2 desc limit 2")?
+            .childrens
+            .pop_only()
+            .childrens
+            .pop_only();
+        if let Operator::Sort(_) = &sort_op.operator {
+        } else {
+            unreachable!("Should be a sort operator")
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_eliminate_sort() -> Result<(), DatabaseError> {
+        let table_state = build_t1_table()?;
+        let has_sort = |sql: &str| -> Result<bool, DatabaseError> {
+            let plan = HepOptimizer::new(table_state.plan(sql)?)
+                .batch(
+                    "test_eliminate_sort".to_string(),
+                    HepBatchStrategy::once_topdown(),
+                    vec![NormalizationRuleImpl::EliminateSort],
+                )
+                .find_best::<RocksTransaction>(None)?;
+
+            Ok(matches!(
+                plan.childrens.pop_only().operator,
+                Operator::Sort(_)
+            ))
+        };
+
+        // the scan of `t2` has no other index than its primary key `c3`
+        assert!(!has_sort("select * from t2 order by c3")?);
+        assert!(!has_sort(
+            "select * from t2 where c4 > 1 order by c3, c4 desc"
+        )?);
+        assert!(has_sort("select * from t2 order by c3 desc")?);
+        assert!(has_sort("select * from t2 order by c4, c3")?);
+
+        Ok(())
+    }
 }
@@ -2,6 +2,10 @@ use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::range_detacher::{Range, RangeDetacher};
 use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::fdw::ForeignFilter;
+use crate::function::match_against::MatchAgainst;
+use crate::function::similarity::Similarity;
+use crate::function::spatial::SpatialKind;
 use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::pattern::PatternChildrenPredicate;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
@@ -12,6 +16,8 @@ use crate::planner::operator::Operator;
 use crate::types::index::{IndexInfo, IndexMetaRef, IndexType};
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
+use crate::utils::spatial::Extent;
+use crate::utils::{full_text, spatial, trigram};
 use itertools::Itertools;
 use std::mem;
 use std::ops::Bound;
@@ -33,6 +39,14 @@ static PUSH_PREDICATE_INTO_SCAN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
     }]),
 });
 
+static PUSH_PREDICATE_INTO_FOREIGN_SCAN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Filter(_)),
+    children: PatternChildrenPredicate::Predicate(vec![Pattern {
+        predicate: |op| matches!(op, Operator::ForeignScan(_)),
+        children: PatternChildrenPredicate::None,
+    }]),
+});
+
 // TODO: 感觉是只是处理projection中的alias反向替换为filter中表达式
 #[allow(dead_code)]
 static PUSH_PREDICATE_THROUGH_NON_JOIN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
@@ -43,7 +57,7 @@ static PUSH_PREDICATE_THROUGH_NON_JOIN: LazyLock<Pattern> = LazyLock::new(|| Pat
     }]),
 });
 
-fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
+pub(super) fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
     match expr {
         ScalarExpression::Binary {
             op: BinaryOperator::And,
@@ -60,7 +74,10 @@ fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression
 
 /// reduce filters into a filter, and then build a new LogicalFilter node with input child.
 /// if filters is empty, return the input child.
-fn reduce_filters(filters: Vec<ScalarExpression>, having: bool) -> Option<FilterOperator> {
+pub(super) fn reduce_filters(
+    filters: Vec<ScalarExpression>,
+    having: bool,
+) -> Option<FilterOperator> {
     filters
         .into_iter()
         .reduce(|a, b| ScalarExpression::Binary {
@@ -127,13 +144,18 @@ impl NormalizationRule for PushPredicateThroughJoin {
 
             if let Operator::Filter(op) = graph.operator(node_id) {
                 let filter_exprs = split_conjunctive_predicates(&op.predicate);
+                // a volatile filter, e.g. `random() < 0.5`, is evaluated once per joined row
+                let (volatile_filters, filter_exprs): (Vec<_>, Vec<_>) = filter_exprs
+                    .into_iter()
+                    .partition(ScalarExpression::has_volatile_function);
 
                 let (left_filters, rest): (Vec<_>, Vec<_>) = filter_exprs
                     .into_iter()
                     .partition(|f| is_subset_cols(&f.referenced_columns(true), &left_columns));
-                let (right_filters, common_filters): (Vec<_>, Vec<_>) = rest
+                let (right_filters, mut common_filters): (Vec<_>, Vec<_>) = rest
                     .into_iter()
                     .partition(|f| is_subset_cols(&f.referenced_columns(true), &right_columns));
+                common_filters.extend(volatile_filters);
 
                 let replace_filters = match child_op.join_type {
                     JoinType::Inner => {
@@ -217,7 +239,12 @@ impl NormalizationRule for PushPredicateIntoScan {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 if let Operator::TableScan(child_op) = graph.operator_mut(child_id) {
                     //FIXME: now only support `unique` and `primary key`
-                    for IndexInfo { meta, range } in &mut child_op.index_infos {
+                    for IndexInfo {
+                        meta,
+                        range,
+                        skip_range,
+                    } in &mut child_op.index_infos
+                    {
                         if range.is_some() {
                             continue;
                         }
@@ -231,7 +258,50 @@ impl NormalizationRule for PushPredicateIntoScan {
                             IndexType::PrimaryKey { is_multiple: true } | IndexType::Composite => {
                                 Self::composite_range(&op, meta)?
                             }
+                            IndexType::FullText => Self::full_text_range(&op, meta),
+                            IndexType::Trigram => Self::trigram_range(&op, meta),
+                            // probed by `PushNearestNeighbourIntoScan` only
+                            IndexType::Vector => None,
+                            IndexType::ZOrder => Self::z_order_range(&op, meta),
                         };
+                        if range.is_none()
+                            && matches!(meta.ty, IndexType::Composite)
+                            && meta.column_ids.len() > 1
+                        {
+                            // read by a skip scan over the values of the leading column, if they are few
+                            *skip_range =
+                                RangeDetacher::new(meta.table_name.as_str(), &meta.column_ids[1])
+                                    .detach(&op.predicate)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Hands the `column <op> constant` conjuncts of the filter to the foreign scan,
+/// the filter is kept to recheck the rows returned by the remote source.
+pub struct PushPredicateIntoForeignScan;
+
+impl MatchPattern for PushPredicateIntoForeignScan {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_PREDICATE_INTO_FOREIGN_SCAN
+    }
+}
+
+impl NormalizationRule for PushPredicateIntoForeignScan {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        if let Operator::Filter(op) = graph.operator(node_id).clone() {
+            if let Some(child_id) = graph.eldest_child_at(node_id) {
+                if let Operator::ForeignScan(child_op) = graph.operator_mut(child_id) {
+                    if child_op.filters.is_empty() {
+                        child_op.filters = split_conjunctive_predicates(&op.predicate)
+                            .iter()
+                            .filter_map(ForeignFilter::from_expr)
+                            .collect_vec();
                     }
                 }
             }
@@ -242,6 +312,188 @@ impl NormalizationRule for PushPredicateIntoScan {
 }
 
 impl PushPredicateIntoScan {
+    /// The tokens of the query of a conjunct `MATCH (column) AGAINST ('query') > 0`,
+    /// a relevant row contains at least one of them, the filter is kept to check the score.
+    fn full_text_range(op: &FilterOperator, meta: &IndexMetaRef) -> Option<Range> {
+        split_conjunctive_predicates(&op.predicate)
+            .iter()
+            .find_map(|expr| {
+                let args = Self::positive_score_args(expr, MatchAgainst::NAME)?;
+
+                match (&args[0], &args[1]) {
+                    (column, ScalarExpression::Constant(query))
+                        if Self::is_index_column(column, meta) =>
+                    {
+                        Some(full_text::index_tokens(query))
+                    }
+                    _ => None,
+                }
+            })
+            .and_then(Self::eq_ranges)
+    }
+
+    /// A row matched by `column LIKE 'pattern'` contains every trigram inside the words of
+    /// the pattern, so one of them is probed, and a row similar by `similarity(column, 'text')`
+    /// shares at least one trigram with the text.
+    fn trigram_range(op: &FilterOperator, meta: &IndexMetaRef) -> Option<Range> {
+        split_conjunctive_predicates(&op.predicate)
+            .iter()
+            .find_map(|expr| {
+                if let ScalarExpression::Binary {
+                    op: BinaryOperator::Like(escape_char),
+                    left_expr,
+                    right_expr,
+                    ..
+                } = expr
+                {
+                    let ScalarExpression::Constant(DataValue::Utf8 { value: pattern, .. }) =
+                        right_expr.as_ref()
+                    else {
+                        return None;
+                    };
+                    return Self::is_index_column(left_expr, meta).then(|| {
+                        trigram::like_trigrams(pattern, *escape_char)
+                            .into_iter()
+                            .take(1)
+                            .map(trigram::trigram_value)
+                            .collect_vec()
+                    });
+                }
+                let args = Self::positive_score_args(expr, Similarity::NAME)?;
+
+                match (&args[0], &args[1]) {
+                    (column, ScalarExpression::Constant(text))
+                    | (ScalarExpression::Constant(text), column)
+                        if Self::is_index_column(column, meta) =>
+                    {
+                        Some(trigram::index_trigrams(text))
+                    }
+                    _ => None,
+                }
+            })
+            .and_then(Self::eq_ranges)
+    }
+
+    /// The Z-order codes of the points in the extent of a conjunct `st_contains(extent, column)`,
+    /// `st_within(column, extent)` or `st_intersects` of them, or in the box around the point
+    /// of `st_distance(column, point) <= d`, the filter is kept to recheck the points found.
+    fn z_order_range(op: &FilterOperator, meta: &IndexMetaRef) -> Option<Range> {
+        let mut ranges = split_conjunctive_predicates(&op.predicate)
+            .iter()
+            .find_map(|expr| Self::z_order_extent(expr, meta))
+            .map(|extent| spatial::z_order_ranges(&extent))?
+            .into_iter()
+            .map(|(start, end)| Range::Scope {
+                min: Bound::Included(DataValue::UInt64(start)),
+                max: Bound::Included(DataValue::UInt64(end)),
+            })
+            .collect_vec();
+
+        match ranges.len() {
+            0 => None,
+            1 => ranges.pop(),
+            _ => Some(Range::SortedRanges(ranges)),
+        }
+    }
+
+    fn z_order_extent(expr: &ScalarExpression, meta: &IndexMetaRef) -> Option<Extent> {
+        match expr {
+            ScalarExpression::ScalaFunction(function) => {
+                let kind = SpatialKind::from_name(&function.summary().name)?;
+
+                match (kind, &function.args[0], &function.args[1]) {
+                    (
+                        SpatialKind::Contains | SpatialKind::Intersects,
+                        ScalarExpression::Constant(value),
+                        column,
+                    )
+                    | (
+                        SpatialKind::Within | SpatialKind::Intersects,
+                        column,
+                        ScalarExpression::Constant(value),
+                    ) if Self::is_index_column(column, meta) => spatial::extent(value),
+                    _ => None,
+                }
+            }
+            ScalarExpression::Binary {
+                op: BinaryOperator::Lt | BinaryOperator::LtEq,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                let (ScalarExpression::ScalaFunction(function), ScalarExpression::Constant(value)) =
+                    (left_expr.as_ref(), right_expr.as_ref())
+                else {
+                    return None;
+                };
+                if function.summary().name != SpatialKind::Distance.name() {
+                    return None;
+                }
+                let radius = value.clone().cast(&LogicalType::Double).ok()?.double()?;
+                let [x_min, y_min, x_max, y_max] = match (&function.args[0], &function.args[1]) {
+                    (column, ScalarExpression::Constant(value))
+                    | (ScalarExpression::Constant(value), column)
+                        if Self::is_index_column(column, meta) =>
+                    {
+                        spatial::extent(value)?
+                    }
+                    _ => return None,
+                };
+                (radius >= 0.0).then_some([
+                    x_min - radius,
+                    y_min - radius,
+                    x_max + radius,
+                    y_max + radius,
+                ])
+            }
+            _ => None,
+        }
+    }
+
+    /// The arguments of the function named `name` in `name(..) > c` for `c >= 0`
+    /// or `name(..) >= c` for `c > 0`, which hold only for a positive score.
+    fn positive_score_args<'a>(
+        expr: &'a ScalarExpression,
+        name: &str,
+    ) -> Option<&'a [ScalarExpression]> {
+        let ScalarExpression::Binary {
+            op,
+            left_expr,
+            right_expr,
+            ..
+        } = expr
+        else {
+            return None;
+        };
+        let (ScalarExpression::ScalaFunction(function), ScalarExpression::Constant(value)) =
+            (left_expr.as_ref(), right_expr.as_ref())
+        else {
+            return None;
+        };
+        let bound = value.clone().cast(&LogicalType::Double).ok()?.double()?;
+        let is_positive = match op {
+            BinaryOperator::Gt => bound >= 0.0,
+            BinaryOperator::GtEq => bound > 0.0,
+            _ => false,
+        };
+        (is_positive && function.summary().name == name).then_some(function.args.as_slice())
+    }
+
+    fn is_index_column(expr: &ScalarExpression, meta: &IndexMetaRef) -> bool {
+        matches!(expr, ScalarExpression::ColumnRef(column) if column.id() == Some(meta.column_ids[0])
+            && column.table_name().map(|name| name.as_str()) == Some(meta.table_name.as_str()))
+    }
+
+    fn eq_ranges(mut values: Vec<DataValue>) -> Option<Range> {
+        match values.len() {
+            0 => None,
+            1 => values.pop().map(Range::Eq),
+            _ => Some(Range::SortedRanges(
+                values.into_iter().map(Range::Eq).collect_vec(),
+            )),
+        }
+    }
+
     fn composite_range(
         op: &FilterOperator,
         meta: &mut IndexMetaRef,
@@ -0,0 +1,238 @@
+use crate::catalog::{ColumnRelation, ColumnSummary};
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::shared_scan::{SharedScanOperator, SharedTuples};
+use crate::planner::operator::Operator;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+static SHARE_REPEATED_SUBQUERY_RULE: LazyLock<Pattern> = LazyLock::new(|| Pattern {
+    predicate: |op| matches!(op, Operator::Join(_)),
+    children: PatternChildrenPredicate::None,
+});
+
+fn operator_exprs(operator: &Operator) -> Vec<&ScalarExpression> {
+    match operator {
+        Operator::Aggregate(op) => op.agg_calls.iter().chain(op.groupby_exprs.iter()).collect(),
+        Operator::Filter(op) => vec![&op.predicate],
+        Operator::Project(op) => op.exprs.iter().collect(),
+        Operator::Sort(op) => op.sort_fields.iter().map(|field| &field.expr).collect(),
+        Operator::Join(op) => match &op.on {
+            JoinCondition::On { on, filter } => on
+                .iter()
+                .flat_map(|(left_expr, right_expr)| [left_expr, right_expr])
+                .chain(filter)
+                .collect(),
+            JoinCondition::None => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+/// Whether the subtree gives the same rows wherever it is read in the statement: it reads no
+/// column of the tables outside of it, as a correlated subquery does, and calls no volatile
+/// function.
+fn is_shareable(graph: &HepGraph, node_id: HepNodeId) -> Result<bool, DatabaseError> {
+    let mut scanned = HashSet::new();
+    let mut referenced: Vec<ColumnSummary> = Vec::new();
+
+    for id in graph.nodes_iter(Some(node_id)) {
+        let operator = graph.operator(id);
+
+        match operator {
+            Operator::TableScan(op) => {
+                scanned.extend(op.columns.values().map(|column| column.summary().clone()))
+            }
+            Operator::ForeignScan(op) => {
+                scanned.extend(op.columns.values().map(|column| column.summary().clone()))
+            }
+            Operator::Values(op) => {
+                scanned.extend(op.schema_ref.iter().map(|column| column.summary().clone()))
+            }
+            Operator::Dummy
+            | Operator::Aggregate(_)
+            | Operator::Filter(_)
+            | Operator::Join(_)
+            | Operator::Project(_)
+            | Operator::Sort(_)
+            | Operator::Limit(_)
+            | Operator::Union(_) => (),
+            _ => return Ok(false),
+        }
+        for expr in operator_exprs(operator) {
+            if expr.has_volatile_function() {
+                return Ok(false);
+            }
+            referenced.extend(
+                expr.referenced_columns(true)
+                    .into_iter()
+                    .map(|column| column.summary().clone()),
+            );
+        }
+    }
+
+    Ok(referenced.iter().all(|summary| {
+        matches!(summary.relation, ColumnRelation::None) || scanned.contains(summary)
+    }))
+}
+
+fn is_same_subtree(graph: &HepGraph, left_id: HepNodeId, right_id: HepNodeId) -> bool {
+    if graph.operator(left_id) != graph.operator(right_id) {
+        return false;
+    }
+    let left_childrens = graph.children_at(left_id).collect_vec();
+    let right_childrens = graph.children_at(right_id).collect_vec();
+
+    left_childrens.len() == right_childrens.len()
+        && left_childrens
+            .into_iter()
+            .zip(right_childrens)
+            .all(|(left_id, right_id)| is_same_subtree(graph, left_id, right_id))
+}
+
+/// The subqueries joined by a join: its right child, or the child of the projection of a
+/// subquery of `IN`, which names the column apart from the other subqueries.
+fn subquery_candidates(graph: &HepGraph, join_id: HepNodeId) -> Vec<HepNodeId> {
+    let Some(right_id) = graph.youngest_child_at(join_id) else {
+        return vec![];
+    };
+    let mut candidates = vec![right_id];
+
+    if let Operator::Project(_) = graph.operator(right_id) {
+        candidates.extend(graph.eldest_child_at(right_id));
+    }
+    candidates
+        .into_iter()
+        .filter(|id| {
+            !matches!(graph.operator(*id), Operator::SharedScan(_))
+                && !graph.parent_id(*id).is_some_and(|parent_id| {
+                    matches!(graph.operator(parent_id), Operator::SharedScan(_))
+                })
+        })
+        .collect_vec()
+}
+
+/// Reads the subqueries repeated in a statement once, e.g. the two of
+/// `a > (SELECT avg(a) FROM t) AND b > (SELECT avg(a) FROM t)`, by putting each of them under a
+/// shared scan with the same rows.
+pub struct ShareRepeatedSubquery;
+
+impl MatchPattern for ShareRepeatedSubquery {
+    fn pattern(&self) -> &Pattern {
+        &SHARE_REPEATED_SUBQUERY_RULE
+    }
+}
+
+impl NormalizationRule for ShareRepeatedSubquery {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let joins = graph
+            .nodes_iter(None)
+            .filter(|id| *id != node_id && matches!(graph.operator(*id), Operator::Join(_)))
+            .collect_vec();
+
+        for candidate_id in subquery_candidates(graph, node_id) {
+            let repeats = joins
+                .iter()
+                .flat_map(|join_id| subquery_candidates(graph, *join_id))
+                .filter(|id| is_same_subtree(graph, candidate_id, *id))
+                .collect_vec();
+            if repeats.is_empty() || !is_shareable(graph, candidate_id)? {
+                continue;
+            }
+            let id = graph
+                .nodes_iter(None)
+                .filter_map(|id| match graph.operator(id) {
+                    Operator::SharedScan(op) => Some(op.id + 1),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or(0);
+            let tuples = SharedTuples::default();
+
+            for child_id in [candidate_id].into_iter().chain(repeats) {
+                let Some(parent_id) = graph.parent_id(child_id) else {
+                    continue;
+                };
+                graph.add_node(
+                    parent_id,
+                    Some(child_id),
+                    Operator::SharedScan(SharedScanOperator {
+                        id,
+                        tuples: tuples.clone(),
+                    }),
+                );
+            }
+            break;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::build_t1_table;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn shared_scans(plan: &LogicalPlan) -> Vec<usize> {
+        let mut ids = match &plan.operator {
+            Operator::SharedScan(op) => vec![op.id],
+            _ => vec![],
+        };
+        for child in plan.childrens.iter() {
+            ids.append(&mut shared_scans(child));
+        }
+        ids
+    }
+
+    fn optimize(sql: &str) -> Result<LogicalPlan, DatabaseError> {
+        let table_state = build_t1_table()?;
+        let plan = table_state.plan(sql)?;
+
+        HepOptimizer::new(plan)
+            .batch(
+                "test_share_repeated_subquery".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::ShareRepeatedSubquery],
+            )
+            .find_best::<RocksTransaction>(None)
+    }
+
+    #[test]
+    fn test_share_repeated_subquery() -> Result<(), DatabaseError> {
+        let plan = optimize(
+            "select * from t1 where c1 > (select max(c3) from t2) and c2 < (select max(c3) from t2)",
+        )?;
+        assert_eq!(shared_scans(&plan), vec![0, 0]);
+
+        let plan = optimize(
+            "select * from t1 where c1 in (select c3 from t2) and c2 in (select c3 from t2) and c1 > (select min(c4) from t2) and c2 > (select min(c4) from t2)",
+        )?;
+        let mut ids = shared_scans(&plan);
+        ids.sort();
+        assert_eq!(ids, vec![0, 0, 1, 1]);
+
+        let plan = optimize(
+            "select * from t1 where c1 > (select max(c3) from t2) and c2 < (select min(c3) from t2)",
+        )?;
+        assert!(shared_scans(&plan).is_empty());
+
+        let plan = optimize(
+            "select * from t1 where c1 > (select max(c3) from t2 where c3 > random(0, 10)) and c2 < (select max(c3) from t2 where c3 > random(0, 10))",
+        )?;
+        assert!(shared_scans(&plan).is_empty());
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use crate::expression::visitor_mut::VisitorMut;
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::properties::not_null_columns;
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::Operator;
 use itertools::Itertools;
@@ -97,12 +98,16 @@ impl MatchPattern for SimplifyFilter {
 impl NormalizationRule for SimplifyFilter {
     fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
         let mut is_optimized = false;
+        if matches!(graph.operator(node_id), Operator::Filter(op) if op.is_optimized) {
+            return Ok(());
+        }
+        let not_null_columns = graph
+            .eldest_child_at(node_id)
+            .map(|child_id| not_null_columns(graph, child_id))
+            .unwrap_or_default();
         if let Operator::Filter(filter_op) = graph.operator_mut(node_id) {
-            if filter_op.is_optimized {
-                return Ok(());
-            }
             ConstantCalculator.visit(&mut filter_op.predicate)?;
-            Simplify::default().visit(&mut filter_op.predicate)?;
+            Simplify::with_not_null_columns(not_null_columns).visit(&mut filter_op.predicate)?;
             filter_op.is_optimized = true;
             is_optimized = true;
         }
@@ -179,6 +184,38 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_constant_calculation_volatility() -> Result<(), DatabaseError> {
+        let table_state = build_t1_table()?;
+        let plan = table_state.plan("select lower('A'), current_date(), random() from t1")?;
+
+        let best_plan = HepOptimizer::new(plan)
+            .batch(
+                "test_simplification".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::ConstantCalculation],
+            )
+            .find_best::<RocksTransaction>(None)?;
+        let Operator::Project(project_op) = best_plan.operator else {
+            unreachable!();
+        };
+        // only the immutable function is folded
+        assert!(matches!(
+            &project_op.exprs[0],
+            ScalarExpression::Constant(DataValue::Utf8 { value, .. }) if value == "a"
+        ));
+        assert!(matches!(
+            project_op.exprs[1],
+            ScalarExpression::ScalaFunction(_)
+        ));
+        assert!(matches!(
+            project_op.exprs[2],
+            ScalarExpression::ScalaFunction(_)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_simplify_filter_single_column() -> Result<(), DatabaseError> {
         let table_state = build_t1_table()?;
@@ -262,7 +299,7 @@ mod test {
         if let Operator::Filter(filter_op) = filter_op.operator {
             let c1_col = ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c1".to_string(),
+                    name: "c1".into(),
                     relation: ColumnRelation::Table {
                         column_id: *table_state.column_id_by_name("c1"),
                         table_name: Arc::new("t1".to_string()),
@@ -275,7 +312,7 @@ mod test {
             );
             let c2_col = ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c2".to_string(),
+                    name: "c2".into(),
                     relation: ColumnRelation::Table {
                         column_id: *table_state.column_id_by_name("c2"),
                         table_name: Arc::new("t1".to_string()),
@@ -457,9 +494,10 @@ mod test {
         let table_state = build_t1_table()?;
         let plan_1 = table_state.plan("select * from t1 where c1 is null")?;
 
+        // `c1` is the primary key, which is never NULL
         assert_eq!(
             plan_filter(&plan_1, table_state.column_id_by_name("c1"))?,
-            Some(Range::Eq(DataValue::Null))
+            Some(Range::Dummy)
         );
 
         Ok(())
@@ -478,6 +516,47 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_simplify_filter_not_null_columns() -> Result<(), DatabaseError> {
+        let table_state = build_t1_table()?;
+        let c1_id = table_state.column_id_by_name("c1");
+
+        let plan_1 = table_state.plan("select * from t1 where coalesce(c1, 0) > 1")?;
+        assert_eq!(
+            plan_filter(&plan_1, c1_id)?,
+            Some(Range::Scope {
+                min: Bound::Excluded(DataValue::Int32(1)),
+                max: Bound::Unbounded,
+            })
+        );
+        let plan_2 = table_state.plan("select * from t1 where c2 is null or c1 = 1")?;
+        assert_eq!(
+            plan_filter(&plan_2, c1_id)?,
+            Some(Range::Eq(DataValue::Int32(1)))
+        );
+
+        // `c4` is padded with NULL by the left join
+        let plan_3 =
+            table_state.plan("select * from t1 left join t2 on c1 = c3 where c4 is null")?;
+        let best_plan = HepOptimizer::new(plan_3)
+            .batch(
+                "test_simplify_filter".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::SimplifyFilter],
+            )
+            .find_best::<RocksTransaction>(None)?;
+        let filter_op = best_plan.childrens.pop_only();
+        let Operator::Filter(filter_op) = filter_op.operator else {
+            unreachable!()
+        };
+        assert!(matches!(
+            filter_op.predicate,
+            ScalarExpression::IsNull { .. }
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_simplify_filter_column_in() -> Result<(), DatabaseError> {
         let table_state = build_t1_table()?;
@@ -1,7 +1,95 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::catalog::DELETED_AT_COLUMN;
+use crate::errors::DatabaseError;
+use sqlparser::ast::{AddDropSync, DataType, Ident, ObjectName, ObjectType};
+use sqlparser::dialect::{Dialect, GenericDialect, MySqlDialect};
+use sqlparser::keywords::{
+    Keyword, ALL_KEYWORDS, ALL_KEYWORDS_INDEX, RESERVED_FOR_COLUMN_ALIAS, RESERVED_FOR_TABLE_ALIAS,
+};
 use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Location, Token, TokenWithLocation, Tokenizer, Whitespace, Word};
 use sqlparser::{ast::Statement, dialect::PostgreSqlDialect, parser::Parser};
 
-const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
+/// The dialect the statements are parsed in, set by `DataBaseBuilder::dialect` or
+/// `SET dialect = 'mysql'`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// `SELECT TOP n` takes the first rows as `LIMIT n`
+    Generic,
+    #[default]
+    PostgreSql,
+    /// Identifiers are quoted by backticks, `||` is `OR` and `LIMIT offset, count` takes the
+    /// rows as `LIMIT count OFFSET offset`
+    MySql,
+}
+
+/// How the identifiers of the statements name the tables, columns and the other objects,
+/// set by `DataBaseBuilder::identifier_case`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdentifierCase {
+    /// `T1` and `t1` name `t1`, the quoted `"T1"` names `T1`
+    #[default]
+    Fold,
+    /// Quoted identifiers are folded too, `"T1"` names `t1` as the databases created before
+    /// the quoted identifiers kept their case
+    Lower,
+}
+
+/// The settings of [`parse_sql_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParserOptions {
+    pub dialect: SqlDialect,
+    pub identifier_case: IdentifierCase,
+}
+
+impl SqlDialect {
+    fn dialect(&self) -> &'static dyn Dialect {
+        match self {
+            SqlDialect::Generic => &GenericDialect {},
+            SqlDialect::PostgreSql => &PostgreSqlDialect {},
+            SqlDialect::MySql => &MySqlDialect {},
+        }
+    }
+
+    /// The quote of the identifiers of the SQL generated to be parsed in the dialect.
+    pub(crate) fn quote_style(&self) -> char {
+        match self {
+            SqlDialect::MySql => '`',
+            SqlDialect::Generic | SqlDialect::PostgreSql => '"',
+        }
+    }
+}
+
+impl FromStr for SqlDialect {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "generic" | "ansi" => Ok(SqlDialect::Generic),
+            "postgres" | "postgresql" => Ok(SqlDialect::PostgreSql),
+            "mysql" => Ok(SqlDialect::MySql),
+            _ => Err(DatabaseError::InvalidValue(format!(
+                "unknown dialect `{}`, expected `generic`, `postgresql` or `mysql`",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SqlDialect::Generic => write!(f, "generic"),
+            SqlDialect::PostgreSql => write!(f, "postgresql"),
+            SqlDialect::MySql => write!(f, "mysql"),
+        }
+    }
+}
 
 /// Parse a string to a collection of statements.
 ///
@@ -16,5 +104,906 @@ const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 /// println!("{:?}", ast);
 /// ```
 pub fn parse_sql<S: AsRef<str>>(sql: S) -> Result<Vec<Statement>, ParserError> {
-    Parser::parse_sql(&DIALECT, sql.as_ref())
+    parse_sql_with_options(sql, ParserOptions::default())
+}
+
+/// Parse a string to a collection of statements of the dialect of the options.
+pub fn parse_sql_with_options<S: AsRef<str>>(
+    sql: S,
+    options: ParserOptions,
+) -> Result<Vec<Statement>, ParserError> {
+    let ParserOptions {
+        dialect,
+        identifier_case,
+    } = options;
+    // tokenized with locations, which `CREATE TASK` keeps the statement text by
+    let mut tokens = Tokenizer::new(dialect.dialect(), sql.as_ref()).tokenize_with_location()?;
+    if identifier_case == IdentifierCase::Lower {
+        lower_quoted_identifiers(&mut tokens);
+    }
+    match dialect {
+        SqlDialect::Generic => (),
+        SqlDialect::PostgreSql => check_no_top(&tokens)?,
+        SqlDialect::MySql => {
+            check_no_top(&tokens)?;
+            rewrite_mysql_operators(&mut tokens);
+        }
+    }
+    rewrite_copy_options(&mut tokens);
+    rewrite_match_against(&mut tokens);
+    rewrite_pivot(&mut tokens);
+    rewrite_table_sample(&mut tokens);
+    rewrite_with_deleted(&mut tokens);
+    rewrite_cte_options(&mut tokens);
+    rewrite_vector_operators(&mut tokens);
+
+    let mut parser = Parser::new(dialect.dialect()).with_tokens_with_locations(tokens);
+    let mut stmts = Vec::new();
+    let mut expecting_statement_delimiter = false;
+
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_statement_delimiter = false;
+        }
+        if parser.peek_token() == Token::EOF {
+            break;
+        }
+        if expecting_statement_delimiter {
+            return parser.expected("end of statement", parser.peek_token());
+        }
+        let stmt = if is_keyword(&parser, "REINDEX") {
+            parse_reindex(&mut parser)?
+        } else if is_keyword(&parser, "CHECK") {
+            parse_check_table(&mut parser)?
+        } else if is_keyword(&parser, "VACUUM") {
+            parse_vacuum(&mut parser)?
+        } else if is_keyword(&parser, "PURGE") {
+            parse_purge(&mut parser)?
+        } else if is_keyword(&parser, "ATTACH") {
+            parse_attach_database(&mut parser)?
+        } else if is_keyword(&parser, "DETACH") {
+            parse_detach_database(&mut parser)?
+        } else if is_keyword(&parser, "CREATE") && is_nth_keyword(&parser, 1, "TASK") {
+            parse_create_task(&mut parser, sql.as_ref())?
+        } else if is_keyword(&parser, "DROP") && is_nth_keyword(&parser, 1, "TASK") {
+            parse_drop_task(&mut parser)?
+        } else if is_keyword(&parser, "CREATE") && is_nth_keyword(&parser, 1, "FULLTEXT") {
+            parse_create_full_text_index(&mut parser)?
+        } else {
+            parser.parse_statement()?
+        };
+        stmts.push(stmt);
+        expecting_statement_delimiter = true;
+    }
+    Ok(stmts)
+}
+
+/// The name as an identifier of the SQL generated from it, e.g. by `EXPLAIN`, read back as the
+/// same name: quoted when it is a reserved keyword or is not lower-case letters, digits and `_`.
+pub fn quote_identifier(name: &str) -> Cow<'_, str> {
+    let is_plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    let is_reserved = || {
+        ALL_KEYWORDS
+            .binary_search(&name.to_uppercase().as_str())
+            .is_ok_and(|i| {
+                let keyword = ALL_KEYWORDS_INDEX[i];
+                RESERVED_FOR_COLUMN_ALIAS.contains(&keyword)
+                    || RESERVED_FOR_TABLE_ALIAS.contains(&keyword)
+            })
+    };
+
+    if is_plain && !is_reserved() {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("\"{}\"", name.replace('"', "\"\"")))
+    }
+}
+
+fn is_keyword(parser: &Parser, keyword: &str) -> bool {
+    is_nth_keyword(parser, 0, keyword)
+}
+
+fn is_nth_keyword(parser: &Parser, n: usize, keyword: &str) -> bool {
+    matches!(parser.peek_nth_token(n).token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword))
+}
+
+/// Byte offset of a token location, which counts lines and characters from 1.
+fn location_offset(sql: &str, location: &Location) -> usize {
+    let mut offset = 0;
+
+    for (i, line) in sql.split('\n').enumerate() {
+        if i + 1 == location.line as usize {
+            return offset
+                + line
+                    .char_indices()
+                    .nth(location.column as usize - 1)
+                    .map_or(line.len(), |(pos, _)| pos);
+        }
+        offset += line.len() + 1;
+    }
+    sql.len()
+}
+
+/// The options `PARTITION BY (columns)` and `COMPRESSION codec` of `COPY ... TO 'path' (...)`,
+/// which the sqlparser has no options for, are carried as the options `FORCE_NULL (columns)` and
+/// `FORCE_NOT_NULL (codec)` that only `COPY ... FROM` takes.
+fn rewrite_copy_options(tokens: &mut Vec<TokenWithLocation>) {
+    let is_word = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+    let positions = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let mut options = Vec::new();
+    let mut n = 0;
+
+    // the opening parenthesis of the options of each `COPY ... TO 'path'`
+    while n < positions.len() {
+        if !is_word(&tokens[positions[n]].token, "COPY") {
+            n += 1;
+            continue;
+        }
+        let mut depth = 0;
+        n += 1;
+        while n < positions.len() {
+            match &tokens[positions[n]].token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                Token::SemiColon if depth == 0 => break,
+                token if depth == 0 && is_word(token, "TO") => {
+                    let mut m = n + 2;
+                    if positions
+                        .get(m)
+                        .is_some_and(|i| is_word(&tokens[*i].token, "WITH"))
+                    {
+                        m += 1;
+                    }
+                    if positions
+                        .get(n + 1)
+                        .is_some_and(|i| matches!(tokens[*i].token, Token::SingleQuotedString(_)))
+                        && positions
+                            .get(m)
+                            .is_some_and(|i| tokens[*i].token == Token::LParen)
+                    {
+                        options.push(m);
+                    }
+                }
+                _ => (),
+            }
+            n += 1;
+        }
+    }
+
+    // from the end, so the tokens inserted do not move the positions left to visit
+    for open in options.into_iter().rev() {
+        let Some(close) = matching_paren(tokens, &positions, open) else {
+            continue;
+        };
+        let mut depth = 0;
+
+        for n in (open + 1..close).rev() {
+            match tokens[positions[n]].token {
+                Token::LParen => depth -= 1,
+                Token::RParen => depth += 1,
+                _ => (),
+            }
+            if depth != 0 {
+                continue;
+            }
+            let token = &tokens[positions[n]].token;
+
+            if is_word(token, "PARTITION") && is_word(&tokens[positions[n + 1]].token, "BY") {
+                tokens[positions[n]].token = Token::make_word("FORCE_NULL", None);
+                tokens[positions[n + 1]].token = Token::Whitespace(Whitespace::Space);
+            } else if is_word(token, "COMPRESSION") && n + 1 < close {
+                let codec = positions[n + 1];
+                let (Token::Word(Word { value, .. }) | Token::SingleQuotedString(value)) =
+                    &tokens[codec].token
+                else {
+                    continue;
+                };
+                let codec_token = Token::make_word(value, Some('"'));
+                let with_location = |token: Token| {
+                    let mut with_location = tokens[codec].clone();
+                    with_location.token = token;
+                    with_location
+                };
+                let (lparen, rparen) = (with_location(Token::LParen), with_location(Token::RParen));
+                tokens[codec].token = codec_token;
+                tokens.insert(codec + 1, rparen);
+                tokens.insert(codec, lparen);
+                tokens[positions[n]].token = Token::make_word("FORCE_NOT_NULL", None);
+            }
+        }
+    }
+}
+
+/// `MATCH (column) AGAINST ('query')`, which the sqlparser only reads for the MySQL dialect,
+/// is rewritten to the function call `match_against(column, 'query')`.
+fn rewrite_match_against(tokens: &mut [TokenWithLocation]) {
+    let is_word = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+    let positions = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    for (n, i) in positions.iter().enumerate() {
+        if !is_word(&tokens[*i].token, "MATCH")
+            || positions.get(n + 1).map(|j| &tokens[*j].token) != Some(&Token::LParen)
+        {
+            continue;
+        }
+        let mut depth = 0;
+        let Some(close) = positions[n + 1..].iter().position(|j| {
+            match tokens[*j].token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                _ => (),
+            }
+            depth == 0
+        }) else {
+            continue;
+        };
+        let close = n + 1 + close;
+        let (Some(against), Some(open)) = (positions.get(close + 1), positions.get(close + 2))
+        else {
+            continue;
+        };
+        if !is_word(&tokens[*against].token, "AGAINST") || tokens[*open].token != Token::LParen {
+            continue;
+        }
+        tokens[*i].token = Token::Word(Word {
+            value: "match_against".to_string(),
+            quote_style: None,
+            keyword: Keyword::NoKeyword,
+        });
+        tokens[positions[close]].token = Token::Comma;
+        tokens[*against].token = Token::Whitespace(Whitespace::Space);
+        tokens[*open].token = Token::Whitespace(Whitespace::Space);
+    }
+}
+
+/// `source PIVOT (agg(expr) FOR column IN (value [AS name], ...))` and
+/// `source UNPIVOT (value FOR name IN (column [AS label], ...))`, which the sqlparser has no clause
+/// for, are rewritten to the table functions `pivot(source, agg(expr), column, name => value, ...)`
+/// and `unpivot(source, value, name, label => column, ...)`.
+fn rewrite_pivot(tokens: &mut Vec<TokenWithLocation>) {
+    while let Some((range, rewritten)) = next_pivot(tokens) {
+        tokens.splice(range, rewritten);
+    }
+}
+
+fn next_pivot(
+    tokens: &[TokenWithLocation],
+) -> Option<(RangeInclusive<usize>, Vec<TokenWithLocation>)> {
+    let is_word = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+    let positions = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let between = |from: usize, to: usize| tokens[positions[from]..=positions[to]].iter().cloned();
+
+    for (n, i) in positions.iter().enumerate().skip(1) {
+        let function = if is_word(&tokens[*i].token, "PIVOT") {
+            "pivot"
+        } else if is_word(&tokens[*i].token, "UNPIVOT") {
+            "unpivot"
+        } else {
+            continue;
+        };
+        if positions.get(n + 1).map(|j| &tokens[*j].token) != Some(&Token::LParen) {
+            continue;
+        }
+        // the table or the parenthesized subquery right before the keyword
+        let start = match tokens[positions[n - 1]].token {
+            Token::RParen => match matching_paren(tokens, &positions, n - 1) {
+                Some(start) => start,
+                None => continue,
+            },
+            Token::Word(_) => {
+                let mut start = n - 1;
+                while start > 1
+                    && tokens[positions[start - 1]].token == Token::Period
+                    && matches!(tokens[positions[start - 2]].token, Token::Word(_))
+                {
+                    start -= 2;
+                }
+                start
+            }
+            _ => continue,
+        };
+        let Some(close) = matching_paren(tokens, &positions, n + 1) else {
+            continue;
+        };
+        let mut depth = 0;
+        let (mut for_n, mut in_n) = (None, None);
+
+        for m in n + 2..close {
+            match &tokens[positions[m]].token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                token if depth == 0 && for_n.is_none() && is_word(token, "FOR") => for_n = Some(m),
+                token if depth == 0 && for_n.is_some() && is_word(token, "IN") => {
+                    in_n = Some(m);
+                    break;
+                }
+                _ => (),
+            }
+        }
+        let (Some(for_n), Some(in_n)) = (for_n, in_n) else {
+            continue;
+        };
+        if for_n == n + 2
+            || in_n == for_n + 1
+            || tokens[positions[in_n + 1]].token != Token::LParen
+            || matching_paren(tokens, &positions, in_n + 1) != Some(close - 1)
+        {
+            continue;
+        }
+        let token = |token: Token| {
+            let mut with_location = tokens[*i].clone();
+            with_location.token = token;
+            with_location
+        };
+        let mut rewritten = vec![
+            token(Token::Word(Word {
+                value: function.to_string(),
+                quote_style: None,
+                keyword: Keyword::NoKeyword,
+            })),
+            token(Token::LParen),
+        ];
+        rewritten.extend(between(start, n - 1));
+        rewritten.push(token(Token::Comma));
+        rewritten.extend(between(n + 2, for_n - 1));
+        rewritten.push(token(Token::Comma));
+        rewritten.extend(between(for_n + 1, in_n - 1));
+
+        // each of the values, `value AS name` turns into the named argument `name => value`
+        let mut items = Vec::new();
+        let mut item = in_n + 2;
+        depth = 0;
+        for m in in_n + 2..close - 1 {
+            match tokens[positions[m]].token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                Token::Comma if depth == 0 => {
+                    items.push((item, m - 1));
+                    item = m + 1;
+                }
+                _ => (),
+            }
+        }
+        items.push((item, close - 2));
+
+        for (from, to) in items {
+            rewritten.push(token(Token::Comma));
+            if to >= from + 2 && is_word(&tokens[positions[to - 1]].token, "AS") {
+                rewritten.push(tokens[positions[to]].clone());
+                rewritten.push(token(Token::RArrow));
+                rewritten.extend(between(from, to - 2));
+            } else if from <= to {
+                rewritten.extend(between(from, to));
+            }
+        }
+        rewritten.push(token(Token::RParen));
+
+        return Some((positions[start]..=positions[close], rewritten));
+    }
+    None
+}
+
+/// `TABLESAMPLE method (percentage) [REPEATABLE (seed)]`, which the sqlparser has no clause for,
+/// is rewritten to the table hints `WITH (method(percentage), REPEATABLE(seed))`.
+fn rewrite_table_sample(tokens: &mut Vec<TokenWithLocation>) {
+    while let Some((range, rewritten)) = next_table_sample(tokens) {
+        tokens.splice(range, rewritten);
+    }
+}
+
+fn next_table_sample(
+    tokens: &[TokenWithLocation],
+) -> Option<(RangeInclusive<usize>, Vec<TokenWithLocation>)> {
+    let is_word = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+    let positions = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    for (n, i) in positions.iter().enumerate() {
+        if !is_word(&tokens[*i].token, "TABLESAMPLE")
+            || !matches!(
+                positions.get(n + 1).map(|j| &tokens[*j].token),
+                Some(Token::Word(_))
+            )
+            || positions.get(n + 2).map(|j| &tokens[*j].token) != Some(&Token::LParen)
+        {
+            continue;
+        }
+        let Some(mut close) = matching_paren(tokens, &positions, n + 2) else {
+            continue;
+        };
+        let method_close = close;
+
+        if positions
+            .get(close + 1)
+            .is_some_and(|j| is_word(&tokens[*j].token, "REPEATABLE"))
+            && positions.get(close + 2).map(|j| &tokens[*j].token) == Some(&Token::LParen)
+        {
+            let Some(seed_close) = matching_paren(tokens, &positions, close + 2) else {
+                continue;
+            };
+            close = seed_close;
+        }
+        let token = |token: Token| {
+            let mut with_location = tokens[*i].clone();
+            with_location.token = token;
+            with_location
+        };
+        let mut rewritten = vec![
+            token(Token::Word(Word {
+                value: "WITH".to_string(),
+                quote_style: None,
+                keyword: Keyword::WITH,
+            })),
+            token(Token::LParen),
+        ];
+        rewritten.extend(
+            tokens[positions[n + 1]..=positions[method_close]]
+                .iter()
+                .cloned(),
+        );
+        if close != method_close {
+            rewritten.push(token(Token::Comma));
+            rewritten.extend(
+                tokens[positions[method_close + 1]..=positions[close]]
+                    .iter()
+                    .cloned(),
+            );
+        }
+        rewritten.push(token(Token::RParen));
+
+        return Some((positions[n]..=positions[close], rewritten));
+    }
+    None
+}
+
+/// `WITH DELETED` after a table, which the sqlparser has no clause for, is rewritten to the table
+/// hint `WITH (DELETED)`, a common table expression named `deleted` is followed by `AS` or its columns.
+fn rewrite_with_deleted(tokens: &mut Vec<TokenWithLocation>) {
+    let is_word = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+    let positions = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    // from the end, so the tokens inserted do not move the positions left to visit
+    for n in (1..positions.len()).rev() {
+        let deleted = positions[n];
+
+        if !is_word(&tokens[positions[n - 1]].token, "WITH")
+            || !is_word(&tokens[deleted].token, "DELETED")
+            || positions.get(n + 1).is_some_and(|i| {
+                tokens[*i].token == Token::LParen || is_word(&tokens[*i].token, "AS")
+            })
+        {
+            continue;
+        }
+        let token = |token: Token| {
+            let mut with_location = tokens[deleted].clone();
+            with_location.token = token;
+            with_location
+        };
+        let (lparen, rparen) = (token(Token::LParen), token(Token::RParen));
+        tokens.insert(deleted + 1, rparen);
+        tokens.insert(deleted, lparen);
+    }
+}
+
+/// `name AS [NOT] MATERIALIZED (query) CYCLE column, ...` in a `WITH` clause, whose hint and
+/// `CYCLE` the sqlparser has no clause for, is rewritten to
+/// `name AS (query) FROM "[not_]materialized cycle=column,..."`.
+fn rewrite_cte_options(tokens: &mut Vec<TokenWithLocation>) {
+    while let Some((range, rewritten)) = next_cte_options(tokens) {
+        tokens.splice(range, rewritten);
+    }
+}
+
+fn next_cte_options(
+    tokens: &[TokenWithLocation],
+) -> Option<(RangeInclusive<usize>, Vec<TokenWithLocation>)> {
+    let is_word = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+    let positions = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let is_nth_word = |n: usize, keyword: &str| {
+        positions
+            .get(n)
+            .is_some_and(|i| is_word(&tokens[*i].token, keyword))
+    };
+
+    for n in 0..positions.len() {
+        if !is_nth_word(n, "AS") {
+            continue;
+        }
+        let (mut options, lparen) = if is_nth_word(n + 1, "MATERIALIZED") {
+            (vec!["materialized".to_string()], n + 2)
+        } else if is_nth_word(n + 1, "NOT") && is_nth_word(n + 2, "MATERIALIZED") {
+            (vec!["not_materialized".to_string()], n + 3)
+        } else {
+            (vec![], n + 1)
+        };
+        if positions.get(lparen).map(|i| &tokens[*i].token) != Some(&Token::LParen) {
+            continue;
+        }
+        let Some(close) = matching_paren(tokens, &positions, lparen) else {
+            continue;
+        };
+        let mut last = close;
+
+        if is_nth_word(close + 1, "CYCLE") {
+            let mut columns = Vec::new();
+
+            while let Some(Token::Word(word)) = positions.get(last + 2).map(|i| &tokens[*i].token) {
+                // folded as the binder folds identifiers, the options are carried quoted
+                columns.push(if word.quote_style.is_some() {
+                    word.value.clone()
+                } else {
+                    word.value.to_lowercase()
+                });
+                last += 2;
+
+                if positions.get(last + 1).map(|i| &tokens[*i].token) != Some(&Token::Comma) {
+                    break;
+                }
+            }
+            if columns.is_empty() {
+                continue;
+            }
+            options.push(format!("cycle={}", columns.join(",")));
+        }
+        if options.is_empty() {
+            continue;
+        }
+        let token = |token: Token| {
+            let mut with_location = tokens[positions[n]].clone();
+            with_location.token = token;
+            with_location
+        };
+        let mut rewritten = tokens[positions[lparen]..=positions[close]].to_vec();
+        rewritten.extend([
+            token(Token::Whitespace(Whitespace::Space)),
+            token(Token::make_keyword("FROM")),
+            token(Token::Whitespace(Whitespace::Space)),
+            token(Token::make_word(&options.join(" "), Some('"'))),
+        ]);
+
+        return Some((positions[n + 1]..=positions[last], rewritten));
+    }
+    None
+}
+
+/// Position of the parenthesis matching the one at `positions[n]`, forward for `(` and backward
+/// for `)`.
+fn matching_paren(tokens: &[TokenWithLocation], positions: &[usize], n: usize) -> Option<usize> {
+    let forward = tokens[positions[n]].token == Token::LParen;
+    let mut depth = 0;
+    let mut m = n;
+
+    loop {
+        match tokens[positions[m]].token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            _ => (),
+        }
+        if depth == 0 {
+            return Some(m);
+        }
+        if forward {
+            m += 1;
+            if m == positions.len() {
+                return None;
+            }
+        } else {
+            m = m.checked_sub(1)?;
+        }
+    }
+}
+
+/// The vector distances `<->` and `<#>`, which the sqlparser reads as `<` followed by `->` or `#>`,
+/// are rewritten to the operators `~` and `!~` that the binder takes as them for vectors.
+fn rewrite_vector_operators(tokens: &mut [TokenWithLocation]) {
+    for i in 1..tokens.len() {
+        let operator = match (&tokens[i - 1].token, &tokens[i].token) {
+            (Token::Lt, Token::Arrow) => Token::Tilde,
+            (Token::Lt, Token::HashArrow) => Token::ExclamationMarkTilde,
+            _ => continue,
+        };
+        tokens[i - 1].token = operator;
+        tokens[i].token = Token::Whitespace(Whitespace::Space);
+    }
+}
+
+fn lower_quoted_identifiers(tokens: &mut [TokenWithLocation]) {
+    for token in tokens.iter_mut() {
+        if let Token::Word(word) = &mut token.token {
+            if word.quote_style.is_some() {
+                word.value = word.value.to_lowercase();
+            }
+        }
+    }
+}
+
+/// `SELECT TOP n`, which the sqlparser reads in any dialect, only limits the rows in the
+/// generic dialect.
+fn check_no_top(tokens: &[TokenWithLocation]) -> Result<(), ParserError> {
+    let is_word = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+    let tokens = tokens
+        .iter()
+        .filter(|token| !matches!(token.token, Token::Whitespace(_)))
+        .collect::<Vec<_>>();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !is_word(&token.token, "TOP") {
+            continue;
+        }
+        let after_select = tokens[..i]
+            .iter()
+            .rev()
+            .find(|token| !is_word(&token.token, "DISTINCT") && !is_word(&token.token, "ALL"))
+            .is_some_and(|token| is_word(&token.token, "SELECT"));
+        if after_select {
+            return Err(ParserError::ParserError(format!(
+                "`SELECT TOP` is only supported by the generic dialect, use LIMIT instead at line {}, column {}",
+                token.location.line, token.location.column
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// `a || b` is carried as `a OR b`, and `LIMIT offset, count` as `LIMIT count OFFSET offset`.
+fn rewrite_mysql_operators(tokens: &mut [TokenWithLocation]) {
+    let positions = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    for (n, i) in positions.iter().enumerate() {
+        match &tokens[*i].token {
+            Token::StringConcat => tokens[*i].token = Token::make_keyword("OR"),
+            Token::Word(word) if word.keyword == Keyword::LIMIT => {
+                let [offset, comma, count] = [n + 1, n + 2, n + 3].map(|m| positions.get(m));
+                let (Some(offset), Some(comma), Some(count)) = (offset, comma, count) else {
+                    continue;
+                };
+                if !matches!(
+                    tokens[*offset].token,
+                    Token::Number(..) | Token::Placeholder(_)
+                ) || tokens[*comma].token != Token::Comma
+                    || !matches!(
+                        tokens[*count].token,
+                        Token::Number(..) | Token::Placeholder(_)
+                    )
+                {
+                    continue;
+                }
+                let offset_token = tokens[*offset].token.clone();
+                tokens[*offset].token = tokens[*count].token.clone();
+                tokens[*comma].token = Token::make_keyword("OFFSET");
+                tokens[*count].token = offset_token;
+            }
+            _ => (),
+        }
+    }
+}
+
+/// `REINDEX INDEX t.idx` and `REINDEX TABLE t`, which the sqlparser has no statement for,
+/// are carried as `MSCK REPAIR TABLE t.idx` and `MSCK REPAIR TABLE t`.
+fn parse_reindex(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let is_index =
+        parser.expect_one_of_keywords(&[Keyword::INDEX, Keyword::TABLE])? == Keyword::INDEX;
+    let name = parser.parse_object_name()?;
+
+    if is_index != (name.0.len() == 2) {
+        return Err(ParserError::ParserError(format!(
+            "expected {} after REINDEX {}, found: {}",
+            if is_index {
+                "<table>.<index>"
+            } else {
+                "<table>"
+            },
+            if is_index { "INDEX" } else { "TABLE" },
+            name
+        )));
+    }
+    Ok(Statement::Msck {
+        table_name: name,
+        repair: true,
+        partition_action: None,
+    })
+}
+
+/// `CHECK TABLE t` is carried as `MSCK TABLE t`.
+fn parse_check_table(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    parser.expect_keyword(Keyword::TABLE)?;
+
+    Ok(Statement::Msck {
+        table_name: parser.parse_object_name()?,
+        repair: false,
+        partition_action: None,
+    })
+}
+
+/// `VACUUM [TABLE] t` is carried as `MSCK REPAIR TABLE t SYNC PARTITIONS`.
+fn parse_vacuum(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let _ = parser.parse_keyword(Keyword::TABLE);
+
+    Ok(Statement::Msck {
+        table_name: parser.parse_object_name()?,
+        repair: true,
+        partition_action: Some(AddDropSync::SYNC),
+    })
+}
+
+/// `PURGE [TABLE] t` is carried as `DELETE FROM t WITH DELETED WHERE __deleted_at IS NOT NULL`,
+/// which removes the rows deleted from a table in soft delete mode.
+fn parse_purge(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let _ = parser.parse_keyword(Keyword::TABLE);
+    let table_name = parser.parse_object_name()?;
+
+    Ok(parse_sql(format!(
+        "DELETE FROM {table_name} WITH DELETED WHERE {DELETED_AT_COLUMN} IS NOT NULL"
+    ))?
+    .remove(0))
+}
+
+/// `ATTACH [DATABASE] 'path' AS name` is carried as `CREATE DATABASE name LOCATION 'path'`.
+fn parse_attach_database(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let _ = parser.parse_keyword(Keyword::DATABASE);
+    let location = parser.parse_literal_string()?;
+    parser.expect_keyword(Keyword::AS)?;
+
+    Ok(Statement::CreateDatabase {
+        db_name: ObjectName(vec![parser.parse_identifier()?]),
+        if_not_exists: false,
+        location: Some(location),
+        managed_location: None,
+    })
+}
+
+/// `DETACH [DATABASE] name` is carried as `DROP SCHEMA name`.
+fn parse_detach_database(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let _ = parser.parse_keyword(Keyword::DATABASE);
+
+    Ok(Statement::Drop {
+        object_type: ObjectType::Schema,
+        if_exists: false,
+        names: vec![ObjectName(vec![parser.parse_identifier()?])],
+        cascade: false,
+        restrict: false,
+        purge: false,
+    })
+}
+
+/// `CREATE TASK name SCHEDULE 'cron' AS statement` is carried as `PREPARE name AS statement`,
+/// with the schedule and the source text of the statement kept in a custom data type,
+/// the text is what the scheduler runs, as statements like `REINDEX` do not display back to it.
+fn parse_create_task(parser: &mut Parser, sql: &str) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let _ = parser.next_token();
+    let name = parser.parse_identifier()?;
+    if !is_keyword(parser, "SCHEDULE") {
+        return parser.expected("SCHEDULE", parser.peek_token());
+    }
+    let _ = parser.next_token();
+    let schedule = parser.parse_literal_string()?;
+    parser.expect_keyword(Keyword::AS)?;
+
+    let start = location_offset(sql, &parser.peek_token().location);
+    let statement = if is_keyword(parser, "REINDEX") {
+        parse_reindex(parser)?
+    } else if is_keyword(parser, "CHECK") {
+        parse_check_table(parser)?
+    } else if is_keyword(parser, "VACUUM") {
+        parse_vacuum(parser)?
+    } else if is_keyword(parser, "PURGE") {
+        parse_purge(parser)?
+    } else {
+        parser.parse_statement()?
+    };
+    let next_token = parser.peek_token();
+    let end = if next_token.token == Token::EOF {
+        sql.len()
+    } else {
+        location_offset(sql, &next_token.location)
+    };
+
+    Ok(Statement::Prepare {
+        name,
+        data_types: vec![DataType::Custom(
+            ObjectName(vec![Ident::with_quote('\'', schedule)]),
+            vec![sql[start..end].trim().to_string()],
+        )],
+        statement: Box::new(statement),
+    })
+}
+
+/// `DROP TASK [IF EXISTS] name` is carried as `DEALLOCATE [PREPARE] name`,
+/// where `PREPARE` stands for `IF EXISTS`.
+fn parse_drop_task(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let _ = parser.next_token();
+    let if_exists = parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+
+    Ok(Statement::Deallocate {
+        name: parser.parse_identifier()?,
+        prepare: if_exists,
+    })
+}
+
+/// `CREATE FULLTEXT INDEX [IF NOT EXISTS] [name] ON t (column)` is carried as
+/// `CREATE INDEX name ON t USING fulltext (column)`, the name defaults to `ft_{column}`.
+fn parse_create_full_text_index(parser: &mut Parser) -> Result<Statement, ParserError> {
+    let _ = parser.next_token();
+    let _ = parser.next_token();
+    parser.expect_keyword(Keyword::INDEX)?;
+    let if_not_exists = parser.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+    let name = if parser.parse_keyword(Keyword::ON) {
+        None
+    } else {
+        let name = parser.parse_object_name()?;
+        parser.expect_keyword(Keyword::ON)?;
+        Some(name)
+    };
+    let table_name = parser.parse_object_name()?;
+    parser.expect_token(&Token::LParen)?;
+    let columns = parser.parse_comma_separated(Parser::parse_order_by_expr)?;
+    parser.expect_token(&Token::RParen)?;
+
+    let name = match name {
+        Some(name) => name,
+        None => ObjectName(vec![Ident::new(format!(
+            "ft_{}",
+            columns
+                .iter()
+                .map(|column| column.expr.to_string())
+                .collect::<Vec<_>>()
+                .join("_")
+        ))]),
+    };
+    Ok(Statement::CreateIndex {
+        name,
+        table_name,
+        using: Some(Ident::new("fulltext")),
+        columns,
+        unique: false,
+        if_not_exists,
+    })
 }
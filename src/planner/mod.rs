@@ -2,6 +2,7 @@ pub mod operator;
 
 use crate::catalog::{ColumnCatalog, ColumnRef, TableName};
 use crate::planner::operator::join::JoinType;
+use crate::planner::operator::recursive_union::{RecursiveUnionOperator, WorkTableScanOperator};
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
@@ -112,8 +113,10 @@ impl LogicalPlan {
 
     pub fn referenced_table(&self) -> Vec<TableName> {
         fn collect_table(plan: &LogicalPlan, results: &mut Vec<TableName>) {
-            if let Operator::TableScan(op) = &plan.operator {
-                results.push(op.table_name.clone());
+            match &plan.operator {
+                Operator::TableScan(op) => results.push(op.table_name.clone()),
+                Operator::ForeignScan(op) => results.push(op.table_name.clone()),
+                _ => (),
             }
             for child in plan.childrens.iter() {
                 collect_table(child, results);
@@ -130,9 +133,10 @@ impl LogicalPlan {
         mut childrens_iter: ChildrensIter,
     ) -> SchemaOutput {
         match operator {
-            Operator::Filter(_) | Operator::Sort(_) | Operator::Limit(_) => {
-                childrens_iter.next().unwrap().output_schema_direct()
-            }
+            Operator::Filter(_)
+            | Operator::Sort(_)
+            | Operator::Limit(_)
+            | Operator::SharedScan(_) => childrens_iter.next().unwrap().output_schema_direct(),
             Operator::Aggregate(op) => SchemaOutput::Schema(
                 op.agg_calls
                     .iter()
@@ -162,6 +166,9 @@ impl LogicalPlan {
             Operator::TableScan(op) => {
                 SchemaOutput::Schema(op.columns.values().cloned().collect_vec())
             }
+            Operator::ForeignScan(op) => {
+                SchemaOutput::Schema(op.columns.values().cloned().collect_vec())
+            }
             Operator::FunctionScan(op) => {
                 SchemaOutput::SchemaRef(op.table_function.output_schema().clone())
             }
@@ -169,14 +176,29 @@ impl LogicalPlan {
             | Operator::Union(UnionOperator {
                 left_schema_ref: schema_ref,
                 ..
-            }) => SchemaOutput::SchemaRef(schema_ref.clone()),
+            })
+            | Operator::RecursiveUnion(RecursiveUnionOperator { schema_ref, .. })
+            | Operator::WorkTableScan(WorkTableScanOperator { schema_ref, .. }) => {
+                SchemaOutput::SchemaRef(schema_ref.clone())
+            }
             Operator::Dummy => SchemaOutput::Schema(vec![]),
             Operator::ShowTable => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("TABLE".to_string()),
             )]),
+            Operator::ShowTableStatus => SchemaOutput::Schema(vec![
+                ColumnRef::from(ColumnCatalog::new_dummy("TABLE".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("INDEX".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("ROWS".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("BYTES".to_string())),
+            ]),
             Operator::ShowView => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("VIEW".to_string()),
             )]),
+            Operator::ShowTask => SchemaOutput::Schema(vec![
+                ColumnRef::from(ColumnCatalog::new_dummy("TASK".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("SCHEDULE".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("SQL".to_string())),
+            ]),
             Operator::Explain => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("PLAN".to_string()),
             )]),
@@ -197,9 +219,16 @@ impl LogicalPlan {
             Operator::Delete(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("DELETED".to_string()),
             )]),
-            Operator::Analyze(_) => SchemaOutput::Schema(vec![ColumnRef::from(
-                ColumnCatalog::new_dummy("STATISTICS_META_PATH".to_string()),
-            )]),
+            Operator::CheckTable(_) => SchemaOutput::Schema(vec![
+                ColumnRef::from(ColumnCatalog::new_dummy("INDEX".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("TUPLE_ID".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("PROBLEM".to_string())),
+            ]),
+            Operator::Analyze(_) => SchemaOutput::Schema(vec![
+                ColumnRef::from(ColumnCatalog::new_dummy("ROWS".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("COLUMNS".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("STATISTICS_META_PATH".to_string())),
+            ]),
             Operator::AddColumn(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("ADD COLUMN SUCCESS".to_string()),
             )]),
@@ -209,24 +238,38 @@ impl LogicalPlan {
             Operator::CreateTable(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("CREATE TABLE SUCCESS".to_string()),
             )]),
-            Operator::CreateIndex(_) => SchemaOutput::Schema(vec![ColumnRef::from(
-                ColumnCatalog::new_dummy("CREATE INDEX SUCCESS".to_string()),
-            )]),
+            Operator::CreateIndex(_) => SchemaOutput::Schema(vec![
+                ColumnRef::from(ColumnCatalog::new_dummy("CREATE INDEX SUCCESS".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("ROWS".to_string())),
+            ]),
             Operator::CreateView(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("CREATE VIEW SUCCESS".to_string()),
             )]),
+            Operator::CreateTask(_) => SchemaOutput::Schema(vec![ColumnRef::from(
+                ColumnCatalog::new_dummy("CREATE TASK SUCCESS".to_string()),
+            )]),
             Operator::DropTable(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("DROP TABLE SUCCESS".to_string()),
             )]),
             Operator::DropView(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("DROP VIEW SUCCESS".to_string()),
             )]),
+            Operator::DropTask(_) => SchemaOutput::Schema(vec![ColumnRef::from(
+                ColumnCatalog::new_dummy("DROP TASK SUCCESS".to_string()),
+            )]),
             Operator::DropIndex(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("DROP INDEX SUCCESS".to_string()),
             )]),
+            Operator::Reindex(_) => SchemaOutput::Schema(vec![
+                ColumnRef::from(ColumnCatalog::new_dummy("INDEX".to_string())),
+                ColumnRef::from(ColumnCatalog::new_dummy("ROWS".to_string())),
+            ]),
             Operator::Truncate(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("TRUNCATE TABLE SUCCESS".to_string()),
             )]),
+            Operator::Vacuum(_) => SchemaOutput::Schema(vec![ColumnRef::from(
+                ColumnCatalog::new_dummy("ROWS".to_string()),
+            )]),
             Operator::CopyFromFile(_) => SchemaOutput::Schema(vec![ColumnRef::from(
                 ColumnCatalog::new_dummy("COPY FROM SOURCE".to_string()),
             )]),
@@ -5,11 +5,19 @@ use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
 
+/// Bytes of the groups of a `GROUP BY` kept in memory by default, see
+/// [`AggregateOperator::aggregate_memory_limit`].
+pub const DEFAULT_AGGREGATE_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
 pub struct AggregateOperator {
     pub groupby_exprs: Vec<ScalarExpression>,
     pub agg_calls: Vec<ScalarExpression>,
     pub is_distinct: bool,
+    /// Bytes of the keys and the accumulators of the groups the hash aggregation keeps in memory,
+    /// by their estimate, the rows of the other groups are spilled to temporary files and
+    /// aggregated once the input is read, set by `DataBaseBuilder::aggregate_memory_limit`
+    pub aggregate_memory_limit: usize,
 }
 
 impl AggregateOperator {
@@ -18,12 +26,14 @@ impl AggregateOperator {
         agg_calls: Vec<ScalarExpression>,
         groupby_exprs: Vec<ScalarExpression>,
         is_distinct: bool,
+        aggregate_memory_limit: usize,
     ) -> LogicalPlan {
         LogicalPlan::new(
             Operator::Aggregate(Self {
                 groupby_exprs,
                 agg_calls,
                 is_distinct,
+                aggregate_memory_limit,
             }),
             Childrens::Only(children),
         )
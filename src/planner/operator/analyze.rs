@@ -1,9 +1,14 @@
 use crate::catalog::TableName;
 use crate::types::index::IndexMetaRef;
 use kite_sql_serde_macros::ReferenceSerialization;
+use ordered_float::OrderedFloat;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
 pub struct AnalyzeOperator {
     pub table_name: TableName,
     pub index_metas: Vec<IndexMetaRef>,
+    /// Only rebuild the statistics of indexes whose columns were modified since the last `ANALYZE`
+    pub is_incremental: bool,
+    /// Fraction of the rows sampled to build the statistics
+    pub sample_fraction: OrderedFloat<f64>,
 }
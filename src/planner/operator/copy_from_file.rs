@@ -1,5 +1,6 @@
-use crate::binder::copy::ExtSource;
+use crate::binder::copy::{CopyLoadOptions, ExtSource};
 use crate::catalog::TableName;
+use crate::parser::quote_identifier;
 use crate::types::tuple::SchemaRef;
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -11,6 +12,7 @@ pub struct CopyFromFileOperator {
     pub table: TableName,
     pub source: ExtSource,
     pub schema_ref: SchemaRef,
+    pub load: CopyLoadOptions,
 }
 
 impl fmt::Display for CopyFromFileOperator {
@@ -18,13 +20,13 @@ impl fmt::Display for CopyFromFileOperator {
         let columns = self
             .schema_ref
             .iter()
-            .map(|column| column.name().to_string())
+            .map(|column| quote_identifier(column.name()))
             .join(", ");
         write!(
             f,
             "Copy {} -> {} [{}]",
             self.source.path.display(),
-            self.table,
+            quote_identifier(&self.table),
             columns
         )?;
 
@@ -1,4 +1,5 @@
 use crate::binder::copy::ExtSource;
+use crate::parser::quote_identifier;
 use crate::types::tuple::SchemaRef;
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -9,6 +10,9 @@ use std::fmt::Formatter;
 pub struct CopyToFileOperator {
     pub target: ExtSource,
     pub schema_ref: SchemaRef,
+    /// Positions of the columns of `PARTITION BY`, each partition is written to
+    /// `target/column=value/.../part-0.<format>` without them
+    pub partition_by: Vec<usize>,
 }
 
 impl fmt::Display for CopyToFileOperator {
@@ -16,9 +20,17 @@ impl fmt::Display for CopyToFileOperator {
         let columns = self
             .schema_ref
             .iter()
-            .map(|column| column.name().to_string())
+            .map(|column| quote_identifier(column.name()))
             .join(", ");
         write!(f, "Copy To {} [{}]", self.target.path.display(), columns)?;
+        if !self.partition_by.is_empty() {
+            let partition_by = self
+                .partition_by
+                .iter()
+                .map(|i| quote_identifier(self.schema_ref[*i].name()))
+                .join(", ");
+            write!(f, " Partition By [{}]", partition_by)?;
+        }
 
         Ok(())
     }
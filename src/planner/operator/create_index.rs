@@ -1,4 +1,5 @@
 use crate::catalog::{ColumnRef, TableName};
+use crate::parser::quote_identifier;
 use crate::types::index::IndexType;
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -20,12 +21,14 @@ impl fmt::Display for CreateIndexOperator {
         let columns = self
             .columns
             .iter()
-            .map(|column| column.name().to_string())
+            .map(|column| quote_identifier(column.name()))
             .join(", ");
         write!(
             f,
             "Create Index On {} -> [{}], If Not Exists: {}",
-            self.table_name, columns, self.if_not_exists
+            quote_identifier(&self.table_name),
+            columns,
+            self.if_not_exists
         )?;
 
         Ok(())
@@ -1,4 +1,6 @@
-use crate::catalog::{ColumnCatalog, TableName};
+use crate::catalog::{AuditColumns, ColumnCatalog, ForeignTable, TableName};
+use crate::parser::quote_identifier;
+use crate::storage::tuple_codec::TupleCodecKind;
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
@@ -10,6 +12,12 @@ pub struct CreateTableOperator {
     pub table_name: TableName,
     /// List of columns of the table
     pub columns: Vec<ColumnCatalog>,
+    /// The remote source of a foreign table
+    pub foreign: Option<ForeignTable>,
+    /// How the rows of the table are stored
+    pub tuple_codec: TupleCodecKind,
+    /// The columns filled by INSERT and UPDATE themselves
+    pub audit_columns: AuditColumns,
     pub if_not_exists: bool,
 }
 
@@ -18,12 +26,14 @@ impl fmt::Display for CreateTableOperator {
         let columns = self
             .columns
             .iter()
-            .map(|column| column.name().to_string())
+            .map(|column| quote_identifier(column.name()))
             .join(", ");
         write!(
             f,
             "Create {} -> [{}], If Not Exists: {}",
-            self.table_name, columns, self.if_not_exists
+            quote_identifier(&self.table_name),
+            columns,
+            self.if_not_exists
         )?;
 
         Ok(())
@@ -0,0 +1,17 @@
+use crate::catalog::task::Task;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct CreateTaskOperator {
+    pub task: Task,
+}
+
+impl fmt::Display for CreateTaskOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Create {}", self.task)?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::catalog::{ColumnRef, TableName};
+use crate::parser::quote_identifier;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -12,7 +13,7 @@ pub struct DeleteOperator {
 
 impl fmt::Display for DeleteOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Delete {}", self.table_name)?;
+        write!(f, "Delete {}", quote_identifier(&self.table_name))?;
 
         Ok(())
     }
@@ -1,4 +1,5 @@
 use crate::catalog::TableName;
+use crate::parser::quote_identifier;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -10,7 +11,7 @@ pub struct DescribeOperator {
 
 impl fmt::Display for DescribeOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Describe {}", self.table_name)?;
+        write!(f, "Describe {}", quote_identifier(&self.table_name))?;
 
         Ok(())
     }
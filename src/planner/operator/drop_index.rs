@@ -1,4 +1,5 @@
 use crate::catalog::TableName;
+use crate::parser::quote_identifier;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -35,7 +36,9 @@ impl fmt::Display for DropIndexOperator {
         write!(
             f,
             "Drop Index {} On {}, If Exists: {}",
-            self.index_name, self.table_name, self.if_exists
+            quote_identifier(&self.index_name),
+            quote_identifier(&self.table_name),
+            self.if_exists
         )?;
 
         Ok(())
@@ -1,4 +1,5 @@
 use crate::catalog::TableName;
+use crate::parser::quote_identifier;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -15,7 +16,8 @@ impl fmt::Display for DropTableOperator {
         write!(
             f,
             "Drop Table {}, If Exists: {}",
-            self.table_name, self.if_exists
+            quote_identifier(&self.table_name),
+            self.if_exists
         )?;
 
         Ok(())
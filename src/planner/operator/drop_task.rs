@@ -0,0 +1,21 @@
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct DropTaskOperator {
+    pub task_name: String,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropTaskOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Drop Task {}, If Exists: {}",
+            self.task_name, self.if_exists
+        )?;
+
+        Ok(())
+    }
+}
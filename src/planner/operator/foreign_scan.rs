@@ -0,0 +1,71 @@
+use crate::catalog::{ColumnRef, ForeignTable, TableCatalog, TableName};
+use crate::fdw::{ArcForeignDataWrapper, ForeignFilter};
+use crate::parser::quote_identifier;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use itertools::Itertools;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct ForeignScanOperator {
+    pub(crate) table_name: TableName,
+    #[rustfmt::skip]
+    pub(crate) columns: BTreeMap::<usize, ColumnRef>,
+    pub(crate) foreign: ForeignTable,
+    pub(crate) wrapper: ArcForeignDataWrapper,
+    // Support push down simple predicates into the remote source.
+    pub(crate) filters: Vec<ForeignFilter>,
+}
+
+impl ForeignScanOperator {
+    pub fn build(
+        table_name: TableName,
+        table_catalog: &TableCatalog,
+        foreign: ForeignTable,
+        wrapper: ArcForeignDataWrapper,
+    ) -> LogicalPlan {
+        let columns = table_catalog
+            .columns()
+            .enumerate()
+            .map(|(i, column)| (i, column.clone()))
+            .collect();
+
+        LogicalPlan::new(
+            Operator::ForeignScan(ForeignScanOperator {
+                table_name,
+                columns,
+                foreign,
+                wrapper,
+                filters: vec![],
+            }),
+            Childrens::None,
+        )
+    }
+}
+
+impl fmt::Display for ForeignScanOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let projection_columns = self
+            .columns
+            .values()
+            .map(|column| quote_identifier(column.name()))
+            .join(", ");
+
+        write!(
+            f,
+            "ForeignScan {} -> [{}], Remote: {}.{}",
+            quote_identifier(&self.table_name),
+            projection_columns,
+            self.foreign.wrapper,
+            self.foreign.remote_table
+        )?;
+        if !self.filters.is_empty() {
+            write!(f, ", Filters: [{}]", self.filters.iter().join(", "))?;
+        }
+
+        Ok(())
+    }
+}
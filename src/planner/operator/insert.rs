@@ -1,4 +1,5 @@
 use crate::catalog::TableName;
+use crate::parser::quote_identifier;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -15,7 +16,9 @@ impl fmt::Display for InsertOperator {
         write!(
             f,
             "Insert {}, Is Overwrite: {}, Is Mapping By Name: {}",
-            self.table_name, self.is_overwrite, self.is_mapping_by_name
+            quote_identifier(&self.table_name),
+            self.is_overwrite,
+            self.is_mapping_by_name
         )?;
 
         Ok(())
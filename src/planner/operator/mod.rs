@@ -1,27 +1,35 @@
 pub mod aggregate;
 pub mod alter_table;
 pub mod analyze;
+pub mod check_table;
 pub mod copy_from_file;
 pub mod copy_to_file;
 pub mod create_index;
 pub mod create_table;
+pub mod create_task;
 pub mod create_view;
 pub mod delete;
 pub mod describe;
 pub mod drop_index;
 pub mod drop_table;
+pub mod drop_task;
 pub mod drop_view;
 pub mod filter;
+pub mod foreign_scan;
 pub mod function_scan;
 pub mod insert;
 pub mod join;
 pub mod limit;
 pub mod project;
+pub mod recursive_union;
+pub mod reindex;
+pub mod shared_scan;
 pub mod sort;
 pub mod table_scan;
 pub mod truncate;
 pub mod union;
 pub mod update;
+pub mod vacuum;
 pub mod values;
 
 use self::{
@@ -30,25 +38,34 @@ use self::{
     sort::SortOperator, table_scan::TableScanOperator,
 };
 use crate::catalog::ColumnRef;
+use crate::execution::custom::ArcCustomExecutor;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
 use crate::planner::operator::analyze::AnalyzeOperator;
+use crate::planner::operator::check_table::CheckTableOperator;
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 use crate::planner::operator::copy_to_file::CopyToFileOperator;
 use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::operator::create_table::CreateTableOperator;
+use crate::planner::operator::create_task::CreateTaskOperator;
 use crate::planner::operator::create_view::CreateViewOperator;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::operator::describe::DescribeOperator;
 use crate::planner::operator::drop_index::DropIndexOperator;
 use crate::planner::operator::drop_table::DropTableOperator;
+use crate::planner::operator::drop_task::DropTaskOperator;
 use crate::planner::operator::drop_view::DropViewOperator;
+use crate::planner::operator::foreign_scan::ForeignScanOperator;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::recursive_union::{RecursiveUnionOperator, WorkTableScanOperator};
+use crate::planner::operator::reindex::ReindexOperator;
+use crate::planner::operator::shared_scan::SharedScanOperator;
 use crate::planner::operator::truncate::TruncateOperator;
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::update::UpdateOperator;
+use crate::planner::operator::vacuum::VacuumOperator;
 use crate::planner::operator::values::ValuesOperator;
 use crate::types::index::IndexInfo;
 use itertools::Itertools;
@@ -66,14 +83,21 @@ pub enum Operator {
     Project(ProjectOperator),
     TableScan(TableScanOperator),
     FunctionScan(FunctionScanOperator),
+    ForeignScan(ForeignScanOperator),
     Sort(SortOperator),
     Limit(LimitOperator),
     Values(ValuesOperator),
     ShowTable,
+    ShowTableStatus,
     ShowView,
+    ShowTask,
     Explain,
     Describe(DescribeOperator),
+    CheckTable(CheckTableOperator),
     Union(UnionOperator),
+    SharedScan(SharedScanOperator),
+    RecursiveUnion(RecursiveUnionOperator),
+    WorkTableScan(WorkTableScanOperator),
     // DML
     Insert(InsertOperator),
     Update(UpdateOperator),
@@ -85,10 +109,14 @@ pub enum Operator {
     CreateTable(CreateTableOperator),
     CreateIndex(CreateIndexOperator),
     CreateView(CreateViewOperator),
+    CreateTask(CreateTaskOperator),
     DropTable(DropTableOperator),
     DropView(DropViewOperator),
+    DropTask(DropTaskOperator),
     DropIndex(DropIndexOperator),
+    Reindex(ReindexOperator),
     Truncate(TruncateOperator),
+    Vacuum(VacuumOperator),
     // Copy
     CopyFromFile(CopyFromFileOperator),
     CopyToFile(CopyToFileOperator),
@@ -99,14 +127,22 @@ pub enum PhysicalOption {
     Dummy,
     SimpleAggregate,
     HashAggregate,
+    CountStar,
+    MinMaxIndex,
     Filter,
+    FusedFilter,
     HashJoin,
     NestLoopJoin,
     Project,
+    FusedProject,
     SeqScan,
     FunctionScan,
+    ForeignScan,
     IndexScan(IndexInfo),
+    MultiGet(IndexInfo),
+    SkipScan(IndexInfo),
     Sort,
+    IncrementalSort,
     Limit,
     Values,
     Insert,
@@ -121,6 +157,7 @@ pub enum PhysicalOption {
     CopyFromFile,
     CopyToFile,
     Analyze,
+    Custom(ArcCustomExecutor),
 }
 
 impl Operator {
@@ -142,12 +179,20 @@ impl Operator {
                     .map(|column| ScalarExpression::ColumnRef(column.clone()))
                     .collect_vec(),
             ),
-            Operator::Sort(_) | Operator::Limit(_) => None,
+            Operator::ForeignScan(op) => Some(
+                op.columns
+                    .values()
+                    .map(|column| ScalarExpression::ColumnRef(column.clone()))
+                    .collect_vec(),
+            ),
+            Operator::Sort(_) | Operator::Limit(_) | Operator::SharedScan(_) => None,
             Operator::Values(ValuesOperator { schema_ref, .. })
             | Operator::Union(UnionOperator {
                 left_schema_ref: schema_ref,
                 ..
-            }) => Some(
+            })
+            | Operator::RecursiveUnion(RecursiveUnionOperator { schema_ref, .. })
+            | Operator::WorkTableScan(WorkTableScanOperator { schema_ref, .. }) => Some(
                 schema_ref
                     .iter()
                     .cloned()
@@ -163,9 +208,12 @@ impl Operator {
                     .collect_vec(),
             ),
             Operator::ShowTable
+            | Operator::ShowTableStatus
             | Operator::ShowView
+            | Operator::ShowTask
             | Operator::Explain
             | Operator::Describe(_)
+            | Operator::CheckTable(_)
             | Operator::Insert(_)
             | Operator::Update(_)
             | Operator::Delete(_)
@@ -175,10 +223,14 @@ impl Operator {
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateTask(_)
             | Operator::DropTable(_)
             | Operator::DropView(_)
+            | Operator::DropTask(_)
             | Operator::DropIndex(_)
+            | Operator::Reindex(_)
             | Operator::Truncate(_)
+            | Operator::Vacuum(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_) => None,
         }
@@ -214,6 +266,7 @@ impl Operator {
                 .flat_map(|expr| expr.referenced_columns(only_column_ref))
                 .collect_vec(),
             Operator::TableScan(op) => op.columns.values().cloned().collect_vec(),
+            Operator::ForeignScan(op) => op.columns.values().cloned().collect_vec(),
             Operator::FunctionScan(op) => op
                 .table_function
                 .args
@@ -226,7 +279,11 @@ impl Operator {
                 .map(|field| &field.expr)
                 .flat_map(|expr| expr.referenced_columns(only_column_ref))
                 .collect_vec(),
-            Operator::Values(ValuesOperator { schema_ref, .. }) => Vec::clone(schema_ref),
+            Operator::Values(ValuesOperator { schema_ref, .. })
+            | Operator::RecursiveUnion(RecursiveUnionOperator { schema_ref, .. })
+            | Operator::WorkTableScan(WorkTableScanOperator { schema_ref, .. }) => {
+                Vec::clone(schema_ref)
+            }
             Operator::Union(UnionOperator {
                 left_schema_ref,
                 _right_schema_ref,
@@ -239,10 +296,14 @@ impl Operator {
             Operator::Delete(op) => op.primary_keys.clone(),
             Operator::Dummy
             | Operator::Limit(_)
+            | Operator::SharedScan(_)
             | Operator::ShowTable
+            | Operator::ShowTableStatus
             | Operator::ShowView
+            | Operator::ShowTask
             | Operator::Explain
             | Operator::Describe(_)
+            | Operator::CheckTable(_)
             | Operator::Insert(_)
             | Operator::Update(_)
             | Operator::AddColumn(_)
@@ -250,10 +311,14 @@ impl Operator {
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateTask(_)
             | Operator::DropTable(_)
             | Operator::DropView(_)
+            | Operator::DropTask(_)
             | Operator::DropIndex(_)
+            | Operator::Reindex(_)
             | Operator::Truncate(_)
+            | Operator::Vacuum(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_) => vec![],
         }
@@ -270,13 +335,17 @@ impl fmt::Display for Operator {
             Operator::Project(op) => write!(f, "{}", op),
             Operator::TableScan(op) => write!(f, "{}", op),
             Operator::FunctionScan(op) => write!(f, "{}", op),
+            Operator::ForeignScan(op) => write!(f, "{}", op),
             Operator::Sort(op) => write!(f, "{}", op),
             Operator::Limit(op) => write!(f, "{}", op),
             Operator::Values(op) => write!(f, "{}", op),
             Operator::ShowTable => write!(f, "Show Tables"),
+            Operator::ShowTableStatus => write!(f, "Show Table Status"),
             Operator::ShowView => write!(f, "Show Views"),
+            Operator::ShowTask => write!(f, "Show Tasks"),
             Operator::Explain => unreachable!(),
             Operator::Describe(op) => write!(f, "{}", op),
+            Operator::CheckTable(op) => write!(f, "{}", op),
             Operator::Insert(op) => write!(f, "{}", op),
             Operator::Update(op) => write!(f, "{}", op),
             Operator::Delete(op) => write!(f, "{}", op),
@@ -286,13 +355,20 @@ impl fmt::Display for Operator {
             Operator::CreateTable(op) => write!(f, "{}", op),
             Operator::CreateIndex(op) => write!(f, "{}", op),
             Operator::CreateView(op) => write!(f, "{}", op),
+            Operator::CreateTask(op) => write!(f, "{}", op),
             Operator::DropTable(op) => write!(f, "{}", op),
             Operator::DropView(op) => write!(f, "{}", op),
+            Operator::DropTask(op) => write!(f, "{}", op),
             Operator::DropIndex(op) => write!(f, "{}", op),
+            Operator::Reindex(op) => write!(f, "{}", op),
             Operator::Truncate(op) => write!(f, "{}", op),
+            Operator::Vacuum(op) => write!(f, "{}", op),
             Operator::CopyFromFile(op) => write!(f, "{}", op),
             Operator::CopyToFile(op) => write!(f, "{}", op),
             Operator::Union(op) => write!(f, "{}", op),
+            Operator::SharedScan(op) => write!(f, "{}", op),
+            Operator::RecursiveUnion(op) => write!(f, "{}", op),
+            Operator::WorkTableScan(op) => write!(f, "{}", op),
         }
     }
 }
@@ -303,14 +379,27 @@ impl fmt::Display for PhysicalOption {
             PhysicalOption::Dummy => write!(f, "Dummy"),
             PhysicalOption::SimpleAggregate => write!(f, "SimpleAggregate"),
             PhysicalOption::HashAggregate => write!(f, "HashAggregate"),
+            PhysicalOption::CountStar => write!(f, "CountStar"),
+            PhysicalOption::MinMaxIndex => write!(f, "MinMaxIndex"),
             PhysicalOption::Filter => write!(f, "Filter"),
+            PhysicalOption::FusedFilter => write!(f, "FusedFilter"),
             PhysicalOption::HashJoin => write!(f, "HashJoin"),
             PhysicalOption::NestLoopJoin => write!(f, "NestLoopJoin"),
             PhysicalOption::Project => write!(f, "Project"),
+            PhysicalOption::FusedProject => write!(f, "FusedProject"),
             PhysicalOption::SeqScan => write!(f, "SeqScan"),
             PhysicalOption::FunctionScan => write!(f, "FunctionScan"),
+            PhysicalOption::ForeignScan => write!(f, "ForeignScan"),
             PhysicalOption::IndexScan(index) => write!(f, "IndexScan By {}", index),
+            PhysicalOption::MultiGet(index) => write!(f, "MultiGet By {}", index),
+            PhysicalOption::SkipScan(IndexInfo {
+                meta,
+                skip_range: Some(range),
+                ..
+            }) => write!(f, "SkipScan By {} => {}", meta, range),
+            PhysicalOption::SkipScan(index) => write!(f, "SkipScan By {}", index),
             PhysicalOption::Sort => write!(f, "Sort"),
+            PhysicalOption::IncrementalSort => write!(f, "IncrementalSort"),
             PhysicalOption::Limit => write!(f, "Limit"),
             PhysicalOption::Values => write!(f, "Values"),
             PhysicalOption::Insert => write!(f, "Insert"),
@@ -325,6 +414,7 @@ impl fmt::Display for PhysicalOption {
             PhysicalOption::CopyFromFile => write!(f, "CopyFromFile"),
             PhysicalOption::CopyToFile => write!(f, "CopyToFile"),
             PhysicalOption::Analyze => write!(f, "Analyze"),
+            PhysicalOption::Custom(executor) => write!(f, "Custom {}", executor.name()),
         }
     }
 }
@@ -0,0 +1,139 @@
+use crate::errors::DatabaseError;
+use crate::serdes::{ReferenceSerialization, ReferenceTables};
+use crate::storage::{TableCache, Transaction};
+use crate::types::tuple::{SchemaRef, Tuple};
+use itertools::Itertools;
+use kite_sql_serde_macros::ReferenceSerialization;
+use parking_lot::Mutex;
+use std::fmt::Formatter;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+use std::sync::Arc;
+use std::{fmt, ptr};
+
+/// How far a recursive query runs, set by `DataBaseBuilder::max_recursion_depth` and
+/// `DataBaseBuilder::max_recursion_rows`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, ReferenceSerialization)]
+pub struct RecursionLimits {
+    /// Iterations of the recursive term giving new rows
+    pub depth: usize,
+    /// Rows of the anchor and of all the iterations
+    pub rows: usize,
+}
+
+impl Default for RecursionLimits {
+    fn default() -> Self {
+        RecursionLimits {
+            depth: 1000,
+            rows: 1_000_000,
+        }
+    }
+}
+
+/// The rows of the last iteration of a recursive query, which its recursive term reads.
+#[derive(Debug, Clone, Default)]
+pub struct WorkTable(Arc<Mutex<Vec<Tuple>>>);
+
+impl WorkTable {
+    pub(crate) fn tuples(&self) -> Vec<Tuple> {
+        self.0.lock().clone()
+    }
+
+    pub(crate) fn set(&self, tuples: Vec<Tuple>) {
+        *self.0.lock() = tuples;
+    }
+}
+
+impl PartialEq for WorkTable {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for WorkTable {}
+
+impl Hash for WorkTable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ptr::hash(Arc::as_ptr(&self.0), state);
+    }
+}
+
+// the rows belong to a single run of a statement, the scans of a decoded plan are linked again by
+// the `id` of their operators
+impl ReferenceSerialization for WorkTable {
+    fn encode<W: Write>(
+        &self,
+        _: &mut W,
+        _: bool,
+        _: &mut ReferenceTables,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn decode<T: Transaction, R: Read>(
+        _: &mut R,
+        _: Option<(&T, &TableCache)>,
+        _: &ReferenceTables,
+    ) -> Result<Self, DatabaseError> {
+        Ok(WorkTable::default())
+    }
+}
+
+/// `WITH RECURSIVE name AS (anchor UNION [ALL] recursive)`: the rows of the anchor, then those of
+/// the recursive term run on the rows of the previous iteration until it gives no new rows.
+///
+/// `UNION` drops the rows given before, `CYCLE` the rows whose `cycle` columns were given before.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct RecursiveUnionOperator {
+    pub id: usize,
+    pub name: String,
+    pub schema_ref: SchemaRef,
+    pub is_all: bool,
+    pub cycle: Vec<usize>,
+    pub limits: RecursionLimits,
+    pub work_table: WorkTable,
+}
+
+/// The reference of the recursive term of a recursive query to the query itself.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct WorkTableScanOperator {
+    pub id: usize,
+    pub name: String,
+    pub schema_ref: SchemaRef,
+    pub work_table: WorkTable,
+}
+
+impl fmt::Display for RecursiveUnionOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let schema = self
+            .schema_ref
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+
+        write!(
+            f,
+            "Recursive Union {} #{}: [{}]",
+            self.name, self.id, schema
+        )?;
+        if !self.is_all {
+            write!(f, " Distinct")?;
+        }
+        if !self.cycle.is_empty() {
+            let cycle = self
+                .cycle
+                .iter()
+                .map(|i| self.schema_ref[*i].name().to_string())
+                .join(", ");
+            write!(f, " Cycle: [{}]", cycle)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for WorkTableScanOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Work Table Scan {} #{}", self.name, self.id)
+    }
+}
@@ -0,0 +1,32 @@
+use crate::catalog::TableName;
+use crate::parser::quote_identifier;
+use crate::types::index::IndexMetaRef;
+use itertools::Itertools;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct ReindexOperator {
+    pub table_name: TableName,
+    /// Indexes whose entries are rebuilt from the table
+    pub index_metas: Vec<IndexMetaRef>,
+}
+
+impl fmt::Display for ReindexOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let indexes = self
+            .index_metas
+            .iter()
+            .map(|index_meta| quote_identifier(&index_meta.name))
+            .join(", ");
+        write!(
+            f,
+            "Reindex {} -> [{}]",
+            quote_identifier(&self.table_name),
+            indexes
+        )?;
+
+        Ok(())
+    }
+}
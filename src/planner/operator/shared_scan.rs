@@ -0,0 +1,72 @@
+use crate::errors::DatabaseError;
+use crate::serdes::{ReferenceSerialization, ReferenceTables};
+use crate::storage::{TableCache, Transaction};
+use crate::types::tuple::Tuple;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt::Formatter;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+use std::sync::{Arc, OnceLock};
+use std::{fmt, ptr};
+
+/// The rows of a subquery, read by the first of the shared scans over it and kept for the others.
+#[derive(Debug, Clone, Default)]
+pub struct SharedTuples(Arc<OnceLock<Vec<Tuple>>>);
+
+impl SharedTuples {
+    pub(crate) fn get(&self) -> Option<&Vec<Tuple>> {
+        self.0.get()
+    }
+
+    pub(crate) fn set(&self, tuples: Vec<Tuple>) {
+        let _ = self.0.set(tuples);
+    }
+}
+
+impl PartialEq for SharedTuples {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedTuples {}
+
+impl Hash for SharedTuples {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ptr::hash(Arc::as_ptr(&self.0), state);
+    }
+}
+
+// the rows belong to a single run of a statement, a decoded plan reads its subqueries again
+impl ReferenceSerialization for SharedTuples {
+    fn encode<W: Write>(
+        &self,
+        _: &mut W,
+        _: bool,
+        _: &mut ReferenceTables,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn decode<T: Transaction, R: Read>(
+        _: &mut R,
+        _: Option<(&T, &TableCache)>,
+        _: &ReferenceTables,
+    ) -> Result<Self, DatabaseError> {
+        Ok(SharedTuples::default())
+    }
+}
+
+/// A subquery repeated in a statement, whose every occurrence is kept as the child of a shared
+/// scan with the same `id` and rows.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct SharedScanOperator {
+    pub id: usize,
+    pub tuples: SharedTuples,
+}
+
+impl fmt::Display for SharedScanOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Shared Scan #{}", self.id)
+    }
+}
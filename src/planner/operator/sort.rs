@@ -26,6 +26,9 @@ pub struct SortOperator {
     pub sort_fields: Vec<SortField>,
     /// Support push down limit to sort plan.
     pub limit: Option<usize>,
+    /// Leading fields the input is ordered by already, e.g. a prefix of the primary key of the
+    /// scan below, the rows are only sorted within the runs of equal values of them
+    pub presorted: usize,
 }
 
 impl fmt::Display for SortOperator {
@@ -1,11 +1,13 @@
 use super::Operator;
 use crate::catalog::{ColumnRef, TableCatalog, TableName};
+use crate::parser::quote_identifier;
 use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::Bounds;
-use crate::types::index::IndexInfo;
+use crate::types::index::{IndexInfo, IndexMetaRef};
 use crate::types::ColumnId;
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
+use ordered_float::OrderedFloat;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Formatter;
@@ -23,9 +25,56 @@ pub struct TableScanOperator {
     // If pre_where is simple predicate, for example:  a > 1 then can calculate directly when read data.
     pub(crate) index_infos: Vec<IndexInfo>,
     pub(crate) with_pk: bool,
+    pub(crate) sample: Option<TableSample>,
+    // Support push down sort by the primary key, read from the last key down.
+    pub(crate) reverse: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, ReferenceSerialization)]
+pub enum SampleMethod {
+    // each row is kept on its own
+    Bernoulli,
+    // each block of rows adjacent in key order is kept or skipped as a whole
+    System,
+}
+
+/// `TABLESAMPLE method (percentage) [REPEATABLE (seed)]`, applied to the rows as the scan reads them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, ReferenceSerialization)]
+pub struct TableSample {
+    pub method: SampleMethod,
+    /// Percentage of the rows kept, from 0 to 100
+    pub percentage: OrderedFloat<f64>,
+    /// Seed of a sample repeated across queries
+    pub seed: Option<u64>,
 }
 
 impl TableScanOperator {
+    /// The table the scan reads.
+    pub fn table_name(&self) -> &TableName {
+        &self.table_name
+    }
+
+    /// The columns of the rows yielded by the scan, in order.
+    pub fn columns(&self) -> impl Iterator<Item = &ColumnRef> {
+        self.columns.values()
+    }
+
+    /// The offset and limit pushed down to the scan.
+    pub fn limit(&self) -> (Option<usize>, Option<usize>) {
+        self.limit
+    }
+
+    /// The index on exactly the only column read by the scan.
+    pub(crate) fn single_column_index(&self) -> Option<&IndexMetaRef> {
+        let (_, column) = self.columns.iter().exactly_one().ok()?;
+        let column_id = column.id()?;
+
+        self.index_infos
+            .iter()
+            .find(|index_info| index_info.meta.column_ids == [column_id])
+            .map(|index_info| &index_info.meta)
+    }
+
     pub fn build(
         table_name: TableName,
         table_catalog: &TableCatalog,
@@ -48,6 +97,7 @@ impl TableScanOperator {
             .map(|meta| IndexInfo {
                 meta: meta.clone(),
                 range: None,
+                skip_range: None,
             })
             .collect_vec();
 
@@ -59,25 +109,39 @@ impl TableScanOperator {
                 columns,
                 limit: (None, None),
                 with_pk,
+                sample: None,
+                reverse: false,
             }),
             Childrens::None,
         )
     }
 }
 
+impl fmt::Display for TableSample {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}({}%)", self.method, self.percentage)?;
+        if let Some(seed) = self.seed {
+            write!(f, " Repeatable({})", seed)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for TableScanOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let projection_columns = self
             .columns
             .values()
-            .map(|column| column.name().to_string())
+            .map(|column| quote_identifier(column.name()))
             .join(", ");
         let (offset, limit) = self.limit;
 
         write!(
             f,
             "TableScan {} -> [{}]",
-            self.table_name, projection_columns
+            quote_identifier(&self.table_name),
+            projection_columns
         )?;
         if let Some(limit) = limit {
             write!(f, ", Limit: {}", limit)?;
@@ -85,6 +149,12 @@ impl fmt::Display for TableScanOperator {
         if let Some(offset) = offset {
             write!(f, ", Offset: {}", offset)?;
         }
+        if let Some(sample) = &self.sample {
+            write!(f, ", Sample: {}", sample)?;
+        }
+        if self.reverse {
+            write!(f, ", Reverse")?;
+        }
 
         Ok(())
     }
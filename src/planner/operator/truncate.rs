@@ -1,4 +1,5 @@
 use crate::catalog::TableName;
+use crate::parser::quote_identifier;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -11,7 +12,7 @@ pub struct TruncateOperator {
 
 impl fmt::Display for TruncateOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Truncate {}", self.table_name)?;
+        write!(f, "Truncate {}", quote_identifier(&self.table_name))?;
 
         Ok(())
     }
@@ -1,5 +1,6 @@
 use crate::catalog::{ColumnRef, TableName};
 use crate::expression::ScalarExpression;
+use crate::parser::quote_identifier;
 use itertools::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
@@ -18,7 +19,12 @@ impl fmt::Display for UpdateOperator {
             .iter()
             .map(|(column, expr)| format!("{} -> {}", column.full_name(), expr))
             .join(", ");
-        write!(f, "Update {} set {}", self.table_name, values)?;
+        write!(
+            f,
+            "Update {} set {}",
+            quote_identifier(&self.table_name),
+            values
+        )?;
 
         Ok(())
     }
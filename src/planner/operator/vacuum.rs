@@ -0,0 +1,18 @@
+use crate::catalog::TableName;
+use crate::parser::quote_identifier;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct VacuumOperator {
+    pub table_name: TableName,
+}
+
+impl fmt::Display for VacuumOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Vacuum {}", quote_identifier(&self.table_name))?;
+
+        Ok(())
+    }
+}
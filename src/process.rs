@@ -0,0 +1,516 @@
+use crate::catalog::{ColumnCatalog, ColumnDesc, TableCatalog};
+use crate::db::CancellationToken;
+use crate::errors::DatabaseError;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::{Operator, PhysicalOption};
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use chrono::{Local, NaiveDateTime};
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use parking_lot::{Condvar, Mutex};
+use sqlparser::ast::CharLengthUnits;
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+pub(crate) const STAT_STATEMENTS: &str = "stat_statements";
+
+// the scans of a heavy statement yield the thread to the others every so many tuples read
+const HEAVY_QUERY_YIELD_INTERVAL: usize = 256;
+
+thread_local! {
+    // tuples read by the heavy statement executing on the thread, if any
+    static HEAVY_QUERY_READ: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+pub(crate) static STAT_STATEMENTS_TABLE: LazyLock<TableCatalog> = LazyLock::new(|| {
+    let column = |name: &str, ty: LogicalType| {
+        ColumnCatalog::new(
+            name.to_string(),
+            false,
+            ColumnDesc::new(ty, None, false, None).unwrap(),
+        )
+    };
+
+    TableCatalog::new(
+        Arc::new(STAT_STATEMENTS.to_string()),
+        vec![
+            column(
+                "query",
+                LogicalType::Varchar(None, CharLengthUnits::Characters),
+            ),
+            column("calls", LogicalType::Bigint),
+            column("total_ms", LogicalType::Double),
+            column("min_ms", LogicalType::Double),
+            column("max_ms", LogicalType::Double),
+            column("rows", LogicalType::Bigint),
+        ],
+    )
+    .unwrap()
+});
+
+/// The statements running on a database, listed by `SHOW PROCESSLIST` and cancelled by `KILL <id>`,
+/// and the statistics of those finished.
+pub(crate) struct ProcessList {
+    next_id: AtomicU64,
+    processes: Mutex<BTreeMap<u64, Arc<Process>>>,
+    pub(crate) statistics: StatementStatistics,
+    pub(crate) heavy_queries: HeavyQueries,
+}
+
+/// Limits the statements running at once whose plan is estimated to read at least `cost` tuples,
+/// so that a few large scans do not starve the point lookups of a mixed workload.
+///
+/// A heavy statement waits for a slot before it executes, and its scans yield the thread more often.
+/// The slots are taken by threads: a heavy statement run by a thread still reading the rows of
+/// another shares its slot, instead of waiting for a slot the thread itself holds.
+pub(crate) struct HeavyQueries {
+    cost: Option<usize>,
+    limit: usize,
+    // permits held by each thread with a slot
+    running: Mutex<HashMap<ThreadId, usize>>,
+    released: Condvar,
+}
+
+/// A permit of the slot of a thread in [`HeavyQueries`], released when dropped with the statement.
+pub(crate) struct HeavyQueryPermit<'a> {
+    heavy_queries: &'a HeavyQueries,
+    thread: ThreadId,
+}
+
+/// Marks the thread as executing a heavy statement until it is dropped.
+pub(crate) struct HeavyQueryScope(Option<usize>);
+
+/// Cumulative statistics of the statements finished, by their fingerprint, which the virtual
+/// table `stat_statements` reads.
+#[derive(Default)]
+pub(crate) struct StatementStatistics(Mutex<BTreeMap<String, StatementStatistic>>);
+
+struct StatementStatistic {
+    calls: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+    rows: u64,
+}
+
+pub(crate) struct Process {
+    pub(crate) id: u64,
+    pub(crate) sql: String,
+    pub(crate) start: NaiveDateTime,
+    started: Instant,
+    // tuples the statement returned so far
+    pub(crate) rows: AtomicUsize,
+    // waiting for a slot of the heavy queries
+    queued: AtomicBool,
+    pub(crate) cancellation_token: CancellationToken,
+}
+
+/// Keeps a statement on the [`ProcessList`] until it is dropped with the result of the statement.
+pub(crate) struct ProcessGuard<'a> {
+    list: &'a ProcessList,
+    pub(crate) process: Arc<Process>,
+    pub(crate) heavy_query: Option<HeavyQueryPermit<'a>>,
+}
+
+impl ProcessList {
+    pub(crate) fn new(heavy_query_cost: Option<usize>, max_heavy_queries: usize) -> Self {
+        ProcessList {
+            next_id: Default::default(),
+            processes: Default::default(),
+            statistics: Default::default(),
+            heavy_queries: HeavyQueries {
+                cost: heavy_query_cost,
+                limit: max_heavy_queries,
+                running: Default::default(),
+                released: Condvar::new(),
+            },
+        }
+    }
+
+    pub(crate) fn register(&self, sql: String) -> ProcessGuard<'_> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let process = Arc::new(Process {
+            id,
+            sql,
+            start: Local::now().naive_local(),
+            started: Instant::now(),
+            rows: AtomicUsize::new(0),
+            queued: AtomicBool::new(false),
+            cancellation_token: Default::default(),
+        });
+        self.processes.lock().insert(id, process.clone());
+
+        ProcessGuard {
+            list: self,
+            process,
+            heavy_query: None,
+        }
+    }
+
+    pub(crate) fn processes(&self) -> Vec<Arc<Process>> {
+        self.processes.lock().values().cloned().collect()
+    }
+
+    /// Cancels the statement, which fails before its next tuple.
+    pub(crate) fn kill(&self, id: u64) -> bool {
+        self.processes
+            .lock()
+            .get(&id)
+            .map(|process| process.cancellation_token.cancel())
+            .is_some()
+    }
+}
+
+impl Process {
+    pub(crate) fn state(&self) -> &'static str {
+        if self.cancellation_token.is_cancelled() {
+            "killed"
+        } else if self.queued.load(Ordering::Relaxed) {
+            "queued"
+        } else if self.rows.load(Ordering::Relaxed) == 0 {
+            "executing"
+        } else {
+            "sending"
+        }
+    }
+}
+
+impl Default for ProcessList {
+    fn default() -> Self {
+        ProcessList::new(None, 1)
+    }
+}
+
+impl ProcessGuard<'_> {
+    /// Waits for a slot of the heavy queries if the plan is estimated to read at least their cost.
+    ///
+    /// The wait ends early when the statement is killed, which then fails before its first tuple.
+    pub(crate) fn admit<T: Transaction>(
+        &mut self,
+        plan: &LogicalPlan,
+        loader: &StatisticMetaLoader<T>,
+    ) -> Result<(), DatabaseError> {
+        let Some(cost) = self.list.heavy_queries.cost else {
+            return Ok(());
+        };
+        if estimated_cost(plan, loader)? >= cost {
+            self.wait_heavy_query();
+        }
+        Ok(())
+    }
+
+    fn wait_heavy_query(&mut self) {
+        let heavy_queries = &self.list.heavy_queries;
+        let thread = thread::current().id();
+        let mut running = heavy_queries.running.lock();
+
+        while !running.contains_key(&thread) && running.len() >= heavy_queries.limit {
+            if self.process.cancellation_token.is_cancelled() {
+                self.process.queued.store(false, Ordering::Relaxed);
+                return;
+            }
+            self.process.queued.store(true, Ordering::Relaxed);
+            heavy_queries
+                .released
+                .wait_for(&mut running, Duration::from_millis(100));
+        }
+        self.process.queued.store(false, Ordering::Relaxed);
+        *running.entry(thread).or_default() += 1;
+        self.heavy_query = Some(HeavyQueryPermit {
+            heavy_queries,
+            thread,
+        });
+    }
+}
+
+/// Tuples the scans of the plan are estimated to read, by their limits and the statistics of `ANALYZE`.
+///
+/// A full scan of a table not analyzed is taken as heavy, while an index scan without statistics
+/// is taken as a lookup.
+pub(crate) fn estimated_cost<T: Transaction>(
+    plan: &LogicalPlan,
+    loader: &StatisticMetaLoader<T>,
+) -> Result<usize, DatabaseError> {
+    let mut cost = match (&plan.operator, &plan.physical_option) {
+        (
+            Operator::TableScan(op),
+            Some(PhysicalOption::IndexScan(index_info) | PhysicalOption::MultiGet(index_info)),
+        ) => {
+            match (
+                loader.load(&op.table_name, index_info.meta.id)?,
+                &index_info.range,
+            ) {
+                (Some(statistics_meta), Some(range)) => statistics_meta.collect_count(range)?,
+                _ => 1,
+            }
+        }
+        (Operator::TableScan(op), _) => match op.limit {
+            (offset, Some(limit)) => offset.unwrap_or(0) + limit,
+            (_, None) => {
+                let mut cost = usize::MAX;
+
+                for index_info in op.index_infos.iter() {
+                    if index_info.meta.column_ids != op.primary_keys {
+                        continue;
+                    }
+                    if let Some(statistics_meta) =
+                        loader.load(&op.table_name, index_info.meta.id)?
+                    {
+                        cost = statistics_meta.histogram().values_len();
+                    }
+                }
+                cost
+            }
+        },
+        _ => 0,
+    };
+    for child in plan.childrens.iter() {
+        cost = cost.saturating_add(estimated_cost(child, loader)?);
+    }
+    Ok(cost)
+}
+
+impl Drop for HeavyQueryPermit<'_> {
+    fn drop(&mut self) {
+        let mut running = self.heavy_queries.running.lock();
+        let permits = running.get_mut(&self.thread).unwrap();
+
+        *permits -= 1;
+        if *permits == 0 {
+            running.remove(&self.thread);
+            self.heavy_queries.released.notify_one();
+        }
+    }
+}
+
+impl HeavyQueryScope {
+    pub(crate) fn enter() -> Self {
+        HeavyQueryScope(HEAVY_QUERY_READ.replace(Some(0)))
+    }
+}
+
+impl Drop for HeavyQueryScope {
+    fn drop(&mut self) {
+        HEAVY_QUERY_READ.set(self.0);
+    }
+}
+
+/// Called by the scans for each tuple read, yields the thread now and then within a heavy statement.
+pub(crate) fn heavy_query_yield() {
+    HEAVY_QUERY_READ.with(|read| {
+        if let Some(count) = read.get() {
+            let count = count + 1;
+
+            if count % HEAVY_QUERY_YIELD_INTERVAL == 0 {
+                std::thread::yield_now();
+            }
+            read.set(Some(count));
+        }
+    });
+}
+
+impl StatementStatistics {
+    fn record(&self, sql: &str, elapsed: Duration, rows: usize) {
+        let mut statistics = self.0.lock();
+        let statistic = statistics
+            .entry(fingerprint(sql))
+            .or_insert(StatementStatistic {
+                calls: 0,
+                total: Duration::ZERO,
+                min: Duration::MAX,
+                max: Duration::ZERO,
+                rows: 0,
+            });
+        statistic.calls += 1;
+        statistic.total += elapsed;
+        statistic.min = statistic.min.min(elapsed);
+        statistic.max = statistic.max.max(elapsed);
+        statistic.rows += rows as u64;
+    }
+
+    /// The rows of `stat_statements`.
+    pub(crate) fn rows(&self) -> Vec<Vec<DataValue>> {
+        let millis =
+            |duration: Duration| DataValue::Float64(OrderedFloat(duration.as_secs_f64() * 1000.0));
+
+        self.0
+            .lock()
+            .iter()
+            .map(|(query, statistic)| {
+                vec![
+                    DataValue::Utf8 {
+                        value: query.clone(),
+                        ty: Utf8Type::Variable(None),
+                        unit: CharLengthUnits::Characters,
+                    },
+                    DataValue::Int64(statistic.calls as i64),
+                    millis(statistic.total),
+                    millis(statistic.min),
+                    millis(statistic.max),
+                    DataValue::Int64(statistic.rows as i64),
+                ]
+            })
+            .collect_vec()
+    }
+}
+
+/// The statement with its literals replaced by `?`, which the statements only differing in them share.
+fn fingerprint(sql: &str) -> String {
+    let Ok(tokens) = Tokenizer::new(&PostgreSqlDialect {}, sql).tokenize() else {
+        return sql.to_string();
+    };
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            Token::Number(..) | Token::SingleQuotedString(_) | Token::Placeholder(_) => {
+                "?".to_string()
+            }
+            token => token.to_string(),
+        })
+        .join("")
+}
+
+impl Drop for ProcessGuard<'_> {
+    fn drop(&mut self) {
+        self.list.processes.lock().remove(&self.process.id);
+        self.list.statistics.record(
+            &self.process.sql,
+            self.process.started.elapsed(),
+            self.process.rows.load(Ordering::Relaxed),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::process::{fingerprint, ProcessList};
+    use crate::types::value::DataValue;
+
+    #[test]
+    fn test_process_list() {
+        let list = ProcessList::default();
+        let guard_1 = list.register("select 1".to_string());
+        let guard_2 = list.register("select 2".to_string());
+
+        assert_eq!(
+            list.processes()
+                .iter()
+                .map(|process| (process.id, process.sql.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "select 1"), (2, "select 2")]
+        );
+        assert!(list.kill(2));
+        assert!(guard_2.process.cancellation_token.is_cancelled());
+        assert_eq!(guard_2.process.state(), "killed");
+        assert_eq!(guard_1.process.state(), "executing");
+
+        drop(guard_2);
+        assert!(!list.kill(2));
+        assert_eq!(list.processes().len(), 1);
+    }
+
+    #[test]
+    fn test_statement_statistics() {
+        assert_eq!(
+            fingerprint("SELECT * FROM t1 WHERE a = 1 AND b = 'x'"),
+            "SELECT * FROM t1 WHERE a = ? AND b = ?"
+        );
+        let list = ProcessList::default();
+
+        for sql in ["SELECT 1", "SELECT 2", "SELECT * FROM t1"] {
+            let guard = list.register(sql.to_string());
+            guard
+                .process
+                .rows
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+        let rows = list.statistics.rows();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][0].to_string(), "SELECT * FROM t1");
+        assert_eq!(rows[1][0].to_string(), "SELECT ?");
+        assert_eq!(rows[1][1], DataValue::Int64(2));
+        assert_eq!(rows[1][5], DataValue::Int64(2));
+        assert!(rows[1][3] <= rows[1][4]);
+    }
+
+    #[test]
+    fn test_heavy_queries() {
+        let list = ProcessList::new(Some(100), 1);
+        let mut guard_1 = list.register("select * from t1".to_string());
+        guard_1.wait_heavy_query();
+        assert!(guard_1.heavy_query.is_some());
+
+        let wait_queued = |list: &ProcessList| loop {
+            if let Some(process) = list
+                .processes()
+                .into_iter()
+                .find(|process| process.state() == "queued")
+            {
+                return process.id;
+            }
+            std::thread::yield_now();
+        };
+        std::thread::scope(|scope| {
+            // the statement killed stops waiting without a slot
+            let handle = scope.spawn(|| {
+                let mut guard_2 = list.register("select * from t2".to_string());
+                guard_2.wait_heavy_query();
+                guard_2.heavy_query.is_some()
+            });
+            assert!(list.kill(wait_queued(&list)));
+            assert!(!handle.join().unwrap());
+
+            let handle = scope.spawn(|| {
+                let mut guard_3 = list.register("select * from t3".to_string());
+                guard_3.wait_heavy_query();
+                guard_3.heavy_query.is_some()
+            });
+            wait_queued(&list);
+            drop(guard_1);
+            assert!(handle.join().unwrap());
+        });
+        assert!(list.heavy_queries.running.lock().is_empty());
+    }
+
+    #[test]
+    fn test_nested_heavy_queries() {
+        let list = ProcessList::new(Some(100), 1);
+        let mut guard_1 = list.register("select * from t1".to_string());
+        guard_1.wait_heavy_query();
+
+        // the thread still reading the rows of a heavy statement runs another in its slot
+        let mut guard_2 = list.register("select * from t2".to_string());
+        guard_2.wait_heavy_query();
+        assert!(guard_2.heavy_query.is_some());
+        drop(guard_1);
+
+        std::thread::scope(|scope| {
+            let handle = scope.spawn(|| {
+                let mut guard_3 = list.register("select * from t3".to_string());
+                guard_3.wait_heavy_query();
+                guard_3.heavy_query.is_some()
+            });
+            while !list
+                .processes()
+                .iter()
+                .any(|process| process.state() == "queued")
+            {
+                std::thread::yield_now();
+            }
+            // the slot is released with the last statement of the thread
+            drop(guard_2);
+            assert!(handle.join().unwrap());
+        });
+        assert!(list.heavy_queries.running.lock().is_empty());
+    }
+}
@@ -0,0 +1,261 @@
+use crate::db::{Database, State};
+use crate::errors::DatabaseError;
+use crate::replication::{
+    read_message, write_message, Change, Message, Position, Request, HEARTBEAT,
+};
+use crate::storage::rocksdb::RocksStorage;
+use crate::storage::table_codec::TableCodec;
+use parking_lot::lock_api::ArcRwLockWriteGuard;
+use parking_lot::{Mutex, RawRwLock, RwLock};
+use rocksdb::{IteratorMode, OptimisticTransactionDB, ReadOptions};
+use std::io::BufReader;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+const STOP_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Applies the changes a [`Publisher`](crate::replication::Publisher) streams to a database, see
+/// [`Database::follow`], until it is dropped.
+///
+/// The database only runs the statements that read while it follows, dropping the follower
+/// promotes it to a database that writes, e.g. once its primary is lost.
+pub struct Follower {
+    stop: Arc<AtomicBool>,
+    status: Arc<Mutex<FollowerStatus>>,
+    state: Arc<State<RocksStorage>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct FollowerStatus {
+    lsn: u64,
+    last_error: Option<String>,
+}
+
+impl Follower {
+    /// The LSN of the last change batch applied, `0` before the first one.
+    pub fn lsn(&self) -> u64 {
+        self.status.lock().lsn
+    }
+
+    /// Why the follower lost its primary the last time, it reconnects until it is dropped.
+    pub fn last_error(&self) -> Option<String> {
+        self.status.lock().last_error.clone()
+    }
+}
+
+impl Drop for Follower {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        self.state.set_replica(false);
+    }
+}
+
+impl Database<RocksStorage> {
+    /// Follows the primary publishing on `addr`, see [`Database::publish`]: the changes of its
+    /// transactions are applied here in the order of their commits, on a background thread.
+    ///
+    /// A follower that was never in sync with the primary, or fell behind its replication log,
+    /// first replaces all of its data by a snapshot of the primary.
+    pub fn follow(&self, addr: impl ToSocketAddrs) -> Result<Follower, DatabaseError> {
+        let addr = addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| DatabaseError::Replication("no address to follow".to_string()))?;
+        let position = position(&self.storage.inner)?;
+        if self.state.set_replica(true) {
+            return Err(DatabaseError::Replication(
+                "the database already follows a primary".to_string(),
+            ));
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let status = Arc::new(Mutex::new(FollowerStatus {
+            lsn: position.as_ref().map(|position| position.lsn).unwrap_or(0),
+            last_error: None,
+        }));
+        let mut replica = Replica {
+            db: self.storage.inner.clone(),
+            mdl: self.mdl.clone(),
+            state: self.state.clone(),
+            status: status.clone(),
+            position,
+        };
+
+        let handle = {
+            let stop = stop.clone();
+
+            thread::spawn(move || {
+                while !stop.load(Ordering::Acquire) {
+                    if let Err(err) = replica.sync(&addr, &stop) {
+                        replica.status.lock().last_error = Some(err.to_string());
+                        sleep_unless_stopped(RETRY_INTERVAL, &stop);
+                    }
+                }
+            })
+        };
+        Ok(Follower {
+            stop,
+            status,
+            state: self.state.clone(),
+            handle: Some(handle),
+        })
+    }
+}
+
+struct Replica {
+    db: Arc<OptimisticTransactionDB>,
+    mdl: Arc<RwLock<()>>,
+    state: Arc<State<RocksStorage>>,
+    status: Arc<Mutex<FollowerStatus>>,
+    position: Option<Position>,
+}
+
+impl Replica {
+    /// Subscribes from the position of the replica and applies what the primary sends, until the
+    /// follower is stopped or the connection fails.
+    fn sync(&mut self, addr: &SocketAddr, stop: &AtomicBool) -> Result<(), DatabaseError> {
+        let stream = TcpStream::connect_timeout(addr, CONNECT_TIMEOUT)?;
+        // an idle primary sends a heartbeat at least once in the meantime
+        stream.set_read_timeout(Some(HEARTBEAT * 3))?;
+        stream.set_nodelay(true)?;
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+
+        write_message(&mut writer, &Request::Subscribe(self.position.clone()))?;
+        // queries wait for the end of a snapshot rather than read a part of it
+        let mut snapshot: Option<(Position, ArcRwLockWriteGuard<RawRwLock, ()>)> = None;
+
+        while !stop.load(Ordering::Acquire) {
+            match read_message(&mut reader)? {
+                Message::SnapshotBegin(position) => {
+                    let guard = match snapshot.take() {
+                        Some((_, guard)) => guard,
+                        None => self.mdl.write_arc(),
+                    };
+                    self.remove_all()?;
+                    snapshot = Some((position, guard));
+                }
+                Message::SnapshotChunk(pairs) => {
+                    if snapshot.is_none() {
+                        return Err(DatabaseError::Replication(
+                            "snapshot chunk out of a snapshot".to_string(),
+                        ));
+                    }
+                    let tx = self.db.transaction();
+                    for (key, value) in pairs {
+                        tx.put(key, value)?;
+                    }
+                    tx.commit()?;
+                }
+                Message::SnapshotEnd => {
+                    let Some((position, _guard)) = snapshot.take() else {
+                        return Err(DatabaseError::Replication(
+                            "snapshot end out of a snapshot".to_string(),
+                        ));
+                    };
+                    self.apply(position, &[])?;
+                }
+                Message::Batch(batch) => {
+                    let position = match &self.position {
+                        Some(position) if snapshot.is_none() && batch.lsn == position.lsn + 1 => {
+                            Position {
+                                epoch: position.epoch.clone(),
+                                lsn: batch.lsn,
+                            }
+                        }
+                        _ => {
+                            return Err(DatabaseError::Replication(format!(
+                                "change batch {} out of order",
+                                batch.lsn
+                            )))
+                        }
+                    };
+                    let _guard = self.mdl.write_arc();
+                    self.apply(position, &batch.changes)?;
+                }
+                Message::Heartbeat => (),
+            }
+            self.status.lock().last_error = None;
+        }
+        Ok(())
+    }
+
+    /// Applies `changes` and moves the replica to `position` in one transaction.
+    fn apply(&mut self, position: Position, changes: &[Change]) -> Result<(), DatabaseError> {
+        let table_codec = TableCodec::default();
+        let tx = self.db.transaction();
+
+        for change in changes {
+            match change {
+                Change::Put(key, value) => tx.put(key, value)?,
+                Change::Delete(key) => tx.delete(key)?,
+            }
+        }
+        let (key, value) =
+            table_codec.encode_replication_position(&position.epoch, position.lsn)?;
+        tx.put(&key, &value)?;
+        tx.commit()?;
+
+        self.clear_caches();
+        self.status.lock().lsn = position.lsn;
+        self.position = Some(position);
+        Ok(())
+    }
+
+    /// Removes all the keys and the position of the replica, before a snapshot.
+    fn remove_all(&mut self) -> Result<(), DatabaseError> {
+        let mut options = ReadOptions::default();
+        options.set_total_order_seek(true);
+        let tx = self.db.transaction();
+
+        let keys = tx
+            .iterator_opt(IteratorMode::Start, options)
+            .map(|item| item.map(|(key, _)| key))
+            .collect::<Result<Vec<_>, _>>()?;
+        for key in keys {
+            tx.delete(key)?;
+        }
+        tx.commit()?;
+
+        self.clear_caches();
+        self.position = None;
+        Ok(())
+    }
+
+    // the changes may have created, altered or dropped any table or view
+    fn clear_caches(&self) {
+        self.state.meta_cache().clear();
+        self.state.table_cache().clear();
+        self.state.view_cache().clear();
+    }
+}
+
+fn position(db: &OptimisticTransactionDB) -> Result<Option<Position>, DatabaseError> {
+    let table_codec = TableCodec::default();
+    let key = table_codec.encode_replication_position_key();
+
+    db.transaction()
+        .get(&key)?
+        .map(|bytes| {
+            TableCodec::decode_replication_position(&bytes)
+                .map(|(epoch, lsn)| Position { epoch, lsn })
+        })
+        .transpose()
+}
+
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+
+    while !stop.load(Ordering::Acquire) && Instant::now() < deadline {
+        thread::sleep(STOP_INTERVAL);
+    }
+}
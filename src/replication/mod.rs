@@ -0,0 +1,339 @@
+//! Logical replication: a primary ships its committed changes to followers over TCP.
+//!
+//! With [`DataBaseBuilder::replication_log`](crate::db::DataBaseBuilder::replication_log), every
+//! transaction of the primary that wrote something appends its puts and deletes to a
+//! [`ChangeLog`] as one [`ChangeBatch`], in the order of their commits. [`Publisher`] streams the
+//! batches to the followers, which apply each of them as a transaction, see [`Follower`].
+//!
+//! A follower that connects for the first time, fell behind the batches the log keeps or follows
+//! a primary that restarted since (a new epoch) first receives a snapshot of all the keys.
+pub mod follower;
+pub mod publisher;
+
+pub use follower::Follower;
+pub use publisher::Publisher;
+
+use crate::errors::DatabaseError;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use parking_lot::{Condvar, Mutex};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use ulid::Ulid;
+
+/// How often the publisher tells an idle follower that it is alive.
+pub(crate) const HEARTBEAT: Duration = Duration::from_secs(1);
+/// The longest message read from a peer, so that a corrupt or hostile length does not allocate
+/// the memory it claims.
+pub(crate) const MAX_MESSAGE_LEN: usize = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Change {
+    Put(Vec<u8>, Vec<u8>),
+    Delete(Vec<u8>),
+}
+
+/// The changes of a committed transaction, numbered by the order of the commits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeBatch {
+    pub lsn: u64,
+    pub changes: Vec<Change>,
+}
+
+/// Keeps the last `capacity` change batches of the primary in memory.
+///
+/// The LSNs restart with the epoch, which is new each time the database is opened.
+pub(crate) struct ChangeLog {
+    epoch: String,
+    capacity: usize,
+    inner: Mutex<ChangeLogInner>,
+    appended: Condvar,
+}
+
+struct ChangeLogInner {
+    last_lsn: u64,
+    batches: VecDeque<Arc<ChangeBatch>>,
+}
+
+impl ChangeLog {
+    pub(crate) fn new(capacity: usize) -> Self {
+        ChangeLog {
+            epoch: Ulid::new().to_string(),
+            capacity,
+            inner: Mutex::new(ChangeLogInner {
+                last_lsn: 0,
+                batches: VecDeque::with_capacity(capacity),
+            }),
+            appended: Condvar::new(),
+        }
+    }
+
+    pub(crate) fn epoch(&self) -> &str {
+        &self.epoch
+    }
+
+    #[cfg(test)]
+    pub(crate) fn last_lsn(&self) -> u64 {
+        self.inner.lock().last_lsn
+    }
+
+    /// Runs `commit` and appends `changes` if it succeeds.
+    ///
+    /// The log is locked across both, so that the batches are in the order of the commits and a
+    /// snapshot never sees a commit without its batch.
+    pub(crate) fn commit<F>(&self, changes: Vec<Change>, commit: F) -> Result<(), DatabaseError>
+    where
+        F: FnOnce() -> Result<(), DatabaseError>,
+    {
+        if changes.is_empty() {
+            return commit();
+        }
+        let mut inner = self.inner.lock();
+        commit()?;
+
+        inner.last_lsn += 1;
+        let batch = Arc::new(ChangeBatch {
+            lsn: inner.last_lsn,
+            changes,
+        });
+        if inner.batches.len() == self.capacity {
+            inner.batches.pop_front();
+        }
+        inner.batches.push_back(batch);
+        self.appended.notify_all();
+
+        Ok(())
+    }
+
+    /// Runs `f` between two commits, e.g. to take a snapshot of the storage, and returns the LSN
+    /// of the last batch it sees.
+    pub(crate) fn snapshot<T, F: FnOnce() -> T>(&self, f: F) -> (T, u64) {
+        let inner = self.inner.lock();
+
+        (f(), inner.last_lsn)
+    }
+
+    /// The batches after `lsn`, waiting up to `timeout` for the next one if there are none yet.
+    ///
+    /// Returns `None` if the log no longer keeps all of them.
+    pub(crate) fn batches_after(
+        &self,
+        lsn: u64,
+        timeout: Duration,
+    ) -> Option<Vec<Arc<ChangeBatch>>> {
+        let mut inner = self.inner.lock();
+
+        if inner.last_lsn == lsn {
+            let _ = self.appended.wait_for(&mut inner, timeout);
+        }
+        let first_lsn = inner
+            .batches
+            .front()
+            .map(|batch| batch.lsn)
+            .unwrap_or(inner.last_lsn + 1);
+        if lsn > inner.last_lsn || lsn + 1 < first_lsn {
+            return None;
+        }
+        Some(
+            inner
+                .batches
+                .iter()
+                .filter(|batch| batch.lsn > lsn)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+/// Where a follower stands: the last batch it applied of an epoch of the primary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Position {
+    pub(crate) epoch: String,
+    pub(crate) lsn: u64,
+}
+
+/// Sent by a follower once connected.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Request {
+    Subscribe(Option<Position>),
+}
+
+/// Sent by the publisher to a follower.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Message {
+    /// The keys that follow replace all the keys of the follower, up to the batch of `lsn`.
+    SnapshotBegin(Position),
+    SnapshotChunk(Vec<(Vec<u8>, Vec<u8>)>),
+    SnapshotEnd,
+    Batch(Arc<ChangeBatch>),
+    Heartbeat,
+}
+
+/// Writes `message` as its length in 4 bytes followed by its bincode encoding.
+pub(crate) fn write_message<W: Write, T: Serialize>(
+    writer: &mut W,
+    message: &T,
+) -> Result<(), DatabaseError> {
+    let bytes = bincode::serialize(message)?;
+    if bytes.len() > MAX_MESSAGE_LEN {
+        return Err(DatabaseError::Replication(format!(
+            "message of {} bytes is longer than {MAX_MESSAGE_LEN} bytes",
+            bytes.len()
+        )));
+    }
+
+    writer.write_u32::<BigEndian>(bytes.len() as u32)?;
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub(crate) fn read_message<R: Read, T: DeserializeOwned>(
+    reader: &mut R,
+) -> Result<T, DatabaseError> {
+    let len = reader.read_u32::<BigEndian>()? as usize;
+    if len > MAX_MESSAGE_LEN {
+        return Err(DatabaseError::Replication(format!(
+            "message of {len} bytes is longer than {MAX_MESSAGE_LEN} bytes"
+        )));
+    }
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, Database, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::replication::{read_message, Change, ChangeLog, Message};
+    use crate::storage::rocksdb::RocksStorage;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    fn rows(database: &Database<RocksStorage>) -> Result<usize, DatabaseError> {
+        let mut iter = database.run("select * from t1")?;
+        let rows = iter.by_ref().collect::<Result<Vec<_>, _>>()?.len();
+        iter.done()?;
+
+        Ok(rows)
+    }
+
+    fn wait_until<F: FnMut() -> bool>(mut f: F) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        while !f() {
+            assert!(Instant::now() < deadline, "the follower did not catch up");
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_change_log() {
+        let log = ChangeLog::new(2);
+        let put = |key: &str| vec![Change::Put(key.as_bytes().to_vec(), vec![])];
+
+        log.commit(vec![], || Ok(())).unwrap();
+        assert_eq!(log.last_lsn(), 0);
+        assert_eq!(log.batches_after(0, Duration::ZERO), Some(vec![]));
+
+        for key in ["a", "b", "c"] {
+            log.commit(put(key), || Ok(())).unwrap();
+        }
+        assert!(log
+            .commit(put("d"), || Err(crate::errors::DatabaseError::Cancelled))
+            .is_err());
+        assert_eq!(log.last_lsn(), 3);
+
+        // the batch of lsn 1 is no longer kept
+        assert_eq!(log.batches_after(0, Duration::ZERO), None);
+        let batches = log.batches_after(1, Duration::ZERO).unwrap();
+        assert_eq!(
+            batches.iter().map(|batch| batch.lsn).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(batches[1].changes, put("c"));
+        assert_eq!(log.batches_after(3, Duration::ZERO), Some(vec![]));
+        assert_eq!(log.batches_after(4, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn test_message_too_long() {
+        let mut bytes = u32::MAX.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&[0; 16]);
+
+        assert!(matches!(
+            read_message::<_, Message>(&mut bytes.as_slice()),
+            Err(DatabaseError::Replication(_))
+        ));
+    }
+
+    #[test]
+    fn test_follow_primary() -> Result<(), DatabaseError> {
+        let primary_dir = TempDir::new().expect("unable to create temporary working directory");
+        let follower_dir = TempDir::new().expect("unable to create temporary working directory");
+        let primary = DataBaseBuilder::path(primary_dir.path())
+            .replication_log(2)
+            .build()?;
+        let replica = DataBaseBuilder::path(follower_dir.path()).build()?;
+        let last_lsn = || primary.storage.change_log.as_ref().unwrap().last_lsn();
+
+        primary
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        primary
+            .run("insert into t1 values (0, 0), (1, 1)")?
+            .done()?;
+        assert!(matches!(
+            replica.publish("127.0.0.1:0"),
+            Err(DatabaseError::ReplicationLogNotFound)
+        ));
+        let publisher = primary.publish("127.0.0.1:0")?;
+
+        // catches up by a snapshot, then by the change batches
+        let follower = replica.follow(publisher.local_addr())?;
+        wait_until(|| follower.lsn() == last_lsn());
+        assert_eq!(rows(&replica)?, 2);
+
+        for i in 2..6 {
+            primary
+                .run(format!("insert into t1 values ({i}, {i})"))?
+                .done()?;
+        }
+        primary.run("delete from t1 where a = 0")?.done()?;
+        primary.run("alter table t1 add column c int")?.done()?;
+        wait_until(|| follower.lsn() == last_lsn());
+        assert_eq!(rows(&replica)?, 5);
+        assert_eq!(
+            replica
+                .run("select c from t1 where a = 5")?
+                .next()
+                .unwrap()?
+                .values
+                .len(),
+            1
+        );
+        assert!(follower.last_error().is_none());
+
+        assert!(matches!(
+            replica.run("insert into t1 values (6, 6, 6)"),
+            Err(DatabaseError::ReadOnlyReplica)
+        ));
+        assert!(matches!(
+            replica.new_transaction()?.run("delete from t1"),
+            Err(DatabaseError::ReadOnlyReplica)
+        ));
+
+        // promotes the follower
+        drop(follower);
+        replica.run("insert into t1 values (6, 6, 6)")?.done()?;
+        assert_eq!(rows(&replica)?, 6);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::db::Database;
+use crate::errors::DatabaseError;
+use crate::replication::{
+    read_message, write_message, ChangeLog, Message, Position, Request, HEARTBEAT,
+};
+use crate::storage::rocksdb::RocksStorage;
+use crate::storage::table_codec::TableCodec;
+use rocksdb::{IteratorMode, OptimisticTransactionDB, ReadOptions};
+use std::io::{BufWriter, Write};
+use std::mem;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+const SNAPSHOT_CHUNK: usize = 1024;
+
+/// Streams the committed changes of a primary to the followers connecting to its address, see
+/// [`Database::publish`], until it is dropped.
+pub struct Publisher {
+    local_addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Publisher {
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for Publisher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Database<RocksStorage> {
+    /// Listens on `addr` for the followers of [`Database::follow`], each of them on its own
+    /// thread. The database must keep a [`replication_log`](crate::db::DataBaseBuilder::replication_log).
+    pub fn publish(&self, addr: impl ToSocketAddrs) -> Result<Publisher, DatabaseError> {
+        let change_log = self
+            .storage
+            .change_log
+            .clone()
+            .ok_or(DatabaseError::ReplicationLogNotFound)?;
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let db = self.storage.inner.clone();
+            let stop = stop.clone();
+            thread::spawn(move || listen(listener, db, change_log, stop))
+        };
+        Ok(Publisher {
+            local_addr,
+            stop,
+            handle: Some(handle),
+        })
+    }
+}
+
+fn listen(
+    listener: TcpListener,
+    db: Arc<OptimisticTransactionDB>,
+    change_log: Arc<ChangeLog>,
+    stop: Arc<AtomicBool>,
+) {
+    let mut followers: Vec<JoinHandle<()>> = Vec::new();
+
+    while !stop.load(Ordering::Acquire) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let (db, change_log, stop) = (db.clone(), change_log.clone(), stop.clone());
+
+                followers.push(thread::spawn(move || {
+                    // the follower reconnects if its stream fails
+                    let _ = serve(stream, &db, &change_log, &stop);
+                }));
+            }
+            // nobody is connecting, or the connection was reset before it was accepted
+            Err(_) => thread::sleep(ACCEPT_INTERVAL),
+        }
+        followers.retain(|follower| !follower.is_finished());
+    }
+    for follower in followers {
+        let _ = follower.join();
+    }
+}
+
+/// Sends a follower the batches after its position, or a snapshot first if the log no longer
+/// keeps them all.
+fn serve(
+    stream: TcpStream,
+    db: &OptimisticTransactionDB,
+    change_log: &ChangeLog,
+    stop: &AtomicBool,
+) -> Result<(), DatabaseError> {
+    stream.set_nonblocking(false)?;
+    stream.set_nodelay(true)?;
+    let mut reader = stream.try_clone()?;
+    let mut writer = BufWriter::new(stream);
+
+    let Request::Subscribe(position) = read_message(&mut reader)?;
+    let mut lsn = match position {
+        Some(position) if position.epoch == change_log.epoch() => position.lsn,
+        _ => send_snapshot(&mut writer, db, change_log)?,
+    };
+    while !stop.load(Ordering::Acquire) {
+        let Some(batches) = change_log.batches_after(lsn, HEARTBEAT) else {
+            lsn = send_snapshot(&mut writer, db, change_log)?;
+            continue;
+        };
+        if batches.is_empty() {
+            write_message(&mut writer, &Message::Heartbeat)?;
+        }
+        for batch in batches {
+            lsn = batch.lsn;
+            write_message(&mut writer, &Message::Batch(batch))?;
+        }
+    }
+    Ok(())
+}
+
+/// Sends all the keys as of the last batch of the log, and returns its LSN.
+fn send_snapshot<W: Write>(
+    writer: &mut W,
+    db: &OptimisticTransactionDB,
+    change_log: &ChangeLog,
+) -> Result<u64, DatabaseError> {
+    let (snapshot, lsn) = change_log.snapshot(|| db.snapshot());
+    let table_codec = TableCodec::default();
+    let position_key = table_codec.encode_replication_position_key();
+
+    write_message(
+        writer,
+        &Message::SnapshotBegin(Position {
+            epoch: change_log.epoch().to_string(),
+            lsn,
+        }),
+    )?;
+    let mut options = ReadOptions::default();
+    options.set_total_order_seek(true);
+
+    let mut chunk = Vec::with_capacity(SNAPSHOT_CHUNK);
+    for item in snapshot.iterator_opt(IteratorMode::Start, options) {
+        let (key, value) = item?;
+
+        // the position of a primary that followed another one before
+        if key.starts_with(&position_key) {
+            continue;
+        }
+        chunk.push((key.into_vec(), value.into_vec()));
+
+        if chunk.len() == SNAPSHOT_CHUNK {
+            write_message(writer, &Message::SnapshotChunk(mem::take(&mut chunk)))?;
+        }
+    }
+    if !chunk.is_empty() {
+        write_message(writer, &Message::SnapshotChunk(chunk))?;
+    }
+    write_message(writer, &Message::SnapshotEnd)?;
+
+    Ok(lsn)
+}
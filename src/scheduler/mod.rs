@@ -0,0 +1,78 @@
+pub mod schedule;
+
+use crate::db::{Database, ResultIter};
+use crate::errors::DatabaseError;
+use crate::scheduler::schedule::Schedule;
+use crate::storage::{Storage, Transaction};
+use chrono::{Local, NaiveDateTime, Timelike};
+use std::sync::mpsc;
+use std::sync::mpsc::{RecvTimeoutError, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+const TICK: Duration = Duration::from_secs(1);
+
+/// Runs the tasks created with `CREATE TASK` on a background thread,
+/// which is stopped when the owning [`Database`] is dropped.
+pub(crate) struct Scheduler {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Scheduler {
+    pub(crate) fn start<S: Storage + Send + Sync + 'static>(database: Database<S>) -> Self {
+        let (stop, receiver) = mpsc::channel::<()>();
+        let handle = thread::spawn(move || {
+            let mut last_minute = None;
+
+            // the sender is dropped with the scheduler, which disconnects the channel
+            while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(TICK) {
+                let now = Local::now().naive_local();
+                let minute = now.with_second(0).and_then(|now| now.with_nanosecond(0));
+
+                if minute != last_minute {
+                    last_minute = minute;
+                    // A failed run is retried at the next time of its schedule
+                    let _ = run_due(&database, &now);
+                }
+            }
+        });
+
+        Scheduler {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Runs each task whose schedule matches `now`, one after another and each in its own transaction.
+///
+/// Returns the names of the tasks that ran, with their results.
+#[allow(clippy::type_complexity)]
+pub(crate) fn run_due<S: Storage>(
+    database: &Database<S>,
+    now: &NaiveDateTime,
+) -> Result<Vec<(String, Result<(), DatabaseError>)>, DatabaseError> {
+    let tasks = database.storage.transaction()?.tasks()?;
+    let mut results = Vec::new();
+
+    for task in tasks {
+        if !task.schedule.parse::<Schedule>()?.matches(now) {
+            continue;
+        }
+        let result = database.run(&task.sql).and_then(|iter| iter.done());
+
+        results.push((task.name, result));
+    }
+    Ok(results)
+}
@@ -0,0 +1,173 @@
+use crate::errors::DatabaseError;
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use std::str::FromStr;
+
+const FIELDS: [(&str, u32, u32); 5] = [
+    ("minute", 0, 59),
+    ("hour", 0, 23),
+    ("day of month", 1, 31),
+    ("month", 1, 12),
+    ("day of week", 0, 7),
+];
+
+/// A cron expression of the five fields `minute hour day-of-month month day-of-week`,
+/// each of them `*`, `n`, `a-b`, a list of those separated by `,`, optionally stepped by `/n`.
+///
+/// `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` are accepted as shorthands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    // bit `n` is set when the value `n` of the field matches
+    fields: [u64; 5],
+    // as in cron, a time matches either of the days once both of them are restricted
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl Schedule {
+    /// Whether the minute of `time` is one the schedule runs at.
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        let is_set = |field: usize, value: u32| self.fields[field] & (1 << value) != 0;
+
+        let day_of_month = is_set(2, time.day());
+        let day_of_week = is_set(4, time.weekday().num_days_from_sunday());
+        let day = match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => day_of_week,
+            (false, true) => day_of_month,
+            (false, false) => day_of_month || day_of_week,
+        };
+
+        day && is_set(0, time.minute()) && is_set(1, time.hour()) && is_set(3, time.month())
+    }
+
+    fn parse_field(field: &str, (name, min, max): (&str, u32, u32)) -> Result<u64, DatabaseError> {
+        let invalid = || DatabaseError::InvalidSchedule(format!("{}: {}", name, field));
+        let parse = |value: &str| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(invalid)
+        };
+        let mut bits = 0;
+
+        for item in field.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, parse(step).ok().filter(|step| *step > 0)),
+                None => (item, Some(1)),
+            };
+            let step = step.ok_or_else(invalid)?;
+            let (start, end) = match range {
+                "*" => (min, max),
+                range => match range.split_once('-') {
+                    Some((start, end)) => (parse(start)?, parse(end)?),
+                    None => {
+                        let start = parse(range)?;
+                        (start, if step > 1 { max } else { start })
+                    }
+                },
+            };
+            if start > end {
+                return Err(invalid());
+            }
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+        Ok(bits)
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expr = match s.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expr => expr,
+        };
+        let items = expr.split_whitespace().collect::<Vec<_>>();
+
+        if items.len() != FIELDS.len() {
+            return Err(DatabaseError::InvalidSchedule(format!(
+                "expected {} fields, got: {}",
+                FIELDS.len(),
+                s
+            )));
+        }
+        let mut fields = [0; 5];
+        for (i, item) in items.iter().enumerate() {
+            fields[i] = Self::parse_field(item, FIELDS[i])?;
+        }
+        // Sunday is both 0 and 7
+        if fields[4] & (1 << 7) != 0 {
+            fields[4] |= 1;
+        }
+
+        Ok(Schedule {
+            fields,
+            any_day_of_month: items[2] == "*",
+            any_day_of_week: items[4] == "*",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::DatabaseError;
+    use crate::scheduler::schedule::Schedule;
+    use chrono::NaiveDateTime;
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_schedule() -> Result<(), DatabaseError> {
+        let schedule = "*/15 2-4 * * *".parse::<Schedule>()?;
+        assert!(schedule.matches(&time("2024-10-10 02:00")));
+        assert!(schedule.matches(&time("2024-10-10 04:45")));
+        assert!(!schedule.matches(&time("2024-10-10 04:50")));
+        assert!(!schedule.matches(&time("2024-10-10 05:00")));
+
+        let schedule = "30 12 1,15 * *".parse::<Schedule>()?;
+        assert!(schedule.matches(&time("2024-10-15 12:30")));
+        assert!(!schedule.matches(&time("2024-10-16 12:30")));
+
+        // 2024-10-13 is a Sunday, either of the restricted days matches
+        let schedule = "0 0 1 * 7".parse::<Schedule>()?;
+        assert!(schedule.matches(&time("2024-10-13 00:00")));
+        assert!(schedule.matches(&time("2024-11-01 00:00")));
+        assert!(!schedule.matches(&time("2024-10-14 00:00")));
+
+        let schedule = "@hourly".parse::<Schedule>()?;
+        assert!(schedule.matches(&time("2024-10-10 07:00")));
+        assert!(!schedule.matches(&time("2024-10-10 07:01")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        for schedule in [
+            "* * * *",
+            "60 * * * *",
+            "5-1 * * * *",
+            "*/0 * * * *",
+            "a * * * *",
+        ] {
+            assert!(
+                matches!(
+                    schedule.parse::<Schedule>(),
+                    Err(DatabaseError::InvalidSchedule(_))
+                ),
+                "{}",
+                schedule
+            );
+        }
+    }
+}
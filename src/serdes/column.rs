@@ -51,7 +51,7 @@ impl ReferenceSerialization for ColumnRef {
         {
             let table = transaction
                 .table(table_cache, table_name.clone())?
-                .ok_or(DatabaseError::TableNotFound)?;
+                .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
             let column = table
                 .get_column_by_id(column_id)
                 .ok_or(DatabaseError::InvalidColumn(format!(
@@ -163,12 +163,12 @@ pub(crate) mod test {
     fn test_column_serialization() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let meta_cache = StatisticsMetaCache::new(4, 1, RandomState::new())?;
 
         let table_name = Arc::new("t1".to_string());
-        build_table(&table_cache, &mut transaction)?;
+        build_table(&table_cache, &transaction)?;
 
         let mut cursor = Cursor::new(Vec::new());
         let mut reference_tables = ReferenceTables::new();
@@ -182,7 +182,7 @@ pub(crate) mod test {
         {
             let ref_column = ColumnRef(Arc::new(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c3".to_string(),
+                    name: "c3".into(),
                     relation: ColumnRelation::Table {
                         column_id: c3_column_id,
                         table_name: table_name.clone(),
@@ -219,7 +219,7 @@ pub(crate) mod test {
         {
             let not_ref_column = ColumnRef(Arc::new(ColumnCatalog::direct_new(
                 ColumnSummary {
-                    name: "c3".to_string(),
+                    name: "c3".into(),
                     relation: ColumnRelation::None,
                 },
                 false,
@@ -252,7 +252,7 @@ pub(crate) mod test {
         let mut cursor = Cursor::new(Vec::new());
         let mut reference_tables = ReferenceTables::new();
         let summary = ColumnSummary {
-            name: "c1".to_string(),
+            name: "c1".into(),
             relation: ColumnRelation::Table {
                 column_id: Ulid::new(),
                 table_name: Arc::new("t1".to_string()),
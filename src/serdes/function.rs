@@ -1,6 +1,128 @@
+use crate::errors::DatabaseError;
+use crate::execution::custom::ArcCustomExecutor;
 use crate::expression::function::scala::ArcScalarFunctionImpl;
 use crate::expression::function::table::ArcTableFunctionImpl;
+use crate::fdw::ArcForeignDataWrapper;
 use crate::implement_serialization_by_bincode;
+use crate::serdes::{ReferenceSerialization, ReferenceTables};
+use crate::storage::{TableCache, Transaction};
+use std::io::{Read, Write};
 
-implement_serialization_by_bincode!(ArcScalarFunctionImpl);
 implement_serialization_by_bincode!(ArcTableFunctionImpl);
+implement_serialization_by_bincode!(ArcForeignDataWrapper);
+implement_serialization_by_bincode!(ArcCustomExecutor);
+
+/// Marks a scalar function written with its name ahead: the implementation alone starts with the
+/// number of its fields, which is never this.
+const NAME_AHEAD: [u8; 8] = u64::MAX.to_le_bytes();
+
+/// The name of a scalar function is written ahead of its implementation, so that views and
+/// defaults calling a function unknown to this process, e.g. a UDF the embedder no longer
+/// registers, fail to decode with the name of the function instead of the tag of the payload.
+/// Those written before hold the implementation alone, which is still read.
+impl ReferenceSerialization for ArcScalarFunctionImpl {
+    fn encode<W: Write>(
+        &self,
+        writer: &mut W,
+        is_direct: bool,
+        reference_tables: &mut ReferenceTables,
+    ) -> Result<(), DatabaseError> {
+        writer.write_all(&NAME_AHEAD)?;
+        self.summary()
+            .name
+            .encode(writer, is_direct, reference_tables)?;
+        bincode::serialize_into(writer, self)?;
+
+        Ok(())
+    }
+
+    fn decode<T: Transaction, R: Read>(
+        reader: &mut R,
+        drive: Option<(&T, &TableCache)>,
+        reference_tables: &ReferenceTables,
+    ) -> Result<Self, DatabaseError> {
+        let mut prefix = [0; 8];
+        reader.read_exact(&mut prefix)?;
+
+        if prefix != NAME_AHEAD {
+            return Ok(bincode::deserialize_from(prefix.as_slice().chain(reader))?);
+        }
+        let name = String::decode(reader, drive, reference_tables)?;
+
+        bincode::deserialize_from(reader).map_err(|err| match *err {
+            // the tag of the implementation is not registered
+            bincode::ErrorKind::Custom(message) if message.starts_with("unknown variant") => {
+                DatabaseError::FunctionNotRegistered(name)
+            }
+            err => DatabaseError::Bincode(Box::new(err)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::expression::function::scala::{
+        ArcScalarFunctionImpl, ScalarFunction, ScalarFunctionImpl,
+    };
+    use crate::expression::ScalarExpression;
+    use crate::function::lower::Lower;
+    use crate::serdes::{ReferenceSerialization, ReferenceTables};
+    use crate::storage::rocksdb::RocksTransaction;
+    use crate::types::value::DataValue;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_unregistered_function() -> Result<(), DatabaseError> {
+        let source = ScalarExpression::ScalaFunction(ScalarFunction {
+            args: vec![ScalarExpression::Constant(DataValue::Int32(1))],
+            inner: ArcScalarFunctionImpl(Lower::new()),
+        });
+        let mut reference_tables = ReferenceTables::new();
+        let mut bytes = Vec::new();
+        source.encode(&mut bytes, false, &mut reference_tables)?;
+
+        let decoded = ScalarExpression::decode::<RocksTransaction, _>(
+            &mut Cursor::new(&bytes),
+            None,
+            &reference_tables,
+        )?;
+        assert_eq!(source, decoded);
+
+        let tag = bytes
+            .windows(5)
+            .position(|window| window == b"Lower")
+            .unwrap();
+        // a truncated implementation is not taken for an unknown function
+        let result = ScalarExpression::decode::<RocksTransaction, _>(
+            &mut Cursor::new(&bytes[..tag + 5]),
+            None,
+            &reference_tables,
+        );
+        assert!(matches!(result, Err(DatabaseError::Bincode(_))));
+
+        // the implementation of the function is unknown to this process
+        bytes[tag..tag + 5].copy_from_slice(b"Lowex");
+        let result = ScalarExpression::decode::<RocksTransaction, _>(
+            &mut Cursor::new(&bytes),
+            None,
+            &reference_tables,
+        );
+        assert!(matches!(
+            result,
+            Err(DatabaseError::FunctionNotRegistered(name)) if name == "lower"
+        ));
+
+        // written before the name was, with the implementation alone
+        let mut bytes = Vec::new();
+        bincode::serialize_into(&mut bytes, &ArcScalarFunctionImpl(Lower::new()))?;
+        let decoded = ArcScalarFunctionImpl::decode::<RocksTransaction, _>(
+            &mut Cursor::new(&bytes),
+            None,
+            &reference_tables,
+        )?;
+        assert_eq!(decoded.summary(), Lower::new().summary());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::errors::DatabaseError;
 use crate::serdes::{ReferenceSerialization, ReferenceTables};
 use crate::storage::{TableCache, Transaction};
+use ordered_float::OrderedFloat;
 use std::io::Read;
 use std::io::Write;
 use std::mem::size_of;
@@ -65,6 +66,25 @@ impl ReferenceSerialization for usize {
     }
 }
 
+impl ReferenceSerialization for OrderedFloat<f64> {
+    fn encode<W: Write>(
+        &self,
+        writer: &mut W,
+        is_direct: bool,
+        reference_tables: &mut ReferenceTables,
+    ) -> Result<(), DatabaseError> {
+        self.0.encode(writer, is_direct, reference_tables)
+    }
+
+    fn decode<T: Transaction, R: Read>(
+        reader: &mut R,
+        drive: Option<(&T, &TableCache)>,
+        reference_tables: &ReferenceTables,
+    ) -> Result<Self, DatabaseError> {
+        Ok(OrderedFloat(f64::decode(reader, drive, reference_tables)?))
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use crate::errors::DatabaseError;
@@ -1,13 +1,42 @@
+use crate::errors::DatabaseError;
 use crate::implement_serialization_by_bincode;
+use crate::serdes::{ReferenceSerialization, ReferenceTables};
+use crate::storage::{TableCache, Transaction};
+use std::io::{Read, Write};
+use std::sync::Arc;
 
 implement_serialization_by_bincode!(String);
 
+impl ReferenceSerialization for Arc<str> {
+    fn encode<W: Write>(
+        &self,
+        writer: &mut W,
+        _: bool,
+        _: &mut ReferenceTables,
+    ) -> Result<(), DatabaseError> {
+        bincode::serialize_into(writer, self.as_ref())?;
+
+        Ok(())
+    }
+
+    fn decode<T: Transaction, R: Read>(
+        reader: &mut R,
+        _: Option<(&T, &TableCache)>,
+        _: &ReferenceTables,
+    ) -> Result<Self, DatabaseError> {
+        let string: String = bincode::deserialize_from(reader)?;
+
+        Ok(Arc::from(string))
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use crate::errors::DatabaseError;
     use crate::serdes::{ReferenceSerialization, ReferenceTables};
     use crate::storage::rocksdb::RocksTransaction;
     use std::io::{Cursor, Seek, SeekFrom};
+    use std::sync::Arc;
 
     #[test]
     fn test_serialization() -> Result<(), DatabaseError> {
@@ -23,6 +52,15 @@ pub(crate) mod test {
             source
         );
 
+        let mut bytes = Vec::new();
+        let mut cursor = Cursor::new(&mut bytes);
+        let source: Arc<str> = Arc::from("hello");
+        ReferenceSerialization::encode(&source, &mut cursor, true, &mut reference_tables)?;
+        cursor.seek(SeekFrom::Start(0))?;
+        let decoded =
+            Arc::<str>::decode::<RocksTransaction, _>(&mut cursor, None, &reference_tables)?;
+        assert_eq!(decoded, source);
+
         Ok(())
     }
 }
@@ -0,0 +1,423 @@
+use crate::catalog::interner::StringInterner;
+use crate::errors::DatabaseError;
+use crate::storage::table_codec::{BumpBytes, Bytes, TableCodec};
+use crate::storage::{Storage, Transaction, WritePressure};
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+use std::collections::{Bound, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use ulid::Ulid;
+
+/// A failure injected by [`FaultStorage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// A write of a transaction failed, the statement writing it fails with it
+    WriteError,
+    /// A commit failed before any of its writes was applied
+    CommitError,
+    /// A commit was applied, but failed as if its acknowledgement was lost
+    LostAck,
+    /// The storage crashed while syncing a commit, which is not applied as RocksDB drops a torn
+    /// batch of its log at recovery. Every transaction fails until [`FaultStorage::restart`]
+    TornSync,
+}
+
+/// The chances, in `[0, 1]`, of each [`Fault`] to be injected.
+///
+/// The chance of a write error is drawn for each write, the others for each commit.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FaultOptions {
+    pub write_error: f64,
+    pub commit_error: f64,
+    pub lost_ack: f64,
+    pub torn_sync: f64,
+}
+
+struct FaultInjector {
+    options: FaultOptions,
+    rng: Mutex<StdRng>,
+    enabled: AtomicBool,
+    crashed: AtomicBool,
+    injected: Mutex<Vec<Fault>>,
+}
+
+impl FaultInjector {
+    fn check_crashed(&self) -> Result<(), DatabaseError> {
+        if self.crashed.load(Ordering::Acquire) {
+            return Err(DatabaseError::InjectedFault(Fault::TornSync));
+        }
+        Ok(())
+    }
+
+    fn inject(&self, fault: Fault) -> DatabaseError {
+        if fault == Fault::TornSync {
+            self.crashed.store(true, Ordering::Release);
+        }
+        self.injected.lock().push(fault);
+        DatabaseError::InjectedFault(fault)
+    }
+
+    fn write(&self) -> Result<(), DatabaseError> {
+        self.check_crashed()?;
+        if !self.enabled.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        if self.rng.lock().gen_bool(self.options.write_error) {
+            return Err(self.inject(Fault::WriteError));
+        }
+        Ok(())
+    }
+
+    fn commit(&self) -> Result<Option<Fault>, DatabaseError> {
+        self.check_crashed()?;
+        if !self.enabled.load(Ordering::Acquire) {
+            return Ok(None);
+        }
+        let FaultOptions {
+            commit_error,
+            lost_ack,
+            torn_sync,
+            ..
+        } = self.options;
+        let draw = self.rng.lock().gen::<f64>();
+
+        Ok(if draw < commit_error {
+            Some(Fault::CommitError)
+        } else if draw < commit_error + lost_ack {
+            Some(Fault::LostAck)
+        } else if draw < commit_error + lost_ack + torn_sync {
+            Some(Fault::TornSync)
+        } else {
+            None
+        })
+    }
+}
+
+/// Wraps a storage to inject the failures of [`FaultOptions`], in an order given by the seed, to
+/// test what the database keeps after them.
+///
+/// The same seed and the same operations inject the same faults, see [`FaultStorage::faults`].
+#[derive(Clone)]
+pub struct FaultStorage<S> {
+    inner: S,
+    injector: Arc<FaultInjector>,
+}
+
+impl<S: Storage> FaultStorage<S> {
+    pub fn new(inner: S, seed: u64, options: FaultOptions) -> Result<Self, DatabaseError> {
+        let FaultOptions {
+            write_error,
+            commit_error,
+            lost_ack,
+            torn_sync,
+        } = options;
+        if [write_error, commit_error, lost_ack, torn_sync]
+            .iter()
+            .any(|chance| !(0.0..=1.0).contains(chance))
+            || commit_error + lost_ack + torn_sync > 1.0
+        {
+            return Err(DatabaseError::InvalidValue(format!(
+                "the chances of the faults must be in [0, 1], got {:?}",
+                options
+            )));
+        }
+
+        Ok(FaultStorage {
+            inner,
+            injector: Arc::new(FaultInjector {
+                options,
+                rng: Mutex::new(StdRng::seed_from_u64(seed)),
+                enabled: AtomicBool::new(true),
+                crashed: AtomicBool::new(false),
+                injected: Default::default(),
+            }),
+        })
+    }
+
+    /// Pauses or resumes the injection, e.g. to check the database between two faults.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.injector.enabled.store(enabled, Ordering::Release);
+    }
+
+    pub fn is_crashed(&self) -> bool {
+        self.injector.crashed.load(Ordering::Acquire)
+    }
+
+    /// Recovers from a [`Fault::TornSync`], the torn commit being left out.
+    pub fn restart(&self) {
+        self.injector.crashed.store(false, Ordering::Release);
+    }
+
+    /// The faults injected so far, in their order.
+    pub fn faults(&self) -> Vec<Fault> {
+        self.injector.injected.lock().clone()
+    }
+}
+
+impl<S: Storage> Storage for FaultStorage<S> {
+    type TransactionType<'a>
+        = FaultTransaction<'a, S>
+    where
+        Self: 'a;
+
+    fn transaction(&self) -> Result<Self::TransactionType<'_>, DatabaseError> {
+        self.injector.check_crashed()?;
+
+        Ok(FaultTransaction {
+            inner: self.inner.transaction()?,
+            injector: &self.injector,
+        })
+    }
+
+    fn write_pressure(&self) -> Result<WritePressure, DatabaseError> {
+        self.inner.write_pressure()
+    }
+}
+
+pub struct FaultTransaction<'a, S: Storage + 'a> {
+    inner: S::TransactionType<'a>,
+    injector: &'a FaultInjector,
+}
+
+impl<'txn, S: Storage + 'txn> Transaction for FaultTransaction<'txn, S> {
+    type IterType<'iter>
+        = <S::TransactionType<'txn> as Transaction>::IterType<'iter>
+    where
+        Self: 'iter;
+
+    #[inline]
+    fn table_codec(&self) -> *const TableCodec {
+        self.inner.table_codec()
+    }
+
+    #[inline]
+    fn id(&self) -> Ulid {
+        self.inner.id()
+    }
+
+    #[inline]
+    fn tuple_counts(&self) -> &RefCell<HashMap<String, i64>> {
+        self.inner.tuple_counts()
+    }
+
+    #[inline]
+    fn interner(&self) -> &StringInterner {
+        self.inner.interner()
+    }
+
+    #[inline]
+    fn get(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError> {
+        self.inner.get(key)
+    }
+
+    #[inline]
+    fn multi_get(&self, keys: &[BumpBytes]) -> Result<Vec<Option<Bytes>>, DatabaseError> {
+        self.inner.multi_get(keys)
+    }
+
+    #[inline]
+    fn approximate_split_points(
+        &self,
+        min: &[u8],
+        max: &[u8],
+        max_splits: usize,
+    ) -> Result<Vec<Bytes>, DatabaseError> {
+        self.inner.approximate_split_points(min, max, max_splits)
+    }
+
+    #[inline]
+    fn throttle_write(&self, table_name: &str) -> Result<(), DatabaseError> {
+        self.inner.throttle_write(table_name)
+    }
+
+    #[inline]
+    fn set(&self, key: BumpBytes, value: BumpBytes) -> Result<(), DatabaseError> {
+        self.injector.write()?;
+        self.inner.set(key, value)
+    }
+
+    #[inline]
+    fn remove(&self, key: &[u8]) -> Result<(), DatabaseError> {
+        self.injector.write()?;
+        self.inner.remove(key)
+    }
+
+    #[inline]
+    fn range<'a>(
+        &'a self,
+        min: Bound<BumpBytes<'a>>,
+        max: Bound<BumpBytes<'a>>,
+    ) -> Result<Self::IterType<'a>, DatabaseError> {
+        self.inner.range(min, max)
+    }
+
+    #[inline]
+    fn range_rev<'a>(
+        &'a self,
+        min: Bound<BumpBytes<'a>>,
+        max: Bound<BumpBytes<'a>>,
+    ) -> Result<Self::IterType<'a>, DatabaseError> {
+        self.inner.range_rev(min, max)
+    }
+
+    #[inline]
+    fn set_savepoint(&self) -> Result<(), DatabaseError> {
+        self.inner.set_savepoint()
+    }
+
+    #[inline]
+    fn rollback_to_savepoint(&self) -> Result<(), DatabaseError> {
+        self.inner.rollback_to_savepoint()
+    }
+
+    #[inline]
+    fn release_savepoint(&self) -> Result<(), DatabaseError> {
+        self.inner.release_savepoint()
+    }
+
+    fn commit(self) -> Result<(), DatabaseError> {
+        match self.injector.commit()? {
+            None => self.inner.commit(),
+            Some(Fault::LostAck) => {
+                self.inner.commit()?;
+                Err(self.injector.inject(Fault::LostAck))
+            }
+            // dropping the transaction rolls it back
+            Some(fault) => Err(self.injector.inject(fault)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::errors::DatabaseError;
+    use crate::storage::fault::{Fault, FaultOptions, FaultStorage};
+    use crate::storage::rocksdb::RocksStorage;
+    use crate::types::tuple::Tuple;
+    use crate::types::value::DataValue;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    const FAULT_OPTIONS: FaultOptions = FaultOptions {
+        write_error: 0.01,
+        commit_error: 0.05,
+        lost_ack: 0.05,
+        torn_sync: 0.03,
+    };
+
+    /// Runs random writes on a table with an index, and checks after each of them that the table
+    /// holds either all or none of its changes, and that its index still matches it.
+    fn run_workload(seed: u64) -> Result<Vec<Fault>, DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = FaultStorage::new(RocksStorage::new(temp_dir.path())?, seed, FAULT_OPTIONS)?;
+        storage.set_enabled(false);
+        let kite_sql =
+            DataBaseBuilder::path(temp_dir.path()).build_with_storage(storage.clone())?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql.run("create index b_index on t1 (b)")?.done()?;
+
+        let rows = || -> Result<BTreeMap<i32, i32>, DatabaseError> {
+            let mut iter = kite_sql.run("select a, b from t1")?;
+            let tuples = iter.by_ref().collect::<Result<Vec<Tuple>, _>>()?;
+            iter.done()?;
+            Ok(tuples
+                .into_iter()
+                .map(|tuple| match tuple.values[..] {
+                    [DataValue::Int32(a), DataValue::Int32(b)] => (a, b),
+                    _ => unreachable!(),
+                })
+                .collect())
+        };
+        let mut model = BTreeMap::new();
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for _ in 0..200 {
+            let a = rng.gen_range(0..20);
+            let b = rng.gen_range(0..100);
+            let mut expected = model.clone();
+            let sql = match rng.gen_range(0..3) {
+                0 if model.contains_key(&a) => {
+                    expected.insert(a, b);
+                    format!("update t1 set b = {b} where a = {a}")
+                }
+                0 => {
+                    expected.insert(a, b);
+                    format!("insert into t1 values ({a}, {b})")
+                }
+                1 => {
+                    expected.remove(&a);
+                    format!("delete from t1 where a = {a}")
+                }
+                _ => {
+                    expected.range_mut(..a).for_each(|(_, value)| *value += 1);
+                    format!("update t1 set b = b + 1 where a < {a}")
+                }
+            };
+            storage.set_enabled(true);
+            let result = kite_sql.run(&sql).and_then(|iter| iter.done());
+            storage.set_enabled(false);
+            if storage.is_crashed() {
+                assert!(result.is_err());
+                storage.restart();
+            }
+
+            let actual = rows()?;
+            match result {
+                Ok(()) => assert_eq!(actual, expected, "{sql}"),
+                Err(_) => assert!(actual == model || actual == expected, "{sql}"),
+            }
+            let mut iter = kite_sql.run("check table t1")?;
+            assert!(iter.next().is_none(), "{sql}");
+            iter.done()?;
+
+            model = actual;
+        }
+
+        Ok(storage.faults())
+    }
+
+    #[test]
+    fn test_recovery_invariants() -> Result<(), DatabaseError> {
+        let mut faults = Vec::new();
+        for seed in 0..4 {
+            faults.extend(run_workload(seed)?);
+        }
+        for fault in [
+            Fault::WriteError,
+            Fault::CommitError,
+            Fault::LostAck,
+            Fault::TornSync,
+        ] {
+            assert!(faults.contains(&fault), "{fault:?}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deterministic_faults() -> Result<(), DatabaseError> {
+        assert_eq!(run_workload(7)?, run_workload(7)?);
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        assert!(FaultStorage::new(
+            RocksStorage::new(temp_dir.path())?,
+            0,
+            FaultOptions {
+                commit_error: 0.6,
+                lost_ack: 0.6,
+                ..Default::default()
+            },
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,530 @@
+//! The memcomparable encoding of the keys of the primary keys and the indexes: the bytes of two
+//! keys compare as their values do, so that a range of values is a range of keys.
+//!
+//! The key of a value is a tag, the NULLs being below the values, followed by the value:
+//!
+//! - the integers are big-endian, with their sign bit flipped for the signed ones
+//! - the floats have their sign bit flipped when positive and all their bits when negative,
+//!   `-0.0` is `0.0` and every NaN is the same one, above the infinity
+//! - the decimals and the numerics are their exponent followed by their digits
+//! - the strings are groups of 8 bytes, each followed by a marker of its padding
+//! - the elements of the tuples, the arrays and the structs are keys themselves
+//!
+//! No key is a prefix of another one, so that the keys of several columns are concatenated as
+//! they are. A descending key has all its bytes inverted.
+use crate::errors::DatabaseError;
+use crate::storage::table_codec::{BumpBytes, BOUND_MAX_TAG, BOUND_MIN_TAG};
+use crate::types::value::DataValue;
+use bigdecimal::num_bigint::Sign;
+use bigdecimal::BigDecimal;
+use rust_decimal::Decimal;
+use std::io::Write;
+
+const NULL_TAG: u8 = 0x01;
+const VALUE_TAG: u8 = 0x02;
+const NULL_LAST_TAG: u8 = 0x03;
+/// Ends the elements of an array, below the tags so that a prefix sorts first
+const ARRAY_END_TAG: u8 = 0x00;
+
+const ENCODE_GROUP_SIZE: usize = 8;
+const ENCODE_MARKER: u8 = 0xFF;
+
+macro_rules! encode_u {
+    ($writer:ident, $u:expr) => {
+        $writer.write_all(&$u.to_be_bytes())?
+    };
+}
+
+/// The order of the keys of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KeyOrder {
+    pub(crate) asc: bool,
+    pub(crate) nulls_first: bool,
+}
+
+impl KeyOrder {
+    /// The order of the primary keys and the indexes.
+    pub(crate) const ASC: KeyOrder = KeyOrder {
+        asc: true,
+        nulls_first: true,
+    };
+}
+
+/// Appends the key of `value` in `order` to `b`.
+pub(crate) fn encode_key(
+    value: &DataValue,
+    order: KeyOrder,
+    b: &mut BumpBytes,
+) -> Result<(), DatabaseError> {
+    let start = b.len();
+
+    if value.is_null() {
+        b.push(if order.nulls_first == order.asc {
+            NULL_TAG
+        } else {
+            NULL_LAST_TAG
+        });
+    } else {
+        b.push(VALUE_TAG);
+        encode_value(value, b)?;
+    }
+    if !order.asc {
+        for byte in b[start..].iter_mut() {
+            *byte = !*byte;
+        }
+    }
+    Ok(())
+}
+
+/// Appends `value` without its tag, nothing for a NULL.
+fn encode_value(value: &DataValue, b: &mut BumpBytes) -> Result<(), DatabaseError> {
+    match value {
+        DataValue::Null => (),
+        DataValue::Int8(v) => encode_u!(b, *v as u8 ^ 0x80_u8),
+        DataValue::Int16(v) => encode_u!(b, *v as u16 ^ 0x8000_u16),
+        DataValue::Int32(v) | DataValue::Date32(v) => {
+            encode_u!(b, *v as u32 ^ 0x80000000_u32)
+        }
+        DataValue::Int64(v) | DataValue::Date64(v) | DataValue::Time64(v, ..) => {
+            encode_u!(b, *v as u64 ^ 0x8000000000000000_u64)
+        }
+        DataValue::UInt8(v) => encode_u!(b, v),
+        DataValue::UInt16(v) => encode_u!(b, v),
+        DataValue::UInt32(v) | DataValue::Time32(v, ..) => encode_u!(b, v),
+        DataValue::UInt64(v) => encode_u!(b, v),
+        DataValue::Int128(v) => encode_u!(b, *v as u128 ^ 1_u128 << 127),
+        DataValue::UInt128(v) => encode_u!(b, v),
+        DataValue::Utf8 { value: v, .. } => encode_bytes(b, v.as_bytes()),
+        DataValue::Boolean(v) => b.push(if *v { b'1' } else { b'0' }),
+        DataValue::Float32(f) => encode_u!(b, f32_bits(f.0)),
+        DataValue::Float64(f) => encode_u!(b, f64_bits(f.0)),
+        DataValue::Decimal(v) => encode_decimal(*v, b),
+        DataValue::Numeric(v) => encode_numeric(v, b),
+        // the bounds of the ranges of the composite indexes, see `Range::combining_eqs`
+        DataValue::Tuple(values, is_upper) => {
+            let last = values.len() - 1;
+
+            for (i, value) in values.iter().enumerate() {
+                // a NULL of an upper bound is above every value of its column, as in format 1
+                if value.is_null() && *is_upper {
+                    b.push(BOUND_MAX_TAG);
+                } else {
+                    encode_key(value, KeyOrder::ASC, b)?;
+                }
+                b.push(if (value.is_null() || i == last) && *is_upper {
+                    BOUND_MAX_TAG
+                } else {
+                    BOUND_MIN_TAG
+                });
+            }
+        }
+        DataValue::Vector(v) => {
+            for component in v {
+                encode_u!(b, f32_bits(component.0));
+            }
+        }
+        DataValue::Point(x, y) => {
+            for coordinate in [x, y] {
+                encode_u!(b, f64_bits(coordinate.0));
+            }
+        }
+        DataValue::Box(x_min, y_min, x_max, y_max) => {
+            for coordinate in [x_min, y_min, x_max, y_max] {
+                encode_u!(b, f64_bits(coordinate.0));
+            }
+        }
+        DataValue::Array(values) => {
+            for value in values {
+                encode_key(value, KeyOrder::ASC, b)?;
+            }
+            b.push(ARRAY_END_TAG);
+        }
+        DataValue::Struct(fields) => {
+            for (_, value) in fields {
+                encode_key(value, KeyOrder::ASC, b)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn f32_bits(f: f32) -> u32 {
+    let f = if f.is_nan() {
+        f32::NAN
+    } else if f == 0.0 {
+        0.0
+    } else {
+        f
+    };
+    let u = f.to_bits();
+
+    if f.is_sign_positive() {
+        u | 0x80000000_u32
+    } else {
+        !u
+    }
+}
+
+fn f64_bits(f: f64) -> u64 {
+    let f = if f.is_nan() {
+        f64::NAN
+    } else if f == 0.0 {
+        0.0
+    } else {
+        f
+    };
+    let u = f.to_bits();
+
+    if f.is_sign_positive() {
+        u | 0x8000000000000000_u64
+    } else {
+        !u
+    }
+}
+
+// EncodeBytes guarantees the encoded value is in ascending order for comparison,
+// encoding with the following rule:
+//
+//	[group1][marker1]...[groupN][markerN]
+//	group is 8 bytes slice which is padding with 0.
+//	marker is `0xFF - padding 0 count`
+//
+// For example:
+//
+//	[] -> [0, 0, 0, 0, 0, 0, 0, 0, 247]
+//	[1, 2, 3] -> [1, 2, 3, 0, 0, 0, 0, 0, 250]
+//	[1, 2, 3, 0] -> [1, 2, 3, 0, 0, 0, 0, 0, 251]
+//	[1, 2, 3, 4, 5, 6, 7, 8] -> [1, 2, 3, 4, 5, 6, 7, 8, 255, 0, 0, 0, 0, 0, 0, 0, 0, 247]
+//
+// Refer: https://github.com/facebook/mysql-5.6/wiki/MyRocks-record-format#memcomparable-format
+fn encode_bytes(b: &mut BumpBytes, data: &[u8]) {
+    let d_len = data.len();
+    b.reserve((d_len / ENCODE_GROUP_SIZE + 1) * (ENCODE_GROUP_SIZE + 1));
+
+    let mut idx = 0;
+    while idx <= d_len {
+        let remain = d_len - idx;
+        let pad_count: usize;
+
+        if remain >= ENCODE_GROUP_SIZE {
+            b.extend_from_slice(&data[idx..idx + ENCODE_GROUP_SIZE]);
+            pad_count = 0;
+        } else {
+            pad_count = ENCODE_GROUP_SIZE - remain;
+            b.extend_from_slice(&data[idx..]);
+            b.resize(b.len() + pad_count, 0);
+        }
+
+        b.push(ENCODE_MARKER - pad_count as u8);
+        idx += ENCODE_GROUP_SIZE;
+    }
+}
+
+// https://github.com/risingwavelabs/memcomparable/blob/main/src/ser.rs#L468
+fn encode_decimal(decimal: Decimal, bytes: &mut BumpBytes) {
+    if decimal.is_zero() {
+        bytes.push(0x15);
+        return;
+    }
+    let (exponent, significand) = decimal_e_m(decimal);
+    if decimal.is_sign_positive() {
+        match exponent {
+            11.. => {
+                bytes.push(0x22);
+                bytes.push(exponent as u8);
+            }
+            0..=10 => {
+                bytes.push(0x17 + exponent as u8);
+            }
+            _ => {
+                bytes.push(0x16);
+                bytes.push(!(-exponent) as u8);
+            }
+        }
+        bytes.extend_from_slice(&significand)
+    } else {
+        match exponent {
+            11.. => {
+                bytes.push(0x8);
+                bytes.push(!exponent as u8);
+            }
+            0..=10 => {
+                bytes.push(0x13 - exponent as u8);
+            }
+            _ => {
+                bytes.push(0x14);
+                bytes.push(-exponent as u8);
+            }
+        }
+        for b in significand {
+            bytes.push(!b);
+        }
+    }
+}
+
+/// The sign, then the position of the most significant digit and the digits without the
+/// trailing zeros, all inverted for the negative numbers so that larger magnitudes sort first.
+fn encode_numeric(numeric: &BigDecimal, bytes: &mut BumpBytes) {
+    let (mantissa, scale) = numeric.normalized().into_bigint_and_exponent();
+    let is_negative = match mantissa.sign() {
+        Sign::NoSign => {
+            bytes.push(0x02);
+            return;
+        }
+        Sign::Minus => {
+            bytes.push(0x01);
+            true
+        }
+        Sign::Plus => {
+            bytes.push(0x03);
+            false
+        }
+    };
+    let digits = mantissa.magnitude().to_string();
+    let exponent = digits.len() as i64 - scale;
+
+    let mut encoded = (exponent as u64 ^ 0x8000000000000000_u64)
+        .to_be_bytes()
+        .to_vec();
+    encoded.extend_from_slice(digits.as_bytes());
+    // ends the digits, below every digit so that a prefix sorts first
+    encoded.push(0);
+    if is_negative {
+        encoded.iter_mut().for_each(|b| *b = !*b);
+    }
+    bytes.extend_from_slice(&encoded);
+}
+
+fn decimal_e_m(decimal: Decimal) -> (i8, Vec<u8>) {
+    if decimal.is_zero() {
+        return (0, vec![]);
+    }
+    const POW10: [u128; 30] = [
+        1,
+        10,
+        100,
+        1000,
+        10000,
+        100000,
+        1000000,
+        10000000,
+        100000000,
+        1000000000,
+        10000000000,
+        100000000000,
+        1000000000000,
+        10000000000000,
+        100000000000000,
+        1000000000000000,
+        10000000000000000,
+        100000000000000000,
+        1000000000000000000,
+        10000000000000000000,
+        100000000000000000000,
+        1000000000000000000000,
+        10000000000000000000000,
+        100000000000000000000000,
+        1000000000000000000000000,
+        10000000000000000000000000,
+        100000000000000000000000000,
+        1000000000000000000000000000,
+        10000000000000000000000000000,
+        100000000000000000000000000000,
+    ];
+    let mut mantissa = decimal.mantissa().unsigned_abs();
+    let prec = POW10.as_slice().partition_point(|&p| p <= mantissa);
+
+    let e10 = prec as i32 - decimal.scale() as i32;
+    let e100 = if e10 >= 0 { (e10 + 1) / 2 } else { e10 / 2 };
+    // Maybe need to add a zero at the beginning.
+    // e.g. 111.11 -> 2(exponent which is 100 based) + 0.011111(mantissa).
+    // So, the `digit_num` of 111.11 will be 6.
+    let mut digit_num = if e10 == 2 * e100 { prec } else { prec + 1 };
+
+    let mut byte_array = Vec::with_capacity(16);
+    // Remove trailing zero.
+    while mantissa % 10 == 0 && mantissa != 0 {
+        mantissa /= 10;
+        digit_num -= 1;
+    }
+
+    // Cases like: 0.12345, not 0.01111.
+    if digit_num % 2 == 1 {
+        mantissa *= 10;
+        // digit_num += 1;
+    }
+    while mantissa >> 64 != 0 {
+        let byte = (mantissa % 100) as u8 * 2 + 1;
+        byte_array.push(byte);
+        mantissa /= 100;
+    }
+    // optimize for division
+    let mut mantissa = mantissa as u64;
+    while mantissa != 0 {
+        let byte = (mantissa % 100) as u8 * 2 + 1;
+        byte_array.push(byte);
+        mantissa /= 100;
+    }
+    byte_array[0] -= 1;
+    byte_array.reverse();
+
+    (e100 as i8, byte_array)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::storage::memcomparable::{encode_key, KeyOrder};
+    use crate::storage::table_codec::BumpBytes;
+    use crate::types::value::{DataValue, Utf8Type};
+    use bumpalo::Bump;
+    use ordered_float::OrderedFloat;
+    use sqlparser::ast::CharLengthUnits;
+
+    fn keys<'a>(
+        arena: &'a Bump,
+        values: &[DataValue],
+        order: KeyOrder,
+    ) -> Result<Vec<BumpBytes<'a>>, DatabaseError> {
+        values
+            .iter()
+            .map(|value| {
+                let mut key = BumpBytes::new_in(arena);
+                encode_key(value, order, &mut key)?;
+                Ok(key)
+            })
+            .collect()
+    }
+
+    fn assert_sorted(keys: &[BumpBytes]) {
+        for pair in keys.windows(2) {
+            assert!(pair[0] < pair[1], "{:?} < {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_key_order() -> Result<(), DatabaseError> {
+        let arena = Bump::new();
+        let utf8 = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        assert_sorted(&keys(
+            &arena,
+            &[
+                DataValue::Null,
+                DataValue::Int32(i32::MIN),
+                DataValue::Int32(-1),
+                DataValue::Int32(0),
+                DataValue::Int32(i32::MAX),
+            ],
+            KeyOrder::ASC,
+        )?);
+        // longer strings are not below shorter ones
+        assert_sorted(&keys(
+            &arena,
+            &[
+                DataValue::Null,
+                utf8(""),
+                utf8("a"),
+                utf8("a\0"),
+                utf8("abcdefghijklmnop"),
+                utf8("b"),
+            ],
+            KeyOrder::ASC,
+        )?);
+        assert_sorted(&keys(
+            &arena,
+            &[
+                DataValue::Float64(OrderedFloat(f64::NEG_INFINITY)),
+                DataValue::Float64(OrderedFloat(-1.0)),
+                DataValue::Float64(OrderedFloat(0.0)),
+                DataValue::Float64(OrderedFloat(1.0)),
+                DataValue::Float64(OrderedFloat(f64::INFINITY)),
+                DataValue::Float64(OrderedFloat(f64::NAN)),
+            ],
+            KeyOrder::ASC,
+        )?);
+        // equal values have the same key
+        let zeros = keys(
+            &arena,
+            &[
+                DataValue::Float64(OrderedFloat(0.0)),
+                DataValue::Float64(OrderedFloat(-0.0)),
+            ],
+            KeyOrder::ASC,
+        )?;
+        assert_eq!(zeros[0], zeros[1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_descending_and_nulls() -> Result<(), DatabaseError> {
+        let arena = Bump::new();
+        let values = [DataValue::Int8(1), DataValue::Int8(2), DataValue::Null];
+
+        let desc_nulls_first = keys(
+            &arena,
+            &values,
+            KeyOrder {
+                asc: false,
+                nulls_first: true,
+            },
+        )?;
+        assert_sorted(&[
+            desc_nulls_first[2].clone(),
+            desc_nulls_first[1].clone(),
+            desc_nulls_first[0].clone(),
+        ]);
+        let desc_nulls_last = keys(
+            &arena,
+            &values,
+            KeyOrder {
+                asc: false,
+                nulls_first: false,
+            },
+        )?;
+        assert_sorted(&[
+            desc_nulls_last[1].clone(),
+            desc_nulls_last[0].clone(),
+            desc_nulls_last[2].clone(),
+        ]);
+        let asc_nulls_last = keys(
+            &arena,
+            &values,
+            KeyOrder {
+                asc: true,
+                nulls_first: false,
+            },
+        )?;
+        assert_sorted(&asc_nulls_last);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_composite_key() -> Result<(), DatabaseError> {
+        let arena = Bump::new();
+        let tuple = |values: Vec<DataValue>, is_upper| DataValue::Tuple(values, is_upper);
+
+        // the NULLs of a column of a composite index are below its values, whatever the values
+        // of the next columns
+        assert_sorted(&keys(
+            &arena,
+            &[
+                tuple(vec![DataValue::Null, DataValue::UInt32(u32::MAX)], false),
+                tuple(vec![DataValue::UInt32(0), DataValue::Null], false),
+                tuple(vec![DataValue::UInt32(0), DataValue::UInt32(0)], false),
+                tuple(vec![DataValue::UInt32(0)], true),
+                tuple(vec![DataValue::UInt32(1), DataValue::Null], false),
+            ],
+            KeyOrder::ASC,
+        )?);
+
+        Ok(())
+    }
+}
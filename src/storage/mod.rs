@@ -1,27 +1,40 @@
+pub mod fault;
+pub(crate) mod memcomparable;
 pub mod rocksdb;
 pub(crate) mod table_codec;
+pub mod tuple_codec;
 
+use crate::catalog::interner::StringInterner;
+use crate::catalog::task::Task;
 use crate::catalog::view::View;
-use crate::catalog::{ColumnCatalog, ColumnRef, TableCatalog, TableMeta, TableName};
+use crate::catalog::{
+    AuditColumns, ColumnCatalog, ColumnRef, ForeignTable, TableCatalog, TableMeta, TableName,
+};
 use crate::errors::DatabaseError;
 use crate::execution::dml::analyze::Analyze;
+use crate::execution::dql::projection::Projection;
 use crate::expression::range_detacher::Range;
 use crate::optimizer::core::statistics_meta::{StatisticMetaLoader, StatisticsMeta};
 use crate::serdes::ReferenceTables;
 use crate::storage::table_codec::{BumpBytes, Bytes, TableCodec};
+use crate::storage::tuple_codec::{TupleCodec, TupleCodecEnum, TupleCodecKind};
 use crate::types::index::{Index, IndexId, IndexMetaRef, IndexType};
 use crate::types::tuple::{Tuple, TupleId};
 use crate::types::value::DataValue;
 use crate::types::{ColumnId, LogicalType};
 use crate::utils::lru::SharedLruCache;
+use crate::utils::vector;
+use bumpalo::Bump;
 use itertools::Itertools;
-use std::collections::{BTreeMap, Bound};
+use ordered_float::OrderedFloat;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, Bound, HashMap, HashSet};
 use std::io::Cursor;
 use std::ops::SubAssign;
 use std::sync::Arc;
 use std::vec::IntoIter;
 use std::{fs, mem};
-use ulid::Generator;
+use ulid::{Generator, Ulid};
 
 pub(crate) type StatisticsMetaCache = SharedLruCache<(TableName, IndexId), StatisticsMeta>;
 pub(crate) type TableCache = SharedLruCache<TableName, TableCatalog>;
@@ -33,11 +46,72 @@ pub trait Storage: Clone {
         Self: 'a;
 
     fn transaction(&self) -> Result<Self::TransactionType<'_>, DatabaseError>;
+
+    /// How far the storage falls behind the writes, by the storages that keep track of it.
+    fn write_pressure(&self) -> Result<WritePressure, DatabaseError> {
+        Ok(WritePressure::default())
+    }
+}
+
+/// The signals of a storage falling behind the writes, see [`Storage::write_pressure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WritePressure {
+    /// Bytes the compactions are estimated to rewrite
+    pub pending_compaction_bytes: u64,
+    /// Memtables full and waiting to be flushed
+    pub immutable_memtables: u64,
+    /// Bytes of the write-ahead log files
+    pub wal_size: u64,
+}
+
+/// The versions of a table that the plans bound and optimized for it depend on, see
+/// [`Transaction::table_version`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TableVersion {
+    /// Bumped by the statements changing its columns, indexes or the table itself, e.g.
+    /// `ALTER TABLE`, `CREATE INDEX` or `DROP TABLE`, or the view of the name
+    pub catalog: u64,
+    /// Bumped by `ANALYZE` and `TRUNCATE`, and when the statistics of an index are dropped
+    pub statistics: u64,
 }
 
 /// Optional bounds of the reader, of the form (offset, limit).
 pub(crate) type Bounds = (Option<usize>, Option<usize>);
 
+/// Tuples of a table encoded apart from a transaction, e.g. by the threads of `COPY FROM`, which
+/// [`Transaction::write_batch`] writes in the order they were pushed.
+#[derive(Debug, Default)]
+pub struct WriteBatch {
+    table_name: String,
+    tuples: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl WriteBatch {
+    pub(crate) fn push_tuple(
+        &mut self,
+        table_codec: &TableCodec,
+        table_name: &str,
+        tuple: &mut Tuple,
+        tuple_codec: &impl TupleCodec,
+    ) -> Result<(), DatabaseError> {
+        let (key, value) = table_codec.encode_tuple(table_name, tuple, tuple_codec)?;
+        if self.table_name != table_name {
+            self.table_name = table_name.to_string();
+        }
+        self.tuples.push((key.to_vec(), value.to_vec()));
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.tuples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tuples.is_empty()
+    }
+}
+
 pub trait Transaction: Sized {
     type IterType<'a>: InnerIter
     where
@@ -45,9 +119,24 @@ pub trait Transaction: Sized {
 
     fn table_codec(&self) -> *const TableCodec;
 
+    /// Identifies the transaction among those running, for the keys only it writes.
+    fn id(&self) -> Ulid;
+
+    /// The tuples the transaction added to each table, which it writes to the count of the table
+    /// once as it commits.
+    fn tuple_counts(&self) -> &RefCell<HashMap<String, i64>>;
+
+    /// Shares the names of the columns of the tables of the storage, see [`StringInterner`].
+    fn interner(&self) -> &StringInterner;
+
+    /// Points the names of the columns at those of the other tables of the storage.
+    fn intern_column(&self, column: &mut ColumnCatalog) {
+        column.summary_mut().name = self.interner().intern(column.name());
+    }
+
     /// The bounds is applied to the whole data batches, not per batch.
     ///
-    /// The projections is column indices.
+    /// The projections is column indices, `reverse` reads from the last primary key down.
     fn read<'a>(
         &'a self,
         table_cache: &'a TableCache,
@@ -55,13 +144,14 @@ pub trait Transaction: Sized {
         bounds: Bounds,
         mut columns: BTreeMap<usize, ColumnRef>,
         with_pk: bool,
+        reverse: bool,
     ) -> Result<TupleIter<'a, Self>, DatabaseError> {
         debug_assert!(columns.keys().all_unique());
 
         let table = self
             .table(table_cache, table_name.clone())?
-            .ok_or(DatabaseError::TableNotFound)?;
-        let table_types = table.types();
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let tuple_codec = TupleCodecEnum::instance(table)?;
         if columns.is_empty() || with_pk {
             for (i, column) in table.primary_keys() {
                 columns.insert(*i, column.clone());
@@ -76,12 +166,16 @@ pub trait Transaction: Sized {
         let remap_pk_indices = remap_pk_indices(&projections, table.primary_keys_indices());
 
         let (min, max) = unsafe { &*self.table_codec() }.tuple_bound(&table_name);
-        let iter = self.range(Bound::Included(min), Bound::Included(max))?;
+        let iter = if reverse {
+            self.range_rev(Bound::Included(min), Bound::Included(max))?
+        } else {
+            self.range(Bound::Included(min), Bound::Included(max))?
+        };
 
         Ok(TupleIter {
             offset: bounds.0.unwrap_or(0),
             limit: bounds.1,
-            table_types,
+            tuple_codec,
             tuple_columns: Arc::new(tuple_columns),
             remap_pk_indices,
             projections,
@@ -90,6 +184,92 @@ pub trait Transaction: Sized {
         })
     }
 
+    /// Counts the tuples of the table by the counts its writes keep, or by walking its keys for
+    /// the tables whose count was never kept.
+    fn count_tuples(&self, table_name: &str) -> Result<usize, DatabaseError> {
+        let table_codec = unsafe { &*self.table_codec() };
+        let (min, max) = table_codec.tuple_count_bound(table_name);
+        let mut counts = self.range(Bound::Included(min), Bound::Included(max))?;
+
+        if let Some((key, value)) = counts.try_next()? {
+            if key.as_slice()
+                == table_codec
+                    .encode_tuple_count_key(table_name, Ulid::nil())
+                    .as_slice()
+            {
+                let mut count = TableCodec::decode_tuple_count(&value)?;
+
+                while let Some((_, value)) = counts.try_next()? {
+                    count += TableCodec::decode_tuple_count(&value)?;
+                }
+                count += self
+                    .tuple_counts()
+                    .borrow()
+                    .get(table_name)
+                    .copied()
+                    .unwrap_or(0);
+                return Ok(count.max(0) as usize);
+            }
+        }
+        drop(counts);
+
+        let (min, max) = table_codec.tuple_bound(table_name);
+        let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
+        let mut count = 0;
+
+        while iter.try_next()?.is_some() {
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// The tuples of the table and the bytes their keys and values take, before compression.
+    fn table_usage(&self, table_name: &str) -> Result<(u64, u64), DatabaseError> {
+        let (min, max) = unsafe { &*self.table_codec() }.tuple_bound(table_name);
+
+        self.range_usage(min, max)
+    }
+
+    /// The entries of the index and the bytes their keys and values take, before compression.
+    fn index_usage(
+        &self,
+        table_name: &str,
+        index_id: IndexId,
+    ) -> Result<(u64, u64), DatabaseError> {
+        let (min, max) = unsafe { &*self.table_codec() }.index_bound(table_name, index_id)?;
+
+        self.range_usage(min, max)
+    }
+
+    fn range_usage<'a>(
+        &'a self,
+        min: BumpBytes<'a>,
+        max: BumpBytes<'a>,
+    ) -> Result<(u64, u64), DatabaseError> {
+        let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
+        let (mut keys, mut bytes) = (0, 0);
+
+        while let Some((key, value)) = iter.try_next()? {
+            keys += 1;
+            bytes += (key.len() + value.len()) as u64;
+        }
+        Ok((keys, bytes))
+    }
+
+    /// Approximate keys splitting the tuples of the table into at most `max_splits` ranges of a
+    /// similar size, ascending and strictly between the bounds of the table.
+    ///
+    /// An empty result means the table is read as a single range.
+    fn split_points(
+        &self,
+        table_name: &str,
+        max_splits: usize,
+    ) -> Result<Vec<Bytes>, DatabaseError> {
+        let (min, max) = unsafe { &*self.table_codec() }.tuple_bound(table_name);
+
+        self.approximate_split_points(&min, &max, max_splits)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn read_by_index<'a>(
         &'a self,
@@ -105,8 +285,8 @@ pub trait Transaction: Sized {
 
         let table = self
             .table(table_cache, table_name.clone())?
-            .ok_or(DatabaseError::TableNotFound)?;
-        let table_types = table.types();
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let tuple_codec = TupleCodecEnum::instance(table)?;
         let table_name = table.name.as_str();
         let offset = offset_option.unwrap_or(0);
 
@@ -122,7 +302,14 @@ pub trait Transaction: Sized {
             projections.push(projection);
         }
         let remap_pk_indices = remap_pk_indices(&projections, table.primary_keys_indices());
+        let ranges = if matches!(index_meta.ty, IndexType::Vector) {
+            self.vector_probe_ranges(table_name, index_meta.id, ranges)?
+        } else {
+            ranges
+        };
         let inner = IndexImplEnum::instance(index_meta.ty);
+        let seen_tuple_ids =
+            matches!(index_meta.ty, IndexType::FullText | IndexType::Trigram).then(HashSet::new);
 
         Ok(IndexIter {
             offset,
@@ -133,18 +320,124 @@ pub trait Transaction: Sized {
                 projections,
                 index_meta,
                 table_name,
-                table_types,
+                tuple_codec,
                 with_pk,
+                reverse: false,
                 tx: self,
             },
             inner,
             ranges: ranges.into_iter(),
             state: IndexIterState::Init,
+            seen_tuple_ids,
+        })
+    }
+
+    /// Reads the tuples of the keys of the primary key in one batch of the storage, in the order
+    /// of `keys`, skipping those not found.
+    #[allow(clippy::too_many_arguments)]
+    fn read_by_keys<'a>(
+        &'a self,
+        table_cache: &'a TableCache,
+        table_name: TableName,
+        (offset_option, limit_option): Bounds,
+        mut columns: BTreeMap<usize, ColumnRef>,
+        index_meta: IndexMetaRef,
+        keys: Vec<DataValue>,
+        with_pk: bool,
+    ) -> Result<KeysIter, DatabaseError> {
+        debug_assert!(columns.keys().all_unique());
+
+        let table = self
+            .table(table_cache, table_name.clone())?
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let tuple_codec = TupleCodecEnum::instance(table)?;
+
+        if columns.is_empty() || with_pk {
+            for (i, column) in table.primary_keys() {
+                columns.insert(*i, column.clone());
+            }
+        }
+        let mut tuple_columns = Vec::with_capacity(columns.len());
+        let mut projections = Vec::with_capacity(columns.len());
+        for (projection, column) in columns {
+            tuple_columns.push(column);
+            projections.push(projection);
+        }
+        let remap_pk_indices = remap_pk_indices(&projections, table.primary_keys_indices());
+
+        let table_codec = unsafe { &*self.table_codec() };
+        let keys = keys
+            .into_iter()
+            .map(|key| {
+                let key = if key.logical_type() != index_meta.value_ty {
+                    key.cast(&index_meta.value_ty)?
+                } else {
+                    key
+                };
+                table_codec.encode_tuple_key(&table_name, &key)
+            })
+            .try_collect::<_, Vec<_>, _>()?;
+        let values = self
+            .multi_get(&keys)?
+            .into_iter()
+            .flatten()
+            .skip(offset_option.unwrap_or(0))
+            .take(limit_option.unwrap_or(usize::MAX))
+            .collect_vec();
+
+        Ok(KeysIter {
+            tuple_codec,
+            tuple_columns: Arc::new(tuple_columns),
+            remap_pk_indices,
+            projections,
+            with_pk,
+            values: values.into_iter(),
+        })
+    }
+
+    /// Reads a composite index on `(a, b, ...)` by the range of `b` when `a` is not filtered,
+    /// seeking each distinct value of `a` and then the range of `b` within it.
+    #[allow(clippy::too_many_arguments)]
+    fn read_by_skip<'a>(
+        &'a self,
+        table_cache: &'a TableCache,
+        table_name: TableName,
+        (offset_option, limit_option): Bounds,
+        columns: BTreeMap<usize, ColumnRef>,
+        index_meta: IndexMetaRef,
+        range: Range,
+        with_pk: bool,
+    ) -> Result<SkipIter<'a, Self>, DatabaseError> {
+        let table = self
+            .table(table_cache, table_name.clone())?
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        let leading = table
+            .columns()
+            .enumerate()
+            .find(|(_, column)| column.id() == Some(index_meta.column_ids[0]))
+            .map(|(i, column)| (i, column.clone()))
+            .ok_or(DatabaseError::ColumnNotFound(
+                index_meta.column_ids[0].to_string(),
+            ))?;
+
+        Ok(SkipIter {
+            offset: offset_option.unwrap_or(0),
+            limit: limit_option,
+            tx: self,
+            table_cache,
+            table_name,
+            columns,
+            leading,
+            index_meta,
+            range,
+            with_pk,
+            lower: Bound::Unbounded,
+            iter: None,
         })
     }
 
     fn add_index_meta(
-        &mut self,
+        &self,
         table_cache: &TableCache,
         table_name: &TableName,
         index_name: String,
@@ -156,16 +449,17 @@ pub trait Transaction: Sized {
             let (key, value) =
                 unsafe { &*self.table_codec() }.encode_index_meta(table_name, index_meta)?;
             self.set(key, value)?;
+            self.bump_catalog_version(table_name)?;
             table_cache.remove(table_name);
 
             Ok(index_meta.id)
         } else {
-            Err(DatabaseError::TableNotFound)
+            Err(DatabaseError::TableNotFound(table_name.to_string()))
         }
     }
 
     fn add_index(
-        &mut self,
+        &self,
         table_name: &str,
         index: Index,
         tuple_id: &TupleId,
@@ -173,13 +467,37 @@ pub trait Transaction: Sized {
         if matches!(index.ty, IndexType::PrimaryKey { .. }) {
             return Ok(());
         }
+        if matches!(index.ty, IndexType::Vector) {
+            let Some(list) = self.vector_list(table_name, index.id, index.value)? else {
+                return Ok(());
+            };
+            return self.add_index(
+                table_name,
+                Index::new(index.id, &list, IndexType::Normal),
+                tuple_id,
+            );
+        }
+        if let Some(entries) = index.ty.index_entries(index.value) {
+            for entry in entries {
+                let (key, value) = unsafe { &*self.table_codec() }.encode_index(
+                    table_name,
+                    &Index::new(index.id, &entry, index.ty),
+                    tuple_id,
+                )?;
+                self.set(key, value)?;
+            }
+            return Ok(());
+        }
         let (key, value) =
             unsafe { &*self.table_codec() }.encode_index(table_name, &index, tuple_id)?;
 
         if matches!(index.ty, IndexType::Unique) {
             if let Some(bytes) = self.get(&key)? {
                 return if bytes != value.as_slice() {
-                    Err(DatabaseError::DuplicateUniqueValue)
+                    Err(DatabaseError::DuplicateUniqueValue {
+                        table: table_name.to_string(),
+                        index: None,
+                    })
                 } else {
                     Ok(())
                 };
@@ -191,7 +509,7 @@ pub trait Transaction: Sized {
     }
 
     fn del_index(
-        &mut self,
+        &self,
         table_name: &str,
         index: &Index,
         tuple_id: &TupleId,
@@ -199,6 +517,26 @@ pub trait Transaction: Sized {
         if matches!(index.ty, IndexType::PrimaryKey { .. }) {
             return Ok(());
         }
+        if matches!(index.ty, IndexType::Vector) {
+            let Some(list) = self.vector_list(table_name, index.id, index.value)? else {
+                return Ok(());
+            };
+            return self.del_index(
+                table_name,
+                &Index::new(index.id, &list, IndexType::Normal),
+                tuple_id,
+            );
+        }
+        if let Some(entries) = index.ty.index_entries(index.value) {
+            for entry in entries {
+                self.remove(&unsafe { &*self.table_codec() }.encode_index_key(
+                    table_name,
+                    &Index::new(index.id, &entry, index.ty),
+                    Some(tuple_id),
+                )?)?;
+            }
+            return Ok(());
+        }
         self.remove(&unsafe { &*self.table_codec() }.encode_index_key(
             table_name,
             index,
@@ -208,33 +546,211 @@ pub trait Transaction: Sized {
         Ok(())
     }
 
+    /// Saves the centroids of the lists of an IVF index, the entries added afterwards are kept
+    /// in the list of their nearest centroid.
+    fn save_vector_centroids(
+        &self,
+        table_name: &str,
+        index_id: IndexId,
+        centroids: &[Vec<OrderedFloat<f32>>],
+    ) -> Result<(), DatabaseError> {
+        let (key, value) = unsafe { &*self.table_codec() }
+            .encode_vector_centroids(table_name, index_id, centroids)?;
+        self.set(key, value)
+    }
+
+    fn vector_centroids(
+        &self,
+        table_name: &str,
+        index_id: IndexId,
+    ) -> Result<Vec<Vec<OrderedFloat<f32>>>, DatabaseError> {
+        let key = unsafe { &*self.table_codec() }.encode_vector_centroids_key(table_name, index_id);
+
+        self.get(&key)?
+            .map(|bytes| TableCodec::decode_vector_centroids(&bytes))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// The list of an IVF index a vector is kept in, `None` for `NULL`.
+    fn vector_list(
+        &self,
+        table_name: &str,
+        index_id: IndexId,
+        value: &DataValue,
+    ) -> Result<Option<DataValue>, DatabaseError> {
+        let DataValue::Vector(components) = value else {
+            return Ok(None);
+        };
+        let centroids = self.vector_centroids(table_name, index_id)?;
+
+        Ok(Some(DataValue::UInt32(vector::nearest(
+            &centroids, components,
+        ))))
+    }
+
+    /// An IVF index is probed by the query vector, which is replaced by the lists of its
+    /// [`vector::PROBES`] nearest centroids.
+    fn vector_probe_ranges(
+        &self,
+        table_name: &str,
+        index_id: IndexId,
+        ranges: Vec<Range>,
+    ) -> Result<Vec<Range>, DatabaseError> {
+        let centroids = self.vector_centroids(table_name, index_id)?;
+        let mut probe_ranges = Vec::new();
+
+        for range in ranges {
+            let Range::Eq(DataValue::Vector(components)) = range else {
+                return Err(DatabaseError::InvalidIndex);
+            };
+            let lists = if centroids.is_empty() {
+                vec![0]
+            } else {
+                vector::nearest_n(&centroids, &components, vector::PROBES)
+            };
+            probe_ranges.extend(
+                lists
+                    .into_iter()
+                    .map(|list| Range::Eq(DataValue::UInt32(list))),
+            );
+        }
+        Ok(probe_ranges)
+    }
+
+    fn clear_index(&self, table_name: &str, index_id: IndexId) -> Result<(), DatabaseError> {
+        let (index_min, index_max) =
+            unsafe { &*self.table_codec() }.index_bound(table_name, index_id)?;
+        self._drop_data(index_min, index_max)
+    }
+
     fn append_tuple(
-        &mut self,
+        &self,
         table_name: &str,
         mut tuple: Tuple,
-        types: &[LogicalType],
+        tuple_codec: &impl TupleCodec,
         is_overwrite: bool,
     ) -> Result<(), DatabaseError> {
         let (key, value) =
-            unsafe { &*self.table_codec() }.encode_tuple(table_name, &mut tuple, types)?;
+            unsafe { &*self.table_codec() }.encode_tuple(table_name, &mut tuple, tuple_codec)?;
 
-        if !is_overwrite && self.get(&key)?.is_some() {
-            return Err(DatabaseError::DuplicatePrimaryKey);
+        if self.get(&key)?.is_some() {
+            if !is_overwrite {
+                return Err(DatabaseError::DuplicatePrimaryKey {
+                    table: table_name.to_string(),
+                });
+            }
+        } else {
+            self.add_tuple_count(table_name, 1)?;
         }
         self.set(key, value)?;
 
         Ok(())
     }
 
-    fn remove_tuple(&mut self, table_name: &str, tuple_id: &TupleId) -> Result<(), DatabaseError> {
+    /// Writes the tuples of a batch as [`append_tuple`](Self::append_tuple) does, in their order.
+    fn write_batch(&self, batch: WriteBatch, is_overwrite: bool) -> Result<(), DatabaseError> {
+        let arena = Bump::new();
+        let mut added = 0;
+
+        for (key, value) in batch.tuples {
+            if self.get(&key)?.is_some() {
+                if !is_overwrite {
+                    return Err(DatabaseError::DuplicatePrimaryKey {
+                        table: batch.table_name,
+                    });
+                }
+            } else {
+                added += 1;
+            }
+            self.set(
+                BumpBytes::from_iter_in(key, &arena),
+                BumpBytes::from_iter_in(value, &arena),
+            )?;
+        }
+        self.add_tuple_count(&batch.table_name, added)
+    }
+
+    fn remove_tuple(&self, table_name: &str, tuple_id: &TupleId) -> Result<(), DatabaseError> {
         let key = unsafe { &*self.table_codec() }.encode_tuple_key(table_name, tuple_id)?;
-        self.remove(&key)?;
+        if self.get(&key)?.is_some() {
+            self.remove(&key)?;
+            self.add_tuple_count(table_name, -1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Starts the count of the tuples of the table at zero, as it is created or truncated.
+    fn reset_tuple_count(&self, table_name: &str) -> Result<(), DatabaseError> {
+        let (key, value) =
+            unsafe { &*self.table_codec() }.encode_tuple_count(table_name, Ulid::nil(), 0);
 
+        self.set(key, value)
+    }
+
+    /// Adds to the count of the tuples of the table in the transaction, see
+    /// [`write_tuple_counts`](Self::write_tuple_counts).
+    fn add_tuple_count(&self, table_name: &str, added: i64) -> Result<(), DatabaseError> {
+        if added != 0 {
+            *self
+                .tuple_counts()
+                .borrow_mut()
+                .entry(table_name.to_string())
+                .or_default() += added;
+        }
         Ok(())
     }
 
+    /// Writes the tuples the transaction added to each table under the key of this transaction,
+    /// which no other transaction writes, as it commits.
+    fn write_tuple_counts(&self) -> Result<(), DatabaseError> {
+        let table_codec = unsafe { &*self.table_codec() };
+
+        for (table_name, added) in self.tuple_counts().take() {
+            if added == 0 {
+                continue;
+            }
+            self.fold_tuple_counts(&table_name)?;
+            let (key, value) = table_codec.encode_tuple_count(&table_name, self.id(), added);
+            self.set(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Folds the counts of the transactions into the one of the table once they pile up, which a
+    /// transaction does as it writes its count of the table.
+    fn fold_tuple_counts(&self, table_name: &str) -> Result<(), DatabaseError> {
+        const MAX_TUPLE_COUNTS: usize = 64;
+
+        let table_codec = unsafe { &*self.table_codec() };
+        let (min, max) = table_codec.tuple_count_bound(table_name);
+        let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
+        let mut counts = Vec::new();
+
+        while let Some((key, value)) = iter.try_next()? {
+            counts.push((key, TableCodec::decode_tuple_count(&value)?));
+        }
+        drop(iter);
+
+        let table_key = table_codec.encode_tuple_count_key(table_name, Ulid::nil());
+        if counts.len() <= MAX_TUPLE_COUNTS || counts[0].0 != table_key.as_slice() {
+            return Ok(());
+        }
+        let mut table_count = 0;
+        for (key, count) in counts {
+            if key != table_key.as_slice() {
+                self.remove(&key)?;
+            }
+            table_count += count;
+        }
+        let (key, value) = table_codec.encode_tuple_count(table_name, Ulid::nil(), table_count);
+
+        self.set(key, value)
+    }
+
     fn add_column(
-        &mut self,
+        &self,
         table_cache: &TableCache,
         table_name: &TableName,
         column: &ColumnCatalog,
@@ -254,7 +770,16 @@ pub trait Transaction: Sized {
                     };
                 }
             }
+            if table
+                .dropped_columns
+                .iter()
+                .any(|(name, ty)| name == column.name() && ty != column.datatype())
+            {
+                return Err(DatabaseError::DroppedColumn(column.name().to_string()));
+            }
             let mut generator = Generator::new();
+            let mut column = column.clone();
+            self.intern_column(&mut column);
             let col_id = table.add_column(column.clone(), &mut generator)?;
 
             if column.desc().is_unique() {
@@ -272,23 +797,29 @@ pub trait Transaction: Sized {
             let (key, value) = unsafe { &*self.table_codec() }
                 .encode_column(column, &mut ReferenceTables::new())?;
             self.set(key, value)?;
+
+            table.push_schema_version(None);
+            let (key, value) = unsafe { &*self.table_codec() }.encode_root_table(&table.meta())?;
+            self.set(key, value)?;
+            self.bump_catalog_version(table_name)?;
             table_cache.remove(table_name);
 
             Ok(col_id)
         } else {
-            Err(DatabaseError::TableNotFound)
+            Err(DatabaseError::TableNotFound(table_name.to_string()))
         }
     }
 
     fn drop_column(
-        &mut self,
+        &self,
         table_cache: &TableCache,
         meta_cache: &StatisticsMetaCache,
         table_name: &TableName,
         column_name: &str,
     ) -> Result<(), DatabaseError> {
-        if let Some(table_catalog) = self.table(table_cache, table_name.clone())?.cloned() {
+        if let Some(mut table_catalog) = self.table(table_cache, table_name.clone())?.cloned() {
             let column = table_catalog.get_column_by_name(column_name).unwrap();
+            let column_id = column.id().unwrap();
 
             let (key, _) = unsafe { &*self.table_codec() }
                 .encode_column(column, &mut ReferenceTables::new())?;
@@ -308,16 +839,50 @@ pub trait Transaction: Sized {
 
                 self.remove_table_meta(meta_cache, table_name, index_meta.id)?;
             }
+            // the values stay in the rows of a versioned table until it is vacuumed
+            if table_catalog.tuple_codec == TupleCodecKind::Versioned {
+                table_catalog
+                    .dropped_columns
+                    .push((column.name().to_string(), column.datatype().clone()));
+            }
+            table_catalog.audit_columns.remove(column_name);
+            table_catalog.push_schema_version(Some(&column_id));
+            let (key, value) =
+                unsafe { &*self.table_codec() }.encode_root_table(&table_catalog.meta())?;
+            self.set(key, value)?;
+            self.bump_catalog_version(table_name)?;
             table_cache.remove(table_name);
 
             Ok(())
         } else {
-            Err(DatabaseError::TableNotFound)
+            Err(DatabaseError::TableNotFound(table_name.to_string()))
         }
     }
 
+    /// Leaves only the current schema version of the table, once its rows are written again with it.
+    fn reset_schema_versions(
+        &self,
+        table_cache: &TableCache,
+        table_name: &TableName,
+    ) -> Result<(), DatabaseError> {
+        let mut table_catalog = self
+            .table(table_cache, table_name.clone())?
+            .cloned()
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+        table_catalog.schema_versions.clear();
+        table_catalog.dropped_columns.clear();
+        table_catalog.push_schema_version(None);
+
+        let (key, value) =
+            unsafe { &*self.table_codec() }.encode_root_table(&table_catalog.meta())?;
+        self.set(key, value)?;
+        table_cache.remove(table_name);
+
+        Ok(())
+    }
+
     fn create_view(
-        &mut self,
+        &self,
         view_cache: &ViewCache,
         view: View,
         or_replace: bool,
@@ -326,41 +891,109 @@ pub trait Transaction: Sized {
 
         let already_exists = self.get(&view_key)?.is_some();
         if !or_replace && already_exists {
-            return Err(DatabaseError::ViewExists);
+            return Err(DatabaseError::ViewExists(view.name.to_string()));
         }
         if !already_exists {
             self.check_name_hash(&view.name)?;
         }
         self.set(view_key, value)?;
+        self.bump_catalog_version(&view.name)?;
         let _ = view_cache.put(view.name.clone(), view);
 
         Ok(())
     }
 
+    fn create_task(&self, task: Task) -> Result<(), DatabaseError> {
+        let (task_key, value) = unsafe { &*self.table_codec() }.encode_task(&task)?;
+
+        if self.get(&task_key)?.is_some() {
+            return Err(DatabaseError::TaskExists(task.name));
+        }
+        self.set(task_key, value)?;
+
+        Ok(())
+    }
+
     fn create_table(
-        &mut self,
+        &self,
+        table_cache: &TableCache,
+        table_name: TableName,
+        columns: Vec<ColumnCatalog>,
+        tuple_codec: TupleCodecKind,
+        audit_columns: AuditColumns,
+        if_not_exists: bool,
+    ) -> Result<TableName, DatabaseError> {
+        self._create_table(
+            table_cache,
+            table_name,
+            columns,
+            None,
+            tuple_codec,
+            audit_columns,
+            if_not_exists,
+        )
+    }
+
+    /// Creates a table whose rows are read from the remote source through its foreign data wrapper.
+    fn create_foreign_table(
+        &self,
+        table_cache: &TableCache,
+        table_name: TableName,
+        columns: Vec<ColumnCatalog>,
+        foreign: ForeignTable,
+        if_not_exists: bool,
+    ) -> Result<TableName, DatabaseError> {
+        self._create_table(
+            table_cache,
+            table_name,
+            columns,
+            Some(foreign),
+            TupleCodecKind::default(),
+            AuditColumns::default(),
+            if_not_exists,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn _create_table(
+        &self,
         table_cache: &TableCache,
         table_name: TableName,
         columns: Vec<ColumnCatalog>,
+        foreign: Option<ForeignTable>,
+        tuple_codec: TupleCodecKind,
+        audit_columns: AuditColumns,
         if_not_exists: bool,
     ) -> Result<TableName, DatabaseError> {
+        let columns = columns
+            .into_iter()
+            .map(|mut column| {
+                self.intern_column(&mut column);
+                column
+            })
+            .collect_vec();
         let mut table_catalog = TableCatalog::new(table_name.clone(), columns)?;
+        table_catalog.foreign = foreign;
+        table_catalog.tuple_codec = tuple_codec;
+        table_catalog.audit_columns = audit_columns;
 
         for (_, column) in table_catalog.primary_keys() {
             TableCodec::check_primary_key_type(column.datatype())?;
         }
 
-        let (table_key, value) = unsafe { &*self.table_codec() }
-            .encode_root_table(&TableMeta::empty(table_name.clone()))?;
+        let (table_key, value) =
+            unsafe { &*self.table_codec() }.encode_root_table(&table_catalog.meta())?;
         if self.get(&table_key)?.is_some() {
             if if_not_exists {
                 return Ok(table_name);
             }
-            return Err(DatabaseError::TableExists);
+            return Err(DatabaseError::TableExists(table_name.to_string()));
         }
         self.check_name_hash(&table_name)?;
         self.create_index_meta_from_column(&mut table_catalog)?;
         self.set(table_key, value)?;
+        self.reset_tuple_count(&table_name)?;
+        self.bump_catalog_version(&table_name)?;
 
         let mut reference_tables = ReferenceTables::new();
         for column in table_catalog.columns() {
@@ -374,7 +1007,7 @@ pub trait Transaction: Sized {
         Ok(table_name)
     }
 
-    fn check_name_hash(&mut self, table_name: &TableName) -> Result<(), DatabaseError> {
+    fn check_name_hash(&self, table_name: &TableName) -> Result<(), DatabaseError> {
         let (hash_key, value) = unsafe { &*self.table_codec() }.encode_table_hash(table_name);
         if self.get(&hash_key)?.is_some() {
             return Err(DatabaseError::DuplicateSourceHash(table_name.to_string()));
@@ -382,12 +1015,12 @@ pub trait Transaction: Sized {
         self.set(hash_key, value)
     }
 
-    fn drop_name_hash(&mut self, table_name: &TableName) -> Result<(), DatabaseError> {
+    fn drop_name_hash(&self, table_name: &TableName) -> Result<(), DatabaseError> {
         self.remove(&unsafe { &*self.table_codec() }.encode_table_hash_key(table_name))
     }
 
     fn drop_view(
-        &mut self,
+        &self,
         view_cache: &ViewCache,
         table_cache: &TableCache,
         view_name: TableName,
@@ -401,18 +1034,34 @@ pub trait Transaction: Sized {
             if if_exists {
                 return Ok(());
             } else {
-                return Err(DatabaseError::ViewNotFound);
+                return Err(DatabaseError::ViewNotFound(view_name.to_string()));
             }
         }
 
         self.remove(&unsafe { &*self.table_codec() }.encode_view_key(view_name.as_str()))?;
+        self.bump_catalog_version(&view_name)?;
         view_cache.remove(&view_name);
 
         Ok(())
     }
 
+    fn drop_task(&self, task_name: &str, if_exists: bool) -> Result<(), DatabaseError> {
+        let task_key = unsafe { &*self.table_codec() }.encode_task_key(task_name);
+
+        if self.get(&task_key)?.is_none() {
+            if if_exists {
+                return Ok(());
+            } else {
+                return Err(DatabaseError::TaskNotFound(task_name.to_string()));
+            }
+        }
+        self.remove(&task_key)?;
+
+        Ok(())
+    }
+
     fn drop_index(
-        &mut self,
+        &self,
         table_cache: &TableCache,
         table_name: TableName,
         index_name: &str,
@@ -420,22 +1069,28 @@ pub trait Transaction: Sized {
     ) -> Result<(), DatabaseError> {
         let table = self
             .table(table_cache, table_name.clone())?
-            .ok_or(DatabaseError::TableNotFound)?;
+            .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
         let Some(index_meta) = table.indexes.iter().find(|index| index.name == index_name) else {
             if if_exists {
                 return Ok(());
             } else {
-                return Err(DatabaseError::TableNotFound);
+                return Err(DatabaseError::TableNotFound(table_name.to_string()));
             }
         };
         match index_meta.ty {
             IndexType::PrimaryKey { .. } | IndexType::Unique => {
                 return Err(DatabaseError::InvalidIndex)
             }
-            IndexType::Normal | IndexType::Composite => (),
+            IndexType::Normal
+            | IndexType::Composite
+            | IndexType::FullText
+            | IndexType::Trigram
+            | IndexType::Vector
+            | IndexType::ZOrder => (),
         }
 
         let index_id = index_meta.id;
+        let is_vector = matches!(index_meta.ty, IndexType::Vector);
         let index_meta_key =
             unsafe { &*self.table_codec() }.encode_index_meta_key(table_name.as_str(), index_id)?;
         self.remove(&index_meta_key)?;
@@ -447,7 +1102,14 @@ pub trait Transaction: Sized {
         let statistics_min_key = unsafe { &*self.table_codec() }
             .encode_statistics_path_key(table_name.as_str(), index_id);
         self.remove(&statistics_min_key)?;
+        if is_vector {
+            self.remove(
+                &unsafe { &*self.table_codec() }
+                    .encode_vector_centroids_key(table_name.as_str(), index_id),
+            )?;
+        }
 
+        self.bump_catalog_version(&table_name)?;
         table_cache.remove(&table_name);
         //  When dropping Index, the statistics file corresponding to the Index is not cleaned up and is processed uniformly by the Analyze Table.
 
@@ -455,7 +1117,7 @@ pub trait Transaction: Sized {
     }
 
     fn drop_table(
-        &mut self,
+        &self,
         table_cache: &TableCache,
         table_name: TableName,
         if_exists: bool,
@@ -464,7 +1126,7 @@ pub trait Transaction: Sized {
             if if_exists {
                 return Ok(());
             } else {
-                return Err(DatabaseError::TableNotFound);
+                return Err(DatabaseError::TableNotFound(table_name.to_string()));
             }
         }
         self.drop_name_hash(&table_name)?;
@@ -479,6 +1141,7 @@ pub trait Transaction: Sized {
         self._drop_data(index_meta_min, index_meta_max)?;
 
         self.remove(&unsafe { &*self.table_codec() }.encode_root_table_key(table_name.as_str()))?;
+        self.bump_catalog_version(&table_name)?;
         table_cache.remove(&table_name);
 
         let _ = fs::remove_dir(Analyze::build_statistics_meta_path(&table_name));
@@ -486,7 +1149,7 @@ pub trait Transaction: Sized {
         Ok(())
     }
 
-    fn drop_data(&mut self, table_name: &str) -> Result<(), DatabaseError> {
+    fn drop_data(&self, table_name: &str) -> Result<(), DatabaseError> {
         let (tuple_min, tuple_max) = unsafe { &*self.table_codec() }.tuple_bound(table_name);
         self._drop_data(tuple_min, tuple_max)?;
 
@@ -496,6 +1159,7 @@ pub trait Transaction: Sized {
         let (statistics_min, statistics_max) =
             unsafe { &*self.table_codec() }.statistics_bound(table_name);
         self._drop_data(statistics_min, statistics_max)?;
+        self.tuple_counts().borrow_mut().remove(table_name);
 
         Ok(())
     }
@@ -532,6 +1196,77 @@ pub trait Transaction: Sized {
         Ok(metas)
     }
 
+    fn tasks(&self) -> Result<Vec<Task>, DatabaseError> {
+        let mut tasks = vec![];
+        let (min, max) = unsafe { &*self.table_codec() }.task_bound();
+        let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
+
+        while let Some((_, value)) = iter.try_next()? {
+            tasks.push(TableCodec::decode_task::<Self>(&value)?);
+        }
+
+        Ok(tasks)
+    }
+
+    /// The primary key of the last row a statement with `batch_commit` committed, if it stopped
+    /// before its end.
+    fn batch_checkpoint(&self, statement: &str) -> Result<Option<Vec<DataValue>>, DatabaseError> {
+        let key = unsafe { &*self.table_codec() }.encode_batch_checkpoint_key(statement);
+
+        self.get(&key)?
+            .map(|bytes| TableCodec::decode_batch_checkpoint::<Self>(&bytes))
+            .transpose()
+    }
+
+    fn set_batch_checkpoint(
+        &self,
+        statement: &str,
+        primary_key: &[DataValue],
+    ) -> Result<(), DatabaseError> {
+        let (key, value) =
+            unsafe { &*self.table_codec() }.encode_batch_checkpoint(statement, primary_key)?;
+
+        self.set(key, value)
+    }
+
+    fn remove_batch_checkpoint(&self, statement: &str) -> Result<(), DatabaseError> {
+        let key = unsafe { &*self.table_codec() }.encode_batch_checkpoint_key(statement);
+
+        self.remove(&key)
+    }
+
+    /// The versions of the catalog and of the statistics of the table, or of the view of the
+    /// name, that a plan cached for it checks to be bound and optimized again once they changed.
+    ///
+    /// They only grow, through `DROP TABLE` and a table created again with the same name as
+    /// well, and are written by the transactions of the statements changing them.
+    fn table_version(&self, table_name: &str) -> Result<TableVersion, DatabaseError> {
+        let key = unsafe { &*self.table_codec() }.encode_table_version_key(table_name);
+
+        self.get(&key)?
+            .map(|bytes| TableCodec::decode_table_version(&bytes))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    fn bump_catalog_version(&self, table_name: &str) -> Result<(), DatabaseError> {
+        let mut version = self.table_version(table_name)?;
+        version.catalog += 1;
+        let (key, value) =
+            unsafe { &*self.table_codec() }.encode_table_version(table_name, version)?;
+
+        self.set(key, value)
+    }
+
+    fn bump_statistics_version(&self, table_name: &str) -> Result<(), DatabaseError> {
+        let mut version = self.table_version(table_name)?;
+        version.statistics += 1;
+        let (key, value) =
+            unsafe { &*self.table_codec() }.encode_table_version(table_name, version)?;
+
+        self.set(key, value)
+    }
+
     fn table<'a>(
         &'a self,
         table_cache: &'a TableCache,
@@ -543,9 +1278,20 @@ pub trait Transaction: Sized {
 
         // `TableCache` is not theoretically used in `table_collect` because ColumnCatalog should not depend on other Column
         self.table_collect(&table_name)?
-            .map(|(columns, indexes)| {
+            .map(|(mut columns, indexes)| {
+                for column in columns.iter_mut() {
+                    // decoded just now, so not shared yet
+                    if let Some(column) = Arc::get_mut(&mut column.0) {
+                        self.intern_column(column);
+                    }
+                }
                 table_cache.get_or_insert(table_name.clone(), |_| {
-                    TableCatalog::reload(table_name, columns, indexes)
+                    let meta = self
+                        .get(&unsafe { &*self.table_codec() }.encode_root_table_key(&table_name))?
+                        .map(|bytes| TableCodec::decode_root_table::<Self>(&bytes))
+                        .transpose()?;
+
+                    TableCatalog::reload(table_name, columns, indexes, meta)
                 })
             })
             .transpose()
@@ -565,8 +1311,79 @@ pub trait Transaction: Sized {
         Ok(metas)
     }
 
+    /// Writes the keys of the tuples and the indexes of every table again from the tuples, for a
+    /// database written before the values of the keys were tagged as NULL or not, see
+    /// [`memcomparable`]. The tuples of a table are read before their keys are written.
+    fn upgrade_keys(&self, table_cache: &TableCache) -> Result<(), DatabaseError> {
+        let table_codec = unsafe { &*self.table_codec() };
+
+        for meta in self.table_metas()? {
+            // the rows of a foreign table are not stored
+            if meta.foreign.is_some() {
+                continue;
+            }
+            let table_name = meta.table_name;
+            let table = self
+                .table(table_cache, table_name.clone())?
+                .cloned()
+                .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+            let schema = table.columns().cloned().collect_vec();
+            let mut tuples = Vec::new();
+            let mut iter = self.read(
+                table_cache,
+                table_name.clone(),
+                (None, None),
+                schema.iter().cloned().enumerate().collect(),
+                true,
+                false,
+            )?;
+            while let Some(tuple) = iter.next_tuple()? {
+                tuples.push(tuple);
+            }
+            drop(iter);
+
+            let (min, max) = table_codec.tuple_bound(&table_name);
+            self._drop_data(min, max)?;
+            let mut indexes = Vec::with_capacity(table.indexes.len());
+            for index_meta in table
+                .indexes
+                .iter()
+                .filter(|index_meta| !matches!(index_meta.ty, IndexType::PrimaryKey { .. }))
+            {
+                self.clear_index(&table_name, index_meta.id)?;
+                indexes.push((index_meta, index_meta.column_exprs(&table)?));
+            }
+            let tuple_codec = TupleCodecEnum::instance(&table)?;
+
+            for mut tuple in tuples {
+                let (key, value) =
+                    table_codec.encode_tuple(&table_name, &mut tuple, &tuple_codec)?;
+                self.set(key, value)?;
+                let Some(tuple_id) = tuple.pk.as_ref() else {
+                    continue;
+                };
+
+                for (index_meta, column_exprs) in indexes.iter() {
+                    let Some(value) = DataValue::values_to_tuple(Projection::projection(
+                        &tuple,
+                        column_exprs,
+                        &schema,
+                    )?) else {
+                        continue;
+                    };
+                    self.add_index(
+                        &table_name,
+                        Index::new(index_meta.id, &value, index_meta.ty),
+                        tuple_id,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn save_table_meta(
-        &mut self,
+        &self,
         meta_cache: &StatisticsMetaCache,
         table_name: &TableName,
         path: String,
@@ -581,6 +1398,7 @@ pub trait Transaction: Sized {
             path,
         );
         self.set(key, value)?;
+        self.bump_statistics_version(table_name)?;
 
         Ok(())
     }
@@ -597,19 +1415,71 @@ pub trait Transaction: Sized {
     }
 
     fn remove_table_meta(
-        &mut self,
+        &self,
         meta_cache: &StatisticsMetaCache,
         table_name: &TableName,
         index_id: IndexId,
     ) -> Result<(), DatabaseError> {
         let key = unsafe { &*self.table_codec() }.encode_statistics_path_key(table_name, index_id);
         self.remove(&key)?;
+        self.bump_statistics_version(table_name)?;
 
         meta_cache.remove(&(table_name.clone(), index_id));
 
         Ok(())
     }
 
+    /// Records that the given columns were modified since the table was last analyzed.
+    ///
+    /// A column that already carries a marker is left alone, so the markers stay few until the next `ANALYZE`.
+    fn mark_statistics_modified(
+        &self,
+        table_name: &str,
+        column_ids: &[ColumnId],
+    ) -> Result<(), DatabaseError> {
+        for column_id in column_ids {
+            let (min, max) = unsafe { &*self.table_codec() }
+                .statistics_modified_bound(table_name, Some(column_id));
+            if self
+                .range(Bound::Included(min), Bound::Included(max))?
+                .try_next()?
+                .is_some()
+            {
+                continue;
+            }
+            let (key, value) =
+                unsafe { &*self.table_codec() }.encode_statistics_modified(table_name, column_id);
+            self.set(key, value)?;
+        }
+
+        Ok(())
+    }
+
+    fn statistics_modified_columns(
+        &self,
+        table_name: &str,
+    ) -> Result<HashSet<ColumnId>, DatabaseError> {
+        let (min, max) =
+            unsafe { &*self.table_codec() }.statistics_modified_bound(table_name, None);
+        let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
+        let mut column_ids = HashSet::new();
+
+        while let Some((key, _)) = iter.try_next()? {
+            column_ids.insert(TableCodec::decode_statistics_modified_key(&key));
+        }
+        Ok(column_ids)
+    }
+
+    fn clear_statistics_modified(
+        &self,
+        table_name: &str,
+        column_id: &ColumnId,
+    ) -> Result<(), DatabaseError> {
+        let (min, max) =
+            unsafe { &*self.table_codec() }.statistics_modified_bound(table_name, Some(column_id));
+        self._drop_data(min, max)
+    }
+
     fn meta_loader<'a>(
         &'a self,
         meta_cache: &'a StatisticsMetaCache,
@@ -652,7 +1522,7 @@ pub trait Transaction: Sized {
         Ok((!columns.is_empty()).then_some((columns, index_metas)))
     }
 
-    fn _drop_data(&mut self, min: BumpBytes, max: BumpBytes) -> Result<(), DatabaseError> {
+    fn _drop_data(&self, min: BumpBytes, max: BumpBytes) -> Result<(), DatabaseError> {
         let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
         let mut data_keys = vec![];
 
@@ -668,10 +1538,7 @@ pub trait Transaction: Sized {
         Ok(())
     }
 
-    fn create_index_meta_from_column(
-        &mut self,
-        table: &mut TableCatalog,
-    ) -> Result<(), DatabaseError> {
+    fn create_index_meta_from_column(&self, table: &mut TableCatalog) -> Result<(), DatabaseError> {
         let table_name = table.name.clone();
         let mut primary_keys = Vec::new();
 
@@ -710,9 +1577,31 @@ pub trait Transaction: Sized {
 
     fn get(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError>;
 
-    fn set(&mut self, key: BumpBytes, value: BumpBytes) -> Result<(), DatabaseError>;
+    /// The values of the keys, fetched in one batch by the storages that support it.
+    fn multi_get(&self, keys: &[BumpBytes]) -> Result<Vec<Option<Bytes>>, DatabaseError> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
 
-    fn remove(&mut self, key: &[u8]) -> Result<(), DatabaseError>;
+    /// Keys splitting `min..=max` into at most `max_splits` ranges, estimated by the storages
+    /// that keep the size of their key ranges, the others never split.
+    fn approximate_split_points(
+        &self,
+        _min: &[u8],
+        _max: &[u8],
+        _max_splits: usize,
+    ) -> Result<Vec<Bytes>, DatabaseError> {
+        Ok(Vec::new())
+    }
+
+    /// Called before a statement writes into the table, fails with [`DatabaseError::Throttled`]
+    /// when the storage wants the writes to back off, the others never throttle.
+    fn throttle_write(&self, _table_name: &str) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn set(&self, key: BumpBytes, value: BumpBytes) -> Result<(), DatabaseError>;
+
+    fn remove(&self, key: &[u8]) -> Result<(), DatabaseError>;
 
     fn range<'a>(
         &'a self,
@@ -720,6 +1609,23 @@ pub trait Transaction: Sized {
         max: Bound<BumpBytes<'a>>,
     ) -> Result<Self::IterType<'a>, DatabaseError>;
 
+    /// Same as [`Transaction::range`], but iterates from `max` down to `min`.
+    fn range_rev<'a>(
+        &'a self,
+        min: Bound<BumpBytes<'a>>,
+        max: Bound<BumpBytes<'a>>,
+    ) -> Result<Self::IterType<'a>, DatabaseError>;
+
+    /// Marks the writes so far, the next [`Transaction::rollback_to_savepoint`] undoes those after
+    /// it.
+    fn set_savepoint(&self) -> Result<(), DatabaseError>;
+
+    /// Undoes the writes since the last savepoint and drops it.
+    fn rollback_to_savepoint(&self) -> Result<(), DatabaseError>;
+
+    /// Drops the last savepoint, keeping the writes since it.
+    fn release_savepoint(&self) -> Result<(), DatabaseError>;
+
     fn commit(self) -> Result<(), DatabaseError>;
 }
 
@@ -758,7 +1664,13 @@ impl IndexImplEnum {
         match index_type {
             IndexType::PrimaryKey { .. } => IndexImplEnum::PrimaryKey(PrimaryKeyIndexImpl),
             IndexType::Unique => IndexImplEnum::Unique(UniqueIndexImpl),
-            IndexType::Normal => IndexImplEnum::Normal(NormalIndexImpl),
+            // the entries of an inverted index are the tokens or trigrams of the text, those of
+            // an IVF index the lists of the vectors and of a Z-order index the codes of the points
+            IndexType::Normal
+            | IndexType::FullText
+            | IndexType::Trigram
+            | IndexType::Vector
+            | IndexType::ZOrder => IndexImplEnum::Normal(NormalIndexImpl),
             IndexType::Composite => IndexImplEnum::Composite(CompositeIndexImpl),
         }
     }
@@ -775,12 +1687,25 @@ struct IndexImplParams<'a, T: Transaction> {
 
     index_meta: IndexMetaRef,
     table_name: &'a str,
-    table_types: Vec<LogicalType>,
+    tuple_codec: TupleCodecEnum,
     with_pk: bool,
+    reverse: bool,
     tx: &'a T,
 }
 
-impl<T: Transaction> IndexImplParams<'_, T> {
+impl<'a, T: Transaction> IndexImplParams<'a, T> {
+    fn range(
+        &self,
+        min: Bound<BumpBytes<'a>>,
+        max: Bound<BumpBytes<'a>>,
+    ) -> Result<T::IterType<'a>, DatabaseError> {
+        if self.reverse {
+            self.tx.range_rev(min, max)
+        } else {
+            self.tx.range(min, max)
+        }
+    }
+
     #[inline]
     pub(crate) fn value_ty(&self) -> &LogicalType {
         &self.index_meta.value_ty
@@ -811,7 +1736,7 @@ impl<T: Transaction> IndexImplParams<'_, T> {
             .get(&key)?
             .map(|bytes| {
                 TableCodec::decode_tuple(
-                    &self.table_types,
+                    &self.tuple_codec,
                     pk_indices,
                     &self.projections,
                     &self.tuple_schema_ref,
@@ -880,7 +1805,7 @@ impl<'bytes, T: Transaction + 'bytes> IndexImpl<'bytes, T> for PrimaryKeyIndexIm
         params: &IndexImplParams<T>,
     ) -> Result<Tuple, DatabaseError> {
         TableCodec::decode_tuple(
-            &params.table_types,
+            &params.tuple_codec,
             pk_indices,
             &params.projections,
             &params.tuple_schema_ref,
@@ -900,7 +1825,7 @@ impl<'bytes, T: Transaction + 'bytes> IndexImpl<'bytes, T> for PrimaryKeyIndexIm
             .get(&unsafe { &*params.table_codec() }.encode_tuple_key(params.table_name, value)?)?
             .map(|bytes| {
                 TableCodec::decode_tuple(
-                    &params.table_types,
+                    &params.tuple_codec,
                     pk_indices,
                     &params.projections,
                     &params.tuple_schema_ref,
@@ -990,9 +1915,7 @@ impl<'bytes, T: Transaction + 'bytes> IndexImpl<'bytes, T> for NormalIndexImpl {
         let min = self.bound_key(params, value, false)?;
         let max = self.bound_key(params, value, true)?;
 
-        let iter = params
-            .tx
-            .range(Bound::Included(min), Bound::Included(max))?;
+        let iter = params.range(Bound::Included(min), Bound::Included(max))?;
         Ok(IndexResult::Scope(iter))
     }
 
@@ -1031,9 +1954,7 @@ impl<'bytes, T: Transaction + 'bytes> IndexImpl<'bytes, T> for CompositeIndexImp
         let min = self.bound_key(params, value, false)?;
         let max = self.bound_key(params, value, true)?;
 
-        let iter = params
-            .tx
-            .range(Bound::Included(min), Bound::Included(max))?;
+        let iter = params.range(Bound::Included(min), Bound::Included(max))?;
         Ok(IndexResult::Scope(iter))
     }
 
@@ -1056,7 +1977,7 @@ impl<'bytes, T: Transaction + 'bytes> IndexImpl<'bytes, T> for CompositeIndexImp
 pub struct TupleIter<'a, T: Transaction + 'a> {
     offset: usize,
     limit: Option<usize>,
-    table_types: Vec<LogicalType>,
+    tuple_codec: TupleCodecEnum,
     tuple_columns: Arc<Vec<ColumnRef>>,
     remap_pk_indices: Vec<usize>,
     projections: Vec<usize>,
@@ -1082,7 +2003,7 @@ impl<'a, T: Transaction + 'a> Iter for TupleIter<'a, T> {
                 *limit -= 1;
             }
             let tuple = TableCodec::decode_tuple(
-                &self.table_types,
+                &self.tuple_codec,
                 &self.remap_pk_indices,
                 &self.projections,
                 &self.tuple_columns,
@@ -1097,6 +2018,108 @@ impl<'a, T: Transaction + 'a> Iter for TupleIter<'a, T> {
     }
 }
 
+pub struct KeysIter {
+    tuple_codec: TupleCodecEnum,
+    tuple_columns: Arc<Vec<ColumnRef>>,
+    remap_pk_indices: Vec<usize>,
+    projections: Vec<usize>,
+    with_pk: bool,
+    values: IntoIter<Bytes>,
+}
+
+impl Iter for KeysIter {
+    fn next_tuple(&mut self) -> Result<Option<Tuple>, DatabaseError> {
+        self.values
+            .next()
+            .map(|value| {
+                TableCodec::decode_tuple(
+                    &self.tuple_codec,
+                    &self.remap_pk_indices,
+                    &self.projections,
+                    &self.tuple_columns,
+                    &value,
+                    self.with_pk,
+                )
+            })
+            .transpose()
+    }
+}
+
+pub struct SkipIter<'a, T: Transaction> {
+    offset: usize,
+    limit: Option<usize>,
+    tx: &'a T,
+    table_cache: &'a TableCache,
+    table_name: TableName,
+    columns: BTreeMap<usize, ColumnRef>,
+    // the position and the column of the leading column of the index
+    leading: (usize, ColumnRef),
+    index_meta: IndexMetaRef,
+    range: Range,
+    with_pk: bool,
+    // past the values of the leading column read
+    lower: Bound<DataValue>,
+    iter: Option<IndexIter<'a, T>>,
+}
+
+impl<T: Transaction> Iter for SkipIter<'_, T> {
+    fn next_tuple(&mut self) -> Result<Option<Tuple>, DatabaseError> {
+        loop {
+            if matches!(self.limit, Some(0)) {
+                return Ok(None);
+            }
+            if let Some(iter) = self.iter.as_mut() {
+                if let Some(tuple) = iter.next_tuple()? {
+                    if self.offset > 0 {
+                        self.offset -= 1;
+                        continue;
+                    }
+                    if let Some(limit) = self.limit.as_mut() {
+                        *limit -= 1;
+                    }
+                    return Ok(Some(tuple));
+                }
+                self.iter = None;
+            }
+            // seek the next value of the leading column
+            let mut probe = self.tx.read_by_index(
+                self.table_cache,
+                self.table_name.clone(),
+                (None, Some(1)),
+                BTreeMap::from([self.leading.clone()]),
+                self.index_meta.clone(),
+                vec![Range::Scope {
+                    min: self.lower.clone(),
+                    max: Bound::Unbounded,
+                }],
+                false,
+            )?;
+            let Some(Tuple { mut values, .. }) = probe.next_tuple()? else {
+                return Ok(None);
+            };
+            let value = values.swap_remove(0);
+            self.lower = Bound::Excluded(DataValue::Tuple(vec![value.clone()], true));
+
+            let Some(range) = self.range.combining_eqs(&[Range::Eq(value)]) else {
+                continue;
+            };
+            let ranges = match range {
+                Range::SortedRanges(ranges) => ranges,
+                range => vec![range],
+            };
+            self.iter = Some(self.tx.read_by_index(
+                self.table_cache,
+                self.table_name.clone(),
+                (None, None),
+                self.columns.clone(),
+                self.index_meta.clone(),
+                ranges,
+                self.with_pk,
+            )?);
+        }
+    }
+}
+
 pub struct IndexIter<'a, T: Transaction> {
     offset: usize,
     limit: Option<usize>,
@@ -1107,6 +2130,8 @@ pub struct IndexIter<'a, T: Transaction> {
     // for buffering data
     ranges: IntoIter<Range>,
     state: IndexIterState<'a, T>,
+    // a row of an inverted index is found once for each of the searched entries of its text
+    seen_tuple_ids: Option<HashSet<TupleId>>,
 }
 
 pub enum IndexIterState<'a, T: Transaction + 'a> {
@@ -1116,6 +2141,13 @@ pub enum IndexIterState<'a, T: Transaction + 'a> {
 }
 
 impl<'a, T: Transaction + 'a> IndexIter<'a, T> {
+    /// Iterates the ranges and the index entries within them in descending order.
+    pub(crate) fn reverse(mut self) -> Self {
+        self.params.reverse = !self.params.reverse;
+        self.ranges = self.ranges.rev().collect_vec().into_iter();
+        self
+    }
+
     fn offset_move(offset: &mut usize) -> bool {
         if *offset > 0 {
             offset.sub_assign(1);
@@ -1197,7 +2229,7 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
                             let mut encode_max = bound_encode(max, true)?;
                             check_bound(&mut encode_max, bound_max);
 
-                            let iter = self.params.tx.range(encode_min, encode_max)?;
+                            let iter = self.params.range(encode_min, encode_max)?;
                             self.state = IndexIterState::Range(iter);
                         }
                         Range::Eq(mut val) => {
@@ -1225,6 +2257,11 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
                 }
                 IndexIterState::Range(iter) => {
                     while let Some((_, bytes)) = iter.try_next()? {
+                        if let Some(seen_tuple_ids) = self.seen_tuple_ids.as_mut() {
+                            if !seen_tuple_ids.insert(TableCodec::decode_index(&bytes)?) {
+                                continue;
+                            }
+                        }
                         if Self::offset_move(&mut self.offset) {
                             continue;
                         }
@@ -1270,6 +2307,7 @@ mod test {
     use crate::expression::range_detacher::Range;
     use crate::storage::rocksdb::{RocksStorage, RocksTransaction};
     use crate::storage::table_codec::TableCodec;
+    use crate::storage::tuple_codec::TupleCodecEnum;
     use crate::storage::{
         IndexIter, InnerIter, Iter, StatisticsMetaCache, Storage, TableCache, Transaction,
     };
@@ -1345,12 +2383,12 @@ mod test {
     fn test_table_create_drop() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
 
-        build_table(&table_cache, &mut transaction)?;
+        build_table(&table_cache, &transaction)?;
 
-        let fn_assert = |transaction: &mut RocksTransaction,
+        let fn_assert = |transaction: &RocksTransaction,
                          table_cache: &TableCache|
          -> Result<(), DatabaseError> {
             let table = transaction
@@ -1383,7 +2421,7 @@ mod test {
             assert_eq!(
                 c1_column.summary(),
                 &ColumnSummary {
-                    name: "c1".to_string(),
+                    name: "c1".into(),
                     relation: ColumnRelation::Table {
                         column_id: c1_column_id,
                         table_name: Arc::new("t1".to_string()),
@@ -1401,7 +2439,7 @@ mod test {
             assert_eq!(
                 c2_column.summary(),
                 &ColumnSummary {
-                    name: "c2".to_string(),
+                    name: "c2".into(),
                     relation: ColumnRelation::Table {
                         column_id: c2_column_id,
                         table_name: Arc::new("t1".to_string()),
@@ -1419,7 +2457,7 @@ mod test {
             assert_eq!(
                 c3_column.summary(),
                 &ColumnSummary {
-                    name: "c3".to_string(),
+                    name: "c3".into(),
                     relation: ColumnRelation::Table {
                         column_id: c3_column_id,
                         table_name: Arc::new("t1".to_string()),
@@ -1434,9 +2472,9 @@ mod test {
 
             Ok(())
         };
-        fn_assert(&mut transaction, &table_cache)?;
+        fn_assert(&transaction, &table_cache)?;
         fn_assert(
-            &mut transaction,
+            &transaction,
             &Arc::new(SharedLruCache::new(4, 1, RandomState::new())?),
         )?;
 
@@ -1448,23 +2486,19 @@ mod test {
         let table_codec = TableCodec::default();
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
 
-        build_table(&table_cache, &mut transaction)?;
+        build_table(&table_cache, &transaction)?;
 
         let tuples = build_tuples();
+        let tuple_codec = TupleCodecEnum::instance(
+            transaction
+                .table(&table_cache, Arc::new("t1".to_string()))?
+                .unwrap(),
+        )?;
         for tuple in tuples.iter().cloned() {
-            transaction.append_tuple(
-                "t1",
-                tuple,
-                &[
-                    LogicalType::Integer,
-                    LogicalType::Boolean,
-                    LogicalType::Integer,
-                ],
-                false,
-            )?;
+            transaction.append_tuple("t1", tuple, &tuple_codec, false)?;
         }
         {
             let mut tuple_iter = transaction.read(
@@ -1473,6 +2507,7 @@ mod test {
                 (None, None),
                 full_columns(),
                 true,
+                false,
             )?;
 
             assert_eq!(tuple_iter.next_tuple()?.unwrap(), tuples[0]);
@@ -1499,6 +2534,7 @@ mod test {
                 (None, None),
                 full_columns(),
                 true,
+                false,
             )?;
 
             assert_eq!(tuple_iter.next_tuple()?.unwrap(), tuples[0]);
@@ -1522,10 +2558,10 @@ mod test {
         let table_codec = TableCodec::default();
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
 
-        build_table(&table_cache, &mut transaction)?;
+        build_table(&table_cache, &transaction)?;
         let (c2_column_id, c3_column_id) = {
             let t1_table = transaction
                 .table(&table_cache, Arc::new("t1".to_string()))?
@@ -1552,7 +2588,7 @@ mod test {
             IndexType::Composite,
         )?;
 
-        let fn_assert = |transaction: &mut RocksTransaction,
+        let fn_assert = |transaction: &RocksTransaction,
                          table_cache: &TableCache|
          -> Result<(), DatabaseError> {
             let table = transaction
@@ -1577,9 +2613,9 @@ mod test {
 
             Ok(())
         };
-        fn_assert(&mut transaction, &table_cache)?;
+        fn_assert(&transaction, &table_cache)?;
         fn_assert(
-            &mut transaction,
+            &transaction,
             &Arc::new(SharedLruCache::new(4, 1, RandomState::new())?),
         )?;
         {
@@ -1656,10 +2692,10 @@ mod test {
         let table_codec = TableCodec::default();
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
 
-        build_table(&table_cache, &mut transaction)?;
+        build_table(&table_cache, &transaction)?;
         let t1_table = transaction
             .table(&table_cache, Arc::new("t1".to_string()))?
             .unwrap();
@@ -1691,17 +2727,13 @@ mod test {
         for (tuple_id, index) in indexes.iter().cloned() {
             transaction.add_index("t1", index, &tuple_id)?;
         }
+        let tuple_codec = TupleCodecEnum::instance(
+            transaction
+                .table(&table_cache, Arc::new("t1".to_string()))?
+                .unwrap(),
+        )?;
         for tuple in tuples.iter().cloned() {
-            transaction.append_tuple(
-                "t1",
-                tuple,
-                &[
-                    LogicalType::Integer,
-                    LogicalType::Boolean,
-                    LogicalType::Integer,
-                ],
-                false,
-            )?;
+            transaction.append_tuple("t1", tuple, &tuple_codec, false)?;
         }
         {
             let mut index_iter = build_index_iter(&transaction, &table_cache, c3_column_id)?;
@@ -1744,11 +2776,11 @@ mod test {
     fn test_column_add_drop() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let meta_cache = StatisticsMetaCache::new(4, 1, RandomState::new())?;
 
-        build_table(&table_cache, &mut transaction)?;
+        build_table(&table_cache, &transaction)?;
         let table_name = Arc::new("t1".to_string());
 
         let new_column = ColumnCatalog::new(
@@ -1811,7 +2843,7 @@ mod test {
                 table_state.plan("select c1, c3 from t1 inner join t2 on c1 = c3 and c1 > 1")?,
             ),
         };
-        let mut transaction = table_state.storage.transaction()?;
+        let transaction = table_state.storage.transaction()?;
         transaction.create_view(&table_state.view_cache, view.clone(), true)?;
 
         assert_eq!(
@@ -1,34 +1,323 @@
+use crate::catalog::interner::StringInterner;
 use crate::errors::DatabaseError;
+use crate::replication::{Change, ChangeLog};
 use crate::storage::table_codec::{BumpBytes, Bytes, TableCodec};
-use crate::storage::{InnerIter, Storage, Transaction};
+use crate::storage::{InnerIter, Storage, Transaction, WritePressure};
+use crate::utils::lru::SharedLruCache;
+use bumpalo::Bump;
+use parking_lot::Mutex;
 use rocksdb::{
-    DBIteratorWithThreadMode, Direction, IteratorMode, OptimisticTransactionDB, SliceTransform,
+    DBIteratorWithThreadMode, Direction, IteratorMode, OptimisticTransactionDB,
+    OptimisticTransactionOptions, ReadOptions, SliceTransform, SnapshotWithThreadMode,
+    WriteOptions,
 };
-use std::collections::Bound;
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, Bound, HashMap};
+use std::fs;
+use std::hash::RandomState;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
+use ulid::Ulid;
+
+/// What the reads of a transaction see of the transactions committed since it began.
+///
+/// Both are optimistic: a commit fails with a conflict if another transaction committed a key it
+/// wrote, after the transaction first read or wrote the key, or since it began for
+/// `RepeatableRead`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IsolationLevel {
+    /// Each read sees the last committed value of its keys
+    #[default]
+    ReadCommitted,
+    /// The reads see a snapshot of the database as of the beginning of the transaction
+    RepeatableRead,
+}
+
+/// How the write-ahead log of RocksDB keeps the commits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WalOptions {
+    /// The commits skip the log, which is faster, but the last ones are lost by a crash
+    pub disabled: bool,
+    /// Each commit waits for the log to be synced to the disk, so that it survives a crash of the
+    /// machine and not only of the process
+    pub sync: bool,
+    /// Directory of the log files, e.g. on a faster disk, the directory of the database by default
+    pub dir: Option<PathBuf>,
+    /// Bytes of log files after which the memtables they cover are flushed, by default 4 times
+    /// the size of the memtables
+    pub max_total_size: Option<u64>,
+}
+
+/// When the writes fail with [`DatabaseError::Throttled`], to be retried later, instead of
+/// stalling in RocksDB until it catches up, e.g. for the bulk loaders to back off. Nothing is
+/// throttled by default.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WriteThrottle {
+    /// Bytes the compactions are estimated to rewrite above which the writes are throttled
+    pub max_pending_compaction_bytes: Option<u64>,
+    /// Memtables waiting to be flushed above which the writes are throttled
+    pub max_immutable_memtables: Option<u64>,
+    /// Bytes of the write-ahead log files above which the writes are throttled
+    pub max_wal_size: Option<u64>,
+    /// Statements writing into a table per second, beyond which they are throttled, `0` throttles
+    /// them all
+    pub table_writes_per_second: BTreeMap<String, u32>,
+}
+
+/// How [`RocksStorage`] opens its database, set by the `DataBaseBuilder`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StorageOptions {
+    /// Bytes of the block cache of the reads, `40MiB` by default
+    pub block_cache_size: usize,
+    /// Threads flushing and compacting in the background, as RocksDB sets them by default if `None`
+    pub parallelism: Option<usize>,
+    /// Keys of a block between two keys stored whole, the others only store what follows the
+    /// prefix they share with the previous key, for the data and the index blocks. `None` keeps
+    /// the intervals of RocksDB, `16` for the data blocks and `1` for the index blocks.
+    ///
+    /// The keys of an index on long strings sharing their prefixes, e.g. URLs or paths, take less
+    /// space with a larger interval, at the cost of slower seeks in a block.
+    pub key_restart_interval: Option<usize>,
+    pub wal: WalOptions,
+    pub isolation_level: IsolationLevel,
+    pub write_throttle: WriteThrottle,
+}
+
+impl Default for StorageOptions {
+    fn default() -> Self {
+        StorageOptions {
+            block_cache_size: 40 * 1_024 * 1_024,
+            parallelism: None,
+            key_restart_interval: None,
+            wal: Default::default(),
+            isolation_level: Default::default(),
+            write_throttle: Default::default(),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct RocksStorage {
     pub inner: Arc<OptimisticTransactionDB>,
+    pub(crate) change_log: Option<Arc<ChangeLog>>,
+    write_options: Arc<WriteOptions>,
+    isolation_level: IsolationLevel,
+    throttler: Arc<Throttler>,
+    interner: Arc<StringInterner>,
+}
+
+// the throttle of the writes of a storage, shared by its transactions
+struct Throttler {
+    options: WriteThrottle,
+    wal_dir: PathBuf,
+    // the writes left to each table and when they were counted
+    tokens: Mutex<HashMap<String, (f64, Instant)>>,
+}
+
+impl Throttler {
+    fn check(&self, db: &OptimisticTransactionDB, table_name: &str) -> Result<(), DatabaseError> {
+        let throttled = |reason: String| {
+            Err(DatabaseError::Throttled {
+                table: table_name.to_string(),
+                reason,
+            })
+        };
+        let WriteThrottle {
+            max_pending_compaction_bytes,
+            max_immutable_memtables,
+            max_wal_size,
+            table_writes_per_second,
+        } = &self.options;
+
+        if max_pending_compaction_bytes.is_some()
+            || max_immutable_memtables.is_some()
+            || max_wal_size.is_some()
+        {
+            let pressure = write_pressure(db, &self.wal_dir)?;
+            let over = |value: u64, max: &Option<u64>| max.is_some_and(|max| value > max);
+
+            if over(
+                pressure.pending_compaction_bytes,
+                max_pending_compaction_bytes,
+            ) {
+                return throttled(format!(
+                    "{} bytes pending compaction",
+                    pressure.pending_compaction_bytes
+                ));
+            }
+            if over(pressure.immutable_memtables, max_immutable_memtables) {
+                return throttled(format!(
+                    "{} memtables pending flush",
+                    pressure.immutable_memtables
+                ));
+            }
+            if over(pressure.wal_size, max_wal_size) {
+                return throttled(format!("{} bytes of write-ahead log", pressure.wal_size));
+            }
+        }
+        if let Some(&max_rate) = table_writes_per_second.get(table_name) {
+            if max_rate == 0 {
+                return throttled("no writes allowed".to_string());
+            }
+            let rate = max_rate as f64;
+            let now = Instant::now();
+            let mut tokens = self.tokens.lock();
+            let (left, counted_at) = tokens.entry(table_name.to_string()).or_insert((rate, now));
+
+            *left = (*left + now.duration_since(*counted_at).as_secs_f64() * rate).min(rate);
+            *counted_at = now;
+            if *left < 1.0 {
+                return throttled(format!("more than {max_rate} writes per second"));
+            }
+            *left -= 1.0;
+        }
+        Ok(())
+    }
+}
+
+fn write_pressure(
+    db: &OptimisticTransactionDB,
+    wal_dir: &Path,
+) -> Result<WritePressure, DatabaseError> {
+    let property = |name: &str| -> Result<u64, DatabaseError> {
+        Ok(db.property_int_value(name)?.unwrap_or(0))
+    };
+    let mut wal_size = 0;
+
+    for entry in fs::read_dir(wal_dir)? {
+        let entry = entry?;
+
+        if entry.path().extension().is_some_and(|ext| ext == "log") {
+            wal_size += entry.metadata()?.len();
+        }
+    }
+    Ok(WritePressure {
+        pending_compaction_bytes: property("rocksdb.estimate-pending-compaction-bytes")?,
+        immutable_memtables: property("rocksdb.num-immutable-mem-table")?,
+        wal_size,
+    })
 }
 
 impl RocksStorage {
     pub fn new(path: impl Into<PathBuf> + Send) -> Result<Self, DatabaseError> {
+        Self::with_options(path, &StorageOptions::default())
+    }
+
+    pub fn with_options(
+        path: impl Into<PathBuf> + Send,
+        options: &StorageOptions,
+    ) -> Result<Self, DatabaseError> {
+        let path = path.into();
         let mut bb = rocksdb::BlockBasedOptions::default();
-        bb.set_block_cache(&rocksdb::Cache::new_lru_cache(40 * 1_024 * 1_024));
+        bb.set_block_cache(&rocksdb::Cache::new_lru_cache(options.block_cache_size));
         bb.set_whole_key_filtering(false);
+        if let Some(interval) = options.key_restart_interval {
+            let interval = interval.clamp(1, i32::MAX as usize) as i32;
+
+            bb.set_block_restart_interval(interval);
+            // the separators of the index blocks share the prefixes of the keys as well
+            bb.set_index_block_restart_interval(interval);
+        }
 
         let mut opts = rocksdb::Options::default();
         opts.set_block_based_table_factory(&bb);
         opts.create_if_missing(true);
         opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(4));
+        if let Some(parallelism) = options.parallelism {
+            let parallelism = parallelism.clamp(1, i32::MAX as usize) as i32;
+
+            opts.increase_parallelism(parallelism);
+            opts.set_max_background_jobs(parallelism);
+        }
+        if let Some(dir) = &options.wal.dir {
+            opts.set_wal_dir(dir);
+        }
+        if let Some(max_total_size) = options.wal.max_total_size {
+            opts.set_max_total_wal_size(max_total_size);
+        }
+        let mut write_options = WriteOptions::default();
+        write_options.disable_wal(options.wal.disabled);
+        write_options.set_sync(options.wal.sync);
 
-        let storage = OptimisticTransactionDB::open(&opts, path.into())?;
+        let storage = OptimisticTransactionDB::open(&opts, &path)?;
+        let throttler = Throttler {
+            options: options.write_throttle.clone(),
+            wal_dir: options.wal.dir.clone().unwrap_or(path),
+            tokens: Default::default(),
+        };
 
-        Ok(RocksStorage {
+        let storage = RocksStorage {
             inner: Arc::new(storage),
-        })
+            change_log: None,
+            write_options: Arc::new(write_options),
+            isolation_level: options.isolation_level,
+            throttler: Arc::new(throttler),
+            interner: Default::default(),
+        };
+        check_format_version(&storage)?;
+
+        Ok(storage)
+    }
+
+    /// Records the changes of each commit in a log of the last `capacity` transactions, which
+    /// [`Publisher`](crate::replication::Publisher) streams to the followers.
+    ///
+    /// Tips: the commits that write something are serialized by the log
+    pub fn with_change_log(mut self, capacity: usize) -> Self {
+        self.change_log = Some(Arc::new(ChangeLog::new(capacity)));
+        self
+    }
+}
+
+/// The version of the encoding of the keys a database is written in, stored by its first open.
+///
+/// - 1: the databases written before it was stored
+/// - 2: the values of the keys are tagged as NULL or not, and NULL sorts first, see
+///   `storage::memcomparable`
+pub const STORAGE_FORMAT_VERSION: u32 = 2;
+
+/// Stores the version of the encoding of the keys in a new database, and writes the keys of one
+/// written in a former version again as it is first opened, in one transaction that stores the
+/// version as well. A database of a newer version is refused: its keys would neither be found nor
+/// sort as they were written.
+fn check_format_version(storage: &RocksStorage) -> Result<(), DatabaseError> {
+    let table_codec = TableCodec::default();
+    let key = table_codec.encode_format_version_key();
+
+    let found = match storage.inner.get(&key)? {
+        Some(value) => value
+            .as_slice()
+            .try_into()
+            .map(u32::from_be_bytes)
+            .map_err(|_| DatabaseError::InvalidValue("storage format version".to_string()))?,
+        None if storage.inner.iterator(IteratorMode::Start).next().is_none() => {
+            storage
+                .inner
+                .put(&key, STORAGE_FORMAT_VERSION.to_be_bytes())?;
+            return Ok(());
+        }
+        None => 1,
+    };
+    match found.cmp(&STORAGE_FORMAT_VERSION) {
+        Ordering::Equal => Ok(()),
+        Ordering::Less => {
+            let transaction = storage.transaction()?;
+            let table_cache = SharedLruCache::new(4, 1, RandomState::new())?;
+            let arena = Bump::new();
+
+            transaction.upgrade_keys(&table_cache)?;
+            transaction.set(
+                BumpBytes::from_iter_in(key.iter().copied(), &arena),
+                BumpBytes::from_iter_in(STORAGE_FORMAT_VERSION.to_be_bytes(), &arena),
+            )?;
+            transaction.commit()
+        }
+        Ordering::Greater => Err(DatabaseError::StorageFormatMismatch {
+            found,
+            expected: STORAGE_FORMAT_VERSION,
+        }),
     }
 }
 
@@ -39,16 +328,98 @@ impl Storage for RocksStorage {
         Self: 'a;
 
     fn transaction(&self) -> Result<Self::TransactionType<'_>, DatabaseError> {
+        let mut transaction_options = OptimisticTransactionOptions::default();
+        let snapshot = match self.isolation_level {
+            IsolationLevel::ReadCommitted => None,
+            IsolationLevel::RepeatableRead => {
+                transaction_options.set_snapshot(true);
+                Some(self.inner.snapshot())
+            }
+        };
+
         Ok(RocksTransaction {
-            tx: self.inner.transaction(),
+            db: &self.inner,
+            tx: self
+                .inner
+                .transaction_opt(&self.write_options, &transaction_options),
+            id: Ulid::new(),
+            snapshot,
             table_codec: Default::default(),
+            change_log: self.change_log.as_deref(),
+            changes: RefCell::new(Vec::new()),
+            tuple_counts: RefCell::new(HashMap::new()),
+            savepoints: RefCell::new(Vec::new()),
+            throttler: &self.throttler,
+            interner: &self.interner,
         })
     }
+
+    fn write_pressure(&self) -> Result<WritePressure, DatabaseError> {
+        write_pressure(&self.inner, &self.throttler.wal_dir)
+    }
 }
 
 pub struct RocksTransaction<'db> {
+    db: &'db OptimisticTransactionDB,
     tx: rocksdb::Transaction<'db, OptimisticTransactionDB>,
+    id: Ulid,
+    // the reads see it with `IsolationLevel::RepeatableRead`
+    snapshot: Option<SnapshotWithThreadMode<'db, OptimisticTransactionDB>>,
     table_codec: TableCodec,
+    change_log: Option<&'db ChangeLog>,
+    // the writes take the transaction by a shared reference, as the scans reading it
+    changes: RefCell<Vec<Change>>,
+    tuple_counts: RefCell<HashMap<String, i64>>,
+    savepoints: RefCell<Vec<Savepoint>>,
+    throttler: &'db Throttler,
+    interner: &'db StringInterner,
+}
+
+/// A savepoint of [`Transaction::set_savepoint`], which is set in the transaction of RocksDB by
+/// the first write after it only: RocksDB drops a savepoint by rolling back to it alone, so that
+/// the statements writing nothing leave none behind.
+struct Savepoint {
+    // the changes logged before it
+    changes: usize,
+    tuple_counts: HashMap<String, i64>,
+    is_set: bool,
+    // the savepoints of RocksDB above it, of the savepoints released since it
+    released: usize,
+}
+
+impl<'db> RocksTransaction<'db> {
+    /// Sets the savepoints of RocksDB of those with no write after them yet, before a write.
+    fn before_write(&self) {
+        let mut savepoints = self.savepoints.borrow_mut();
+
+        if savepoints.last().is_some_and(|savepoint| !savepoint.is_set) {
+            for savepoint in savepoints.iter_mut().filter(|savepoint| !savepoint.is_set) {
+                self.tx.set_savepoint();
+                savepoint.is_set = true;
+            }
+        }
+    }
+
+    fn read_options(&self) -> ReadOptions {
+        let mut options = ReadOptions::default();
+
+        if let Some(snapshot) = &self.snapshot {
+            options.set_snapshot(snapshot);
+        }
+        options
+    }
+
+    // same as `rocksdb::Transaction::prefix_iterator`, with the snapshot of the transaction
+    fn prefix_iterator<'a>(
+        &'a self,
+        prefix: &[u8],
+    ) -> DBIteratorWithThreadMode<'a, rocksdb::Transaction<'db, OptimisticTransactionDB>> {
+        let mut options = self.read_options();
+        options.set_prefix_same_as_start(true);
+
+        self.tx
+            .iterator_opt(IteratorMode::From(prefix, Direction::Forward), options)
+    }
 }
 
 impl<'txn> Transaction for RocksTransaction<'txn> {
@@ -62,21 +433,107 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
         &self.table_codec
     }
 
+    #[inline]
+    fn id(&self) -> Ulid {
+        self.id
+    }
+
+    #[inline]
+    fn tuple_counts(&self) -> &RefCell<HashMap<String, i64>> {
+        &self.tuple_counts
+    }
+
+    #[inline]
+    fn interner(&self) -> &StringInterner {
+        self.interner
+    }
+
     #[inline]
     fn get(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError> {
-        Ok(self.tx.get(key)?)
+        Ok(self.tx.get_opt(key, &self.read_options())?)
+    }
+
+    #[inline]
+    fn multi_get(&self, keys: &[BumpBytes]) -> Result<Vec<Option<Bytes>>, DatabaseError> {
+        self.tx
+            .multi_get_opt(keys.iter().map(|key| &key[..]), &self.read_options())
+            .into_iter()
+            .map(|value| Ok(value?))
+            .collect()
     }
 
     #[inline]
-    fn set(&mut self, key: BumpBytes, value: BumpBytes) -> Result<(), DatabaseError> {
-        self.tx.put(key, value)?;
+    fn throttle_write(&self, table_name: &str) -> Result<(), DatabaseError> {
+        self.throttler.check(self.db, table_name)
+    }
+
+    #[inline]
+    /// Splits on the last keys of the SST files overlapping the range, so that each split covers
+    /// about the same number of bytes on disk. The first keys are not used, a file flushed after
+    /// writing tuples starts with the other keys of their table. The writes not flushed yet are
+    /// not counted.
+    fn approximate_split_points(
+        &self,
+        min: &[u8],
+        max: &[u8],
+        max_splits: usize,
+    ) -> Result<Vec<Bytes>, DatabaseError> {
+        if max_splits < 2 {
+            return Ok(Vec::new());
+        }
+        let mut files = self
+            .db
+            .live_files()?
+            .into_iter()
+            .filter_map(|file| {
+                let (Some(start), Some(end)) = (file.start_key, file.end_key) else {
+                    return None;
+                };
+                (start.as_slice() <= max && end.as_slice() >= min).then_some((end, file.size))
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let total = files.iter().map(|(_, size)| *size).sum::<usize>();
+        let step = total.div_ceil(max_splits).max(1);
+        let mut points: Vec<Bytes> = Vec::with_capacity(max_splits - 1);
+        let mut covered = 0;
+
+        for (end, size) in files {
+            covered += size;
+
+            if end.as_slice() > min
+                && end.as_slice() < max
+                && covered >= step * (points.len() + 1)
+                && covered < total
+                && points.last() != Some(&end)
+                && points.len() < max_splits - 1
+            {
+                points.push(end);
+            }
+        }
+        Ok(points)
+    }
+
+    fn set(&self, key: BumpBytes, value: BumpBytes) -> Result<(), DatabaseError> {
+        self.before_write();
+        self.tx.put(&key, &value)?;
+        if self.change_log.is_some() {
+            self.changes
+                .borrow_mut()
+                .push(Change::Put(key.to_vec(), value.to_vec()));
+        }
 
         Ok(())
     }
 
     #[inline]
-    fn remove(&mut self, key: &[u8]) -> Result<(), DatabaseError> {
+    fn remove(&self, key: &[u8]) -> Result<(), DatabaseError> {
+        self.before_write();
         self.tx.delete(key)?;
+        if self.change_log.is_some() {
+            self.changes.borrow_mut().push(Change::Delete(key.to_vec()));
+        }
 
         Ok(())
     }
@@ -112,23 +569,119 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
                 .count();
 
             debug_assert!(len > 0);
-            let mut iter = self.tx.prefix_iterator(&min_bytes[..len]);
+            let mut iter = self.prefix_iterator(&min_bytes[..len]);
             iter.set_mode(lower);
 
-            return Ok(RocksIter { upper: max, iter });
+            return Ok(RocksIter {
+                lower: Bound::Unbounded,
+                upper: max,
+                iter,
+            });
         }
-        let iter = self.tx.iterator(lower);
+        let iter = self.tx.iterator_opt(lower, self.read_options());
 
-        Ok(RocksIter { upper: max, iter })
+        Ok(RocksIter {
+            lower: Bound::Unbounded,
+            upper: max,
+            iter,
+        })
+    }
+
+    #[inline]
+    fn range_rev<'a>(
+        &'a self,
+        min: Bound<BumpBytes<'a>>,
+        max: Bound<BumpBytes<'a>>,
+    ) -> Result<Self::IterType<'a>, DatabaseError> {
+        let upper = match &max {
+            Bound::Included(bytes) | Bound::Excluded(bytes) => {
+                IteratorMode::From(bytes, Direction::Reverse)
+            }
+            Bound::Unbounded => IteratorMode::End,
+        };
+
+        let iter = if let (
+            Bound::Included(min_bytes) | Bound::Excluded(min_bytes),
+            Bound::Included(max_bytes) | Bound::Excluded(max_bytes),
+        ) = (&min, &max)
+        {
+            let len = min_bytes
+                .iter()
+                .zip(max_bytes.iter())
+                .take_while(|(x, y)| x == y)
+                .count();
+
+            debug_assert!(len > 0);
+            let mut iter = self.prefix_iterator(&max_bytes[..len]);
+            iter.set_mode(upper);
+            iter
+        } else {
+            self.tx.iterator_opt(upper, self.read_options())
+        };
+
+        Ok(RocksIter {
+            lower: min,
+            upper: max,
+            iter,
+        })
+    }
+
+    fn set_savepoint(&self) -> Result<(), DatabaseError> {
+        self.savepoints.borrow_mut().push(Savepoint {
+            changes: self.changes.borrow().len(),
+            tuple_counts: self.tuple_counts.borrow().clone(),
+            is_set: false,
+            released: 0,
+        });
+
+        Ok(())
+    }
+
+    fn rollback_to_savepoint(&self) -> Result<(), DatabaseError> {
+        let Some(savepoint) = self.savepoints.borrow_mut().pop() else {
+            return Ok(());
+        };
+        if savepoint.is_set {
+            for _ in 0..=savepoint.released {
+                self.tx.rollback_to_savepoint()?;
+            }
+        }
+        self.changes.borrow_mut().truncate(savepoint.changes);
+        self.tuple_counts.replace(savepoint.tuple_counts);
+
+        Ok(())
+    }
+
+    fn release_savepoint(&self) -> Result<(), DatabaseError> {
+        let mut savepoints = self.savepoints.borrow_mut();
+
+        if let Some(savepoint) = savepoints.pop().filter(|savepoint| savepoint.is_set) {
+            // rolled back with the savepoint below, that of the transaction itself goes with it
+            if let Some(below) = savepoints.last_mut() {
+                below.released += savepoint.released + 1;
+            }
+        }
+
+        Ok(())
     }
 
     fn commit(self) -> Result<(), DatabaseError> {
-        self.tx.commit()?;
+        self.write_tuple_counts()?;
+        let tx = self.tx;
+
+        match self.change_log {
+            Some(change_log) => {
+                change_log.commit(self.changes.into_inner(), || Ok(tx.commit()?))?
+            }
+            None => tx.commit()?,
+        }
         Ok(())
     }
 }
 
 pub struct RocksIter<'txn, 'iter> {
+    // Tips: only checked by the reverse iteration, the forward one seeks to it
+    lower: Bound<BumpBytes<'iter>>,
     upper: Bound<BumpBytes<'iter>>,
     iter: DBIteratorWithThreadMode<'iter, rocksdb::Transaction<'txn, OptimisticTransactionDB>>,
 }
@@ -136,7 +689,7 @@ pub struct RocksIter<'txn, 'iter> {
 impl InnerIter for RocksIter<'_, '_> {
     #[inline]
     fn try_next(&mut self) -> Result<Option<(Bytes, Bytes)>, DatabaseError> {
-        if let Some(result) = self.iter.by_ref().next() {
+        for result in self.iter.by_ref() {
             let (key, value) = result?;
             let upper_bound_check = match &self.upper {
                 Bound::Included(ref upper) => key.as_ref() <= upper.as_slice(),
@@ -144,6 +697,19 @@ impl InnerIter for RocksIter<'_, '_> {
                 Bound::Unbounded => true,
             };
             if !upper_bound_check {
+                // the reverse iteration starts at the excluded upper bound
+                if matches!(self.upper, Bound::Excluded(ref upper) if key.as_ref() == upper.as_slice())
+                {
+                    continue;
+                }
+                return Ok(None);
+            }
+            let lower_bound_check = match &self.lower {
+                Bound::Included(ref lower) => key.as_ref() >= lower.as_slice(),
+                Bound::Excluded(ref lower) => key.as_ref() > lower.as_slice(),
+                Bound::Unbounded => true,
+            };
+            if !lower_bound_check {
                 return Ok(None);
             }
             return Ok(Some((Vec::from(key), Vec::from(value))));
@@ -154,31 +720,35 @@ impl InnerIter for RocksIter<'_, '_> {
 
 #[cfg(test)]
 mod test {
-    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
-    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::catalog::{AuditColumns, ColumnCatalog, ColumnDesc, ColumnRef};
+    use crate::db::{DataBaseBuilder, Database, ResultIter};
     use crate::errors::DatabaseError;
     use crate::expression::range_detacher::Range;
-    use crate::storage::rocksdb::RocksStorage;
+    use crate::storage::rocksdb::{RocksStorage, STORAGE_FORMAT_VERSION};
+    use crate::storage::table_codec::{BumpBytes, TableCodec};
+    use crate::storage::tuple_codec::{TupleCodecEnum, TupleCodecKind};
     use crate::storage::{
-        IndexImplEnum, IndexImplParams, IndexIter, IndexIterState, Iter, PrimaryKeyIndexImpl,
-        Storage, Transaction,
+        IndexImplEnum, IndexImplParams, IndexIter, IndexIterState, InnerIter, Iter,
+        PrimaryKeyIndexImpl, Storage, Transaction,
     };
-    use crate::types::index::{IndexMeta, IndexType};
+    use crate::types::index::{Index, IndexMeta, IndexType};
     use crate::types::tuple::Tuple;
     use crate::types::value::DataValue;
     use crate::types::LogicalType;
     use crate::utils::lru::SharedLruCache;
+    use bumpalo::Bump;
     use itertools::Itertools;
-    use std::collections::{BTreeMap, Bound};
+    use std::collections::{BTreeMap, Bound, HashSet};
     use std::hash::RandomState;
     use std::sync::Arc;
     use tempfile::TempDir;
+    use ulid::Ulid;
 
     #[test]
     fn test_in_rocksdb_storage_works_with_data() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
         let storage = RocksStorage::new(temp_dir.path())?;
-        let mut transaction = storage.transaction()?;
+        let transaction = storage.transaction()?;
         let table_cache = Arc::new(SharedLruCache::new(4, 1, RandomState::new())?);
         let columns = Arc::new(vec![
             ColumnRef::from(ColumnCatalog::new(
@@ -201,6 +771,8 @@ mod test {
             &table_cache,
             Arc::new("test".to_string()),
             source_columns,
+            TupleCodecKind::default(),
+            AuditColumns::default(),
             false,
         )?;
 
@@ -210,6 +782,7 @@ mod test {
             .unwrap()
             .get_column_id_by_name(&"c1".to_string())
             .is_some());
+        let tuple_codec = TupleCodecEnum::instance(table_catalog.unwrap())?;
 
         transaction.append_tuple(
             &"test".to_string(),
@@ -217,7 +790,7 @@ mod test {
                 Some(DataValue::Int32(1)),
                 vec![DataValue::Int32(1), DataValue::Boolean(true)],
             ),
-            &[LogicalType::Integer, LogicalType::Boolean],
+            &tuple_codec,
             false,
         )?;
         transaction.append_tuple(
@@ -226,7 +799,7 @@ mod test {
                 Some(DataValue::Int32(2)),
                 vec![DataValue::Int32(2), DataValue::Boolean(true)],
             ),
-            &[LogicalType::Integer, LogicalType::Boolean],
+            &tuple_codec,
             false,
         )?;
 
@@ -239,6 +812,7 @@ mod test {
             (Some(1), Some(1)),
             read_columns,
             true,
+            false,
         )?;
 
         let option_1 = iter.next_tuple()?;
@@ -292,8 +866,9 @@ mod test {
                     ty: IndexType::PrimaryKey { is_multiple: false },
                 }),
                 table_name: &table.name,
-                table_types: table.types(),
+                tuple_codec: TupleCodecEnum::instance(&table)?,
                 with_pk: true,
+                reverse: false,
                 tx: &transaction,
             },
             ranges: vec![
@@ -306,6 +881,7 @@ mod test {
             .into_iter(),
             state: IndexIterState::Init,
             inner: IndexImplEnum::PrimaryKey(PrimaryKeyIndexImpl),
+            seen_tuple_ids: None,
         };
         let mut result = Vec::new();
 
@@ -357,4 +933,722 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_by_keys() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 (a, b) values (0, 0), (1, 10), (2, 20), (3, 30)")?
+            .done()?;
+        let transaction = kite_sql.storage.transaction().unwrap();
+
+        let table = transaction
+            .table(kite_sql.state.table_cache(), Arc::new("t1".to_string()))?
+            .unwrap()
+            .clone();
+        let columns = table.columns().cloned().enumerate().collect();
+        let mut iter = transaction.read_by_keys(
+            kite_sql.state.table_cache(),
+            Arc::new("t1".to_string()),
+            (Some(1), None),
+            columns,
+            table.indexes[0].clone(),
+            vec![
+                DataValue::Int32(0),
+                DataValue::Int32(2),
+                DataValue::Int64(3),
+                DataValue::Int32(4),
+            ],
+            true,
+        )?;
+        let mut values = Vec::new();
+
+        while let Some(tuple) = iter.next_tuple()? {
+            values.push(tuple.values);
+        }
+        assert_eq!(
+            values,
+            vec![
+                vec![DataValue::Int32(2), DataValue::Int32(20)],
+                vec![DataValue::Int32(3), DataValue::Int32(30)],
+            ]
+        );
+
+        let mut iter = kite_sql.run("explain select * from t1 where a in (1, 3, 5)")?;
+        assert!(iter.next().unwrap()?.values[0]
+            .utf8()
+            .unwrap()
+            .contains("MultiGet By"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_by_skip() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (id int primary key, a int, b int)")?
+            .done()?;
+        kite_sql.run("create index ab_index on t1 (a, b)")?.done()?;
+        for i in 0..128 {
+            kite_sql
+                .run(format!("insert into t1 values ({i}, {}, {i})", i % 2))?
+                .done()?;
+        }
+        let explain = |kite_sql: &Database<RocksStorage>| -> Result<String, DatabaseError> {
+            let mut iter = kite_sql.run("explain select id from t1 where b > 124")?;
+            Ok(iter.next().unwrap()?.values[0].utf8().unwrap().to_string())
+        };
+        // the distinct values of the leading column are not known before `ANALYZE`
+        assert!(!explain(&kite_sql)?.contains("SkipScan"));
+
+        kite_sql.run("analyze table t1")?.done()?;
+        assert!(explain(&kite_sql)?.contains("SkipScan By ab_index => (124, +inf)"));
+
+        let mut ids = kite_sql
+            .run("select id from t1 where b > 124")?
+            .map(|tuple| Ok(tuple?.values[0].clone()))
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+        ids.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            ids,
+            vec![
+                DataValue::Int32(125),
+                DataValue::Int32(126),
+                DataValue::Int32(127)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_projected_columns() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int, c varchar, d int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values (0, 1, 'kite', 2), (1, 3, 'sql', 4)")?
+            .done()?;
+        let transaction = kite_sql.storage.transaction()?;
+
+        let table = transaction
+            .table(kite_sql.state.table_cache(), Arc::new("t1".to_string()))?
+            .unwrap()
+            .clone();
+        let columns = table
+            .columns()
+            .cloned()
+            .enumerate()
+            .filter(|(i, _)| *i == 1 || *i == 3)
+            .collect();
+        let mut iter = transaction.read(
+            kite_sql.state.table_cache(),
+            Arc::new("t1".to_string()),
+            (None, None),
+            columns,
+            false,
+            false,
+        )?;
+
+        // only the projected columns are decoded
+        let mut tuples = Vec::new();
+        while let Some(tuple) = iter.next_tuple()? {
+            tuples.push(tuple.values);
+        }
+        assert_eq!(
+            tuples,
+            vec![
+                vec![DataValue::Int32(1), DataValue::Int32(2)],
+                vec![DataValue::Int32(3), DataValue::Int32(4)],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_savepoints() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let transaction = storage.transaction()?;
+        let arena = Bump::new();
+        let set = |key: &[u8]| {
+            transaction.set(
+                BumpBytes::from_iter_in(key.iter().copied(), &arena),
+                BumpBytes::from_iter_in(key.iter().copied(), &arena),
+            )
+        };
+
+        // a statement writing nothing sets no savepoint of RocksDB
+        transaction.set_savepoint()?;
+        transaction.release_savepoint()?;
+        assert!(transaction.savepoints.borrow().is_empty());
+        assert!(transaction.tx.rollback_to_savepoint().is_err());
+
+        transaction.set_savepoint()?;
+        set(b"a")?;
+        transaction.set_savepoint()?;
+        set(b"b")?;
+        transaction.release_savepoint()?;
+        transaction.set_savepoint()?;
+        set(b"c")?;
+        transaction.rollback_to_savepoint()?;
+        assert!(transaction.get(b"b")?.is_some());
+        assert!(transaction.get(b"c")?.is_none());
+        // rolls back past the savepoint released above it
+        transaction.rollback_to_savepoint()?;
+        assert!(transaction.savepoints.borrow().is_empty());
+        assert!(transaction.get(b"a")?.is_none());
+        assert!(transaction.get(b"b")?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_version() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let key = TableCodec::default().encode_format_version_key().to_vec();
+
+        drop(RocksStorage::new(temp_dir.path())?);
+        let storage = RocksStorage::new(temp_dir.path())?;
+        // a database written before the version was stored
+        storage.inner.delete(&key)?;
+        storage.inner.put(b"key", b"value")?;
+        drop(storage);
+
+        let storage = RocksStorage::new(temp_dir.path())?;
+        assert_eq!(
+            storage.inner.get(&key)?,
+            Some(STORAGE_FORMAT_VERSION.to_be_bytes().to_vec())
+        );
+        storage
+            .inner
+            .put(&key, (STORAGE_FORMAT_VERSION + 1).to_be_bytes())?;
+        drop(storage);
+
+        assert!(matches!(
+            RocksStorage::new(temp_dir.path()),
+            Err(DatabaseError::StorageFormatMismatch {
+                found,
+                expected: STORAGE_FORMAT_VERSION
+            }) if found == STORAGE_FORMAT_VERSION + 1
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_format() -> Result<(), DatabaseError> {
+        // the key of format version 1, without the tags of the values: the values of the keys
+        // are `Int32`, of 4 bytes, or NULL, of none
+        fn untag(key: &[u8], values: &[&DataValue]) -> Vec<u8> {
+            let mut key = key.to_vec();
+            let mut end = key.len();
+
+            for value in values.iter().rev() {
+                let len = if value.is_null() { 0 } else { 4 };
+                key.remove(end - len - 1);
+                end -= len + 1;
+            }
+            key
+        }
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int, c int)")?
+            .done()?;
+        kite_sql.run("create index b_index on t1 (b)")?.done()?;
+        kite_sql
+            .run("create unique index c_index on t1 (c)")?
+            .done()?;
+        let values = (0..200)
+            .map(|i| match i % 7 {
+                0 => format!("({i}, null, {i})"),
+                _ => format!("({i}, {}, {i})", i % 10),
+            })
+            .join(", ");
+        kite_sql
+            .run(format!("insert into t1 values {values}"))?
+            .done()?;
+
+        let transaction = kite_sql.storage.transaction()?;
+        let table = transaction
+            .table(kite_sql.state.table_cache(), Arc::new("t1".to_string()))?
+            .cloned()
+            .unwrap();
+        let index_meta = |name: &str| {
+            table
+                .indexes
+                .iter()
+                .find(|index_meta| index_meta.name == name)
+                .unwrap()
+        };
+        let table_codec = unsafe { &*transaction.table_codec() };
+        let arena = Bump::new();
+        let mut keys = Vec::new();
+        for i in 0..200 {
+            let a = DataValue::Int32(i);
+            let b = if i % 7 == 0 {
+                DataValue::Null
+            } else {
+                DataValue::Int32(i % 10)
+            };
+            let c = DataValue::Int32(i);
+
+            let key = table_codec.encode_tuple_key("t1", &a)?;
+            let value = transaction.get(&key)?.unwrap();
+            keys.push(key.to_vec());
+            transaction.remove(&key)?;
+            transaction.set(
+                BumpBytes::from_iter_in(untag(&key, &[&a]), &arena),
+                BumpBytes::from_iter_in(value, &arena),
+            )?;
+
+            for (index_meta, value, values) in [
+                (index_meta("b_index"), &b, vec![&b, &a]),
+                (index_meta("c_index"), &c, vec![&c]),
+            ] {
+                let index = Index::new(index_meta.id, value, index_meta.ty);
+                let (key, value) = table_codec.encode_index("t1", &index, &a)?;
+                keys.push(key.to_vec());
+                transaction.remove(&key)?;
+                transaction.set(BumpBytes::from_iter_in(untag(&key, &values), &arena), value)?;
+            }
+        }
+        transaction.remove(&table_codec.encode_format_version_key())?;
+        transaction.commit()?;
+        drop(kite_sql);
+
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let transaction = kite_sql.storage.transaction()?;
+        let table_codec = unsafe { &*transaction.table_codec() };
+        for key in keys.iter() {
+            assert!(transaction.get(key)?.is_some());
+        }
+        let mut count = 0;
+        for (min, max) in [
+            table_codec.tuple_bound("t1"),
+            table_codec.all_index_bound("t1"),
+        ] {
+            let mut iter = transaction.range(Bound::Included(min), Bound::Included(max))?;
+            while iter.try_next()?.is_some() {
+                count += 1;
+            }
+        }
+        assert_eq!(count, keys.len());
+        drop(transaction);
+
+        let query = |sql: &str| -> Result<Vec<DataValue>, DatabaseError> {
+            let mut iter = kite_sql.run(sql)?;
+            let values = iter
+                .by_ref()
+                .map(|tuple| tuple.map(|tuple| tuple.values[0].clone()))
+                .try_collect()?;
+            iter.done()?;
+            Ok(values)
+        };
+        assert_eq!(
+            query("select a from t1 where a = 42")?,
+            vec![DataValue::Int32(42)]
+        );
+        assert_eq!(
+            query("select a from t1 where b = 3")?,
+            (0..200)
+                .filter(|i| i % 7 != 0 && i % 10 == 3)
+                .map(DataValue::Int32)
+                .collect_vec()
+        );
+        assert_eq!(query("select a from t1 where b is null")?.len(), 29);
+        assert_eq!(
+            query("select a from t1 where c = 8")?,
+            vec![DataValue::Int32(8)]
+        );
+        assert_eq!(
+            query("select count(*) from t1")?,
+            vec![DataValue::Int32(200)]
+        );
+        assert!(kite_sql
+            .run("insert into t1 values (200, 0, 8)")?
+            .done()
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_intern_column_names() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let column_name = |kite_sql: &Database<RocksStorage>, table_name: &str| {
+            let transaction = kite_sql.storage.transaction()?;
+            let table = transaction
+                .table(
+                    kite_sql.state.table_cache(),
+                    Arc::new(table_name.to_string()),
+                )?
+                .unwrap();
+
+            Ok::<_, DatabaseError>(
+                table
+                    .get_column_by_name("b")
+                    .unwrap()
+                    .summary()
+                    .name
+                    .clone(),
+            )
+        };
+
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("create table t2 (a int primary key, c int)")?
+            .done()?;
+        kite_sql.run("alter table t2 add column b int")?.done()?;
+        assert!(Arc::ptr_eq(
+            &column_name(&kite_sql, "t1")?,
+            &column_name(&kite_sql, "t2")?
+        ));
+        drop(kite_sql);
+
+        // the catalogs read again share the names as well, within the storage only
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let b = column_name(&kite_sql, "t1")?;
+        assert!(Arc::ptr_eq(&b, &column_name(&kite_sql, "t2")?));
+
+        let other_dir = TempDir::new().expect("unable to create temporary working directory");
+        let other = DataBaseBuilder::path(other_dir.path()).build()?;
+        other
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        assert!(!Arc::ptr_eq(&b, &column_name(&other, "t1")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_tuples() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql
+            .run("create table t2 (a int primary key)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values (0, 0), (1, null), (2, 2)")?
+            .done()?;
+        kite_sql.run("insert into t2 values (0)")?.done()?;
+        let transaction = kite_sql.storage.transaction()?;
+
+        assert_eq!(transaction.count_tuples("t1")?, 3);
+        assert_eq!(transaction.count_tuples("t2")?, 1);
+        drop(transaction);
+
+        kite_sql
+            .run("insert overwrite t1 values (0, 9), (3, 3)")?
+            .done()?;
+        kite_sql.run("update t1 set a = 4 where a = 1")?.done()?;
+        kite_sql.run("update t1 set b = 1 where a = 4")?.done()?;
+        kite_sql.run("delete from t1 where a = 2")?.done()?;
+        kite_sql.run("delete from t1 where a = 2")?.done()?;
+        assert_eq!(kite_sql.storage.transaction()?.count_tuples("t1")?, 3);
+
+        // the counts of the transactions fold into the one of the table as they pile up
+        for i in 5..105 {
+            kite_sql
+                .run(format!("insert into t1 values ({i}, {i})"))?
+                .done()?;
+        }
+        let transaction = kite_sql.storage.transaction()?;
+        let (min, max) = unsafe { &*transaction.table_codec() }.tuple_count_bound("t1");
+        let mut iter = transaction.range(Bound::Included(min), Bound::Included(max))?;
+        let mut counts = 0;
+        while iter.try_next()?.is_some() {
+            counts += 1;
+        }
+        drop(iter);
+        assert!(counts <= 65);
+        assert_eq!(transaction.count_tuples("t1")?, 103);
+        drop(transaction);
+
+        // the writes of a transaction count as it sees them, and not once rolled back
+        let mut transaction = kite_sql.new_transaction()?;
+        transaction.run("delete from t1 where a < 50")?.done()?;
+        let mut iter = transaction.run("select count(*) from t1")?;
+        assert_eq!(iter.next().unwrap()?.values, vec![DataValue::Int32(55)]);
+        iter.done()?;
+        assert!(transaction
+            .run("insert into t1 values (200, 200), (60, 60)")
+            .and_then(|iter| iter.done())
+            .is_err());
+        let mut iter = transaction.run("select count(*) from t1")?;
+        assert_eq!(iter.next().unwrap()?.values, vec![DataValue::Int32(55)]);
+        iter.done()?;
+        drop(transaction);
+        assert_eq!(kite_sql.storage.transaction()?.count_tuples("t1")?, 103);
+
+        kite_sql.run("truncate t1")?.done()?;
+        kite_sql.run("insert into t1 values (0, 0)")?.done()?;
+        assert_eq!(kite_sql.storage.transaction()?.count_tuples("t1")?, 1);
+
+        // the tables whose count was never kept are counted by their keys
+        let transaction = kite_sql.storage.transaction()?;
+        let key = unsafe { &*transaction.table_codec() }.encode_tuple_count_key("t2", Ulid::nil());
+        transaction.remove(&key)?;
+        transaction.commit()?;
+        assert_eq!(kite_sql.storage.transaction()?.count_tuples("t2")?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_points() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+
+        let transaction = kite_sql.storage.transaction()?;
+        assert!(transaction.split_points("t1", 4)?.is_empty());
+        drop(transaction);
+
+        for i in 0..3 {
+            let values = (i * 100..(i + 1) * 100)
+                .map(|a| format!("({a}, {a})"))
+                .join(", ");
+            kite_sql
+                .run(format!("insert into t1 values {values}"))?
+                .done()?;
+            kite_sql.storage.inner.flush()?;
+        }
+        let transaction = kite_sql.storage.transaction()?;
+        let (min, max) = unsafe { &*transaction.table_codec() }.tuple_bound("t1");
+
+        let points = transaction.split_points("t1", 4)?;
+        assert!(!points.is_empty() && points.len() < 4);
+        assert!(points.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(points
+            .iter()
+            .all(|point| point.as_slice() > min.as_slice() && point.as_slice() < max.as_slice()));
+        assert!(transaction.split_points("t1", 1)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_statistics_modified() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int, c int)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values (0, 0, 0), (1, 1, 1)")?
+            .done()?;
+        let column_ids = {
+            let transaction = kite_sql.storage.transaction()?;
+            let table = transaction
+                .table(kite_sql.state.table_cache(), Arc::new("t1".to_string()))?
+                .unwrap();
+            table
+                .columns()
+                .filter_map(|column| column.id())
+                .collect_vec()
+        };
+        let transaction = kite_sql.storage.transaction()?;
+
+        assert_eq!(
+            transaction.statistics_modified_columns("t1")?,
+            HashSet::from_iter(column_ids.iter().cloned())
+        );
+        transaction.clear_statistics_modified("t1", &column_ids[0])?;
+        transaction.clear_statistics_modified("t1", &column_ids[1])?;
+        transaction.clear_statistics_modified("t1", &column_ids[2])?;
+        assert!(transaction.statistics_modified_columns("t1")?.is_empty());
+        transaction.commit()?;
+
+        kite_sql.run("update t1 set c = 2 where a = 0")?.done()?;
+        kite_sql.run("update t1 set b = 2 where a = 2")?.done()?;
+
+        let transaction = kite_sql.storage.transaction()?;
+        assert_eq!(
+            transaction.statistics_modified_columns("t1")?,
+            HashSet::from([column_ids[2]])
+        );
+        transaction.mark_statistics_modified("t1", &column_ids[2..])?;
+        assert_eq!(
+            transaction.statistics_modified_columns("t1")?,
+            HashSet::from([column_ids[2]])
+        );
+        let (min, max) = unsafe { &*transaction.table_codec() }
+            .statistics_modified_bound("t1", Some(&column_ids[2]));
+        let mut iter = transaction.range(Bound::Included(min), Bound::Included(max))?;
+        assert!(iter.try_next()?.is_some());
+        assert!(iter.try_next()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_by_index_reverse() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        kite_sql
+            .run("create table t1 (a int primary key, b int)")?
+            .done()?;
+        kite_sql.run("create index b_index on t1 (b)")?.done()?;
+        kite_sql
+            .run("insert into t1 (a, b) values (0, 3), (1, 1), (2, 2), (3, 0)")?
+            .done()?;
+        let transaction = kite_sql.storage.transaction()?;
+
+        let table = transaction
+            .table(kite_sql.state.table_cache(), Arc::new("t1".to_string()))?
+            .unwrap()
+            .clone();
+        let columns: BTreeMap<_, _> = table.columns().cloned().enumerate().collect();
+        let read = |index: usize, ranges: Vec<Range>| -> Result<Vec<DataValue>, DatabaseError> {
+            let mut iter = transaction
+                .read_by_index(
+                    kite_sql.state.table_cache(),
+                    Arc::new("t1".to_string()),
+                    (None, None),
+                    columns.clone(),
+                    table.indexes[index].clone(),
+                    ranges,
+                    false,
+                )?
+                .reverse();
+            let mut values = Vec::new();
+
+            while let Some(tuple) = iter.next_tuple()? {
+                values.push(tuple.values[1].clone());
+            }
+            Ok(values)
+        };
+
+        let pk_index = table
+            .indexes
+            .iter()
+            .position(|index| matches!(index.ty, IndexType::PrimaryKey { .. }))
+            .unwrap();
+        let b_index = 1 - pk_index;
+        assert_eq!(
+            read(
+                b_index,
+                vec![Range::Scope {
+                    min: Bound::Unbounded,
+                    max: Bound::Unbounded,
+                }]
+            )?,
+            vec![
+                DataValue::Int32(3),
+                DataValue::Int32(2),
+                DataValue::Int32(1),
+                DataValue::Int32(0)
+            ]
+        );
+        assert_eq!(
+            read(
+                b_index,
+                vec![
+                    Range::Scope {
+                        min: Bound::Unbounded,
+                        max: Bound::Included(DataValue::Int32(0)),
+                    },
+                    Range::Scope {
+                        min: Bound::Included(DataValue::Int32(2)),
+                        max: Bound::Included(DataValue::Int32(3)),
+                    }
+                ]
+            )?,
+            vec![
+                DataValue::Int32(3),
+                DataValue::Int32(2),
+                DataValue::Int32(0)
+            ]
+        );
+        assert_eq!(
+            read(
+                pk_index,
+                vec![Range::Scope {
+                    min: Bound::Included(DataValue::Int32(1)),
+                    max: Bound::Excluded(DataValue::Int32(3)),
+                }]
+            )?,
+            vec![DataValue::Int32(2), DataValue::Int32(1)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_restart_interval() -> Result<(), DatabaseError> {
+        let build = |interval: Option<usize>| -> Result<(TempDir, u64), DatabaseError> {
+            let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+            let mut builder = DataBaseBuilder::path(temp_dir.path());
+            if let Some(interval) = interval {
+                builder = builder.key_restart_interval(interval);
+            }
+            let kite_sql = builder.build()?;
+            kite_sql
+                .run("create table t1 (a int primary key, b varchar, c varchar)")?
+                .done()?;
+            kite_sql.run("create index bc_index on t1 (b, c)")?.done()?;
+            for i in 0..1_000 {
+                kite_sql
+                    .run(format!(
+                        "insert into t1 values ({i}, 'https://example.com/{}/', 'page/{i:04}')",
+                        i % 10
+                    ))?
+                    .done()?;
+            }
+            // the keys are written to blocks by the compaction
+            kite_sql
+                .storage
+                .inner
+                .compact_range::<&[u8], &[u8]>(None, None);
+
+            let mut iter = kite_sql.run(
+                "select a from t1 where b = 'https://example.com/3/' and c >= 'page/0900' order by a",
+            )?;
+            let a = iter
+                .by_ref()
+                .map(|tuple| Ok(tuple?.values[0].clone()))
+                .collect::<Result<Vec<_>, DatabaseError>>()?;
+            iter.done()?;
+            assert_eq!(
+                a,
+                (903..1_000)
+                    .step_by(10)
+                    .map(DataValue::Int32)
+                    .collect::<Vec<_>>()
+            );
+            let size = kite_sql
+                .storage
+                .inner
+                .property_int_value("rocksdb.total-sst-files-size")?
+                .unwrap_or(0);
+
+            Ok((temp_dir, size))
+        };
+        let (_default_dir, default_size) = build(None)?;
+        let (_interval_dir, interval_size) = build(Some(128))?;
+        assert!(default_size > 0);
+        assert!(interval_size < default_size);
+
+        Ok(())
+    }
 }
@@ -1,24 +1,37 @@
+use crate::catalog::task::Task;
 use crate::catalog::view::View;
 use crate::catalog::{ColumnRef, ColumnRelation, TableMeta};
 use crate::errors::DatabaseError;
 use crate::serdes::{ReferenceSerialization, ReferenceTables};
-use crate::storage::{TableCache, Transaction};
+use crate::storage::tuple_codec::TupleCodec;
+use crate::storage::{TableCache, TableVersion, Transaction};
 use crate::types::index::{Index, IndexId, IndexMeta, IndexType};
 use crate::types::tuple::{Schema, Tuple, TupleId};
 use crate::types::value::DataValue;
-use crate::types::LogicalType;
+use crate::types::{ColumnId, LogicalType};
 use bumpalo::Bump;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use ordered_float::OrderedFloat;
 use siphasher::sip::SipHasher;
 use std::hash::{Hash, Hasher};
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use std::sync::LazyLock;
+use ulid::Ulid;
 
 pub(crate) const BOUND_MIN_TAG: u8 = u8::MIN;
 pub(crate) const BOUND_MAX_TAG: u8 = u8::MAX;
+const STATISTICS_MODIFIED_TAG: u8 = 1;
+const VECTOR_CENTROIDS_TAG: u8 = 2;
+const TUPLE_COUNT_TAG: u8 = 3;
 
 static ROOT_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Root".to_vec());
 static VIEW_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"View".to_vec());
+static TASK_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Task".to_vec());
 static HASH_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Hash".to_vec());
+static CHECKPOINT_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Ckpt".to_vec());
+static VERSION_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Vers".to_vec());
+static REPLICATION_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Repl".to_vec());
+static FORMAT_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Fmtv".to_vec());
 static EMPTY_REFERENCE_TABLES: LazyLock<ReferenceTables> = LazyLock::new(ReferenceTables::new);
 
 pub type Bytes = Vec<u8>;
@@ -36,6 +49,9 @@ enum CodecType {
     Index,
     Statistics,
     View,
+    Task,
+    Checkpoint,
+    Version,
     Tuple,
     Root,
     Hash,
@@ -48,17 +64,23 @@ impl TableCodec {
         hasher.finish().to_le_bytes()
     }
 
-    pub fn check_primary_key(value: &DataValue, indentation: usize) -> Result<(), DatabaseError> {
+    pub fn check_primary_key(
+        table_name: &str,
+        value: &DataValue,
+        indentation: usize,
+    ) -> Result<(), DatabaseError> {
         if indentation > 1 {
             return Err(DatabaseError::PrimaryKeyTooManyLayers);
         }
         if value.is_null() {
-            return Err(DatabaseError::NotNull);
+            return Err(DatabaseError::NotNull(format!(
+                "the primary key of {table_name}"
+            )));
         }
 
         if let DataValue::Tuple(values, _) = &value {
             for value in values {
-                Self::check_primary_key(value, indentation + 1)?
+                Self::check_primary_key(table_name, value, indentation + 1)?
             }
 
             return Ok(());
@@ -80,6 +102,8 @@ impl TableCodec {
                 | LogicalType::USmallint
                 | LogicalType::UInteger
                 | LogicalType::UBigint
+                | LogicalType::Hugeint
+                | LogicalType::UHugeint
                 | LogicalType::Char(..)
                 | LogicalType::Varchar(..)
         ) {
@@ -91,7 +115,7 @@ impl TableCodec {
     /// TableName + Type
     ///
     /// Tips:
-    /// 1. Root & View & Hash full key = key_prefix
+    /// 1. Root & View & Task & Checkpoint & Version & Hash full key = key_prefix
     /// 2. hash table name makes it 4 as a fixed length, and [prefix_extractor](https://github.com/facebook/rocksdb/wiki/Prefix-Seek#defining-a-prefix) can be enabled in rocksdb
     fn key_prefix(&self, ty: CodecType, name: &str) -> BumpBytes {
         let mut table_bytes = BumpBytes::new_in(&self.arena);
@@ -131,6 +155,33 @@ impl TableCodec {
 
                 return bytes;
             }
+            CodecType::Task => {
+                let mut bytes = BumpBytes::new_in(&self.arena);
+
+                bytes.extend_from_slice(&TASK_BYTES);
+                bytes.push(BOUND_MIN_TAG);
+                bytes.extend_from_slice(&table_bytes);
+
+                return bytes;
+            }
+            CodecType::Checkpoint => {
+                let mut bytes = BumpBytes::new_in(&self.arena);
+
+                bytes.extend_from_slice(&CHECKPOINT_BYTES);
+                bytes.push(BOUND_MIN_TAG);
+                bytes.extend_from_slice(&table_bytes);
+
+                return bytes;
+            }
+            CodecType::Version => {
+                let mut bytes = BumpBytes::new_in(&self.arena);
+
+                bytes.extend_from_slice(&VERSION_BYTES);
+                bytes.push(BOUND_MIN_TAG);
+                bytes.extend_from_slice(&table_bytes);
+
+                return bytes;
+            }
             CodecType::Hash => {
                 let mut bytes = BumpBytes::new_in(&self.arena);
 
@@ -252,18 +303,30 @@ impl TableCodec {
         (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
     }
 
+    pub fn task_bound(&self) -> (BumpBytes, BumpBytes) {
+        let op = |bound_id| {
+            let mut key_prefix = BumpBytes::new_in(&self.arena);
+
+            key_prefix.extend_from_slice(&TASK_BYTES);
+            key_prefix.push(bound_id);
+            key_prefix
+        };
+
+        (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
+    }
+
     /// Key: {TableName}{TUPLE_TAG}{BOUND_MIN_TAG}{RowID}(Sorted)
     /// Value: Tuple
     pub fn encode_tuple(
         &self,
         table_name: &str,
         tuple: &mut Tuple,
-        types: &[LogicalType],
+        tuple_codec: &impl TupleCodec,
     ) -> Result<(BumpBytes, BumpBytes), DatabaseError> {
         let tuple_id = tuple.pk.as_ref().ok_or(DatabaseError::PrimaryKeyNotFound)?;
         let key = self.encode_tuple_key(table_name, tuple_id)?;
 
-        Ok((key, tuple.serialize_to(types, &self.arena)?))
+        Ok((key, tuple_codec.encode(tuple, &self.arena)?))
     }
 
     pub fn encode_tuple_key(
@@ -271,7 +334,7 @@ impl TableCodec {
         table_name: &str,
         tuple_id: &TupleId,
     ) -> Result<BumpBytes, DatabaseError> {
-        Self::check_primary_key(tuple_id, 0)?;
+        Self::check_primary_key(table_name, tuple_id, 0)?;
 
         let mut key_prefix = self.key_prefix(CodecType::Tuple, table_name);
         key_prefix.push(BOUND_MIN_TAG);
@@ -283,14 +346,14 @@ impl TableCodec {
 
     #[inline]
     pub fn decode_tuple(
-        table_types: &[LogicalType],
+        tuple_codec: &impl TupleCodec,
         pk_indices: &[usize],
         projections: &[usize],
         schema: &Schema,
         bytes: &[u8],
         with_pk: bool,
     ) -> Result<Tuple, DatabaseError> {
-        Tuple::deserialize_from(table_types, pk_indices, projections, schema, bytes, with_pk)
+        tuple_codec.decode(pk_indices, projections, schema, bytes, with_pk)
     }
 
     pub fn encode_index_meta_key(
@@ -334,6 +397,8 @@ impl TableCodec {
     ///
     /// Tips: The unique index has only one ColumnID and one corresponding DataValue,
     /// so it can be positioned directly.
+    ///
+    /// The values and the TupleID are written as keys of [`memcomparable`](super::memcomparable).
     pub fn encode_index(
         &self,
         name: &str,
@@ -376,7 +441,15 @@ impl TableCodec {
         let mut key_prefix = self.encode_index_bound_key(name, index, false)?;
 
         if let Some(tuple_id) = tuple_id {
-            if matches!(index.ty, IndexType::Normal | IndexType::Composite) {
+            if matches!(
+                index.ty,
+                IndexType::Normal
+                    | IndexType::Composite
+                    | IndexType::FullText
+                    | IndexType::Trigram
+                    | IndexType::Vector
+                    | IndexType::ZOrder
+            ) {
                 tuple_id.memcomparable_encode(&mut key_prefix)?;
             }
         }
@@ -450,10 +523,146 @@ impl TableCodec {
         key_prefix
     }
 
+    /// Whether the key is of [`TableCodec::encode_statistics_path_key`].
+    pub fn is_statistics_path_key(key: &[u8]) -> bool {
+        key.len() == 10 + size_of::<IndexId>() && key[8] == b'4' && key[9] == BOUND_MIN_TAG
+    }
+
     pub fn decode_statistics_path(bytes: &[u8]) -> Result<String, DatabaseError> {
         Ok(String::from_utf8(bytes.to_vec())?)
     }
 
+    /// Key: {TableName}{STATISTICS_TAG}{VECTOR_CENTROIDS_TAG}{INDEX_ID}
+    /// Value: the centroids of the lists of an IVF index
+    pub fn encode_vector_centroids(
+        &self,
+        table_name: &str,
+        index_id: IndexId,
+        centroids: &[Vec<OrderedFloat<f32>>],
+    ) -> Result<(BumpBytes, BumpBytes), DatabaseError> {
+        let key = self.encode_vector_centroids_key(table_name, index_id);
+
+        let mut value = BumpBytes::new_in(&self.arena);
+        bincode::serialize_into(&mut value, centroids)?;
+
+        Ok((key, value))
+    }
+
+    pub fn encode_vector_centroids_key(&self, table_name: &str, index_id: IndexId) -> BumpBytes {
+        let mut key_prefix = self.key_prefix(CodecType::Statistics, table_name);
+
+        key_prefix.push(VECTOR_CENTROIDS_TAG);
+        key_prefix.extend(index_id.to_le_bytes());
+        key_prefix
+    }
+
+    pub fn decode_vector_centroids(
+        bytes: &[u8],
+    ) -> Result<Vec<Vec<OrderedFloat<f32>>>, DatabaseError> {
+        Ok(bincode::deserialize_from(&mut Cursor::new(bytes))?)
+    }
+
+    /// Key: {TableName}{STATISTICS_TAG}{STATISTICS_MODIFIED_TAG}{ColumnId}{Ulid}
+    /// Value: Empty
+    ///
+    /// Each marker gets its own suffix so that concurrent transactions touching the same column never write the same key.
+    pub fn encode_statistics_modified(
+        &self,
+        table_name: &str,
+        column_id: &ColumnId,
+    ) -> (BumpBytes, BumpBytes) {
+        let mut key = self.statistics_modified_prefix(table_name, Some(column_id));
+        key.extend(Ulid::new().to_bytes());
+
+        (key, BumpBytes::new_in(&self.arena))
+    }
+
+    pub fn decode_statistics_modified_key(bytes: &[u8]) -> ColumnId {
+        let column_end = bytes.len() - 16;
+        let mut column_id = [0u8; 16];
+        column_id.copy_from_slice(&bytes[column_end - 16..column_end]);
+
+        Ulid::from_bytes(column_id)
+    }
+
+    /// Bound of the modification markers of the table, or of one of its columns if given.
+    pub fn statistics_modified_bound(
+        &self,
+        table_name: &str,
+        column_id: Option<&ColumnId>,
+    ) -> (BumpBytes, BumpBytes) {
+        let min = self.statistics_modified_prefix(table_name, column_id);
+        let mut max = self.statistics_modified_prefix(table_name, column_id);
+        max.extend(
+            [BOUND_MAX_TAG; 32]
+                .iter()
+                .take(if column_id.is_some() { 16 } else { 32 }),
+        );
+
+        (min, max)
+    }
+
+    fn statistics_modified_prefix(
+        &self,
+        table_name: &str,
+        column_id: Option<&ColumnId>,
+    ) -> BumpBytes {
+        let mut key_prefix = self.key_prefix(CodecType::Statistics, table_name);
+
+        key_prefix.push(STATISTICS_MODIFIED_TAG);
+        if let Some(column_id) = column_id {
+            key_prefix.extend(column_id.to_bytes());
+        }
+        key_prefix
+    }
+
+    /// Key: {TableName}{STATISTICS_TAG}{TUPLE_COUNT_TAG}{Ulid}
+    /// Value: the tuples the transaction of the ulid added to the table, less those it removed
+    ///
+    /// The nil ulid keeps the count of the table as of its creation or its last fold, so that the
+    /// transactions writing the table never write the same key.
+    pub fn encode_tuple_count(
+        &self,
+        table_name: &str,
+        id: Ulid,
+        count: i64,
+    ) -> (BumpBytes, BumpBytes) {
+        let key = self.encode_tuple_count_key(table_name, id);
+
+        let mut value = BumpBytes::new_in(&self.arena);
+        value.extend(count.to_le_bytes());
+
+        (key, value)
+    }
+
+    pub fn encode_tuple_count_key(&self, table_name: &str, id: Ulid) -> BumpBytes {
+        let mut key_prefix = self.key_prefix(CodecType::Statistics, table_name);
+
+        key_prefix.push(TUPLE_COUNT_TAG);
+        key_prefix.extend(id.to_bytes());
+        key_prefix
+    }
+
+    pub fn decode_tuple_count(bytes: &[u8]) -> Result<i64, DatabaseError> {
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| DatabaseError::InvalidValue("tuple count".to_string()))?;
+
+        Ok(i64::from_le_bytes(bytes))
+    }
+
+    pub fn tuple_count_bound(&self, table_name: &str) -> (BumpBytes, BumpBytes) {
+        let op = |bound_id| {
+            let mut key_prefix = self.key_prefix(CodecType::Statistics, table_name);
+
+            key_prefix.push(TUPLE_COUNT_TAG);
+            key_prefix.extend([bound_id; 16]);
+            key_prefix
+        };
+
+        (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
+    }
+
     /// Key: View{BOUND_MIN_TAG}{ViewName}
     /// Value: View
     pub fn encode_view(&self, view: &View) -> Result<(BumpBytes, BumpBytes), DatabaseError> {
@@ -495,6 +704,125 @@ impl TableCodec {
         View::decode(&mut cursor, Some(drive), &reference_tables)
     }
 
+    /// Key: Task{BOUND_MIN_TAG}{TaskName}
+    /// Value: Task
+    pub fn encode_task(&self, task: &Task) -> Result<(BumpBytes, BumpBytes), DatabaseError> {
+        let key = self.encode_task_key(&task.name);
+
+        let mut bytes = BumpBytes::new_in(&self.arena);
+        task.encode(&mut bytes, false, &mut ReferenceTables::new())?;
+        Ok((key, bytes))
+    }
+
+    pub fn encode_task_key(&self, task_name: &str) -> BumpBytes {
+        self.key_prefix(CodecType::Task, task_name)
+    }
+
+    pub fn decode_task<T: Transaction>(bytes: &[u8]) -> Result<Task, DatabaseError> {
+        let mut bytes = Cursor::new(bytes);
+
+        Task::decode::<T, _>(&mut bytes, None, &EMPTY_REFERENCE_TABLES)
+    }
+
+    /// Key: Checkpoint{BOUND_MIN_TAG}{Statement}
+    /// Value: the primary key of the last row committed by `batch_commit`
+    pub fn encode_batch_checkpoint(
+        &self,
+        statement: &str,
+        primary_key: &[DataValue],
+    ) -> Result<(BumpBytes, BumpBytes), DatabaseError> {
+        let key = self.encode_batch_checkpoint_key(statement);
+
+        let mut bytes = BumpBytes::new_in(&self.arena);
+        primary_key
+            .to_vec()
+            .encode(&mut bytes, false, &mut ReferenceTables::new())?;
+        Ok((key, bytes))
+    }
+
+    pub fn encode_batch_checkpoint_key(&self, statement: &str) -> BumpBytes {
+        self.key_prefix(CodecType::Checkpoint, statement)
+    }
+
+    pub fn decode_batch_checkpoint<T: Transaction>(
+        bytes: &[u8],
+    ) -> Result<Vec<DataValue>, DatabaseError> {
+        let mut bytes = Cursor::new(bytes);
+
+        Vec::<DataValue>::decode::<T, _>(&mut bytes, None, &EMPTY_REFERENCE_TABLES)
+    }
+
+    /// Key: Version{BOUND_MIN_TAG}{TableName}
+    /// Value: the versions of the catalog and of the statistics of the table, kept once it is
+    /// dropped for those of a table created again with its name to go on from them
+    pub fn encode_table_version(
+        &self,
+        table_name: &str,
+        version: TableVersion,
+    ) -> Result<(BumpBytes, BumpBytes), DatabaseError> {
+        let key = self.encode_table_version_key(table_name);
+
+        let mut bytes = BumpBytes::new_in(&self.arena);
+        bytes.write_u64::<BigEndian>(version.catalog)?;
+        bytes.write_u64::<BigEndian>(version.statistics)?;
+        Ok((key, bytes))
+    }
+
+    pub fn encode_table_version_key(&self, table_name: &str) -> BumpBytes {
+        self.key_prefix(CodecType::Version, table_name)
+    }
+
+    pub fn decode_table_version(bytes: &[u8]) -> Result<TableVersion, DatabaseError> {
+        let mut bytes = Cursor::new(bytes);
+
+        Ok(TableVersion {
+            catalog: bytes.read_u64::<BigEndian>()?,
+            statistics: bytes.read_u64::<BigEndian>()?,
+        })
+    }
+
+    /// Key: Replication{BOUND_MIN_TAG}
+    /// Value: the LSN and the epoch of the primary, as of the last change batch a follower applied
+    pub fn encode_replication_position(
+        &self,
+        epoch: &str,
+        lsn: u64,
+    ) -> Result<(BumpBytes, BumpBytes), DatabaseError> {
+        let key = self.encode_replication_position_key();
+
+        let mut bytes = BumpBytes::new_in(&self.arena);
+        bytes.write_u64::<BigEndian>(lsn)?;
+        bytes.extend_from_slice(epoch.as_bytes());
+        Ok((key, bytes))
+    }
+
+    pub fn encode_replication_position_key(&self) -> BumpBytes {
+        let mut bytes = BumpBytes::new_in(&self.arena);
+
+        bytes.extend_from_slice(&REPLICATION_BYTES);
+        bytes.push(BOUND_MIN_TAG);
+        bytes
+    }
+
+    /// Key: Fmtv{BOUND_MIN_TAG}
+    /// Value: the version of the encoding of the keys, see [`crate::storage::rocksdb::STORAGE_FORMAT_VERSION`]
+    pub fn encode_format_version_key(&self) -> BumpBytes {
+        let mut bytes = BumpBytes::new_in(&self.arena);
+
+        bytes.extend_from_slice(&FORMAT_BYTES);
+        bytes.push(BOUND_MIN_TAG);
+        bytes
+    }
+
+    pub fn decode_replication_position(bytes: &[u8]) -> Result<(String, u64), DatabaseError> {
+        let mut bytes = Cursor::new(bytes);
+        let lsn = bytes.read_u64::<BigEndian>()?;
+        let mut epoch = String::new();
+        bytes.read_to_string(&mut epoch)?;
+
+        Ok((epoch, lsn))
+    }
+
     /// Key: Root{BOUND_MIN_TAG}{TableName}
     /// Value: TableMeta
     pub fn encode_root_table(
@@ -533,14 +861,16 @@ impl TableCodec {
 #[cfg(test)]
 mod tests {
     use crate::binder::test::build_t1_table;
+    use crate::catalog::task::Task;
     use crate::catalog::view::View;
     use crate::catalog::{
-        ColumnCatalog, ColumnDesc, ColumnRef, ColumnRelation, TableCatalog, TableMeta,
+        ColumnCatalog, ColumnDesc, ColumnRef, ColumnRelation, ForeignTable, TableCatalog,
     };
     use crate::errors::DatabaseError;
     use crate::serdes::ReferenceTables;
     use crate::storage::rocksdb::RocksTransaction;
     use crate::storage::table_codec::{BumpBytes, TableCodec};
+    use crate::storage::tuple_codec::TupleCodecEnum;
     use crate::storage::Storage;
     use crate::types::index::{Index, IndexMeta, IndexType};
     use crate::types::tuple::Tuple;
@@ -581,24 +911,14 @@ mod tests {
             Some(DataValue::Int32(0)),
             vec![DataValue::Int32(0), DataValue::Decimal(Decimal::new(1, 0))],
         );
-        let (_, bytes) = table_codec.encode_tuple(
-            &table_catalog.name,
-            &mut tuple,
-            &[LogicalType::Integer, LogicalType::Decimal(None, None)],
-        )?;
+        let tuple_codec = TupleCodecEnum::instance(&table_catalog)?;
+        let (_, bytes) = table_codec.encode_tuple(&table_catalog.name, &mut tuple, &tuple_codec)?;
         let schema = table_catalog.schema_ref();
         let pk_indices = table_catalog.primary_keys_indices();
 
         tuple.pk = None;
         assert_eq!(
-            TableCodec::decode_tuple(
-                &table_catalog.types(),
-                pk_indices,
-                &[0, 1],
-                schema,
-                &bytes,
-                false
-            )?,
+            TableCodec::decode_tuple(&tuple_codec, pk_indices, &[0, 1], schema, &bytes, false)?,
             tuple
         );
 
@@ -610,16 +930,40 @@ mod tests {
         let table_codec = TableCodec {
             arena: Default::default(),
         };
-        let table_catalog = build_table_codec();
+        let mut table_catalog = build_table_codec();
+        let foreign = ForeignTable {
+            wrapper: "postgres".to_string(),
+            connection: "host=127.0.0.1".to_string(),
+            remote_table: "t1".to_string(),
+        };
+        table_catalog.foreign = Some(foreign.clone());
         let (_, bytes) = table_codec
-            .encode_root_table(&TableMeta {
-                table_name: table_catalog.name.clone(),
-            })
+            .encode_root_table(&table_catalog.meta())
             .unwrap();
 
         let table_meta = TableCodec::decode_root_table::<RocksTransaction>(&bytes).unwrap();
 
-        assert_eq!(table_meta.table_name.as_str(), table_catalog.name.as_str());
+        assert_eq!(table_meta, table_catalog.meta());
+        assert_eq!(table_meta.foreign, Some(foreign));
+    }
+
+    #[test]
+    fn test_table_codec_task() -> Result<(), DatabaseError> {
+        let table_codec = TableCodec {
+            arena: Default::default(),
+        };
+        let task = Task {
+            name: "ttl".to_string(),
+            schedule: "*/5 * * * *".to_string(),
+            sql: "delete from t1 where c1 < 10".to_string(),
+        };
+        let (key, bytes) = table_codec.encode_task(&task)?;
+        let (min, max) = table_codec.task_bound();
+
+        assert!(min.as_slice() < key.as_slice() && key.as_slice() < max.as_slice());
+        assert_eq!(TableCodec::decode_task::<RocksTransaction>(&bytes)?, task);
+
+        Ok(())
     }
 
     #[test]
@@ -0,0 +1,378 @@
+use crate::catalog::TableCatalog;
+use crate::errors::DatabaseError;
+use crate::storage::table_codec::BumpBytes;
+use crate::types::tuple::{Schema, Tuple};
+use crate::types::value::DataValue;
+use crate::types::{ColumnId, LogicalType};
+use bumpalo::Bump;
+use itertools::Itertools;
+use kite_sql_serde_macros::ReferenceSerialization;
+
+const VERSION_LEN: usize = size_of::<u32>();
+
+/// The columns of a table the rows were written with, in the order their values are stored.
+pub type SchemaVersion = Vec<(ColumnId, LogicalType)>;
+
+/// How the rows of a table are stored, chosen by `CREATE TABLE ... WITH (tuple_codec = '...')`.
+///
+/// The catalog of a table records its codec, the tables whose catalog records none were written
+/// before the codecs and are read as [`TupleCodecKind::Plain`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub enum TupleCodecKind {
+    /// The values of the current columns, ADD/DROP COLUMN rewrites all rows
+    #[default]
+    Plain,
+    /// The values of the columns of a schema version followed by the version,
+    /// ADD/DROP COLUMN only adds a version and the old rows are decoded with it
+    Versioned,
+}
+
+impl TupleCodecKind {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "plain" => Some(TupleCodecKind::Plain),
+            "versioned" => Some(TupleCodecKind::Versioned),
+            _ => None,
+        }
+    }
+}
+
+pub trait TupleCodec {
+    /// Serializes the values of the current columns of the table.
+    fn encode<'a>(&self, tuple: &Tuple, arena: &'a Bump) -> Result<BumpBytes<'a>, DatabaseError>;
+
+    /// Deserializes the values of `projections`, the sorted indices of the current columns.
+    fn decode(
+        &self,
+        pk_indices: &[usize],
+        projections: &[usize],
+        schema: &Schema,
+        bytes: &[u8],
+        with_pk: bool,
+    ) -> Result<Tuple, DatabaseError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum TupleCodecEnum {
+    Plain(PlainTupleCodec),
+    Versioned(VersionedTupleCodec),
+}
+
+/// e.g.: bits(u8)..|data_0(len for utf8_1)|utf8_0|data_1|, see [`Tuple::serialize_to`]
+#[derive(Debug, Clone)]
+pub struct PlainTupleCodec {
+    types: Vec<LogicalType>,
+}
+
+/// e.g.: bits(u8)..|data_0|data_1|version(u32)|
+///
+/// The row is written with the columns of the current version, and decoded with the columns of
+/// its own version: the dropped columns are skipped and the added ones take their default value.
+#[derive(Debug, Clone)]
+pub struct VersionedTupleCodec {
+    version: u32,
+    types: Vec<LogicalType>,
+    /// By version, the types of the stored values and the slot of each current column
+    versions: Vec<(Vec<LogicalType>, Vec<Option<usize>>)>,
+    /// The values of the current columns in the rows written before they were added
+    defaults: Vec<DataValue>,
+}
+
+impl TupleCodecEnum {
+    pub(crate) fn instance(table: &TableCatalog) -> Result<Self, DatabaseError> {
+        Ok(match table.tuple_codec {
+            TupleCodecKind::Plain => TupleCodecEnum::Plain(PlainTupleCodec::new(table.types())),
+            TupleCodecKind::Versioned => {
+                TupleCodecEnum::Versioned(VersionedTupleCodec::new(table)?)
+            }
+        })
+    }
+}
+
+impl TupleCodec for TupleCodecEnum {
+    fn encode<'a>(&self, tuple: &Tuple, arena: &'a Bump) -> Result<BumpBytes<'a>, DatabaseError> {
+        match self {
+            TupleCodecEnum::Plain(codec) => codec.encode(tuple, arena),
+            TupleCodecEnum::Versioned(codec) => codec.encode(tuple, arena),
+        }
+    }
+
+    fn decode(
+        &self,
+        pk_indices: &[usize],
+        projections: &[usize],
+        schema: &Schema,
+        bytes: &[u8],
+        with_pk: bool,
+    ) -> Result<Tuple, DatabaseError> {
+        match self {
+            TupleCodecEnum::Plain(codec) => {
+                codec.decode(pk_indices, projections, schema, bytes, with_pk)
+            }
+            TupleCodecEnum::Versioned(codec) => {
+                codec.decode(pk_indices, projections, schema, bytes, with_pk)
+            }
+        }
+    }
+}
+
+impl PlainTupleCodec {
+    pub(crate) fn new(types: Vec<LogicalType>) -> Self {
+        PlainTupleCodec { types }
+    }
+}
+
+impl TupleCodec for PlainTupleCodec {
+    fn encode<'a>(&self, tuple: &Tuple, arena: &'a Bump) -> Result<BumpBytes<'a>, DatabaseError> {
+        tuple.serialize_to(&self.types, arena)
+    }
+
+    fn decode(
+        &self,
+        pk_indices: &[usize],
+        projections: &[usize],
+        schema: &Schema,
+        bytes: &[u8],
+        with_pk: bool,
+    ) -> Result<Tuple, DatabaseError> {
+        Tuple::deserialize_from(&self.types, pk_indices, projections, schema, bytes, with_pk)
+    }
+}
+
+impl VersionedTupleCodec {
+    pub(crate) fn new(table: &TableCatalog) -> Result<Self, DatabaseError> {
+        let column_ids = table.columns().map(|column| column.id()).collect_vec();
+        let versions = table
+            .schema_versions
+            .iter()
+            .map(|version| {
+                let slots = column_ids
+                    .iter()
+                    .map(|column_id| version.iter().position(|(id, _)| Some(*id) == *column_id))
+                    .collect_vec();
+                let types = version.iter().map(|(_, ty)| ty.clone()).collect_vec();
+
+                (types, slots)
+            })
+            .collect_vec();
+        let defaults = table
+            .columns()
+            .map(|column| Ok(column.default_value()?.unwrap_or(DataValue::Null)))
+            .try_collect::<_, _, DatabaseError>()?;
+
+        Ok(VersionedTupleCodec {
+            version: versions.len().saturating_sub(1) as u32,
+            types: table.types(),
+            versions,
+            defaults,
+        })
+    }
+}
+
+impl TupleCodec for VersionedTupleCodec {
+    fn encode<'a>(&self, tuple: &Tuple, arena: &'a Bump) -> Result<BumpBytes<'a>, DatabaseError> {
+        let mut bytes = tuple.serialize_to(&self.types, arena)?;
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+
+        Ok(bytes)
+    }
+
+    fn decode(
+        &self,
+        pk_indices: &[usize],
+        projections: &[usize],
+        schema: &Schema,
+        bytes: &[u8],
+        with_pk: bool,
+    ) -> Result<Tuple, DatabaseError> {
+        let (bytes, version) = bytes.split_last_chunk::<VERSION_LEN>().ok_or_else(|| {
+            DatabaseError::InvalidValue(format!(
+                "tuple of {} bytes without its schema version",
+                bytes.len()
+            ))
+        })?;
+        let version = u32::from_le_bytes(*version);
+
+        if version == self.version {
+            return Tuple::deserialize_from(
+                &self.types,
+                pk_indices,
+                projections,
+                schema,
+                bytes,
+                with_pk,
+            );
+        }
+        let (types, slots) =
+            self.versions
+                .get(version as usize)
+                .ok_or(DatabaseError::InvalidValue(format!(
+                    "schema version {version} of the tuple"
+                )))?;
+        let mut values = Vec::with_capacity(projections.len());
+        let mut stored = Vec::with_capacity(projections.len());
+
+        for (position, i) in projections.iter().enumerate() {
+            match slots[*i] {
+                Some(slot) => {
+                    values.push(DataValue::Null);
+                    stored.push((slot, position));
+                }
+                None => values.push(self.defaults[*i].clone()),
+            }
+        }
+        stored.sort_unstable();
+        let (stored_projections, positions): (Vec<_>, Vec<_>) = stored.into_iter().unzip();
+
+        Tuple::decode_raw(types, &stored_projections, bytes, |i, value| {
+            values[positions[i]] = value;
+        })?;
+
+        Ok(Tuple {
+            pk: with_pk.then(|| Tuple::primary_projection(pk_indices, &values)),
+            values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::{ColumnCatalog, ColumnDesc, TableCatalog};
+    use crate::errors::DatabaseError;
+    use crate::expression::ScalarExpression;
+    use crate::storage::tuple_codec::{TupleCodec, TupleCodecEnum, TupleCodecKind};
+    use crate::types::tuple::Tuple;
+    use crate::types::value::{DataValue, Utf8Type};
+    use crate::types::LogicalType;
+    use bumpalo::Bump;
+    use itertools::Itertools;
+    use sqlparser::ast::CharLengthUnits;
+    use std::sync::Arc;
+    use ulid::Generator;
+
+    #[test]
+    fn test_versioned_tuple_codec() -> Result<(), DatabaseError> {
+        let varchar = LogicalType::Varchar(None, CharLengthUnits::Characters);
+        let mut table = TableCatalog::new(
+            Arc::new("t1".to_string()),
+            vec![
+                ColumnCatalog::new(
+                    "c1".to_string(),
+                    false,
+                    ColumnDesc::new(LogicalType::Integer, Some(0), false, None)?,
+                ),
+                ColumnCatalog::new(
+                    "c2".to_string(),
+                    true,
+                    ColumnDesc::new(LogicalType::Integer, None, false, None)?,
+                ),
+                ColumnCatalog::new(
+                    "c3".to_string(),
+                    true,
+                    ColumnDesc::new(varchar.clone(), None, false, None)?,
+                ),
+            ],
+        )?;
+        table.tuple_codec = TupleCodecKind::Versioned;
+        let arena = Bump::new();
+        let a = DataValue::Utf8 {
+            value: "a".to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+        let bytes = TupleCodecEnum::instance(&table)?.encode(
+            &Tuple::new(
+                Some(DataValue::Int32(0)),
+                vec![DataValue::Int32(0), DataValue::Null, a.clone()],
+            ),
+            &arena,
+        )?;
+
+        // drop c2, then add c4 with a default
+        let c2_id = *table.get_column_id_by_name("c2").unwrap();
+        table.push_schema_version(Some(&c2_id));
+        let _ = table.add_column(
+            ColumnCatalog::new(
+                "c4".to_string(),
+                true,
+                ColumnDesc::new(
+                    LogicalType::Integer,
+                    None,
+                    false,
+                    Some(ScalarExpression::Constant(DataValue::Int32(1))),
+                )?,
+            ),
+            &mut Generator::new(),
+        )?;
+        table.push_schema_version(Some(&c2_id));
+        let columns = table
+            .columns()
+            .filter(|column| column.id() != Some(c2_id))
+            .cloned()
+            .collect_vec();
+        let table = TableCatalog::reload(table.name.clone(), columns, vec![], Some(table.meta()))?;
+        let tuple_codec = TupleCodecEnum::instance(&table)?;
+        let schema = table.schema_ref();
+
+        let tuple = tuple_codec.decode(
+            table.primary_keys_indices(),
+            &[0, 1, 2],
+            schema,
+            &bytes,
+            true,
+        )?;
+        assert_eq!(tuple.pk, Some(DataValue::Int32(0)));
+        assert_eq!(
+            tuple.values,
+            vec![DataValue::Int32(0), a.clone(), DataValue::Int32(1)]
+        );
+
+        let tuple = tuple_codec.decode(
+            table.primary_keys_indices(),
+            &[1, 2],
+            &schema[1..].to_vec(),
+            &bytes,
+            false,
+        )?;
+        assert_eq!(tuple.values, vec![a.clone(), DataValue::Int32(1)]);
+
+        // the rows written now are of the last version
+        let bytes = tuple_codec.encode(
+            &Tuple::new(
+                Some(DataValue::Int32(1)),
+                vec![DataValue::Int32(1), DataValue::Null, DataValue::Int32(2)],
+            ),
+            &arena,
+        )?;
+        let tuple = tuple_codec.decode(
+            table.primary_keys_indices(),
+            &[0, 1, 2],
+            schema,
+            &bytes,
+            false,
+        )?;
+        assert_eq!(
+            tuple.values,
+            vec![DataValue::Int32(1), DataValue::Null, DataValue::Int32(2)]
+        );
+        assert_eq!(table.schema_versions.len(), 3);
+        assert_eq!(
+            table.schema_versions[2][1],
+            (schema[1].id().unwrap(), varchar)
+        );
+
+        // a value too short to hold a schema version
+        assert!(matches!(
+            tuple_codec.decode(table.primary_keys_indices(), &[0], schema, &[0, 1], false),
+            Err(DatabaseError::InvalidValue(_))
+        ));
+        // the catalogs of the tables written before the codecs read them as plain
+        let table = TableCatalog::reload(table.name.clone(), schema.to_vec(), vec![], None)?;
+        assert!(matches!(
+            TupleCodecEnum::instance(&table)?,
+            TupleCodecEnum::Plain(_)
+        ));
+
+        Ok(())
+    }
+}
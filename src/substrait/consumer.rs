@@ -0,0 +1,530 @@
+use crate::catalog::TableName;
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::{AliasType, BinaryOperator, ScalarExpression, UnaryOperator};
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::filter::FilterOperator;
+use crate::planner::operator::limit::LimitOperator;
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::sort::{SortField, SortOperator};
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::{TableCache, Transaction};
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use ahash::HashMap;
+use ordered_float::OrderedFloat;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+use substrait::proto::aggregate_function::AggregationInvocation;
+use substrait::proto::expression::field_reference::ReferenceType;
+use substrait::proto::expression::literal::LiteralType;
+use substrait::proto::expression::{reference_segment, Literal, RexType};
+use substrait::proto::extensions::simple_extension_declaration::MappingType;
+use substrait::proto::function_argument::ArgType;
+use substrait::proto::read_rel::ReadType;
+use substrait::proto::rel_common::EmitKind;
+use substrait::proto::sort_field::{SortDirection, SortKind};
+use substrait::proto::{
+    plan_rel, rel, AggregateFunction, Expression, FunctionArgument, Plan, Rel, RelCommon,
+};
+
+/// Binds a Substrait plan to the tables of the database, as a plan of a query before it is
+/// optimized.
+pub(crate) struct Consumer<'a, T: Transaction> {
+    plan: &'a Plan,
+    transaction: &'a T,
+    table_cache: &'a TableCache,
+    aggregate_memory_limit: usize,
+    functions: HashMap<u32, &'a str>,
+}
+
+impl<'a, T: Transaction> Consumer<'a, T> {
+    pub(crate) fn new(
+        plan: &'a Plan,
+        transaction: &'a T,
+        table_cache: &'a TableCache,
+        aggregate_memory_limit: usize,
+    ) -> Self {
+        let functions = plan
+            .extensions
+            .iter()
+            .filter_map(|extension| match &extension.mapping_type {
+                // the signature of the function, e.g. `add:i32_i32`, follows its name
+                Some(MappingType::ExtensionFunction(function)) => Some((
+                    function.function_anchor,
+                    function.name.split(':').next().unwrap_or_default(),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        Consumer {
+            plan,
+            transaction,
+            table_cache,
+            aggregate_memory_limit,
+            functions,
+        }
+    }
+
+    pub(crate) fn consume(&self) -> Result<LogicalPlan, DatabaseError> {
+        let Some(rel_type) = self
+            .plan
+            .relations
+            .first()
+            .and_then(|relation| relation.rel_type.as_ref())
+        else {
+            return Err(DatabaseError::EmptyPlan);
+        };
+        let (input, names) = match rel_type {
+            plan_rel::RelType::Root(root) => (root.input.as_ref(), root.names.as_slice()),
+            plan_rel::RelType::Rel(rel) => (Some(rel), [].as_slice()),
+        };
+        let (plan, mut exprs) = self.rel(input.ok_or_else(|| missing("input"))?)?;
+
+        // the root names the fields of the rows
+        if !names.is_empty() {
+            if names.len() != exprs.len() {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "the plan names {} of its {} fields",
+                    names.len(),
+                    exprs.len()
+                )));
+            }
+            exprs = exprs
+                .into_iter()
+                .zip(names)
+                .map(|(expr, name)| ScalarExpression::Alias {
+                    expr: Box::new(expr),
+                    alias: AliasType::Name(name.clone()),
+                })
+                .collect();
+        }
+
+        Ok(LogicalPlan::new(
+            Operator::Project(ProjectOperator { exprs }),
+            Childrens::Only(plan),
+        ))
+    }
+
+    /// The plan of the relation with the expressions of its fields in order.
+    fn rel(&self, rel: &Rel) -> Result<(LogicalPlan, Vec<ScalarExpression>), DatabaseError> {
+        let (common, plan, exprs) = match rel.rel_type.as_ref().ok_or_else(|| missing("rel"))? {
+            rel::RelType::Read(read) => {
+                let Some(ReadType::NamedTable(named_table)) = &read.read_type else {
+                    return Err(unsupported("the reads of anything but a named table"));
+                };
+                let table_name: TableName = Arc::new(
+                    named_table
+                        .names
+                        .last()
+                        .ok_or_else(|| missing("table name"))?
+                        .to_lowercase(),
+                );
+                let table = self
+                    .transaction
+                    .table(self.table_cache, table_name.clone())?
+                    .ok_or_else(|| DatabaseError::TableNotFound(table_name.to_string()))?;
+                let exprs = match &read.base_schema {
+                    Some(schema) => schema
+                        .names
+                        .iter()
+                        .map(|name| {
+                            table
+                                .get_column_by_name(name)
+                                .map(|column| ScalarExpression::ColumnRef(column.clone()))
+                                .ok_or_else(|| DatabaseError::ColumnNotFound(name.clone()))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    None => table
+                        .visible_columns()
+                        .map(|column| ScalarExpression::ColumnRef(column.clone()))
+                        .collect(),
+                };
+                let mut plan = TableScanOperator::build(table_name, table, true);
+
+                if let Some(filter) = &read.filter {
+                    plan = FilterOperator::build(self.expr(filter, &exprs)?, plan, false);
+                }
+                (&read.common, plan, exprs)
+            }
+            rel::RelType::Filter(filter) => {
+                let (plan, exprs) = self.input(&filter.input)?;
+                let predicate = filter
+                    .condition
+                    .as_ref()
+                    .ok_or_else(|| missing("condition"))?;
+                let plan = FilterOperator::build(self.expr(predicate, &exprs)?, plan, false);
+
+                (&filter.common, plan, exprs)
+            }
+            rel::RelType::Project(project) => {
+                let (plan, mut exprs) = self.input(&project.input)?;
+                // the expressions of a projection follow the fields of its input
+                for expr in project.expressions.iter() {
+                    let expr = self.expr(expr, &exprs)?;
+                    exprs.push(expr);
+                }
+                let plan = LogicalPlan::new(
+                    Operator::Project(ProjectOperator {
+                        exprs: exprs.clone(),
+                    }),
+                    Childrens::Only(plan),
+                );
+
+                (&project.common, plan, exprs)
+            }
+            rel::RelType::Aggregate(aggregate) => {
+                let (plan, input_exprs) = self.input(&aggregate.input)?;
+                let mut groupby_exprs = Vec::new();
+
+                match aggregate.groupings.as_slice() {
+                    [] => (),
+                    [grouping] => {
+                        for expr in grouping.grouping_expressions.iter() {
+                            groupby_exprs.push(self.expr(expr, &input_exprs)?);
+                        }
+                    }
+                    _ => return Err(unsupported("the grouping sets")),
+                }
+                let agg_calls = aggregate
+                    .measures
+                    .iter()
+                    .map(|measure| {
+                        if measure.filter.is_some() {
+                            return Err(unsupported("the filters of the aggregate functions"));
+                        }
+                        let function =
+                            measure.measure.as_ref().ok_or_else(|| missing("measure"))?;
+                        self.agg_call(function, &input_exprs)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                // the groups come first in substrait, after the calls in KiteSQL
+                let exprs = groupby_exprs
+                    .iter()
+                    .chain(agg_calls.iter())
+                    .cloned()
+                    .collect();
+                let plan = AggregateOperator::build(
+                    plan,
+                    agg_calls,
+                    groupby_exprs,
+                    false,
+                    self.aggregate_memory_limit,
+                );
+
+                (&aggregate.common, plan, exprs)
+            }
+            rel::RelType::Sort(sort) => {
+                let (plan, exprs) = self.input(&sort.input)?;
+                let sort_fields = sort
+                    .sorts
+                    .iter()
+                    .map(|field| {
+                        let expr = field
+                            .expr
+                            .as_ref()
+                            .ok_or_else(|| missing("sort expression"))?;
+                        let (asc, nulls_first) = match field.sort_kind {
+                            Some(SortKind::Direction(direction)) => {
+                                match SortDirection::try_from(direction) {
+                                    Ok(SortDirection::AscNullsFirst) => (true, true),
+                                    Ok(SortDirection::AscNullsLast) => (true, false),
+                                    Ok(SortDirection::DescNullsFirst) => (false, true),
+                                    Ok(SortDirection::DescNullsLast) => (false, false),
+                                    _ => return Err(unsupported("the sort direction")),
+                                }
+                            }
+                            _ => return Err(unsupported("the sorts by a comparison function")),
+                        };
+                        Ok(SortField::new(self.expr(expr, &exprs)?, asc, nulls_first))
+                    })
+                    .collect::<Result<Vec<_>, DatabaseError>>()?;
+                let plan = LogicalPlan::new(
+                    Operator::Sort(SortOperator {
+                        sort_fields,
+                        limit: None,
+                        presorted: 0,
+                    }),
+                    Childrens::Only(plan),
+                );
+
+                (&sort.common, plan, exprs)
+            }
+            rel::RelType::Fetch(fetch) => {
+                let (plan, exprs) = self.input(&fetch.input)?;
+                let offset = (fetch.offset > 0).then_some(fetch.offset as usize);
+                // a negative count fetches all the rows after the offset
+                let limit = (fetch.count >= 0).then_some(fetch.count as usize);
+
+                (
+                    &fetch.common,
+                    LimitOperator::build(offset, limit, plan),
+                    exprs,
+                )
+            }
+            _ => return Err(unsupported("the relation")),
+        };
+
+        Ok((plan, emit(common, exprs)?))
+    }
+
+    fn input(
+        &self,
+        input: &Option<Box<Rel>>,
+    ) -> Result<(LogicalPlan, Vec<ScalarExpression>), DatabaseError> {
+        self.rel(input.as_ref().ok_or_else(|| missing("input"))?)
+    }
+
+    fn agg_call(
+        &self,
+        function: &AggregateFunction,
+        exprs: &[ScalarExpression],
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let mut args = self.arguments(&function.arguments, exprs)?;
+        let kind = match self.function_name(function.function_reference)? {
+            "count" => {
+                // `count()` counts the rows as `count(*)`
+                if args.is_empty() {
+                    args.push(ScalarExpression::Constant(DataValue::Utf8 {
+                        value: "*".to_string(),
+                        ty: Utf8Type::Variable(None),
+                        unit: CharLengthUnits::Characters,
+                    }));
+                }
+                AggKind::Count
+            }
+            "sum" => AggKind::Sum,
+            "min" => AggKind::Min,
+            "max" => AggKind::Max,
+            "avg" => AggKind::Avg,
+            name => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "the substrait aggregate function `{}`",
+                    name
+                )))
+            }
+        };
+        if args.len() != 1 {
+            return Err(DatabaseError::MisMatch(
+                "number of aggregate function parameters",
+                "1",
+            ));
+        }
+        let ty = match kind {
+            AggKind::Count => LogicalType::Integer,
+            AggKind::Avg => LogicalType::Double,
+            _ => args[0].return_type(),
+        };
+        let distinct = function.invocation == AggregationInvocation::Distinct as i32;
+
+        Ok(ScalarExpression::AggCall {
+            distinct,
+            kind,
+            args,
+            ty,
+        })
+    }
+
+    fn expr(
+        &self,
+        expr: &Expression,
+        exprs: &[ScalarExpression],
+    ) -> Result<ScalarExpression, DatabaseError> {
+        match expr
+            .rex_type
+            .as_ref()
+            .ok_or_else(|| missing("expression"))?
+        {
+            RexType::Selection(field) => {
+                let Some(ReferenceType::DirectReference(segment)) = &field.reference_type else {
+                    return Err(unsupported("the masked references"));
+                };
+                let Some(reference_segment::ReferenceType::StructField(field)) =
+                    &segment.reference_type
+                else {
+                    return Err(unsupported("the references into anything but a field"));
+                };
+                field_of(exprs, field.field)
+            }
+            RexType::Literal(literal) => Ok(ScalarExpression::Constant(value(literal)?)),
+            RexType::ScalarFunction(function) => {
+                let mut args = self.arguments(&function.arguments, exprs)?;
+                let name = self.function_name(function.function_reference)?;
+
+                let op = match name {
+                    "not" | "negate" | "is_null" | "is_not_null" => {
+                        let [expr] = <[ScalarExpression; 1]>::try_from(args).map_err(|_| {
+                            DatabaseError::MisMatch("number of unary function parameters", "1")
+                        })?;
+                        let expr = Box::new(expr);
+
+                        return Ok(match name {
+                            "not" => ScalarExpression::Unary {
+                                op: UnaryOperator::Not,
+                                expr,
+                                evaluator: None,
+                                ty: LogicalType::Boolean,
+                            },
+                            "negate" => ScalarExpression::Unary {
+                                op: UnaryOperator::Minus,
+                                ty: expr.return_type(),
+                                expr,
+                                evaluator: None,
+                            },
+                            _ => ScalarExpression::IsNull {
+                                negated: name == "is_not_null",
+                                expr,
+                            },
+                        });
+                    }
+                    "add" => BinaryOperator::Plus,
+                    "subtract" => BinaryOperator::Minus,
+                    "multiply" => BinaryOperator::Multiply,
+                    "divide" => BinaryOperator::Divide,
+                    "modulus" => BinaryOperator::Modulo,
+                    "gt" => BinaryOperator::Gt,
+                    "lt" => BinaryOperator::Lt,
+                    "gte" => BinaryOperator::GtEq,
+                    "lte" => BinaryOperator::LtEq,
+                    "equal" => BinaryOperator::Eq,
+                    "not_equal" => BinaryOperator::NotEq,
+                    "and" => BinaryOperator::And,
+                    "or" => BinaryOperator::Or,
+                    name => {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "the substrait function `{}`",
+                            name
+                        )))
+                    }
+                };
+                // `and` and `or` take any number of arguments
+                if args.len() < 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of binary function parameters",
+                        "2",
+                    ));
+                }
+                let first = args.remove(0);
+                args.into_iter().try_fold(first, |left_expr, right_expr| {
+                    let ty = binary_type(&op, &left_expr, &right_expr)?;
+
+                    Ok(ScalarExpression::Binary {
+                        op,
+                        left_expr: Box::new(left_expr),
+                        right_expr: Box::new(right_expr),
+                        evaluator: None,
+                        ty,
+                    })
+                })
+            }
+            _ => Err(unsupported("the expression")),
+        }
+    }
+
+    fn arguments(
+        &self,
+        arguments: &[FunctionArgument],
+        exprs: &[ScalarExpression],
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        arguments
+            .iter()
+            .map(|argument| match &argument.arg_type {
+                Some(ArgType::Value(expr)) => self.expr(expr, exprs),
+                _ => Err(unsupported("the arguments of anything but a value")),
+            })
+            .collect()
+    }
+
+    fn function_name(&self, anchor: u32) -> Result<&'a str, DatabaseError> {
+        self.functions.get(&anchor).copied().ok_or_else(|| {
+            DatabaseError::InvalidValue(format!("the function anchor {} is not declared", anchor))
+        })
+    }
+}
+
+/// The type of the binary expression, as the binder types it.
+fn binary_type(
+    op: &BinaryOperator,
+    left_expr: &ScalarExpression,
+    right_expr: &ScalarExpression,
+) -> Result<LogicalType, DatabaseError> {
+    let ty = LogicalType::max_logical_type(&left_expr.return_type(), &right_expr.return_type());
+
+    Ok(match op {
+        BinaryOperator::Plus
+        | BinaryOperator::Minus
+        | BinaryOperator::Multiply
+        | BinaryOperator::Modulo => ty?,
+        BinaryOperator::Divide => match ty? {
+            LogicalType::Decimal(precision, scale) => LogicalType::Decimal(precision, scale),
+            _ => LogicalType::Double,
+        },
+        _ => LogicalType::Boolean,
+    })
+}
+
+/// The fields of the relation kept by its `emit`, in the order of the mapping.
+fn emit(
+    common: &Option<RelCommon>,
+    exprs: Vec<ScalarExpression>,
+) -> Result<Vec<ScalarExpression>, DatabaseError> {
+    match common.as_ref().and_then(|common| common.emit_kind.as_ref()) {
+        Some(EmitKind::Emit(emit)) => emit
+            .output_mapping
+            .iter()
+            .map(|field| field_of(&exprs, *field))
+            .collect(),
+        _ => Ok(exprs),
+    }
+}
+
+fn field_of(exprs: &[ScalarExpression], field: i32) -> Result<ScalarExpression, DatabaseError> {
+    usize::try_from(field)
+        .ok()
+        .and_then(|field| exprs.get(field))
+        .cloned()
+        .ok_or_else(|| {
+            DatabaseError::InvalidValue(format!("the field {} is out of the relation", field))
+        })
+}
+
+fn value(literal: &Literal) -> Result<DataValue, DatabaseError> {
+    Ok(
+        match literal
+            .literal_type
+            .as_ref()
+            .ok_or_else(|| missing("literal"))?
+        {
+            LiteralType::Null(_) => DataValue::Null,
+            LiteralType::Boolean(value) => DataValue::Boolean(*value),
+            LiteralType::I8(value) => DataValue::Int8(*value as i8),
+            LiteralType::I16(value) => DataValue::Int16(*value as i16),
+            LiteralType::I32(value) => DataValue::Int32(*value),
+            LiteralType::I64(value) => DataValue::Int64(*value),
+            LiteralType::Fp32(value) => DataValue::Float32(OrderedFloat(*value)),
+            LiteralType::Fp64(value) => DataValue::Float64(OrderedFloat(*value)),
+            LiteralType::String(value) => DataValue::Utf8 {
+                value: value.clone(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            },
+            literal_type => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "the substrait literal `{:?}`",
+                    literal_type
+                )))
+            }
+        },
+    )
+}
+
+fn missing(field: &str) -> DatabaseError {
+    DatabaseError::InvalidValue(format!("the substrait plan has no {}", field))
+}
+
+fn unsupported(what: &str) -> DatabaseError {
+    DatabaseError::UnsupportedStmt(format!("{} of substrait", what))
+}
@@ -0,0 +1,137 @@
+//! The plans of queries as [Substrait](https://substrait.io) plans, see
+//! `Database::to_substrait` and `Database::execute_substrait`.
+//!
+//! The relations of a plan are scans of named tables, filters, projections, aggregations,
+//! sorts and fetches, on the types and the functions both sides have in common.
+pub(crate) mod consumer;
+pub(crate) mod producer;
+
+use crate::errors::DatabaseError;
+use crate::types::LogicalType;
+use substrait::proto::r#type::{self, Kind, Nullability};
+use substrait::proto::Type;
+
+const EXTENSIONS: &str = "https://github.com/substrait-io/substrait/blob/main/extensions";
+
+/// The extension of the standard functions the function is declared in.
+pub(crate) fn extension_uri(function: &str) -> String {
+    let extension = match function {
+        "and" | "or" | "not" => "functions_boolean",
+        "add" | "subtract" | "multiply" | "divide" | "modulus" | "negate" | "sum" | "min"
+        | "max" | "avg" => "functions_arithmetic",
+        "count" => "functions_aggregate_generic",
+        _ => "functions_comparison",
+    };
+    format!("{EXTENSIONS}/{extension}.yaml")
+}
+
+pub(crate) fn to_type(ty: &LogicalType) -> Result<Type, DatabaseError> {
+    let nullability = Nullability::Nullable as i32;
+    let kind = match ty {
+        LogicalType::Boolean => Kind::Bool(r#type::Boolean {
+            nullability,
+            ..Default::default()
+        }),
+        LogicalType::Tinyint => Kind::I8(r#type::I8 {
+            nullability,
+            ..Default::default()
+        }),
+        LogicalType::Smallint => Kind::I16(r#type::I16 {
+            nullability,
+            ..Default::default()
+        }),
+        LogicalType::Integer => Kind::I32(r#type::I32 {
+            nullability,
+            ..Default::default()
+        }),
+        LogicalType::Bigint => Kind::I64(r#type::I64 {
+            nullability,
+            ..Default::default()
+        }),
+        LogicalType::Float => Kind::Fp32(r#type::Fp32 {
+            nullability,
+            ..Default::default()
+        }),
+        LogicalType::Double => Kind::Fp64(r#type::Fp64 {
+            nullability,
+            ..Default::default()
+        }),
+        LogicalType::Char(..) | LogicalType::Varchar(..) => Kind::String(r#type::String {
+            nullability,
+            ..Default::default()
+        }),
+        ty => {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "the type `{}` has no substrait type",
+                ty
+            )))
+        }
+    };
+
+    Ok(Type { kind: Some(kind) })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{DataBaseBuilder, ResultIter};
+    use crate::errors::DatabaseError;
+    use itertools::Itertools;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_substrait_round_trip() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        kite_sql
+            .run("create table t1 (a int primary key, b int, c varchar)")?
+            .done()?;
+        kite_sql
+            .run("insert into t1 values (0, 1, 'x'), (1, 1, null), (2, 2, 'y'), (3, 4, 'z')")?
+            .done()?;
+
+        for sql in [
+            "select a, b + 1 as d from t1 where a > 0 and c is not null order by a desc limit 1",
+            "select c from t1 where not (b = 1) order by a offset 1",
+            "select b, count(a), sum(a), max(c) from t1 group by b order by b",
+            "select count(*), avg(b) from t1",
+        ] {
+            let plan = kite_sql.to_substrait(sql)?;
+            let iter = kite_sql.execute_substrait(&plan)?;
+            let names = iter
+                .schema()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect_vec();
+            let rows: Vec<_> = iter
+                .map(|tuple| tuple.map(|tuple| tuple.values))
+                .try_collect()?;
+
+            let iter = kite_sql.run(sql)?;
+            let expected_names = iter
+                .schema()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect_vec();
+            let expected: Vec<_> = iter
+                .map(|tuple| tuple.map(|tuple| tuple.values))
+                .try_collect()?;
+            assert_eq!(names, expected_names, "{sql}");
+            assert_eq!(rows, expected, "{sql}");
+        }
+        assert!(matches!(
+            kite_sql.to_substrait("select * from t1 join t1 as t2 on t1.a = t2.b"),
+            Err(DatabaseError::UnsupportedStmt(_))
+        ));
+        assert!(matches!(
+            kite_sql.to_substrait("delete from t1"),
+            Err(DatabaseError::UnsupportedStmt(_))
+        ));
+        assert!(matches!(
+            kite_sql.execute_substrait(b"not a plan"),
+            Err(DatabaseError::InvalidValue(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::{BinaryOperator, ScalarExpression, UnaryOperator};
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::substrait::{extension_uri, to_type};
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use ahash::HashMap;
+use substrait::proto::aggregate_function::AggregationInvocation;
+use substrait::proto::expression::field_reference::{ReferenceType, RootReference, RootType};
+use substrait::proto::expression::literal::LiteralType;
+use substrait::proto::expression::reference_segment::StructField;
+use substrait::proto::expression::{
+    reference_segment, FieldReference, Literal, ReferenceSegment, RexType, ScalarFunction,
+};
+use substrait::proto::extensions::simple_extension_declaration::{ExtensionFunction, MappingType};
+use substrait::proto::extensions::{SimpleExtensionDeclaration, SimpleExtensionUri};
+use substrait::proto::function_argument::ArgType;
+use substrait::proto::read_rel::{NamedTable, ReadType};
+use substrait::proto::rel_common::{Emit, EmitKind};
+use substrait::proto::sort_field::{SortDirection, SortKind};
+use substrait::proto::{
+    aggregate_rel, plan_rel, r#type, rel, AggregateFunction, AggregateRel, AggregationPhase,
+    Expression, FetchRel, FilterRel, FunctionArgument, NamedStruct, Plan, PlanRel, ProjectRel,
+    ReadRel, Rel, RelCommon, RelRoot, SortField, SortRel,
+};
+
+/// Writes a plan, bound and normalized but not implemented, as a Substrait plan.
+#[derive(Default)]
+pub(crate) struct Producer {
+    extension_uris: Vec<SimpleExtensionUri>,
+    extensions: Vec<SimpleExtensionDeclaration>,
+    anchors: HashMap<&'static str, u32>,
+}
+
+impl Producer {
+    pub(crate) fn produce(mut self, mut plan: LogicalPlan) -> Result<Plan, DatabaseError> {
+        let names = plan
+            .output_schema()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect();
+        let (input, _) = self.rel(plan)?;
+
+        Ok(Plan {
+            extension_uris: self.extension_uris,
+            extensions: self.extensions,
+            relations: vec![PlanRel {
+                rel_type: Some(plan_rel::RelType::Root(RelRoot {
+                    input: Some(input),
+                    names,
+                })),
+            }],
+            ..Default::default()
+        })
+    }
+
+    /// The relation of the plan with the number of its fields.
+    fn rel(&mut self, plan: LogicalPlan) -> Result<(Rel, usize), DatabaseError> {
+        let LogicalPlan {
+            operator,
+            childrens,
+            ..
+        } = plan;
+        let input = match *childrens {
+            Childrens::None => None,
+            Childrens::Only(child) => Some(self.rel(child)?),
+            Childrens::Twins { .. } => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "`{}` can not be exported to substrait",
+                    operator
+                )))
+            }
+        };
+
+        let (rel_type, len) = match (operator, input) {
+            (Operator::TableScan(op), None) => {
+                let (names, types) = op
+                    .columns()
+                    .map(|column| Ok((column.name().to_string(), to_type(column.datatype())?)))
+                    .collect::<Result<(Vec<_>, Vec<_>), DatabaseError>>()?;
+                let len = names.len();
+                let read = rel::RelType::Read(Box::new(ReadRel {
+                    base_schema: Some(NamedStruct {
+                        names,
+                        r#struct: Some(r#type::Struct {
+                            types,
+                            nullability: r#type::Nullability::Required as i32,
+                            ..Default::default()
+                        }),
+                    }),
+                    read_type: Some(ReadType::NamedTable(NamedTable {
+                        names: vec![op.table_name().to_string()],
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }));
+                (read, len)
+            }
+            (Operator::Filter(op), Some((input, len))) => (
+                rel::RelType::Filter(Box::new(FilterRel {
+                    input: Some(Box::new(input)),
+                    condition: Some(Box::new(self.expr(&op.predicate)?)),
+                    ..Default::default()
+                })),
+                len,
+            ),
+            (Operator::Project(op), Some((input, len))) => {
+                let expressions = op
+                    .exprs
+                    .iter()
+                    .map(|expr| self.expr(expr))
+                    .collect::<Result<Vec<_>, _>>()?;
+                // the expressions of a projection follow the fields of its input
+                let output_mapping = (len..len + expressions.len()).map(|i| i as i32).collect();
+                let project = rel::RelType::Project(Box::new(ProjectRel {
+                    common: Some(emit(output_mapping)),
+                    input: Some(Box::new(input)),
+                    expressions,
+                    ..Default::default()
+                }));
+                (project, op.exprs.len())
+            }
+            (Operator::Aggregate(op), Some((input, _))) => {
+                let grouping_expressions = op
+                    .groupby_exprs
+                    .iter()
+                    .map(|expr| self.expr(expr))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let measures = op
+                    .agg_calls
+                    .iter()
+                    .map(|expr| self.measure(expr))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (groups, calls) = (grouping_expressions.len(), measures.len());
+                // the groups come first in substrait, after the calls in KiteSQL
+                let output_mapping = (groups..groups + calls)
+                    .chain(0..groups)
+                    .map(|i| i as i32)
+                    .collect();
+                let groupings = if groups == 0 {
+                    vec![]
+                } else {
+                    vec![aggregate_rel::Grouping {
+                        grouping_expressions,
+                        ..Default::default()
+                    }]
+                };
+                let aggregate = rel::RelType::Aggregate(Box::new(AggregateRel {
+                    common: Some(emit(output_mapping)),
+                    input: Some(Box::new(input)),
+                    groupings,
+                    measures,
+                    ..Default::default()
+                }));
+                (aggregate, groups + calls)
+            }
+            (Operator::Sort(op), Some((input, len))) => {
+                let sorts = op
+                    .sort_fields
+                    .iter()
+                    .map(|field| {
+                        let direction = match (field.asc, field.nulls_first) {
+                            (true, true) => SortDirection::AscNullsFirst,
+                            (true, false) => SortDirection::AscNullsLast,
+                            (false, true) => SortDirection::DescNullsFirst,
+                            (false, false) => SortDirection::DescNullsLast,
+                        };
+                        Ok(SortField {
+                            expr: Some(self.expr(&field.expr)?),
+                            sort_kind: Some(SortKind::Direction(direction as i32)),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, DatabaseError>>()?;
+                let sort = rel::RelType::Sort(Box::new(SortRel {
+                    input: Some(Box::new(input)),
+                    sorts,
+                    ..Default::default()
+                }));
+                match op.limit {
+                    Some(limit) => (
+                        fetch(
+                            Rel {
+                                rel_type: Some(sort),
+                            },
+                            None,
+                            Some(limit),
+                        ),
+                        len,
+                    ),
+                    None => (sort, len),
+                }
+            }
+            (Operator::Limit(op), Some((input, len))) => (fetch(input, op.offset, op.limit), len),
+            (operator, _) => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "`{}` can not be exported to substrait",
+                    operator
+                )))
+            }
+        };
+
+        Ok((
+            Rel {
+                rel_type: Some(rel_type),
+            },
+            len,
+        ))
+    }
+
+    fn measure(
+        &mut self,
+        expr: &ScalarExpression,
+    ) -> Result<aggregate_rel::Measure, DatabaseError> {
+        let ScalarExpression::AggCall {
+            distinct,
+            kind,
+            args,
+            ty,
+        } = expr.unpack_alias_ref()
+        else {
+            return Err(unsupported(expr));
+        };
+        let name = match kind {
+            AggKind::Count => "count",
+            AggKind::Sum => "sum",
+            AggKind::Min => "min",
+            AggKind::Max => "max",
+            AggKind::Avg => "avg",
+            _ => return Err(unsupported(expr)),
+        };
+        let invocation = if *distinct {
+            AggregationInvocation::Distinct
+        } else {
+            AggregationInvocation::All
+        };
+
+        Ok(aggregate_rel::Measure {
+            measure: Some(AggregateFunction {
+                function_reference: self.anchor(name),
+                arguments: self.arguments(args)?,
+                output_type: Some(to_type(ty)?),
+                phase: AggregationPhase::InitialToResult as i32,
+                invocation: invocation as i32,
+                ..Default::default()
+            }),
+            filter: None,
+        })
+    }
+
+    fn expr(&mut self, expr: &ScalarExpression) -> Result<Expression, DatabaseError> {
+        let rex_type = match expr {
+            ScalarExpression::Reference { pos, .. } => RexType::Selection(Box::new(field(*pos))),
+            ScalarExpression::Alias { expr, .. } => return self.expr(expr),
+            ScalarExpression::Constant(value) => RexType::Literal(literal(value)?),
+            ScalarExpression::Binary {
+                op,
+                left_expr,
+                right_expr,
+                ty,
+                ..
+            } => {
+                let name = match op {
+                    BinaryOperator::Plus => "add",
+                    BinaryOperator::Minus => "subtract",
+                    BinaryOperator::Multiply => "multiply",
+                    BinaryOperator::Divide => "divide",
+                    BinaryOperator::Modulo => "modulus",
+                    BinaryOperator::Gt => "gt",
+                    BinaryOperator::Lt => "lt",
+                    BinaryOperator::GtEq => "gte",
+                    BinaryOperator::LtEq => "lte",
+                    BinaryOperator::Eq => "equal",
+                    BinaryOperator::NotEq => "not_equal",
+                    BinaryOperator::And => "and",
+                    BinaryOperator::Or => "or",
+                    _ => return Err(unsupported(expr)),
+                };
+                self.function(name, &[left_expr, right_expr], ty)?
+            }
+            ScalarExpression::Unary { op, expr, ty, .. } => match op {
+                UnaryOperator::Not => self.function("not", &[expr], ty)?,
+                UnaryOperator::Minus => self.function("negate", &[expr], ty)?,
+                UnaryOperator::Plus => return self.expr(expr),
+            },
+            ScalarExpression::IsNull { negated, expr } => {
+                let name = if *negated { "is_not_null" } else { "is_null" };
+                self.function(name, &[expr], &LogicalType::Boolean)?
+            }
+            _ => return Err(unsupported(expr)),
+        };
+
+        Ok(Expression {
+            rex_type: Some(rex_type),
+        })
+    }
+
+    fn function(
+        &mut self,
+        name: &'static str,
+        args: &[&ScalarExpression],
+        ty: &LogicalType,
+    ) -> Result<RexType, DatabaseError> {
+        Ok(RexType::ScalarFunction(ScalarFunction {
+            function_reference: self.anchor(name),
+            arguments: self.arguments(args.iter().copied())?,
+            output_type: Some(to_type(ty)?),
+            ..Default::default()
+        }))
+    }
+
+    fn arguments<'a>(
+        &mut self,
+        args: impl IntoIterator<Item = &'a ScalarExpression>,
+    ) -> Result<Vec<FunctionArgument>, DatabaseError> {
+        args.into_iter()
+            .map(|arg| {
+                Ok(FunctionArgument {
+                    arg_type: Some(ArgType::Value(self.expr(arg)?)),
+                })
+            })
+            .collect()
+    }
+
+    /// The anchor of the function in the extensions of the plan, declared on its first use.
+    fn anchor(&mut self, name: &'static str) -> u32 {
+        if let Some(anchor) = self.anchors.get(name) {
+            return *anchor;
+        }
+        let anchor = self.anchors.len() as u32 + 1;
+        let uri = extension_uri(name);
+        let extension_uri_reference = match self
+            .extension_uris
+            .iter()
+            .find(|extension| extension.uri == uri)
+        {
+            Some(extension) => extension.extension_uri_anchor,
+            None => {
+                let extension_uri_anchor = self.extension_uris.len() as u32 + 1;
+                self.extension_uris.push(SimpleExtensionUri {
+                    extension_uri_anchor,
+                    uri,
+                });
+                extension_uri_anchor
+            }
+        };
+
+        self.extensions.push(SimpleExtensionDeclaration {
+            mapping_type: Some(MappingType::ExtensionFunction(ExtensionFunction {
+                extension_uri_reference,
+                function_anchor: anchor,
+                name: name.to_string(),
+            })),
+        });
+        self.anchors.insert(name, anchor);
+        anchor
+    }
+}
+
+fn emit(output_mapping: Vec<i32>) -> RelCommon {
+    RelCommon {
+        emit_kind: Some(EmitKind::Emit(Emit { output_mapping })),
+        ..Default::default()
+    }
+}
+
+fn fetch(input: Rel, offset: Option<usize>, limit: Option<usize>) -> rel::RelType {
+    rel::RelType::Fetch(Box::new(FetchRel {
+        input: Some(Box::new(input)),
+        offset: offset.unwrap_or(0) as i64,
+        // `-1` fetches all the rows after the offset
+        count: limit.map(|limit| limit as i64).unwrap_or(-1),
+        ..Default::default()
+    }))
+}
+
+fn field(pos: usize) -> FieldReference {
+    FieldReference {
+        reference_type: Some(ReferenceType::DirectReference(ReferenceSegment {
+            reference_type: Some(reference_segment::ReferenceType::StructField(Box::new(
+                StructField {
+                    field: pos as i32,
+                    child: None,
+                },
+            ))),
+        })),
+        root_type: Some(RootType::RootReference(RootReference {})),
+    }
+}
+
+fn literal(value: &DataValue) -> Result<Literal, DatabaseError> {
+    let literal_type = match value {
+        DataValue::Null => LiteralType::Null(to_type(&value.logical_type())?),
+        DataValue::Boolean(value) => LiteralType::Boolean(*value),
+        DataValue::Int8(value) => LiteralType::I8(*value as i32),
+        DataValue::Int16(value) => LiteralType::I16(*value as i32),
+        DataValue::Int32(value) => LiteralType::I32(*value),
+        DataValue::Int64(value) => LiteralType::I64(*value),
+        DataValue::Float32(value) => LiteralType::Fp32(value.0),
+        DataValue::Float64(value) => LiteralType::Fp64(value.0),
+        DataValue::Utf8 { value, .. } => LiteralType::String(value.clone()),
+        value => {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "the value `{}` can not be exported to substrait",
+                value
+            )))
+        }
+    };
+
+    Ok(Literal {
+        nullable: value.is_null(),
+        type_variation_reference: 0,
+        literal_type: Some(literal_type),
+    })
+}
+
+fn unsupported(expr: &ScalarExpression) -> DatabaseError {
+    DatabaseError::UnsupportedStmt(format!(
+        "the expression `{}` can not be exported to substrait",
+        expr
+    ))
+}
@@ -17,12 +17,12 @@ pub struct BooleanNotEqBinaryEvaluator;
 
 #[typetag::serde]
 impl UnaryEvaluator for BooleanNotUnaryEvaluator {
-    fn unary_eval(&self, value: &DataValue) -> DataValue {
-        match value {
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match value {
             DataValue::Boolean(value) => DataValue::Boolean(!value),
             DataValue::Null => DataValue::Null,
             _ => unsafe { hint::unreachable_unchecked() },
-        }
+        })
     }
 }
 #[typetag::serde]
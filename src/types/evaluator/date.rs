@@ -2,8 +2,81 @@ use crate::numeric_binary_evaluator_definition;
 use crate::types::evaluator::BinaryEvaluator;
 use crate::types::evaluator::DataValue;
 use crate::types::DatabaseError;
+use chrono::NaiveDate;
 use paste::paste;
 use serde::{Deserialize, Serialize};
 use std::hint;
 
 numeric_binary_evaluator_definition!(Date, DataValue::Date32);
+
+/// `date + days`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DatePlusDaysBinaryEvaluator;
+/// `date - days`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateMinusDaysBinaryEvaluator;
+/// `days + date`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DaysPlusDateBinaryEvaluator;
+/// `date - date`, the days between them
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateMinusDateBinaryEvaluator;
+
+fn date_plus_days(date: i32, days: i64) -> Result<DataValue, DatabaseError> {
+    let date = (date as i64)
+        .checked_add(days)
+        .and_then(|date| i32::try_from(date).ok())
+        .ok_or(DatabaseError::OverFlow)?;
+
+    if NaiveDate::from_num_days_from_ce_opt(date).is_none() {
+        return Err(DatabaseError::OverFlow);
+    }
+    Ok(DataValue::Date32(date))
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for DatePlusDaysBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Date32(date), DataValue::Int64(days)) => date_plus_days(*date, *days)?,
+            (DataValue::Date32(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Int64(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for DateMinusDaysBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Date32(date), DataValue::Int64(days)) => {
+                date_plus_days(*date, days.checked_neg().ok_or(DatabaseError::OverFlow)?)?
+            }
+            (DataValue::Date32(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Int64(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for DaysPlusDateBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        DatePlusDaysBinaryEvaluator.binary_eval(right, left)
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for DateMinusDateBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Date32(v1), DataValue::Date32(v2)) => {
+                DataValue::Int32(v1.checked_sub(*v2).ok_or(DatabaseError::OverFlow)?)
+            }
+            (DataValue::Date32(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Date32(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
@@ -7,3 +7,80 @@ use serde::{Deserialize, Serialize};
 use std::hint;
 
 numeric_binary_evaluator_definition!(DateTime, DataValue::Date64);
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// `datetime + days`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateTimePlusDaysBinaryEvaluator;
+/// `datetime - days`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateTimeMinusDaysBinaryEvaluator;
+/// `days + datetime`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DaysPlusDateTimeBinaryEvaluator;
+/// `datetime - datetime`, the seconds between them
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateTimeMinusDateTimeBinaryEvaluator;
+
+fn date_time_plus_days(date_time: i64, days: i64) -> Result<DataValue, DatabaseError> {
+    let date_time = days
+        .checked_mul(SECONDS_PER_DAY)
+        .and_then(|seconds| date_time.checked_add(seconds))
+        .ok_or(DatabaseError::OverFlow)?;
+
+    if chrono::DateTime::from_timestamp(date_time, 0).is_none() {
+        return Err(DatabaseError::OverFlow);
+    }
+    Ok(DataValue::Date64(date_time))
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for DateTimePlusDaysBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Date64(date_time), DataValue::Int64(days)) => {
+                date_time_plus_days(*date_time, *days)?
+            }
+            (DataValue::Date64(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Int64(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for DateTimeMinusDaysBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Date64(date_time), DataValue::Int64(days)) => date_time_plus_days(
+                *date_time,
+                days.checked_neg().ok_or(DatabaseError::OverFlow)?,
+            )?,
+            (DataValue::Date64(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Int64(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for DaysPlusDateTimeBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        DateTimePlusDaysBinaryEvaluator.binary_eval(right, left)
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for DateTimeMinusDateTimeBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Date64(v1), DataValue::Date64(v2)) => {
+                DataValue::Int64(v1.checked_sub(*v2).ok_or(DatabaseError::OverFlow)?)
+            }
+            (DataValue::Date64(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Date64(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
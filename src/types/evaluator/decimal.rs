@@ -1,9 +1,31 @@
 use crate::errors::DatabaseError;
-use crate::types::evaluator::BinaryEvaluator;
 use crate::types::evaluator::DataValue;
+use crate::types::evaluator::{BinaryEvaluator, UnaryEvaluator};
 use serde::{Deserialize, Serialize};
 use std::hint;
 
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DecimalPlusUnaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DecimalMinusUnaryEvaluator;
+
+#[typetag::serde]
+impl UnaryEvaluator for DecimalPlusUnaryEvaluator {
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(value.clone())
+    }
+}
+#[typetag::serde]
+impl UnaryEvaluator for DecimalMinusUnaryEvaluator {
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match value {
+            DataValue::Decimal(value) => DataValue::Decimal(-value),
+            DataValue::Null => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct DecimalPlusBinaryEvaluator;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
@@ -31,7 +53,9 @@ pub struct DecimalModBinaryEvaluator;
 impl BinaryEvaluator for DecimalPlusBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
-            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => DataValue::Decimal(v1 + v2),
+            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => {
+                DataValue::Decimal(v1.checked_add(*v2).ok_or(DatabaseError::OverFlow)?)
+            }
             (DataValue::Decimal(_), DataValue::Null)
             | (DataValue::Null, DataValue::Decimal(_))
             | (DataValue::Null, DataValue::Null) => DataValue::Null,
@@ -43,7 +67,9 @@ impl BinaryEvaluator for DecimalPlusBinaryEvaluator {
 impl BinaryEvaluator for DecimalMinusBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
-            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => DataValue::Decimal(v1 - v2),
+            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => {
+                DataValue::Decimal(v1.checked_sub(*v2).ok_or(DatabaseError::OverFlow)?)
+            }
             (DataValue::Decimal(_), DataValue::Null)
             | (DataValue::Null, DataValue::Decimal(_))
             | (DataValue::Null, DataValue::Null) => DataValue::Null,
@@ -55,7 +81,9 @@ impl BinaryEvaluator for DecimalMinusBinaryEvaluator {
 impl BinaryEvaluator for DecimalMultiplyBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
-            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => DataValue::Decimal(v1 * v2),
+            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => {
+                DataValue::Decimal(v1.checked_mul(*v2).ok_or(DatabaseError::OverFlow)?)
+            }
             (DataValue::Decimal(_), DataValue::Null)
             | (DataValue::Null, DataValue::Decimal(_))
             | (DataValue::Null, DataValue::Null) => DataValue::Null,
@@ -67,7 +95,12 @@ impl BinaryEvaluator for DecimalMultiplyBinaryEvaluator {
 impl BinaryEvaluator for DecimalDivideBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
-            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => DataValue::Decimal(v1 / v2),
+            (DataValue::Decimal(_), DataValue::Decimal(v2)) if v2.is_zero() => {
+                return Err(DatabaseError::DivisionByZero)
+            }
+            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => {
+                DataValue::Decimal(v1.checked_div(*v2).ok_or(DatabaseError::OverFlow)?)
+            }
             (DataValue::Decimal(_), DataValue::Null)
             | (DataValue::Null, DataValue::Decimal(_))
             | (DataValue::Null, DataValue::Null) => DataValue::Null,
@@ -151,7 +184,12 @@ impl BinaryEvaluator for DecimalNotEqBinaryEvaluator {
 impl BinaryEvaluator for DecimalModBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
-            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => DataValue::Decimal(v1 % v2),
+            (DataValue::Decimal(_), DataValue::Decimal(v2)) if v2.is_zero() => {
+                return Err(DatabaseError::DivisionByZero)
+            }
+            (DataValue::Decimal(v1), DataValue::Decimal(v2)) => {
+                DataValue::Decimal(v1.checked_rem(*v2).ok_or(DatabaseError::OverFlow)?)
+            }
             (DataValue::Decimal(_), DataValue::Null)
             | (DataValue::Null, DataValue::Decimal(_))
             | (DataValue::Null, DataValue::Null) => DataValue::Null,
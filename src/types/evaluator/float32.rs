@@ -11,18 +11,18 @@ pub struct Float32MinusUnaryEvaluator;
 
 #[typetag::serde]
 impl UnaryEvaluator for Float32PlusUnaryEvaluator {
-    fn unary_eval(&self, value: &DataValue) -> DataValue {
-        value.clone()
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(value.clone())
     }
 }
 #[typetag::serde]
 impl UnaryEvaluator for Float32MinusUnaryEvaluator {
-    fn unary_eval(&self, value: &DataValue) -> DataValue {
-        match value {
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match value {
             DataValue::Float32(value) => DataValue::Float32(-value),
             DataValue::Null => DataValue::Null,
             _ => unsafe { hint::unreachable_unchecked() },
-        }
+        })
     }
 }
 
@@ -0,0 +1,10 @@
+use crate::types::evaluator::DataValue;
+use crate::types::evaluator::{BinaryEvaluator, UnaryEvaluator};
+use crate::types::DatabaseError;
+use crate::{numeric_binary_evaluator_definition, numeric_unary_evaluator_definition};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+use std::hint;
+
+numeric_unary_evaluator_definition!(Int128, DataValue::Int128);
+numeric_binary_evaluator_definition!(Int128, DataValue::Int128);
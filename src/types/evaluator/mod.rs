@@ -4,19 +4,25 @@ pub mod datetime;
 pub mod decimal;
 pub mod float32;
 pub mod float64;
+pub mod int128;
 pub mod int16;
 pub mod int32;
 pub mod int64;
 pub mod int8;
+pub mod nested;
 pub mod null;
+pub mod numeric;
+pub mod spatial;
 pub mod time32;
 pub mod time64;
 pub mod tuple;
+pub mod uint128;
 pub mod uint16;
 pub mod uint32;
 pub mod uint64;
 pub mod uint8;
 pub mod utf8;
+pub mod vector;
 
 use crate::errors::DatabaseError;
 use crate::expression::{BinaryOperator, UnaryOperator};
@@ -26,17 +32,22 @@ use crate::types::evaluator::datetime::*;
 use crate::types::evaluator::decimal::*;
 use crate::types::evaluator::float32::*;
 use crate::types::evaluator::float64::*;
+use crate::types::evaluator::int128::*;
 use crate::types::evaluator::int16::*;
 use crate::types::evaluator::int32::*;
 use crate::types::evaluator::int64::*;
 use crate::types::evaluator::int8::*;
+use crate::types::evaluator::nested::{NestedEqBinaryEvaluator, NestedNotEqBinaryEvaluator};
 use crate::types::evaluator::null::NullBinaryEvaluator;
+use crate::types::evaluator::numeric::*;
+use crate::types::evaluator::spatial::{SpatialEqBinaryEvaluator, SpatialNotEqBinaryEvaluator};
 use crate::types::evaluator::time32::*;
 use crate::types::evaluator::time64::*;
 use crate::types::evaluator::tuple::{
     TupleEqBinaryEvaluator, TupleGtBinaryEvaluator, TupleGtEqBinaryEvaluator,
     TupleLtBinaryEvaluator, TupleLtEqBinaryEvaluator, TupleNotEqBinaryEvaluator,
 };
+use crate::types::evaluator::uint128::*;
 use crate::types::evaluator::uint16::*;
 use crate::types::evaluator::uint32::*;
 use crate::types::evaluator::uint64::*;
@@ -46,6 +57,7 @@ use crate::types::evaluator::utf8::{
     Utf8EqBinaryEvaluator, Utf8GtBinaryEvaluator, Utf8GtEqBinaryEvaluator, Utf8LtBinaryEvaluator,
     Utf8LtEqBinaryEvaluator, Utf8NotEqBinaryEvaluator, Utf8StringConcatBinaryEvaluator,
 };
+use crate::types::evaluator::vector::{VectorEqBinaryEvaluator, VectorNotEqBinaryEvaluator};
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use paste::paste;
@@ -62,7 +74,7 @@ pub trait BinaryEvaluator: Send + Sync + Debug {
 
 #[typetag::serde(tag = "unary")]
 pub trait UnaryEvaluator: Send + Sync + Debug {
-    fn unary_eval(&self, value: &DataValue) -> DataValue;
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError>;
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -90,7 +102,7 @@ impl BinaryEvaluatorBox {
 pub struct UnaryEvaluatorBox(pub Arc<dyn UnaryEvaluator>);
 
 impl UnaryEvaluatorBox {
-    pub fn unary_eval(&self, value: &DataValue) -> DataValue {
+    pub fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
         self.0.unary_eval(value)
     }
 }
@@ -180,12 +192,15 @@ impl EvaluatorFactory {
             LogicalType::Smallint => numeric_unary_evaluator!(Int16, op, LogicalType::Smallint),
             LogicalType::Integer => numeric_unary_evaluator!(Int32, op, LogicalType::Integer),
             LogicalType::Bigint => numeric_unary_evaluator!(Int64, op, LogicalType::Bigint),
+            LogicalType::Hugeint => numeric_unary_evaluator!(Int128, op, LogicalType::Hugeint),
             LogicalType::Boolean => match op {
                 UnaryOperator::Not => Ok(UnaryEvaluatorBox(Arc::new(BooleanNotUnaryEvaluator))),
                 _ => Err(DatabaseError::UnsupportedUnaryOperator(ty, op)),
             },
             LogicalType::Float => numeric_unary_evaluator!(Float32, op, LogicalType::Float),
             LogicalType::Double => numeric_unary_evaluator!(Float64, op, LogicalType::Double),
+            LogicalType::Decimal(_, _) => numeric_unary_evaluator!(Decimal, op, ty),
+            LogicalType::Numeric => numeric_unary_evaluator!(Numeric, op, LogicalType::Numeric),
             _ => Err(DatabaseError::UnsupportedUnaryOperator(ty, op)),
         }
     }
@@ -202,8 +217,23 @@ impl EvaluatorFactory {
             LogicalType::USmallint => numeric_binary_evaluator!(UInt16, op, LogicalType::USmallint),
             LogicalType::UInteger => numeric_binary_evaluator!(UInt32, op, LogicalType::UInteger),
             LogicalType::UBigint => numeric_binary_evaluator!(UInt64, op, LogicalType::UBigint),
+            LogicalType::Hugeint => numeric_binary_evaluator!(Int128, op, LogicalType::Hugeint),
+            LogicalType::UHugeint => numeric_binary_evaluator!(UInt128, op, LogicalType::UHugeint),
             LogicalType::Float => numeric_binary_evaluator!(Float32, op, LogicalType::Float),
             LogicalType::Double => numeric_binary_evaluator!(Float64, op, LogicalType::Double),
+            // see `EvaluatorFactory::temporal_binary_create` for their arithmetic
+            LogicalType::Date | LogicalType::DateTime
+                if matches!(
+                    op,
+                    BinaryOperator::Plus
+                        | BinaryOperator::Minus
+                        | BinaryOperator::Multiply
+                        | BinaryOperator::Divide
+                        | BinaryOperator::Modulo
+                ) =>
+            {
+                Err(DatabaseError::UnsupportedBinaryOperator(ty, op))
+            }
             LogicalType::Date => numeric_binary_evaluator!(Date, op, LogicalType::Date),
             LogicalType::DateTime => numeric_binary_evaluator!(DateTime, op, LogicalType::DateTime),
             LogicalType::Time(_) => match op {
@@ -229,6 +259,7 @@ impl EvaluatorFactory {
                 _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
             },
             LogicalType::Decimal(_, _) => numeric_binary_evaluator!(Decimal, op, ty),
+            LogicalType::Numeric => numeric_binary_evaluator!(Numeric, op, ty),
             LogicalType::Boolean => match op {
                 BinaryOperator::And => Ok(BinaryEvaluatorBox(Arc::new(BooleanAndBinaryEvaluator))),
                 BinaryOperator::Or => Ok(BinaryEvaluatorBox(Arc::new(BooleanOrBinaryEvaluator))),
@@ -275,10 +306,151 @@ impl EvaluatorFactory {
                 BinaryOperator::LtEq => Ok(BinaryEvaluatorBox(Arc::new(TupleLtEqBinaryEvaluator))),
                 _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
             },
+            LogicalType::Vector(_) => match op {
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(VectorEqBinaryEvaluator))),
+                BinaryOperator::NotEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(VectorNotEqBinaryEvaluator)))
+                }
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+            LogicalType::Point | LogicalType::Box => match op {
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(SpatialEqBinaryEvaluator))),
+                BinaryOperator::NotEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(SpatialNotEqBinaryEvaluator)))
+                }
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+            LogicalType::Array(_) | LogicalType::Struct(_) => match op {
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(NestedEqBinaryEvaluator))),
+                BinaryOperator::NotEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(NestedNotEqBinaryEvaluator)))
+                }
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+        }
+    }
+
+    /// The arithmetic of dates and datetimes, whose operands are not of the same type:
+    /// - `date ± n` and `n + date` add days to the date
+    /// - `datetime ± n` and `n + datetime` add days to the datetime
+    /// - `date - date` is the days between them, as an `INTEGER`
+    /// - `datetime - datetime` is the seconds between them, as a `BIGINT`, a date is then at midnight
+    ///
+    /// A string operand is read as the date or datetime of the other. Returns `None` if neither
+    /// operand is a date or datetime or `op` is a comparison, an error for the other operators.
+    pub fn temporal_binary_create(
+        left: &LogicalType,
+        right: &LogicalType,
+        op: BinaryOperator,
+    ) -> Result<Option<TemporalBinary>, DatabaseError> {
+        let is_temporal =
+            |ty: &LogicalType| matches!(ty, LogicalType::Date | LogicalType::DateTime);
+        let is_days = |ty: &LogicalType| {
+            ty.is_signed_numeric() || ty.is_unsigned_numeric() || ty == &LogicalType::SqlNull
+        };
+        let is_text =
+            |ty: &LogicalType| matches!(ty, LogicalType::Varchar(..) | LogicalType::Char(..));
+
+        if !is_temporal(left) && !is_temporal(right) {
+            return Ok(None);
+        }
+        let (left, right) = match (left, right) {
+            (left, right) if is_text(left) => (right, right),
+            (left, right) if is_text(right) => (left, left),
+            (left, right) => (left, right),
+        };
+        let temporal_binary = |left_ty, right_ty, ty, evaluator: Arc<dyn BinaryEvaluator>| {
+            Ok(Some(TemporalBinary {
+                left_ty,
+                right_ty,
+                ty,
+                evaluator: BinaryEvaluatorBox(evaluator),
+            }))
+        };
+
+        match (op, left, right) {
+            (BinaryOperator::Plus, LogicalType::Date, days) if is_days(days) => temporal_binary(
+                LogicalType::Date,
+                LogicalType::Bigint,
+                LogicalType::Date,
+                Arc::new(DatePlusDaysBinaryEvaluator),
+            ),
+            (BinaryOperator::Minus, LogicalType::Date, days) if is_days(days) => temporal_binary(
+                LogicalType::Date,
+                LogicalType::Bigint,
+                LogicalType::Date,
+                Arc::new(DateMinusDaysBinaryEvaluator),
+            ),
+            (BinaryOperator::Plus, days, LogicalType::Date) if is_days(days) => temporal_binary(
+                LogicalType::Bigint,
+                LogicalType::Date,
+                LogicalType::Date,
+                Arc::new(DaysPlusDateBinaryEvaluator),
+            ),
+            (BinaryOperator::Plus, LogicalType::DateTime, days) if is_days(days) => {
+                temporal_binary(
+                    LogicalType::DateTime,
+                    LogicalType::Bigint,
+                    LogicalType::DateTime,
+                    Arc::new(DateTimePlusDaysBinaryEvaluator),
+                )
+            }
+            (BinaryOperator::Minus, LogicalType::DateTime, days) if is_days(days) => {
+                temporal_binary(
+                    LogicalType::DateTime,
+                    LogicalType::Bigint,
+                    LogicalType::DateTime,
+                    Arc::new(DateTimeMinusDaysBinaryEvaluator),
+                )
+            }
+            (BinaryOperator::Plus, days, LogicalType::DateTime) if is_days(days) => {
+                temporal_binary(
+                    LogicalType::Bigint,
+                    LogicalType::DateTime,
+                    LogicalType::DateTime,
+                    Arc::new(DaysPlusDateTimeBinaryEvaluator),
+                )
+            }
+            (BinaryOperator::Minus, LogicalType::Date, LogicalType::Date) => temporal_binary(
+                LogicalType::Date,
+                LogicalType::Date,
+                LogicalType::Integer,
+                Arc::new(DateMinusDateBinaryEvaluator),
+            ),
+            (BinaryOperator::Minus, left, right) if is_temporal(left) && is_temporal(right) => {
+                temporal_binary(
+                    LogicalType::DateTime,
+                    LogicalType::DateTime,
+                    LogicalType::Bigint,
+                    Arc::new(DateTimeMinusDateTimeBinaryEvaluator),
+                )
+            }
+            (
+                BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo,
+                left,
+                right,
+            ) => Err(DatabaseError::UnsupportedBinaryOperator(
+                if is_temporal(left) { left } else { right }.clone(),
+                op,
+            )),
+            _ => Ok(None),
         }
     }
 }
 
+/// The types the operands are cast to, the type of the result and the evaluator of an arithmetic
+/// of dates, see [`EvaluatorFactory::temporal_binary_create`].
+pub struct TemporalBinary {
+    pub left_ty: LogicalType,
+    pub right_ty: LogicalType,
+    pub ty: LogicalType,
+    pub evaluator: BinaryEvaluatorBox,
+}
+
 #[macro_export]
 macro_rules! numeric_unary_evaluator_definition {
     ($value_type:ident, $compute_type:path) => {
@@ -290,18 +462,18 @@ macro_rules! numeric_unary_evaluator_definition {
 
             #[typetag::serde]
             impl UnaryEvaluator for [<$value_type PlusUnaryEvaluator>] {
-                fn unary_eval(&self, value: &DataValue) -> DataValue {
-                    value.clone()
+                fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+                    Ok(value.clone())
                 }
             }
             #[typetag::serde]
             impl UnaryEvaluator for [<$value_type MinusUnaryEvaluator>] {
-                fn unary_eval(&self, value: &DataValue) -> DataValue {
-                    match value {
-                        $compute_type(value) => $compute_type(-value),
+                fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+                    Ok(match value {
+                        $compute_type(value) => $compute_type(value.checked_neg().ok_or(DatabaseError::OverFlow)?),
                         DataValue::Null => DataValue::Null,
                         _ => unsafe { hint::unreachable_unchecked() },
-                    }
+                    })
                 }
             }
         }
@@ -439,7 +611,9 @@ macro_rules! numeric_binary_evaluator_definition {
             impl BinaryEvaluator for [<$value_type ModBinaryEvaluator>] {
                 fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
                     Ok(match (left, right) {
-                        ($compute_type(v1), $compute_type(v2)) => $compute_type(*v1 % *v2),
+                        ($compute_type(_), $compute_type(0)) => return Err(DatabaseError::DivisionByZero),
+                        // `MIN % -1` overflows, though its remainder is 0
+                        ($compute_type(v1), $compute_type(v2)) => $compute_type(v1.wrapping_rem(*v2)),
                         ($compute_type(_), DataValue::Null) | (DataValue::Null, $compute_type(_)) | (DataValue::Null, DataValue::Null) => DataValue::Null,
                         _ => unsafe { hint::unreachable_unchecked() },
                     })
@@ -452,7 +626,7 @@ macro_rules! numeric_binary_evaluator_definition {
 #[cfg(test)]
 mod test {
     use crate::errors::DatabaseError;
-    use crate::expression::BinaryOperator;
+    use crate::expression::{BinaryOperator, UnaryOperator};
     use crate::serdes::{ReferenceSerialization, ReferenceTables};
     use crate::storage::rocksdb::RocksTransaction;
     use crate::types::evaluator::boolean::{BooleanNotEqBinaryEvaluator, BooleanNotUnaryEvaluator};
@@ -460,6 +634,7 @@ mod test {
     use crate::types::value::{DataValue, Utf8Type};
     use crate::types::LogicalType;
     use ordered_float::OrderedFloat;
+    use proptest::prelude::*;
     use sqlparser::ast::CharLengthUnits;
     use std::io::{Cursor, Seek, SeekFrom};
     use std::sync::Arc;
@@ -1228,6 +1403,140 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_binary_op_date_arithmetic() -> Result<(), DatabaseError> {
+        let date = |value: &str| {
+            DataValue::Utf8 {
+                value: value.to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }
+            .cast(&LogicalType::Date)
+        };
+        let date_time = |value: &str| {
+            DataValue::Utf8 {
+                value: value.to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }
+            .cast(&LogicalType::DateTime)
+        };
+        let eval = |left: DataValue, op: BinaryOperator, right: DataValue| {
+            let temporal = EvaluatorFactory::temporal_binary_create(
+                &left.logical_type(),
+                &right.logical_type(),
+                op,
+            )?
+            .unwrap();
+            let value = temporal.evaluator.binary_eval(
+                &left.cast(&temporal.left_ty)?,
+                &right.cast(&temporal.right_ty)?,
+            )?;
+            assert!(value.is_null() || value.logical_type() == temporal.ty);
+
+            Ok::<_, DatabaseError>(value)
+        };
+
+        assert_eq!(
+            eval(
+                date("2024-02-28")?,
+                BinaryOperator::Plus,
+                DataValue::Int32(2)
+            )?,
+            date("2024-03-01")?
+        );
+        assert_eq!(
+            eval(
+                DataValue::Int32(2),
+                BinaryOperator::Plus,
+                date("2024-02-28")?
+            )?,
+            date("2024-03-01")?
+        );
+        assert_eq!(
+            eval(
+                date("2024-03-01")?,
+                BinaryOperator::Minus,
+                DataValue::Int64(1)
+            )?,
+            date("2024-02-29")?
+        );
+        assert_eq!(
+            eval(
+                date("2024-03-01")?,
+                BinaryOperator::Minus,
+                date("2023-03-01")?
+            )?,
+            DataValue::Int32(366)
+        );
+        assert_eq!(
+            eval(
+                date_time("2024-03-01 12:00:00")?,
+                BinaryOperator::Plus,
+                DataValue::Int32(1)
+            )?,
+            date_time("2024-03-02 12:00:00")?
+        );
+        assert_eq!(
+            eval(
+                date_time("2024-03-01 12:00:00")?,
+                BinaryOperator::Minus,
+                date("2024-03-01")?
+            )?,
+            DataValue::Int64(43200)
+        );
+        assert_eq!(
+            eval(
+                date_time("2024-03-01 12:00:00")?,
+                BinaryOperator::Minus,
+                DataValue::Utf8 {
+                    value: "2024-03-01 11:59:00".to_string(),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                }
+            )?,
+            DataValue::Int64(60)
+        );
+        assert_eq!(
+            eval(date("2024-03-01")?, BinaryOperator::Plus, DataValue::Null)?,
+            DataValue::Null
+        );
+        assert!(eval(
+            date("2024-03-01")?,
+            BinaryOperator::Plus,
+            DataValue::Int64(i64::MAX)
+        )
+        .is_err());
+
+        assert!(EvaluatorFactory::temporal_binary_create(
+            &LogicalType::Date,
+            &LogicalType::Date,
+            BinaryOperator::Plus
+        )
+        .is_err());
+        assert!(EvaluatorFactory::temporal_binary_create(
+            &LogicalType::DateTime,
+            &LogicalType::Integer,
+            BinaryOperator::Multiply
+        )
+        .is_err());
+        assert!(EvaluatorFactory::temporal_binary_create(
+            &LogicalType::Date,
+            &LogicalType::Double,
+            BinaryOperator::Plus
+        )
+        .is_err());
+        assert!(EvaluatorFactory::temporal_binary_create(
+            &LogicalType::Date,
+            &LogicalType::Date,
+            BinaryOperator::Gt
+        )?
+        .is_none());
+        assert!(EvaluatorFactory::binary_create(LogicalType::Date, BinaryOperator::Plus).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_reference_serialization() -> Result<(), DatabaseError> {
         let mut cursor = Cursor::new(Vec::new());
@@ -1256,4 +1565,226 @@ mod test {
 
         Ok(())
     }
+
+    const INTEGER_TYPES: [LogicalType; 8] = [
+        LogicalType::Tinyint,
+        LogicalType::Smallint,
+        LogicalType::Integer,
+        LogicalType::Bigint,
+        LogicalType::UTinyint,
+        LogicalType::USmallint,
+        LogicalType::UInteger,
+        LogicalType::UBigint,
+    ];
+
+    const ARITHMETIC_OPERATORS: [BinaryOperator; 5] = [
+        BinaryOperator::Plus,
+        BinaryOperator::Minus,
+        BinaryOperator::Multiply,
+        BinaryOperator::Divide,
+        BinaryOperator::Modulo,
+    ];
+
+    const COMPARISON_OPERATORS: [BinaryOperator; 6] = [
+        BinaryOperator::Gt,
+        BinaryOperator::GtEq,
+        BinaryOperator::Lt,
+        BinaryOperator::LtEq,
+        BinaryOperator::Eq,
+        BinaryOperator::NotEq,
+    ];
+
+    /// Pairs of integers of a type, biased to the bounds where the overflows are.
+    macro_rules! integer_pair {
+        ($ty:expr, $value:path, $int:ty) => {{
+            let integer = || {
+                prop_oneof![
+                    Just(<$int>::MIN),
+                    Just(<$int>::MAX),
+                    Just(0 as $int),
+                    Just((0 as $int).wrapping_sub(1)),
+                    any::<$int>(),
+                ]
+            };
+            (integer(), integer()).prop_map(|(a, b)| ($ty, $value(a), $value(b)))
+        }};
+    }
+
+    fn integer_pair() -> impl Strategy<Value = (LogicalType, DataValue, DataValue)> {
+        prop_oneof![
+            integer_pair!(LogicalType::Tinyint, DataValue::Int8, i8),
+            integer_pair!(LogicalType::Smallint, DataValue::Int16, i16),
+            integer_pair!(LogicalType::Integer, DataValue::Int32, i32),
+            integer_pair!(LogicalType::Bigint, DataValue::Int64, i64),
+            integer_pair!(LogicalType::UTinyint, DataValue::UInt8, u8),
+            integer_pair!(LogicalType::USmallint, DataValue::UInt16, u16),
+            integer_pair!(LogicalType::UInteger, DataValue::UInt32, u32),
+            integer_pair!(LogicalType::UBigint, DataValue::UInt64, u64),
+        ]
+    }
+
+    fn value_pair() -> impl Strategy<Value = (LogicalType, DataValue, DataValue)> {
+        let utf8 = |value: String| DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        prop_oneof![
+            4 => integer_pair(),
+            1 => (any::<bool>(), any::<bool>()).prop_map(|(a, b)| {
+                (LogicalType::Boolean, DataValue::Boolean(a), DataValue::Boolean(b))
+            }),
+            1 => (any::<f64>(), any::<f64>()).prop_map(|(a, b)| {
+                (
+                    LogicalType::Double,
+                    DataValue::Float64(OrderedFloat(a)),
+                    DataValue::Float64(OrderedFloat(b)),
+                )
+            }),
+            1 => ("[a-c]{0,3}", "[a-c]{0,3}").prop_map(move |(a, b)| {
+                (
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    utf8(a),
+                    utf8(b),
+                )
+            }),
+            1 => (-100_000..100_000, -100_000..100_000).prop_map(|(a, b)| {
+                (LogicalType::Date, DataValue::Date32(a), DataValue::Date32(b))
+            }),
+        ]
+    }
+
+    fn integer(value: &DataValue) -> i128 {
+        match value {
+            DataValue::Int8(value) => *value as i128,
+            DataValue::Int16(value) => *value as i128,
+            DataValue::Int32(value) => *value as i128,
+            DataValue::Int64(value) => *value as i128,
+            DataValue::UInt8(value) => *value as i128,
+            DataValue::UInt16(value) => *value as i128,
+            DataValue::UInt32(value) => *value as i128,
+            DataValue::UInt64(value) => *value as i128,
+            _ => unreachable!("{value:?} is not an integer"),
+        }
+    }
+
+    fn contains(ty: &LogicalType, value: i128) -> bool {
+        let (min, max) = match ty {
+            LogicalType::Tinyint => (i8::MIN as i128, i8::MAX as i128),
+            LogicalType::Smallint => (i16::MIN as i128, i16::MAX as i128),
+            LogicalType::Integer => (i32::MIN as i128, i32::MAX as i128),
+            LogicalType::Bigint => (i64::MIN as i128, i64::MAX as i128),
+            LogicalType::UTinyint => (0, u8::MAX as i128),
+            LogicalType::USmallint => (0, u16::MAX as i128),
+            LogicalType::UInteger => (0, u32::MAX as i128),
+            LogicalType::UBigint => (0, u64::MAX as i128),
+            _ => unreachable!("{ty} is not an integer"),
+        };
+        (min..=max).contains(&value)
+    }
+
+    proptest! {
+        #[test]
+        fn test_comparison_symmetry((ty, a, b) in value_pair()) {
+            let eval = |op: BinaryOperator, left: &DataValue, right: &DataValue| {
+                EvaluatorFactory::binary_create(ty.clone(), op)?.binary_eval(left, right)
+            };
+
+            prop_assert_eq!(eval(BinaryOperator::Eq, &a, &b)?, eval(BinaryOperator::Eq, &b, &a)?);
+            prop_assert_eq!(eval(BinaryOperator::Eq, &a, &a)?, DataValue::Boolean(true));
+            prop_assert_eq!(
+                eval(BinaryOperator::NotEq, &a, &b)?,
+                DataValue::Boolean(eval(BinaryOperator::Eq, &a, &b)? == DataValue::Boolean(false))
+            );
+            if ty != LogicalType::Boolean {
+                prop_assert_eq!(eval(BinaryOperator::Lt, &a, &b)?, eval(BinaryOperator::Gt, &b, &a)?);
+                prop_assert_eq!(
+                    eval(BinaryOperator::LtEq, &a, &b)?,
+                    eval(BinaryOperator::GtEq, &b, &a)?
+                );
+            }
+        }
+
+        #[test]
+        fn test_null_propagation((ty, value, _) in value_pair()) {
+            let null = DataValue::Null;
+            let operators = if ty == LogicalType::Boolean {
+                // `AND` and `OR` are three-valued: `false AND NULL` is false
+                vec![BinaryOperator::Eq, BinaryOperator::NotEq]
+            } else {
+                ARITHMETIC_OPERATORS
+                    .into_iter()
+                    .chain(COMPARISON_OPERATORS)
+                    .collect()
+            };
+
+            for op in operators {
+                let Ok(evaluator) = EvaluatorFactory::binary_create(ty.clone(), op) else {
+                    continue;
+                };
+                prop_assert_eq!(evaluator.binary_eval(&value, &null)?, DataValue::Null, "{}", op);
+                prop_assert_eq!(evaluator.binary_eval(&null, &value)?, DataValue::Null, "{}", op);
+                prop_assert_eq!(evaluator.binary_eval(&null, &null)?, DataValue::Null, "{}", op);
+            }
+            for op in [UnaryOperator::Plus, UnaryOperator::Minus, UnaryOperator::Not] {
+                if let Ok(evaluator) = EvaluatorFactory::unary_create(ty.clone(), op) {
+                    prop_assert_eq!(evaluator.unary_eval(&null)?, DataValue::Null, "{}", op);
+                }
+            }
+        }
+
+        #[test]
+        fn test_integer_arithmetic((ty, a, b) in integer_pair()) {
+            let (x, y) = (integer(&a), integer(&b));
+
+            for (op, expected) in [
+                (BinaryOperator::Plus, x.checked_add(y)),
+                (BinaryOperator::Minus, x.checked_sub(y)),
+                (BinaryOperator::Multiply, x.checked_mul(y)),
+            ] {
+                match EvaluatorFactory::binary_create(ty.clone(), op)?.binary_eval(&a, &b) {
+                    Ok(value) => prop_assert_eq!(Some(integer(&value)), expected, "{}", op),
+                    Err(DatabaseError::OverFlow) => {
+                        prop_assert!(!expected.is_some_and(|expected| contains(&ty, expected)), "{}", op)
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            let modulo = EvaluatorFactory::binary_create(ty.clone(), BinaryOperator::Modulo)?
+                .binary_eval(&a, &b);
+            if y == 0 {
+                prop_assert!(matches!(modulo, Err(DatabaseError::DivisionByZero)));
+            } else {
+                prop_assert_eq!(integer(&modulo?), x % y);
+            }
+            if let Ok(evaluator) = EvaluatorFactory::unary_create(ty.clone(), UnaryOperator::Minus) {
+                match evaluator.unary_eval(&a) {
+                    Ok(value) => prop_assert_eq!(integer(&value), -x),
+                    Err(DatabaseError::OverFlow) => prop_assert!(!contains(&ty, -x)),
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+
+        #[test]
+        fn test_cast_round_trip(
+            (ty, value, _) in integer_pair(),
+            to in prop::sample::select(INTEGER_TYPES.to_vec()),
+        ) {
+            let x = integer(&value);
+
+            match value.clone().cast(&to) {
+                Ok(cast) => {
+                    prop_assert!(contains(&to, x));
+                    prop_assert_eq!(integer(&cast), x);
+                    prop_assert_eq!(cast.cast(&ty)?, value.clone());
+                }
+                Err(_) => prop_assert!(!contains(&to, x)),
+            }
+            let varchar = LogicalType::Varchar(None, CharLengthUnits::Characters);
+            prop_assert_eq!(value.clone().cast(&varchar)?.cast(&ty)?, value);
+            prop_assert_eq!(DataValue::Null.cast(&to)?, DataValue::Null);
+        }
+    }
 }
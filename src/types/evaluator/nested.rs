@@ -0,0 +1,28 @@
+use crate::errors::DatabaseError;
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NestedEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NestedNotEqBinaryEvaluator;
+
+#[typetag::serde]
+impl BinaryEvaluator for NestedEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Null, _) | (_, DataValue::Null) => DataValue::Null,
+            (v1, v2) => DataValue::Boolean(v1 == v2),
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NestedNotEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Null, _) | (_, DataValue::Null) => DataValue::Null,
+            (v1, v2) => DataValue::Boolean(v1 != v2),
+        })
+    }
+}
@@ -0,0 +1,210 @@
+use crate::errors::DatabaseError;
+use crate::types::evaluator::DataValue;
+use crate::types::evaluator::{BinaryEvaluator, UnaryEvaluator};
+use bigdecimal::{BigDecimal, RoundingMode};
+use serde::{Deserialize, Serialize};
+use std::hint;
+
+/// The fewest fractional digits a division of numerics is rounded to.
+const DIVIDE_SCALE: i64 = 16;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericPlusUnaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericMinusUnaryEvaluator;
+
+#[typetag::serde]
+impl UnaryEvaluator for NumericPlusUnaryEvaluator {
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(value.clone())
+    }
+}
+#[typetag::serde]
+impl UnaryEvaluator for NumericMinusUnaryEvaluator {
+    fn unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match value {
+            DataValue::Numeric(value) => DataValue::Numeric(-value),
+            DataValue::Null => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericPlusBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericMinusBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericMultiplyBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericDivideBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericGtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericGtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericLtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericLtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericNotEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct NumericModBinaryEvaluator;
+
+fn scale(numeric: &BigDecimal) -> i64 {
+    numeric.as_bigint_and_exponent().1
+}
+
+/// Rounded half up to the larger of the scales of the operands and `DIVIDE_SCALE`.
+fn divide(v1: &BigDecimal, v2: &BigDecimal) -> Result<BigDecimal, DatabaseError> {
+    if v2 == &BigDecimal::default() {
+        return Err(DatabaseError::DivisionByZero);
+    }
+    let scale = scale(v1).max(scale(v2)).max(DIVIDE_SCALE);
+
+    Ok((v1 / v2).with_scale_round(scale, RoundingMode::HalfUp))
+}
+
+fn remainder(v1: &BigDecimal, v2: &BigDecimal) -> Result<BigDecimal, DatabaseError> {
+    if v2 == &BigDecimal::default() {
+        return Err(DatabaseError::DivisionByZero);
+    }
+    Ok(v1 % v2)
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for NumericPlusBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Numeric(v1 + v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericMinusBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Numeric(v1 - v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericMultiplyBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Numeric(v1 * v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericDivideBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Numeric(divide(v1, v2)?),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericGtBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Boolean(v1 > v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericGtEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Boolean(v1 >= v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericLtBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Boolean(v1 < v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericLtEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Boolean(v1 <= v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Boolean(v1 == v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericNotEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => DataValue::Boolean(v1 != v2),
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for NumericModBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Numeric(v1), DataValue::Numeric(v2)) => {
+                DataValue::Numeric(remainder(v1, v2)?)
+            }
+            (DataValue::Numeric(_), DataValue::Null)
+            | (DataValue::Null, DataValue::Numeric(_))
+            | (DataValue::Null, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
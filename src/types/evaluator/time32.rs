@@ -30,12 +30,12 @@ impl BinaryEvaluator for TimePlusBinaryEvaluator {
                 let (mut v1, n1) = DataValue::unpack(*v1, *p1);
                 let (v2, n2) = DataValue::unpack(*v2, *p2);
                 let mut n = n1 + n2;
-                while n > ONE_SEC_TO_NANO {
+                while n >= ONE_SEC_TO_NANO {
                     v1 += 1;
                     n -= ONE_SEC_TO_NANO;
                 }
                 let p = if p2 > p1 { *p2 } else { *p1 };
-                if v1 + v2 > ONE_DAY_TO_SEC {
+                if v1 + v2 >= ONE_DAY_TO_SEC {
                     return Ok(DataValue::Null);
                 }
                 DataValue::Time32(DataValue::pack(v1 + v2, n, p), p)
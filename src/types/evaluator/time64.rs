@@ -2,6 +2,7 @@ use crate::errors::DatabaseError;
 use crate::types::evaluator::BinaryEvaluator;
 use crate::types::evaluator::DataValue;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::hint;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
@@ -17,22 +18,24 @@ pub struct Time64EqBinaryEvaluator;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct Time64NotEqBinaryEvaluator;
 
+/// Compares the timestamps at the greater of their precisions.
+fn compare(v1: i64, p1: u64, v2: i64, p2: u64) -> Option<Ordering> {
+    let p = p1.max(p2);
+
+    Some(
+        DataValue::timestamp_with_precision(v1, p1, p)?
+            .cmp(&DataValue::timestamp_with_precision(v2, p2, p)?),
+    )
+}
+
 #[typetag::serde]
 impl BinaryEvaluator for Time64GtBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
             (DataValue::Time64(v1, p1, _), DataValue::Time64(v2, p2, _)) => {
-                if let (Some(v1), Some(v2)) = (
-                    DataValue::from_timestamp_precision(*v1, *p1),
-                    DataValue::from_timestamp_precision(*v2, *p2),
-                ) {
-                    let p = if p2 > p1 { *p2 } else { *p1 };
-                    DataValue::Boolean(
-                        DataValue::timestamp_precision(v1, p)
-                            > DataValue::timestamp_precision(v2, p),
-                    )
-                } else {
-                    DataValue::Null
+                match compare(*v1, *p1, *v2, *p2) {
+                    Some(ordering) => DataValue::Boolean(ordering > Ordering::Equal),
+                    None => DataValue::Null,
                 }
             }
             (DataValue::Time64(..), DataValue::Null)
@@ -47,17 +50,9 @@ impl BinaryEvaluator for Time64GtEqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
             (DataValue::Time64(v1, p1, _), DataValue::Time64(v2, p2, _)) => {
-                if let (Some(v1), Some(v2)) = (
-                    DataValue::from_timestamp_precision(*v1, *p1),
-                    DataValue::from_timestamp_precision(*v2, *p2),
-                ) {
-                    let p = if p2 > p1 { *p2 } else { *p1 };
-                    DataValue::Boolean(
-                        DataValue::timestamp_precision(v1, p)
-                            >= DataValue::timestamp_precision(v2, p),
-                    )
-                } else {
-                    DataValue::Null
+                match compare(*v1, *p1, *v2, *p2) {
+                    Some(ordering) => DataValue::Boolean(ordering >= Ordering::Equal),
+                    None => DataValue::Null,
                 }
             }
             (DataValue::Time64(..), DataValue::Null)
@@ -72,17 +67,9 @@ impl BinaryEvaluator for Time64LtBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
             (DataValue::Time64(v1, p1, _), DataValue::Time64(v2, p2, _)) => {
-                if let (Some(v1), Some(v2)) = (
-                    DataValue::from_timestamp_precision(*v1, *p1),
-                    DataValue::from_timestamp_precision(*v2, *p2),
-                ) {
-                    let p = if p2 > p1 { *p2 } else { *p1 };
-                    DataValue::Boolean(
-                        DataValue::timestamp_precision(v1, p)
-                            < DataValue::timestamp_precision(v2, p),
-                    )
-                } else {
-                    DataValue::Null
+                match compare(*v1, *p1, *v2, *p2) {
+                    Some(ordering) => DataValue::Boolean(ordering < Ordering::Equal),
+                    None => DataValue::Null,
                 }
             }
             (DataValue::Time64(..), DataValue::Null)
@@ -97,17 +84,9 @@ impl BinaryEvaluator for Time64LtEqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
             (DataValue::Time64(v1, p1, _), DataValue::Time64(v2, p2, _)) => {
-                if let (Some(v1), Some(v2)) = (
-                    DataValue::from_timestamp_precision(*v1, *p1),
-                    DataValue::from_timestamp_precision(*v2, *p2),
-                ) {
-                    let p = if p2 > p1 { *p2 } else { *p1 };
-                    DataValue::Boolean(
-                        DataValue::timestamp_precision(v1, p)
-                            <= DataValue::timestamp_precision(v2, p),
-                    )
-                } else {
-                    DataValue::Null
+                match compare(*v1, *p1, *v2, *p2) {
+                    Some(ordering) => DataValue::Boolean(ordering <= Ordering::Equal),
+                    None => DataValue::Null,
                 }
             }
             (DataValue::Time64(..), DataValue::Null)
@@ -122,17 +101,9 @@ impl BinaryEvaluator for Time64EqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
             (DataValue::Time64(v1, p1, _), DataValue::Time64(v2, p2, _)) => {
-                if let (Some(v1), Some(v2)) = (
-                    DataValue::from_timestamp_precision(*v1, *p1),
-                    DataValue::from_timestamp_precision(*v2, *p2),
-                ) {
-                    let p = if p2 > p1 { *p2 } else { *p1 };
-                    DataValue::Boolean(
-                        DataValue::timestamp_precision(v1, p)
-                            == DataValue::timestamp_precision(v2, p),
-                    )
-                } else {
-                    DataValue::Null
+                match compare(*v1, *p1, *v2, *p2) {
+                    Some(ordering) => DataValue::Boolean(ordering == Ordering::Equal),
+                    None => DataValue::Null,
                 }
             }
             (DataValue::Time64(..), DataValue::Null)
@@ -147,17 +118,9 @@ impl BinaryEvaluator for Time64NotEqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
         Ok(match (left, right) {
             (DataValue::Time64(v1, p1, _), DataValue::Time64(v2, p2, _)) => {
-                if let (Some(v1), Some(v2)) = (
-                    DataValue::from_timestamp_precision(*v1, *p1),
-                    DataValue::from_timestamp_precision(*v2, *p2),
-                ) {
-                    let p = if p2 > p1 { *p2 } else { *p1 };
-                    DataValue::Boolean(
-                        DataValue::timestamp_precision(v1, p)
-                            != DataValue::timestamp_precision(v2, p),
-                    )
-                } else {
-                    DataValue::Null
+                match compare(*v1, *p1, *v2, *p2) {
+                    Some(ordering) => DataValue::Boolean(ordering != Ordering::Equal),
+                    None => DataValue::Null,
                 }
             }
             (DataValue::Time64(..), DataValue::Null)
@@ -162,20 +162,22 @@ impl BinaryEvaluator for Utf8NotLikeBinaryEvaluator {
 }
 
 fn string_like(value: &str, pattern: &str, escape_char: Option<char>) -> bool {
-    let mut regex_pattern = String::new();
+    let mut regex_pattern = String::from("(?s)^");
     let mut chars = pattern.chars().peekable();
+    let mut buf = [0u8; 4];
     while let Some(c) = chars.next() {
         if matches!(escape_char.map(|escape_c| escape_c == c), Some(true)) {
             if let Some(next_char) = chars.next() {
-                regex_pattern.push(next_char);
+                regex_pattern.push_str(&regex::escape(next_char.encode_utf8(&mut buf)));
             }
         } else if c == '%' {
             regex_pattern.push_str(".*");
         } else if c == '_' {
             regex_pattern.push('.');
         } else {
-            regex_pattern.push(c);
+            regex_pattern.push_str(&regex::escape(c.encode_utf8(&mut buf)));
         }
     }
+    regex_pattern.push('$');
     Regex::new(&regex_pattern).unwrap().is_match(value)
 }
@@ -0,0 +1,31 @@
+use crate::errors::DatabaseError;
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use serde::{Deserialize, Serialize};
+use std::hint;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct VectorEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct VectorNotEqBinaryEvaluator;
+
+#[typetag::serde]
+impl BinaryEvaluator for VectorEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Vector(v1), DataValue::Vector(v2)) => DataValue::Boolean(v1 == v2),
+            (DataValue::Null, _) | (_, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for VectorNotEqBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(match (left, right) {
+            (DataValue::Vector(v1), DataValue::Vector(v2)) => DataValue::Boolean(v1 != v2),
+            (DataValue::Null, _) | (_, DataValue::Null) => DataValue::Null,
+            _ => unsafe { hint::unreachable_unchecked() },
+        })
+    }
+}
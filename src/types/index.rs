@@ -4,6 +4,7 @@ use crate::expression::range_detacher::Range;
 use crate::expression::ScalarExpression;
 use crate::types::value::DataValue;
 use crate::types::{ColumnId, LogicalType};
+use crate::utils::{full_text, spatial, trigram};
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -14,16 +15,43 @@ pub type IndexMetaRef = Arc<IndexMeta>;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, ReferenceSerialization)]
 pub enum IndexType {
-    PrimaryKey { is_multiple: bool },
+    PrimaryKey {
+        is_multiple: bool,
+    },
     Unique,
     Normal,
     Composite,
+    /// Inverted index of the tokens of a text column, searched by `MATCH ... AGAINST`
+    FullText,
+    /// Inverted index of the trigrams of a text column, searched by infix `LIKE` and `%`
+    Trigram,
+    /// IVF index of a vector column, whose entries are the lists of their nearest centroids,
+    /// probed by `ORDER BY column <-> vector LIMIT k`
+    Vector,
+    /// Index of the Z-order codes of a point column, probed by `st_contains`, `st_within`,
+    /// `st_intersects` and `st_distance(..) <= d`
+    ZOrder,
+}
+
+impl IndexType {
+    /// The entries an index keeps in place of the value of its column,
+    /// `None` for those keeping the value itself.
+    pub(crate) fn index_entries(&self, value: &DataValue) -> Option<Vec<DataValue>> {
+        match self {
+            IndexType::FullText => Some(full_text::index_tokens(value)),
+            IndexType::Trigram => Some(trigram::index_trigrams(value)),
+            IndexType::ZOrder => Some(spatial::index_z_order(value)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, ReferenceSerialization)]
 pub struct IndexInfo {
     pub(crate) meta: IndexMetaRef,
     pub(crate) range: Option<Range>,
+    // the range of the second column of a composite index whose leading column is not filtered
+    pub(crate) skip_range: Option<Range>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, ReferenceSerialization)]
@@ -4,6 +4,7 @@ pub mod tuple;
 pub mod tuple_builder;
 pub mod value;
 
+use bigdecimal::BigDecimal;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -53,6 +54,20 @@ pub enum LogicalType {
     // decimal (precision, scale)
     Decimal(Option<u8>, Option<u8>),
     Tuple(Vec<LogicalType>),
+    // vector of f32 (dimension)
+    Vector(u32),
+    // (x, y) of f64
+    Point,
+    // (x_min, y_min, x_max, y_max) of f64
+    Box,
+    Hugeint,
+    UHugeint,
+    // decimal of unbounded precision
+    Numeric,
+    // list of elements of the type
+    Array(Box<LogicalType>),
+    // named fields
+    Struct(Vec<(String, LogicalType)>),
 }
 
 impl LogicalType {
@@ -75,6 +90,10 @@ impl LogicalType {
             Some(LogicalType::UInteger)
         } else if type_id == TypeId::of::<u64>() {
             Some(LogicalType::UBigint)
+        } else if type_id == TypeId::of::<i128>() {
+            Some(LogicalType::Hugeint)
+        } else if type_id == TypeId::of::<u128>() {
+            Some(LogicalType::UHugeint)
         } else if type_id == TypeId::of::<f32>() {
             Some(LogicalType::Float)
         } else if type_id == TypeId::of::<f64>() {
@@ -87,6 +106,8 @@ impl LogicalType {
             Some(LogicalType::Time(Some(0)))
         } else if type_id == TypeId::of::<Decimal>() {
             Some(LogicalType::Decimal(None, None))
+        } else if type_id == TypeId::of::<BigDecimal>() {
+            Some(LogicalType::Numeric)
         } else if type_id == TypeId::of::<String>() {
             Some(LogicalType::Varchar(None, CharLengthUnits::Characters))
         } else {
@@ -106,6 +127,8 @@ impl LogicalType {
             LogicalType::UInteger => Some(4),
             LogicalType::Bigint => Some(8),
             LogicalType::UBigint => Some(8),
+            LogicalType::Hugeint => Some(16),
+            LogicalType::UHugeint => Some(16),
             LogicalType::Float => Some(4),
             LogicalType::Double => Some(8),
             /// Note: The non-fixed length type's raw_len is None e.g. Varchar
@@ -115,11 +138,16 @@ impl LogicalType {
                 CharLengthUnits::Octets => Some(*len as usize),
             },
             LogicalType::Decimal(_, _) => Some(16),
+            LogicalType::Numeric => None,
             LogicalType::Date => Some(4),
             LogicalType::DateTime => Some(8),
             LogicalType::Time(_) => Some(4),
             LogicalType::TimeStamp(_, _) => Some(8),
             LogicalType::Tuple(_) => unreachable!(),
+            LogicalType::Vector(dimension) => Some(*dimension as usize * 4),
+            LogicalType::Point => Some(16),
+            LogicalType::Box => Some(32),
+            LogicalType::Array(_) | LogicalType::Struct(_) => None,
         }
     }
 
@@ -133,6 +161,8 @@ impl LogicalType {
             LogicalType::UInteger,
             LogicalType::Bigint,
             LogicalType::UBigint,
+            LogicalType::Hugeint,
+            LogicalType::UHugeint,
             LogicalType::Float,
             LogicalType::Double,
         ]
@@ -149,9 +179,12 @@ impl LogicalType {
                 | LogicalType::UInteger
                 | LogicalType::Bigint
                 | LogicalType::UBigint
+                | LogicalType::Hugeint
+                | LogicalType::UHugeint
                 | LogicalType::Float
                 | LogicalType::Double
                 | LogicalType::Decimal(_, _)
+                | LogicalType::Numeric
         )
     }
 
@@ -162,6 +195,7 @@ impl LogicalType {
                 | LogicalType::Smallint
                 | LogicalType::Integer
                 | LogicalType::Bigint
+                | LogicalType::Hugeint
         )
     }
 
@@ -172,6 +206,7 @@ impl LogicalType {
                 | LogicalType::USmallint
                 | LogicalType::UInteger
                 | LogicalType::UBigint
+                | LogicalType::UHugeint
         )
     }
 
@@ -197,6 +232,26 @@ impl LogicalType {
                     return Ok(right.clone());
                 }
             }
+            // the values are compared at the greater precision
+            (LogicalType::Time(precision_0), LogicalType::Time(precision_1)) => {
+                return Ok(LogicalType::Time(Some(
+                    precision_0.unwrap_or(0).max(precision_1.unwrap_or(0)),
+                )));
+            }
+            (
+                LogicalType::TimeStamp(precision_0, zone_0),
+                LogicalType::TimeStamp(precision_1, zone_1),
+            ) => {
+                return Ok(LogicalType::TimeStamp(
+                    Some(precision_0.unwrap_or(0).max(precision_1.unwrap_or(0))),
+                    *zone_0 || *zone_1,
+                ));
+            }
+            (LogicalType::Array(element_0), LogicalType::Array(element_1)) => {
+                return Ok(LogicalType::Array(Box::new(LogicalType::max_logical_type(
+                    element_0, element_1,
+                )?)));
+            }
             _ => {}
         }
         if left.is_numeric() && right.is_numeric() {
@@ -222,6 +277,26 @@ impl LogicalType {
         ) {
             return Ok(LogicalType::DateTime);
         }
+        if let (
+            LogicalType::Vector(_) | LogicalType::Point | LogicalType::Box,
+            LogicalType::Varchar(..) | LogicalType::Char(..),
+        ) = (left, right)
+        {
+            return Ok(left.clone());
+        }
+        if let (
+            LogicalType::Varchar(..) | LogicalType::Char(..),
+            LogicalType::Vector(_) | LogicalType::Point | LogicalType::Box,
+        ) = (left, right)
+        {
+            return Ok(right.clone());
+        }
+        if matches!(
+            (left, right),
+            (LogicalType::Point, LogicalType::Box) | (LogicalType::Box, LogicalType::Point)
+        ) {
+            return Ok(LogicalType::Box);
+        }
         if let (LogicalType::Char(..), LogicalType::Varchar(..))
         | (LogicalType::Varchar(..), LogicalType::Char(..))
         | (LogicalType::Char(..), LogicalType::Char(..))
@@ -239,9 +314,9 @@ impl LogicalType {
         if left == right {
             return Ok(left.clone());
         }
-        if left.is_signed_numeric() && right.is_unsigned_numeric() {
+        if left.is_unsigned_numeric() && right.is_signed_numeric() {
             // this method is symmetric
-            // arrange it so the left type is smaller
+            // arrange it so the left type is the signed one
             // to limit the number of options we need to check
             return LogicalType::combine_numeric_types(right, left);
         }
@@ -257,7 +332,8 @@ impl LogicalType {
         // e.g. INTEGER and UINTEGER
         // in this case we need to upcast to make sure the types fit
         match (left, right) {
-            (LogicalType::Bigint, _) | (_, LogicalType::UBigint) => Ok(LogicalType::Double),
+            (LogicalType::Hugeint, _) | (_, LogicalType::UHugeint) => Ok(LogicalType::Numeric),
+            (LogicalType::Bigint, _) | (_, LogicalType::UBigint) => Ok(LogicalType::Hugeint),
             (LogicalType::Integer, _) | (_, LogicalType::UInteger) => Ok(LogicalType::Bigint),
             (LogicalType::Smallint, _) | (_, LogicalType::USmallint) => Ok(LogicalType::Integer),
             (LogicalType::Tinyint, _) | (_, LogicalType::UTinyint) => Ok(LogicalType::Smallint),
@@ -291,65 +367,99 @@ impl LogicalType {
                 LogicalType::Smallint
                     | LogicalType::Integer
                     | LogicalType::Bigint
+                    | LogicalType::Hugeint
                     | LogicalType::Float
                     | LogicalType::Double
                     | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
             LogicalType::UTinyint => matches!(
                 to,
                 LogicalType::USmallint
                     | LogicalType::UInteger
                     | LogicalType::UBigint
+                    | LogicalType::UHugeint
                     | LogicalType::Smallint
                     | LogicalType::Integer
                     | LogicalType::Bigint
+                    | LogicalType::Hugeint
                     | LogicalType::Float
                     | LogicalType::Double
                     | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
             LogicalType::Smallint => matches!(
                 to,
                 LogicalType::Integer
                     | LogicalType::Bigint
+                    | LogicalType::Hugeint
                     | LogicalType::Float
                     | LogicalType::Double
                     | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
             LogicalType::USmallint => matches!(
                 to,
                 LogicalType::UInteger
                     | LogicalType::UBigint
+                    | LogicalType::UHugeint
                     | LogicalType::Integer
                     | LogicalType::Bigint
+                    | LogicalType::Hugeint
                     | LogicalType::Float
                     | LogicalType::Double
                     | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
             LogicalType::Integer => matches!(
                 to,
                 LogicalType::Bigint
+                    | LogicalType::Hugeint
                     | LogicalType::Float
                     | LogicalType::Double
                     | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
             LogicalType::UInteger => matches!(
                 to,
                 LogicalType::UBigint
+                    | LogicalType::UHugeint
                     | LogicalType::Bigint
+                    | LogicalType::Hugeint
                     | LogicalType::Float
                     | LogicalType::Double
                     | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
             LogicalType::Bigint => matches!(
                 to,
-                LogicalType::Float | LogicalType::Double | LogicalType::Decimal(_, _)
+                LogicalType::Hugeint
+                    | LogicalType::Float
+                    | LogicalType::Double
+                    | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
             LogicalType::UBigint => matches!(
                 to,
-                LogicalType::Float | LogicalType::Double | LogicalType::Decimal(_, _)
+                LogicalType::UHugeint
+                    | LogicalType::Hugeint
+                    | LogicalType::Float
+                    | LogicalType::Double
+                    | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
             ),
-            LogicalType::Float => matches!(to, LogicalType::Double | LogicalType::Decimal(_, _)),
-            LogicalType::Double => matches!(to, LogicalType::Decimal(_, _)),
+            LogicalType::Hugeint | LogicalType::UHugeint => matches!(
+                to,
+                LogicalType::Float
+                    | LogicalType::Double
+                    | LogicalType::Decimal(_, _)
+                    | LogicalType::Numeric
+            ),
+            LogicalType::Float => matches!(
+                to,
+                LogicalType::Double | LogicalType::Decimal(_, _) | LogicalType::Numeric
+            ),
+            LogicalType::Double => matches!(to, LogicalType::Decimal(_, _) | LogicalType::Numeric),
             LogicalType::Char(..) => false,
             LogicalType::Varchar(..) => false,
             LogicalType::Date => matches!(
@@ -366,7 +476,19 @@ impl LogicalType {
             LogicalType::Time(..) => {
                 matches!(to, LogicalType::Varchar(..) | LogicalType::Char(..))
             }
-            LogicalType::Decimal(_, _) | LogicalType::Tuple(_) => false,
+            LogicalType::Point => matches!(to, LogicalType::Box),
+            LogicalType::Decimal(_, _) => matches!(to, LogicalType::Numeric),
+            LogicalType::Array(from_element) => match to {
+                LogicalType::Array(to_element) => {
+                    LogicalType::can_implicit_cast(from_element, to_element)
+                }
+                _ => false,
+            },
+            LogicalType::Numeric
+            | LogicalType::Struct(_)
+            | LogicalType::Tuple(_)
+            | LogicalType::Vector(_)
+            | LogicalType::Box => false,
         }
     }
 }
@@ -464,14 +586,59 @@ impl TryFrom<sqlparser::ast::DataType> for LogicalType {
                 }
                 Ok(LogicalType::TimeStamp(precision, zone))
             }
-            sqlparser::ast::DataType::Decimal(info) | sqlparser::ast::DataType::Dec(info) => {
-                match info {
-                    ExactNumberInfo::None => Ok(Self::Decimal(None, None)),
-                    ExactNumberInfo::Precision(p) => Ok(Self::Decimal(Some(p as u8), None)),
-                    ExactNumberInfo::PrecisionAndScale(p, s) => {
-                        Ok(Self::Decimal(Some(p as u8), Some(s as u8)))
-                    }
+            sqlparser::ast::DataType::Numeric(ExactNumberInfo::None) => Ok(LogicalType::Numeric),
+            sqlparser::ast::DataType::Decimal(info)
+            | sqlparser::ast::DataType::Dec(info)
+            | sqlparser::ast::DataType::Numeric(info) => match info {
+                ExactNumberInfo::None => Ok(Self::Decimal(None, None)),
+                ExactNumberInfo::Precision(p) => Ok(Self::Decimal(Some(p as u8), None)),
+                ExactNumberInfo::PrecisionAndScale(p, s) => {
+                    Ok(Self::Decimal(Some(p as u8), Some(s as u8)))
                 }
+            },
+            sqlparser::ast::DataType::Custom(name, modifiers)
+                if name.to_string().eq_ignore_ascii_case("vector") =>
+            {
+                match modifiers.as_slice() {
+                    [dimension] => match dimension.parse::<u32>() {
+                        Ok(dimension) if dimension > 0 => Ok(LogicalType::Vector(dimension)),
+                        _ => Err(DatabaseError::UnsupportedStmt(format!(
+                            "vector's dimension must be positive, got: {dimension}"
+                        ))),
+                    },
+                    _ => Err(DatabaseError::UnsupportedStmt(
+                        "vector's dimension must be given, e.g. VECTOR(3)".to_string(),
+                    )),
+                }
+            }
+            sqlparser::ast::DataType::Custom(name, modifiers)
+                if modifiers.is_empty() && name.to_string().eq_ignore_ascii_case("point") =>
+            {
+                Ok(LogicalType::Point)
+            }
+            sqlparser::ast::DataType::Custom(name, modifiers)
+                if modifiers.is_empty() && name.to_string().eq_ignore_ascii_case("box") =>
+            {
+                Ok(LogicalType::Box)
+            }
+            sqlparser::ast::DataType::Custom(name, modifiers)
+                if modifiers.is_empty()
+                    && ["hugeint", "int128"]
+                        .iter()
+                        .any(|alias| name.to_string().eq_ignore_ascii_case(alias)) =>
+            {
+                Ok(LogicalType::Hugeint)
+            }
+            sqlparser::ast::DataType::Array(Some(element)) => Ok(LogicalType::Array(Box::new(
+                LogicalType::try_from(*element)?,
+            ))),
+            sqlparser::ast::DataType::Custom(name, modifiers)
+                if modifiers.is_empty()
+                    && ["uhugeint", "uint128"]
+                        .iter()
+                        .any(|alias| name.to_string().eq_ignore_ascii_case(alias)) =>
+            {
+                Ok(LogicalType::UHugeint)
             }
             other => Err(DatabaseError::UnsupportedStmt(format!(
                 "unsupported data type: {other}"
@@ -493,6 +660,9 @@ impl std::fmt::Display for LogicalType {
             LogicalType::UInteger => write!(f, "UInteger")?,
             LogicalType::Bigint => write!(f, "Bigint")?,
             LogicalType::UBigint => write!(f, "UBigint")?,
+            LogicalType::Hugeint => write!(f, "Hugeint")?,
+            LogicalType::UHugeint => write!(f, "UHugeint")?,
+            LogicalType::Numeric => write!(f, "Numeric")?,
             LogicalType::Float => write!(f, "Float")?,
             LogicalType::Double => write!(f, "Double")?,
             LogicalType::Char(len, units) => write!(f, "Char({}, {})", len, units)?,
@@ -518,6 +688,20 @@ impl std::fmt::Display for LogicalType {
                 }
                 write!(f, ")")?
             }
+            LogicalType::Vector(dimension) => write!(f, "Vector({})", dimension)?,
+            LogicalType::Point => write!(f, "Point")?,
+            LogicalType::Box => write!(f, "Box")?,
+            LogicalType::Array(element) => write!(f, "Array({})", element)?,
+            LogicalType::Struct(fields) => {
+                write!(f, "Struct(")?;
+                for (i, (name, ty)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} {}", name, ty)?;
+                }
+                write!(f, ")")?
+            }
         }
 
         Ok(())
@@ -565,6 +749,9 @@ pub(crate) mod test {
         fn_assert(&mut cursor, &mut reference_tables, LogicalType::UInteger)?;
         fn_assert(&mut cursor, &mut reference_tables, LogicalType::Bigint)?;
         fn_assert(&mut cursor, &mut reference_tables, LogicalType::UBigint)?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::Hugeint)?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::UHugeint)?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::Numeric)?;
         fn_assert(&mut cursor, &mut reference_tables, LogicalType::Float)?;
         fn_assert(&mut cursor, &mut reference_tables, LogicalType::Double)?;
         fn_assert(
@@ -650,6 +837,72 @@ pub(crate) mod test {
             &mut reference_tables,
             LogicalType::Tuple(vec![LogicalType::Integer]),
         )?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::Vector(3))?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::Point)?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::Box)?;
+        fn_assert(
+            &mut cursor,
+            &mut reference_tables,
+            LogicalType::Array(Box::new(LogicalType::Integer)),
+        )?;
+        fn_assert(
+            &mut cursor,
+            &mut reference_tables,
+            LogicalType::Struct(vec![
+                ("a".to_string(), LogicalType::Integer),
+                (
+                    "b".to_string(),
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                ),
+            ]),
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_logical_type_numeric() -> Result<(), DatabaseError> {
+        let types = [
+            LogicalType::Tinyint,
+            LogicalType::UTinyint,
+            LogicalType::Smallint,
+            LogicalType::USmallint,
+            LogicalType::Integer,
+            LogicalType::UInteger,
+            LogicalType::Bigint,
+            LogicalType::UBigint,
+            LogicalType::Hugeint,
+            LogicalType::UHugeint,
+            LogicalType::Float,
+            LogicalType::Double,
+            LogicalType::Decimal(Some(10), Some(2)),
+            LogicalType::Numeric,
+        ];
+        for left in types.iter() {
+            for right in types.iter() {
+                let max = LogicalType::max_logical_type(left, right)?;
+
+                assert_eq!(max, LogicalType::max_logical_type(right, left)?);
+                assert!(LogicalType::can_implicit_cast(left, &max));
+                assert!(LogicalType::can_implicit_cast(right, &max));
+            }
+        }
+        assert_eq!(
+            LogicalType::max_logical_type(&LogicalType::Integer, &LogicalType::UInteger)?,
+            LogicalType::Bigint
+        );
+        assert_eq!(
+            LogicalType::max_logical_type(&LogicalType::UBigint, &LogicalType::Tinyint)?,
+            LogicalType::Hugeint
+        );
+        assert_eq!(
+            LogicalType::max_logical_type(&LogicalType::Hugeint, &LogicalType::UHugeint)?,
+            LogicalType::Numeric
+        );
+        assert_eq!(
+            LogicalType::max_logical_type(&LogicalType::Double, &LogicalType::Decimal(None, None))?,
+            LogicalType::Decimal(None, None)
+        );
 
         Ok(())
     }
@@ -44,16 +44,33 @@ impl Tuple {
         with_pk: bool,
     ) -> Result<Self, DatabaseError> {
         debug_assert!(!schema.is_empty());
-        debug_assert!(projections.is_sorted());
         debug_assert_eq!(projections.len(), schema.len());
 
+        let mut values = vec![DataValue::Null; projections.len()];
+        Self::decode_raw(table_types, projections, bytes, |i, value| {
+            values[i] = value;
+        })?;
+
+        Ok(Tuple {
+            pk: with_pk.then(|| Tuple::primary_projection(pk_indices, &values)),
+            values,
+        })
+    }
+
+    pub(crate) fn decode_raw<F: FnMut(usize, DataValue)>(
+        table_types: &[LogicalType],
+        projections: &[usize],
+        bytes: &[u8],
+        mut f: F,
+    ) -> Result<(), DatabaseError> {
+        debug_assert!(projections.is_sorted());
+
         fn is_none(bits: u8, i: usize) -> bool {
             bits & (1 << (7 - i)) > 0
         }
 
         let types_len = table_types.len();
         let bits_len = (types_len + BITS_MAX_INDEX) / BITS_MAX_INDEX;
-        let mut values = vec![DataValue::Null; projections.len()];
 
         let mut projection_i = 0;
         let mut cursor = Cursor::new(&bytes[bits_len..]);
@@ -64,21 +81,19 @@ impl Tuple {
             }
             debug_assert!(projection_i < types_len);
             if is_none(bytes[i / BITS_MAX_INDEX], i % BITS_MAX_INDEX) {
-                projection_i += 1;
+                if projections[projection_i] == i {
+                    projection_i += 1;
+                }
                 continue;
             }
             if let Some(value) =
                 DataValue::from_raw(&mut cursor, logic_type, projections[projection_i] == i)?
             {
-                values[projection_i] = value;
+                f(projection_i, value);
                 projection_i += 1;
             }
         }
-
-        Ok(Tuple {
-            pk: with_pk.then(|| Tuple::primary_projection(pk_indices, &values)),
-            values,
-        })
+        Ok(())
     }
 
     /// e.g.: bits(u8)..|data_0(len for utf8_1)|utf8_0|data_1|
@@ -157,11 +172,13 @@ mod tests {
     use crate::types::tuple::Tuple;
     use crate::types::value::{DataValue, Utf8Type};
     use crate::types::LogicalType;
+    use bigdecimal::BigDecimal;
     use bumpalo::Bump;
     use itertools::Itertools;
     use ordered_float::OrderedFloat;
     use rust_decimal::Decimal;
     use sqlparser::ast::CharLengthUnits;
+    use std::str::FromStr;
     use std::sync::Arc;
 
     #[test]
@@ -271,6 +288,11 @@ mod tests {
                 )
                 .unwrap(),
             )),
+            ColumnRef::from(ColumnCatalog::new(
+                "c17".to_string(),
+                false,
+                ColumnDesc::new(LogicalType::Numeric, None, false, None).unwrap(),
+            )),
         ]);
 
         let tuples = vec![
@@ -309,6 +331,9 @@ mod tests {
                         ty: Utf8Type::Fixed(10),
                         unit: CharLengthUnits::Octets,
                     },
+                    DataValue::Numeric(
+                        BigDecimal::from_str("-123456789012345678901234567890.123456789").unwrap(),
+                    ),
                 ],
             ),
             Tuple::new(
@@ -330,6 +355,7 @@ mod tests {
                     DataValue::Null,
                     DataValue::Null,
                     DataValue::Null,
+                    DataValue::Null,
                 ],
             ),
         ];
@@ -343,7 +369,7 @@ mod tests {
             let tuple_0 = Tuple::deserialize_from(
                 &types,
                 &Arc::new(vec![0]),
-                &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+                &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
                 &columns,
                 &tuples[0].serialize_to(&types, &arena).unwrap(),
                 true,
@@ -356,7 +382,7 @@ mod tests {
             let tuple_1 = Tuple::deserialize_from(
                 &types,
                 &Arc::new(vec![0]),
-                &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+                &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
                 &columns,
                 &tuples[1].serialize_to(&types, &arena).unwrap(),
                 true,
@@ -24,13 +24,22 @@ impl<'a> TupleBuilder<'a> {
         Tuple::new(None, values)
     }
 
+    /// Builds a tuple from the text of its values, `null` being the text of a NULL if any.
     pub fn build_with_row<'b>(
         &self,
         row: impl IntoIterator<Item = &'b str>,
+        null: Option<&str>,
     ) -> Result<Tuple, DatabaseError> {
         let mut values = Vec::with_capacity(self.schema.len());
 
         for (i, value) in row.into_iter().enumerate() {
+            if Some(value) == null {
+                if !self.schema[i].nullable() {
+                    return Err(DatabaseError::NotNull(self.schema[i].name().to_string()));
+                }
+                values.push(DataValue::Null);
+                continue;
+            }
             values.push(
                 DataValue::Utf8 {
                     value: value.to_string(),
@@ -1,6 +1,10 @@
 use super::LogicalType;
 use crate::errors::DatabaseError;
-use crate::storage::table_codec::{BumpBytes, BOUND_MAX_TAG, BOUND_MIN_TAG};
+use crate::storage::memcomparable::{self, KeyOrder};
+use crate::storage::table_codec::BumpBytes;
+use crate::utils::{spatial, vector};
+use bigdecimal::num_bigint::{BigInt, Sign};
+use bigdecimal::BigDecimal;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use chrono::format::{DelayedFormat, StrftimeItems};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
@@ -15,7 +19,7 @@ use std::hash::Hash;
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::str::FromStr;
 use std::sync::LazyLock;
-use std::{cmp, fmt, mem};
+use std::{fmt, mem};
 
 pub static NULL_VALUE: LazyLock<DataValue> = LazyLock::new(|| DataValue::Null);
 
@@ -37,15 +41,23 @@ pub const TIME_FMT_WITHOUT_PRECISION: &str = "%H:%M:%S%z";
 pub const ONE_SEC_TO_NANO: u32 = 1_000_000_000;
 pub const ONE_DAY_TO_SEC: u32 = 86_400;
 
-const ENCODE_GROUP_SIZE: usize = 8;
-const ENCODE_MARKER: u8 = 0xFF;
-
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub enum Utf8Type {
     Variable(Option<u32>),
     Fixed(u32),
 }
 
+/// How a text is implicitly cast to the number it is compared or computed with, e.g. `'12abc' + 1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StringCoercion {
+    /// The whole text must be a number, otherwise the statement fails with the text in the error
+    #[default]
+    Strict,
+    /// The longest prefix of the text that is a number is taken, `NULL` when there is none,
+    /// see [`DataValue::cast_lenient`]
+    Lenient,
+}
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub enum DataValue {
     Null,
@@ -74,6 +86,24 @@ pub enum DataValue {
     Decimal(Decimal),
     /// (values, is_upper)
     Tuple(Vec<DataValue>, bool),
+    /// The components of a `VECTOR(n)`
+    Vector(Vec<OrderedFloat<f32>>),
+    /// (x, y)
+    Point(OrderedFloat<f64>, OrderedFloat<f64>),
+    /// (x_min, y_min, x_max, y_max)
+    Box(
+        OrderedFloat<f64>,
+        OrderedFloat<f64>,
+        OrderedFloat<f64>,
+        OrderedFloat<f64>,
+    ),
+    Int128(i128),
+    UInt128(u128),
+    Numeric(BigDecimal),
+    /// The elements of an `ARRAY`, nulls included
+    Array(Vec<DataValue>),
+    /// (field name, value) of a `STRUCT`
+    Struct(Vec<(String, DataValue)>),
 }
 
 macro_rules! generate_get_option {
@@ -102,6 +132,8 @@ generate_get_option!(DataValue,
     u16 : UInt16(Option<u16>),
     u32 : UInt32(Option<u32>),
     u64 : UInt64(Option<u64>),
+    i128 : Int128(Option<i128>),
+    u128 : UInt128(Option<u128>),
     decimal : Decimal(Option<Decimal>)
 );
 
@@ -136,6 +168,12 @@ impl PartialEq for DataValue {
             (UInt32(_), _) => false,
             (UInt64(v1), UInt64(v2)) => v1.eq(v2),
             (UInt64(_), _) => false,
+            (Int128(v1), Int128(v2)) => v1.eq(v2),
+            (Int128(_), _) => false,
+            (UInt128(v1), UInt128(v2)) => v1.eq(v2),
+            (UInt128(_), _) => false,
+            (Numeric(v1), Numeric(v2)) => v1.eq(v2),
+            (Numeric(_), _) => false,
             (Utf8 { value: v1, .. }, Utf8 { value: v2, .. }) => v1.eq(v2),
             (Utf8 { .. }, _) => false,
             (Null, Null) => true,
@@ -154,6 +192,18 @@ impl PartialEq for DataValue {
                 values_1.eq(values_2) && is_upper_1.eq(is_upper_2)
             }
             (Tuple(..), _) => false,
+            (Vector(v1), Vector(v2)) => v1.eq(v2),
+            (Vector(_), _) => false,
+            (Point(x1, y1), Point(x2, y2)) => x1.eq(x2) && y1.eq(y2),
+            (Point(..), _) => false,
+            (Box(x_min1, y_min1, x_max1, y_max1), Box(x_min2, y_min2, x_max2, y_max2)) => {
+                (x_min1, y_min1, x_max1, y_max1).eq(&(x_min2, y_min2, x_max2, y_max2))
+            }
+            (Box(..), _) => false,
+            (Array(v1), Array(v2)) => v1.eq(v2),
+            (Array(_), _) => false,
+            (Struct(v1), Struct(v2)) => v1.eq(v2),
+            (Struct(_), _) => false,
         }
     }
 }
@@ -184,6 +234,12 @@ impl PartialOrd for DataValue {
             (UInt32(_), _) => None,
             (UInt64(v1), UInt64(v2)) => v1.partial_cmp(v2),
             (UInt64(_), _) => None,
+            (Int128(v1), Int128(v2)) => v1.partial_cmp(v2),
+            (Int128(_), _) => None,
+            (UInt128(v1), UInt128(v2)) => v1.partial_cmp(v2),
+            (UInt128(_), _) => None,
+            (Numeric(v1), Numeric(v2)) => v1.partial_cmp(v2),
+            (Numeric(_), _) => None,
             (Utf8 { value: v1, .. }, Utf8 { value: v2, .. }) => v1.partial_cmp(v2),
             (Utf8 { .. }, _) => None,
             (Null, Null) => Some(Ordering::Equal),
@@ -199,16 +255,22 @@ impl PartialOrd for DataValue {
             (Decimal(v1), Decimal(v2)) => v1.partial_cmp(v2),
             (Decimal(_), _) => None,
             (Tuple(..), _) => None,
+            (Vector(v1), Vector(v2)) => v1.partial_cmp(v2),
+            (Vector(_), _) => None,
+            (Point(x1, y1), Point(x2, y2)) => (x1, y1).partial_cmp(&(x2, y2)),
+            (Point(..), _) => None,
+            (Box(x_min1, y_min1, x_max1, y_max1), Box(x_min2, y_min2, x_max2, y_max2)) => {
+                (x_min1, y_min1, x_max1, y_max1).partial_cmp(&(x_min2, y_min2, x_max2, y_max2))
+            }
+            (Box(..), _) => None,
+            (Array(v1), Array(v2)) => v1.partial_cmp(v2),
+            (Array(_), _) => None,
+            (Struct(v1), Struct(v2)) => v1.partial_cmp(v2),
+            (Struct(_), _) => None,
         }
     }
 }
 
-macro_rules! encode_u {
-    ($writer:ident, $u:expr) => {
-        $writer.write_all(&$u.to_be_bytes())?
-    };
-}
-
 impl Eq for DataValue {}
 
 impl Hash for DataValue {
@@ -226,6 +288,9 @@ impl Hash for DataValue {
             UInt16(v) => v.hash(state),
             UInt32(v) => v.hash(state),
             UInt64(v) => v.hash(state),
+            Int128(v) => v.hash(state),
+            UInt128(v) => v.hash(state),
+            Numeric(v) => v.hash(state),
             Utf8 { value: v, .. } => v.hash(state),
             Null => 1.hash(state),
             Date32(v) => v.hash(state),
@@ -237,6 +302,19 @@ impl Hash for DataValue {
                 values.hash(state);
                 is_upper.hash(state);
             }
+            Vector(v) => v.hash(state),
+            Point(x, y) => {
+                x.hash(state);
+                y.hash(state);
+            }
+            Box(x_min, y_min, x_max, y_max) => {
+                x_min.hash(state);
+                y_min.hash(state);
+                x_max.hash(state);
+                y_max.hash(state);
+            }
+            Array(values) => values.hash(state),
+            Struct(fields) => fields.hash(state),
         }
     }
 }
@@ -274,7 +352,9 @@ macro_rules! float_to_int {
         let float_value: $float_type = $float_value;
         if float_value.is_nan() {
             Ok(0)
-        } else if float_value <= 0.0 || float_value > <$int_type>::MAX as $float_type {
+        } else if float_value.trunc() < <$int_type>::MIN as $float_type
+            || float_value.trunc() > <$int_type>::MAX as $float_type
+        {
             Err(DatabaseError::OverFlow)
         } else {
             Ok(float_value as $int_type)
@@ -461,7 +541,7 @@ impl DataValue {
     }
 
     fn format_time(value: u32, precision: u64) -> Option<String> {
-        Self::time_format(value, precision).map(|fmt| format!("{}", fmt))
+        Self::time_format(value, precision)
     }
 
     fn format_timestamp(value: i64, precision: u64) -> Option<String> {
@@ -473,6 +553,24 @@ impl DataValue {
         matches!(self, DataValue::Null)
     }
 
+    /// Bytes of the value in memory, its heap allocations included, by an estimate.
+    pub(crate) fn estimated_size(&self) -> usize {
+        mem::size_of::<DataValue>()
+            + match self {
+                DataValue::Utf8 { value, .. } => value.capacity(),
+                DataValue::Tuple(values, _) | DataValue::Array(values) => {
+                    values.iter().map(DataValue::estimated_size).sum()
+                }
+                DataValue::Vector(values) => values.capacity() * mem::size_of::<f32>(),
+                DataValue::Numeric(value) => value.digits() as usize,
+                DataValue::Struct(fields) => fields
+                    .iter()
+                    .map(|(name, value)| name.capacity() + value.estimated_size())
+                    .sum(),
+                _ => 0,
+            }
+    }
+
     #[inline]
     pub fn init(logic_type: &LogicalType) -> DataValue {
         match logic_type {
@@ -486,6 +584,8 @@ impl DataValue {
             LogicalType::UInteger => DataValue::UInt32(0),
             LogicalType::Bigint => DataValue::Int64(0),
             LogicalType::UBigint => DataValue::UInt64(0),
+            LogicalType::Hugeint => DataValue::Int128(0),
+            LogicalType::UHugeint => DataValue::UInt128(0),
             LogicalType::Float => DataValue::Float32(OrderedFloat(0.0)),
             LogicalType::Double => DataValue::Float64(OrderedFloat(0.0)),
             LogicalType::Char(len, unit) => DataValue::Utf8 {
@@ -518,11 +618,29 @@ impl DataValue {
                 _ => unreachable!(),
             },
             LogicalType::Decimal(_, _) => DataValue::Decimal(Decimal::new(0, 0)),
+            LogicalType::Numeric => DataValue::Numeric(BigDecimal::default()),
             LogicalType::Tuple(types) => {
                 let values = types.iter().map(DataValue::init).collect_vec();
 
                 DataValue::Tuple(values, false)
             }
+            LogicalType::Vector(dimension) => {
+                DataValue::Vector(vec![OrderedFloat(0.0); *dimension as usize])
+            }
+            LogicalType::Point => DataValue::Point(OrderedFloat(0.0), OrderedFloat(0.0)),
+            LogicalType::Box => DataValue::Box(
+                OrderedFloat(0.0),
+                OrderedFloat(0.0),
+                OrderedFloat(0.0),
+                OrderedFloat(0.0),
+            ),
+            LogicalType::Array(_) => DataValue::Array(Vec::new()),
+            LogicalType::Struct(fields) => DataValue::Struct(
+                fields
+                    .iter()
+                    .map(|(name, ty)| (name.clone(), DataValue::init(ty)))
+                    .collect_vec(),
+            ),
         }
     }
 
@@ -574,6 +692,14 @@ impl DataValue {
                 writer.write_u64::<LittleEndian>(*v)?;
                 return Ok(());
             }
+            DataValue::Int128(v) => {
+                writer.write_i128::<LittleEndian>(*v)?;
+                return Ok(());
+            }
+            DataValue::UInt128(v) => {
+                writer.write_u128::<LittleEndian>(*v)?;
+                return Ok(());
+            }
             DataValue::Utf8 { value: v, ty, unit } => match ty {
                 Utf8Type::Variable(_) => {
                     let bytes = v.as_bytes();
@@ -625,11 +751,73 @@ impl DataValue {
                 writer.write_all(&v.serialize())?;
                 return Ok(());
             }
+            DataValue::Numeric(v) => {
+                let (mantissa, scale) = v.as_bigint_and_exponent();
+                let bytes = mantissa.to_signed_bytes_le();
+
+                writer.write_i64::<LittleEndian>(scale)?;
+                writer.write_u32::<LittleEndian>(bytes.len() as u32)?;
+                writer.write_all(&bytes)?;
+                return Ok(());
+            }
             DataValue::Tuple(..) => unreachable!(),
+            DataValue::Vector(v) => {
+                for component in v {
+                    writer.write_f32::<LittleEndian>(component.0)?;
+                }
+                return Ok(());
+            }
+            DataValue::Point(x, y) => {
+                writer.write_f64::<LittleEndian>(x.0)?;
+                writer.write_f64::<LittleEndian>(y.0)?;
+                return Ok(());
+            }
+            DataValue::Box(x_min, y_min, x_max, y_max) => {
+                for coordinate in [x_min, y_min, x_max, y_max] {
+                    writer.write_f64::<LittleEndian>(coordinate.0)?;
+                }
+                return Ok(());
+            }
+            DataValue::Array(values) => {
+                Self::nested_to_raw(writer, values.iter())?;
+                return Ok(());
+            }
+            DataValue::Struct(fields) => {
+                Self::nested_to_raw(writer, fields.iter().map(|(_, value)| value))?;
+                return Ok(());
+            }
         }
         Ok(())
     }
 
+    /// Writes the byte length ahead of the elements so that an unprojected value can be skipped,
+    /// then the count and every element behind a null flag.
+    fn nested_to_raw<'a, W: Write>(
+        writer: &mut W,
+        values: impl ExactSizeIterator<Item = &'a DataValue>,
+    ) -> Result<(), DatabaseError> {
+        let mut bytes = Vec::new();
+
+        bytes.write_u32::<LittleEndian>(values.len() as u32)?;
+        for value in values {
+            bytes.write_u8(value.is_null() as u8)?;
+            value.to_raw(&mut bytes)?;
+        }
+        writer.write_u32::<LittleEndian>(bytes.len() as u32)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    fn nested_element_from_raw<R: Read + Seek>(
+        reader: &mut R,
+        ty: &LogicalType,
+    ) -> Result<DataValue, DatabaseError> {
+        if reader.read_u8()? != 0 {
+            return Ok(DataValue::Null);
+        }
+        Ok(Self::from_raw(reader, ty, true)?.unwrap_or(DataValue::Null))
+    }
+
     #[inline]
     pub fn from_raw<R: Read + Seek>(
         reader: &mut R,
@@ -706,6 +894,20 @@ impl DataValue {
                 }
                 DataValue::UInt64(reader.read_u64::<LittleEndian>()?)
             }
+            LogicalType::Hugeint => {
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(16))?;
+                    return Ok(None);
+                }
+                DataValue::Int128(reader.read_i128::<LittleEndian>()?)
+            }
+            LogicalType::UHugeint => {
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(16))?;
+                    return Ok(None);
+                }
+                DataValue::UInt128(reader.read_u128::<LittleEndian>()?)
+            }
             LogicalType::Float => {
                 if !is_projection {
                     reader.seek(SeekFrom::Current(4))?;
@@ -805,7 +1007,79 @@ impl DataValue {
 
                 DataValue::Decimal(Decimal::deserialize(bytes))
             }
+            LogicalType::Numeric => {
+                let scale = reader.read_i64::<LittleEndian>()?;
+                let len = reader.read_u32::<LittleEndian>()? as usize;
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(len as i64))?;
+                    return Ok(None);
+                }
+                let mut bytes = vec![0; len];
+                reader.read_exact(&mut bytes)?;
+
+                DataValue::Numeric(BigDecimal::new(BigInt::from_signed_bytes_le(&bytes), scale))
+            }
             LogicalType::Tuple(_) => unreachable!(),
+            LogicalType::Vector(dimension) => {
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(*dimension as i64 * 4))?;
+                    return Ok(None);
+                }
+                let mut components = Vec::with_capacity(*dimension as usize);
+                for _ in 0..*dimension {
+                    components.push(OrderedFloat(reader.read_f32::<LittleEndian>()?));
+                }
+                DataValue::Vector(components)
+            }
+            LogicalType::Point => {
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(16))?;
+                    return Ok(None);
+                }
+                DataValue::Point(
+                    OrderedFloat(reader.read_f64::<LittleEndian>()?),
+                    OrderedFloat(reader.read_f64::<LittleEndian>()?),
+                )
+            }
+            LogicalType::Box => {
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(32))?;
+                    return Ok(None);
+                }
+                DataValue::Box(
+                    OrderedFloat(reader.read_f64::<LittleEndian>()?),
+                    OrderedFloat(reader.read_f64::<LittleEndian>()?),
+                    OrderedFloat(reader.read_f64::<LittleEndian>()?),
+                    OrderedFloat(reader.read_f64::<LittleEndian>()?),
+                )
+            }
+            LogicalType::Array(element) => {
+                let len = reader.read_u32::<LittleEndian>()?;
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(len as i64))?;
+                    return Ok(None);
+                }
+                let count = reader.read_u32::<LittleEndian>()? as usize;
+                let mut values = Vec::with_capacity(count);
+                for _ in 0..count {
+                    values.push(Self::nested_element_from_raw(reader, element)?);
+                }
+                DataValue::Array(values)
+            }
+            LogicalType::Struct(fields) => {
+                let len = reader.read_u32::<LittleEndian>()?;
+                if !is_projection {
+                    reader.seek(SeekFrom::Current(len as i64))?;
+                    return Ok(None);
+                }
+                let count = reader.read_u32::<LittleEndian>()? as usize;
+                debug_assert_eq!(count, fields.len());
+                let mut values = Vec::with_capacity(count);
+                for (name, ty) in fields {
+                    values.push((name.clone(), Self::nested_element_from_raw(reader, ty)?));
+                }
+                DataValue::Struct(values)
+            }
         };
         Ok(Some(value))
     }
@@ -825,6 +1099,8 @@ impl DataValue {
             DataValue::UInt16(_) => LogicalType::USmallint,
             DataValue::UInt32(_) => LogicalType::UInteger,
             DataValue::UInt64(_) => LogicalType::UBigint,
+            DataValue::Int128(_) => LogicalType::Hugeint,
+            DataValue::UInt128(_) => LogicalType::UHugeint,
             DataValue::Utf8 {
                 ty: Utf8Type::Variable(len),
                 unit,
@@ -837,255 +1113,149 @@ impl DataValue {
             } => LogicalType::Char(*len, *unit),
             DataValue::Date32(_) => LogicalType::Date,
             DataValue::Date64(_) => LogicalType::DateTime,
-            DataValue::Time32(..) => LogicalType::Time(None),
-            DataValue::Time64(..) => LogicalType::TimeStamp(None, false),
+            DataValue::Time32(_, precision) => LogicalType::Time(Some(*precision)),
+            DataValue::Time64(_, precision, zone) => {
+                LogicalType::TimeStamp(Some(*precision), *zone)
+            }
             DataValue::Decimal(_) => LogicalType::Decimal(None, None),
+            DataValue::Numeric(_) => LogicalType::Numeric,
             DataValue::Tuple(values, ..) => {
                 let types = values.iter().map(|v| v.logical_type()).collect_vec();
                 LogicalType::Tuple(types)
             }
+            DataValue::Vector(v) => LogicalType::Vector(v.len() as u32),
+            DataValue::Point(..) => LogicalType::Point,
+            DataValue::Box(..) => LogicalType::Box,
+            DataValue::Array(values) => LogicalType::Array(Box::new(
+                values
+                    .iter()
+                    .find(|value| !value.is_null())
+                    .map(DataValue::logical_type)
+                    .unwrap_or(LogicalType::SqlNull),
+            )),
+            DataValue::Struct(fields) => LogicalType::Struct(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.logical_type()))
+                    .collect_vec(),
+            ),
         }
     }
 
-    // EncodeBytes guarantees the encoded value is in ascending order for comparison,
-    // encoding with the following rule:
-    //
-    //	[group1][marker1]...[groupN][markerN]
-    //	group is 8 bytes slice which is padding with 0.
-    //	marker is `0xFF - padding 0 count`
-    //
-    // For example:
-    //
-    //	[] -> [0, 0, 0, 0, 0, 0, 0, 0, 247]
-    //	[1, 2, 3] -> [1, 2, 3, 0, 0, 0, 0, 0, 250]
-    //	[1, 2, 3, 0] -> [1, 2, 3, 0, 0, 0, 0, 0, 251]
-    //	[1, 2, 3, 4, 5, 6, 7, 8] -> [1, 2, 3, 4, 5, 6, 7, 8, 255, 0, 0, 0, 0, 0, 0, 0, 0, 247]
-    //
-    // Refer: https://github.com/facebook/mysql-5.6/wiki/MyRocks-record-format#memcomparable-format
+    /// The key of the value in the primary keys and the indexes, see [`memcomparable`].
     #[inline]
-    fn encode_bytes(b: &mut BumpBytes, data: &[u8]) {
-        let d_len = data.len();
-        let realloc_size = (d_len / ENCODE_GROUP_SIZE + 1) * (ENCODE_GROUP_SIZE + 1);
-        Self::realloc_bytes(b, realloc_size);
-
-        let mut idx = 0;
-        while idx <= d_len {
-            let remain = d_len - idx;
-            let pad_count: usize;
-
-            if remain >= ENCODE_GROUP_SIZE {
-                b.extend_from_slice(&data[idx..idx + ENCODE_GROUP_SIZE]);
-                pad_count = 0;
-            } else {
-                pad_count = ENCODE_GROUP_SIZE - remain;
-                b.extend_from_slice(&data[idx..]);
-                b.extend_from_slice(&vec![0; pad_count]);
-            }
-
-            b.push(ENCODE_MARKER - pad_count as u8);
-            idx += ENCODE_GROUP_SIZE;
-        }
+    pub fn memcomparable_encode(&self, b: &mut BumpBytes) -> Result<(), DatabaseError> {
+        memcomparable::encode_key(self, KeyOrder::ASC, b)
     }
 
     #[inline]
-    fn realloc_bytes(b: &mut BumpBytes, size: usize) {
-        let len = b.len();
-
-        if size > len {
-            b.reserve(size - len);
-            b.resize(size, 0);
+    pub fn is_true(&self) -> Result<bool, DatabaseError> {
+        if self.is_null() {
+            return Ok(false);
+        }
+        if let DataValue::Boolean(is_true) = self {
+            Ok(*is_true)
+        } else {
+            Err(DatabaseError::InvalidType)
         }
     }
 
-    #[inline]
-    pub fn memcomparable_encode(&self, b: &mut BumpBytes) -> Result<(), DatabaseError> {
-        match self {
-            DataValue::Int8(v) => encode_u!(b, *v as u8 ^ 0x80_u8),
-            DataValue::Int16(v) => encode_u!(b, *v as u16 ^ 0x8000_u16),
-            DataValue::Int32(v) | DataValue::Date32(v) => {
-                encode_u!(b, *v as u32 ^ 0x80000000_u32)
-            }
-            DataValue::Int64(v) | DataValue::Date64(v) | DataValue::Time64(v, ..) => {
-                encode_u!(b, *v as u64 ^ 0x8000000000000000_u64)
-            }
-            DataValue::UInt8(v) => encode_u!(b, v),
-            DataValue::UInt16(v) => encode_u!(b, v),
-            DataValue::UInt32(v) | DataValue::Time32(v, ..) => encode_u!(b, v),
-            DataValue::UInt64(v) => encode_u!(b, v),
-            DataValue::Utf8 { value: v, .. } => Self::encode_bytes(b, v.as_bytes()),
-            DataValue::Boolean(v) => b.push(if *v { b'1' } else { b'0' }),
-            DataValue::Float32(f) => {
-                let mut u = f.to_bits();
-
-                if f.0 >= 0_f32 {
-                    u |= 0x80000000_u32;
-                } else {
-                    u = !u;
-                }
-
-                encode_u!(b, u);
-            }
-            DataValue::Float64(f) => {
-                let mut u = f.to_bits();
-
-                if f.0 >= 0_f64 {
-                    u |= 0x8000000000000000_u64;
-                } else {
-                    u = !u;
-                }
+    /// Parses a text as a number of the numeric type `to`, the error names the text.
+    fn parse_numeric(value: &str, to: &LogicalType) -> Result<DataValue, DatabaseError> {
+        let number = match to {
+            LogicalType::Tinyint => i8::from_str(value).ok().map(DataValue::Int8),
+            LogicalType::UTinyint => u8::from_str(value).ok().map(DataValue::UInt8),
+            LogicalType::Smallint => i16::from_str(value).ok().map(DataValue::Int16),
+            LogicalType::USmallint => u16::from_str(value).ok().map(DataValue::UInt16),
+            LogicalType::Integer => i32::from_str(value).ok().map(DataValue::Int32),
+            LogicalType::UInteger => u32::from_str(value).ok().map(DataValue::UInt32),
+            LogicalType::Bigint => i64::from_str(value).ok().map(DataValue::Int64),
+            LogicalType::UBigint => u64::from_str(value).ok().map(DataValue::UInt64),
+            LogicalType::Hugeint => i128::from_str(value).ok().map(DataValue::Int128),
+            LogicalType::UHugeint => u128::from_str(value).ok().map(DataValue::UInt128),
+            LogicalType::Float => f32::from_str(value)
+                .ok()
+                .map(|value| DataValue::Float32(OrderedFloat(value))),
+            LogicalType::Double => f64::from_str(value)
+                .ok()
+                .map(|value| DataValue::Float64(OrderedFloat(value))),
+            LogicalType::Decimal(_, _) => Decimal::from_str(value).ok().map(DataValue::Decimal),
+            LogicalType::Numeric => BigDecimal::from_str(value).ok().map(DataValue::Numeric),
+            _ => None,
+        };
+        number.ok_or_else(|| DatabaseError::CastValueFail {
+            value: value.to_string(),
+            to: to.clone(),
+        })
+    }
 
-                encode_u!(b, u);
-            }
-            DataValue::Null => (),
-            DataValue::Decimal(v) => Self::serialize_decimal(*v, b)?,
-            DataValue::Tuple(values, is_upper) => {
-                let last = values.len() - 1;
-
-                for (i, v) in values.iter().enumerate() {
-                    v.memcomparable_encode(b)?;
-                    if (v.is_null() || i == last) && *is_upper {
-                        b.push(BOUND_MAX_TAG);
-                    } else {
-                        b.push(BOUND_MIN_TAG);
-                    }
-                }
+    /// The longest prefix of a text that is a number of `to`: an optional sign and digits,
+    /// followed by a fraction for the decimals and floats and an exponent for the floats.
+    fn numeric_prefix<'a>(value: &'a str, to: &LogicalType) -> &'a str {
+        let bytes = value.as_bytes();
+        let digits = |start: usize| {
+            start
+                + bytes[start..]
+                    .iter()
+                    .take_while(|byte| byte.is_ascii_digit())
+                    .count()
+        };
+        let sign = |start: usize| match bytes.get(start) {
+            Some(b'+' | b'-') => start + 1,
+            _ => start,
+        };
+        let has_fraction = matches!(
+            to,
+            LogicalType::Float
+                | LogicalType::Double
+                | LogicalType::Decimal(_, _)
+                | LogicalType::Numeric
+        );
+        let start = sign(0);
+        let mut end = digits(start);
+        let mut has_digits = end > start;
+
+        if has_fraction && bytes.get(end) == Some(&b'.') {
+            let fraction_end = digits(end + 1);
+            if has_digits || fraction_end > end + 1 {
+                has_digits = true;
+                end = fraction_end;
             }
         }
-
-        Ok(())
-    }
-
-    // https://github.com/risingwavelabs/memcomparable/blob/main/src/ser.rs#L468
-    pub fn serialize_decimal(decimal: Decimal, bytes: &mut BumpBytes) -> Result<(), DatabaseError> {
-        if decimal.is_zero() {
-            bytes.push(0x15);
-            return Ok(());
+        if !has_digits {
+            return "";
         }
-        let (exponent, significand) = Self::decimal_e_m(decimal);
-        if decimal.is_sign_positive() {
-            match exponent {
-                11.. => {
-                    bytes.push(0x22);
-                    bytes.push(exponent as u8);
-                }
-                0..=10 => {
-                    bytes.push(0x17 + exponent as u8);
-                }
-                _ => {
-                    bytes.push(0x16);
-                    bytes.push(!(-exponent) as u8);
-                }
-            }
-            bytes.extend_from_slice(&significand)
-        } else {
-            match exponent {
-                11.. => {
-                    bytes.push(0x8);
-                    bytes.push(!exponent as u8);
-                }
-                0..=10 => {
-                    bytes.push(0x13 - exponent as u8);
-                }
-                _ => {
-                    bytes.push(0x14);
-                    bytes.push(-exponent as u8);
-                }
-            }
-            for b in significand {
-                bytes.push(!b);
+        if to.is_floating_point_numeric() && matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let exponent_start = sign(end + 1);
+            let exponent_end = digits(exponent_start);
+            if exponent_end > exponent_start {
+                end = exponent_end;
             }
         }
-        Ok(())
+        &value[..end]
     }
 
-    fn decimal_e_m(decimal: Decimal) -> (i8, Vec<u8>) {
-        if decimal.is_zero() {
-            return (0, vec![]);
-        }
-        const POW10: [u128; 30] = [
-            1,
-            10,
-            100,
-            1000,
-            10000,
-            100000,
-            1000000,
-            10000000,
-            100000000,
-            1000000000,
-            10000000000,
-            100000000000,
-            1000000000000,
-            10000000000000,
-            100000000000000,
-            1000000000000000,
-            10000000000000000,
-            100000000000000000,
-            1000000000000000000,
-            10000000000000000000,
-            100000000000000000000,
-            1000000000000000000000,
-            10000000000000000000000,
-            100000000000000000000000,
-            1000000000000000000000000,
-            10000000000000000000000000,
-            100000000000000000000000000,
-            1000000000000000000000000000,
-            10000000000000000000000000000,
-            100000000000000000000000000000,
-        ];
-        let mut mantissa = decimal.mantissa().unsigned_abs();
-        let prec = POW10.as_slice().partition_point(|&p| p <= mantissa);
-
-        let e10 = prec as i32 - decimal.scale() as i32;
-        let e100 = if e10 >= 0 { (e10 + 1) / 2 } else { e10 / 2 };
-        // Maybe need to add a zero at the beginning.
-        // e.g. 111.11 -> 2(exponent which is 100 based) + 0.011111(mantissa).
-        // So, the `digit_num` of 111.11 will be 6.
-        let mut digit_num = if e10 == 2 * e100 { prec } else { prec + 1 };
-
-        let mut byte_array = Vec::with_capacity(16);
-        // Remove trailing zero.
-        while mantissa % 10 == 0 && mantissa != 0 {
-            mantissa /= 10;
-            digit_num -= 1;
-        }
-
-        // Cases like: 0.12345, not 0.01111.
-        if digit_num % 2 == 1 {
-            mantissa *= 10;
-            // digit_num += 1;
-        }
-        while mantissa >> 64 != 0 {
-            let byte = (mantissa % 100) as u8 * 2 + 1;
-            byte_array.push(byte);
-            mantissa /= 100;
-        }
-        // optimize for division
-        let mut mantissa = mantissa as u64;
-        while mantissa != 0 {
-            let byte = (mantissa % 100) as u8 * 2 + 1;
-            byte_array.push(byte);
-            mantissa /= 100;
-        }
-        byte_array[0] -= 1;
-        byte_array.reverse();
-
-        (e100 as i8, byte_array)
-    }
+    /// Converts the value into `to` as [`cast`](Self::cast) does, except that a text cast to a
+    /// numeric type is read up to the first character that does not belong to the number, e.g.
+    /// `'12abc'` is `12`, and a text without a leading number, or one that does not fit `to`,
+    /// is `NULL`. It is the implicit cast of [`StringCoercion::Lenient`].
+    pub fn cast_lenient(self, to: &LogicalType) -> Result<DataValue, DatabaseError> {
+        match &self {
+            DataValue::Utf8 { value, .. } if to.is_numeric() => {
+                let prefix = Self::numeric_prefix(value.trim_start(), to);
 
-    #[inline]
-    pub fn is_true(&self) -> Result<bool, DatabaseError> {
-        if self.is_null() {
-            return Ok(false);
-        }
-        if let DataValue::Boolean(is_true) = self {
-            Ok(*is_true)
-        } else {
-            Err(DatabaseError::InvalidType)
+                Ok(Self::parse_numeric(prefix, to).unwrap_or(DataValue::Null))
+            }
+            _ => self.cast(to),
         }
     }
 
+    /// Converts the value into `to`, between the numeric types:
+    ///
+    /// - integers that do not fit a narrower or unsigned integer fail with `OverFlow`
+    /// - floats truncate toward zero and fail with `OverFlow` out of range, `NaN` becomes 0
+    /// - decimals truncate toward zero and saturate at the bounds of the integer
+    /// - floats and decimals round half away from zero to the scale of a `Decimal`
     pub fn cast(self, to: &LogicalType) -> Result<DataValue, DatabaseError> {
         let value = match self {
             DataValue::Null => Ok(DataValue::Null),
@@ -1100,6 +1270,8 @@ impl DataValue {
                 LogicalType::UInteger => Ok(DataValue::UInt32(value.into())),
                 LogicalType::Bigint => Ok(DataValue::Int64(value.into())),
                 LogicalType::UBigint => Ok(DataValue::UInt64(value.into())),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(value.into())),
                 LogicalType::Float => Ok(DataValue::Float32(value.into())),
                 LogicalType::Double => Ok(DataValue::Float64(value.into())),
                 LogicalType::Char(len, unit) => {
@@ -1133,6 +1305,9 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::from_str(
+                    &value.to_string(),
+                )?)),
                 LogicalType::Tinyint => {
                     Ok(DataValue::Int8(float_to_int!(value.into_inner(), i8, f32)?))
                 }
@@ -1171,6 +1346,16 @@ impl DataValue {
                     u64,
                     f32
                 )?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(float_to_int!(
+                    value.into_inner(),
+                    i128,
+                    f32
+                )?)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(float_to_int!(
+                    value.into_inner(),
+                    u128,
+                    f32
+                )?)),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
                     to: to.clone(),
@@ -1196,6 +1381,9 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::from_str(
+                    &value.to_string(),
+                )?)),
                 LogicalType::Tinyint => {
                     Ok(DataValue::Int8(float_to_int!(value.into_inner(), i8, f64)?))
                 }
@@ -1234,6 +1422,16 @@ impl DataValue {
                     u64,
                     f64
                 )?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(float_to_int!(
+                    value.into_inner(),
+                    i128,
+                    f64
+                )?)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(float_to_int!(
+                    value.into_inner(),
+                    u128,
+                    f64
+                )?)),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
                     to: to.clone(),
@@ -1246,6 +1444,8 @@ impl DataValue {
                 LogicalType::USmallint => Ok(DataValue::UInt16(u16::try_from(value)?)),
                 LogicalType::UInteger => Ok(DataValue::UInt32(u32::try_from(value)?)),
                 LogicalType::UBigint => Ok(DataValue::UInt64(u64::try_from(value)?)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(u128::try_from(value)?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
                 LogicalType::Smallint => Ok(DataValue::Int16(value.into())),
                 LogicalType::Integer => Ok(DataValue::Int32(value.into())),
                 LogicalType::Bigint => Ok(DataValue::Int64(value.into())),
@@ -1263,6 +1463,7 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1275,6 +1476,8 @@ impl DataValue {
                 LogicalType::USmallint => Ok(DataValue::UInt16(u16::try_from(value)?)),
                 LogicalType::UInteger => Ok(DataValue::UInt32(u32::try_from(value)?)),
                 LogicalType::UBigint => Ok(DataValue::UInt64(u64::try_from(value)?)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(u128::try_from(value)?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
                 LogicalType::Tinyint => Ok(DataValue::Int8(i8::try_from(value)?)),
                 LogicalType::Smallint => Ok(DataValue::Int16(value)),
                 LogicalType::Integer => Ok(DataValue::Int32(value.into())),
@@ -1293,6 +1496,7 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1305,6 +1509,8 @@ impl DataValue {
                 LogicalType::USmallint => Ok(DataValue::UInt16(u16::try_from(value)?)),
                 LogicalType::UInteger => Ok(DataValue::UInt32(u32::try_from(value)?)),
                 LogicalType::UBigint => Ok(DataValue::UInt64(u64::try_from(value)?)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(u128::try_from(value)?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
                 LogicalType::Tinyint => Ok(DataValue::Int8(i8::try_from(value)?)),
                 LogicalType::Smallint => Ok(DataValue::Int16(i16::try_from(value)?)),
                 LogicalType::Integer => Ok(DataValue::Int32(value)),
@@ -1323,6 +1529,7 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1335,6 +1542,8 @@ impl DataValue {
                 LogicalType::USmallint => Ok(DataValue::UInt16(u16::try_from(value)?)),
                 LogicalType::UInteger => Ok(DataValue::UInt32(u32::try_from(value)?)),
                 LogicalType::UBigint => Ok(DataValue::UInt64(u64::try_from(value)?)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(u128::try_from(value)?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
                 LogicalType::Tinyint => Ok(DataValue::Int8(i8::try_from(value)?)),
                 LogicalType::Smallint => Ok(DataValue::Int16(i16::try_from(value)?)),
                 LogicalType::Integer => Ok(DataValue::Int32(i32::try_from(value)?)),
@@ -1353,6 +1562,7 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1369,6 +1579,8 @@ impl DataValue {
                 LogicalType::UInteger => Ok(DataValue::UInt32(value.into())),
                 LogicalType::Bigint => Ok(DataValue::Int64(value.into())),
                 LogicalType::UBigint => Ok(DataValue::UInt64(value.into())),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(value.into())),
                 LogicalType::Float => Ok(DataValue::Float32(value.into())),
                 LogicalType::Double => Ok(DataValue::Float64(value.into())),
                 LogicalType::Char(len, unit) => {
@@ -1383,6 +1595,7 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1399,6 +1612,8 @@ impl DataValue {
                 LogicalType::UInteger => Ok(DataValue::UInt32(value.into())),
                 LogicalType::Bigint => Ok(DataValue::Int64(value.into())),
                 LogicalType::UBigint => Ok(DataValue::UInt64(value.into())),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(value.into())),
                 LogicalType::Float => Ok(DataValue::Float32(value.into())),
                 LogicalType::Double => Ok(DataValue::Float64(value.into())),
                 LogicalType::Char(len, unit) => {
@@ -1413,6 +1628,7 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1429,6 +1645,8 @@ impl DataValue {
                 LogicalType::UInteger => Ok(DataValue::UInt32(value)),
                 LogicalType::Bigint => Ok(DataValue::Int64(value.into())),
                 LogicalType::UBigint => Ok(DataValue::UInt64(value.into())),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(value.into())),
                 LogicalType::Float => Ok(DataValue::Float32(OrderedFloat(value as f32))),
                 LogicalType::Double => Ok(DataValue::Float64(OrderedFloat(value.into()))),
                 LogicalType::Char(len, unit) => {
@@ -1443,6 +1661,7 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1459,6 +1678,8 @@ impl DataValue {
                 LogicalType::UInteger => Ok(DataValue::UInt32(u32::try_from(value)?)),
                 LogicalType::Bigint => Ok(DataValue::Int64(i64::try_from(value)?)),
                 LogicalType::UBigint => Ok(DataValue::UInt64(value)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.into())),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(value.into())),
                 LogicalType::Float => Ok(DataValue::Float32(OrderedFloat(value as f32))),
                 LogicalType::Double => Ok(DataValue::Float64(OrderedFloat(value as f64))),
                 LogicalType::Char(len, unit) => {
@@ -1473,6 +1694,73 @@ impl DataValue {
 
                     Ok(DataValue::Decimal(decimal))
                 }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
+                LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
+                _ => Err(DatabaseError::CastFail {
+                    from: self.logical_type(),
+                    to: to.clone(),
+                }),
+            },
+            DataValue::Int128(value) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Tinyint => Ok(DataValue::Int8(i8::try_from(value)?)),
+                LogicalType::UTinyint => Ok(DataValue::UInt8(u8::try_from(value)?)),
+                LogicalType::Smallint => Ok(DataValue::Int16(i16::try_from(value)?)),
+                LogicalType::USmallint => Ok(DataValue::UInt16(u16::try_from(value)?)),
+                LogicalType::Integer => Ok(DataValue::Int32(i32::try_from(value)?)),
+                LogicalType::UInteger => Ok(DataValue::UInt32(u32::try_from(value)?)),
+                LogicalType::Bigint => Ok(DataValue::Int64(i64::try_from(value)?)),
+                LogicalType::UBigint => Ok(DataValue::UInt64(u64::try_from(value)?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(value)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(u128::try_from(value)?)),
+                LogicalType::Float => Ok(DataValue::Float32(OrderedFloat(value as f32))),
+                LogicalType::Double => Ok(DataValue::Float64(OrderedFloat(value as f64))),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
+                }
+                LogicalType::Decimal(_, option) => {
+                    let mut decimal = Decimal::from_i128(value).ok_or(DatabaseError::OverFlow)?;
+                    Self::decimal_round_i(option, &mut decimal);
+
+                    Ok(DataValue::Decimal(decimal))
+                }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
+                LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
+                _ => Err(DatabaseError::CastFail {
+                    from: self.logical_type(),
+                    to: to.clone(),
+                }),
+            },
+            DataValue::UInt128(value) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Tinyint => Ok(DataValue::Int8(i8::try_from(value)?)),
+                LogicalType::UTinyint => Ok(DataValue::UInt8(u8::try_from(value)?)),
+                LogicalType::Smallint => Ok(DataValue::Int16(i16::try_from(value)?)),
+                LogicalType::USmallint => Ok(DataValue::UInt16(u16::try_from(value)?)),
+                LogicalType::Integer => Ok(DataValue::Int32(i32::try_from(value)?)),
+                LogicalType::UInteger => Ok(DataValue::UInt32(u32::try_from(value)?)),
+                LogicalType::Bigint => Ok(DataValue::Int64(i64::try_from(value)?)),
+                LogicalType::UBigint => Ok(DataValue::UInt64(u64::try_from(value)?)),
+                LogicalType::Hugeint => Ok(DataValue::Int128(i128::try_from(value)?)),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(value)),
+                LogicalType::Float => Ok(DataValue::Float32(OrderedFloat(value as f32))),
+                LogicalType::Double => Ok(DataValue::Float64(OrderedFloat(value as f64))),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
+                }
+                LogicalType::Decimal(_, option) => {
+                    let mut decimal = Decimal::from_u128(value).ok_or(DatabaseError::OverFlow)?;
+                    Self::decimal_round_i(option, &mut decimal);
+
+                    Ok(DataValue::Decimal(decimal))
+                }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(value.into(), 0))),
                 LogicalType::Boolean => numeric_to_boolean!(value, self.logical_type()),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1482,16 +1770,7 @@ impl DataValue {
             DataValue::Utf8 { ref value, .. } => match to {
                 LogicalType::SqlNull => Ok(DataValue::Null),
                 LogicalType::Boolean => Ok(DataValue::Boolean(bool::from_str(value)?)),
-                LogicalType::Tinyint => Ok(DataValue::Int8(i8::from_str(value)?)),
-                LogicalType::UTinyint => Ok(DataValue::UInt8(u8::from_str(value)?)),
-                LogicalType::Smallint => Ok(DataValue::Int16(i16::from_str(value)?)),
-                LogicalType::USmallint => Ok(DataValue::UInt16(u16::from_str(value)?)),
-                LogicalType::Integer => Ok(DataValue::Int32(i32::from_str(value)?)),
-                LogicalType::UInteger => Ok(DataValue::UInt32(u32::from_str(value)?)),
-                LogicalType::Bigint => Ok(DataValue::Int64(i64::from_str(value)?)),
-                LogicalType::UBigint => Ok(DataValue::UInt64(u64::from_str(value)?)),
-                LogicalType::Float => Ok(DataValue::Float32(OrderedFloat(f32::from_str(value)?))),
-                LogicalType::Double => Ok(DataValue::Float64(OrderedFloat(f64::from_str(value)?))),
+                to if to.is_numeric() => Self::parse_numeric(value, to),
                 LogicalType::Char(len, unit) => {
                     varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
                 }
@@ -1500,8 +1779,11 @@ impl DataValue {
                 }
                 LogicalType::Date => {
                     let value = NaiveDate::parse_from_str(value, DATE_FMT)
-                        .map(|date| date.num_days_from_ce())
-                        .unwrap();
+                        .or_else(|_| {
+                            NaiveDateTime::parse_from_str(value, DATE_TIME_FMT)
+                                .map(|date_time| date_time.date())
+                        })
+                        .map(|date| date.num_days_from_ce())?;
                     Ok(DataValue::Date32(value))
                 }
                 LogicalType::DateTime => {
@@ -1565,7 +1847,13 @@ impl DataValue {
                             *zone,
                         ));
                     }
-                    let value = DateTime::parse_from_str(&complete_value, fmt);
+                    let value = if *zone {
+                        DateTime::parse_from_str(&complete_value, fmt)
+                            .map(|date_time| date_time.with_timezone(&Utc))
+                    } else {
+                        NaiveDateTime::parse_from_str(&complete_value, fmt)
+                            .map(|date_time| date_time.and_utc())
+                    };
                     let value = match precision {
                         3 => value.map(|date_time| date_time.timestamp_millis())?,
                         6 => value.map(|date_time| date_time.timestamp_micros())?,
@@ -1586,7 +1874,11 @@ impl DataValue {
                     };
                     Ok(DataValue::Time64(value, precision, *zone))
                 }
-                LogicalType::Decimal(_, _) => Ok(DataValue::Decimal(Decimal::from_str(value)?)),
+                LogicalType::Vector(dimension) => {
+                    Ok(DataValue::Vector(vector::parse(value, *dimension)?))
+                }
+                LogicalType::Point => spatial::parse_point(value),
+                LogicalType::Box => spatial::parse_box(value),
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
                     to: to.clone(),
@@ -1694,6 +1986,12 @@ impl DataValue {
                         Some(precision) => *precision,
                         None => 0,
                     };
+                    let value = Self::timestamp_with_precision(value, 0, precision).ok_or(
+                        DatabaseError::CastFail {
+                            from: self.logical_type(),
+                            to: to.clone(),
+                        },
+                    )?;
                     Ok(DataValue::Time64(value, precision, *zone))
                 }
                 _ => Err(DatabaseError::CastFail {
@@ -1726,7 +2024,12 @@ impl DataValue {
                     )
                 }
                 LogicalType::Time(to_precision) => {
-                    Ok(DataValue::Time32(value, to_precision.unwrap_or(0)))
+                    let to_precision = to_precision.unwrap_or(0);
+
+                    Ok(DataValue::Time32(
+                        Self::time_with_precision(value, precision, to_precision),
+                        to_precision,
+                    ))
                 }
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1798,7 +2101,14 @@ impl DataValue {
                     Ok(DataValue::Time32(Self::pack(value, nano, p), p))
                 }
                 LogicalType::TimeStamp(to_precision, zone) => {
-                    Ok(DataValue::Time64(value, to_precision.unwrap_or(0), *zone))
+                    let to_precision = to_precision.unwrap_or(0);
+                    let value = Self::timestamp_with_precision(value, precision, to_precision)
+                        .ok_or(DatabaseError::CastFail {
+                            from: self.logical_type(),
+                            to: to.clone(),
+                        })?;
+
+                    Ok(DataValue::Time64(value, to_precision, *zone))
                 }
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
@@ -1819,7 +2129,16 @@ impl DataValue {
                         to: to.clone(),
                     },
                 )?))),
-                LogicalType::Decimal(_, _) => Ok(DataValue::Decimal(value)),
+                LogicalType::Decimal(_, option) => {
+                    let mut decimal = value;
+                    Self::decimal_round_f(option, &mut decimal);
+
+                    Ok(DataValue::Decimal(decimal))
+                }
+                LogicalType::Numeric => Ok(DataValue::Numeric(BigDecimal::new(
+                    BigInt::from(value.mantissa()),
+                    value.scale() as i64,
+                ))),
                 LogicalType::Char(len, unit) => {
                     varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
                 }
@@ -1834,6 +2153,77 @@ impl DataValue {
                 LogicalType::USmallint => Ok(DataValue::UInt16(decimal_to_int!(value, u16))),
                 LogicalType::UInteger => Ok(DataValue::UInt32(decimal_to_int!(value, u32))),
                 LogicalType::UBigint => Ok(DataValue::UInt64(decimal_to_int!(value, u64))),
+                // a decimal always fits in an i128, the negative ones are cast to 0 as unsigned
+                LogicalType::Hugeint => Ok(DataValue::Int128(value.to_i128().unwrap_or_default())),
+                LogicalType::UHugeint => {
+                    Ok(DataValue::UInt128(value.to_u128().unwrap_or_default()))
+                }
+                _ => Err(DatabaseError::CastFail {
+                    from: self.logical_type(),
+                    to: to.clone(),
+                }),
+            },
+            DataValue::Numeric(ref value) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Tinyint => Ok(DataValue::Int8(
+                    value.to_i8().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::UTinyint => Ok(DataValue::UInt8(
+                    value.to_u8().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::Smallint => Ok(DataValue::Int16(
+                    value.to_i16().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::USmallint => Ok(DataValue::UInt16(
+                    value.to_u16().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::Integer => Ok(DataValue::Int32(
+                    value.to_i32().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::UInteger => Ok(DataValue::UInt32(
+                    value.to_u32().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::Bigint => Ok(DataValue::Int64(
+                    value.to_i64().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::UBigint => Ok(DataValue::UInt64(
+                    value.to_u64().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::Hugeint => Ok(DataValue::Int128(
+                    value.to_i128().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::UHugeint => Ok(DataValue::UInt128(
+                    value.to_u128().ok_or(DatabaseError::OverFlow)?,
+                )),
+                LogicalType::Float => Ok(DataValue::Float32(OrderedFloat(
+                    value.to_f32().ok_or(DatabaseError::OverFlow)?,
+                ))),
+                LogicalType::Double => Ok(DataValue::Float64(OrderedFloat(
+                    value.to_f64().ok_or(DatabaseError::OverFlow)?,
+                ))),
+                LogicalType::Decimal(_, option) => {
+                    let mut decimal = Self::numeric_to_decimal(value)?;
+                    Self::decimal_round_f(option, &mut decimal);
+
+                    Ok(DataValue::Decimal(decimal))
+                }
+                LogicalType::Numeric => Ok(self),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(
+                        Self::numeric_format(value),
+                        Some(len),
+                        Utf8Type::Fixed(*len),
+                        *unit
+                    )
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(
+                        Self::numeric_format(value),
+                        len,
+                        Utf8Type::Variable(*len),
+                        *unit
+                    )
+                }
                 _ => Err(DatabaseError::CastFail {
                     from: self.logical_type(),
                     to: to.clone(),
@@ -1853,6 +2243,110 @@ impl DataValue {
                     to: to.clone(),
                 }),
             },
+            DataValue::Vector(ref values) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Vector(dimension) if *dimension as usize == values.len() => Ok(self),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(self, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(self, len, Utf8Type::Variable(*len), *unit)
+                }
+                _ => Err(DatabaseError::CastFail {
+                    from: self.logical_type(),
+                    to: to.clone(),
+                }),
+            },
+            DataValue::Point(x, y) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Point => Ok(self),
+                LogicalType::Box => Ok(DataValue::Box(x, y, x, y)),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(self, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(self, len, Utf8Type::Variable(*len), *unit)
+                }
+                _ => Err(DatabaseError::CastFail {
+                    from: self.logical_type(),
+                    to: to.clone(),
+                }),
+            },
+            DataValue::Box(..) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Box => Ok(self),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(self, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(self, len, Utf8Type::Variable(*len), *unit)
+                }
+                _ => Err(DatabaseError::CastFail {
+                    from: self.logical_type(),
+                    to: to.clone(),
+                }),
+            },
+            DataValue::Array(values) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Array(element) => Ok(DataValue::Array(
+                    values
+                        .into_iter()
+                        .map(|value| value.cast(element))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(
+                        DataValue::Array(values),
+                        Some(len),
+                        Utf8Type::Fixed(*len),
+                        *unit
+                    )
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(
+                        DataValue::Array(values),
+                        len,
+                        Utf8Type::Variable(*len),
+                        *unit
+                    )
+                }
+                _ => Err(DatabaseError::CastFail {
+                    from: DataValue::Array(values).logical_type(),
+                    to: to.clone(),
+                }),
+            },
+            DataValue::Struct(fields) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Struct(to_fields) if to_fields.len() == fields.len() => {
+                    Ok(DataValue::Struct(
+                        fields
+                            .into_iter()
+                            .zip(to_fields)
+                            .map(|((_, value), (name, ty))| Ok((name.clone(), value.cast(ty)?)))
+                            .collect::<Result<Vec<_>, DatabaseError>>()?,
+                    ))
+                }
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(
+                        DataValue::Struct(fields),
+                        Some(len),
+                        Utf8Type::Fixed(*len),
+                        *unit
+                    )
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(
+                        DataValue::Struct(fields),
+                        len,
+                        Utf8Type::Variable(*len),
+                        *unit
+                    )
+                }
+                _ => Err(DatabaseError::CastFail {
+                    from: DataValue::Struct(fields).logical_type(),
+                    to: to.clone(),
+                }),
+            },
         }?;
         value.check_len(to)?;
         Ok(value)
@@ -1870,18 +2364,12 @@ impl DataValue {
         if let (DataValue::Utf8 { value: v1, .. }, DataValue::Utf8 { value: v2, .. }) =
             (self, target)
         {
-            let min_len = cmp::min(v1.len(), v2.len());
-
-            let mut v1_iter = v1.get(0..min_len).unwrap().chars();
-            let mut v2_iter = v2.get(0..min_len).unwrap().chars();
-
-            for i in 0..min_len {
-                if v1_iter.next() != v2_iter.next() {
-                    return Some(i);
-                }
-            }
-
-            return Some(min_len);
+            return Some(
+                v1.bytes()
+                    .zip(v2.bytes())
+                    .take_while(|(b1, b2)| b1 == b2)
+                    .count(),
+            );
         }
         Some(0)
     }
@@ -1895,6 +2383,15 @@ impl DataValue {
         }
     }
 
+    /// Truncated to the 28 fractional digits a decimal holds at most.
+    fn numeric_to_decimal(numeric: &BigDecimal) -> Result<Decimal, DatabaseError> {
+        let scale = numeric.as_bigint_and_exponent().1.clamp(0, 28);
+        let (mantissa, scale) = numeric.with_scale(scale).into_bigint_and_exponent();
+        let mantissa = mantissa.to_i128().ok_or(DatabaseError::OverFlow)?;
+
+        Ok(Decimal::try_from_i128_with_scale(mantissa, scale as u32)?)
+    }
+
     fn decimal_round_i(option: &Option<u8>, decimal: &mut Decimal) {
         if let Some(scale) = option {
             let new_decimal = decimal.trunc_with_scale(*scale as u32);
@@ -1920,25 +2417,66 @@ impl DataValue {
         DateTime::from_timestamp(v, 0).map(|date_time| date_time.format(DATE_TIME_FMT))
     }
 
-    fn time_format<'a>(v: u32, precision: u64) -> Option<DelayedFormat<StrftimeItems<'a>>> {
+    /// With as many fractional digits as the precision.
+    fn time_format(v: u32, precision: u64) -> Option<String> {
         let (v, n) = Self::unpack(v, precision);
-        NaiveTime::from_num_seconds_from_midnight_opt(v, n)
-            .map(|time| time.format(TIME_FMT_WITHOUT_ZONE))
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(v, 0)?.format(TIME_FMT);
+
+        Some(match precision {
+            0 => time.to_string(),
+            _ => format!(
+                "{time}.{:0width$}",
+                n / 10_u32.pow(9 - precision as u32),
+                width = precision as usize
+            ),
+        })
     }
 
+    /// With as many fractional digits as the precision.
     fn time_stamp_format<'a>(
         v: i64,
         precision: u64,
         _zone: bool,
     ) -> Option<DelayedFormat<StrftimeItems<'a>>> {
-        Self::from_timestamp_precision(v, precision)
-            .map(|date_time| date_time.format(TIME_STAMP_FMT_WITHOUT_ZONE))
+        let fmt = match precision {
+            3 => "%Y-%m-%d %H:%M:%S%.3f",
+            6 => "%Y-%m-%d %H:%M:%S%.6f",
+            9 => "%Y-%m-%d %H:%M:%S%.9f",
+            _ => DATE_TIME_FMT,
+        };
+        Self::from_timestamp_precision(v, precision).map(|date_time| date_time.format(fmt))
     }
 
     fn decimal_format(v: &Decimal) -> String {
         v.to_string()
     }
 
+    /// Without the exponent notation `BigDecimal` switches to for very large or small numbers.
+    fn numeric_format(v: &BigDecimal) -> String {
+        let (mantissa, scale) = v.as_bigint_and_exponent();
+        let sign = if mantissa.sign() == Sign::Minus {
+            "-"
+        } else {
+            ""
+        };
+        let digits = mantissa.magnitude().to_string();
+
+        if scale <= 0 {
+            format!(
+                "{}{}{}",
+                sign,
+                digits,
+                "0".repeat(scale.unsigned_abs() as usize)
+            )
+        } else {
+            let scale = scale as usize;
+            let digits = format!("{:0>width$}", digits, width = scale + 1);
+            let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+            format!("{}{}.{}", sign, integer, fraction)
+        }
+    }
+
     pub fn timestamp_precision(v: DateTime<Utc>, precision: u64) -> i64 {
         match precision {
             3 => v.timestamp_millis(),
@@ -1949,6 +2487,25 @@ impl DataValue {
         }
     }
 
+    /// The packed time `v` of `precision` at `to_precision`, the fraction is truncated.
+    pub fn time_with_precision(v: u32, precision: u64, to_precision: u64) -> u32 {
+        if precision == to_precision {
+            return v;
+        }
+        let (v, n) = Self::unpack(v, precision);
+        Self::pack(v, n, to_precision)
+    }
+
+    /// The timestamp `v` of `precision` at `to_precision`, the fraction is truncated,
+    /// `None` if it overflows.
+    pub fn timestamp_with_precision(v: i64, precision: u64, to_precision: u64) -> Option<i64> {
+        match precision.cmp(&to_precision) {
+            Ordering::Equal => Some(v),
+            Ordering::Less => v.checked_mul(10_i64.pow((to_precision - precision) as u32)),
+            Ordering::Greater => Some(v.div_euclid(10_i64.pow((precision - to_precision) as u32))),
+        }
+    }
+
     pub fn from_timestamp_precision(v: i64, precision: u64) -> Option<DateTime<chrono::Utc>> {
         match precision {
             0 => DateTime::from_timestamp(v, 0),
@@ -1993,6 +2550,8 @@ impl_scalar!(u8, UInt8);
 impl_scalar!(u16, UInt16);
 impl_scalar!(u32, UInt32);
 impl_scalar!(u64, UInt64);
+impl_scalar!(i128, Int128);
+impl_scalar!(u128, UInt128);
 impl_scalar!(Decimal, Decimal);
 
 impl From<f32> for DataValue {
@@ -2116,8 +2675,18 @@ impl TryFrom<&sqlparser::ast::Value> for DataValue {
                     v.into()
                 } else if let Ok(v) = n.parse::<i64>() {
                     v.into()
-                } else if let Ok(v) = n.parse::<f64>() {
+                } else if let Ok(v) = n.parse::<i128>() {
                     v.into()
+                } else if let Ok(v) = n.parse::<f64>() {
+                    // the literals a double can't hold exactly keep their precision as numerics
+                    match BigDecimal::from_str(n) {
+                        Ok(exact)
+                            if BigDecimal::from_str(&v.to_string()).ok() != Some(exact.clone()) =>
+                        {
+                            DataValue::Numeric(exact)
+                        }
+                        _ => v.into(),
+                    }
                 } else if let Ok(v) = n.parse::<f32>() {
                     v.into()
                 } else {
@@ -2160,6 +2729,8 @@ impl fmt::Display for DataValue {
             DataValue::UInt16(e) => write!(f, "{}", e)?,
             DataValue::UInt32(e) => write!(f, "{}", e)?,
             DataValue::UInt64(e) => write!(f, "{}", e)?,
+            DataValue::Int128(e) => write!(f, "{}", e)?,
+            DataValue::UInt128(e) => write!(f, "{}", e)?,
             DataValue::Utf8 { value: e, .. } => write!(f, "{}", e)?,
             DataValue::Null => write!(f, "null")?,
             DataValue::Date32(e) => write!(f, "{}", DataValue::date_format(*e).unwrap())?,
@@ -2173,6 +2744,7 @@ impl fmt::Display for DataValue {
                 DataValue::time_stamp_format(*e, *precision, *zone).unwrap()
             )?,
             DataValue::Decimal(e) => write!(f, "{}", DataValue::decimal_format(e))?,
+            DataValue::Numeric(e) => write!(f, "{}", DataValue::numeric_format(e))?,
             DataValue::Tuple(values, ..) => {
                 write!(f, "(")?;
                 let len = values.len();
@@ -2185,6 +2757,47 @@ impl fmt::Display for DataValue {
                 }
                 write!(f, ")")?;
             }
+            DataValue::Vector(values) => {
+                write!(f, "[")?;
+                let len = values.len();
+
+                for (i, value) in values.iter().enumerate() {
+                    write!(f, "{}", value)?;
+                    if len != i + 1 {
+                        write!(f, ",")?;
+                    }
+                }
+                write!(f, "]")?;
+            }
+            DataValue::Point(x, y) => write!(f, "({},{})", x, y)?,
+            DataValue::Box(x_min, y_min, x_max, y_max) => {
+                write!(f, "({},{}),({},{})", x_min, y_min, x_max, y_max)?
+            }
+            DataValue::Array(values) => {
+                write!(f, "[")?;
+                let len = values.len();
+
+                for (i, value) in values.iter().enumerate() {
+                    value.fmt(f)?;
+                    if len != i + 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "]")?;
+            }
+            DataValue::Struct(fields) => {
+                write!(f, "{{")?;
+                let len = fields.len();
+
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    write!(f, "{}: ", name)?;
+                    value.fmt(f)?;
+                    if len != i + 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "}}")?;
+            }
         };
         Ok(())
     }
@@ -2204,6 +2817,8 @@ impl fmt::Debug for DataValue {
             DataValue::UInt16(_) => write!(f, "UInt16({})", self),
             DataValue::UInt32(_) => write!(f, "UInt32({})", self),
             DataValue::UInt64(_) => write!(f, "UInt64({})", self),
+            DataValue::Int128(_) => write!(f, "Int128({})", self),
+            DataValue::UInt128(_) => write!(f, "UInt128({})", self),
             DataValue::Utf8 { .. } => write!(f, "Utf8(\"{}\")", self),
             DataValue::Null => write!(f, "null"),
             DataValue::Date32(_) => write!(f, "Date32({})", self),
@@ -2211,6 +2826,7 @@ impl fmt::Debug for DataValue {
             DataValue::Time32(..) => write!(f, "Time32({})", self),
             DataValue::Time64(..) => write!(f, "Time64({})", self),
             DataValue::Decimal(_) => write!(f, "Decimal({})", self),
+            DataValue::Numeric(_) => write!(f, "Numeric({})", self),
             DataValue::Tuple(..) => {
                 write!(f, "Tuple({}", self)?;
                 if matches!(self, DataValue::Tuple(_, true)) {
@@ -2218,6 +2834,11 @@ impl fmt::Debug for DataValue {
                 }
                 write!(f, ")")
             }
+            DataValue::Vector(_) => write!(f, "Vector({})", self),
+            DataValue::Point(..) => write!(f, "Point{}", self),
+            DataValue::Box(..) => write!(f, "Box({})", self),
+            DataValue::Array(_) => write!(f, "Array({})", self),
+            DataValue::Struct(_) => write!(f, "Struct({})", self),
         }
     }
 }
@@ -2226,10 +2847,148 @@ impl fmt::Debug for DataValue {
 mod test {
     use crate::errors::DatabaseError;
     use crate::storage::table_codec::BumpBytes;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
+    use crate::types::LogicalType;
     use bumpalo::Bump;
     use ordered_float::OrderedFloat;
     use rust_decimal::Decimal;
+    use sqlparser::ast::CharLengthUnits;
+
+    #[test]
+    fn test_cast_lenient() -> Result<(), DatabaseError> {
+        let utf8 = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        assert!(matches!(
+            utf8("12abc").cast(&LogicalType::Integer),
+            Err(DatabaseError::CastValueFail { value, .. }) if value == "12abc"
+        ));
+        assert_eq!(
+            utf8("12abc").cast_lenient(&LogicalType::Integer)?,
+            DataValue::Int32(12)
+        );
+        assert_eq!(
+            utf8(" -12.5e1x").cast_lenient(&LogicalType::Integer)?,
+            DataValue::Int32(-12)
+        );
+        assert_eq!(
+            utf8(" -12.5e1x").cast_lenient(&LogicalType::Double)?,
+            DataValue::Float64(OrderedFloat(-125.0))
+        );
+        assert_eq!(
+            utf8(".5.5").cast_lenient(&LogicalType::Decimal(None, None))?,
+            DataValue::Decimal(Decimal::new(5, 1))
+        );
+        assert_eq!(
+            utf8("12e").cast_lenient(&LogicalType::Double)?,
+            DataValue::Float64(OrderedFloat(12.0))
+        );
+        assert_eq!(
+            utf8("abc").cast_lenient(&LogicalType::Integer)?,
+            DataValue::Null
+        );
+        assert_eq!(
+            utf8("-").cast_lenient(&LogicalType::Double)?,
+            DataValue::Null
+        );
+        assert_eq!(
+            utf8("300").cast_lenient(&LogicalType::Tinyint)?,
+            DataValue::Null
+        );
+        assert_eq!(
+            utf8("12").cast_lenient(&LogicalType::Varchar(None, CharLengthUnits::Characters))?,
+            utf8("12")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_precision() -> Result<(), DatabaseError> {
+        let utf8 = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        // parsing and casting truncate the fraction to the precision
+        let time = utf8("15:12:55.123456").cast(&LogicalType::Time(Some(4)))?;
+        assert_eq!(time.logical_type(), LogicalType::Time(Some(4)));
+        assert_eq!(time.to_string(), "15:12:55.1234");
+        assert_eq!(
+            time.clone().cast(&LogicalType::Time(Some(1)))?.to_string(),
+            "15:12:55.1"
+        );
+        assert_eq!(
+            time.clone().cast(&LogicalType::Time(None))?.to_string(),
+            "15:12:55"
+        );
+        assert_eq!(
+            utf8("15:12:55.1")
+                .cast(&LogicalType::Time(Some(3)))?
+                .to_string(),
+            "15:12:55.100"
+        );
+        assert_eq!(
+            time.cast(&LogicalType::Time(Some(2)))?,
+            utf8("15:12:55.12").cast(&LogicalType::Time(Some(2)))?
+        );
+
+        let timestamp =
+            utf8("2025-01-05 15:12:55.123456789").cast(&LogicalType::TimeStamp(Some(9), false))?;
+        assert_eq!(
+            timestamp.logical_type(),
+            LogicalType::TimeStamp(Some(9), false)
+        );
+        assert_eq!(
+            timestamp
+                .clone()
+                .cast(&LogicalType::TimeStamp(Some(3), false))?,
+            DataValue::Time64(1736089975123, 3, false)
+        );
+        assert_eq!(
+            timestamp
+                .clone()
+                .cast(&LogicalType::TimeStamp(None, false))?
+                .to_string(),
+            "2025-01-05 15:12:55"
+        );
+        assert_eq!(
+            DataValue::Time64(1736089975123, 3, false)
+                .cast(&LogicalType::TimeStamp(Some(6), false))?
+                .to_string(),
+            "2025-01-05 15:12:55.123000"
+        );
+        assert_eq!(
+            DataValue::Date64(1736089975).cast(&LogicalType::TimeStamp(Some(3), false))?,
+            DataValue::Time64(1736089975000, 3, false)
+        );
+        // before the epoch the fraction is truncated towards the past
+        assert_eq!(
+            DataValue::Time64(-1500, 3, false).cast(&LogicalType::TimeStamp(None, false))?,
+            DataValue::Time64(-2, 0, false)
+        );
+        assert!(DataValue::Time64(i64::MAX, 0, false)
+            .cast(&LogicalType::TimeStamp(Some(9), false))
+            .is_err());
+
+        assert_eq!(
+            LogicalType::max_logical_type(&LogicalType::Time(Some(1)), &LogicalType::Time(None))?,
+            LogicalType::Time(Some(1))
+        );
+        assert_eq!(
+            LogicalType::max_logical_type(
+                &LogicalType::TimeStamp(Some(3), false),
+                &LogicalType::TimeStamp(Some(6), true)
+            )?,
+            LogicalType::TimeStamp(Some(6), true)
+        );
+
+        Ok(())
+    }
 
     #[test]
     fn test_mem_comparable_null() -> Result<(), DatabaseError> {
@@ -2309,6 +3068,19 @@ mod test {
         assert!(key_i64_1 < key_i64_2);
         assert!(key_i64_2 < key_i64_3);
 
+        let mut key_i128_1 = BumpBytes::new_in(&arena);
+        let mut key_i128_2 = BumpBytes::new_in(&arena);
+        let mut key_i128_3 = BumpBytes::new_in(&arena);
+
+        DataValue::Int128(i128::MIN).memcomparable_encode(&mut key_i128_1)?;
+        DataValue::Int128(-1_i128).memcomparable_encode(&mut key_i128_2)?;
+        DataValue::Int128(i128::MAX).memcomparable_encode(&mut key_i128_3)?;
+
+        println!("{:?} < {:?}", key_i128_1, key_i128_2);
+        println!("{:?} < {:?}", key_i128_2, key_i128_3);
+        assert!(key_i128_1 < key_i128_2);
+        assert!(key_i128_2 < key_i128_3);
+
         Ok(())
     }
 
@@ -2422,6 +3194,42 @@ mod test {
         assert!(key_tuple_2 < key_tuple_3);
         assert!(key_tuple_3 < key_tuple_1);
 
+        // the entries of the values of an upper bound, followed by their tuple ids, are below it
+        let mut key_entry = BumpBytes::new_in(&arena);
+        DataValue::Tuple(
+            vec![DataValue::Int8(0), DataValue::Int8(0), DataValue::Int8(2)],
+            false,
+        )
+        .memcomparable_encode(&mut key_entry)?;
+        DataValue::Int32(i32::MAX).memcomparable_encode(&mut key_entry)?;
+        assert!(key_tuple_2 < key_entry);
+        assert!(key_entry < key_tuple_3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mem_comparable_array() -> Result<(), DatabaseError> {
+        let arena = Bump::new();
+        let mut key_array_1 = BumpBytes::new_in(&arena);
+        let mut key_array_2 = BumpBytes::new_in(&arena);
+        let mut key_array_3 = BumpBytes::new_in(&arena);
+        let mut key_array_4 = BumpBytes::new_in(&arena);
+
+        DataValue::Array(vec![DataValue::Int8(0)]).memcomparable_encode(&mut key_array_1)?;
+        DataValue::Array(vec![DataValue::Int8(0), DataValue::Null])
+            .memcomparable_encode(&mut key_array_2)?;
+        DataValue::Array(vec![DataValue::Int8(0), DataValue::Int8(-1)])
+            .memcomparable_encode(&mut key_array_3)?;
+        DataValue::Array(vec![DataValue::Int8(1)]).memcomparable_encode(&mut key_array_4)?;
+
+        println!("{:?} < {:?}", key_array_1, key_array_2);
+        println!("{:?} < {:?}", key_array_2, key_array_3);
+        println!("{:?} < {:?}", key_array_3, key_array_4);
+        assert!(key_array_1 < key_array_2);
+        assert!(key_array_2 < key_array_3);
+        assert!(key_array_3 < key_array_4);
+
         Ok(())
     }
 }
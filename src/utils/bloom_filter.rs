@@ -0,0 +1,77 @@
+use ahash::RandomState;
+use fixedbitset::FixedBitSet;
+use std::hash::Hash;
+
+const DEFAULT_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// A fixed size Bloom filter using double hashing: `h1 + i * h2` for the i-th probe.
+pub(crate) struct BloomFilter {
+    bits: FixedBitSet,
+    num_hashes: u32,
+    hash_builder: RandomState,
+}
+
+impl BloomFilter {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self::with_false_positive_rate(capacity, DEFAULT_FALSE_POSITIVE_RATE)
+    }
+
+    pub(crate) fn with_false_positive_rate(capacity: usize, false_positive_rate: f64) -> Self {
+        let capacity = capacity.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-capacity * false_positive_rate.ln() / (ln2 * ln2)).ceil() as usize;
+        let num_hashes = ((num_bits as f64 / capacity) * ln2).round().max(1.0) as u32;
+
+        BloomFilter {
+            bits: FixedBitSet::with_capacity(num_bits.max(64)),
+            num_hashes,
+            hash_builder: RandomState::new(),
+        }
+    }
+
+    fn positions(hash: u64, num_hashes: u32, num_bits: usize) -> impl Iterator<Item = usize> {
+        let (h1, h2) = (hash as u32 as u64, hash >> 32);
+
+        (0..num_hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits as u64) as usize)
+    }
+
+    pub(crate) fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
+        let hash = self.hash_builder.hash_one(item);
+
+        for position in Self::positions(hash, self.num_hashes, self.bits.len()) {
+            self.bits.insert(position);
+        }
+    }
+
+    /// `false` means the item was definitely never inserted.
+    pub(crate) fn contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        let hash = self.hash_builder.hash_one(item);
+
+        Self::positions(hash, self.num_hashes, self.bits.len())
+            .all(|position| self.bits.contains(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::bloom_filter::BloomFilter;
+
+    #[test]
+    fn test_bloom_filter() {
+        let mut filter = BloomFilter::with_capacity(1000);
+
+        for i in 0..1000_u64 {
+            filter.insert(&i);
+        }
+        for i in 0..1000_u64 {
+            assert!(filter.contains(&i));
+        }
+        let false_positives = (1000..11000_u64).filter(|i| filter.contains(i)).count();
+        assert!(
+            false_positives < 500,
+            "false positives: {}",
+            false_positives
+        );
+    }
+}
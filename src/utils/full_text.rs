@@ -0,0 +1,74 @@
+use crate::types::value::{DataValue, Utf8Type};
+use itertools::Itertools;
+use sqlparser::ast::CharLengthUnits;
+
+/// Splits the text into lower case words of letters and digits.
+pub(crate) fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// The distinct tokens of the text kept by a full-text index, nothing for `NULL`.
+pub(crate) fn index_tokens(value: &DataValue) -> Vec<DataValue> {
+    let DataValue::Utf8 { value, .. } = value else {
+        return vec![];
+    };
+    tokenize(value)
+        .sorted()
+        .dedup()
+        .map(|token| DataValue::Utf8 {
+            value: token,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        })
+        .collect_vec()
+}
+
+/// Relevance of the text to the query, the sum over the distinct query terms of their
+/// saturated frequency `tf / (tf + 1)` in the text, `0` when no term occurs.
+pub(crate) fn score(text: &str, query: &str) -> f64 {
+    let terms = tokenize(query).sorted().dedup().collect_vec();
+    let mut frequencies = vec![0usize; terms.len()];
+
+    for token in tokenize(text) {
+        if let Ok(i) = terms.binary_search(&token) {
+            frequencies[i] += 1;
+        }
+    }
+    frequencies
+        .into_iter()
+        .map(|tf| tf as f64 / (tf as f64 + 1.0))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::value::DataValue;
+    use crate::utils::full_text::{index_tokens, score, tokenize};
+    use itertools::Itertools;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Disk full: /var/log, retry#2").collect_vec(),
+            vec!["disk", "full", "var", "log", "retry", "2"]
+        );
+        assert_eq!(
+            index_tokens(&DataValue::from("b a B".to_string()))
+                .iter()
+                .map(DataValue::to_string)
+                .collect_vec(),
+            vec!["a", "b"]
+        );
+        assert!(index_tokens(&DataValue::Null).is_empty());
+    }
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score("disk full", "memory"), 0.0);
+        assert_eq!(score("Disk full", "disk"), 0.5);
+        assert!(score("disk disk full", "disk") > score("disk full", "disk"));
+        assert!(score("disk full", "disk full") > score("disk disk disk", "disk full"));
+    }
+}
@@ -8,6 +8,7 @@ use std::hash::{BuildHasher, Hash, Hasher};
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
 
 // 只读Node操作裸指针
 // https://course.rs/advance/concurrency-with-threads/send-sync.html#:~:text=%E5%AE%89%E5%85%A8%E7%9A%84%E4%BD%BF%E7%94%A8%E3%80%82-,%E4%B8%BA%E8%A3%B8%E6%8C%87%E9%92%88%E5%AE%9E%E7%8E%B0Send,-%E4%B8%8A%E9%9D%A2%E6%88%91%E4%BB%AC%E6%8F%90%E5%88%B0
@@ -45,6 +46,8 @@ unsafe impl<K: Sync, V: Sync, S: Sync> Sync for SharedLruCache<K, V, S> {}
 pub struct SharedLruCache<K, V, S = RandomState> {
     shared_vec: Vec<Mutex<LruCache<K, V>>>,
     hasher: S,
+    hits: AtomicU64,
+    misses: AtomicU64,
 }
 
 struct Node<K, V> {
@@ -123,15 +126,20 @@ impl<K: Hash + Eq + PartialEq, V, S: BuildHasher> SharedLruCache<K, V, S> {
             shared_vec.push(Mutex::new(LruCache::new(shared_cap)?));
         }
 
-        Ok(SharedLruCache { shared_vec, hasher })
+        Ok(SharedLruCache {
+            shared_vec,
+            hasher,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
     }
 
     #[inline]
     pub fn get(&self, key: &K) -> Option<&V> {
-        self.shard(key)
-            .lock()
-            .get_node(key)
-            .map(|node| unsafe { &node.as_ref().value })
+        let node = self.shard(key).lock().get_node(key);
+        self.record(node.is_some());
+
+        node.map(|node| unsafe { &node.as_ref().value })
     }
 
     #[inline]
@@ -144,6 +152,13 @@ impl<K: Hash + Eq + PartialEq, V, S: BuildHasher> SharedLruCache<K, V, S> {
         self.shard(key).lock().remove(key)
     }
 
+    #[inline]
+    pub fn clear(&self) {
+        for lru in &self.shared_vec {
+            lru.lock().clear();
+        }
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         for lru in &self.shared_vec {
@@ -159,10 +174,40 @@ impl<K: Hash + Eq + PartialEq, V, S: BuildHasher> SharedLruCache<K, V, S> {
     where
         F: FnOnce(&K) -> Result<V, DatabaseError>,
     {
-        self.shard(&key)
-            .lock()
-            .get_or_insert_node(key, fn_once)
-            .map(|node| unsafe { &node.as_ref().value })
+        let mut missed = false;
+        let node = self.shard(&key).lock().get_or_insert_node(key, |key| {
+            missed = true;
+            fn_once(key)
+        });
+        self.record(!missed);
+
+        node.map(|node| unsafe { &node.as_ref().value })
+    }
+
+    /// Lookups of [`get`](Self::get) and [`get_or_insert`](Self::get_or_insert) that found their key.
+    #[inline]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(AtomicOrdering::Relaxed)
+    }
+
+    #[inline]
+    pub fn misses(&self) -> u64 {
+        self.misses.load(AtomicOrdering::Relaxed)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shared_vec.iter().map(|lru| lru.lock().len()).sum()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.shared_vec.iter().map(|lru| lru.lock().cap).sum()
+    }
+
+    fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, AtomicOrdering::Relaxed);
     }
 
     fn shared_size(&self) -> usize {
@@ -351,6 +396,18 @@ impl<K: Hash + Eq + PartialEq, V> LruCache<K, V> {
         self.inner.is_empty()
     }
 
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.tail = None;
+        while let Some(node) = self.head.take() {
+            unsafe {
+                self.head = node.as_ref().next;
+                drop(Box::from_raw(node.as_ptr()))
+            }
+        }
+    }
+
     #[allow(dead_code)]
     #[inline]
     pub fn iter(&self) -> LruCacheIter<K, V> {
@@ -416,6 +473,11 @@ mod tests {
         for item in lru.iter() {
             assert!(set.remove(&item))
         }
+
+        lru.clear();
+        assert!(lru.is_empty());
+        assert_eq!(lru.get(&9), None);
+        assert_eq!(lru.put(9, 90), None);
     }
 
     #[test]
@@ -426,5 +488,10 @@ mod tests {
         assert_eq!(lru.get(&1), Some(&10));
         assert!(!lru.is_empty());
         assert_eq!(lru.get_or_insert(9, |_| Ok(9)).unwrap(), &9);
+        assert_eq!(lru.get_or_insert(9, |_| Ok(90)).unwrap(), &9);
+        assert_eq!(lru.get(&2), None);
+
+        assert_eq!((lru.hits(), lru.misses()), (2, 2));
+        assert_eq!((lru.len(), lru.capacity()), (2, 4));
     }
 }
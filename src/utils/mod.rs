@@ -1 +1,8 @@
+pub(crate) mod bloom_filter;
+pub(crate) mod full_text;
 pub(crate) mod lru;
+#[cfg(feature = "parquet")]
+pub(crate) mod parquet_writer;
+pub(crate) mod spatial;
+pub(crate) mod trigram;
+pub(crate) mod vector;
@@ -0,0 +1,245 @@
+use crate::binder::copy::Compression;
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use chrono::{Datelike, NaiveDate};
+use itertools::Itertools;
+use parquet::basic::{
+    Compression as ParquetCompression, GzipLevel, LogicalType as ParquetLogicalType, Repetition,
+    Type as PhysicalType, ZstdLevel,
+};
+use parquet::data_type::{
+    BoolType, ByteArray, ByteArrayType, DoubleType, FloatType, Int32Type, Int64Type,
+};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::types::Type;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Rows buffered before they are written to the file as a row group.
+const ROW_GROUP_ROWS: usize = 8192;
+
+/// How the values of a column are stored, the types without a Parquet counterpart, e.g. the
+/// unsigned 64-bit integers, decimals or timestamps, are stored as their texts.
+#[derive(Debug, Clone, Copy)]
+enum ParquetColumn {
+    Boolean,
+    Int32,
+    Int64,
+    Float,
+    Double,
+    Date,
+    Text,
+}
+
+/// Writes the rows of `COPY ... TO (FORMAT parquet)` to a file, all of its columns are optional.
+pub(crate) struct ParquetWriter {
+    writer: SerializedFileWriter<File>,
+    columns: Vec<ParquetColumn>,
+    rows: Vec<Vec<DataValue>>,
+}
+
+fn parquet_error<E: ToString>(err: E) -> DatabaseError {
+    DatabaseError::Parquet(err.to_string())
+}
+
+impl ParquetColumn {
+    fn new(ty: &LogicalType) -> Self {
+        match ty {
+            LogicalType::Boolean => ParquetColumn::Boolean,
+            LogicalType::Tinyint
+            | LogicalType::UTinyint
+            | LogicalType::Smallint
+            | LogicalType::USmallint
+            | LogicalType::Integer => ParquetColumn::Int32,
+            LogicalType::UInteger | LogicalType::Bigint => ParquetColumn::Int64,
+            LogicalType::Float => ParquetColumn::Float,
+            LogicalType::Double => ParquetColumn::Double,
+            LogicalType::Date => ParquetColumn::Date,
+            _ => ParquetColumn::Text,
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<Type, DatabaseError> {
+        let (physical_type, logical_type) = match self {
+            ParquetColumn::Boolean => (PhysicalType::BOOLEAN, None),
+            ParquetColumn::Int32 => (
+                PhysicalType::INT32,
+                Some(ParquetLogicalType::Integer {
+                    bit_width: 32,
+                    is_signed: true,
+                }),
+            ),
+            ParquetColumn::Int64 => (
+                PhysicalType::INT64,
+                Some(ParquetLogicalType::Integer {
+                    bit_width: 64,
+                    is_signed: true,
+                }),
+            ),
+            ParquetColumn::Float => (PhysicalType::FLOAT, None),
+            ParquetColumn::Double => (PhysicalType::DOUBLE, None),
+            ParquetColumn::Date => (PhysicalType::INT32, Some(ParquetLogicalType::Date)),
+            ParquetColumn::Text => (PhysicalType::BYTE_ARRAY, Some(ParquetLogicalType::String)),
+        };
+        Type::primitive_type_builder(name, physical_type)
+            .with_repetition(Repetition::OPTIONAL)
+            .with_logical_type(logical_type)
+            .build()
+            .map_err(parquet_error)
+    }
+}
+
+impl ParquetWriter {
+    pub(crate) fn try_new(
+        path: &Path,
+        columns: &[&ColumnRef],
+        compression: Compression,
+    ) -> Result<Self, DatabaseError> {
+        let types = columns
+            .iter()
+            .map(|column| ParquetColumn::new(column.datatype()))
+            .collect_vec();
+        let fields = columns
+            .iter()
+            .zip(types.iter())
+            .map(|(column, ty)| Ok(Arc::new(ty.field(column.name())?)))
+            .try_collect::<_, Vec<_>, DatabaseError>()?;
+        let schema = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()
+            .map_err(parquet_error)?;
+        let compression = match compression {
+            Compression::Uncompressed => ParquetCompression::UNCOMPRESSED,
+            Compression::Snappy => ParquetCompression::SNAPPY,
+            Compression::Gzip => ParquetCompression::GZIP(GzipLevel::default()),
+            Compression::Zstd => ParquetCompression::ZSTD(ZstdLevel::default()),
+        };
+        let properties = WriterProperties::builder()
+            .set_compression(compression)
+            .build();
+        let writer =
+            SerializedFileWriter::new(File::create(path)?, Arc::new(schema), Arc::new(properties))
+                .map_err(parquet_error)?;
+
+        Ok(ParquetWriter {
+            writer,
+            columns: types,
+            rows: Vec::with_capacity(ROW_GROUP_ROWS),
+        })
+    }
+
+    pub(crate) fn write(&mut self, values: Vec<DataValue>) -> Result<(), DatabaseError> {
+        self.rows.push(values);
+
+        if self.rows.len() == ROW_GROUP_ROWS {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn close(mut self) -> Result<(), DatabaseError> {
+        if !self.rows.is_empty() {
+            self.write_row_group()?;
+        }
+        self.writer.close().map_err(parquet_error)?;
+
+        Ok(())
+    }
+
+    fn write_row_group(&mut self) -> Result<(), DatabaseError> {
+        let rows = std::mem::take(&mut self.rows);
+        let mut row_group = self.writer.next_row_group().map_err(parquet_error)?;
+
+        for (i, column) in self.columns.iter().enumerate() {
+            let mut writer = row_group
+                .next_column()
+                .map_err(parquet_error)?
+                .ok_or_else(|| parquet_error("the columns of the row group are fewer"))?;
+            let levels = rows
+                .iter()
+                .map(|row| i16::from(!row[i].is_null()))
+                .collect_vec();
+            let values = rows
+                .iter()
+                .map(|row| &row[i])
+                .filter(|value| !value.is_null());
+
+            match column {
+                ParquetColumn::Boolean => {
+                    let values = values
+                        .map(|value| matches!(value, DataValue::Boolean(true)))
+                        .collect_vec();
+                    writer
+                        .typed::<BoolType>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                ParquetColumn::Int32 => {
+                    let values = values
+                        .map(|value| Ok(value.clone().cast(&LogicalType::Integer)?.i32().unwrap()))
+                        .try_collect::<_, Vec<_>, DatabaseError>()?;
+                    writer
+                        .typed::<Int32Type>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                ParquetColumn::Int64 => {
+                    let values = values
+                        .map(|value| Ok(value.clone().cast(&LogicalType::Bigint)?.i64().unwrap()))
+                        .try_collect::<_, Vec<_>, DatabaseError>()?;
+                    writer
+                        .typed::<Int64Type>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                ParquetColumn::Float => {
+                    let values = values
+                        .map(|value| Ok(value.clone().cast(&LogicalType::Float)?.float().unwrap()))
+                        .try_collect::<_, Vec<_>, DatabaseError>()?;
+                    writer
+                        .typed::<FloatType>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                ParquetColumn::Double => {
+                    let values = values
+                        .map(|value| {
+                            Ok(value.clone().cast(&LogicalType::Double)?.double().unwrap())
+                        })
+                        .try_collect::<_, Vec<_>, DatabaseError>()?;
+                    writer
+                        .typed::<DoubleType>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                ParquetColumn::Date => {
+                    // the days since the epoch, where the values count them from the common era
+                    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)
+                        .unwrap()
+                        .num_days_from_ce();
+                    let values = values
+                        .map(|value| match value {
+                            DataValue::Date32(days) => Ok(days - epoch),
+                            value => Err(DatabaseError::InvalidValue(value.to_string())),
+                        })
+                        .try_collect::<_, Vec<_>, DatabaseError>()?;
+                    writer
+                        .typed::<Int32Type>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+                ParquetColumn::Text => {
+                    let values = values
+                        .map(|value| ByteArray::from(value.to_string().into_bytes()))
+                        .collect_vec();
+                    writer
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, Some(&levels), None)
+                }
+            }
+            .map_err(parquet_error)?;
+            writer.close().map_err(parquet_error)?;
+        }
+        row_group.close().map_err(parquet_error)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use crate::errors::DatabaseError;
+use crate::types::value::DataValue;
+use ordered_float::OrderedFloat;
+
+/// The most ranges of Z-order codes a box is covered by when a Z-order index is probed.
+const MAX_RANGES: usize = 256;
+
+/// `(x_min, y_min, x_max, y_max)` of a point or a box.
+pub(crate) type Extent = [f64; 4];
+
+fn parse_pair(text: &str) -> Option<(f64, f64)> {
+    let (x, y) = text
+        .trim()
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split_once(',')?;
+    let coordinate = |text: &str| text.trim().parse::<f64>().ok().filter(|v| v.is_finite());
+
+    Some((coordinate(x)?, coordinate(y)?))
+}
+
+/// Parses a point of the form `(x, y)`.
+pub(crate) fn parse_point(text: &str) -> Result<DataValue, DatabaseError> {
+    let (x, y) =
+        parse_pair(text).ok_or_else(|| DatabaseError::InvalidValue(format!("point: {}", text)))?;
+
+    Ok(DataValue::Point(OrderedFloat(x), OrderedFloat(y)))
+}
+
+/// Parses a box of the form `(x1, y1), (x2, y2)` of any two opposite corners,
+/// optionally enclosed in parentheses.
+pub(crate) fn parse_box(text: &str) -> Result<DataValue, DatabaseError> {
+    let invalid = || DatabaseError::InvalidValue(format!("box: {}", text));
+    let mut corners = text.trim();
+    if corners.starts_with("((") && corners.ends_with("))") {
+        corners = &corners[1..corners.len() - 1];
+    }
+    let (first, second) = corners.split_once(')').ok_or_else(invalid)?;
+    let second = second.trim_start().strip_prefix(',').ok_or_else(invalid)?;
+    let (x1, y1) = parse_pair(&format!("{})", first)).ok_or_else(invalid)?;
+    let (x2, y2) = parse_pair(second).ok_or_else(invalid)?;
+
+    Ok(DataValue::Box(
+        OrderedFloat(x1.min(x2)),
+        OrderedFloat(y1.min(y2)),
+        OrderedFloat(x1.max(x2)),
+        OrderedFloat(y1.max(y2)),
+    ))
+}
+
+/// Parses a point or a box, told apart by the number of their coordinates.
+pub(crate) fn parse(text: &str) -> Result<DataValue, DatabaseError> {
+    if text.matches(',').count() > 1 {
+        parse_box(text)
+    } else {
+        parse_point(text)
+    }
+}
+
+/// The extent of a point or a box, `None` for the other values.
+pub(crate) fn extent(value: &DataValue) -> Option<Extent> {
+    match value {
+        DataValue::Point(x, y) => Some([x.0, y.0, x.0, y.0]),
+        DataValue::Box(x_min, y_min, x_max, y_max) => Some([x_min.0, y_min.0, x_max.0, y_max.0]),
+        _ => None,
+    }
+}
+
+pub(crate) fn contains(a: &Extent, b: &Extent) -> bool {
+    a[0] <= b[0] && a[1] <= b[1] && b[2] <= a[2] && b[3] <= a[3]
+}
+
+pub(crate) fn intersects(a: &Extent, b: &Extent) -> bool {
+    a[0] <= b[2] && b[0] <= a[2] && a[1] <= b[3] && b[1] <= a[3]
+}
+
+/// The euclidean distance between the nearest points of the extents, `0` when they intersect.
+pub(crate) fn distance(a: &Extent, b: &Extent) -> f64 {
+    let dx = (a[0] - b[2]).max(b[0] - a[2]).max(0.0);
+    let dy = (a[1] - b[3]).max(b[1] - a[3]).max(0.0);
+
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// The cell of the grid of a Z-order index a coordinate falls in, in the order of the coordinates.
+fn grid(coordinate: f64) -> u32 {
+    let bits = (coordinate as f32).to_bits();
+
+    // the negative floats sort inverted as unsigned integers
+    if bits >> 31 == 1 {
+        !bits
+    } else {
+        bits | 1 << 31
+    }
+}
+
+/// Spreads the bits of `v` to the even bits of the result.
+fn spread(v: u32) -> u64 {
+    let mut v = v as u64;
+    v = (v | v << 16) & 0x0000_FFFF_0000_FFFF;
+    v = (v | v << 8) & 0x00FF_00FF_00FF_00FF;
+    v = (v | v << 4) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | v << 2) & 0x3333_3333_3333_3333;
+    (v | v << 1) & 0x5555_5555_5555_5555
+}
+
+fn interleave(x: u32, y: u32) -> u64 {
+    spread(x) | spread(y) << 1
+}
+
+/// The Z-order code a Z-order index keeps for a point, nothing for `NULL`.
+pub(crate) fn index_z_order(value: &DataValue) -> Vec<DataValue> {
+    match value {
+        DataValue::Point(x, y) => vec![DataValue::UInt64(interleave(grid(x.0), grid(y.0)))],
+        _ => vec![],
+    }
+}
+
+/// The ranges of the Z-order codes of the points in the extent, found by splitting the grid
+/// into quadrants until they fall in or out of it, or `MAX_RANGES` would be exceeded.
+/// The ranges may cover points near the extent too, so they are to be rechecked.
+pub(crate) fn z_order_ranges(extent: &Extent) -> Vec<(u64, u64)> {
+    let (x_min, y_min, x_max, y_max) = (
+        grid(extent[0]),
+        grid(extent[1]),
+        grid(extent[2]),
+        grid(extent[3]),
+    );
+    let range = |(x, y, level): (u32, u32, u32)| {
+        let start = interleave(x, y);
+        let len = if level == 0 {
+            0
+        } else {
+            u64::MAX >> (64 - 2 * level)
+        };
+        (start, start + len)
+    };
+    // the cells by their lower corner and the log2 of their side
+    let mut cells = vec![(0_u32, 0_u32, 32_u32)];
+    let mut ranges = Vec::new();
+
+    loop {
+        let mut partial = Vec::new();
+
+        for (x, y, level) in cells {
+            let last = ((1_u64 << level) - 1) as u32;
+            let (x_last, y_last) = (x + last, y + last);
+
+            if x_last < x_min || x > x_max || y_last < y_min || y > y_max {
+                continue;
+            }
+            if x_min <= x && x_last <= x_max && y_min <= y && y_last <= y_max {
+                ranges.push(range((x, y, level)));
+            } else {
+                partial.push((x, y, level));
+            }
+        }
+        if partial.is_empty() {
+            break;
+        }
+        if ranges.len() + partial.len() * 4 > MAX_RANGES {
+            ranges.extend(partial.into_iter().map(range));
+            break;
+        }
+        cells = partial
+            .into_iter()
+            .flat_map(|(x, y, level)| {
+                let half = 1 << (level - 1);
+
+                [(x, y), (x + half, y), (x, y + half), (x + half, y + half)]
+                    .map(|(x, y)| (x, y, level - 1))
+            })
+            .collect();
+    }
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last)) if last.saturating_add(1) >= start => *last = (*last).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::DatabaseError;
+    use crate::types::value::DataValue;
+    use crate::utils::spatial::{
+        contains, distance, extent, grid, index_z_order, intersects, parse, z_order_ranges,
+    };
+
+    #[test]
+    fn test_parse() -> Result<(), DatabaseError> {
+        assert_eq!(parse(" (1, -2.5) ")?.to_string(), "(1,-2.5)");
+        assert_eq!(parse("(3, 4), (1, 2)")?.to_string(), "(1,2),(3,4)");
+        assert_eq!(parse("((1, 4), (3, 2))")?.to_string(), "(1,2),(3,4)");
+        assert!(parse("1, 2").is_err());
+        assert!(parse("(1, a)").is_err());
+        assert!(parse("(1, 2), 3").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_predicates() -> Result<(), DatabaseError> {
+        let area = extent(&parse("(0, 0), (2, 2)")?).unwrap();
+        let inside = extent(&parse("(1, 1)")?).unwrap();
+        let outside = extent(&parse("(5, 2)")?).unwrap();
+
+        assert!(contains(&area, &inside));
+        assert!(!contains(&area, &outside));
+        assert!(!contains(&inside, &area));
+        assert!(intersects(
+            &area,
+            &extent(&parse("(1, 1), (3, 3)")?).unwrap()
+        ));
+        assert!(!intersects(&area, &outside));
+        assert_eq!(distance(&area, &inside), 0.0);
+        assert_eq!(distance(&area, &outside), 3.0);
+        assert_eq!(distance(&inside, &outside), 17.0_f64.sqrt());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_z_order_ranges() -> Result<(), DatabaseError> {
+        assert!(grid(-1.0) < grid(-0.5));
+        assert!(grid(-0.5) < grid(0.0));
+        assert!(grid(0.0) < grid(0.5));
+
+        let is_covered = |ranges: &[(u64, u64)], text: &str| -> Result<bool, DatabaseError> {
+            let DataValue::UInt64(z) = index_z_order(&parse(text)?)[0] else {
+                unreachable!()
+            };
+            Ok(ranges
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&z)))
+        };
+        let ranges = z_order_ranges(&extent(&parse("(10, 10), (20, 30)")?).unwrap());
+        assert!(ranges.len() <= 256);
+        assert!(ranges.windows(2).all(|pair| pair[0].1 < pair[1].0));
+
+        for point in ["(10, 10)", "(15, 20)", "(20, 30)", "(19.5, 10.25)"] {
+            assert!(is_covered(&ranges, point)?, "{}", point);
+        }
+        for point in ["(100, 100)", "(-15, 20)", "(15, -20)", "(21, 20)"] {
+            assert!(!is_covered(&ranges, point)?, "{}", point);
+        }
+        // across the axes
+        let ranges = z_order_ranges(&extent(&parse("(-1, -1), (2, 3)")?).unwrap());
+        for point in ["(-1, -1)", "(0, 0)", "(2, 3)", "(1.5, -0.25)", "(-1, 3)"] {
+            assert!(is_covered(&ranges, point)?, "{}", point);
+        }
+        assert!(!is_covered(&ranges, "(-100, 0)")?);
+        assert!(index_z_order(&DataValue::Null).is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use crate::types::value::{DataValue, Utf8Type};
+use itertools::Itertools;
+use sqlparser::ast::CharLengthUnits;
+use std::collections::BTreeSet;
+
+/// The similarity from which two strings are similar for `a % b`.
+pub(crate) const SIMILARITY_THRESHOLD: f64 = 0.3;
+
+fn words(text: &str) -> impl Iterator<Item = Vec<char>> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase().chars().collect_vec())
+}
+
+fn windows(chars: &[char], trigrams: &mut BTreeSet<String>) {
+    for window in chars.windows(3) {
+        trigrams.insert(window.iter().collect());
+    }
+}
+
+/// The distinct trigrams of the lower case words of the text,
+/// each word padded by two spaces in front and one behind as in `pg_trgm`.
+pub(crate) fn trigrams(text: &str) -> BTreeSet<String> {
+    let mut trigrams = BTreeSet::new();
+
+    for word in words(text) {
+        let padded = [vec![' ', ' '], word, vec![' ']].concat();
+        windows(&padded, &mut trigrams);
+    }
+    trigrams
+}
+
+/// The trigrams that any text matched by the `LIKE` pattern contains,
+/// which are the ones inside the words of its literal parts.
+pub(crate) fn like_trigrams(pattern: &str, escape_char: Option<char>) -> BTreeSet<String> {
+    let mut trigrams = BTreeSet::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        if Some(c) == escape_char {
+            literal.extend(chars.next());
+        } else if c == '%' || c == '_' {
+            for word in words(&literal) {
+                windows(&word, &mut trigrams);
+            }
+            literal.clear();
+        } else {
+            literal.push(c);
+        }
+    }
+    for word in words(&literal) {
+        windows(&word, &mut trigrams);
+    }
+    trigrams
+}
+
+/// The trigrams kept by a trigram index for the text, nothing for `NULL`.
+pub(crate) fn index_trigrams(value: &DataValue) -> Vec<DataValue> {
+    let DataValue::Utf8 { value, .. } = value else {
+        return vec![];
+    };
+    trigrams(value).into_iter().map(trigram_value).collect_vec()
+}
+
+pub(crate) fn trigram_value(trigram: String) -> DataValue {
+    DataValue::Utf8 {
+        value: trigram,
+        ty: Utf8Type::Variable(None),
+        unit: CharLengthUnits::Characters,
+    }
+}
+
+/// The number of trigrams shared by the two strings over the number of the trigrams of either.
+pub(crate) fn similarity(a: &str, b: &str) -> f64 {
+    let a = trigrams(a);
+    let b = trigrams(b);
+    let shared = a.intersection(&b).count();
+    let total = a.len() + b.len() - shared;
+
+    if total == 0 {
+        return 0.0;
+    }
+    shared as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::trigram::{like_trigrams, similarity, trigrams};
+    use itertools::Itertools;
+
+    #[test]
+    fn test_trigrams() {
+        assert_eq!(
+            trigrams("Cat").into_iter().collect_vec(),
+            vec!["  c", " ca", "at ", "cat"]
+        );
+        assert_eq!(
+            like_trigrams("%needle_x%", None).into_iter().collect_vec(),
+            vec!["dle", "edl", "eed", "nee"]
+        );
+        assert_eq!(
+            like_trigrams("%ab\\%cd%", Some('\\'))
+                .into_iter()
+                .collect_vec(),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            like_trigrams("%abc\\%d%", Some('\\'))
+                .into_iter()
+                .collect_vec(),
+            vec!["abc"]
+        );
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("word", "word"), 1.0);
+        assert_eq!(similarity("word", "cat"), 0.0);
+        assert_eq!(similarity("", ""), 0.0);
+        assert!(similarity("word", "words") > 0.3);
+        assert!(similarity("word", "wrd") < 0.3);
+        assert!(similarity("word", "two words") < similarity("word", "words"));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::errors::DatabaseError;
+use ordered_float::OrderedFloat;
+
+/// Iterations of k-means when the lists of an IVF index are trained.
+const TRAIN_ITERATIONS: usize = 10;
+/// The most lists an IVF index is trained with.
+const MAX_LISTS: usize = 1024;
+/// Lists of an IVF index a nearest-neighbour search reads, those of the centroids nearest to the query.
+pub(crate) const PROBES: usize = 4;
+
+/// Lists an IVF index over `rows` vectors is trained with, about the square root of the rows.
+pub(crate) fn lists(rows: usize) -> usize {
+    ((rows as f64).sqrt().ceil() as usize).clamp(1, MAX_LISTS)
+}
+
+/// Parses a vector literal of the form `[1, 2.5, -3]` with `dimension` components.
+pub(crate) fn parse(text: &str, dimension: u32) -> Result<Vec<OrderedFloat<f32>>, DatabaseError> {
+    let invalid = || DatabaseError::InvalidValue(format!("vector: {}", text));
+    let inner = text
+        .trim()
+        .strip_prefix('[')
+        .and_then(|text| text.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+    let components = if inner.trim().is_empty() {
+        vec![]
+    } else {
+        inner
+            .split(',')
+            .map(|component| {
+                component
+                    .trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|component| component.is_finite())
+                    .map(OrderedFloat)
+                    .ok_or_else(invalid)
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    if components.len() != dimension as usize {
+        return Err(DatabaseError::InvalidValue(format!(
+            "expected {} dimensions, got {}",
+            dimension,
+            components.len()
+        )));
+    }
+    Ok(components)
+}
+
+pub(crate) fn inner_product(a: &[OrderedFloat<f32>], b: &[OrderedFloat<f32>]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a.0 as f64 * b.0 as f64).sum()
+}
+
+pub(crate) fn l2_distance(a: &[OrderedFloat<f32>], b: &[OrderedFloat<f32>]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a.0 as f64 - b.0 as f64).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// `1 - cos(a, b)`, from `0` for the same direction to `2` for the opposite one,
+/// `NaN` when either of them is the zero vector.
+pub(crate) fn cosine_distance(a: &[OrderedFloat<f32>], b: &[OrderedFloat<f32>]) -> f64 {
+    let norm = inner_product(a, a).sqrt() * inner_product(b, b).sqrt();
+
+    1.0 - inner_product(a, b) / norm
+}
+
+/// Position of the centroid nearest to the vector by L2 distance, `0` without centroids.
+pub(crate) fn nearest(centroids: &[Vec<OrderedFloat<f32>>], vector: &[OrderedFloat<f32>]) -> u32 {
+    nearest_n(centroids, vector, 1)
+        .first()
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Positions of the `n` centroids nearest to the vector, the nearest first.
+pub(crate) fn nearest_n(
+    centroids: &[Vec<OrderedFloat<f32>>],
+    vector: &[OrderedFloat<f32>],
+    n: usize,
+) -> Vec<u32> {
+    let mut distances = centroids
+        .iter()
+        .enumerate()
+        .map(|(i, centroid)| (OrderedFloat(l2_distance(centroid, vector)), i as u32))
+        .collect::<Vec<_>>();
+    distances.sort();
+
+    distances.into_iter().take(n).map(|(_, i)| i).collect()
+}
+
+/// Trains `lists` centroids over the vectors with k-means, seeded by vectors spread evenly over the input.
+pub(crate) fn train(vectors: &[&[OrderedFloat<f32>]], lists: usize) -> Vec<Vec<OrderedFloat<f32>>> {
+    let lists = lists.min(vectors.len());
+    if lists == 0 {
+        return vec![];
+    }
+    let dimension = vectors[0].len();
+    let step = vectors.len() / lists;
+    let mut centroids = (0..lists)
+        .map(|i| vectors[i * step].to_vec())
+        .collect::<Vec<_>>();
+
+    for _ in 0..TRAIN_ITERATIONS {
+        let mut sums = vec![vec![0.0_f64; dimension]; lists];
+        let mut counts = vec![0usize; lists];
+
+        for vector in vectors {
+            let i = nearest(&centroids, vector) as usize;
+
+            counts[i] += 1;
+            for (sum, component) in sums[i].iter_mut().zip(vector.iter()) {
+                *sum += component.0 as f64;
+            }
+        }
+        for ((centroid, sum), count) in centroids.iter_mut().zip(sums).zip(counts) {
+            // an empty list keeps its centroid
+            if count == 0 {
+                continue;
+            }
+            *centroid = sum
+                .into_iter()
+                .map(|sum| OrderedFloat((sum / count as f64) as f32))
+                .collect();
+        }
+    }
+    centroids
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::DatabaseError;
+    use crate::utils::vector::{
+        cosine_distance, inner_product, l2_distance, nearest, parse, train,
+    };
+    use ordered_float::OrderedFloat;
+
+    fn vector(components: &[f32]) -> Vec<OrderedFloat<f32>> {
+        components.iter().copied().map(OrderedFloat).collect()
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), DatabaseError> {
+        assert_eq!(parse(" [1, 2.5,-3] ", 3)?, vector(&[1.0, 2.5, -3.0]));
+        assert!(parse("[1, 2]", 3).is_err());
+        assert!(parse("1, 2, 3", 3).is_err());
+        assert!(parse("[1, a, 3]", 3).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_distance() {
+        let a = vector(&[1.0, 0.0]);
+        let b = vector(&[0.0, 2.0]);
+
+        assert_eq!(l2_distance(&a, &b), 5.0_f64.sqrt());
+        assert_eq!(inner_product(&a, &b), 0.0);
+        assert_eq!(cosine_distance(&a, &b), 1.0);
+        assert_eq!(cosine_distance(&a, &vector(&[3.0, 0.0])), 0.0);
+    }
+
+    #[test]
+    fn test_train() {
+        let vectors = [
+            vector(&[0.0, 0.0]),
+            vector(&[0.0, 1.0]),
+            vector(&[10.0, 10.0]),
+            vector(&[10.0, 11.0]),
+        ];
+        let vectors = vectors.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let centroids = train(&vectors, 2);
+
+        assert_eq!(centroids.len(), 2);
+        assert_eq!(
+            nearest(&centroids, vectors[0]),
+            nearest(&centroids, vectors[1])
+        );
+        assert_eq!(
+            nearest(&centroids, vectors[2]),
+            nearest(&centroids, vectors[3])
+        );
+        assert_ne!(
+            nearest(&centroids, vectors[0]),
+            nearest(&centroids, vectors[2])
+        );
+        assert!(train(&[], 2).is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+fn main() {}
+
+/// Renders the optimized plans of the queries in `tests/plans/*.sql` and compares them with the
+/// snapshots next to them, in `*.plan`, so that a rule changing the index or the join order chosen
+/// for a query fails until its snapshot is accepted with `KITE_SQL_UPDATE_PLANS=1`, as does a
+/// corpus without its snapshot.
+///
+/// The statements of a corpus end with `;`, those not starting with `explain` set the tables up.
+#[cfg(test)]
+mod test {
+    use kite_sql::db::{DataBaseBuilder, ResultIter};
+    use kite_sql::errors::DatabaseError;
+    use std::fmt::Write;
+    use std::path::{Path, PathBuf};
+    use std::{env, fs};
+    use tempfile::TempDir;
+
+    const UPDATE_PLANS: &str = "KITE_SQL_UPDATE_PLANS";
+
+    fn corpora() -> Result<Vec<PathBuf>, DatabaseError> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../plans");
+        let mut corpora = Vec::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "sql") {
+                corpora.push(path);
+            }
+        }
+        corpora.sort();
+
+        Ok(corpora)
+    }
+
+    /// Each query of the corpus followed by its plan, as in a sqllogictest file.
+    fn render(corpus: &Path) -> Result<String, DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let mut plans = String::new();
+
+        for statement in fs::read_to_string(corpus)?.split(';') {
+            let sql = statement
+                .lines()
+                .filter(|line| !line.trim_start().starts_with("--"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let sql = sql.trim();
+            if sql.is_empty() {
+                continue;
+            }
+            let mut iter = kite_sql.run(sql)?;
+
+            if sql.to_lowercase().starts_with("explain") {
+                let plan = iter.next().expect("explain yields its plan")?;
+                writeln!(plans, "{sql}\n----\n{}\n", plan.values[0]).unwrap();
+            }
+            iter.done()?;
+        }
+        Ok(plans)
+    }
+
+    /// The queries whose plans differ, with both plans.
+    fn diff(expected: &str, actual: &str) -> String {
+        let expected_plans = expected.split("\n\n").collect::<Vec<_>>();
+        let actual_plans = actual.split("\n\n").collect::<Vec<_>>();
+        let mut diff = String::new();
+
+        for i in 0..expected_plans.len().max(actual_plans.len()) {
+            let (expected, actual) = (
+                expected_plans.get(i).copied().unwrap_or_default(),
+                actual_plans.get(i).copied().unwrap_or_default(),
+            );
+            if expected != actual {
+                writeln!(diff, "--- expected\n{expected}\n+++ actual\n{actual}\n").unwrap();
+            }
+        }
+        diff
+    }
+
+    #[test]
+    fn test_golden_plans() -> Result<(), DatabaseError> {
+        let update = env::var_os(UPDATE_PLANS).is_some();
+        let mut changed = String::new();
+
+        for corpus in corpora()? {
+            let actual = render(&corpus)?;
+            let snapshot = corpus.with_extension("plan");
+
+            match fs::read_to_string(&snapshot) {
+                Ok(expected) if expected == actual => (),
+                // the plans are recorded, to be reviewed with the change updating them
+                _ if update => fs::write(&snapshot, actual)?,
+                Ok(expected) => {
+                    writeln!(
+                        changed,
+                        "{}:\n{}",
+                        snapshot.display(),
+                        diff(&expected, &actual)
+                    )
+                    .unwrap();
+                }
+                Err(_) => {
+                    writeln!(changed, "{}: missing\n", snapshot.display()).unwrap();
+                }
+            }
+        }
+        assert!(
+            changed.is_empty(),
+            "the plans changed, rerun with `{UPDATE_PLANS}=1` if intended:\n{changed}"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+-- the access path chosen for the predicates on a table with indexes, given its statistics
+select setseed(42);
+create table t1 (id int primary key, c1 int, c2 int, c3 varchar);
+create unique index u_c1_index on t1 (c1);
+create index c2_index on t1 (c2);
+create index p_index on t1 (c2, c3);
+insert into t1 select * from faker(2000, 'id serial, c1 serial, c2 int(0, 20), c3 word');
+analyze table t1;
+
+explain select * from t1 where id = 1;
+explain select * from t1 where id > 10 and id < 20;
+explain select * from t1 where c1 = 7;
+explain select * from t1 where c1 in (1, 2, 3);
+explain select * from t1 where c2 = 3;
+explain select * from t1 where c2 = 3 and c3 = 'a';
+explain select * from t1 where c2 > 15;
+explain select * from t1 where c3 = 'a';
+explain select * from t1 where c1 = 1 or c2 = 2;
+explain select c2 from t1 order by c2 limit 10;
@@ -0,0 +1,19 @@
+-- the join algorithms and the order of the joined tables, given their sizes
+select setseed(42);
+create table small (id int primary key, v int);
+create table large (id int primary key, small_id int, v int);
+create table other (id int primary key, large_id int);
+create index small_id_index on large (small_id);
+insert into small select * from faker(100, 'id serial, v int(0, 10)');
+insert into large select * from faker(2000, 'id serial, small_id int(0, 99), v int(0, 100)');
+insert into other select * from faker(200, 'id serial, large_id int(0, 1999)');
+analyze table small;
+analyze table large;
+analyze table other;
+
+explain select * from small join large on small.id = large.small_id;
+explain select * from large join small on small.id = large.small_id;
+explain select * from small left join large on small.id = large.small_id;
+explain select * from small join large on small.id = large.small_id join other on large.id = other.large_id;
+explain select * from small join large on small.v < large.v;
+explain select * from small join large on small.id = large.small_id where small.v = 1;
@@ -0,0 +1,16 @@
+-- the rewrites of subqueries and aggregations
+select setseed(42);
+create table t1 (id int primary key, c1 int, c2 int);
+create table t2 (id int primary key, c3 int);
+insert into t1 select * from faker(500, 'id serial, c1 int(0, 50), c2 int(0, 50)');
+insert into t2 select * from faker(100, 'id serial, c3 int(0, 50)');
+analyze table t1;
+analyze table t2;
+
+explain select * from t1 where c1 in (select c3 from t2);
+explain select * from t1 where c1 not in (select c3 from t2);
+explain select * from t1 where exists (select c3 from t2 where c3 > 40);
+explain select * from t1 where c1 = (select max(c3) from t2);
+explain select c1, count(*) from t1 group by c1 having count(*) > 1;
+explain select distinct c1 from t1;
+explain select * from (select c1, c2 from t1 where c2 > 10) as t where c1 < 5;